@@ -0,0 +1,165 @@
+//! Blocking wrappers around the async methods of an [ObjectClient], for synchronous programs like
+//! simple command-line tools that don't otherwise need an async runtime.
+//!
+//! Each method of [BlockingClient] runs the corresponding async method to completion on the
+//! calling thread with a minimal executor ([`futures::executor::block_on`]). The client's network
+//! I/O still happens on the CRT's own event loop threads, so no other runtime is needed. Don't
+//! call these methods from within an async context, such as a Tokio task: they block the thread
+//! they're called on, which stalls every other task scheduled on it, and can deadlock if the
+//! request depends on one of those tasks.
+//!
+//! ```no_run
+//! use mountpoint_s3_client::blocking::BlockingClient;
+//! use mountpoint_s3_client::S3CrtClient;
+//!
+//! let client = BlockingClient::new(S3CrtClient::new(Default::default()).expect("client construction failed"));
+//! let result = client.list_objects("my-bucket", None, Some("/"), 1000, "").expect("list_objects failed");
+//! for object in result.objects {
+//!     println!("{}", object.key);
+//! }
+//! ```
+
+use futures::executor::block_on;
+use futures::StreamExt;
+
+use crate::object_client::{
+    DeleteObjectError, DeleteObjectParams, DeleteObjectResult, GetObjectError, GetObjectParams, HeadObjectError,
+    HeadObjectParams, HeadObjectResult, ListObjectsError, ListObjectsResult, ObjectClientResult, PutObjectError,
+    PutObjectParams, PutObjectResult,
+};
+use crate::{ObjectClient, PutObjectRequest};
+
+/// A wrapper around an [ObjectClient] that makes its requests blocking. See the [module
+/// documentation](self) for when it's safe to use.
+#[derive(Debug, Clone)]
+pub struct BlockingClient<Client> {
+    client: Client,
+}
+
+impl<Client: ObjectClient> BlockingClient<Client> {
+    /// Wrap a client to make blocking requests.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// The wrapped client, to make async requests with.
+    pub fn inner(&self) -> &Client {
+        &self.client
+    }
+
+    /// List the objects in a bucket under a given prefix. See [ObjectClient::list_objects].
+    pub fn list_objects(
+        &self,
+        bucket: &str,
+        continuation_token: Option<&str>,
+        delimiter: Option<&str>,
+        max_keys: usize,
+        prefix: &str,
+    ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, Client::ClientError> {
+        block_on(
+            self.client
+                .list_objects(bucket, continuation_token, delimiter, max_keys, prefix),
+        )
+    }
+
+    /// Retrieve the metadata of an object. See [ObjectClient::head_object].
+    pub fn head_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        params: &HeadObjectParams,
+    ) -> ObjectClientResult<HeadObjectResult, HeadObjectError, Client::ClientError> {
+        block_on(self.client.head_object(bucket, key, params))
+    }
+
+    /// Get an object, reading its whole body into memory. See [ObjectClient::get_object].
+    pub fn get_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        params: &GetObjectParams,
+    ) -> ObjectClientResult<Vec<u8>, GetObjectError, Client::ClientError> {
+        block_on(async {
+            let request = self.client.get_object(bucket, key, params).await?;
+            futures::pin_mut!(request);
+            let mut body = Vec::new();
+            while let Some(part) = request.next().await {
+                let (_offset, part) = part?;
+                body.extend_from_slice(&part);
+            }
+            Ok(body)
+        })
+    }
+
+    /// Put an object with the given body. See [ObjectClient::put_object].
+    pub fn put_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        params: &PutObjectParams,
+        body: &[u8],
+    ) -> ObjectClientResult<PutObjectResult, PutObjectError, Client::ClientError> {
+        block_on(async {
+            let mut request = self.client.put_object(bucket, key, params).await?;
+            request.write(body).await?;
+            request.complete().await
+        })
+    }
+
+    /// Delete an object. See [ObjectClient::delete_object].
+    pub fn delete_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        params: &DeleteObjectParams,
+    ) -> ObjectClientResult<DeleteObjectResult, DeleteObjectError, Client::ClientError> {
+        block_on(self.client.delete_object(bucket, key, params))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_client::{MockClient, MockClientConfig};
+    use crate::object_client::ObjectClientError;
+
+    #[test]
+    fn put_get_list_delete() {
+        let client = BlockingClient::new(MockClient::new(MockClientConfig {
+            bucket: "test_bucket".to_string(),
+            part_size: 1024,
+            ..Default::default()
+        }));
+
+        let body: Vec<u8> = (0..5000u32).map(|i| i as u8).collect();
+        client
+            .put_object("test_bucket", "dir/key", &PutObjectParams::new(), &body)
+            .expect("put_object should succeed");
+
+        let result = client
+            .list_objects("test_bucket", None, Some("/"), 1000, "")
+            .expect("list_objects should succeed");
+        assert_eq!(result.common_prefixes, vec!["dir/"]);
+
+        let head = client
+            .head_object("test_bucket", "dir/key", &HeadObjectParams::new())
+            .expect("head_object should succeed");
+        assert_eq!(head.object.size, body.len() as u64);
+
+        let read = client
+            .get_object("test_bucket", "dir/key", &GetObjectParams::new())
+            .expect("get_object should succeed");
+        assert_eq!(read, body);
+
+        client
+            .delete_object("test_bucket", "dir/key", &DeleteObjectParams::new())
+            .expect("delete_object should succeed");
+        let err = client
+            .get_object("test_bucket", "dir/key", &GetObjectParams::new())
+            .expect_err("deleted object shouldn't be found");
+        assert!(matches!(
+            err,
+            ObjectClientError::ServiceError(GetObjectError::NoSuchKey)
+        ));
+    }
+}
@@ -0,0 +1,100 @@
+use std::net::Ipv4Addr;
+
+use crate::S3RequestError;
+
+/// Check a bucket name against the [S3 bucket naming
+/// rules](https://docs.aws.amazon.com/AmazonS3/latest/userguide/bucketnamingrules.html), so that
+/// requests to a bucket that can't exist fail with [S3RequestError::InvalidBucketName] without
+/// being sent. Names must:
+///
+/// * be between 3 and 63 characters long
+/// * contain only lowercase letters, numbers, dots (`.`), and hyphens (`-`)
+/// * begin and end with a letter or number
+/// * not contain two adjacent dots
+/// * not be formatted as an IP address, like `192.168.5.4`
+///
+/// Access point, Outposts, and Object Lambda ARNs, which begin with `arn:`, are accepted without
+/// checking here; the endpoint resolver parses them and rejects malformed ones.
+/// Access point aliases and S3 Express One Zone directory bucket names follow the same rules.
+pub fn validate_bucket_name(bucket: &str) -> Result<(), S3RequestError> {
+    if bucket.starts_with("arn:") {
+        return Ok(());
+    }
+
+    let invalid = |reason: &'static str| {
+        Err(S3RequestError::InvalidBucketName {
+            bucket: bucket.to_owned(),
+            reason,
+        })
+    };
+
+    if !(3..=63).contains(&bucket.len()) {
+        return invalid("bucket names must be between 3 and 63 characters long");
+    }
+    if !bucket
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'.' || b == b'-')
+    {
+        return invalid("bucket names can only contain lowercase letters, numbers, dots, and hyphens");
+    }
+    let is_alphanumeric = |b: Option<u8>| b.is_some_and(|b| b.is_ascii_alphanumeric());
+    if !is_alphanumeric(bucket.bytes().next()) || !is_alphanumeric(bucket.bytes().last()) {
+        return invalid("bucket names must begin and end with a letter or number");
+    }
+    if bucket.contains("..") {
+        return invalid("bucket names can't contain two adjacent dots");
+    }
+    if bucket.parse::<Ipv4Addr>().is_ok() {
+        return invalid("bucket names can't be formatted as an IP address");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("abc"; "minimum length")]
+    #[test_case("docexamplebucket1"; "letters and numbers")]
+    #[test_case("doc-example-bucket"; "hyphens")]
+    #[test_case("doc.example.bucket"; "dots")]
+    #[test_case("123"; "only numbers")]
+    #[test_case("1.2.3"; "fewer than four numbers")]
+    #[test_case("192.168.5.256"; "out of range address")]
+    #[test_case("a-very-long-bucket-name-that-is-exactly-sixty-three-characters1"; "maximum length")]
+    #[test_case("my-access-point-hrzrlukc5m36ft7okagglf3gmwluquse1b-s3alias"; "access point alias")]
+    #[test_case("bucket-base-name--usw2-az1--x-s3"; "directory bucket")]
+    #[test_case("arn:aws:s3:us-west-2:123456789012:accesspoint/My_Access_Point"; "access point arn")]
+    #[test_case(
+        "arn:aws:s3-outposts:us-west-2:123456789012:outpost/op-01234567890123456/accesspoint/reports";
+        "outposts access point arn"
+    )]
+    fn valid_bucket_name(bucket: &str) {
+        validate_bucket_name(bucket).expect("bucket name should be valid");
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("ab"; "too short")]
+    #[test_case("a-very-long-bucket-name-that-is-exactly-sixty-four-characters-12"; "too long")]
+    #[test_case("DocExampleBucket"; "uppercase")]
+    #[test_case("doc_example_bucket"; "underscore")]
+    #[test_case("doc example bucket"; "space")]
+    #[test_case("doc/example"; "slash")]
+    #[test_case("bücket"; "non-ascii")]
+    #[test_case("-docexamplebucket"; "leading hyphen")]
+    #[test_case("docexamplebucket-"; "trailing hyphen")]
+    #[test_case(".docexamplebucket"; "leading dot")]
+    #[test_case("docexamplebucket."; "trailing dot")]
+    #[test_case("doc..example"; "adjacent dots")]
+    #[test_case("192.168.5.4"; "ip address")]
+    #[test_case("s3://docexamplebucket"; "uri")]
+    fn invalid_bucket_name(bucket: &str) {
+        let err = validate_bucket_name(bucket).expect_err("bucket name should be invalid");
+        assert!(
+            matches!(&err, S3RequestError::InvalidBucketName { bucket: name, .. } if name == bucket),
+            "got {err:?}"
+        );
+    }
+}
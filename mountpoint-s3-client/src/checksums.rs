@@ -1,15 +1,26 @@
 //! Provides base64 encoding/decoding for CRC32C checksums.
-use mountpoint_s3_crt::checksums::crc32c::Crc32c;
+use async_trait::async_trait;
+use mountpoint_s3_crt::checksums::crc32::{self, Crc32};
+use mountpoint_s3_crt::checksums::crc32c::{self, Crc32c};
 
 use base64ct::Base64;
 use base64ct::Encoding;
 use thiserror::Error;
 
+use crate::object_client::{
+    ChecksumAlgorithm, ObjectClientResult, PutObjectError, PutObjectRequest, PutObjectResult, UploadReview,
+};
+
 /// The base64 encoding for this CRC32C checksum value.
 pub fn crc32c_to_base64(checksum: &Crc32c) -> String {
     Base64::encode_string(&checksum.value().to_be_bytes())
 }
 
+/// The base64 encoding for this CRC32 checksum value.
+pub fn crc32_to_base64(checksum: &Crc32) -> String {
+    Base64::encode_string(&checksum.value().to_be_bytes())
+}
+
 /// Create a CRC32C checksum from a base64 encoding.
 pub fn crc32c_from_base64(base64_str: &str) -> Result<Crc32c, ParseError> {
     let mut dec_buf = [0u8; std::mem::size_of::<u32>()];
@@ -17,6 +28,101 @@ pub fn crc32c_from_base64(base64_str: &str) -> Result<Crc32c, ParseError> {
     Ok(Crc32c::new(u32::from_be_bytes(dec_buf)))
 }
 
+/// Incrementally computes a checksum over the body of an upload.
+#[derive(Debug, Clone)]
+pub enum UploadChecksumHasher {
+    /// CRC32C checksum
+    Crc32c(crc32c::Hasher),
+    /// CRC32 checksum
+    Crc32(crc32::Hasher),
+}
+
+impl UploadChecksumHasher {
+    /// Create a new hasher for the given algorithm, or `None` if the client can't compute that
+    /// algorithm incrementally.
+    pub fn new(algorithm: ChecksumAlgorithm) -> Option<Self> {
+        match algorithm {
+            ChecksumAlgorithm::Crc32c => Some(Self::Crc32c(crc32c::Hasher::new())),
+            ChecksumAlgorithm::Crc32 => Some(Self::Crc32(crc32::Hasher::new())),
+            _ => None,
+        }
+    }
+
+    /// The algorithm this hasher computes.
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        match self {
+            Self::Crc32c(_) => ChecksumAlgorithm::Crc32c,
+            Self::Crc32(_) => ChecksumAlgorithm::Crc32,
+        }
+    }
+
+    /// Update the checksum with the given bytes.
+    pub fn update(&mut self, buf: &[u8]) {
+        match self {
+            Self::Crc32c(hasher) => hasher.update(buf),
+            Self::Crc32(hasher) => hasher.update(buf),
+        }
+    }
+
+    /// Finalize the checksum and return its base64 encoding, in the format S3 uses for checksum
+    /// headers.
+    pub fn finalize_base64(self) -> String {
+        match self {
+            Self::Crc32c(hasher) => crc32c_to_base64(&hasher.finalize()),
+            Self::Crc32(hasher) => crc32_to_base64(&hasher.finalize()),
+        }
+    }
+}
+
+/// A [PutObjectRequest] that computes a checksum of the body as it is written to the inner request.
+///
+/// Each slice is hashed on its way through [PutObjectRequest::write], so callers streaming from a
+/// file or other source get the checksum without a second read pass over it.
+#[derive(Debug)]
+pub struct ChecksummedPutObjectRequest<R> {
+    request: R,
+    hasher: UploadChecksumHasher,
+}
+
+impl<R: PutObjectRequest> ChecksummedPutObjectRequest<R> {
+    /// Wrap the given request, computing a checksum with the given hasher.
+    pub fn new(request: R, hasher: UploadChecksumHasher) -> Self {
+        Self { request, hasher }
+    }
+
+    /// The algorithm of the checksum being computed.
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        self.hasher.algorithm()
+    }
+
+    /// The base64-encoded checksum of the bytes written so far.
+    pub fn checksum(&self) -> String {
+        self.hasher.clone().finalize_base64()
+    }
+}
+
+#[cfg_attr(not(docs_rs), async_trait)]
+impl<R: PutObjectRequest> PutObjectRequest for ChecksummedPutObjectRequest<R> {
+    type ClientError = R::ClientError;
+
+    async fn write(&mut self, slice: &[u8]) -> ObjectClientResult<(), PutObjectError, Self::ClientError> {
+        self.request.write(slice).await?;
+        self.hasher.update(slice);
+        Ok(())
+    }
+
+    async fn complete(self) -> ObjectClientResult<PutObjectResult, PutObjectError, Self::ClientError> {
+        self.request.complete().await
+    }
+
+    async fn review_and_complete(
+        self,
+        review_callback: impl FnOnce(UploadReview) -> bool + Send + 'static,
+    ) -> ObjectClientResult<PutObjectResult, PutObjectError, Self::ClientError> {
+        self.request.review_and_complete(review_callback).await
+    }
+}
+
 /// Error parsing CRC32C checksums.
 #[derive(Error, Debug)]
 pub enum ParseError {
@@ -28,6 +134,10 @@ pub enum ParseError {
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    use crate::mock_client::{MockClient, MockClientConfig};
+    use crate::object_client::PutObjectParams;
+    use crate::ObjectClient;
     use test_case::test_case;
 
     #[test]
@@ -44,6 +154,49 @@ mod tests {
         assert_eq!(crc.value(), 1234);
     }
 
+    #[test_case(ChecksumAlgorithm::Crc32c, "4waSgw=="; "crc32c")]
+    #[test_case(ChecksumAlgorithm::Crc32, "y/Q5Jg=="; "crc32")]
+    fn test_upload_checksum_hasher(algorithm: ChecksumAlgorithm, expected: &str) {
+        let mut hasher = UploadChecksumHasher::new(algorithm).expect("algorithm should be supported");
+        hasher.update(b"1234");
+        hasher.update(b"56789");
+        assert_eq!(hasher.algorithm(), algorithm);
+        assert_eq!(hasher.finalize_base64(), expected);
+    }
+
+    #[test_case(ChecksumAlgorithm::Sha1)]
+    #[test_case(ChecksumAlgorithm::Sha256)]
+    fn test_upload_checksum_hasher_unsupported(algorithm: ChecksumAlgorithm) {
+        assert!(UploadChecksumHasher::new(algorithm).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_checksummed_put_object_request() {
+        let bucket = "test_bucket";
+        let key = "key";
+        let client = MockClient::new(MockClientConfig {
+            bucket: bucket.to_string(),
+            part_size: 4,
+            ..Default::default()
+        });
+
+        let request = client
+            .put_object(bucket, key, &PutObjectParams::new())
+            .await
+            .expect("put_object should succeed");
+        let hasher = UploadChecksumHasher::new(ChecksumAlgorithm::Crc32c).unwrap();
+        let mut request = ChecksummedPutObjectRequest::new(request, hasher);
+
+        for chunk in b"123456789".chunks(2) {
+            request.write(chunk).await.expect("write should succeed");
+        }
+
+        assert_eq!(request.algorithm(), ChecksumAlgorithm::Crc32c);
+        assert_eq!(request.checksum(), "4waSgw==");
+        request.complete().await.expect("complete should succeed");
+        assert!(client.contains_key(key));
+    }
+
     #[test_case("AAA")]
     #[test_case("AAAE0g")]
     #[test_case("AAAE0gAA==")]
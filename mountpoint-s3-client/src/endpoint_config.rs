@@ -90,14 +90,17 @@ impl EndpointConfig {
         self
     }
 
-    /// use Transfer Acceleration config for S3
+    /// use Transfer Acceleration config for S3, so that requests go to the bucket's
+    /// `s3-accelerate` endpoint. Transfer Acceleration must be enabled on the bucket, and can't be
+    /// combined with path addressing or a custom endpoint set with [EndpointConfig::endpoint].
     #[must_use = "EndpointConfig follows a builder pattern"]
     pub fn use_accelerate(mut self, accelerate: bool) -> Self {
         self.use_accelerate = accelerate;
         self
     }
 
-    /// use dual stack config for S3
+    /// use dual stack config for S3, so that endpoints resolve to both IPv4 and IPv6 addresses. Can't be
+    /// combined with a custom endpoint set with [EndpointConfig::endpoint].
     #[must_use = "EndpointConfig follows a builder pattern"]
     pub fn use_dual_stack(mut self, dual_stack: bool) -> Self {
         self.use_dual_stack = dual_stack;
@@ -150,6 +153,16 @@ impl EndpointConfig {
 
     /// resolve the endpoint from the [EndpointConfig] and the bucket name
     pub fn resolve_for_bucket(&self, bucket: &str) -> Result<ResolvedEndpointInfo, EndpointError> {
+        if self.use_dual_stack && self.endpoint.is_some() {
+            return Err(EndpointError::DualStackWithCustomEndpoint);
+        }
+        if self.use_accelerate && self.endpoint.is_some() {
+            return Err(EndpointError::AccelerateWithCustomEndpoint);
+        }
+        if self.use_accelerate && self.addressing_style == AddressingStyle::Path {
+            return Err(EndpointError::AccelerateWithPathAddressing);
+        }
+
         let allocator = Allocator::default();
         let mut endpoint_request_context: RequestContext = RequestContext::new(&allocator).unwrap();
 
@@ -248,6 +261,7 @@ impl ResolvedEndpointInfo {
 }
 
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum EndpointError {
     #[error("invalid URI")]
     InvalidUri(#[from] InvalidUriError),
@@ -259,6 +273,12 @@ pub enum EndpointError {
     MissingAuthSchemeField(&'static str),
     #[error("invalid value {1} for AuthScheme field {0}")]
     InvalidAuthSchemeField(&'static str, String),
+    #[error("dual-stack endpoints can't be used with a custom endpoint")]
+    DualStackWithCustomEndpoint,
+    #[error("Transfer Acceleration can't be used with a custom endpoint")]
+    AccelerateWithCustomEndpoint,
+    #[error("Transfer Acceleration can't be used with path addressing")]
+    AccelerateWithPathAddressing,
 }
 
 #[derive(Debug, Error)]
@@ -345,6 +365,42 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_accelerate() {
+        let endpoint_config = EndpointConfig::new("eu-west-1").use_accelerate(true);
+        let endpoint_uri = endpoint_config
+            .resolve_for_bucket("doc-example-bucket")
+            .unwrap()
+            .uri()
+            .unwrap();
+        assert_eq!(
+            "https://doc-example-bucket.s3-accelerate.amazonaws.com",
+            endpoint_uri.as_os_str()
+        );
+    }
+
+    #[test]
+    fn test_accelerate_path_addr() {
+        let endpoint_config = EndpointConfig::new("eu-west-1")
+            .use_accelerate(true)
+            .addressing_style(AddressingStyle::Path);
+        let err = endpoint_config
+            .resolve_for_bucket("doc-example-bucket")
+            .expect_err("acceleration with path addressing should fail");
+        assert!(matches!(err, EndpointError::AccelerateWithPathAddressing));
+    }
+
+    #[test]
+    fn test_accelerate_custom_endpoint() {
+        let endpoint_config = EndpointConfig::new("eu-west-1")
+            .use_accelerate(true)
+            .endpoint(Uri::new_from_str(&Allocator::default(), "https://example.com").unwrap());
+        let err = endpoint_config
+            .resolve_for_bucket("doc-example-bucket")
+            .expect_err("acceleration with a custom endpoint should fail");
+        assert!(matches!(err, EndpointError::AccelerateWithCustomEndpoint));
+    }
+
     #[test]
     fn test_dual_stack_path_addr() {
         let endpoint_config = EndpointConfig::new("eu-west-1")
@@ -361,6 +417,17 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_dual_stack_custom_endpoint() {
+        let endpoint_config = EndpointConfig::new("eu-west-1")
+            .use_dual_stack(true)
+            .endpoint(Uri::new_from_str(&Allocator::default(), "https://example.com").unwrap());
+        let err = endpoint_config
+            .resolve_for_bucket("doc-example-bucket")
+            .expect_err("dual stack with a custom endpoint should fail");
+        assert!(matches!(err, EndpointError::DualStackWithCustomEndpoint));
+    }
+
     #[test]
     fn test_arn_as_bucket() {
         let endpoint_config = EndpointConfig::new("eu-west-1");
@@ -390,6 +457,49 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_regional_access_point_arn() {
+        // The ARN's region wins over the client's, both for the host and for signing
+        let endpoint_config = EndpointConfig::new("eu-west-1");
+        let resolved = endpoint_config
+            .resolve_for_bucket("arn:aws:s3:us-west-2:123456789012:accesspoint/reports")
+            .unwrap();
+        assert_eq!(
+            "https://reports-123456789012.s3-accesspoint.us-west-2.amazonaws.com",
+            resolved.uri().unwrap().as_os_str()
+        );
+        let auth_scheme = resolved.auth_scheme().unwrap();
+        assert_eq!(auth_scheme.signing_region(), "us-west-2");
+        assert_eq!(auth_scheme.signing_name(), "s3");
+    }
+
+    #[test]
+    fn test_outposts_access_point_arn() {
+        let endpoint_config = EndpointConfig::new("us-west-2");
+        let resolved = endpoint_config
+            .resolve_for_bucket(
+                "arn:aws:s3-outposts:us-west-2:123456789012:outpost/op-01234567890123456/accesspoint/reports",
+            )
+            .unwrap();
+        assert_eq!(
+            "https://reports-123456789012.op-01234567890123456.s3-outposts.us-west-2.amazonaws.com",
+            resolved.uri().unwrap().as_os_str()
+        );
+        assert_eq!(resolved.auth_scheme().unwrap().signing_name(), "s3-outposts");
+    }
+
+    #[test]
+    fn test_malformed_access_point_arn() {
+        let endpoint_config = EndpointConfig::new("us-west-2");
+        let err = endpoint_config
+            .resolve_for_bucket("arn:aws:s3:us-west-2:123456789012:accesspoint")
+            .expect_err("an ARN without an access point name should fail");
+        let EndpointError::UnresolvedEndpoint(ResolverError::EndpointNotResolved(message)) = err else {
+            panic!("unexpected error {err:?}");
+        };
+        assert!(message.contains("ARN"), "unclear error: {message}");
+    }
+
     #[test]
     fn test_outpost() {
         let endpoint_config = EndpointConfig::new("us-gov-west-1");
@@ -1,5 +1,5 @@
 /// Additional data fetched from S3 response, which caused an error
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct ClientErrorMetadata {
     /// http code of the response, e.g. 403
     pub http_code: Option<i32>,
@@ -7,6 +7,10 @@ pub struct ClientErrorMetadata {
     pub error_code: Option<String>,
     /// error message from the response xml body, e.g. "Access Denied"
     pub error_message: Option<String>,
+    /// request id from the `x-amz-request-id` response header, if the endpoint returned one
+    pub request_id: Option<String>,
+    /// extended request id from the `x-amz-id-2` response header, if the endpoint returned one
+    pub extended_request_id: Option<String>,
 }
 
 /// Allows using metadata of errors in generic implementations without knowing the exact type of an error,
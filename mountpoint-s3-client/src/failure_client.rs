@@ -14,10 +14,12 @@ use futures::Stream;
 use pin_project::pin_project;
 
 use crate::object_client::{
-    DeleteObjectError, DeleteObjectResult, ETag, GetBodyPart, GetObjectAttributesError, GetObjectAttributesResult,
-    GetObjectError, GetObjectRequest, HeadObjectError, HeadObjectResult, ListObjectsError, ListObjectsResult,
-    ObjectAttribute, ObjectClientError, ObjectClientResult, PutObjectError, PutObjectParams, PutObjectRequest,
-    PutObjectResult, UploadReview,
+    CopyObjectError, CopyObjectParams, CopyObjectResult, DeleteObjectError, DeleteObjectParams, DeleteObjectResult,
+    DeleteObjectsResult, ETag, GetBodyPart, GetObjectAttributesError, GetObjectAttributesResult, GetObjectError,
+    GetObjectParams, GetObjectRequest, GetObjectTaggingError, HeadObjectError, HeadObjectParams, HeadObjectResult,
+    ListObjectsError, ListObjectsResult, ObjectAttribute, ObjectClientError, ObjectClientResult, ObjectExistence,
+    PutObjectError, PutObjectParams, PutObjectRequest, PutObjectResult, PutObjectTaggingError, ResponseMetadata,
+    RestoreObjectError, RestoreObjectResult, RestoreTier, UploadReview,
 };
 use crate::ObjectClient;
 
@@ -47,7 +49,7 @@ pub struct FailureClient<Client: ObjectClient, State, RequestWrapperState> {
         &mut State,
         &str,
         Option<&str>,
-        &str,
+        Option<&str>,
         usize,
         &str,
     ) -> Result<(), ObjectClientError<ListObjectsError, Client::ClientError>>,
@@ -77,30 +79,53 @@ where
         self.client.part_size()
     }
 
+    async fn copy_object(
+        &self,
+        source_bucket: &str,
+        source_key: &str,
+        destination_bucket: &str,
+        destination_key: &str,
+        params: &CopyObjectParams,
+    ) -> ObjectClientResult<CopyObjectResult, CopyObjectError, Self::ClientError> {
+        // TODO failure hook for copy_object
+        self.client
+            .copy_object(source_bucket, source_key, destination_bucket, destination_key, params)
+            .await
+    }
+
     async fn delete_object(
         &self,
         bucket: &str,
         key: &str,
+        params: &DeleteObjectParams,
     ) -> ObjectClientResult<DeleteObjectResult, DeleteObjectError, Self::ClientError> {
         // TODO failure hook for delete_object
-        self.client.delete_object(bucket, key).await
+        self.client.delete_object(bucket, key, params).await
+    }
+
+    async fn delete_objects(
+        &self,
+        bucket: &str,
+        keys: &[String],
+    ) -> ObjectClientResult<DeleteObjectsResult, DeleteObjectError, Self::ClientError> {
+        // TODO failure hook for delete_objects
+        self.client.delete_objects(bucket, keys).await
     }
 
     async fn get_object(
         &self,
         bucket: &str,
         key: &str,
-        range: Option<Range<u64>>,
-        if_match: Option<ETag>,
+        params: &GetObjectParams,
     ) -> ObjectClientResult<Self::GetObjectRequest, GetObjectError, Self::ClientError> {
         let wrapper = (self.get_object_cb)(
             &mut *self.state.lock().unwrap(),
             bucket,
             key,
-            range.clone(),
-            if_match.clone(),
+            params.range.clone(),
+            params.if_match.clone(),
         )?;
-        let request = self.client.get_object(bucket, key, range, if_match).await?;
+        let request = self.client.get_object(bucket, key, params).await?;
         Ok(FailureGetRequest {
             state: wrapper.state,
             result_fn: wrapper.result_fn,
@@ -112,7 +137,7 @@ where
         &self,
         bucket: &str,
         continuation_token: Option<&str>,
-        delimiter: &str,
+        delimiter: Option<&str>,
         max_keys: usize,
         prefix: &str,
     ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, Self::ClientError> {
@@ -134,9 +159,19 @@ where
         &self,
         bucket: &str,
         key: &str,
+        params: &HeadObjectParams,
     ) -> ObjectClientResult<HeadObjectResult, HeadObjectError, Self::ClientError> {
         (self.head_object_cb)(&mut *self.state.lock().unwrap(), bucket, key)?;
-        self.client.head_object(bucket, key).await
+        self.client.head_object(bucket, key, params).await
+    }
+
+    async fn object_exists(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<ObjectExistence, HeadObjectError, Self::ClientError> {
+        // TODO failure hook for object_exists
+        self.client.object_exists(bucket, key).await
     }
 
     async fn put_object(
@@ -167,6 +202,36 @@ where
             .get_object_attributes(bucket, key, max_parts, part_number_marker, object_attributes)
             .await
     }
+
+    async fn get_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<Vec<(String, String)>, GetObjectTaggingError, Self::ClientError> {
+        // TODO failure hook for get_object_tagging
+        self.client.get_object_tagging(bucket, key).await
+    }
+
+    async fn put_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+        tags: &[(String, String)],
+    ) -> ObjectClientResult<(), PutObjectTaggingError, Self::ClientError> {
+        // TODO failure hook for put_object_tagging
+        self.client.put_object_tagging(bucket, key, tags).await
+    }
+
+    async fn restore_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        days: u32,
+        tier: RestoreTier,
+    ) -> ObjectClientResult<RestoreObjectResult, RestoreObjectError, Self::ClientError> {
+        // TODO failure hook for restore_object
+        self.client.restore_object(bucket, key, days, tier).await
+    }
 }
 
 #[pin_project]
@@ -184,6 +249,14 @@ impl<Client: ObjectClient, FailState: Send> GetObjectRequest for FailureGetReque
         let this = self.project();
         this.request.increment_read_window(len);
     }
+
+    fn response_metadata(&self) -> Option<ResponseMetadata> {
+        self.request.response_metadata()
+    }
+
+    fn request_charged(&self) -> bool {
+        self.request.request_charged()
+    }
 }
 
 impl<Client: ObjectClient, FailState> Stream for FailureGetRequest<Client, FailState> {
@@ -403,7 +476,7 @@ mod tests {
 
         let fail_set = HashSet::from([2, 4, 5]);
         for i in 1..=6 {
-            let r = fail_client.get_object(bucket, key, None, None).await;
+            let r = fail_client.get_object(bucket, key, &Default::default()).await;
             if fail_set.contains(&i) {
                 assert!(r.is_err());
             } else {
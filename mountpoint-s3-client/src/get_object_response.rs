@@ -0,0 +1,104 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use pin_project::pin_project;
+
+use crate::object_client::{GetBodyPart, GetObjectError, GetObjectRequest, ObjectClientResult, ResponseMetadata};
+
+/// A streaming GetObject response that applies backpressure automatically, for consumers that
+/// read an object more slowly than the network delivers it.
+///
+/// This wraps a [GetObjectRequest] and reopens its read window by the size of each body part as
+/// the consumer takes it from the stream. With read backpressure enabled on the client (see
+/// [`S3ClientConfig::read_backpressure`](crate::config::S3ClientConfig::read_backpressure)), at
+/// most the client's initial read window (see
+/// [`S3ClientConfig::initial_read_window`](crate::config::S3ClientConfig::initial_read_window))
+/// is buffered ahead of the consumer, and the transfer pauses until the consumer catches up. This
+/// bounds memory use for objects of any size, unlike reading the whole body into memory.
+///
+/// Each item of the stream is a part of the object body together with the part's offset within the
+/// object. Without read backpressure enabled on the client, the response behaves like the
+/// request it wraps and downloads the object as fast as possible.
+#[derive(Debug)]
+#[pin_project]
+pub struct GetObjectResponse<R> {
+    #[pin]
+    request: R,
+}
+
+impl<R: GetObjectRequest> GetObjectResponse<R> {
+    /// Wrap a GetObject request to reopen its read window as its body is consumed.
+    ///
+    /// The caller should not also call [GetObjectRequest::increment_read_window] on the request,
+    /// since that would let more than the initial read window be buffered.
+    pub fn new(request: R) -> Self {
+        Self { request }
+    }
+
+    /// The metadata of the response, once it's available. See
+    /// [GetObjectRequest::response_metadata].
+    pub fn response_metadata(&self) -> Option<ResponseMetadata> {
+        self.request.response_metadata()
+    }
+
+    /// Get back the request this response wraps.
+    pub fn into_inner(self) -> R {
+        self.request
+    }
+}
+
+impl<R: GetObjectRequest> Stream for GetObjectResponse<R> {
+    type Item = ObjectClientResult<GetBodyPart, GetObjectError, R::ClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut request = self.project().request;
+        let poll = request.as_mut().poll_next(cx);
+        if let Poll::Ready(Some(Ok((_, body)))) = &poll {
+            // The consumer has taken this part, so the CRT can fetch as many bytes to replace it
+            request.increment_read_window(body.len());
+        }
+        poll
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::mock_client::{MockClient, MockClientConfig, MockObject};
+    use crate::object_client::{ETag, GetObjectParams};
+    use crate::ObjectClient;
+
+    #[tokio::test]
+    async fn reads_past_initial_window() {
+        const WINDOW_SIZE: usize = 256;
+        let client = MockClient::new(MockClientConfig {
+            bucket: "test_bucket".to_string(),
+            part_size: 1024,
+            unordered_list_seed: None,
+            enable_back_pressure: true,
+            initial_read_window_size: WINDOW_SIZE,
+        });
+
+        let body: Vec<u8> = (0..5000u32).map(|i| i as u8).collect();
+        client.add_object("key", MockObject::from_bytes(&body, ETag::for_tests()));
+
+        let request = client
+            .get_object("test_bucket", "key", &GetObjectParams::new())
+            .await
+            .expect("get_object should succeed");
+        let mut response = Box::pin(GetObjectResponse::new(request));
+
+        let mut accum = vec![];
+        while let Some(part) = response.next().await {
+            let (offset, part) = part.expect("body part should succeed");
+            assert_eq!(offset, accum.len() as u64, "wrong body part offset");
+            // The window never lets more than the initial window through at once
+            assert!(part.len() <= WINDOW_SIZE);
+            accum.extend_from_slice(&part);
+        }
+        assert_eq!(accum, body);
+    }
+}
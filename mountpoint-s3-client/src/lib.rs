@@ -21,7 +21,7 @@
 //!
 //! let client = S3CrtClient::new(Default::default()).expect("client construction failed");
 //!
-//! let response = client.get_object("my-bucket", "my-key", None, None).await.expect("get_object failed");
+//! let response = client.get_object("my-bucket", "my-key", &Default::default()).await.expect("get_object failed");
 //! let body = response.map_ok(|(offset, body)| body.to_vec()).try_concat().await.expect("body streaming failed");
 //! # }
 //! ```
@@ -43,40 +43,80 @@
 // Make async trait docs not-ugly on docs.rs (https://github.com/dtolnay/async-trait/issues/213)
 #![cfg_attr(docs_rs, feature(async_fn_in_trait))]
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod bucket_name;
 mod build_info;
 pub mod checksums;
 mod endpoint_config;
 #[doc(hidden)]
 pub mod failure_client;
+mod get_object_response;
 pub mod imds_crt_client;
 pub mod instance_info;
+pub mod list_stream;
+#[cfg(feature = "mock")]
+mod meta_request_result;
 #[doc(hidden)]
 pub mod mock_client;
 mod object_client;
+mod proxy_config;
 mod s3_crt_client;
+mod tls_config;
 #[doc(hidden)]
 pub mod user_agent;
 
 pub mod error_metadata;
 
+pub use bucket_name::validate_bucket_name;
 pub use object_client::{ObjectClient, PutObjectRequest};
 
-pub use s3_crt_client::{get_object::S3GetObjectRequest, put_object::S3PutObjectRequest, S3CrtClient, S3RequestError};
+pub use mountpoint_s3_crt::s3::client::ConnectionStats;
+pub use s3_crt_client::{
+    get_object::S3GetObjectRequest, put_object::S3PutObjectRequest,
+    select_object_content::S3SelectObjectContentRequest, RequestHandle, S3CrtClient, S3RequestError, ShutdownReport,
+};
 
 /// Configuration for the S3 client
 pub mod config {
     pub use super::endpoint_config::{AddressingStyle, EndpointConfig};
-    pub use super::s3_crt_client::{S3ClientAuthConfig, S3ClientConfig};
+    pub use super::proxy_config::ProxyConfig;
+    pub use super::s3_crt_client::presign::{ClockSource, SystemClock};
+    pub use super::s3_crt_client::{ChecksumMode, S3ClientAuthConfig, S3ClientConfig};
+    pub use super::tls_config::TlsConfig;
+    pub use mountpoint_s3_crt::auth::credentials::ObserveCredentials;
+    pub use mountpoint_s3_crt::auth::signing_config::SigningAlgorithm;
+    pub use mountpoint_s3_crt::io::host_resolver::HostResolutionMode;
+    pub use mountpoint_s3_crt::io::retry_strategy::ExponentialBackoffJitterMode;
+    pub use mountpoint_s3_crt::s3::client::TcpKeepAliveOptions;
 }
 
 /// Types used by all object clients
 pub mod types {
+    pub use super::get_object_response::GetObjectResponse;
     pub use super::object_client::{
-        Checksum, ChecksumAlgorithm, DeleteObjectResult, ETag, GetBodyPart, GetObjectAttributesParts,
-        GetObjectAttributesResult, GetObjectRequest, HeadObjectResult, ListObjectsResult, ObjectAttribute,
-        ObjectClientResult, ObjectInfo, ObjectPart, PutObjectParams, PutObjectResult, PutObjectTrailingChecksums,
-        RestoreStatus, UploadReview, UploadReviewPart,
+        ChangeKind, Checksum, ChecksumAlgorithm, ChecksumType, ContentHeaders, CopyObjectParams, CopyObjectResult,
+        DeleteObjectParams, DeleteObjectResult, DeleteObjectsResult, ETag, GetBodyPart, GetObjectAttributesParts,
+        GetObjectAttributesResult, GetObjectParams, GetObjectRequest, HeadObjectParams, HeadObjectResult, ListEntry,
+        ListObjectsResult, MetaRequestMetrics, MetadataDirective, ObjectAttribute, ObjectClientResult, ObjectExistence,
+        ObjectExpiration, ObjectInfo, ObjectLock, ObjectLockMode, ObjectPart, PutObjectParams, PutObjectResult,
+        PutObjectTrailingChecksums, RequestTimings, ResponseMetadata, RestoreObjectResult, RestoreStatus, RestoreTier,
+        ServerSideEncryption, UploadReview, UploadReviewPart, MAX_OBJECT_TAGS,
     };
+    pub use super::s3_crt_client::get_object::ContentRange;
+    pub use super::s3_crt_client::list_object_versions::{ListVersionsResult, ObjectVersion, ObjectVersionKind};
+    pub use super::s3_crt_client::list_objects::{ListObjectKeysResult, OptionalObjectAttribute};
+    pub use super::s3_crt_client::multipart_upload::{
+        CompleteMultipartUploadResult, CompletedPart, CreateMultipartUploadResult, ListPartsResult, UploadPartResult,
+        UploadedPart,
+    };
+    pub use super::s3_crt_client::object_acl::{AclGrant, AclGrantee, AclOwner, AclPermission, ObjectAcl};
+    pub use super::s3_crt_client::presign::PresignPutParams;
+    pub use super::s3_crt_client::select_object_content::{
+        CsvFileHeaderInfo, JsonType, SelectEvent, SelectInputSerialization, SelectOutputSerialization, SelectStats,
+    };
+    pub use super::s3_crt_client::self_test::{SelfTestFailure, SelfTestReport, SelfTestStep};
+    pub use super::s3_crt_client::send_request::RawResponse;
 }
 
 /// Errors returned by all object clients.
@@ -86,11 +126,40 @@ pub mod types {
 /// client errors. See its documentation for more details.
 pub mod error {
     pub use super::object_client::{
-        DeleteObjectError, GetObjectAttributesError, GetObjectError, HeadObjectError, ListObjectsError,
-        ObjectClientError, PutObjectError,
+        CopyObjectError, DeleteObjectError, GetObjectAttributesError, GetObjectError, GetObjectTaggingError,
+        HeadObjectError, ListObjectsError, ObjectClientError, PutObjectError, PutObjectTaggingError,
+        RestoreObjectError,
     };
-    #[doc(hidden)]
+    pub use super::s3_crt_client::multipart_upload::MultipartUploadError;
+    pub use super::s3_crt_client::object_acl::GetObjectAclError;
+    pub use super::s3_crt_client::select_object_content::SelectObjectContentError;
+    pub use super::s3_crt_client::GetBucketLocationError;
     pub use super::s3_crt_client::HeadBucketError;
+    pub use super::s3_crt_client::S3ErrorDetails;
+    pub use super::s3_crt_client::TimeoutKind;
+}
+
+/// In-memory object clients for testing code that depends on an [`ObjectClient`], without access
+/// to S3. Requires the `mock` feature.
+///
+/// [`MockClient`](test_utils::MockClient) serves objects added with
+/// [`MockClient::add_object`](test_utils::MockClient::add_object), including listing them. To
+/// inject errors into specific operations, wrap it in a
+/// [`FailureClient`](test_utils::FailureClient), for example with
+/// [`countdown_failure_client`](test_utils::countdown_failure_client), which fails the n'th call
+/// to an operation.
+///
+/// [`MetaRequestResultBuilder`](test_utils::MetaRequestResultBuilder) builds fake results of
+/// failed S3 requests, for testing error handling without a real request.
+#[cfg(feature = "mock")]
+pub mod test_utils {
+    pub use super::failure_client::{
+        countdown_failure_client, CountdownFailureClient, FailureClient, RequestFailureMap,
+    };
+    pub use super::meta_request_result::MetaRequestResultBuilder;
+    pub use super::mock_client::{
+        MockClient, MockClientConfig, MockClientError, MockObject, Operation, OperationCounter,
+    };
 }
 
 #[cfg(test)]
@@ -101,4 +170,10 @@ mod tests {
     fn smoke() {
         let _client = S3CrtClient::new(Default::default()).unwrap();
     }
+
+    #[test]
+    fn connection_stats_before_requests() {
+        let client = S3CrtClient::new(Default::default()).unwrap();
+        assert_eq!(client.connection_stats(), Default::default());
+    }
 }
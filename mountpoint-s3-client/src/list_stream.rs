@@ -0,0 +1,361 @@
+//! A stream over the pages of a listing, which can fetch pages ahead of the caller and list
+//! several prefixes at once.
+//!
+//! Each page of a ListObjectsV2 listing needs the continuation token from the page before it, so
+//! the pages of one prefix can only be fetched one at a time. Prefetching lets the next page of a
+//! prefix download while the caller processes the current one, but that's the most it can help
+//! with a single prefix. To fetch more pages at once, split the listing into several disjoint
+//! prefixes ("shards"), like `a`, `b`, `c`, and so on; each shard is paginated on its own, and a
+//! `prefetch` depth greater than one fetches pages of several shards in parallel.
+//!
+//! Prefetched requests only make progress while the stream is polled, unless the stream is given
+//! a runtime to spawn them on with [ListObjectsStreamParams::runtime]. The event loop group of an
+//! [S3CrtClient](crate::S3CrtClient) is a good choice.
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use mountpoint_s3_client::list_stream::{list_objects_stream, ListObjectsStreamParams};
+//! use mountpoint_s3_client::S3CrtClient;
+//!
+//! # async fn list() {
+//! let client = S3CrtClient::new(Default::default()).expect("client construction failed");
+//! let params = ListObjectsStreamParams::new()
+//!     .prefetch(4)
+//!     .runtime(client.event_loop_group());
+//! let shards = ["logs/0", "logs/1", "logs/2", "logs/3"];
+//! let mut pages = list_objects_stream(&client, "my-bucket", &shards, &params);
+//! while let Some(page) = pages.next().await {
+//!     for object in page.expect("list_objects failed").objects {
+//!         println!("{}", object.key);
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::task::{Spawn, SpawnExt};
+use futures::{Future, FutureExt, Stream, StreamExt};
+use tracing::warn;
+
+use crate::object_client::{ListObjectsError, ListObjectsResult, ObjectClientResult};
+use crate::ObjectClient;
+
+/// Parameters of a [list_objects_stream]
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct ListObjectsStreamParams {
+    /// The delimiter to roll up common prefixes with, if any
+    pub delimiter: Option<String>,
+    /// The maximum number of keys to ask for in each page
+    pub max_keys: usize,
+    /// The number of pages to fetch ahead of the caller, across all shards. With zero, each page
+    /// is only requested once the caller asks for it. Each shard has at most one request in flight
+    /// at a time, so a depth greater than one only helps when listing several shards.
+    pub prefetch: usize,
+    /// The runtime to spawn requests on, so that prefetched pages are fetched in the background.
+    /// Without one, requests only make progress while the stream is polled.
+    pub runtime: Option<Arc<dyn Spawn + Send + Sync>>,
+}
+
+impl Debug for ListObjectsStreamParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListObjectsStreamParams")
+            .field("delimiter", &self.delimiter)
+            .field("max_keys", &self.max_keys)
+            .field("prefetch", &self.prefetch)
+            .field("runtime", &self.runtime.is_some())
+            .finish()
+    }
+}
+
+impl Default for ListObjectsStreamParams {
+    fn default() -> Self {
+        Self {
+            delimiter: None,
+            max_keys: 1000,
+            prefetch: 0,
+            runtime: None,
+        }
+    }
+}
+
+impl ListObjectsStreamParams {
+    /// Create a default [ListObjectsStreamParams].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the delimiter.
+    pub fn delimiter(mut self, value: Option<String>) -> Self {
+        self.delimiter = value;
+        self
+    }
+
+    /// Set the maximum number of keys in each page.
+    pub fn max_keys(mut self, value: usize) -> Self {
+        self.max_keys = value;
+        self
+    }
+
+    /// Set the number of pages to fetch ahead of the caller.
+    pub fn prefetch(mut self, value: usize) -> Self {
+        self.prefetch = value;
+        self
+    }
+
+    /// Set the runtime to spawn requests on.
+    pub fn runtime(mut self, runtime: impl Spawn + Send + Sync + 'static) -> Self {
+        self.runtime = Some(Arc::new(runtime));
+        self
+    }
+}
+
+type ListResult<Client> =
+    ObjectClientResult<ListObjectsResult, ListObjectsError, <Client as ObjectClient>::ClientError>;
+
+/// List the objects in a bucket under each of the given prefixes, as a stream of pages.
+///
+/// The pages of each prefix are returned in order, but the pages of different prefixes are
+/// interleaved in the order they arrive. A failed request ends the listing of its prefix with
+/// that error, and the other prefixes carry on. See the [module documentation](self) for how
+/// prefetching and sharding work together.
+pub fn list_objects_stream<'a, Client>(
+    client: &'a Client,
+    bucket: &'a str,
+    prefixes: &'a [&'a str],
+    params: &'a ListObjectsStreamParams,
+) -> impl Stream<Item = ListResult<Client>> + Send + 'a
+where
+    Client: ObjectClient + Clone + Send + Sync + 'static,
+{
+    let state = ListStreamState {
+        client,
+        bucket,
+        prefixes,
+        params,
+        waiting: (0..prefixes.len()).map(|shard| (shard, None)).collect(),
+        in_flight: FuturesUnordered::new(),
+        ready: VecDeque::new(),
+    };
+    futures::stream::unfold(state, |mut state| async move {
+        let page = state.next_page().await?;
+        Some((page, state))
+    })
+}
+
+struct ListStreamState<'a, Client: ObjectClient> {
+    client: &'a Client,
+    bucket: &'a str,
+    prefixes: &'a [&'a str],
+    params: &'a ListObjectsStreamParams,
+    /// The shards with more pages to request, and the continuation token to request them with
+    waiting: VecDeque<(usize, Option<String>)>,
+    /// The requests in flight, with the shard they're for
+    in_flight: FuturesUnordered<BoxFuture<'static, (usize, ListResult<Client>)>>,
+    /// The pages received but not yet returned
+    ready: VecDeque<ListResult<Client>>,
+}
+
+impl<'a, Client: ObjectClient + Clone + Send + Sync + 'static> ListStreamState<'a, Client> {
+    async fn next_page(&mut self) -> Option<ListResult<Client>> {
+        // The page the caller is waiting for doesn't count towards the prefetch depth
+        self.start_requests(self.params.prefetch + 1);
+        while self.ready.is_empty() {
+            let (shard, result) = self.in_flight.next().await?;
+            self.receive(shard, result);
+            self.start_requests(self.params.prefetch + 1);
+        }
+        let page = self.ready.pop_front();
+
+        // Start the pages to prefetch before returning, rather than the next time we're polled
+        self.start_requests(self.params.prefetch);
+        while let Some(Some((shard, result))) = self.in_flight.next().now_or_never() {
+            self.receive(shard, result);
+        }
+        page
+    }
+
+    /// Start requests for waiting shards until `limit` pages are in flight or ready.
+    fn start_requests(&mut self, limit: usize) {
+        while self.in_flight.len() + self.ready.len() < limit {
+            let Some((shard, continuation_token)) = self.waiting.pop_front() else {
+                break;
+            };
+            let request = match &self.params.runtime {
+                Some(runtime) => match runtime.spawn_with_handle(self.request(shard, continuation_token.clone())) {
+                    Ok(handle) => handle.boxed(),
+                    Err(e) => {
+                        // The request can still run when the stream is polled
+                        warn!(?e, "failed to spawn ListObjectsV2 request, fetching it in place");
+                        self.request(shard, continuation_token).boxed()
+                    }
+                },
+                None => self.request(shard, continuation_token).boxed(),
+            };
+            self.in_flight.push(request);
+        }
+    }
+
+    /// A request for the next page of a shard, which doesn't borrow from the stream so that it can
+    /// be spawned.
+    fn request(
+        &self,
+        shard: usize,
+        continuation_token: Option<String>,
+    ) -> impl Future<Output = (usize, ListResult<Client>)> + Send + 'static {
+        let client = self.client.clone();
+        let bucket = self.bucket.to_owned();
+        let prefix = self.prefixes[shard].to_owned();
+        let delimiter = self.params.delimiter.clone();
+        let max_keys = self.params.max_keys;
+        async move {
+            let result = client
+                .list_objects(
+                    &bucket,
+                    continuation_token.as_deref(),
+                    delimiter.as_deref(),
+                    max_keys,
+                    &prefix,
+                )
+                .await;
+            (shard, result)
+        }
+    }
+
+    fn receive(&mut self, shard: usize, result: ListResult<Client>) {
+        if let Ok(ListObjectsResult {
+            next_continuation_token: Some(token),
+            ..
+        }) = &result
+        {
+            self.waiting.push_back((shard, Some(token.clone())));
+        }
+        self.ready.push_back(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    use futures::executor::block_on;
+    use futures::task::{FutureObj, SpawnError};
+    use mountpoint_s3_crt::common::allocator::Allocator;
+    use mountpoint_s3_crt::io::event_loop::EventLoopGroup;
+    use test_case::test_case;
+
+    use super::*;
+    use crate::mock_client::{MockClient, MockClientConfig, MockObject, Operation};
+    use crate::types::ETag;
+
+    fn client_with_keys(keys: &[String]) -> MockClient {
+        let client = MockClient::new(MockClientConfig {
+            bucket: "test_bucket".to_string(),
+            part_size: 1024,
+            ..Default::default()
+        });
+        for key in keys {
+            client.add_object(key, MockObject::constant(0u8, 1, ETag::for_tests()));
+        }
+        client
+    }
+
+    #[test]
+    fn list_shards_in_pages() {
+        let keys: Vec<String> = ["a", "b", "c"]
+            .iter()
+            .flat_map(|shard| (0..5).map(move |i| format!("{shard}/{i}")))
+            .collect();
+        let client = client_with_keys(&keys);
+
+        let params = ListObjectsStreamParams::new().max_keys(2).prefetch(3);
+        let shards = ["a/", "b/", "c/"];
+        let pages: Vec<_> = block_on(list_objects_stream(&client, "test_bucket", &shards, &params).collect());
+        // Each shard of 5 keys takes 3 pages of 2
+        assert_eq!(pages.len(), 9);
+        let mut listed: Vec<_> = pages
+            .into_iter()
+            .flat_map(|page| page.expect("list should succeed").objects)
+            .map(|object| object.key)
+            .collect();
+        listed.sort();
+        assert_eq!(listed, keys);
+    }
+
+    #[test_case(0, 1; "no prefetch")]
+    #[test_case(1, 2; "prefetch one")]
+    // A single shard can't be fetched more than one page ahead
+    #[test_case(4, 2; "prefetch beyond one")]
+    fn prefetch_pages(prefetch: usize, expected_requests: u64) {
+        let keys: Vec<String> = (0..10).map(|i| format!("key{i}")).collect();
+        let client = client_with_keys(&keys);
+        let counter = client.new_counter(Operation::ListObjectsV2);
+
+        let params = ListObjectsStreamParams::new().max_keys(2).prefetch(prefetch);
+        let shards = [""];
+        let pages = list_objects_stream(&client, "test_bucket", &shards, &params);
+        futures::pin_mut!(pages);
+        let first = block_on(pages.next()).expect("should have a page");
+        assert_eq!(first.expect("list should succeed").objects.len(), 2);
+        assert_eq!(counter.count(), expected_requests);
+    }
+
+    /// An event loop group that counts the futures spawned on it
+    struct CountingRuntime {
+        event_loop_group: EventLoopGroup,
+        spawned: Arc<AtomicUsize>,
+    }
+
+    impl Spawn for CountingRuntime {
+        fn spawn_obj(&self, future: FutureObj<'static, ()>) -> Result<(), SpawnError> {
+            self.spawned.fetch_add(1, Ordering::SeqCst);
+            self.event_loop_group.spawn_obj(future)
+        }
+    }
+
+    #[test]
+    fn prefetch_while_idle() {
+        let keys: Vec<String> = (0..10).map(|i| format!("key{i}")).collect();
+        let client = client_with_keys(&keys);
+        let counter = client.new_counter(Operation::ListObjectsV2);
+        let spawned = Arc::new(AtomicUsize::new(0));
+        let runtime = CountingRuntime {
+            event_loop_group: EventLoopGroup::new_default(&Allocator::default(), None, || {}).unwrap(),
+            spawned: spawned.clone(),
+        };
+
+        let params = ListObjectsStreamParams::new().max_keys(2).prefetch(1).runtime(runtime);
+        let shards = [""];
+        let pages = list_objects_stream(&client, "test_bucket", &shards, &params);
+        futures::pin_mut!(pages);
+        let first = block_on(pages.next()).expect("should have a page");
+        assert_eq!(first.expect("list should succeed").objects.len(), 2);
+
+        // Without polling the stream again, the next page should be fetched on the runtime
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while counter.count() < 2 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(counter.count(), 2);
+        assert_eq!(spawned.load(Ordering::SeqCst), 2);
+
+        let second = block_on(pages.next()).expect("should have a page");
+        assert_eq!(second.expect("list should succeed").objects.len(), 2);
+    }
+
+    #[test]
+    fn failed_shard_ends_with_error() {
+        let client = client_with_keys(&["a/0".to_string()]);
+        let params = ListObjectsStreamParams::new();
+        let shards = ["a/"];
+        let pages: Vec<_> = block_on(list_objects_stream(&client, "other_bucket", &shards, &params).collect());
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].is_err());
+    }
+}
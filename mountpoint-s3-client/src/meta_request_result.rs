@@ -0,0 +1,114 @@
+use std::ffi::OsString;
+
+use mountpoint_s3_crt::common::allocator::Allocator;
+use mountpoint_s3_crt::common::error::Error;
+use mountpoint_s3_crt::http::request_response::{Header, Headers};
+use mountpoint_s3_crt::s3::client::MetaRequestResult;
+
+/// A builder for fake [MetaRequestResult]s, for testing code that handles failed S3 requests, like
+/// [`S3RequestError::ResponseError`](crate::S3RequestError::ResponseError).
+///
+/// ```
+/// # use mountpoint_s3_client::test_utils::MetaRequestResultBuilder;
+/// let result = MetaRequestResultBuilder::new(404)
+///     .header("x-amz-request-id", "4VAGDP5HMYTDNB3Y")
+///     .body("<Error><Code>NoSuchKey</Code></Error>")
+///     .build();
+/// assert!(result.is_err());
+/// ```
+#[derive(Debug)]
+pub struct MetaRequestResultBuilder {
+    response_status: i32,
+    crt_error: Option<Error>,
+    headers: Vec<(String, String)>,
+    body: Option<OsString>,
+}
+
+impl MetaRequestResultBuilder {
+    /// Start building a result with the given HTTP response status.
+    pub fn new(response_status: i32) -> Self {
+        Self {
+            response_status,
+            crt_error: None,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// Set the CRT error of the result. By default, results with a 2xx status succeed, and others
+    /// fail with the error the CRT reports for an unsuccessful response status.
+    pub fn crt_error(mut self, crt_error: impl Into<Error>) -> Self {
+        self.crt_error = Some(crt_error.into());
+        self
+    }
+
+    /// Add a header to the error response headers of the result.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Set the error response body of the result.
+    pub fn body(mut self, body: impl Into<OsString>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Build the result.
+    pub fn build(self) -> MetaRequestResult {
+        let crt_error = self.crt_error.unwrap_or_else(|| {
+            if (200..300).contains(&self.response_status) {
+                0i32.into()
+            } else {
+                (mountpoint_s3_crt_sys::aws_s3_errors::AWS_ERROR_S3_INVALID_RESPONSE_STATUS as i32).into()
+            }
+        });
+        let error_response_headers = (!self.headers.is_empty()).then(|| {
+            let mut headers = Headers::new(&Allocator::default()).expect("headers should be created");
+            for (name, value) in &self.headers {
+                headers
+                    .add_header(&Header::new(name, value))
+                    .expect("header should be added");
+            }
+            headers
+        });
+        MetaRequestResult {
+            response_status: self.response_status,
+            crt_error,
+            error_response_headers,
+            error_response_body: self.body,
+            validation_algorithm: None,
+            metrics: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_error_result() {
+        let result = MetaRequestResultBuilder::new(503)
+            .header("x-amz-request-id", "abc")
+            .header("x-amz-id-2", "def")
+            .body("<Error><Code>SlowDown</Code></Error>")
+            .build();
+        assert!(result.is_err());
+        assert_eq!(result.response_status, 503);
+        let headers = result.error_response_headers.expect("headers should be set");
+        assert_eq!(headers.get("x-amz-id-2").unwrap().value().to_string_lossy(), "def");
+        assert_eq!(
+            result.error_response_body.as_deref(),
+            Some("<Error><Code>SlowDown</Code></Error>".as_ref())
+        );
+    }
+
+    #[test]
+    fn build_success_result() {
+        let result = MetaRequestResultBuilder::new(200).build();
+        assert!(!result.is_err());
+        assert!(result.error_response_headers.is_none());
+        assert!(result.error_response_body.is_none());
+    }
+}
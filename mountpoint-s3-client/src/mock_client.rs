@@ -4,7 +4,6 @@
 
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
-use std::ops::Range;
 use std::pin::Pin;
 use std::sync::{Arc, RwLock};
 use std::task::{Context, Poll};
@@ -13,7 +12,6 @@ use std::time::{Duration, SystemTime};
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
 use lazy_static::lazy_static;
-use mountpoint_s3_crt::checksums::crc32c;
 use rand::seq::SliceRandom;
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
@@ -21,14 +19,17 @@ use thiserror::Error;
 use time::OffsetDateTime;
 use tracing::trace;
 
-use crate::checksums::crc32c_to_base64;
+use crate::checksums::UploadChecksumHasher;
 use crate::error_metadata::{ClientErrorMetadata, ProvideErrorMetadata};
 use crate::object_client::{
-    Checksum, ChecksumAlgorithm, DeleteObjectError, DeleteObjectResult, ETag, GetBodyPart, GetObjectAttributesError,
-    GetObjectAttributesParts, GetObjectAttributesResult, GetObjectError, GetObjectRequest, HeadObjectError,
-    HeadObjectResult, ListObjectsError, ListObjectsResult, ObjectAttribute, ObjectClient, ObjectClientError,
-    ObjectClientResult, ObjectInfo, ObjectPart, PutObjectError, PutObjectParams, PutObjectRequest, PutObjectResult,
-    PutObjectTrailingChecksums, RestoreStatus, UploadReview, UploadReviewPart,
+    Checksum, ChecksumAlgorithm, ContentHeaders, CopyObjectError, CopyObjectParams, CopyObjectResult,
+    DeleteObjectError, DeleteObjectParams, DeleteObjectResult, DeleteObjectsResult, ETag, GetBodyPart,
+    GetObjectAttributesError, GetObjectAttributesParts, GetObjectAttributesResult, GetObjectError, GetObjectParams,
+    GetObjectRequest, GetObjectTaggingError, HeadObjectError, HeadObjectParams, HeadObjectResult, ListObjectsError,
+    ListObjectsResult, ObjectAttribute, ObjectClient, ObjectClientError, ObjectClientResult, ObjectExistence,
+    ObjectInfo, ObjectPart, PutObjectError, PutObjectParams, PutObjectRequest, PutObjectResult, PutObjectTaggingError,
+    PutObjectTrailingChecksums, ResponseMetadata, RestoreObjectError, RestoreObjectResult, RestoreStatus, RestoreTier,
+    UploadReview, UploadReviewPart, MAX_OBJECT_TAGS,
 };
 
 mod leaky_bucket;
@@ -55,7 +56,7 @@ lazy_static! {
     static ref RAMP_BYTES: Vec<u8> = ramp_bytes(0, RAMP_BUFFER_SIZE + RAMP_MODULUS);
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct MockClientConfig {
     /// The bucket name this client will connect to
     pub bucket: String,
@@ -70,8 +71,8 @@ pub struct MockClientConfig {
 }
 
 /// A mock implementation of an object client that we can manually add objects to, and then query
-/// via the [ObjectClient] APIs.
-#[derive(Debug)]
+/// via the [ObjectClient] APIs. Clones share the same objects.
+#[derive(Debug, Clone)]
 pub struct MockClient {
     config: MockClientConfig,
     objects: Arc<RwLock<BTreeMap<String, MockObject>>>,
@@ -129,19 +130,6 @@ impl MockClient {
         }
     }
 
-    /// Returns error if object does not exist
-    pub fn restore_object(&self, key: &str) -> Result<(), MockClientError> {
-        match self.objects.write().unwrap().get_mut(key) {
-            Some(mock_object) => {
-                mock_object.restore_status = Some(RestoreStatus::Restored {
-                    expiry: SystemTime::now() + Duration::from_secs(3600),
-                });
-                Ok(())
-            }
-            None => Err(MockClientError("object not found".into())),
-        }
-    }
-
     pub fn is_object_restored(&self, key: &str) -> Result<bool, MockClientError> {
         if let Some(mock_object) = self.objects.read().unwrap().get(key) {
             Ok(matches!(
@@ -175,12 +163,12 @@ impl MockClient {
     fn list_objects_ordered(
         &self,
         continuation_token: Option<&str>,
-        delimiter: &str,
+        delimiter: Option<&str>,
         max_keys: usize,
         prefix: &str,
     ) -> ListObjectsResult {
-        // TODO delimiter and prefix should be optional in the API
-        let delimiter = (!delimiter.is_empty()).then_some(delimiter);
+        // TODO prefix should be optional in the API
+        let delimiter = delimiter.filter(|delimiter| !delimiter.is_empty());
 
         let objects = self.objects.read().unwrap();
 
@@ -251,9 +239,10 @@ impl MockClient {
                     key: key.to_string(),
                     size: object.len() as u64,
                     last_modified: object.last_modified,
-                    etag: object.etag.as_str().to_string(),
+                    etag: Some(object.etag.as_str().to_string()),
                     storage_class: object.storage_class.clone(),
                     restore_status: object.restore_status,
+                    checksum_type: None,
                 });
             }
         }
@@ -264,19 +253,25 @@ impl MockClient {
             objects: object_vec,
             common_prefixes,
             next_continuation_token,
+            prefix: Some(prefix.to_owned()),
+            delimiter: delimiter.map(str::to_owned),
+            max_keys: Some(max_keys),
+            encoding_type: None,
+            request_charged: false,
+            response_metadata: ResponseMetadata::new(200),
         }
     }
 
     fn list_objects_unordered(
         &self,
         continuation_token: Option<&str>,
-        delimiter: &str,
+        delimiter: Option<&str>,
         max_keys: usize,
         prefix: &str,
         seed: u64,
     ) -> ListObjectsResult {
-        // TODO delimiter and prefix should be optional in the API
-        let delimiter = (!delimiter.is_empty()).then_some(delimiter);
+        // TODO prefix should be optional in the API
+        let delimiter = delimiter.filter(|delimiter| !delimiter.is_empty());
 
         let mut common_prefixes: Vec<String> = Vec::new();
         let mut common_prefixes_set: HashSet<String> = HashSet::new();
@@ -312,9 +307,10 @@ impl MockClient {
                     key: key.to_string(),
                     size: object.len() as u64,
                     last_modified: object.last_modified,
-                    etag: object.etag.as_str().to_string(),
+                    etag: Some(object.etag.as_str().to_string()),
                     storage_class: object.storage_class.clone(),
                     restore_status: object.restore_status,
+                    checksum_type: None,
                 });
             }
             next_continuation_token += 1;
@@ -327,6 +323,12 @@ impl MockClient {
             objects: object_vec,
             common_prefixes,
             next_continuation_token,
+            prefix: Some(prefix.to_owned()),
+            delimiter: delimiter.map(str::to_owned),
+            max_keys: Some(max_keys),
+            encoding_type: None,
+            request_charged: false,
+            response_metadata: ResponseMetadata::new(200),
         }
     }
 }
@@ -334,12 +336,17 @@ impl MockClient {
 /// Operations for use in operation counters.
 #[derive(Debug, Eq, Hash, PartialEq)]
 pub enum Operation {
+    CopyObject,
     DeleteObject,
+    DeleteObjects,
     HeadObject,
     GetObject,
     GetObjectAttributes,
+    GetObjectTagging,
     ListObjectsV2,
     PutObject,
+    PutObjectTagging,
+    RestoreObject,
 }
 
 /// Counter for a specific client [Operation].
@@ -371,6 +378,10 @@ pub struct MockObject {
     last_modified: OffsetDateTime,
     etag: ETag,
     parts: Option<MockObjectParts>,
+    tags: Vec<(String, String)>,
+    user_metadata: HashMap<String, String>,
+    content_type: Option<String>,
+    content_headers: ContentHeaders,
 }
 
 impl MockObject {
@@ -389,6 +400,10 @@ impl MockObject {
             last_modified: OffsetDateTime::now_utc(),
             etag,
             parts: None,
+            tags: Vec::new(),
+            user_metadata: HashMap::new(),
+            content_type: None,
+            content_headers: ContentHeaders::default(),
         }
     }
 
@@ -401,6 +416,10 @@ impl MockObject {
             last_modified: OffsetDateTime::now_utc(),
             etag,
             parts: None,
+            tags: Vec::new(),
+            user_metadata: HashMap::new(),
+            content_type: None,
+            content_headers: ContentHeaders::default(),
         }
     }
 
@@ -423,6 +442,10 @@ impl MockObject {
             last_modified: OffsetDateTime::now_utc(),
             etag,
             parts: None,
+            tags: Vec::new(),
+            user_metadata: HashMap::new(),
+            content_type: None,
+            content_headers: ContentHeaders::default(),
         }
     }
 
@@ -438,6 +461,18 @@ impl MockObject {
         self.restore_status = restore_status;
     }
 
+    pub fn set_user_metadata(&mut self, user_metadata: HashMap<String, String>) {
+        self.user_metadata = user_metadata;
+    }
+
+    pub fn set_content_type(&mut self, content_type: Option<String>) {
+        self.content_type = content_type;
+    }
+
+    pub fn set_content_headers(&mut self, content_headers: ContentHeaders) {
+        self.content_headers = content_headers;
+    }
+
     pub fn len(&self) -> usize {
         self.size
     }
@@ -477,6 +512,7 @@ pub struct MockGetObjectRequest {
     part_size: usize,
     enable_back_pressure: bool,
     current_window_size: usize,
+    response_status: i32,
 }
 
 impl MockGetObjectRequest {
@@ -500,6 +536,10 @@ impl GetObjectRequest for MockGetObjectRequest {
     fn increment_read_window(mut self: Pin<&mut Self>, len: usize) {
         self.current_window_size += len;
     }
+
+    fn response_metadata(&self) -> Option<ResponseMetadata> {
+        Some(ResponseMetadata::new(self.response_status))
+    }
 }
 
 impl Stream for MockGetObjectRequest {
@@ -558,46 +598,178 @@ impl ObjectClient for MockClient {
         Some(self.config.part_size)
     }
 
+    async fn copy_object(
+        &self,
+        source_bucket: &str,
+        source_key: &str,
+        destination_bucket: &str,
+        destination_key: &str,
+        params: &CopyObjectParams,
+    ) -> ObjectClientResult<CopyObjectResult, CopyObjectError, Self::ClientError> {
+        trace!(
+            source_bucket,
+            source_key,
+            destination_bucket,
+            destination_key,
+            "CopyObject"
+        );
+        self.inc_op_count(Operation::CopyObject);
+
+        if source_bucket != self.config.bucket || destination_bucket != self.config.bucket {
+            return Err(ObjectClientError::ServiceError(CopyObjectError::NoSuchBucket));
+        }
+
+        let mut object = self
+            .objects
+            .read()
+            .unwrap()
+            .get(source_key)
+            .cloned()
+            .ok_or(ObjectClientError::ServiceError(CopyObjectError::NoSuchKey))?;
+        object.set_storage_class(params.storage_class.clone());
+        object.set_last_modified(OffsetDateTime::now_utc());
+
+        let result = CopyObjectResult {
+            etag: object.etag(),
+            last_modified: object.last_modified,
+            response_metadata: ResponseMetadata::new(200),
+        };
+        self.add_object(destination_key, object);
+        Ok(result)
+    }
+
     async fn delete_object(
         &self,
         bucket: &str,
         key: &str,
+        params: &DeleteObjectParams,
     ) -> ObjectClientResult<DeleteObjectResult, DeleteObjectError, Self::ClientError> {
-        trace!(bucket, key, "DeleteObject");
+        trace!(bucket, key, version_id = ?params.version_id, if_match = ?params.if_match, "DeleteObject");
         self.inc_op_count(Operation::DeleteObject);
 
         if bucket != self.config.bucket {
             return Err(ObjectClientError::ServiceError(DeleteObjectError::NoSuchBucket));
         }
 
-        self.remove_object(key);
+        if let Some(etag_match) = &params.if_match {
+            let etag = self.objects.read().unwrap().get(key).map(|object| object.etag.clone());
+            if etag.as_ref() != Some(etag_match) {
+                return Err(ObjectClientError::ServiceError(DeleteObjectError::PreconditionFailed));
+            }
+        }
+
+        // Like S3, deleting a version that doesn't exist succeeds without deleting anything
+        if params.version_id.is_none() {
+            self.remove_object(key);
+        }
 
-        Ok(DeleteObjectResult {})
+        Ok(DeleteObjectResult {
+            response_metadata: ResponseMetadata::new(204),
+        })
+    }
+
+    async fn delete_objects(
+        &self,
+        bucket: &str,
+        keys: &[String],
+    ) -> ObjectClientResult<DeleteObjectsResult, DeleteObjectError, Self::ClientError> {
+        trace!(bucket, ?keys, "DeleteObjects");
+        self.inc_op_count(Operation::DeleteObjects);
+
+        if bucket != self.config.bucket {
+            return Err(ObjectClientError::ServiceError(DeleteObjectError::NoSuchBucket));
+        }
+
+        for key in keys {
+            self.remove_object(key);
+        }
+
+        Ok(DeleteObjectsResult {
+            deleted: keys.to_vec(),
+            errors: Vec::new(),
+        })
     }
 
     async fn get_object(
         &self,
         bucket: &str,
         key: &str,
-        range: Option<Range<u64>>,
-        if_match: Option<ETag>,
+        params: &GetObjectParams,
     ) -> ObjectClientResult<Self::GetObjectRequest, GetObjectError, Self::ClientError> {
-        trace!(bucket, key, ?range, ?if_match, "GetObject");
+        let GetObjectParams {
+            range,
+            if_match,
+            if_none_match,
+            if_modified_since,
+            if_unmodified_since,
+            decode_content_encoding,
+            version_id,
+            part_number,
+            checksum_mode: _,
+            request_payer: _,
+        } = params;
+        trace!(
+            bucket,
+            key,
+            ?range,
+            ?if_match,
+            ?if_none_match,
+            ?if_modified_since,
+            ?if_unmodified_since,
+            decode_content_encoding,
+            ?version_id,
+            ?part_number,
+            "GetObject"
+        );
         self.inc_op_count(Operation::GetObject);
 
         if bucket != self.config.bucket {
             return Err(ObjectClientError::ServiceError(GetObjectError::NoSuchBucket));
         }
 
+        // The mock client keeps only the current version of each object, with no version ID
+        if version_id.is_some() {
+            return Err(ObjectClientError::ServiceError(GetObjectError::NoSuchKey));
+        }
+
+        if part_number.is_some() {
+            return mock_client_error("GetObject of a single part isn't supported by the mock client");
+        }
+
+        if *decode_content_encoding && range.is_some() {
+            return mock_client_error("decode_content_encoding can't be combined with a range");
+        }
+
         let objects = self.objects.read().unwrap();
 
         if let Some(object) = objects.get(key) {
             if let Some(etag_match) = if_match {
-                if etag_match != object.etag {
+                if *etag_match != object.etag {
                     return Err(ObjectClientError::ServiceError(GetObjectError::PreconditionFailed));
                 }
             }
 
+            if let Some(etag_none_match) = if_none_match {
+                if *etag_none_match == object.etag {
+                    return Err(ObjectClientError::ServiceError(GetObjectError::NotModified));
+                }
+            }
+
+            // Like S3, only compare timestamps when there's no ETag condition, and at the
+            // granularity of the HTTP-date format (whole seconds)
+            let last_modified = object.last_modified.unix_timestamp();
+            if let (None, Some(since)) = (if_match, if_unmodified_since) {
+                if last_modified > since.unix_timestamp() {
+                    return Err(ObjectClientError::ServiceError(GetObjectError::PreconditionFailed));
+                }
+            }
+
+            if let (None, Some(since)) = (if_none_match, if_modified_since) {
+                if last_modified <= since.unix_timestamp() {
+                    return Err(ObjectClientError::ServiceError(GetObjectError::NotModified));
+                }
+            }
+
             let (next_offset, length) = if let Some(range) = range {
                 if range.start >= object.len() as u64 || range.end > object.len() as u64 {
                     return mock_client_error(format!("invalid range, length={}", object.len()));
@@ -614,6 +786,7 @@ impl ObjectClient for MockClient {
                 part_size: self.config.part_size,
                 enable_back_pressure: self.config.enable_back_pressure,
                 current_window_size: self.config.initial_read_window_size,
+                response_status: if range.is_some() { 206 } else { 200 },
             })
         } else {
             Err(ObjectClientError::ServiceError(GetObjectError::NoSuchKey))
@@ -624,11 +797,12 @@ impl ObjectClient for MockClient {
         &self,
         bucket: &str,
         key: &str,
+        params: &HeadObjectParams,
     ) -> ObjectClientResult<HeadObjectResult, HeadObjectError, Self::ClientError> {
-        trace!(bucket, key, "HeadObject");
+        trace!(bucket, key, version_id = ?params.version_id, "HeadObject");
         self.inc_op_count(Operation::HeadObject);
 
-        if bucket != self.config.bucket {
+        if bucket != self.config.bucket || params.version_id.is_some() {
             return Err(ObjectClientError::ServiceError(HeadObjectError::NotFound));
         }
 
@@ -640,10 +814,25 @@ impl ObjectClient for MockClient {
                     key: key.to_string(),
                     size: object.size as u64,
                     last_modified: object.last_modified,
-                    etag: object.etag.as_str().to_string(),
+                    etag: Some(object.etag.as_str().to_string()),
                     storage_class: object.storage_class.clone(),
                     restore_status: object.restore_status,
+                    checksum_type: None,
                 },
+                checksum: Checksum {
+                    checksum_crc32: None,
+                    checksum_crc32c: None,
+                    checksum_sha1: None,
+                    checksum_sha256: None,
+                    checksum_crc64nvme: None,
+                    checksum_type: None,
+                },
+                request_charged: false,
+                user_metadata: object.user_metadata.clone(),
+                content_type: object.content_type.clone(),
+                content_headers: object.content_headers.clone(),
+                expiration: None,
+                response_metadata: ResponseMetadata::new(200),
             })
         } else {
             Err(ObjectClientError::ServiceError(HeadObjectError::NotFound))
@@ -654,11 +843,11 @@ impl ObjectClient for MockClient {
         &self,
         bucket: &str,
         continuation_token: Option<&str>,
-        delimiter: &str,
+        delimiter: Option<&str>,
         max_keys: usize,
         prefix: &str,
     ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, Self::ClientError> {
-        trace!(bucket, ?continuation_token, delimiter, max_keys, prefix, "ListObjects");
+        trace!(bucket, ?continuation_token, ?delimiter, max_keys, prefix, "ListObjects");
         self.inc_op_count(Operation::ListObjectsV2);
 
         if bucket != self.config.bucket {
@@ -672,6 +861,18 @@ impl ObjectClient for MockClient {
         }
     }
 
+    async fn object_exists(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<ObjectExistence, HeadObjectError, Self::ClientError> {
+        match self.head_object(bucket, key, &HeadObjectParams::new()).await {
+            Ok(_) => Ok(ObjectExistence::Exists),
+            Err(ObjectClientError::ServiceError(HeadObjectError::NotFound)) => Ok(ObjectExistence::NotFound),
+            Err(e) => Err(e),
+        }
+    }
+
     async fn put_object(
         &self,
         bucket: &str,
@@ -713,6 +914,7 @@ impl ObjectClient for MockClient {
         let objects = self.objects.read().unwrap();
         if let Some(object) = objects.get(key) {
             let mut result = GetObjectAttributesResult::default();
+            result.response_metadata = ResponseMetadata::new(200);
             for attribute in object_attributes.iter() {
                 match attribute {
                     ObjectAttribute::ETag => result.etag = Some("TODO".to_owned()),
@@ -722,6 +924,8 @@ impl ObjectClient for MockClient {
                             checksum_crc32c: Some("TODO".to_owned()),
                             checksum_sha1: Some("TODO".to_owned()),
                             checksum_sha256: Some("TODO".to_owned()),
+                            checksum_crc64nvme: Some("TODO".to_owned()),
+                            checksum_type: None,
                         })
                     }
                     ObjectAttribute::ObjectParts => {
@@ -744,12 +948,7 @@ impl ObjectClient for MockClient {
                                         .iter()
                                         .enumerate()
                                         .map(|(i, part)| ObjectPart {
-                                            checksum: Some(Checksum {
-                                                checksum_crc32: None,
-                                                checksum_crc32c: part.checksum.clone(),
-                                                checksum_sha1: None,
-                                                checksum_sha256: None,
-                                            }),
+                                            checksum: Some(part_checksum(part.checksum.as_ref())),
                                             // Part numbers start at 1
                                             part_number: i + 1,
                                             size: part.size,
@@ -771,6 +970,88 @@ impl ObjectClient for MockClient {
             Err(ObjectClientError::ServiceError(GetObjectAttributesError::NoSuchKey))
         }
     }
+
+    async fn get_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<Vec<(String, String)>, GetObjectTaggingError, Self::ClientError> {
+        trace!(bucket, key, "GetObjectTagging");
+        self.inc_op_count(Operation::GetObjectTagging);
+
+        if bucket != self.config.bucket {
+            return Err(ObjectClientError::ServiceError(GetObjectTaggingError::NoSuchBucket));
+        }
+
+        match self.objects.read().unwrap().get(key) {
+            Some(object) => Ok(object.tags.clone()),
+            None => Err(ObjectClientError::ServiceError(GetObjectTaggingError::NoSuchKey)),
+        }
+    }
+
+    async fn put_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+        tags: &[(String, String)],
+    ) -> ObjectClientResult<(), PutObjectTaggingError, Self::ClientError> {
+        trace!(bucket, key, ?tags, "PutObjectTagging");
+        self.inc_op_count(Operation::PutObjectTagging);
+
+        if tags.len() > MAX_OBJECT_TAGS {
+            return mock_client_error(format!("objects can have at most {MAX_OBJECT_TAGS} tags"));
+        }
+
+        if bucket != self.config.bucket {
+            return Err(ObjectClientError::ServiceError(PutObjectTaggingError::NoSuchBucket));
+        }
+
+        match self.objects.write().unwrap().get_mut(key) {
+            Some(object) => {
+                object.tags = tags.to_vec();
+                Ok(())
+            }
+            None => Err(ObjectClientError::ServiceError(PutObjectTaggingError::NoSuchKey)),
+        }
+    }
+
+    async fn restore_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        days: u32,
+        tier: RestoreTier,
+    ) -> ObjectClientResult<RestoreObjectResult, RestoreObjectError, Self::ClientError> {
+        trace!(bucket, key, days, ?tier, "RestoreObject");
+        self.inc_op_count(Operation::RestoreObject);
+
+        if bucket != self.config.bucket {
+            return Err(ObjectClientError::ServiceError(RestoreObjectError::NoSuchBucket));
+        }
+
+        let mut objects = self.objects.write().unwrap();
+        let Some(object) = objects.get_mut(key) else {
+            return Err(ObjectClientError::ServiceError(RestoreObjectError::NoSuchKey));
+        };
+        if !matches!(object.storage_class.as_deref(), Some("GLACIER" | "DEEP_ARCHIVE")) {
+            return Err(ObjectClientError::ServiceError(RestoreObjectError::ObjectNotArchived));
+        }
+
+        let result = match object.restore_status {
+            Some(RestoreStatus::InProgress) => {
+                return Err(ObjectClientError::ServiceError(
+                    RestoreObjectError::RestoreAlreadyInProgress,
+                ))
+            }
+            Some(RestoreStatus::Restored { .. }) => RestoreObjectResult::AlreadyRestored,
+            None => RestoreObjectResult::RestoreStarted,
+        };
+        // The mock client completes restores immediately
+        object.restore_status = Some(RestoreStatus::Restored {
+            expiry: SystemTime::now() + Duration::from_secs(days as u64 * 24 * 60 * 60),
+        });
+        Ok(result)
+    }
 }
 
 #[derive(Debug)]
@@ -807,9 +1088,13 @@ impl MockPutObjectRequest {
             .chunks(self.part_size)
             .map(|part| {
                 let size = part.len();
-                let checksum = if self.params.trailing_checksums != PutObjectTrailingChecksums::Disabled {
-                    let checksum = crc32c::checksum(part);
-                    Some(crc32c_to_base64(&checksum))
+                // The mock client can only compute the checksums `UploadChecksumHasher` supports
+                let (trailing_checksums, algorithm) = self.params.upload_checksums();
+                let checksum = if trailing_checksums != PutObjectTrailingChecksums::Disabled {
+                    UploadChecksumHasher::new(algorithm).map(|mut hasher| {
+                        hasher.update(part);
+                        (algorithm, hasher.finalize_base64())
+                    })
                 } else {
                     None
                 };
@@ -824,17 +1109,23 @@ impl MockPutObjectRequest {
     ) -> ObjectClientResult<PutObjectResult, PutObjectError, MockClientError> {
         let buffer = std::mem::take(&mut self.buffer);
         let mut object: MockObject = buffer.into();
+        let etag = object.etag();
+        let last_modified = object.last_modified;
         object.set_storage_class(self.params.storage_class.clone());
         // For S3 Standard, part attributes are only available when additional checksums are used
-        if self.params.trailing_checksums == PutObjectTrailingChecksums::Enabled {
+        if self.params.upload_checksums().0 == PutObjectTrailingChecksums::Enabled {
             object.parts = Some(MockObjectParts::Parts(parts));
         } else {
             object.parts = Some(MockObjectParts::Count(parts.len()));
         }
         add_object(&self.objects, &self.key, object);
         Ok(PutObjectResult {
+            etag: Some(etag),
+            last_modified: Some(last_modified),
             sse_type: None,
             sse_kms_key_id: None,
+            checksum: None,
+            response_metadata: ResponseMetadata::new(200),
         })
     }
 }
@@ -863,17 +1154,14 @@ impl PutObjectRequest for MockPutObjectRequest {
         self,
         review_callback: impl FnOnce(UploadReview) -> bool + Send + 'static,
     ) -> ObjectClientResult<PutObjectResult, PutObjectError, Self::ClientError> {
-        let checksum_algorithm = if self.params.trailing_checksums != PutObjectTrailingChecksums::Disabled {
-            Some(ChecksumAlgorithm::Crc32c)
-        } else {
-            None
-        };
+        let (trailing_checksums, algorithm) = self.params.upload_checksums();
+        let checksum_algorithm = (trailing_checksums != PutObjectTrailingChecksums::Disabled).then_some(algorithm);
         let parts = self.parts();
         let review_parts = parts
             .iter()
             .map(|part| UploadReviewPart {
                 size: part.size as u64,
-                checksum: part.checksum.clone(),
+                checksum: part.checksum.as_ref().map(|(_, checksum)| checksum.clone()),
             })
             .collect();
         let review = UploadReview {
@@ -890,7 +1178,24 @@ impl PutObjectRequest for MockPutObjectRequest {
 #[derive(Debug, Clone)]
 struct MockObjectPartAttributes {
     size: usize,
-    checksum: Option<String>,
+    checksum: Option<(ChecksumAlgorithm, String)>,
+}
+
+/// The checksums GetObjectAttributes reports for a part with the given checksum.
+fn part_checksum(checksum: Option<&(ChecksumAlgorithm, String)>) -> Checksum {
+    let get = |algorithm| {
+        checksum
+            .filter(|(part_algorithm, _)| *part_algorithm == algorithm)
+            .map(|(_, checksum)| checksum.clone())
+    };
+    Checksum {
+        checksum_crc32: get(ChecksumAlgorithm::Crc32),
+        checksum_crc32c: get(ChecksumAlgorithm::Crc32c),
+        checksum_sha1: get(ChecksumAlgorithm::Sha1),
+        checksum_sha256: get(ChecksumAlgorithm::Sha256),
+        checksum_crc64nvme: get(ChecksumAlgorithm::Crc64nvme),
+        checksum_type: None,
+    }
 }
 
 /// Some S3 implementations only report per-part data from GetObjectAttributes if parts were
@@ -905,16 +1210,19 @@ enum MockObjectParts {
 #[cfg(test)]
 mod tests {
     use std::{
+        ops::Range,
         sync::mpsc::{self, RecvTimeoutError},
         thread,
     };
 
     use futures::{pin_mut, StreamExt};
+    use mountpoint_s3_crt::checksums::{crc32, crc32c};
     use rand::{Rng, RngCore, SeedableRng};
     use rand_chacha::ChaChaRng;
     use test_case::test_case;
 
     use super::*;
+    use crate::checksums::{crc32_to_base64, crc32c_to_base64};
 
     async fn test_get_object(key: &str, size: usize, range: Option<Range<u64>>) {
         let mut rng = ChaChaRng::seed_from_u64(0x12345678);
@@ -931,7 +1239,7 @@ mod tests {
         client.add_object(key, MockObject::from_bytes(&body, ETag::for_tests()));
 
         let mut get_request = client
-            .get_object("test_bucket", key, range.clone(), None)
+            .get_object("test_bucket", key, &GetObjectParams::new().range(range.clone()))
             .await
             .expect("should not fail");
 
@@ -943,6 +1251,11 @@ mod tests {
             next_offset += body.len() as u64;
             accum.extend_from_slice(&body[..]);
         }
+        let expected_status = if range.is_some() { 206 } else { 200 };
+        assert_eq!(
+            get_request.response_metadata().and_then(|m| m.response_status),
+            Some(expected_status)
+        );
         let expected_range = range.unwrap_or(0..size as u64);
         let expected_range = expected_range.start as usize..expected_range.end as usize;
         assert_eq!(&accum[..], &body[expected_range], "body does not match");
@@ -976,7 +1289,7 @@ mod tests {
         client.add_object(key, MockObject::from_bytes(&body, ETag::for_tests()));
 
         let get_request = client
-            .get_object("test_bucket", key, range.clone(), None)
+            .get_object("test_bucket", key, &GetObjectParams::new().range(range.clone()))
             .await
             .expect("should not fail");
         pin_mut!(get_request);
@@ -1004,6 +1317,55 @@ mod tests {
         test_get_object_backpressure("key1", 10, Some(0..10), 256).await;
     }
 
+    #[tokio::test]
+    async fn delete_object_if_match() {
+        let client = MockClient::new(MockClientConfig {
+            bucket: "test_bucket".to_string(),
+            part_size: 1024,
+            ..Default::default()
+        });
+        let object = MockObject::constant(0u8, 5, ETag::for_tests());
+        let etag = object.etag();
+        client.add_object("key1", object);
+
+        let other_etag: ETag = "\"other\"".parse().unwrap();
+        let params = DeleteObjectParams::new().if_match(Some(other_etag));
+        assert!(matches!(
+            client.delete_object("test_bucket", "key1", &params).await,
+            Err(ObjectClientError::ServiceError(DeleteObjectError::PreconditionFailed))
+        ));
+        assert!(client.contains_key("key1"));
+
+        let params = DeleteObjectParams::new().if_match(Some(etag));
+        client
+            .delete_object("test_bucket", "key1", &params)
+            .await
+            .expect("delete should succeed");
+        assert!(!client.contains_key("key1"));
+    }
+
+    #[tokio::test]
+    async fn head_objects() {
+        let client = MockClient::new(MockClientConfig {
+            bucket: "test_bucket".to_string(),
+            part_size: 1024,
+            ..Default::default()
+        });
+        client.add_object("key1", MockObject::constant(0u8, 5, ETag::for_tests()));
+        client.add_object("key3", MockObject::constant(0u8, 7, ETag::for_tests()));
+
+        let results = client
+            .head_objects("test_bucket", &["key1", "key2", "key3"], &HeadObjectParams::new(), 2)
+            .await;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().expect("key1 should exist").object.size, 5);
+        assert!(matches!(
+            results[1],
+            Err(ObjectClientError::ServiceError(HeadObjectError::NotFound))
+        ));
+        assert_eq!(results[2].as_ref().expect("key3 should exist").object.size, 7);
+    }
+
     #[allow(clippy::reversed_empty_ranges)]
     #[tokio::test]
     async fn get_object_errors() {
@@ -1033,37 +1395,142 @@ mod tests {
         }
 
         assert!(matches!(
-            client.get_object("wrong_bucket", "key1", None, None).await,
+            client.get_object("wrong_bucket", "key1", &GetObjectParams::new()).await,
             Err(ObjectClientError::ServiceError(GetObjectError::NoSuchBucket))
         ));
 
         assert!(matches!(
-            client.get_object("test_bucket", "wrong_key", None, None).await,
+            client
+                .get_object("test_bucket", "wrong_key", &GetObjectParams::new())
+                .await,
             Err(ObjectClientError::ServiceError(GetObjectError::NoSuchKey))
         ));
 
         assert_client_error!(
-            client.get_object("test_bucket", "key1", Some(0..2001), None).await,
+            client
+                .get_object("test_bucket", "key1", &GetObjectParams::new().range(Some(0..2001)))
+                .await,
             "invalid range, length=2000"
         );
         assert_client_error!(
-            client.get_object("test_bucket", "key1", Some(2000..2000), None).await,
+            client
+                .get_object("test_bucket", "key1", &GetObjectParams::new().range(Some(2000..2000)))
+                .await,
             "invalid range, length=2000"
         );
         assert_client_error!(
-            client.get_object("test_bucket", "key1", Some(500..2001), None).await,
+            client
+                .get_object("test_bucket", "key1", &GetObjectParams::new().range(Some(500..2001)))
+                .await,
             "invalid range, length=2000"
         );
         assert_client_error!(
-            client.get_object("test_bucket", "key1", Some(5000..2001), None).await,
+            client
+                .get_object("test_bucket", "key1", &GetObjectParams::new().range(Some(5000..2001)))
+                .await,
             "invalid range, length=2000"
         );
         assert_client_error!(
-            client.get_object("test_bucket", "key1", Some(5000..1), None).await,
+            client
+                .get_object("test_bucket", "key1", &GetObjectParams::new().range(Some(5000..1)))
+                .await,
             "invalid range, length=2000"
         );
     }
 
+    #[tokio::test]
+    async fn get_object_conditional() {
+        let client = MockClient::new(MockClientConfig {
+            bucket: "test_bucket".to_string(),
+            part_size: 1024,
+            unordered_list_seed: None,
+            ..Default::default()
+        });
+
+        client.add_object("key1", MockObject::constant(0xaa, 100, ETag::for_tests()));
+        let etag = Some(ETag::for_tests());
+        let other_etag = Some(ETag::from_object_bytes(b"other"));
+
+        assert!(matches!(
+            client
+                .get_object(
+                    "test_bucket",
+                    "key1",
+                    &GetObjectParams::new().if_none_match(etag.clone())
+                )
+                .await,
+            Err(ObjectClientError::ServiceError(GetObjectError::NotModified))
+        ));
+        assert!(matches!(
+            client
+                .get_object(
+                    "test_bucket",
+                    "key1",
+                    &GetObjectParams::new().if_match(other_etag.clone())
+                )
+                .await,
+            Err(ObjectClientError::ServiceError(GetObjectError::PreconditionFailed))
+        ));
+
+        let mut get_request = client
+            .get_object(
+                "test_bucket",
+                "key1",
+                &GetObjectParams::new().if_match(etag).if_none_match(other_etag),
+            )
+            .await
+            .expect("conditions should be satisfied");
+        let mut accum = vec![];
+        while let Some(r) = get_request.next().await {
+            let (_offset, body) = r.expect("get_object body part failed");
+            accum.extend_from_slice(&body[..]);
+        }
+        assert_eq!(accum, vec![0xaa; 100]);
+    }
+
+    #[test_case(None, None, true; "no conditions")]
+    #[test_case(Some(-60), None, true; "modified since earlier time")]
+    #[test_case(Some(0), None, false; "not modified since last modified")]
+    #[test_case(None, Some(0), true; "unmodified since last modified")]
+    #[test_case(None, Some(-60), false; "modified after earlier time")]
+    #[tokio::test]
+    async fn get_object_conditional_time(
+        if_modified_since_offset: Option<i64>,
+        if_unmodified_since_offset: Option<i64>,
+        expect_success: bool,
+    ) {
+        let client = MockClient::new(MockClientConfig {
+            bucket: "test_bucket".to_string(),
+            part_size: 1024,
+            unordered_list_seed: None,
+            ..Default::default()
+        });
+
+        let last_modified = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let mut object = MockObject::constant(0xaa, 100, ETag::for_tests());
+        object.set_last_modified(last_modified);
+        client.add_object("key1", object);
+
+        let time = |offset: Option<i64>| offset.map(|offset| last_modified + time::Duration::seconds(offset));
+        let params = GetObjectParams::new()
+            .if_modified_since(time(if_modified_since_offset))
+            .if_unmodified_since(time(if_unmodified_since_offset));
+        let result = client.get_object("test_bucket", "key1", &params).await;
+        if expect_success {
+            result.expect("conditions should be satisfied");
+        } else if if_modified_since_offset.is_some() {
+            assert!(matches!(
+                result,
+                Err(ObjectClientError::ServiceError(GetObjectError::NotModified))
+            ));
+        } else {
+            assert!(matches!(
+                result,
+                Err(ObjectClientError::ServiceError(GetObjectError::PreconditionFailed))
+            ));
+        }
+    }
+
     // Verify that the request is blocked when we don't increment read window size
     #[tokio::test]
     async fn verify_backpressure_get_object() {
@@ -1085,7 +1552,7 @@ mod tests {
         client.add_object(key, MockObject::from_bytes(&body, ETag::for_tests()));
 
         let mut get_request = client
-            .get_object("test_bucket", key, Some(range.clone()), None)
+            .get_object("test_bucket", key, &GetObjectParams::new().range(Some(range.clone())))
             .await
             .expect("should not fail");
 
@@ -1152,20 +1619,20 @@ mod tests {
             };
         }
 
-        check!("", 1000, "", &keys[..], &[]);
-        check!("/", 1000, "", &[], &["dirs/"]);
-        check!("/", 1000, "dirs/", &[], &["dirs/dir1/", "dirs/dir2/"]);
-        check!("/", 1000, "dirs/dir2/", &keys[5..10], &[]);
+        check!(None, 1000, "", &keys[..], &[]);
+        check!(Some("/"), 1000, "", &[], &["dirs/"]);
+        check!(Some("/"), 1000, "dirs/", &[], &["dirs/dir1/", "dirs/dir2/"]);
+        check!(Some("/"), 1000, "dirs/dir2/", &keys[5..10], &[]);
 
         // max-keys tests
-        check!("", 6, "", &keys[..6], &[]);
-        check!("/", 1, "", &[], &["dirs/"]);
-        check!("/", 2, "dirs/", &[], &["dirs/dir1/", "dirs/dir2/"]);
-        check!("/", 1, "dirs/", &[], &["dirs/dir1/"]);
-        check!("/", 2, "dirs/dir2/", &keys[5..7], &[]);
-        check!("/", 1, "dirs/dir2/", &keys[5..6], &[]);
-        check!("/", 1, "dirs/dir2", &[], &["dirs/dir2/"]);
-        check!("/", 2, "dirs/dir2", &[], &["dirs/dir2/"]);
+        check!(None, 6, "", &keys[..6], &[]);
+        check!(Some("/"), 1, "", &[], &["dirs/"]);
+        check!(Some("/"), 2, "dirs/", &[], &["dirs/dir1/", "dirs/dir2/"]);
+        check!(Some("/"), 1, "dirs/", &[], &["dirs/dir1/"]);
+        check!(Some("/"), 2, "dirs/dir2/", &keys[5..7], &[]);
+        check!(Some("/"), 1, "dirs/dir2/", &keys[5..6], &[]);
+        check!(Some("/"), 1, "dirs/dir2", &[], &["dirs/dir2/"]);
+        check!(Some("/"), 2, "dirs/dir2", &[], &["dirs/dir2/"]);
 
         macro_rules! check_continuation {
             ($delimiter:expr, $max_keys:expr, $prefix:expr, $objects:expr, $prefixes:expr) => {
@@ -1197,9 +1664,9 @@ mod tests {
             };
         }
 
-        check_continuation!("", 6, "", &keys[6..], &[]);
-        check_continuation!("/", 1, "dirs/", &[], &["dirs/dir2/"]);
-        check_continuation!("/", 2, "dirs/dir2/", &keys[7..9], &[]);
+        check_continuation!(None, 6, "", &keys[6..], &[]);
+        check_continuation!(Some("/"), 1, "dirs/", &[], &["dirs/dir2/"]);
+        check_continuation!(Some("/"), 2, "dirs/dir2/", &keys[7..9], &[]);
     }
 
     #[tokio::test]
@@ -1241,11 +1708,11 @@ mod tests {
             };
         }
 
-        check!("", "", &keys[..], &[]);
-        check!("/", "dirs/", &[], &["dirs/こんにちは/", "dirs/😄🥹/"]);
-        check!("", "dirs/😄🥹/", &keys[5..], &[]);
-        check!("/", "dirs/😄", &[], &["dirs/😄🥹/"]);
-        check!("", "dirs/😄🥹😮", &[], &[]);
+        check!(None, "", &keys[..], &[]);
+        check!(Some("/"), "dirs/", &[], &["dirs/こんにちは/", "dirs/😄🥹/"]);
+        check!(None, "dirs/😄🥹/", &keys[5..], &[]);
+        check!(Some("/"), "dirs/😄", &[], &["dirs/😄🥹/"]);
+        check!(None, "dirs/😄🥹😮", &[], &[]);
     }
 
     #[test_case(""; "unprefixed")]
@@ -1278,12 +1745,12 @@ mod tests {
         }
 
         let result1 = client
-            .list_objects("test_bucket", None, "/", 10, prefix)
+            .list_objects("test_bucket", None, Some("/"), 10, prefix)
             .await
             .expect("should not fail");
         let continuation_token = result1.next_continuation_token.expect("list should not be finished");
         let result2 = client
-            .list_objects("test_bucket", Some(&continuation_token), "/", 1000, prefix)
+            .list_objects("test_bucket", Some(&continuation_token), Some("/"), 1000, prefix)
             .await
             .expect("should not fail");
 
@@ -1356,7 +1823,13 @@ mod tests {
         let mut continuation_token = None;
         for _ in 0..100 {
             let result = client
-                .list_objects("test_bucket", continuation_token.as_deref(), "/", page_size, prefix)
+                .list_objects(
+                    "test_bucket",
+                    continuation_token.as_deref(),
+                    Some("/"),
+                    page_size,
+                    prefix,
+                )
                 .await
                 .expect("should not fail");
             continuation_token = result.next_continuation_token;
@@ -1424,7 +1897,7 @@ mod tests {
         let mut continuation_token = None;
         for _ in 0..100 {
             let result = client
-                .list_objects("test_bucket", continuation_token.as_deref(), "", page_size, prefix)
+                .list_objects("test_bucket", continuation_token.as_deref(), None, page_size, prefix)
                 .await
                 .expect("should not fail");
             continuation_token = result.next_continuation_token;
@@ -1485,7 +1958,7 @@ mod tests {
         put_request.complete().await.expect("put_object failed");
 
         let mut get_request = client
-            .get_object("test_bucket", "key1", None, None)
+            .get_object("test_bucket", "key1", &GetObjectParams::new())
             .await
             .expect("get_object failed");
 
@@ -1534,11 +2007,11 @@ mod tests {
         put_request.complete().await.unwrap();
 
         // head_object returns storage class
-        let head_result = client.head_object(bucket, key).await.unwrap();
+        let head_result = client.head_object(bucket, key, &HeadObjectParams::new()).await.unwrap();
         assert_eq!(head_result.object.storage_class.as_deref(), storage_class);
 
         // list_objects returns storage class
-        let list_result = client.list_objects(bucket, None, "/", 1, "").await.unwrap();
+        let list_result = client.list_objects(bucket, None, Some("/"), 1, "").await.unwrap();
         assert!(
             matches!(&list_result.objects[..], [object] if object.key == key && object.storage_class.as_deref() == storage_class )
         );
@@ -1557,22 +2030,189 @@ mod tests {
         let head_counter_1 = client.new_counter(Operation::HeadObject);
         let delete_counter_1 = client.new_counter(Operation::DeleteObject);
 
-        let _result = client.head_object(bucket, "key").await;
+        let _result = client.head_object(bucket, "key", &HeadObjectParams::new()).await;
         assert_eq!(1, head_counter_1.count());
         assert_eq!(0, delete_counter_1.count());
 
         let head_counter_2 = client.new_counter(Operation::HeadObject);
         assert_eq!(0, head_counter_2.count());
 
-        let _result = client.head_object(bucket, "key").await;
-        let _result = client.delete_object(bucket, "key").await;
-        let _result = client.delete_object(bucket, "key").await;
-        let _result = client.delete_object(bucket, "key").await;
+        let _result = client.head_object(bucket, "key", &HeadObjectParams::new()).await;
+        let _result = client.delete_object(bucket, "key", &DeleteObjectParams::new()).await;
+        let _result = client.delete_object(bucket, "key", &DeleteObjectParams::new()).await;
+        let _result = client.delete_object(bucket, "key", &DeleteObjectParams::new()).await;
         assert_eq!(2, head_counter_1.count());
         assert_eq!(3, delete_counter_1.count());
         assert_eq!(1, head_counter_2.count());
     }
 
+    #[tokio::test]
+    async fn test_object_exists() {
+        let bucket = "test_bucket";
+        let client = MockClient::new(MockClientConfig {
+            bucket: bucket.to_owned(),
+            part_size: 1024,
+            unordered_list_seed: None,
+            ..Default::default()
+        });
+        let head_counter = client.new_counter(Operation::HeadObject);
+
+        client.add_object("key", MockObject::constant(0u8, 16, ETag::for_tests()));
+        assert_eq!(
+            client.object_exists(bucket, "key").await.unwrap(),
+            ObjectExistence::Exists
+        );
+        assert_eq!(
+            client.object_exists(bucket, "missing").await.unwrap(),
+            ObjectExistence::NotFound
+        );
+        assert_eq!(
+            client.object_exists("other_bucket", "key").await.unwrap(),
+            ObjectExistence::NotFound
+        );
+        assert_eq!(head_counter.count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_object_tagging() {
+        let bucket = "test_bucket";
+        let client = MockClient::new(MockClientConfig {
+            bucket: bucket.to_owned(),
+            part_size: 1024,
+            unordered_list_seed: None,
+            ..Default::default()
+        });
+
+        client.add_object("key", MockObject::constant(0u8, 16, ETag::for_tests()));
+        assert!(client.get_object_tagging(bucket, "key").await.unwrap().is_empty());
+
+        let tags = vec![("project".to_owned(), "mountpoint".to_owned())];
+        client.put_object_tagging(bucket, "key", &tags).await.unwrap();
+        assert_eq!(client.get_object_tagging(bucket, "key").await.unwrap(), tags);
+
+        let too_many_tags: Vec<_> = (0..11).map(|i| (format!("key{i}"), String::new())).collect();
+        assert!(matches!(
+            client.put_object_tagging(bucket, "key", &too_many_tags).await,
+            Err(ObjectClientError::ClientError(_))
+        ));
+        assert_eq!(client.get_object_tagging(bucket, "key").await.unwrap(), tags);
+
+        assert!(matches!(
+            client.get_object_tagging(bucket, "missing").await,
+            Err(ObjectClientError::ServiceError(GetObjectTaggingError::NoSuchKey))
+        ));
+        assert!(matches!(
+            client.put_object_tagging("other_bucket", "key", &tags).await,
+            Err(ObjectClientError::ServiceError(PutObjectTaggingError::NoSuchBucket))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_restore_object() {
+        let bucket = "test_bucket";
+        let client = MockClient::new(MockClientConfig {
+            bucket: bucket.to_owned(),
+            part_size: 1024,
+            unordered_list_seed: None,
+            ..Default::default()
+        });
+
+        let mut object = MockObject::constant(0u8, 16, ETag::for_tests());
+        object.set_storage_class(Some("GLACIER".to_owned()));
+        client.add_object("archived", object);
+        let mut object = MockObject::constant(0u8, 16, ETag::for_tests());
+        object.set_storage_class(Some("DEEP_ARCHIVE".to_owned()));
+        object.set_restored(Some(RestoreStatus::InProgress));
+        client.add_object("restoring", object);
+        client.add_object("standard", MockObject::constant(0u8, 16, ETag::for_tests()));
+
+        let result = client.restore_object(bucket, "archived", 1, RestoreTier::Bulk).await;
+        assert_eq!(result.unwrap(), RestoreObjectResult::RestoreStarted);
+        assert!(client.is_object_restored("archived").unwrap());
+        let result = client.restore_object(bucket, "archived", 2, RestoreTier::Bulk).await;
+        assert_eq!(result.unwrap(), RestoreObjectResult::AlreadyRestored);
+
+        assert!(matches!(
+            client
+                .restore_object(bucket, "restoring", 1, RestoreTier::Standard)
+                .await,
+            Err(ObjectClientError::ServiceError(
+                RestoreObjectError::RestoreAlreadyInProgress
+            ))
+        ));
+        assert!(matches!(
+            client
+                .restore_object(bucket, "standard", 1, RestoreTier::Standard)
+                .await,
+            Err(ObjectClientError::ServiceError(RestoreObjectError::ObjectNotArchived))
+        ));
+        assert!(matches!(
+            client.restore_object(bucket, "missing", 1, RestoreTier::Standard).await,
+            Err(ObjectClientError::ServiceError(RestoreObjectError::NoSuchKey))
+        ));
+        assert!(matches!(
+            client
+                .restore_object("other_bucket", "archived", 1, RestoreTier::Standard)
+                .await,
+            Err(ObjectClientError::ServiceError(RestoreObjectError::NoSuchBucket))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_copy_object() {
+        let bucket = "test_bucket";
+        let client = MockClient::new(MockClientConfig {
+            bucket: bucket.to_owned(),
+            part_size: 1024,
+            unordered_list_seed: None,
+            ..Default::default()
+        });
+
+        let mut object = MockObject::from_bytes(b"hello world", ETag::for_tests());
+        object.set_storage_class(Some("GLACIER_IR".to_owned()));
+        client.add_object("source", object);
+
+        let params = CopyObjectParams::new().storage_class("STANDARD_IA".to_owned());
+        let result = client
+            .copy_object(bucket, "source", bucket, "destination", &params)
+            .await
+            .expect("copy should succeed");
+        assert_eq!(result.etag, ETag::for_tests());
+        assert!(client.contains_key("source"));
+        assert_eq!(
+            client.get_object_storage_class("destination").unwrap().as_deref(),
+            Some("STANDARD_IA")
+        );
+
+        let mut get_request = client
+            .get_object(bucket, "destination", &GetObjectParams::new())
+            .await
+            .expect("should not fail");
+        let mut body = vec![];
+        while let Some(part) = get_request.next().await {
+            body.extend_from_slice(&part.unwrap().1);
+        }
+        assert_eq!(body, b"hello world");
+
+        let err = client
+            .copy_object(bucket, "missing", bucket, "destination", &params)
+            .await
+            .expect_err("copy of missing key should fail");
+        assert!(matches!(
+            err,
+            ObjectClientError::ServiceError(CopyObjectError::NoSuchKey)
+        ));
+
+        let err = client
+            .copy_object(bucket, "source", "other_bucket", "destination", &params)
+            .await
+            .expect_err("copy to missing bucket should fail");
+        assert!(matches!(
+            err,
+            ObjectClientError::ServiceError(CopyObjectError::NoSuchBucket)
+        ));
+    }
+
     #[test_case(PutObjectTrailingChecksums::Enabled; "enabled")]
     #[test_case(PutObjectTrailingChecksums::ReviewOnly; "review only")]
     #[test_case(PutObjectTrailingChecksums::Disabled; "disabled")]
@@ -1649,4 +2289,42 @@ mod tests {
             );
         }
     }
+
+    #[test_case(ChecksumAlgorithm::Crc32, PutObjectTrailingChecksums::Disabled; "crc32 enables checksums")]
+    #[test_case(ChecksumAlgorithm::Crc32, PutObjectTrailingChecksums::ReviewOnly; "crc32 review only")]
+    #[test_case(ChecksumAlgorithm::Sha256, PutObjectTrailingChecksums::Enabled; "sha256")]
+    #[tokio::test]
+    async fn test_checksum_algorithm(algorithm: ChecksumAlgorithm, trailing_checksums: PutObjectTrailingChecksums) {
+        const PART_SIZE: usize = 16 * 1024;
+
+        let body = vec![0xAAu8; PART_SIZE + 10];
+
+        let bucket = "test_bucket";
+        let client = MockClient::new(MockClientConfig {
+            bucket: bucket.to_owned(),
+            part_size: PART_SIZE,
+            unordered_list_seed: None,
+            ..Default::default()
+        });
+
+        let put_params = PutObjectParams::new()
+            .trailing_checksums(trailing_checksums)
+            .checksum_algorithm(Some(algorithm));
+        let mut put_request = client.put_object(bucket, "key1", &put_params).await.unwrap();
+        put_request.write(&body).await.unwrap();
+
+        let expected_checksums: Vec<_> = body
+            .chunks(PART_SIZE)
+            .map(|part| (algorithm == ChecksumAlgorithm::Crc32).then(|| crc32_to_base64(&crc32::checksum(part))))
+            .collect();
+        put_request
+            .review_and_complete(move |review| {
+                assert_eq!(review.checksum_algorithm, Some(algorithm));
+                let checksums: Vec<_> = review.parts.into_iter().map(|part| part.checksum).collect();
+                assert_eq!(checksums, expected_checksums);
+                true
+            })
+            .await
+            .unwrap();
+    }
 }
@@ -1,4 +1,3 @@
-use std::ops::Range;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::Duration;
@@ -11,11 +10,13 @@ use pin_project::pin_project;
 use crate::mock_client::leaky_bucket::LeakyBucket;
 use crate::mock_client::{MockClient, MockClientConfig, MockClientError, MockObject, MockPutObjectRequest};
 use crate::object_client::{
-    DeleteObjectError, DeleteObjectResult, GetBodyPart, GetObjectAttributesError, GetObjectAttributesResult,
-    GetObjectError, GetObjectRequest, HeadObjectError, HeadObjectResult, ListObjectsError, ListObjectsResult,
-    ObjectAttribute, ObjectClient, ObjectClientResult, PutObjectError, PutObjectParams,
+    CopyObjectError, CopyObjectParams, CopyObjectResult, DeleteObjectError, DeleteObjectParams, DeleteObjectResult,
+    DeleteObjectsResult, GetBodyPart, GetObjectAttributesError, GetObjectAttributesResult, GetObjectError,
+    GetObjectParams, GetObjectRequest, GetObjectTaggingError, HeadObjectError, HeadObjectParams, HeadObjectResult,
+    ListObjectsError, ListObjectsResult, ObjectAttribute, ObjectClient, ObjectClientResult, ObjectExistence,
+    PutObjectError, PutObjectParams, PutObjectTaggingError, ResponseMetadata, RestoreObjectError, RestoreObjectResult,
+    RestoreTier,
 };
-use crate::types::ETag;
 
 use super::MockGetObjectRequest;
 
@@ -72,6 +73,14 @@ impl GetObjectRequest for ThroughputGetObjectRequest {
         let this = self.project();
         this.request.increment_read_window(len);
     }
+
+    fn response_metadata(&self) -> Option<ResponseMetadata> {
+        self.request.response_metadata()
+    }
+
+    fn request_charged(&self) -> bool {
+        self.request.request_charged()
+    }
 }
 
 impl Stream for ThroughputGetObjectRequest {
@@ -101,22 +110,43 @@ impl ObjectClient for ThroughputMockClient {
         self.inner.part_size()
     }
 
+    async fn copy_object(
+        &self,
+        source_bucket: &str,
+        source_key: &str,
+        destination_bucket: &str,
+        destination_key: &str,
+        params: &CopyObjectParams,
+    ) -> ObjectClientResult<CopyObjectResult, CopyObjectError, Self::ClientError> {
+        self.inner
+            .copy_object(source_bucket, source_key, destination_bucket, destination_key, params)
+            .await
+    }
+
     async fn delete_object(
         &self,
         bucket: &str,
         key: &str,
+        params: &DeleteObjectParams,
     ) -> ObjectClientResult<DeleteObjectResult, DeleteObjectError, Self::ClientError> {
-        self.inner.delete_object(bucket, key).await
+        self.inner.delete_object(bucket, key, params).await
+    }
+
+    async fn delete_objects(
+        &self,
+        bucket: &str,
+        keys: &[String],
+    ) -> ObjectClientResult<DeleteObjectsResult, DeleteObjectError, Self::ClientError> {
+        self.inner.delete_objects(bucket, keys).await
     }
 
     async fn get_object(
         &self,
         bucket: &str,
         key: &str,
-        range: Option<Range<u64>>,
-        if_match: Option<ETag>,
+        params: &GetObjectParams,
     ) -> ObjectClientResult<Self::GetObjectRequest, GetObjectError, Self::ClientError> {
-        let request = self.inner.get_object(bucket, key, range, if_match).await?;
+        let request = self.inner.get_object(bucket, key, params).await?;
         let rate_limiter = self.rate_limiter.clone();
         Ok(ThroughputGetObjectRequest { request, rate_limiter })
     }
@@ -125,7 +155,7 @@ impl ObjectClient for ThroughputMockClient {
         &self,
         bucket: &str,
         continuation_token: Option<&str>,
-        delimiter: &str,
+        delimiter: Option<&str>,
         max_keys: usize,
         prefix: &str,
     ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, Self::ClientError> {
@@ -138,8 +168,17 @@ impl ObjectClient for ThroughputMockClient {
         &self,
         bucket: &str,
         key: &str,
+        params: &HeadObjectParams,
     ) -> ObjectClientResult<HeadObjectResult, HeadObjectError, Self::ClientError> {
-        self.inner.head_object(bucket, key).await
+        self.inner.head_object(bucket, key, params).await
+    }
+
+    async fn object_exists(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<ObjectExistence, HeadObjectError, Self::ClientError> {
+        self.inner.object_exists(bucket, key).await
     }
 
     async fn put_object(
@@ -163,6 +202,33 @@ impl ObjectClient for ThroughputMockClient {
             .get_object_attributes(bucket, key, max_parts, part_number_marker, object_attributes)
             .await
     }
+
+    async fn get_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<Vec<(String, String)>, GetObjectTaggingError, Self::ClientError> {
+        self.inner.get_object_tagging(bucket, key).await
+    }
+
+    async fn put_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+        tags: &[(String, String)],
+    ) -> ObjectClientResult<(), PutObjectTaggingError, Self::ClientError> {
+        self.inner.put_object_tagging(bucket, key, tags).await
+    }
+
+    async fn restore_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        days: u32,
+        tier: RestoreTier,
+    ) -> ObjectClientResult<RestoreObjectResult, RestoreObjectError, Self::ClientError> {
+        self.inner.restore_object(bucket, key, days, tier).await
+    }
 }
 
 #[cfg(test)]
@@ -173,6 +239,7 @@ mod tests {
     use futures::StreamExt;
 
     use crate::mock_client::MockObject;
+    use crate::types::ETag;
 
     use super::*;
 
@@ -199,7 +266,10 @@ mod tests {
                 let start = Instant::now();
                 let num_bytes = block_on(async move {
                     let mut num_bytes = 0;
-                    let mut get = client.get_object("test_bucket", "testfile", None, None).await.unwrap();
+                    let mut get = client
+                        .get_object("test_bucket", "testfile", &Default::default())
+                        .await
+                        .unwrap();
                     while let Some(part) = get.next().await {
                         let (_offset, part) = part.unwrap();
                         num_bytes += part.len();
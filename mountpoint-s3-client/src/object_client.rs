@@ -1,7 +1,9 @@
 use crate::error_metadata::{ClientErrorMetadata, ProvideErrorMetadata};
 use async_trait::async_trait;
 use auto_impl::auto_impl;
-use futures::Stream;
+use futures::{Stream, StreamExt};
+use mountpoint_s3_crt::common::redact::REDACTED;
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::str::FromStr;
 use std::time::SystemTime;
@@ -81,6 +83,17 @@ pub trait ObjectClient {
     /// can be `None` if the client does not do multi-part operations.
     fn part_size(&self) -> Option<usize>;
 
+    /// Copy an object to a new location in the object store. The source and destination may be in
+    /// different buckets.
+    async fn copy_object(
+        &self,
+        source_bucket: &str,
+        source_key: &str,
+        destination_bucket: &str,
+        destination_key: &str,
+        params: &CopyObjectParams,
+    ) -> ObjectClientResult<CopyObjectResult, CopyObjectError, Self::ClientError>;
+
     /// Delete a single object from the object store.
     ///
     /// DeleteObject will succeed even if the object within the bucket does not exist.
@@ -88,35 +101,131 @@ pub trait ObjectClient {
         &self,
         bucket: &str,
         key: &str,
+        params: &DeleteObjectParams,
     ) -> ObjectClientResult<DeleteObjectResult, DeleteObjectError, Self::ClientError>;
 
+    /// Delete multiple objects from the object store.
+    ///
+    /// Keys that could not be deleted are reported in the result rather than failing the whole
+    /// request. As with DeleteObject, deleting a key that does not exist is considered a success.
+    ///
+    /// The default implementation calls [`delete_object`](ObjectClient::delete_object) for each
+    /// key, one at a time. A [`ServiceError`](ObjectClientError::ServiceError) for a key is
+    /// reported in the result, and a missing bucket or any other error fails the whole request.
+    async fn delete_objects(
+        &self,
+        bucket: &str,
+        keys: &[String],
+    ) -> ObjectClientResult<DeleteObjectsResult, DeleteObjectError, Self::ClientError> {
+        let mut result = DeleteObjectsResult::default();
+        for key in keys {
+            match self.delete_object(bucket, key, &DeleteObjectParams::new()).await {
+                Ok(_) => result.deleted.push(key.clone()),
+                Err(e @ ObjectClientError::ServiceError(DeleteObjectError::NoSuchBucket)) => return Err(e),
+                Err(ObjectClientError::ServiceError(e)) => result.errors.push((key.clone(), e)),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(result)
+    }
+
     /// Get an object from the object store. Returns a stream of body parts of the object. Parts are
     /// guaranteed to be returned by the stream in order and contiguously.
+    ///
+    /// See [GetObjectParams] for how to request a range of the object, or to make the request
+    /// conditional on the object's ETag.
     async fn get_object(
         &self,
         bucket: &str,
         key: &str,
-        range: Option<Range<u64>>,
-        if_match: Option<ETag>,
+        params: &GetObjectParams,
     ) -> ObjectClientResult<Self::GetObjectRequest, GetObjectError, Self::ClientError>;
 
-    /// List the objects in a bucket under a given prefix
+    /// List the objects in a bucket under a given prefix. With a `delimiter`, keys that contain the
+    /// delimiter after the prefix are grouped into common prefixes. Without one, the request has
+    /// no `delimiter` parameter and every key under the prefix is listed.
+    ///
+    /// To continue a truncated listing, pass the `next_continuation_token` of the previous page
+    /// along with the same `delimiter` and `prefix` as the request that returned it. A token used
+    /// with different parameters can return a page that belongs to neither listing, so
+    /// [S3CrtClient](crate::S3CrtClient) panics in debug builds if it sees one.
     async fn list_objects(
         &self,
         bucket: &str,
         continuation_token: Option<&str>,
-        delimiter: &str,
+        delimiter: Option<&str>,
         max_keys: usize,
         prefix: &str,
     ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, Self::ClientError>;
 
+    /// List the objects in a bucket under a given prefix, like
+    /// [`list_objects`](ObjectClient::list_objects), but pass each object in the page to `handler`
+    /// instead of collecting them. The `objects` of the returned result are empty.
+    ///
+    /// `handler` can be called before the whole page has been read, so if the request fails
+    /// partway through the page, for example because the rest of the response can't be parsed,
+    /// `handler` may already have seen some of its objects when the error is returned. Callers that
+    /// act on each object should be ready to discard what they saw, or to see it again when the
+    /// page is retried.
+    ///
+    /// The default implementation calls [`list_objects`](ObjectClient::list_objects) and passes
+    /// each of the returned objects to `handler`. Clients override it to avoid the intermediate
+    /// vector when listing many objects.
+    async fn list_objects_with_handler(
+        &self,
+        bucket: &str,
+        continuation_token: Option<&str>,
+        delimiter: Option<&str>,
+        max_keys: usize,
+        prefix: &str,
+        handler: &mut (dyn FnMut(&ObjectInfo) + Send),
+    ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, Self::ClientError> {
+        let mut result = self
+            .list_objects(bucket, continuation_token, delimiter, max_keys, prefix)
+            .await?;
+        for object in result.objects.drain(..) {
+            handler(&object);
+        }
+        Ok(result)
+    }
+
     /// Retrieve object metadata without retrieving the object contents
     async fn head_object(
         &self,
         bucket: &str,
         key: &str,
+        params: &HeadObjectParams,
     ) -> ObjectClientResult<HeadObjectResult, HeadObjectError, Self::ClientError>;
 
+    /// Retrieve the metadata of many objects, with up to `concurrency` (at least one) requests in
+    /// flight at once. The results are in the same order as `keys`, and a request that fails, for
+    /// example because the object doesn't exist, doesn't stop the others.
+    ///
+    /// The default implementation calls [`head_object`](ObjectClient::head_object) for each key.
+    async fn head_objects(
+        &self,
+        bucket: &str,
+        keys: &[&str],
+        params: &HeadObjectParams,
+        concurrency: usize,
+    ) -> Vec<ObjectClientResult<HeadObjectResult, HeadObjectError, Self::ClientError>> {
+        futures::stream::iter(keys)
+            .map(|key| self.head_object(bucket, key, params))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Check whether an object exists, without retrieving its contents or metadata.
+    ///
+    /// A denied request returns [`ObjectExistence::AccessDenied`] rather than an error. See its
+    /// documentation for why that doesn't mean the object exists.
+    async fn object_exists(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<ObjectExistence, HeadObjectError, Self::ClientError>;
+
     /// Put an object into the object store. Returns a [PutObjectRequest] for callers
     /// to provide the content of the object.
     async fn put_object(
@@ -126,6 +235,20 @@ pub trait ObjectClient {
         params: &PutObjectParams,
     ) -> ObjectClientResult<Self::PutObjectRequest, PutObjectError, Self::ClientError>;
 
+    /// Put an empty object, such as a directory marker with a key ending in `/`.
+    ///
+    /// The default implementation completes a [`put_object`](ObjectClient::put_object) request
+    /// without writing to it. Clients can override it with something cheaper, like a single
+    /// PutObject request instead of a multipart upload.
+    async fn put_empty_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        params: &PutObjectParams,
+    ) -> ObjectClientResult<PutObjectResult, PutObjectError, Self::ClientError> {
+        self.put_object(bucket, key, params).await?.complete().await
+    }
+
     /// Retrieves all the metadata from an object without returning the object contents.
     async fn get_object_attributes(
         &self,
@@ -135,8 +258,53 @@ pub trait ObjectClient {
         part_number_marker: Option<usize>,
         object_attributes: &[ObjectAttribute],
     ) -> ObjectClientResult<GetObjectAttributesResult, GetObjectAttributesError, Self::ClientError>;
+
+    /// Get the tags of an object, as key-value pairs.
+    ///
+    /// The default implementation fails with [GetObjectTaggingError::NotSupported].
+    async fn get_object_tagging(
+        &self,
+        _bucket: &str,
+        _key: &str,
+    ) -> ObjectClientResult<Vec<(String, String)>, GetObjectTaggingError, Self::ClientError> {
+        Err(ObjectClientError::ServiceError(GetObjectTaggingError::NotSupported))
+    }
+
+    /// Replace the tags of an object with the given key-value pairs. An object can have at most
+    /// [MAX_OBJECT_TAGS] tags.
+    ///
+    /// The default implementation fails with [PutObjectTaggingError::NotSupported].
+    async fn put_object_tagging(
+        &self,
+        _bucket: &str,
+        _key: &str,
+        _tags: &[(String, String)],
+    ) -> ObjectClientResult<(), PutObjectTaggingError, Self::ClientError> {
+        Err(ObjectClientError::ServiceError(PutObjectTaggingError::NotSupported))
+    }
+
+    /// Restore a temporary copy of an archived object, such as one in the Glacier Flexible
+    /// Retrieval or Deep Archive storage classes, so that it can be read. The copy expires after
+    /// the given number of days. The retrieval tier determines how long the restore takes.
+    ///
+    /// A restore can take minutes to hours to complete. Use [`head_object`](ObjectClient::head_object)
+    /// to check the [restore status](HeadObjectResult::restore_status) of the object.
+    ///
+    /// The default implementation fails with [RestoreObjectError::NotSupported].
+    async fn restore_object(
+        &self,
+        _bucket: &str,
+        _key: &str,
+        _days: u32,
+        _tier: RestoreTier,
+    ) -> ObjectClientResult<RestoreObjectResult, RestoreObjectError, Self::ClientError> {
+        Err(ObjectClientError::ServiceError(RestoreObjectError::NotSupported))
+    }
 }
 
+/// The maximum number of tags an object can have
+pub const MAX_OBJECT_TAGS: usize = 10;
+
 /// The top-level error type returned by calls to an [`ObjectClient`].
 ///
 /// Errors that are explicitly modeled on a per-request-type basis are [`ServiceError`]s. Other
@@ -178,6 +346,130 @@ where
 /// Shorthand type for the result of an object client request
 pub type ObjectClientResult<T, S, C> = Result<T, ObjectClientError<S, C>>;
 
+/// Parameters to a [`get_object`](ObjectClient::get_object) request
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct GetObjectParams {
+    /// The range of bytes of the object to return. Like other Rust ranges, it includes
+    /// `range.start` but excludes `range.end`, so `0..10` returns the first ten bytes of the
+    /// object. (The HTTP `Range` header is inclusive at both ends, so this range is sent to S3 as
+    /// `bytes=0-9`.)
+    pub range: Option<Range<u64>>,
+    /// Return the object only if its ETag matches this one, and otherwise fail with
+    /// [GetObjectError::PreconditionFailed]
+    pub if_match: Option<ETag>,
+    /// Return the object only if its ETag doesn't match this one, and otherwise fail with
+    /// [GetObjectError::NotModified]
+    pub if_none_match: Option<ETag>,
+    /// Return the object only if it was modified after this time, and otherwise fail with
+    /// [GetObjectError::NotModified]. S3 ignores this condition if `if_none_match` is also set and
+    /// the ETag doesn't match.
+    pub if_modified_since: Option<OffsetDateTime>,
+    /// Return the object only if it wasn't modified after this time, and otherwise fail with
+    /// [GetObjectError::PreconditionFailed]. S3 ignores this condition if `if_match` is also set
+    /// and the ETag matches.
+    pub if_unmodified_since: Option<OffsetDateTime>,
+    /// Decode the body if the response has a `Content-Encoding` header of `gzip`, `aws-chunked`,
+    /// or both, so the returned body parts are the decoded bytes and their offsets are positions in
+    /// the decoded body. S3 normally removes `aws-chunked` framing on upload, but objects stored
+    /// with it intact (for example by some S3-compatible services) are de-framed, and a warning is
+    /// logged when they're read without this set. Bodies with other encodings are returned
+    /// unchanged. A body that isn't validly encoded fails with
+    /// [S3RequestError::ContentDecodingError](crate::S3RequestError::ContentDecodingError).
+    ///
+    /// Part of an encoded body can't be decoded on its own, since a range of an `aws-chunked` body
+    /// starts part way through a chunk and a range of a gzip body part way through the stream, so
+    /// this can't be combined with `range` or `part_number`: requests that set both fail with
+    /// [S3RequestError::ConstructionFailure](crate::S3RequestError::ConstructionFailure). Ranges of
+    /// an encoded object read without this hold the encoded bytes. With backpressure, grow the read window by the length of the decoded
+    /// parts consumed, as for any other request; the client converts it to the encoded bytes the
+    /// CRT counts.
+    pub decode_content_encoding: bool,
+    /// Return this version of the object instead of the current one, for buckets with versioning
+    /// enabled. Fails with [GetObjectError::NoSuchKey] if the version doesn't exist.
+    pub version_id: Option<String>,
+    /// Return only this part of an object uploaded with a multipart upload, numbered from 1. S3
+    /// treats an object uploaded in a single request as having one part. The offsets of the
+    /// returned body parts are positions within the part. Can't be combined with `range`.
+    pub part_number: Option<u32>,
+    /// Ask S3 to return the object's checksum, which the
+    /// [S3CrtClient](crate::S3CrtClient) exposes with `S3GetObjectRequest::checksum`. Unlike
+    /// [S3ClientConfig::checksum_mode](crate::config::S3ClientConfig::checksum_mode), this doesn't
+    /// validate the body against the checksum, so it can be used with ranged reads.
+    pub checksum_mode: bool,
+    /// Send this value in the `x-amz-request-payer` header instead of the client-wide
+    /// [S3ClientConfig::request_payer](crate::config::S3ClientConfig::request_payer), e.g. to read
+    /// from a requester-pays bucket with a client that also accesses normal buckets.
+    pub request_payer: Option<String>,
+}
+
+impl GetObjectParams {
+    /// Create a default [GetObjectParams].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the range of bytes to return.
+    pub fn range(mut self, value: Option<Range<u64>>) -> Self {
+        self.range = value;
+        self
+    }
+
+    /// Set the ETag the object must match.
+    pub fn if_match(mut self, value: Option<ETag>) -> Self {
+        self.if_match = value;
+        self
+    }
+
+    /// Set the ETag the object must not match.
+    pub fn if_none_match(mut self, value: Option<ETag>) -> Self {
+        self.if_none_match = value;
+        self
+    }
+
+    /// Set the time the object must have been modified after.
+    pub fn if_modified_since(mut self, value: Option<OffsetDateTime>) -> Self {
+        self.if_modified_since = value;
+        self
+    }
+
+    /// Set the time the object must not have been modified after.
+    pub fn if_unmodified_since(mut self, value: Option<OffsetDateTime>) -> Self {
+        self.if_unmodified_since = value;
+        self
+    }
+
+    /// Set whether to decode gzip-encoded and `aws-chunked` response bodies.
+    pub fn decode_content_encoding(mut self, value: bool) -> Self {
+        self.decode_content_encoding = value;
+        self
+    }
+
+    /// Set the version of the object to return.
+    pub fn version_id(mut self, value: Option<String>) -> Self {
+        self.version_id = value;
+        self
+    }
+
+    /// Set the part of the object to return.
+    pub fn part_number(mut self, value: Option<u32>) -> Self {
+        self.part_number = value;
+        self
+    }
+
+    /// Set whether to ask S3 for the object's checksum.
+    pub fn checksum_mode(mut self, value: bool) -> Self {
+        self.checksum_mode = value;
+        self
+    }
+
+    /// Set the request payer to send instead of the client-wide one.
+    pub fn request_payer(mut self, value: Option<String>) -> Self {
+        self.request_payer = value;
+        self
+    }
+}
+
 /// Errors returned by a [`get_object`](ObjectClient::get_object) request
 #[derive(Debug, Error, PartialEq, Eq)]
 #[non_exhaustive]
@@ -190,6 +482,52 @@ pub enum GetObjectError {
 
     #[error("At least one of the preconditions specified did not hold")]
     PreconditionFailed,
+
+    /// The object's ETag matched [GetObjectParams::if_none_match], so it hasn't changed
+    #[error("The object was not modified")]
+    NotModified,
+
+    /// The requested range was empty, or started at or after the end of the object
+    #[error("The requested range is not satisfiable")]
+    InvalidRange,
+
+    /// The object is in an archival storage class or access tier, and must be restored with a
+    /// RestoreObject request before it can be read
+    #[error("The object must be restored before it can be read (storage class {storage_class:?}, access tier {access_tier:?})")]
+    InvalidObjectState {
+        storage_class: Option<String>,
+        access_tier: Option<String>,
+    },
+}
+
+/// Metadata about the response to a successful request
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ResponseMetadata {
+    /// HTTP status of the response, e.g. 200, or 206 for a GetObject request for a range of the
+    /// object. `None` if the result wasn't built from an HTTP response.
+    pub response_status: Option<i32>,
+
+    /// Breakdown of the time the last request to S3 spent in each phase, if available. Requests
+    /// that stream their response, like GetObject, return their metadata before the request
+    /// finishes, so don't include timings.
+    pub request_timings: Option<RequestTimings>,
+
+    /// Metrics aggregated over all the requests to S3 that produced this response, such as how
+    /// many of them were retried. Like `request_timings`, these are empty for requests that stream
+    /// their response.
+    pub metrics: MetaRequestMetrics,
+}
+
+impl ResponseMetadata {
+    /// Create metadata for a response with the given HTTP status.
+    pub fn new(response_status: i32) -> Self {
+        Self {
+            response_status: Some(response_status),
+            request_timings: None,
+            metrics: Default::default(),
+        }
+    }
 }
 
 /// Result of a [`list_objects`](ObjectClient::list_objects) request
@@ -205,6 +543,67 @@ pub struct ListObjectsResult {
 
     /// If present, the continuation token to use to query more results.
     pub next_continuation_token: Option<String>,
+
+    /// The prefix S3 says it applied to the listing, if the response included one. Useful to
+    /// confirm that the request wasn't rewritten along the way, for example by a proxy.
+    pub prefix: Option<String>,
+
+    /// The delimiter S3 says it applied to the listing, if the response included one
+    pub delimiter: Option<String>,
+
+    /// The maximum number of keys S3 says it would return in this page, if the response included it
+    pub max_keys: Option<usize>,
+
+    /// The encoding S3 says it applied to keys in the response, if any. The client decodes
+    /// URL-encoded (`url`) keys and prefixes, so the keys, common prefixes, and echoed parameters
+    /// in this result are never encoded.
+    pub encoding_type: Option<String>,
+
+    /// Whether S3 confirmed that the requester was charged for this request to a requester-pays
+    /// bucket
+    pub request_charged: bool,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+impl ListObjectsResult {
+    /// Merge the objects and common prefixes of this page into a single list sorted by key, the way
+    /// a directory listing presents files and subdirectories together.
+    ///
+    /// Keys are sorted in UTF-8 binary order, which is the order general purpose buckets list
+    /// them in. Directory buckets don't list keys in order, so their entries are sorted too, but
+    /// only within this page: entries in later pages can sort before them.
+    pub fn into_entries(self) -> Vec<ListEntry> {
+        let mut entries: Vec<ListEntry> = self
+            .objects
+            .into_iter()
+            .map(ListEntry::Object)
+            .chain(self.common_prefixes.into_iter().map(ListEntry::Prefix))
+            .collect();
+        entries.sort_by(|a, b| a.key().cmp(b.key()));
+        entries
+    }
+}
+
+/// An entry of a listing, from [ListObjectsResult::into_entries].
+#[derive(Debug, Clone)]
+pub enum ListEntry {
+    /// An object
+    Object(ObjectInfo),
+
+    /// A common prefix, which rolls up the objects that share it
+    Prefix(String),
+}
+
+impl ListEntry {
+    /// The key of the object, or the common prefix.
+    pub fn key(&self) -> &str {
+        match self {
+            ListEntry::Object(object) => &object.key,
+            ListEntry::Prefix(prefix) => prefix,
+        }
+    }
 }
 
 /// Errors returned by a [`list_objects`](ObjectClient::list_objects) request
@@ -213,6 +612,10 @@ pub struct ListObjectsResult {
 pub enum ListObjectsError {
     #[error("The bucket does not exist")]
     NoSuchBucket,
+
+    /// The request was sent to the wrong region. `expected` is the region the bucket is in.
+    #[error("The bucket is in region {expected}")]
+    WrongRegion { expected: String },
 }
 
 /// Result of a [`head_object`](ObjectClient::head_object) request
@@ -224,6 +627,114 @@ pub struct HeadObjectResult {
 
     /// Object metadata
     pub object: ObjectInfo,
+
+    /// Checksums of the object, if S3 returned any. S3 only returns them if
+    /// [HeadObjectParams::checksum_mode] is set.
+    pub checksum: Checksum,
+
+    /// Whether S3 confirmed that the requester was charged for this request to a requester-pays
+    /// bucket
+    pub request_charged: bool,
+
+    /// User-defined metadata of the object, from the `x-amz-meta-*` headers with the prefix
+    /// stripped
+    pub user_metadata: HashMap<String, String>,
+
+    /// The media type of the object, from the `Content-Type` header, if S3 returned one
+    pub content_type: Option<String>,
+
+    /// The other standard headers describing how to present the object
+    pub content_headers: ContentHeaders,
+
+    /// When a lifecycle rule of the bucket will expire the object, from the `x-amz-expiration`
+    /// header. `None` if no rule applies to the object, or if the header is malformed.
+    pub expiration: Option<ObjectExpiration>,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// When a lifecycle rule of the bucket will expire an object, from the `x-amz-expiration` header of
+/// a GetObject or HeadObject response.
+///
+/// See [Managing your storage
+/// lifecycle](https://docs.aws.amazon.com/AmazonS3/latest/userguide/object-lifecycle-mgmt.html) in
+/// the *Amazon S3 User Guide* for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ObjectExpiration {
+    /// The time the object expires, after which S3 will delete it
+    pub expiry: OffsetDateTime,
+
+    /// The ID of the lifecycle rule that expires the object, URL-decoded
+    pub rule_id: String,
+}
+
+/// Standard HTTP headers that S3 stores with an object when it's uploaded and returns with it,
+/// describing how to present its content. Each is `None` if the object doesn't have it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ContentHeaders {
+    /// The encodings applied to the object, from the `Content-Encoding` header, for example `gzip`
+    pub content_encoding: Option<String>,
+
+    /// The caching behavior of the object, from the `Cache-Control` header
+    pub cache_control: Option<String>,
+
+    /// How to present the object, from the `Content-Disposition` header, for example as an
+    /// attachment with a file name
+    pub content_disposition: Option<String>,
+
+    /// The language of the object, from the `Content-Language` header
+    pub content_language: Option<String>,
+
+    /// When caches should consider the object stale, from the `Expires` header. This is the
+    /// header's value as S3 stored it, which isn't always a valid date. It's unrelated to
+    /// expiration by a lifecycle rule, which is [HeadObjectResult::expiration].
+    pub expires: Option<String>,
+}
+
+/// Parameters to a [`head_object`](ObjectClient::head_object) request
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct HeadObjectParams {
+    /// Return the metadata of this version of the object instead of the current one, for buckets
+    /// with versioning enabled
+    pub version_id: Option<String>,
+
+    /// Ask S3 to return the object's checksum, in [HeadObjectResult::checksum]
+    pub checksum_mode: bool,
+
+    /// Send this value in the `x-amz-request-payer` header instead of the client-wide
+    /// [S3ClientConfig::request_payer](crate::config::S3ClientConfig::request_payer)
+    pub request_payer: Option<String>,
+}
+
+impl HeadObjectParams {
+    /// Create a default [HeadObjectParams].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the version of the object to return metadata for.
+    pub fn version_id(mut self, value: Option<String>) -> Self {
+        self.version_id = value;
+        self
+    }
+
+    /// Set whether to ask S3 for the object's checksum. S3 only returns it for objects uploaded
+    /// with a checksum, and requires `kms:Decrypt` permission to return it for objects encrypted
+    /// with SSE-KMS.
+    pub fn checksum_mode(mut self, value: bool) -> Self {
+        self.checksum_mode = value;
+        self
+    }
+
+    /// Set the request payer to send instead of the client-wide one.
+    pub fn request_payer(mut self, value: Option<String>) -> Self {
+        self.request_payer = value;
+        self
+    }
 }
 
 /// Errors returned by a [`head_object`](ObjectClient::head_object) request
@@ -233,6 +744,79 @@ pub enum HeadObjectError {
     /// Note that HeadObject cannot distinguish between NoSuchBucket and NoSuchKey errors
     #[error("The object was not found")]
     NotFound,
+
+    /// HeadObject responses have no body, so a 403 carries no further detail about why access was
+    /// denied. The metadata has the response status and request IDs.
+    #[error("Access to the object was denied")]
+    AccessDenied(ClientErrorMetadata),
+}
+
+/// Parameters to a [`copy_object`](ObjectClient::copy_object) request
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct CopyObjectParams {
+    /// Whether the new object keeps the metadata of the source object
+    pub metadata_directive: MetadataDirective,
+    /// Storage class of the new object. If not set, the new object uses the default storage class
+    /// rather than the storage class of the source object.
+    pub storage_class: Option<String>,
+}
+
+impl CopyObjectParams {
+    /// Create a default [CopyObjectParams].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the metadata directive.
+    pub fn metadata_directive(mut self, value: MetadataDirective) -> Self {
+        self.metadata_directive = value;
+        self
+    }
+
+    /// Set the storage class.
+    pub fn storage_class(mut self, value: String) -> Self {
+        self.storage_class = Some(value);
+        self
+    }
+}
+
+/// Whether a [`copy_object`](ObjectClient::copy_object) request copies the metadata of the source
+/// object
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataDirective {
+    /// The new object keeps the metadata of the source object
+    #[default]
+    Copy,
+    /// The metadata of the source object is replaced with the metadata provided in the request.
+    /// This is required to copy an object onto itself without changing its storage class.
+    Replace,
+}
+
+/// Result of a [`copy_object`](ObjectClient::copy_object) request
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CopyObjectResult {
+    /// ETag of the new object
+    pub etag: ETag,
+
+    /// Time the new object was created
+    pub last_modified: OffsetDateTime,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Errors returned by a [`copy_object`](ObjectClient::copy_object) request
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CopyObjectError {
+    /// Either the source or the destination bucket does not exist
+    #[error("The bucket does not exist")]
+    NoSuchBucket,
+
+    #[error("The source key does not exist")]
+    NoSuchKey,
 }
 
 /// Result of a [`delete_object`](ObjectClient::delete_object) request
@@ -241,7 +825,42 @@ pub enum HeadObjectError {
 // TODO: Populate this struct with return fields from the S3 API, e.g., version id, delete marker.
 #[derive(Debug)]
 #[non_exhaustive]
-pub struct DeleteObjectResult {}
+pub struct DeleteObjectResult {
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Parameters to a [`delete_object`](ObjectClient::delete_object) request
+#[derive(Debug, Default, Clone)]
+#[non_exhaustive]
+pub struct DeleteObjectParams {
+    /// Permanently delete this version of the object, for buckets with versioning enabled. Without
+    /// it, deleting from a versioned bucket only adds a delete marker.
+    pub version_id: Option<String>,
+    /// Delete the object only if its ETag matches this one, and otherwise fail with
+    /// [DeleteObjectError::PreconditionFailed], so that an object that changed since it was read
+    /// isn't deleted
+    pub if_match: Option<ETag>,
+}
+
+impl DeleteObjectParams {
+    /// Create a default [DeleteObjectParams].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the version of the object to delete.
+    pub fn version_id(mut self, value: Option<String>) -> Self {
+        self.version_id = value;
+        self
+    }
+
+    /// Set the ETag the object must match.
+    pub fn if_match(mut self, value: Option<ETag>) -> Self {
+        self.if_match = value;
+        self
+    }
+}
 
 /// Errors returned by a [`delete_object`](ObjectClient::delete_object) request
 #[derive(Debug, Error, PartialEq, Eq)]
@@ -249,10 +868,36 @@ pub struct DeleteObjectResult {}
 pub enum DeleteObjectError {
     #[error("The bucket does not exist")]
     NoSuchBucket,
+
+    /// A single key of a [`delete_objects`](ObjectClient::delete_objects) request could not be
+    /// deleted
+    #[error("The object could not be deleted: {code}: {message}")]
+    KeyError { code: String, message: String },
+
+    /// The request of a [`delete_objects`](ObjectClient::delete_objects) operation that included
+    /// this key failed as a whole, after an earlier request of the same operation succeeded
+    #[error("The request to delete the object failed: {0}")]
+    RequestFailed(String),
+
+    /// The object's ETag didn't match [DeleteObjectParams::if_match]
+    #[error("At least one of the preconditions specified did not hold")]
+    PreconditionFailed,
+}
+
+/// Result of a [`delete_objects`](ObjectClient::delete_objects) request
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct DeleteObjectsResult {
+    /// Keys of the objects that were deleted, including keys that did not exist
+    pub deleted: Vec<String>,
+
+    /// Keys of the objects that could not be deleted, with the error for each
+    pub errors: Vec<(String, DeleteObjectError)>,
 }
 
 /// Result of a [`get_object_attributes`](ObjectClient::get_object_attributes) request
 #[derive(Debug, Default)]
+#[non_exhaustive]
 pub struct GetObjectAttributesResult {
     /// ETag of the object
     pub etag: Option<String>,
@@ -268,6 +913,9 @@ pub struct GetObjectAttributesResult {
 
     /// Object size
     pub object_size: Option<u64>,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
 }
 
 /// Errors returned by a [`get_object_attributes`](ObjectClient::get_object_attributes) request
@@ -281,12 +929,131 @@ pub enum GetObjectAttributesError {
     NoSuchKey,
 }
 
+/// Errors returned by a [`get_object_tagging`](ObjectClient::get_object_tagging) request
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GetObjectTaggingError {
+    #[error("The bucket does not exist")]
+    NoSuchBucket,
+
+    #[error("The key does not exist")]
+    NoSuchKey,
+
+    /// The client doesn't implement GetObjectTagging
+    #[error("The client does not support object tagging")]
+    NotSupported,
+}
+
+/// Errors returned by a [`put_object_tagging`](ObjectClient::put_object_tagging) request
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PutObjectTaggingError {
+    #[error("The bucket does not exist")]
+    NoSuchBucket,
+
+    #[error("The key does not exist")]
+    NoSuchKey,
+
+    /// The client doesn't implement PutObjectTagging
+    #[error("The client does not support object tagging")]
+    NotSupported,
+}
+
+/// Result of an [`object_exists`](ObjectClient::object_exists) request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ObjectExistence {
+    /// The object exists
+    Exists,
+
+    /// The object does not exist
+    NotFound,
+
+    /// The request to check for the object was denied, so whether it exists is unknown.
+    ///
+    /// S3 only reveals that an object is missing to callers with `s3:ListBucket` permission on the
+    /// bucket. Without it, a request for a missing object is denied just like a request for an
+    /// object the caller can't read, so this result doesn't mean that the object exists.
+    AccessDenied,
+}
+
+impl ObjectExistence {
+    /// Whether the object is known to exist
+    pub fn exists(&self) -> bool {
+        matches!(self, ObjectExistence::Exists)
+    }
+}
+
+/// Result of a [`restore_object`](ObjectClient::restore_object) request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RestoreObjectResult {
+    /// The service accepted the request and started restoring the object. The object is not
+    /// readable until the restore completes.
+    RestoreStarted,
+
+    /// The object was already restored. The service updated the expiry of the restored copy to
+    /// the requested number of days.
+    AlreadyRestored,
+}
+
+/// Errors returned by a [`restore_object`](ObjectClient::restore_object) request
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RestoreObjectError {
+    #[error("The bucket does not exist")]
+    NoSuchBucket,
+
+    #[error("The key does not exist")]
+    NoSuchKey,
+
+    #[error("A restore of the object is already in progress")]
+    RestoreAlreadyInProgress,
+
+    #[error("The object is not in an archival storage class")]
+    ObjectNotArchived,
+
+    /// The client doesn't implement RestoreObject
+    #[error("The client does not support restoring objects")]
+    NotSupported,
+}
+
+/// The retrieval tier for a [`restore_object`](ObjectClient::restore_object) request.
+///
+/// See [Understanding archive retrieval
+/// options](https://docs.aws.amazon.com/AmazonS3/latest/userguide/restoring-objects-retrieval-options.html)
+/// in the *Amazon S3 User Guide* for how long each tier takes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RestoreTier {
+    #[default]
+    Standard,
+    Bulk,
+    /// Not available for objects in the Deep Archive storage class
+    Expedited,
+}
+
+impl RestoreTier {
+    /// The name of the tier in S3 requests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RestoreTier::Standard => "Standard",
+            RestoreTier::Bulk => "Bulk",
+            RestoreTier::Expedited => "Expedited",
+        }
+    }
+}
+
 /// Parameters to a [`put_object`](ObjectClient::put_object) request
 #[derive(Debug, Default, Clone)]
 #[non_exhaustive]
 pub struct PutObjectParams {
-    /// Enable Crc32c trailing checksums.
+    /// Enable trailing checksums.
     pub trailing_checksums: PutObjectTrailingChecksums,
+    /// Algorithm of the trailing checksums. If not set, Crc32c is used. If set while
+    /// `trailing_checksums` is [Disabled](PutObjectTrailingChecksums::Disabled), trailing checksums
+    /// are enabled.
+    pub checksum_algorithm: Option<ChecksumAlgorithm>,
     /// Storage class to be used when creating new S3 object
     pub storage_class: Option<String>,
     /// The server-side encryption algorithm to be used for this object in Amazon S3 (for example, AES256, aws:kms, aws:kms:dsse)
@@ -294,6 +1061,16 @@ pub struct PutObjectParams {
     /// If `server_side_encryption` has a valid value of aws:kms or aws:kms:dsse, this value may be used to specify AWS KMS key ID to be used
     /// when creating new S3 object
     pub ssekms_key_id: Option<String>,
+    /// Type of checksum S3 should compute for the object when it is uploaded in multiple parts
+    pub checksum_type: Option<ChecksumType>,
+    /// Server-side encryption to be used when creating new S3 object. Can't be combined with
+    /// `server_side_encryption` or `ssekms_key_id`.
+    pub sse: Option<ServerSideEncryption>,
+    /// Use an S3 Bucket Key for SSE-KMS encryption of the new object, which reduces the number of
+    /// requests S3 makes to AWS KMS. Only valid with `aws:kms` encryption.
+    pub bucket_key_enabled: bool,
+    /// Object Lock retention and legal hold to apply to the new object
+    pub object_lock: Option<ObjectLock>,
 }
 
 impl PutObjectParams {
@@ -302,12 +1079,18 @@ impl PutObjectParams {
         Self::default()
     }
 
-    /// Set Crc32c trailing checksums.
+    /// Set trailing checksums.
     pub fn trailing_checksums(mut self, value: PutObjectTrailingChecksums) -> Self {
         self.trailing_checksums = value;
         self
     }
 
+    /// Set the algorithm of the trailing checksums.
+    pub fn checksum_algorithm(mut self, value: Option<ChecksumAlgorithm>) -> Self {
+        self.checksum_algorithm = value;
+        self
+    }
+
     /// Set the storage class.
     pub fn storage_class(mut self, value: String) -> Self {
         self.storage_class = Some(value);
@@ -325,9 +1108,147 @@ impl PutObjectParams {
         self.ssekms_key_id = value;
         self
     }
+
+    /// Set the checksum type for multi-part uploads.
+    pub fn checksum_type(mut self, value: Option<ChecksumType>) -> Self {
+        self.checksum_type = value;
+        self
+    }
+
+    /// Set the server-side encryption to be used for the new object.
+    pub fn sse(mut self, value: Option<ServerSideEncryption>) -> Self {
+        self.sse = value;
+        self
+    }
+
+    /// Set whether to use an S3 Bucket Key for SSE-KMS encryption of the new object. The request
+    /// fails before it's sent if this is set without `aws:kms` encryption.
+    pub fn bucket_key_enabled(mut self, value: bool) -> Self {
+        self.bucket_key_enabled = value;
+        self
+    }
+
+    /// Set the Object Lock retention and legal hold to apply to the new object.
+    pub fn object_lock(mut self, value: Option<ObjectLock>) -> Self {
+        self.object_lock = value;
+        self
+    }
+
+    /// The trailing checksums to use for this upload and their algorithm, after applying the
+    /// defaults for unset options.
+    pub(crate) fn upload_checksums(&self) -> (PutObjectTrailingChecksums, ChecksumAlgorithm) {
+        let trailing_checksums = match (self.trailing_checksums, self.checksum_algorithm) {
+            (PutObjectTrailingChecksums::Disabled, Some(_)) => PutObjectTrailingChecksums::Enabled,
+            (trailing_checksums, _) => trailing_checksums,
+        };
+        (
+            trailing_checksums,
+            self.checksum_algorithm.unwrap_or(ChecksumAlgorithm::Crc32c),
+        )
+    }
+}
+
+/// Server-side encryption for a new object.
+///
+/// See [Protecting data with server-side encryption](https://docs.aws.amazon.com/AmazonS3/latest/userguide/serv-side-encryption.html)
+/// in the *Amazon S3 User Guide* for more details.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ServerSideEncryption {
+    /// Server-side encryption with Amazon S3 managed keys (SSE-S3)
+    S3,
+    /// Server-side encryption with AWS KMS keys (SSE-KMS)
+    Kms {
+        /// The ID of the KMS key to use. If not set, S3 uses the AWS managed key for S3.
+        key_id: Option<String>,
+        /// The encryption context to use, as a JSON object of key-value pairs. The client
+        /// base64-encodes it before sending it to S3.
+        context: Option<String>,
+    },
+    /// Server-side encryption with customer-provided keys (SSE-C)
+    Customer {
+        /// The 256-bit (32-byte) AES key to encrypt the object with. S3 doesn't store this key, so
+        /// the same key must be provided to read the object.
+        key: Vec<u8>,
+    },
+}
+
+impl Debug for ServerSideEncryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::S3 => write!(f, "S3"),
+            Self::Kms { key_id, context } => f
+                .debug_struct("Kms")
+                .field("key_id", key_id)
+                .field("context", context)
+                .finish(),
+            // Don't leak the customer key into logs
+            Self::Customer { .. } => f.debug_struct("Customer").field("key", &REDACTED).finish(),
+        }
+    }
+}
+
+/// Object Lock settings for a new object. The bucket must have Object Lock enabled.
+///
+/// A retention period needs both a `mode` and a `retain_until` date, so setting only one of them
+/// fails the request before it's sent.
+///
+/// See [Locking objects with Object Lock](https://docs.aws.amazon.com/AmazonS3/latest/userguide/object-lock.html)
+/// in the *Amazon S3 User Guide* for more details.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ObjectLock {
+    /// The retention mode to protect the object with until `retain_until`
+    pub mode: Option<ObjectLockMode>,
+    /// The date and time until which the object is protected
+    pub retain_until: Option<OffsetDateTime>,
+    /// Whether to place a legal hold on the object, which protects it until the hold is removed
+    pub legal_hold: Option<bool>,
+}
+
+impl ObjectLock {
+    /// Create [ObjectLock] settings with no retention period or legal hold.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Protect the object with the given retention mode until the given date and time.
+    pub fn retention(mut self, mode: ObjectLockMode, retain_until: OffsetDateTime) -> Self {
+        self.mode = Some(mode);
+        self.retain_until = Some(retain_until);
+        self
+    }
+
+    /// Set whether to place a legal hold on the object.
+    pub fn legal_hold(mut self, value: bool) -> Self {
+        self.legal_hold = Some(value);
+        self
+    }
 }
 
-/// How CRC32c checksums are used for parts of a multi-part PutObject request
+/// Object Lock retention mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ObjectLockMode {
+    /// Users with the `s3:BypassGovernanceRetention` permission can still delete the object or
+    /// shorten its retention
+    Governance,
+    /// No one can delete the object or shorten its retention until it expires
+    Compliance,
+}
+
+impl ObjectLockMode {
+    /// The value of this mode in the `x-amz-object-lock-mode` header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ObjectLockMode::Governance => "GOVERNANCE",
+            ObjectLockMode::Compliance => "COMPLIANCE",
+        }
+    }
+}
+
+/// How checksums are used for parts of a multi-part PutObject request. The algorithm is chosen
+/// with [PutObjectParams::checksum_algorithm], and defaults to CRC32c.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum PutObjectTrailingChecksums {
     /// Checksums are computed, passed to upload review, and also sent to S3
@@ -339,6 +1260,41 @@ pub enum PutObjectTrailingChecksums {
     Disabled,
 }
 
+/// The type of checksum S3 computes for an object uploaded in multiple parts.
+///
+/// See [Checking object integrity](https://docs.aws.amazon.com/AmazonS3/latest/userguide/checking-object-integrity.html)
+/// in the *Amazon S3 User Guide* for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChecksumType {
+    /// The object checksum is computed from the checksums of each individual part. This is the
+    /// default for multi-part uploads.
+    Composite,
+    /// The object checksum is computed over the contents of the full object, as if it had been
+    /// uploaded in a single part.
+    FullObject,
+}
+
+impl ChecksumType {
+    /// The value of this checksum type in the `x-amz-checksum-type` header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumType::Composite => "COMPOSITE",
+            ChecksumType::FullObject => "FULL_OBJECT",
+        }
+    }
+
+    /// The checksum type with the given value of the `x-amz-checksum-type` header or the
+    /// `ChecksumType` element of a response, or `None` if it isn't one this client knows.
+    pub(crate) fn from_value(value: &str) -> Option<Self> {
+        match value {
+            "COMPOSITE" => Some(ChecksumType::Composite),
+            "FULL_OBJECT" => Some(ChecksumType::FullObject),
+            _ => None,
+        }
+    }
+}
+
 /// Info for the caller to review before an upload completes.
 pub type UploadReview = mountpoint_s3_crt::s3::client::UploadReview;
 
@@ -348,6 +1304,12 @@ pub type UploadReviewPart = mountpoint_s3_crt::s3::client::UploadReviewPart;
 /// A checksum algorithm used by the object client for integrity checks on uploads and downloads.
 pub type ChecksumAlgorithm = mountpoint_s3_crt::s3::client::ChecksumAlgorithm;
 
+/// Breakdown of the time a request to S3 spent in each phase.
+pub type RequestTimings = mountpoint_s3_crt::s3::client::RequestTimings;
+
+/// Metrics for a request, aggregated over all the requests to S3 it made.
+pub type MetaRequestMetrics = mountpoint_s3_crt::s3::client::MetaRequestMetrics;
+
 /// A streaming response to a GetObject request.
 ///
 /// This struct implements [`futures::Stream`], which you can use to read the body of the object.
@@ -374,6 +1336,22 @@ pub trait GetObjectRequest:
     /// If `enable_read_backpressure` is false this call will have no effect,
     /// no backpressure is being applied and data is being downloaded as fast as possible.
     fn increment_read_window(self: Pin<&mut Self>, len: usize);
+
+    /// Metadata about the response, such as whether S3 returned 200 for the whole object or 206
+    /// for a range of it. Only available once the stream has returned its first body part.
+    ///
+    /// The default implementation returns `None`, for clients that don't report response metadata.
+    fn response_metadata(&self) -> Option<ResponseMetadata> {
+        None
+    }
+
+    /// Whether the service confirmed that the requester was charged for this request to a
+    /// requester-pays bucket. Only meaningful once the stream has returned its first body part.
+    ///
+    /// The default implementation returns `false`, for clients without requester-pays buckets.
+    fn request_charged(&self) -> bool {
+        false
+    }
 }
 
 /// A streaming put request which allows callers to asynchronously write the body of the request.
@@ -404,14 +1382,23 @@ pub trait PutObjectRequest: Send {
 }
 
 /// Result of a [ObjectClient::put_object] request
-// TODO: Populate this struct with return fields from the S3 API, e.g., etag.
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct PutObjectResult {
+    /// ETag of the uploaded object. `None` if the response didn't include one, as some
+    /// S3-compatible endpoints don't.
+    pub etag: Option<ETag>,
+    /// When the upload completed, according to the server: the `Last-Modified` header of the
+    /// response if it has one, or else its `Date` header. `None` if neither was present and valid.
+    pub last_modified: Option<OffsetDateTime>,
     /// Server-side encryption type that was used to store new object (reported by S3)
     pub sse_type: Option<String>,
     /// Server-side encryption KMS key ID that was used to store new object (reported by S3)
     pub sse_kms_key_id: Option<String>,
+    /// Checksum of the uploaded object and the algorithm used to compute it, if S3 returned one
+    pub checksum: Option<(ChecksumAlgorithm, String)>,
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
 }
 
 /// Errors returned by a [`put_object`](ObjectClient::put_object) request
@@ -447,10 +1434,12 @@ pub struct ObjectInfo {
     /// Key for this object.
     pub key: String,
 
-    /// Size of this object in bytes.
+    /// Size of this object in bytes. Listings that leave out the size of an entry report it as 0.
     pub size: u64,
 
-    /// The time this object was last modified.
+    /// The time this object was last modified. S3 reports it to the second, whether in a listing
+    /// (as RFC 3339) or in the `Last-Modified` header of a HeadObject response (as an HTTP-date), so
+    /// the two can be compared directly.
     pub last_modified: OffsetDateTime,
 
     /// Storage class for this object. Optional because head_object does not return
@@ -462,8 +1451,85 @@ pub struct ObjectInfo {
     /// accessible after restoration
     pub restore_status: Option<RestoreStatus>,
 
-    /// Entity tag of this object.
-    pub etag: String,
+    /// Entity tag of this object. `None` if a listing left it out, as some S3-compatible endpoints
+    /// do for some entries.
+    pub etag: Option<String>,
+
+    /// The type of the object's checksum, if S3 reported one. Listings report it for objects
+    /// uploaded with a checksum, and head_object only if
+    /// [HeadObjectParams::checksum_mode] is set. `None` for endpoints that don't report it.
+    pub checksum_type: Option<ChecksumType>,
+}
+
+impl ObjectInfo {
+    /// Whether this object has the same content as `other`, judging by their sizes and ETags, for
+    /// example to tell whether a copy of an object in another bucket is up to date. The keys and
+    /// last-modified times aren't compared, since they differ between copies of the same content.
+    ///
+    /// ETags are compared ignoring a weak (`W/`) prefix, which proxies and some S3-compatible
+    /// endpoints add. The ETag of an object uploaded with a multipart upload (one containing a
+    /// `-`) depends on how it was split into parts, so copies of the same content uploaded
+    /// differently aren't equal; [ObjectInfo::change_kind] tells that case apart from a change.
+    pub fn content_equals(&self, other: &ObjectInfo) -> bool {
+        self.change_kind(other) == ChangeKind::Unchanged
+    }
+
+    /// Classify how the content of `other` differs from this object, judging by their sizes and
+    /// ETags. See [ObjectInfo::content_equals] for how they're compared. Objects of the same size
+    /// are [Unknown](ChangeKind::Unknown) if either has no ETag.
+    pub fn change_kind(&self, other: &ObjectInfo) -> ChangeKind {
+        if self.size != other.size {
+            return ChangeKind::SizeChanged;
+        }
+        let (Some(etag), Some(other_etag)) = (&self.etag, &other.etag) else {
+            return ChangeKind::Unknown;
+        };
+        let etag = opaque_etag(etag);
+        let other_etag = opaque_etag(other_etag);
+        if etag == other_etag {
+            ChangeKind::Unchanged
+        } else if multipart_etag_parts(etag) == multipart_etag_parts(other_etag) {
+            ChangeKind::ContentChanged
+        } else {
+            ChangeKind::Unknown
+        }
+    }
+}
+
+/// The opaque part of an ETag, without its weak prefix or quotes.
+fn opaque_etag(etag: &str) -> &str {
+    let etag = etag.trim();
+    let etag = etag.strip_prefix("W/").unwrap_or(etag);
+    etag.strip_prefix('"')
+        .and_then(|etag| etag.strip_suffix('"'))
+        .unwrap_or(etag)
+}
+
+/// The number of parts in the ETag of an object uploaded with a multipart upload, which has the
+/// form `<hash>-<parts>`, or `None` for other ETags.
+fn multipart_etag_parts(etag: &str) -> Option<&str> {
+    etag.rsplit_once('-').map(|(_, parts)| parts)
+}
+
+/// How the content of an object differs from another object, such as a copy of it in another
+/// bucket. See [ObjectInfo::change_kind].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChangeKind {
+    /// The objects have the same size and ETag, so the same content.
+    Unchanged,
+
+    /// The objects have different sizes.
+    SizeChanged,
+
+    /// The objects have the same size, but different ETags that were computed the same way, so
+    /// different content.
+    ContentChanged,
+
+    /// The objects have the same size, but their ETags were computed differently, so whether their
+    /// content differs can't be told without comparing it: one was uploaded with a multipart
+    /// upload and the other wasn't, or they were uploaded in different numbers of parts.
+    Unknown,
 }
 
 /// All possible object attributes that can be retrived from [ObjectClient::get_object_attributes].
@@ -503,7 +1569,7 @@ impl fmt::Display for ObjectAttribute {
 ///
 /// See [Checksum](https://docs.aws.amazon.com/AmazonS3/latest/API/API_Checksum.html) in the *Amazon
 /// S3 API Reference* for more details.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Checksum {
     /// Base64-encoded, 32-bit CRC32 checksum of the object
     pub checksum_crc32: Option<String>,
@@ -516,6 +1582,31 @@ pub struct Checksum {
 
     /// Base64-encoded, 256-bit SHA-256 digest of the object
     pub checksum_sha256: Option<String>,
+
+    /// Base64-encoded, 64-bit CRC64NVME checksum of the object
+    pub checksum_crc64nvme: Option<String>,
+
+    /// Whether the checksum covers the full object or is a composite of the checksums of its
+    /// parts, which changes how to recompute it. `None` for the checksums of individual parts, and
+    /// if S3 didn't report the type.
+    pub checksum_type: Option<ChecksumType>,
+}
+
+impl Checksum {
+    /// The algorithm of the checksum that's present, and its value. S3 returns a single checksum
+    /// for an object, of the algorithm it was uploaded with. If there's more than one, this returns
+    /// the first in the order of [ChecksumAlgorithm].
+    pub fn algorithm_and_value(&self) -> Option<(ChecksumAlgorithm, String)> {
+        [
+            (ChecksumAlgorithm::Crc32c, &self.checksum_crc32c),
+            (ChecksumAlgorithm::Crc32, &self.checksum_crc32),
+            (ChecksumAlgorithm::Sha1, &self.checksum_sha1),
+            (ChecksumAlgorithm::Sha256, &self.checksum_sha256),
+            (ChecksumAlgorithm::Crc64nvme, &self.checksum_crc64nvme),
+        ]
+        .into_iter()
+        .find_map(|(algorithm, value)| Some((algorithm, value.clone()?)))
+    }
 }
 
 /// Metadata about object parts from GetObjectAttributes API.
@@ -558,3 +1649,109 @@ pub struct ObjectPart {
     /// Size of the part in bytes
     pub size: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn object_info(size: u64, etag: &str) -> ObjectInfo {
+        ObjectInfo {
+            key: "key".to_string(),
+            size,
+            last_modified: OffsetDateTime::UNIX_EPOCH,
+            storage_class: None,
+            restore_status: None,
+            etag: Some(etag.to_string()),
+            checksum_type: None,
+        }
+    }
+
+    #[test_case(5, "\"abc\"", 5, "\"abc\"", ChangeKind::Unchanged; "same")]
+    #[test_case(5, "\"abc\"", 6, "\"abc\"", ChangeKind::SizeChanged; "different size")]
+    #[test_case(5, "\"abc\"", 5, "\"def\"", ChangeKind::ContentChanged; "different etag")]
+    #[test_case(5, "W/\"abc\"", 5, "\"abc\"", ChangeKind::Unchanged; "weak and strong etag")]
+    #[test_case(5, "W/\"abc\"", 5, "W/\"def\"", ChangeKind::ContentChanged; "different weak etags")]
+    #[test_case(5, "abc", 5, "\"abc\"", ChangeKind::Unchanged; "unquoted etag")]
+    #[test_case(5, "\"abc-2\"", 5, "\"abc-2\"", ChangeKind::Unchanged; "same multipart etag")]
+    #[test_case(5, "\"abc-2\"", 5, "\"def-2\"", ChangeKind::ContentChanged; "multipart etags with same parts")]
+    #[test_case(5, "\"abc-2\"", 5, "\"def-3\"", ChangeKind::Unknown; "multipart etags with different parts")]
+    #[test_case(5, "\"abc\"", 5, "\"def-2\"", ChangeKind::Unknown; "single part and multipart etags")]
+    #[test_case(5, "\"abc-2\"", 6, "\"abc\"", ChangeKind::SizeChanged; "multipart etag and different size")]
+    fn test_change_kind(size: u64, etag: &str, other_size: u64, other_etag: &str, expected: ChangeKind) {
+        let object = object_info(size, etag);
+        let other = object_info(other_size, other_etag);
+        assert_eq!(object.change_kind(&other), expected);
+        assert_eq!(other.change_kind(&object), expected);
+        assert_eq!(object.content_equals(&other), expected == ChangeKind::Unchanged);
+    }
+
+    #[test]
+    fn test_change_kind_without_etag() {
+        let object = object_info(5, "\"abc\"");
+        let without_etag = ObjectInfo {
+            etag: None,
+            ..object.clone()
+        };
+        assert_eq!(object.change_kind(&without_etag), ChangeKind::Unknown);
+        assert_eq!(without_etag.change_kind(&without_etag), ChangeKind::Unknown);
+        let resized = ObjectInfo {
+            size: 6,
+            ..without_etag.clone()
+        };
+        assert_eq!(without_etag.change_kind(&resized), ChangeKind::SizeChanged);
+    }
+
+    #[test]
+    fn test_content_equals_ignores_key_and_last_modified() {
+        let object = object_info(5, "\"abc\"");
+        let copy = ObjectInfo {
+            key: "copy".to_string(),
+            last_modified: OffsetDateTime::now_utc(),
+            ..object.clone()
+        };
+        assert!(object.content_equals(&copy));
+    }
+
+    #[test]
+    fn test_list_entries_sorted_by_key() {
+        let objects = ["a", "b/", "c", "é"]
+            .into_iter()
+            .map(|key| ObjectInfo {
+                key: key.to_string(),
+                ..object_info(1, "\"abc\"")
+            })
+            .collect();
+        let result = ListObjectsResult {
+            objects,
+            common_prefixes: vec!["a/".to_string(), "d/".to_string(), "b-/".to_string()],
+            next_continuation_token: None,
+            prefix: None,
+            delimiter: Some("/".to_string()),
+            max_keys: None,
+            encoding_type: None,
+            request_charged: false,
+            response_metadata: ResponseMetadata::new(200),
+        };
+        let entries: Vec<_> = result
+            .into_entries()
+            .into_iter()
+            .map(|entry| (entry.key().to_string(), matches!(entry, ListEntry::Prefix(_))))
+            .collect();
+        let expected = [
+            ("a", false),
+            ("a/", true),
+            ("b-/", true),
+            ("b/", false),
+            ("c", false),
+            ("d/", true),
+            ("é", false),
+        ];
+        let expected: Vec<_> = expected
+            .iter()
+            .map(|(key, prefix)| (key.to_string(), *prefix))
+            .collect();
+        assert_eq!(entries, expected);
+    }
+}
@@ -1,69 +1,105 @@
 use std::cmp;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
 use std::ffi::{OsStr, OsString};
 use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
 use std::num::NonZeroUsize;
 use std::ops::Deref;
 use std::ops::Range;
 use std::os::unix::prelude::OsStrExt;
 use std::pin::Pin;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
 use crate::error_metadata::{ClientErrorMetadata, ProvideErrorMetadata};
 use mountpoint_s3_crt::auth::credentials::{
-    CredentialsProvider, CredentialsProviderChainDefaultOptions, CredentialsProviderProfileOptions,
+    CredentialsProvider, CredentialsProviderChainDefaultOptions, CredentialsProviderProfileOptions, ObserveCredentials,
 };
-use mountpoint_s3_crt::auth::signing_config::SigningConfig;
+use mountpoint_s3_crt::auth::signing_config::{SigningAlgorithm, SigningConfig};
 use mountpoint_s3_crt::common::allocator::Allocator;
 use mountpoint_s3_crt::common::string::AwsString;
 use mountpoint_s3_crt::common::uri::Uri;
 use mountpoint_s3_crt::http::request_response::{Header, Headers, Message};
 use mountpoint_s3_crt::io::channel_bootstrap::{ClientBootstrap, ClientBootstrapOptions};
-use mountpoint_s3_crt::io::event_loop::EventLoopGroup;
-use mountpoint_s3_crt::io::host_resolver::{AddressKinds, HostResolver, HostResolverDefaultOptions};
+use mountpoint_s3_crt::io::event_loop::{EventLoopGroup, EventLoopTimer};
+use mountpoint_s3_crt::io::futures::{FutureJoinHandle, FutureSpawner};
+use mountpoint_s3_crt::io::host_resolver::{
+    AddressKinds, HostResolutionMode, HostResolver, HostResolverDefaultOptions,
+};
 use mountpoint_s3_crt::io::retry_strategy::{ExponentialBackoffJitterMode, RetryStrategy, StandardRetryOptions};
+use mountpoint_s3_crt::io::stream::InputStream;
 use mountpoint_s3_crt::s3::client::{
-    init_signing_config, ChecksumConfig, Client, ClientConfig, MetaRequest, MetaRequestOptions, MetaRequestResult,
-    MetaRequestType, RequestMetrics, RequestType,
+    init_signing_config, ChecksumConfig, Client, ClientConfig, ClientMetrics, ConnectionStats, MetaRequest,
+    MetaRequestOptions, MetaRequestResult, MetaRequestType, RequestMetrics, RequestType, TcpKeepAliveOptions,
+    UnsupportedChecksumAlgorithm,
 };
 
 use async_trait::async_trait;
 use futures::channel::oneshot;
-use percent_encoding::{percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use lazy_static::lazy_static;
+use percent_encoding::{percent_decode_str, percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use pin_project::{pin_project, pinned_drop};
 use thiserror::Error;
-use tracing::{debug, error, trace, Span};
+use time::format_description::well_known::{Rfc2822, Rfc3339};
+use time::format_description::FormatItem;
+use time::{OffsetDateTime, PrimitiveDateTime};
+use tracing::{debug, error, trace, warn, Span};
 
 use self::get_object::S3GetObjectRequest;
+use self::list_objects::{parse_xml, ContinuationTokens, ListObjectsResponse, OptionalObjectAttribute};
+use self::presign::{ClockSource, SystemClock};
 use self::put_object::S3PutObjectRequest;
 use crate::endpoint_config::EndpointError;
-use crate::endpoint_config::{self, EndpointConfig};
+use crate::endpoint_config::{self, AuthScheme, EndpointConfig};
 use crate::object_client::*;
-use crate::user_agent::UserAgent;
-
+use crate::proxy_config::ProxyConfig;
+use crate::tls_config::TlsConfig;
+use crate::user_agent::{is_valid_user_agent_suffix, UserAgent};
+
+/// Create the span for a new request made through the given [S3CrtClient] handle, recording the
+/// handle's [request context](S3CrtClient::with_request_context) along with the given fields. The
+/// endpoint fields are recorded once the request's message is turned into a meta request (see
+/// [S3Message::record_endpoint]).
 macro_rules! request_span {
     ($self:expr, $method:expr, $($field:tt)*) => {{
-        let counter = $self.next_request_counter();
+        let counter = $self.inner.next_request_counter();
+        let context = $self.request_context.as_deref();
         // I have confused myself at least 4 times about how to choose the level for tracing spans.
         // We want this span to be constructed whenever events at WARN or lower severity (INFO,
         // DEBUG, TRACE) are emitted. So we set its severity to WARN too.
-        let span = tracing::warn_span!(target: "mountpoint_s3_client::s3_crt_client::request", $method, id = counter, $($field)*);
+        let span = tracing::warn_span!(target: "mountpoint_s3_client::s3_crt_client::request", $method, id = counter, context, host = tracing::field::Empty, scheme = tracing::field::Empty, signing_region = tracing::field::Empty, $($field)*);
         span.in_scope(|| tracing::debug!("new request"));
         span
     }};
     ($self:expr, $method:expr) => { request_span!($self, $method,) };
 }
 
+pub(crate) mod copy_object;
 pub(crate) mod delete_object;
+mod event_stream;
+pub(crate) mod get_bucket_location;
 pub(crate) mod get_object;
 pub(crate) mod get_object_attributes;
 pub(crate) mod head_object;
+pub(crate) mod list_object_versions;
 pub(crate) mod list_objects;
+pub(crate) mod multipart_upload;
+pub(crate) mod object_acl;
+pub(crate) mod object_exists;
+pub(crate) mod object_tagging;
+pub(crate) mod presign;
 pub(crate) mod put_object;
+pub(crate) mod restore_object;
+pub(crate) mod select_object_content;
+pub(crate) mod self_test;
+pub(crate) mod send_request;
 
 pub(crate) mod head_bucket;
+pub use get_bucket_location::GetBucketLocationError;
 pub use head_bucket::HeadBucketError;
 
 /// `tracing` doesn't allow dynamic levels but we want to dynamically choose the log level for
@@ -80,35 +116,117 @@ macro_rules! event {
     }
 }
 
-/// Configurations for the CRT-based S3 client
+/// Configurations for the CRT-based S3 client. This is a builder for [S3CrtClient]: chain its
+/// setters, starting from [S3CrtClient::builder] or [S3ClientConfig::new], and then call
+/// [build](S3ClientConfig::build).
 #[derive(Debug, Clone)]
 pub struct S3ClientConfig {
     auth_config: S3ClientAuthConfig,
     throughput_target_gbps: f64,
+    max_active_connections: Option<u32>,
+    network_interface_names: Vec<String>,
     part_size: usize,
+    multipart_upload_threshold: Option<usize>,
+    parallel_get_threshold: u64,
+    memory_limit_in_bytes: u64,
     endpoint_config: EndpointConfig,
     user_agent: Option<UserAgent>,
+    user_agent_suffix: Option<String>,
     request_payer: Option<String>,
     bucket_owner: Option<String>,
+    default_headers: Vec<(String, String)>,
     max_attempts: Option<NonZeroUsize>,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    backoff_jitter_mode: ExponentialBackoffJitterMode,
+    retry_budget: Option<NonZeroUsize>,
+    retry_budget_refill: f64,
     read_backpressure: bool,
     initial_read_window: usize,
+    checksum_mode: ChecksumMode,
+    endpoint_cache_capacity: usize,
+    ranged_get_existence_check: bool,
+    connect_timeout: Option<Duration>,
+    tcp_keep_alive: Option<TcpKeepAliveOptions>,
+    host_resolution_mode: HostResolutionMode,
+    time_to_first_byte_timeout: Option<Duration>,
+    overall_request_timeout: Option<Duration>,
+    verify_region_on_init: bool,
+    validate_bucket_names: bool,
+    list_objects_start_after_fallback: bool,
+    list_objects_page_retries: u32,
+    upload_part_retries: u32,
+    list_objects_page_delay: Range<Duration>,
+    max_response_body_bytes: usize,
+    max_get_redirects: u32,
+    egress_byte_budget: Option<u64>,
+    signing_region: Option<String>,
+    signing_algorithm: Option<SigningAlgorithm>,
+    signing_service: Option<String>,
+    express_support: bool,
+    additional_retryable_statuses: HashSet<u16>,
+    proxy: Option<ProxyConfig>,
+    tls_config: Option<TlsConfig>,
+    clock_source: Arc<dyn ClockSource>,
+    credentials_observer: Option<CredentialsObserver>,
 }
 
 impl Default for S3ClientConfig {
     fn default() -> Self {
+        const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+        const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(20);
         const DEFAULT_PART_SIZE: usize = 8 * 1024 * 1024;
+        const DEFAULT_ENDPOINT_CACHE_CAPACITY: usize = 16;
+        const DEFAULT_RETRY_BUDGET_REFILL: f64 = 0.2;
         Self {
             auth_config: Default::default(),
             throughput_target_gbps: 10.0,
+            max_active_connections: None,
+            network_interface_names: Vec::new(),
             part_size: DEFAULT_PART_SIZE,
+            multipart_upload_threshold: None,
+            parallel_get_threshold: 0,
+            memory_limit_in_bytes: 0,
             endpoint_config: EndpointConfig::new("us-east-1"),
             user_agent: None,
+            user_agent_suffix: None,
             request_payer: None,
             bucket_owner: None,
+            default_headers: Vec::new(),
             max_attempts: None,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            retry_budget: None,
+            retry_budget_refill: DEFAULT_RETRY_BUDGET_REFILL,
+            backoff_jitter_mode: ExponentialBackoffJitterMode::Full,
             read_backpressure: false,
             initial_read_window: DEFAULT_PART_SIZE,
+            checksum_mode: ChecksumMode::default(),
+            endpoint_cache_capacity: DEFAULT_ENDPOINT_CACHE_CAPACITY,
+            ranged_get_existence_check: false,
+            connect_timeout: None,
+            tcp_keep_alive: None,
+            host_resolution_mode: HostResolutionMode::default(),
+            time_to_first_byte_timeout: None,
+            overall_request_timeout: None,
+            verify_region_on_init: false,
+            validate_bucket_names: true,
+            list_objects_start_after_fallback: false,
+            list_objects_page_retries: 0,
+            upload_part_retries: 0,
+            list_objects_page_delay: Duration::ZERO..Duration::ZERO,
+            max_response_body_bytes: 256 * 1024 * 1024,
+            max_get_redirects: 0,
+            egress_byte_budget: None,
+            signing_region: None,
+            signing_algorithm: None,
+            signing_service: None,
+            express_support: true,
+            additional_retryable_statuses: HashSet::new(),
+            proxy: None,
+            tls_config: None,
+            clock_source: Arc::new(SystemClock),
+            credentials_observer: None,
         }
     }
 }
@@ -118,6 +236,13 @@ impl S3ClientConfig {
         Self::default()
     }
 
+    /// Create a new [S3CrtClient] with this configuration. Fails with
+    /// [NewClientError::InvalidConfiguration] if an option, or a combination of options, is
+    /// invalid.
+    pub fn build(self) -> Result<S3CrtClient, NewClientError> {
+        S3CrtClient::new(self)
+    }
+
     /// Set the configuration for authenticating to S3
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn auth_config(mut self, auth_config: S3ClientAuthConfig) -> Self {
@@ -125,6 +250,24 @@ impl S3ClientConfig {
         self
     }
 
+    /// Set a custom credentials provider for signing requests. This is shorthand for
+    /// [auth_config](Self::auth_config) with [S3ClientAuthConfig::Provider].
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn credentials_provider(mut self, credentials_provider: CredentialsProvider) -> Self {
+        self.auth_config = S3ClientAuthConfig::Provider(credentials_provider);
+        self
+    }
+
+    /// Set an observer to report each refresh of the client's credentials, with their new
+    /// expiration, and each failure to get credentials. This makes it possible to alert before
+    /// credentials expire, rather than finding out from requests failing with 403 errors. There's
+    /// no observer by default, and it's never called for [S3ClientAuthConfig::NoSigning].
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn credentials_observer(mut self, observer: impl ObserveCredentials) -> Self {
+        self.credentials_observer = Some(CredentialsObserver(Arc::new(observer)));
+        self
+    }
+
     /// Set the part size for multi-part operations to S3 (both PUT and GET)
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn part_size(mut self, part_size: usize) -> Self {
@@ -132,13 +275,75 @@ impl S3ClientConfig {
         self
     }
 
-    /// Set the target throughput in Gbps for the S3 client
+    /// Set the object size at which uploads switch from a single PutObject request to a multi-part
+    /// upload. By default, the CRT uses the part size.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn multipart_upload_threshold(mut self, multipart_upload_threshold: usize) -> Self {
+        self.multipart_upload_threshold = Some(multipart_upload_threshold);
+        self
+    }
+
+    /// Set the size of the smallest ranged GetObject request that the CRT splits into parallel
+    /// requests for [part_size](Self::part_size) ranges, which it reassembles in order. Requests
+    /// for smaller ranges are sent as a single GET, which saves the overhead of splitting ranges
+    /// that would only take one or two parts anyway. Requests for whole objects are always split,
+    /// since their size isn't known up front. The default of `0` splits every request.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn parallel_get_threshold(mut self, parallel_get_threshold: u64) -> Self {
+        self.parallel_get_threshold = parallel_get_threshold;
+        self
+    }
+
+    /// Set a limit in bytes on the memory the S3 client uses to buffer parts of requests. If `0`,
+    /// which is the default, the CRT chooses a limit based on the target throughput. Otherwise the
+    /// limit must be at least 1GiB, or client creation fails.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn memory_limit_in_bytes(mut self, memory_limit_in_bytes: u64) -> Self {
+        self.memory_limit_in_bytes = memory_limit_in_bytes;
+        self
+    }
+
+    /// Set the target throughput in Gbps for the S3 client. The default is 10 Gbps.
+    ///
+    /// The CRT uses the target to choose how many connections to open, unless
+    /// [max_active_connections](Self::max_active_connections) caps them, and, if
+    /// [memory_limit_in_bytes](Self::memory_limit_in_bytes) is `0`, how much memory to use to
+    /// buffer parts. To saturate a faster network interface, set the target to its bandwidth, for
+    /// example `100.0` on a 100 Gbps instance. The client's event loop group has one thread per
+    /// CPU core, which bounds how many connections it can keep busy, so a target beyond what the
+    /// network and cores can sustain opens more connections and buffers without going faster. An
+    /// explicit memory limit that's too small for the target also limits throughput, as requests
+    /// wait for buffer space.
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn throughput_target_gbps(mut self, throughput_target_gbps: f64) -> Self {
         self.throughput_target_gbps = throughput_target_gbps;
         self
     }
 
+    /// Set the maximum number of connections the S3 client keeps open at once. New requests wait
+    /// for a connection once the limit is reached. By default, the CRT chooses a limit based on
+    /// the target throughput.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn max_active_connections(mut self, max_active_connections: u32) -> Self {
+        self.max_active_connections = Some(max_active_connections);
+        self
+    }
+
+    /// Set the names of the network interfaces to send S3 traffic through, for example `eth1`, to
+    /// control which interface and source address connections use on a host with more than one.
+    /// The CRT spreads connections across the interfaces round-robin. By default, the operating
+    /// system chooses the interface for each connection.
+    ///
+    /// The client logs the configured interfaces at debug level when it's created. The interface
+    /// each connection binds to is in the CRT's socket logs at debug level. Client creation fails
+    /// with [NewClientError::InvalidConfiguration] if the CRT the client was built with can't bind
+    /// connections to interfaces.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn network_interface_names(mut self, network_interface_names: Vec<String>) -> Self {
+        self.network_interface_names = network_interface_names;
+        self
+    }
+
     /// Set the endpoint configuration for endpoint resolution
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn endpoint_config(mut self, endpoint_config: EndpointConfig) -> Self {
@@ -146,6 +351,20 @@ impl S3ClientConfig {
         self
     }
 
+    /// Set the region to send requests to, keeping the rest of the endpoint configuration
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn region(mut self, region: &str) -> Self {
+        self.endpoint_config = self.endpoint_config.region(region);
+        self
+    }
+
+    /// Set a custom endpoint to send requests to, keeping the rest of the endpoint configuration
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn endpoint(mut self, endpoint: Uri) -> Self {
+        self.endpoint_config = self.endpoint_config.endpoint(endpoint);
+        self
+    }
+
     /// Set a constructor for the HTTP User-agent header for S3 requests
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn user_agent(mut self, user_agent: UserAgent) -> Self {
@@ -153,6 +372,15 @@ impl S3ClientConfig {
         self
     }
 
+    /// Set a suffix to append to the HTTP User-agent header for S3 requests, for example to
+    /// identify the application making the requests. The suffix must be one or more
+    /// space-separated products of the form `token[/token]`, as defined by RFC 7230.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn user_agent_suffix(mut self, user_agent_suffix: &str) -> Self {
+        self.user_agent_suffix = Some(user_agent_suffix.to_owned());
+        self
+    }
+
     /// Set a value for the request payer HTTP header for S3 requests
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn request_payer(mut self, request_payer: &str) -> Self {
@@ -160,34 +388,438 @@ impl S3ClientConfig {
         self
     }
 
-    /// Set an expected bucket owner value
+    /// Set the account ID of the expected bucket owner, which is sent with every request in the
+    /// `x-amz-expected-bucket-owner` header. S3 denies requests to a bucket owned by any other
+    /// account, so that a bucket that was deleted and recreated by someone else can't be accessed
+    /// by mistake. Access denied errors from a client with an expected bucket owner are reported
+    /// as [S3RequestError::BucketOwnerAccessDenied], since S3 doesn't say whether the owner was
+    /// wrong or the request wasn't allowed for another reason.
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn bucket_owner(mut self, bucket_owner: &str) -> Self {
         self.bucket_owner = Some(bucket_owner.to_owned());
         self
     }
 
-    /// Set a maximum number of attempts for S3 requests. Will be overridden by the
-    /// `AWS_MAX_ATTEMPTS` environment variable if set.
+    /// Add a header to send with every S3 request, for example to identify a tenant to a gateway
+    /// in front of S3. Headers are added before requests are signed, so they're included in the
+    /// signature. Each header is sent once: a default header replaces an earlier one with the same
+    /// name, and headers that the client sets for a specific request, such as `Range` for a ranged
+    /// GetObject request, or for every request, such as `x-amz-request-payer` when
+    /// [request_payer](Self::request_payer) is set, replace a default header with the same name.
+    ///
+    /// Headers that the client manages itself, like `Authorization`, `Host`, and the signing
+    /// headers, can't be set this way, and [S3CrtClient::new] fails if one is configured.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn default_header(mut self, name: &str, value: &str) -> Self {
+        self.default_headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Set a maximum number of attempts for S3 requests, including the first attempt, so 1 means
+    /// failed requests aren't retried. At most 64 attempts are allowed. The default is 3. Will be
+    /// overridden by the `AWS_MAX_ATTEMPTS` environment variable if set.
+    ///
+    /// This applies to every request the client makes; the CRT can't retry individual requests a
+    /// different number of times, so use separate clients for workloads that need different
+    /// limits.
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn max_attempts(mut self, max_attempts: NonZeroUsize) -> Self {
         self.max_attempts = Some(max_attempts);
         self
     }
 
-    /// Set the flag for backpressure read
+    /// Set the backoff before the first retry of a failed S3 request. Each further retry doubles
+    /// the backoff, up to the [max_backoff](Self::max_backoff). Default is 500ms. Must be at least
+    /// 1ms.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Set the maximum backoff between retries of a failed S3 request. The CRT only supports
+    /// whole seconds, so the value is rounded down. Default is 20s. Must be at least 1s.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Set the size of the retry budget that caps how many failed S3 requests the client retries
+    /// across all of its requests, so that the client backs off during an outage rather than
+    /// adding load with retries. There's no budget by default.
+    ///
+    /// The client keeps a token bucket of this many tokens, which starts full. Each retry of a
+    /// request that failed with a throttling or server error, or a connection failure, takes one
+    /// token, and each request that succeeds returns some (see
+    /// [retry_budget_refill](Self::retry_budget_refill)). Once the bucket is empty, a request that
+    /// fails is canceled rather than retried, and fails fast with
+    /// [S3RequestError::RetryBudgetExhausted]. This is on top of the CRT's own retry token bucket.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn retry_budget(mut self, retry_budget: NonZeroUsize) -> Self {
+        self.retry_budget = Some(retry_budget);
+        self
+    }
+
+    /// Set how many tokens of the [retry budget](Self::retry_budget) each successful request
+    /// returns, up to the budget's size. The default is 0.2, so five successful requests earn back
+    /// one retry. Client creation fails with [NewClientError::InvalidConfiguration] if it's
+    /// negative or not finite.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn retry_budget_refill(mut self, retry_budget_refill: f64) -> Self {
+        self.retry_budget_refill = retry_budget_refill;
+        self
+    }
+
+    /// Set how the CRT randomizes the backoff between retries of a failed S3 request, so that
+    /// many clients that fail at the same time don't retry in lockstep. Default is
+    /// [ExponentialBackoffJitterMode::Full]. The client's own retries (see
+    /// [additional_retryable_statuses](Self::additional_retryable_statuses)) aren't jittered.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn backoff_jitter_mode(mut self, backoff_jitter_mode: ExponentialBackoffJitterMode) -> Self {
+        self.backoff_jitter_mode = backoff_jitter_mode;
+        self
+    }
+
+    /// Set whether GetObject requests apply read backpressure. The default is `false`, which
+    /// downloads objects as fast as possible however slowly the body is consumed.
+    ///
+    /// With backpressure, each GetObject request only downloads as much of the body as its read
+    /// window allows, starting from the [initial_read_window](Self::initial_read_window). The
+    /// caller opens it further with [GetObjectRequest::increment_read_window], or wraps the request
+    /// in a [GetObjectResponse](crate::types::GetObjectResponse), which reopens the window as each
+    /// body part is consumed, so that at most the initial window is buffered.
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn read_backpressure(mut self, read_backpressure: bool) -> Self {
         self.read_backpressure = read_backpressure;
         self
     }
 
-    /// Set a value for initial backpressure read window size
+    /// Set the initial size in bytes of the read window of each GetObject request, when
+    /// [read_backpressure](Self::read_backpressure) is enabled. The default is the default part
+    /// size, 8 MiB. With backpressure disabled, this has no effect.
+    ///
+    /// This is how much of the body the CRT downloads and buffers before waiting for the window to
+    /// be incremented, so it bounds the memory each request uses with a
+    /// [GetObjectResponse](crate::types::GetObjectResponse). A smaller window saves memory for small
+    /// or latency-sensitive reads. A window of several parts lets the CRT download the parts in
+    /// parallel, which streaming reads need for high throughput. A window of 0 means requests don't
+    /// start until their window is incremented. The CRT only takes this setting for the whole
+    /// client: an individual request can grow its window with
+    /// [GetObjectRequest::increment_read_window], but never start with a smaller one.
     #[must_use = "S3ClientConfig follows a builder pattern"]
     pub fn initial_read_window(mut self, initial_read_window: usize) -> Self {
         self.initial_read_window = initial_read_window;
         self
     }
+
+    /// Set whether GetObject responses should be validated against the object's checksum
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn checksum_mode(mut self, checksum_mode: ChecksumMode) -> Self {
+        self.checksum_mode = checksum_mode;
+        self
+    }
+
+    /// Set the maximum number of buckets whose resolved endpoints are cached. If set to 0, the
+    /// endpoint is resolved again for every request.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn endpoint_cache_capacity(mut self, endpoint_cache_capacity: usize) -> Self {
+        self.endpoint_cache_capacity = endpoint_cache_capacity;
+        self
+    }
+
+    /// Set whether [`object_exists`](ObjectClient::object_exists) checks for the object with a
+    /// GetObject request for its first byte, rather than a HeadObject request. This is useful for
+    /// S3-compatible endpoints where HeadObject is unreliable.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn ranged_get_existence_check(mut self, ranged_get_existence_check: bool) -> Self {
+        self.ranged_get_existence_check = ranged_get_existence_check;
+        self
+    }
+
+    /// Set a timeout for establishing a connection to S3. Connection attempts that time out are
+    /// retried, and a request fails with [`S3RequestError::Timeout`] once it runs out of attempts.
+    /// If not set, the CRT's default connect timeout of 3 seconds is used.
+    ///
+    /// This only bounds the TCP connect, so it can be short to fail fast when the endpoint is
+    /// unreachable without limiting how long transfers on an established connection take. See
+    /// [time_to_first_byte_timeout](Self::time_to_first_byte_timeout) and
+    /// [overall_request_timeout](Self::overall_request_timeout) for those.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Enable TCP keepalive on connections to S3 with the given options, or disable it if `None`.
+    /// Keepalive is disabled by default.
+    ///
+    /// Behind a NAT gateway or load balancer with a short idle timeout, pooled connections can be
+    /// silently dropped between requests, so that the next request has to wait for a reconnect.
+    /// Keepalive probes stop that, as long as
+    /// [`keep_alive_interval_sec`](TcpKeepAliveOptions::keep_alive_interval_sec) is shorter than
+    /// the idle timeout: for example, 60 seconds with a 10 second probe timeout and 3 failed
+    /// probes. The interval and probe timeout must both be set, or both left as zero to use the
+    /// operating system's defaults.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn tcp_keep_alive(mut self, tcp_keep_alive: Option<TcpKeepAliveOptions>) -> Self {
+        self.tcp_keep_alive = tcp_keep_alive;
+        self
+    }
+
+    /// Set which address families the client connects to S3 over. By default, the client resolves
+    /// both IPv4 and IPv6 addresses and uses whichever connects first. Use
+    /// [`HostResolutionMode::Ipv4Only`] to avoid an IPv6 path that resolves but doesn't work.
+    /// Resolving an endpoint with no addresses of the chosen family fails the request.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn host_resolution_mode(mut self, host_resolution_mode: HostResolutionMode) -> Self {
+        self.host_resolution_mode = host_resolution_mode;
+        self
+    }
+
+    /// Set a timeout for S3 to start responding to a request. The request is canceled and fails
+    /// with [`S3RequestError::Timeout`] if no response headers or body arrive in time. Does not
+    /// apply to PutObject requests, since their responses wait for the caller to finish writing.
+    /// If not set, there is no time-to-first-byte timeout.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn time_to_first_byte_timeout(mut self, time_to_first_byte_timeout: Duration) -> Self {
+        self.time_to_first_byte_timeout = Some(time_to_first_byte_timeout);
+        self
+    }
+
+    /// Set a timeout for an entire request, including retries and reading the response body. The
+    /// request is canceled and fails with [`S3RequestError::Timeout`] if it doesn't finish in time.
+    /// Does not apply to PutObject requests, since their duration depends on how fast the caller
+    /// writes. If not set, there is no overall request timeout.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn overall_request_timeout(mut self, overall_request_timeout: Duration) -> Self {
+        self.overall_request_timeout = Some(overall_request_timeout);
+        self
+    }
+
+    /// Set whether to check that a bucket is in the configured region before the first request to
+    /// it, using a HeadBucket request. If the regions differ, that request fails with
+    /// [`S3RequestError::RegionMismatch`], rather than with whatever error S3 returns for requests
+    /// to the wrong region.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn verify_region_on_init(mut self, verify_region_on_init: bool) -> Self {
+        self.verify_region_on_init = verify_region_on_init;
+        self
+    }
+
+    /// Set whether to check bucket names with [validate_bucket_name](crate::validate_bucket_name)
+    /// before sending requests, so that requests to an invalid bucket name fail with
+    /// [`S3RequestError::InvalidBucketName`] without a round trip to S3. The default is `true`.
+    /// Disable it for legacy buckets in `us-east-1` whose names predate the current naming rules,
+    /// such as names with uppercase letters or underscores, or for S3-compatible endpoints with
+    /// different rules.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn validate_bucket_names(mut self, validate_bucket_names: bool) -> Self {
+        self.validate_bucket_names = validate_bucket_names;
+        self
+    }
+
+    /// Set whether to continue a truncated ListObjectsV2 listing with `start-after` set to the last
+    /// returned key, when the response doesn't include a `NextContinuationToken`. Some S3-compatible
+    /// endpoints paginate this way. The continuation token of such a listing is made up by the
+    /// client, and is only meaningful to clients with this option set.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn list_objects_start_after_fallback(mut self, list_objects_start_after_fallback: bool) -> Self {
+        self.list_objects_start_after_fallback = list_objects_start_after_fallback;
+        self
+    }
+
+    /// Set how many times to retry a page of a ListObjectsV2 listing, with the same continuation
+    /// token, when it fails with a [transient](S3RequestError::is_transient) error. These retries
+    /// are on top of the ones the CRT and [additional_retryable_statuses](Self::additional_retryable_statuses)
+    /// already make, so a single failed page doesn't end a long listing. Each retry is a new
+    /// request that the CRT may retry up to [max_attempts](Self::max_attempts) times itself, so a
+    /// page can be attempted up to `(list_objects_page_retries + 1) * max_attempts` times.
+    ///
+    /// This is the default for every listing the client makes. Use
+    /// [`S3CrtClient::list_objects_with_retries`] to choose the retries for a single call. The
+    /// default is 0, which doesn't retry pages.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn list_objects_page_retries(mut self, list_objects_page_retries: u32) -> Self {
+        self.list_objects_page_retries = list_objects_page_retries;
+        self
+    }
+
+    /// Set how many times [`upload_part`](S3CrtClient::upload_part) retries a part when it fails
+    /// with a [transient](S3RequestError::is_transient) error, with the same backoff as other
+    /// retries, so a long multipart upload over a flaky network doesn't have to be restarted because
+    /// one part failed; the parts already uploaded are unaffected, since uploading a part again just
+    /// replaces it. Like [list_objects_page_retries](Self::list_objects_page_retries), these retries
+    /// are on top of the ones the CRT and [additional_retryable_statuses](Self::additional_retryable_statuses)
+    /// already make: each retry is a new request that the CRT may retry up to
+    /// [max_attempts](Self::max_attempts) times itself, so a part can be attempted up to
+    /// `(upload_part_retries + 1) * max_attempts` times. The default is 0, which doesn't retry
+    /// parts.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn upload_part_retries(mut self, upload_part_retries: u32) -> Self {
+        self.upload_part_retries = upload_part_retries;
+        self
+    }
+
+    /// Set a delay to wait before requesting each page of a ListObjectsV2 listing after the first,
+    /// which is a request with a continuation token. The delay is chosen at random from the range
+    /// for each page, so that many listings started together spread out their requests, and is
+    /// exactly `start` if the range is empty. Pacing a scan of a huge bucket this way can keep it
+    /// under the request rate that gets it throttled, rather than relying on retries once it has
+    /// been. The default is no delay.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn list_objects_page_delay(mut self, list_objects_page_delay: Range<Duration>) -> Self {
+        self.list_objects_page_delay = list_objects_page_delay;
+        self
+    }
+
+    /// Set the largest response body the client will buffer in memory, for requests like
+    /// ListObjectsV2 that parse the whole body once it has arrived. Requests whose body grows past
+    /// the limit are canceled and fail with [S3RequestError::ResponseTooLarge], so that a
+    /// misbehaving endpoint or proxy can't exhaust memory. Object bodies from GetObject are
+    /// streamed and aren't limited. The default is 256 MiB.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn max_response_body_bytes(mut self, max_response_body_bytes: usize) -> Self {
+        self.max_response_body_bytes = max_response_body_bytes;
+        self
+    }
+
+    /// Set how many redirects a GetObject request follows. When a GET gets a redirect response
+    /// (301, 302, 303, 307, or 308) with a `Location` header, the client sends the request again
+    /// to that location, signed for the new host with the client's usual signing configuration, up
+    /// to this many times before failing with [S3RequestError::TooManyRedirects]. Only redirects to
+    /// the same host, possibly on another port, or to one of the bucket's virtual-hosted S3
+    /// endpoints (like `bucket.s3.us-west-2.amazonaws.com`) are followed, so that signed requests
+    /// and session tokens aren't sent to other hosts. Redirects from HTTPS to plain HTTP are never
+    /// followed either, and redirects are only followed before any of the body has been returned.
+    ///
+    /// The default is 0, which doesn't follow redirects, as blindly following them sends signed
+    /// requests to hosts the client wasn't configured for. Only enable this for endpoints that are
+    /// trusted to redirect, like some S3-compatible storage. Redirects from S3 to the bucket's
+    /// region are reported as [S3RequestError::IncorrectRegion] either way, since they have no
+    /// `Location` header.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn max_get_redirects(mut self, max_get_redirects: u32) -> Self {
+        self.max_get_redirects = max_get_redirects;
+        self
+    }
+
+    /// Set the most object body bytes the client downloads with GetObject requests, across all of
+    /// its requests and every handle that shares it. Once the total delivered passes the budget,
+    /// the request that passed it is canceled, and it and any later GetObject requests fail with
+    /// [S3RequestError::EgressBudgetExceeded]. The bytes counted are those received from S3,
+    /// before any decoding of the body, and include those of the request that passed the budget.
+    /// This is a guardrail against runaway downloads, not a precise quota: requests in flight at
+    /// the same time can each receive a part of the body before the budget is found to be
+    /// exceeded. Use [S3CrtClient::egress_bytes] to see how much of the budget is used. The default
+    /// is no budget.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn egress_byte_budget(mut self, egress_byte_budget: u64) -> Self {
+        self.egress_byte_budget = Some(egress_byte_budget);
+        self
+    }
+
+    /// Set the region to sign requests for, overriding the signing region from endpoint
+    /// resolution. This is only needed when the signing region differs from the region in the
+    /// endpoint configuration, such as for some cross-region access points.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn signing_region(mut self, signing_region: impl Into<String>) -> Self {
+        self.signing_region = Some(signing_region.into());
+        self
+    }
+
+    /// Set the algorithm to sign requests with, overriding the algorithm from endpoint resolution.
+    /// Endpoint resolution already chooses SigV4A for multi-region access point ARNs, so this is
+    /// only needed when addressing a multi-region access point some other way, such as through a
+    /// custom endpoint. With SigV4A, requests are signed for all regions (`*`) unless a
+    /// [signing_region](Self::signing_region) is also set.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn signing_algorithm(mut self, signing_algorithm: SigningAlgorithm) -> Self {
+        self.signing_algorithm = Some(signing_algorithm);
+        self
+    }
+
+    /// Set the name of the service to sign requests for, overriding the service from endpoint
+    /// resolution, which is `s3` (or `s3-outposts` or `s3express` for those endpoints). This is
+    /// only needed when requests go through a service that checks the signature itself before
+    /// forwarding them, like an Amazon API Gateway private API in front of S3, which expects
+    /// `execute-api`.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn signing_service(mut self, signing_service: impl Into<String>) -> Self {
+        self.signing_service = Some(signing_service.into());
+        self
+    }
+
+    /// Set whether to use S3 Express One Zone session authentication. The default is `true`.
+    ///
+    /// Endpoint resolution recognizes directory bucket names, which end in `--x-s3`, and requests
+    /// to them are authenticated with session credentials that the CRT obtains with CreateSession
+    /// requests and refreshes before they expire. With this set to `false`, requests to directory
+    /// buckets are signed with SigV4 instead, which is only useful for testing, since S3 rejects
+    /// most such requests.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn express_support(mut self, express_support: bool) -> Self {
+        self.express_support = express_support;
+        self
+    }
+
+    /// Set additional HTTP response statuses to retry, on top of the ones the CRT retries by
+    /// default. This helps with intermediaries like gateways in front of S3 that fail with statuses
+    /// the CRT doesn't consider retryable. Only 5xx server errors can be retried: 4xx client errors
+    /// are never retried regardless, and client creation fails if this set contains any status
+    /// outside 500-599.
+    ///
+    /// These retries apply to the requests that are safe to repeat and don't stream a body:
+    /// DeleteObject, GetObjectAttributes, HeadObject, [`object_exists`](ObjectClient::object_exists),
+    /// and ListObjectsV2. Each request is attempted at most
+    /// [max_attempts](Self::max_attempts) times for these statuses, with exponential backoff. This
+    /// includes any retries the CRT makes itself, so adding a status the CRT already retries, like
+    /// 503, doesn't multiply the attempts.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn additional_retryable_statuses(mut self, additional_retryable_statuses: HashSet<u16>) -> Self {
+        self.additional_retryable_statuses = additional_retryable_statuses;
+        self
+    }
+
+    /// Set the HTTP proxy to connect to S3 through. If no proxy is set, the client uses the proxy
+    /// in the `HTTPS_PROXY` environment variable, if any, unless the `NO_PROXY` environment
+    /// variable excludes the S3 endpoint. Client creation fails if `HTTPS_PROXY` isn't a valid
+    /// proxy URL.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Set the TLS configuration for connections to S3, for example to trust a custom certificate
+    /// authority or to authenticate with a client certificate. Client creation fails if a
+    /// certificate file in the configuration can't be read.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn tls_config(mut self, tls_config: TlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    /// Set the clock that [presigned URLs](S3CrtClient::presign_url) are signed with, instead of
+    /// the system clock, for example to pin the signing time in tests. The CRT always signs the
+    /// requests the client sends itself with the system clock.
+    #[must_use = "S3ClientConfig follows a builder pattern"]
+    pub fn clock_source(mut self, clock_source: impl ClockSource + 'static) -> Self {
+        self.clock_source = Arc::new(clock_source);
+        self
+    }
+}
+
+/// Checksum validation mode for GetObject requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChecksumMode {
+    /// Do not validate checksums of GetObject responses
+    #[default]
+    Disabled,
+    /// Ask S3 to return the object's checksum and validate the response against it. If the object
+    /// was not uploaded with a checksum, the response is not validated. A mismatch fails the request
+    /// with [S3RequestError::ChecksumValidationError].
+    Enabled,
 }
 
 /// Authentication configuration for the CRT-based S3 client
@@ -204,6 +836,16 @@ pub enum S3ClientAuthConfig {
     Provider(CredentialsProvider),
 }
 
+/// The observer set with [S3ClientConfig::credentials_observer]
+#[derive(Clone)]
+struct CredentialsObserver(Arc<dyn ObserveCredentials>);
+
+impl std::fmt::Debug for CredentialsObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialsObserver").finish_non_exhaustive()
+    }
+}
+
 /// An S3 client that uses the [AWS Common Runtime (CRT)][crt] to make requests.
 ///
 /// The AWS CRT is a C library that provides a common set of functionality for AWS SDKs. Its S3
@@ -216,83 +858,523 @@ pub enum S3ClientAuthConfig {
 #[derive(Debug, Clone)]
 pub struct S3CrtClient {
     inner: Arc<S3CrtClientInner>,
+    overall_request_timeout: Option<Duration>,
+    optional_object_attributes: Arc<[OptionalObjectAttribute]>,
+    request_context: Option<Arc<str>>,
 }
 
 impl S3CrtClient {
+    /// Start configuring a new S3 client. The returned [S3ClientConfig] is a builder; call
+    /// [build](S3ClientConfig::build) on it to create the client.
+    pub fn builder() -> S3ClientConfig {
+        S3ClientConfig::new()
+    }
+
     /// Construct a new S3 client with the given configuration.
     pub fn new(config: S3ClientConfig) -> Result<Self, NewClientError> {
+        let overall_request_timeout = config.overall_request_timeout;
         Ok(Self {
             inner: Arc::new(S3CrtClientInner::new(config)?),
+            overall_request_timeout,
+            optional_object_attributes: Arc::new([OptionalObjectAttribute::RestoreStatus]),
+            request_context: None,
         })
     }
 
+    /// Return a handle to this client that applies the given timeout to each of its operations,
+    /// overriding [`S3ClientConfig::overall_request_timeout`]. `None` means operations never time
+    /// out. Without this override, operations use the client-wide timeout, which is unset by
+    /// default. An operation that takes too long is canceled in the CRT, which releases its
+    /// connection, and then fails with [`S3RequestError::Timeout`]. Like the client-wide setting,
+    /// this doesn't apply to PutObject requests.
+    pub fn with_request_timeout(&self, timeout: Option<Duration>) -> Self {
+        Self {
+            overall_request_timeout: timeout,
+            ..self.clone()
+        }
+    }
+
+    /// Return a handle to this client that requests the given optional object attributes in its
+    /// ListObjectsV2 requests, in the `x-amz-optional-object-attributes` header. An empty set sends
+    /// no header at all. The default is [`OptionalObjectAttribute::RestoreStatus`], which listings
+    /// need to report the [restore status](crate::types::ObjectInfo::restore_status) of objects.
+    ///
+    /// Asking for the restore status makes listings slower and their responses larger, so callers
+    /// that don't need it can pass an empty set, for example `client.with_optional_object_attributes(&[])`.
+    /// Objects in those listings have a `restore_status` of `None`, even if they're archived.
+    ///
+    /// Some S3-compatible endpoints reject the header with a 400 response. If a listing that asks
+    /// for optional attributes fails that way but succeeds without them, the client logs a warning
+    /// and stops asking for them in every later listing, so listings degrade to leaving out the
+    /// attributes rather than failing.
+    pub fn with_optional_object_attributes(&self, attributes: &[OptionalObjectAttribute]) -> Self {
+        Self {
+            optional_object_attributes: attributes.into(),
+            ..self.clone()
+        }
+    }
+
+    /// Return a handle to this client that records the given context, such as a correlation ID for
+    /// the user request that led to these S3 requests, in the `context` field of the tracing span
+    /// of each of its requests. `None` records no context, which is also the default. Like
+    /// [with_request_timeout](Self::with_request_timeout), the handle shares the underlying S3 client,
+    /// so it is cheap to create one for each user request.
+    pub fn with_request_context(&self, context: Option<&str>) -> Self {
+        Self {
+            request_context: context.map(Into::into),
+            ..self.clone()
+        }
+    }
+
     /// Return a copy of the [EndpointConfig] for this client
     pub fn endpoint_config(&self) -> EndpointConfig {
         self.inner.endpoint_config.clone()
     }
 
-    #[doc(hidden)]
-    pub fn event_loop_group(&self) -> EventLoopGroup {
-        self.inner.event_loop_group.clone()
+    /// Return the current stats of the client's connection pools: how many connections are open
+    /// and idle, and how many requests are waiting for a connection. This only reads counters from
+    /// the CRT, so it's cheap enough to poll periodically.
+    pub fn connection_stats(&self) -> ConnectionStats {
+        self.inner.s3_client.poll_connection_stats()
     }
-}
-
-#[derive(Debug)]
-struct S3CrtClientInner {
-    s3_client: Client,
-    event_loop_group: EventLoopGroup,
-    endpoint_config: EndpointConfig,
-    allocator: Allocator,
-    next_request_counter: AtomicU64,
-    /// user_agent_header will be passed into CRT which add additional information "CRTS3NativeClient/0.1.x".
-    /// Here it will add the user agent prefix and s3 client information.
-    user_agent_header: String,
-    request_payer: Option<String>,
-    part_size: usize,
-    bucket_owner: Option<String>,
-    credentials_provider: Option<CredentialsProvider>,
-    host_resolver: HostResolver,
-}
-
-impl S3CrtClientInner {
-    fn new(config: S3ClientConfig) -> Result<Self, NewClientError> {
-        let allocator = Allocator::default();
 
-        let mut event_loop_group = EventLoopGroup::new_default(&allocator, None, || {}).unwrap();
+    /// Return the number of object body bytes the client has downloaded with GetObject requests,
+    /// as counted against its [egress byte budget](S3ClientConfig::egress_byte_budget). It's
+    /// counted whether or not the client has a budget.
+    pub fn egress_bytes(&self) -> u64 {
+        self.inner.egress_bytes.load(Ordering::SeqCst)
+    }
 
-        let resolver_options = HostResolverDefaultOptions {
-            max_entries: 8,
-            event_loop_group: &mut event_loop_group,
+    /// Shut down the client. New requests on this client and every handle that shares it fail with
+    /// [`S3RequestError::ClientShutDown`], and requests already in flight have until `timeout` to
+    /// finish, after which they're canceled.
+    ///
+    /// If this is the last handle to the client, this then releases the client and waits (for the
+    /// rest of the timeout) for its event loop threads to exit. They only exit once nothing else
+    /// holds the event loop group, including outstanding request streams and
+    /// [event_loop_group](Self::event_loop_group) handles, so drop those first for a clean
+    /// shutdown.
+    pub async fn shutdown(self, timeout: Duration) -> ShutdownReport {
+        const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+        // A timeout too long to represent never expires
+        let deadline = Instant::now().checked_add(timeout);
+        let in_flight = {
+            let mut in_flight_requests = self.inner.in_flight_requests.lock().unwrap();
+            in_flight_requests.shutting_down = true;
+            in_flight_requests.requests.len()
         };
+        debug!(in_flight, ?timeout, "shutting down S3 client");
+
+        let mut canceled = None;
+        loop {
+            let (remaining, meta_requests): (usize, Vec<MetaRequest>) = {
+                let in_flight_requests = self.inner.in_flight_requests.lock().unwrap();
+                if in_flight_requests.requests.is_empty() {
+                    break;
+                }
+                let meta_requests = in_flight_requests.requests.values().flatten().cloned().collect();
+                (in_flight_requests.requests.len(), meta_requests)
+            };
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                if canceled.is_none() {
+                    debug!(
+                        remaining,
+                        "canceling requests that didn't finish before the shutdown timeout"
+                    );
+                    canceled = Some(remaining);
+                }
+                // Cancel on every poll, in case a request was still being created the first time
+                for meta_request in &meta_requests {
+                    meta_request.cancel();
+                }
+            }
+            // Canceled requests still have to wait for the CRT to finish them
+            if !matches!(self.sleep::<Infallible>(POLL_INTERVAL).await, Ok(true)) {
+                break;
+            }
+        }
+        let canceled = canceled.unwrap_or(0);
 
-        let mut host_resolver = HostResolver::new_default(&allocator, &resolver_options).unwrap();
-
-        let bootstrap_options = ClientBootstrapOptions {
-            event_loop_group: &mut event_loop_group,
-            host_resolver: &mut host_resolver,
+        // Dropping the last handle releases the client's event loop group
+        let event_loop_group_shutdown = match Arc::try_unwrap(self.inner) {
+            Ok(inner) => Some(inner.event_loop_group_shutdown.into_inner().unwrap()),
+            Err(_) => None,
         };
-
-        let mut client_bootstrap = ClientBootstrap::new(&allocator, &bootstrap_options).unwrap();
-
-        let mut client_config = ClientConfig::new();
-
-        let retry_strategy = {
-            let mut retry_strategy_options = StandardRetryOptions::default(&mut event_loop_group);
-            let max_attempts = std::env::var("AWS_MAX_ATTEMPTS")
-                .ok()
-                .and_then(|s| s.parse::<usize>().ok())
-                .or_else(|| config.max_attempts.map(|m| m.get()))
-                .unwrap_or(3);
-            // Max *attempts* includes the initial attempt, the CRT's max *retries* does not, so
-            // decrement by one
-            retry_strategy_options.backoff_retry_options.max_retries = max_attempts.saturating_sub(1);
-            retry_strategy_options.backoff_retry_options.backoff_scale_factor = Duration::from_millis(500);
-            retry_strategy_options.backoff_retry_options.jitter_mode = ExponentialBackoffJitterMode::Full;
-            RetryStrategy::standard(&allocator, &retry_strategy_options).unwrap()
+        let event_loop_group_shut_down = match event_loop_group_shutdown {
+            Some(event_loop_group_shutdown) => {
+                // The channel is a blocking one, so wait on another thread rather than blocking
+                // this task
+                let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+                let (tx, rx) = oneshot::channel();
+                std::thread::spawn(move || {
+                    let shut_down = match remaining {
+                        Some(remaining) => event_loop_group_shutdown.recv_timeout(remaining).is_ok(),
+                        None => event_loop_group_shutdown.recv().is_ok(),
+                    };
+                    let _ = tx.send(shut_down);
+                });
+                rx.await.unwrap_or(false)
+            }
+            None => false,
         };
 
-        trace!("constructing client with auth config {:?}", config.auth_config);
-        let credentials_provider = match config.auth_config {
+        ShutdownReport {
+            completed: in_flight - canceled,
+            canceled,
+            event_loop_group_shut_down,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn event_loop_group(&self) -> EventLoopGroup {
+        self.inner.event_loop_group.clone()
+    }
+
+    /// Create a new HTTP request template for the given HTTP method and S3 bucket name, using this
+    /// handle's timeout. See [S3CrtClientInner::new_request_template].
+    fn new_request_template(&self, method: &str, bucket: &str) -> Result<S3Message, ConstructionError> {
+        let mut message = self.inner.new_request_template(method, bucket)?;
+        message.request_timeouts.overall = self.overall_request_timeout;
+        Ok(message)
+    }
+
+    /// Run a request that's safe to repeat, retrying it while it fails with one of the client's
+    /// [additional retryable statuses](S3ClientConfig::additional_retryable_statuses). Each attempt
+    /// has already been retried by the CRT for the failures the CRT considers retryable, so the
+    /// requests the CRT made count towards `max_attempts` too.
+    async fn with_additional_retries<T, E, Fut>(
+        &self,
+        mut request: impl FnMut() -> Fut,
+    ) -> ObjectClientResult<T, E, S3RequestError>
+    where
+        Fut: Future<Output = ObjectClientResult<T, E, S3RequestError>>,
+    {
+        let mut attempt: u32 = 1;
+        let mut requests_made: usize = 0;
+        loop {
+            let result = request().await;
+            let (status, num_requests) = match &result {
+                Err(ObjectClientError::ClientError(S3RequestError::ResponseError(result))) => {
+                    (result.response_status, result.metrics.num_requests)
+                }
+                _ => return result,
+            };
+            requests_made += num_requests.max(1) as usize;
+            let retryable = u16::try_from(status).is_ok_and(|s| self.inner.additional_retryable_statuses.contains(&s));
+            if !retryable || requests_made >= self.inner.max_attempts {
+                return result;
+            }
+
+            let backoff = retry_backoff(attempt, self.inner.initial_backoff, self.inner.max_backoff);
+            debug!(
+                status,
+                attempt,
+                ?backoff,
+                "retrying request that failed with a retryable status"
+            );
+            metrics::counter!("s3.client.additional_retries", "status" => status.to_string()).increment(1);
+            if !self.sleep(backoff).await? {
+                return result;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Run a ListObjectsV2 request for one page of a listing, retrying it with the same
+    /// continuation token up to `page_retries` times while it fails with a transient error.
+    async fn list_objects_with_page_retries(
+        &self,
+        bucket: &str,
+        continuation_token: Option<&str>,
+        delimiter: Option<&str>,
+        max_keys: usize,
+        prefix: &str,
+        page_retries: u32,
+    ) -> ObjectClientResult<ListObjectsResponse, ListObjectsError, S3RequestError> {
+        if let Some(continuation_token) = continuation_token {
+            self.inner
+                .continuation_tokens
+                .check(continuation_token, prefix, delimiter);
+            let delay = jittered_delay(&self.inner.list_objects_page_delay);
+            if !delay.is_zero() {
+                trace!(?delay, "delaying ListObjectsV2 page");
+                self.sleep(delay).await?;
+            }
+        }
+
+        let mut attempt: u32 = 1;
+        loop {
+            let result = self
+                .with_additional_retries(|| {
+                    self.list_objects_request(bucket, continuation_token, delimiter, max_keys, prefix)
+                })
+                .await;
+            match &result {
+                Err(ObjectClientError::ClientError(e)) if e.is_transient() && attempt <= page_retries => {}
+                _ => return result,
+            }
+
+            let backoff = retry_backoff(attempt, self.inner.initial_backoff, self.inner.max_backoff);
+            debug!(
+                attempt,
+                ?backoff,
+                ?continuation_token,
+                error = ?result.as_ref().err(),
+                "retrying ListObjectsV2 page that failed with a transient error"
+            );
+            metrics::counter!("s3.client.list_objects_page_retries").increment(1);
+            if !self.sleep(backoff).await? {
+                return result;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Wait for the given duration on one of the client's event loops. Returns `false` if the timer
+    /// failed, in which case the caller shouldn't rely on the wait having happened.
+    async fn sleep<E>(&self, duration: Duration) -> ObjectClientResult<bool, E, S3RequestError> {
+        let event_loop = self
+            .inner
+            .event_loop_group
+            .get_next_loop()
+            .map_err(|e| ObjectClientError::ClientError(e.into()))?;
+        Ok(EventLoopTimer::new(&event_loop, duration).await.is_ok())
+    }
+}
+
+/// A random delay in the given range, or its start if the range is empty. The randomness only
+/// spreads out delays, so the random keys of the standard library's hasher are good enough.
+fn jittered_delay(range: &Range<Duration>) -> Duration {
+    let width = u64::try_from(range.end.saturating_sub(range.start).as_nanos()).unwrap_or(u64::MAX);
+    if width == 0 {
+        return range.start;
+    }
+    let random = RandomState::new().build_hasher().finish();
+    range.start + Duration::from_nanos(random % width)
+}
+
+/// The most attempts of a request the client can be configured with. The CRT's exponential backoff
+/// allows at most 63 retries.
+const MAX_ATTEMPTS_LIMIT: usize = 64;
+
+/// The options for the CRT's standard retry strategy for a client that makes at most
+/// `max_attempts` attempts of each request, which must be more than one.
+fn standard_retry_options<'a>(
+    config: &S3ClientConfig,
+    max_attempts: usize,
+    event_loop_group: &'a mut EventLoopGroup,
+) -> StandardRetryOptions<'a> {
+    let mut retry_strategy_options = StandardRetryOptions::default(event_loop_group);
+    // Max *attempts* includes the initial attempt, the CRT's max *retries* does not, so
+    // decrement by one
+    retry_strategy_options.backoff_retry_options.max_retries = max_attempts - 1;
+    retry_strategy_options.backoff_retry_options.backoff_scale_factor = config.initial_backoff;
+    retry_strategy_options.backoff_retry_options.max_backoff = config.max_backoff;
+    retry_strategy_options.backoff_retry_options.jitter_mode = config.backoff_jitter_mode;
+    retry_strategy_options
+}
+
+/// A token bucket of the retries the client has left, shared by all of its requests. See
+/// [S3ClientConfig::retry_budget].
+#[derive(Debug)]
+struct RetryBudget {
+    capacity: f64,
+    refill: f64,
+    tokens: Mutex<f64>,
+}
+
+impl RetryBudget {
+    fn new(capacity: NonZeroUsize, refill: f64) -> Self {
+        let capacity = capacity.get() as f64;
+        Self {
+            capacity,
+            refill,
+            tokens: Mutex::new(capacity),
+        }
+    }
+
+    /// Take the token for one retry, or return `false` if the budget is exhausted.
+    fn try_acquire(&self) -> bool {
+        let mut tokens = self.tokens.lock().unwrap();
+        if *tokens < 1.0 {
+            return false;
+        }
+        *tokens -= 1.0;
+        true
+    }
+
+    /// Return tokens for a request that succeeded.
+    fn replenish(&self) {
+        let mut tokens = self.tokens.lock().unwrap();
+        *tokens = (*tokens + self.refill).min(self.capacity);
+    }
+}
+
+/// The exponential backoff before retrying a request that failed on the given attempt (starting
+/// from 1).
+fn retry_backoff(attempt: u32, initial_backoff: Duration, max_backoff: Duration) -> Duration {
+    initial_backoff
+        .saturating_mul(2u32.saturating_pow(attempt - 1))
+        .min(max_backoff)
+}
+
+#[derive(Debug)]
+struct S3CrtClientInner {
+    s3_client: Client,
+    event_loop_group: EventLoopGroup,
+    endpoint_config: EndpointConfig,
+    allocator: Allocator,
+    next_request_counter: AtomicU64,
+    /// user_agent_header will be passed into CRT which add additional information "CRTS3NativeClient/0.1.x".
+    /// Here it will add the user agent prefix and s3 client information.
+    user_agent_header: String,
+    request_payer: Option<String>,
+    part_size: usize,
+    /// Ranged GetObject requests for fewer bytes than this are sent as a single GET
+    parallel_get_threshold: u64,
+    bucket_owner: Option<String>,
+    default_headers: Vec<(String, String)>,
+    credentials_provider: Option<CredentialsProvider>,
+    host_resolver: HostResolver,
+    checksum_mode: ChecksumMode,
+    endpoint_cache: EndpointCache,
+    ranged_get_existence_check: bool,
+    request_timeouts: RequestTimeouts,
+    max_active_connections: Option<u32>,
+    list_objects_start_after_fallback: bool,
+    /// Number of times to retry a ListObjectsV2 page that fails with a transient error
+    list_objects_page_retries: u32,
+    /// Number of times to retry an UploadPart request that fails with a transient error
+    upload_part_retries: u32,
+    /// Range of the random delay before requesting each ListObjectsV2 page after the first
+    list_objects_page_delay: Range<Duration>,
+    /// Largest response body to buffer for requests that parse the whole body
+    max_response_body_bytes: usize,
+    /// Number of redirects a GetObject request follows
+    max_get_redirects: u32,
+    /// Most object body bytes to download with GetObject requests, if limited
+    egress_byte_budget: Option<u64>,
+    /// Object body bytes downloaded with GetObject requests so far
+    egress_bytes: Arc<AtomicU64>,
+    /// Parameters of the listings continuation tokens were returned for, checked in debug builds
+    continuation_tokens: ContinuationTokens,
+    /// Set once the endpoint has rejected a ListObjectsV2 request for asking for optional object
+    /// attributes, after which listings stop asking for them
+    optional_object_attributes_unsupported: AtomicBool,
+    /// Region to sign requests for instead of the one from the resolved auth scheme
+    signing_region: Option<String>,
+    /// Algorithm to sign requests with instead of the one from the resolved auth scheme
+    signing_algorithm: Option<SigningAlgorithm>,
+    /// Service to sign requests for instead of the one from the resolved auth scheme
+    signing_service: Option<String>,
+    /// Whether requests to directory buckets use S3 Express One Zone session authentication
+    express_support: bool,
+    /// Response statuses to retry requests on that the CRT doesn't retry itself
+    additional_retryable_statuses: HashSet<u16>,
+    /// Source of the signing time for presigned URLs
+    clock_source: Arc<dyn ClockSource>,
+    max_attempts: usize,
+    /// Retries the client has left, if it has a retry budget
+    retry_budget: Option<Arc<RetryBudget>>,
+    /// Backoff before the first of the client's own retries
+    initial_backoff: Duration,
+    /// Maximum backoff between the client's own retries
+    max_backoff: Duration,
+    /// Buckets whose region has been verified, if the client verifies bucket regions
+    region_verified_buckets: Option<Mutex<HashSet<String>>>,
+    validate_bucket_names: bool,
+    /// Meta requests that haven't finished yet, so that shutdown can wait for them
+    in_flight_requests: Arc<Mutex<InFlightRequests>>,
+    /// Receives a message once the event loop group has shut down and its threads have exited
+    event_loop_group_shutdown: Mutex<std::sync::mpsc::Receiver<()>>,
+}
+
+/// The meta requests a client has in flight, keyed by an ID unique to the client. A request's
+/// [MetaRequest] is `None` until the CRT has accepted it.
+#[derive(Debug, Default)]
+struct InFlightRequests {
+    requests: HashMap<u64, Option<MetaRequest>>,
+    next_id: u64,
+    shutting_down: bool,
+}
+
+impl InFlightRequests {
+    /// Record a new request, unless the client is shutting down.
+    fn register(&mut self) -> Result<u64, S3RequestError> {
+        if self.shutting_down {
+            return Err(S3RequestError::ClientShutDown);
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.requests.insert(id, None);
+        Ok(id)
+    }
+}
+
+impl S3CrtClientInner {
+    fn new(config: S3ClientConfig) -> Result<Self, NewClientError> {
+        let allocator = Allocator::default();
+
+        let (event_loop_group_shutdown_tx, event_loop_group_shutdown) = std::sync::mpsc::channel();
+        let mut event_loop_group = EventLoopGroup::new_default(&allocator, None, move || {
+            let _ = event_loop_group_shutdown_tx.send(());
+        })
+        .unwrap();
+
+        let resolver_options = HostResolverDefaultOptions {
+            max_entries: 8,
+            event_loop_group: &mut event_loop_group,
+        };
+
+        let mut host_resolver = HostResolver::new_default(&allocator, &resolver_options).unwrap();
+
+        let bootstrap_options = ClientBootstrapOptions {
+            event_loop_group: &mut event_loop_group,
+            host_resolver: &mut host_resolver,
+            host_resolution_mode: config.host_resolution_mode,
+        };
+
+        let mut client_bootstrap = ClientBootstrap::new(&allocator, &bootstrap_options).unwrap();
+
+        let mut client_config = ClientConfig::new();
+
+        let max_attempts = std::env::var("AWS_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|s| s.parse::<usize>().ok())
+            .or_else(|| config.max_attempts.map(|m| m.get()))
+            .unwrap_or(3);
+        if !(1..=MAX_ATTEMPTS_LIMIT).contains(&max_attempts) {
+            return Err(NewClientError::InvalidConfiguration(format!(
+                "max attempts must be between 1 and {MAX_ATTEMPTS_LIMIT}"
+            )));
+        }
+        if config.initial_backoff < Duration::from_millis(1) {
+            return Err(NewClientError::InvalidConfiguration(
+                "initial backoff must be at least 1ms".to_string(),
+            ));
+        }
+        if config.max_backoff < Duration::from_secs(1) {
+            return Err(NewClientError::InvalidConfiguration(
+                "max backoff must be at least 1s".to_string(),
+            ));
+        }
+        if !(config.retry_budget_refill.is_finite() && config.retry_budget_refill >= 0.0) {
+            return Err(NewClientError::InvalidConfiguration(
+                "retry budget refill must be a finite, non-negative number".to_string(),
+            ));
+        }
+        let retry_strategy = if max_attempts == 1 {
+            RetryStrategy::no_retry(&allocator).unwrap()
+        } else {
+            let retry_strategy_options = standard_retry_options(&config, max_attempts, &mut event_loop_group);
+            RetryStrategy::standard(&allocator, &retry_strategy_options).unwrap()
+        };
+
+        trace!("constructing client with auth config {:?}", config.auth_config);
+        // Anonymous credentials never refresh or fail, so there's nothing to observe
+        let observe_credentials = !matches!(config.auth_config, S3ClientAuthConfig::NoSigning);
+        let credentials_provider = match config.auth_config {
             S3ClientAuthConfig::Default => {
                 let credentials_chain_default_options = CredentialsProviderChainDefaultOptions {
                     bootstrap: &mut client_bootstrap,
@@ -313,14 +1395,27 @@ impl S3CrtClientInner {
             }
             S3ClientAuthConfig::Provider(provider) => provider,
         };
+        let credentials_provider = match config.credentials_observer {
+            Some(CredentialsObserver(observer)) if observe_credentials => {
+                CredentialsProvider::new_observed(&allocator, credentials_provider, observer)
+                    .map_err(NewClientError::ProviderFailure)?
+            }
+            _ => credentials_provider,
+        };
 
         let endpoint_config = config.endpoint_config;
         client_config.region(endpoint_config.get_region());
+        let signing_region = config.signing_region.or_else(|| {
+            // SigV4A signs for a set of regions rather than a single one
+            (config.signing_algorithm == Some(SigningAlgorithm::SigV4A)).then(|| "*".to_owned())
+        });
         let signing_config = init_signing_config(
-            endpoint_config.get_region(),
+            signing_region
+                .as_deref()
+                .unwrap_or_else(|| endpoint_config.get_region()),
             credentials_provider.clone(),
-            None,
-            None,
+            config.signing_algorithm,
+            config.signing_service.as_deref(),
             None,
         );
 
@@ -339,7 +1434,27 @@ impl S3CrtClientInner {
             Some(_) => endpoint_config,
         };
 
-        client_config.express_support(true);
+        let proxy = match config.proxy {
+            Some(proxy) => Some(proxy),
+            None => {
+                let endpoint_host = match endpoint_config.get_endpoint() {
+                    Some(uri) => uri.host_name().to_string_lossy().into_owned(),
+                    None => format!("s3.{}.amazonaws.com", endpoint_config.get_region()),
+                };
+                ProxyConfig::from_env(&endpoint_host)?
+            }
+        };
+        if let Some(proxy) = proxy {
+            debug!(?proxy, "connecting to S3 through proxy");
+            client_config.proxy_options(proxy.to_proxy_options(&allocator)?);
+        }
+
+        if let Some(tls_config) = &config.tls_config {
+            debug!(?tls_config, "connecting to S3 with custom TLS configuration");
+            client_config.tls_connection_options(tls_config.to_tls_connection_options(&allocator)?);
+        }
+
+        client_config.express_support(config.express_support);
         client_config.read_backpressure(config.read_backpressure);
         client_config.initial_read_window(config.initial_read_window);
         client_config.signing_config(signing_config);
@@ -348,8 +1463,82 @@ impl S3CrtClientInner {
             .client_bootstrap(client_bootstrap)
             .retry_strategy(retry_strategy);
 
+        if !(config.throughput_target_gbps.is_finite() && config.throughput_target_gbps > 0.0) {
+            return Err(NewClientError::InvalidConfiguration(format!(
+                "throughput target must be a positive number of Gbps, but was {}",
+                config.throughput_target_gbps
+            )));
+        }
         client_config.throughput_target_gbps(config.throughput_target_gbps);
 
+        if let Some(max_active_connections) = config.max_active_connections {
+            // The CRT treats zero as unset
+            if max_active_connections == 0 {
+                return Err(NewClientError::InvalidConfiguration(
+                    "max active connections must be at least 1".to_string(),
+                ));
+            }
+            client_config.max_active_connections_override(max_active_connections);
+        }
+
+        if !config.network_interface_names.is_empty() {
+            if config.network_interface_names.iter().any(|name| name.is_empty()) {
+                return Err(NewClientError::InvalidConfiguration(
+                    "network interface names must not be empty".to_string(),
+                ));
+            }
+            if !ClientConfig::supports_network_interface_names() {
+                return Err(NewClientError::InvalidConfiguration(
+                    "the CRT this client was built with can't bind connections to network interfaces".to_string(),
+                ));
+            }
+            debug!(network_interface_names = ?config.network_interface_names, "binding connections to network interfaces");
+            client_config.network_interface_names(config.network_interface_names.clone());
+        }
+
+        if let Some(status) = config
+            .additional_retryable_statuses
+            .iter()
+            .find(|status| !(500..=599).contains(*status))
+        {
+            return Err(NewClientError::InvalidConfiguration(format!(
+                "only 5xx statuses can be retried, but {status} was configured as retryable"
+            )));
+        }
+
+        if let (Some(time_to_first_byte), Some(overall)) =
+            (config.time_to_first_byte_timeout, config.overall_request_timeout)
+        {
+            if time_to_first_byte > overall {
+                return Err(NewClientError::InvalidConfiguration(format!(
+                    "time to first byte timeout ({time_to_first_byte:?}) can't be longer than the overall request timeout ({overall:?})"
+                )));
+            }
+        }
+
+        if let Some(connect_timeout) = config.connect_timeout {
+            // The CRT treats a zero timeout as unset, so round sub-millisecond timeouts up
+            let connect_timeout_ms = u32::try_from(connect_timeout.as_millis()).unwrap_or(u32::MAX);
+            client_config.connect_timeout_ms(connect_timeout_ms.max(1));
+        }
+
+        if let Some(tcp_keep_alive) = config.tcp_keep_alive {
+            // The CRT silently ignores the interval or the probe timeout if the other is missing
+            if (tcp_keep_alive.keep_alive_interval_sec == 0) != (tcp_keep_alive.keep_alive_timeout_sec == 0) {
+                return Err(NewClientError::InvalidConfiguration(format!(
+                    "TCP keepalive interval and probe timeout must both be set or both be zero: {tcp_keep_alive:?}"
+                )));
+            }
+            client_config.tcp_keep_alive_options(Some(tcp_keep_alive));
+        }
+
+        if config.list_objects_page_delay.start > config.list_objects_page_delay.end {
+            return Err(NewClientError::InvalidConfiguration(format!(
+                "list objects page delay range is reversed: {:?}",
+                config.list_objects_page_delay
+            )));
+        }
+
         // max_part_size is 5GB or less depending on the platform (4GB on 32-bit)
         let max_part_size = cmp::min(5_u64 * 1024 * 1024 * 1024, usize::MAX as u64) as usize;
         if !(5 * 1024 * 1024..=max_part_size).contains(&config.part_size) {
@@ -360,8 +1549,48 @@ impl S3CrtClientInner {
         }
         client_config.part_size(config.part_size);
 
+        // The CRT rejects memory limits below 1GiB, other than 0 for its default
+        if config.memory_limit_in_bytes != 0 && config.memory_limit_in_bytes < 1024 * 1024 * 1024 {
+            return Err(NewClientError::InvalidConfiguration(format!(
+                "memory limit must be at least 1GiB, but {} bytes were configured",
+                config.memory_limit_in_bytes
+            )));
+        }
+        client_config.memory_limit_in_bytes(config.memory_limit_in_bytes);
+
+        if let Some(multipart_upload_threshold) = config.multipart_upload_threshold {
+            // Smaller uploads are sent in a single PutObject request, which can be at most 5GiB
+            if !(1..=max_part_size).contains(&multipart_upload_threshold) {
+                return Err(NewClientError::InvalidConfiguration(format!(
+                    "multipart upload threshold must be between 1 byte and {}GiB",
+                    max_part_size / 1024 / 1024 / 1024
+                )));
+            }
+            client_config.multipart_upload_threshold(multipart_upload_threshold);
+        }
+
+        if let Some((name, _)) = config.default_headers.iter().find(|(name, _)| {
+            name.is_empty()
+                || RESERVED_HEADER_NAMES
+                    .iter()
+                    .any(|reserved| reserved.eq_ignore_ascii_case(name))
+        }) {
+            return Err(NewClientError::InvalidConfiguration(format!(
+                "{name:?} can't be set as a default header"
+            )));
+        }
+
         let user_agent = config.user_agent.unwrap_or_else(|| UserAgent::new(None));
-        let user_agent_header = user_agent.build();
+        let mut user_agent_header = user_agent.build();
+        if let Some(suffix) = config.user_agent_suffix {
+            if !is_valid_user_agent_suffix(&suffix) {
+                return Err(NewClientError::InvalidConfiguration(format!(
+                    "invalid User-agent suffix {suffix:?}: must be space-separated products of RFC 7230 tokens"
+                )));
+            }
+            user_agent_header.push(' ');
+            user_agent_header.push_str(&suffix);
+        }
 
         let s3_client = Client::new(&allocator, client_config).map_err(NewClientError::CrtError)?;
 
@@ -374,9 +1603,45 @@ impl S3CrtClientInner {
             user_agent_header,
             request_payer: config.request_payer,
             part_size: config.part_size,
+            parallel_get_threshold: config.parallel_get_threshold,
             bucket_owner: config.bucket_owner,
+            default_headers: config.default_headers,
             credentials_provider: Some(credentials_provider),
             host_resolver,
+            checksum_mode: config.checksum_mode,
+            endpoint_cache: EndpointCache::new(config.endpoint_cache_capacity),
+            ranged_get_existence_check: config.ranged_get_existence_check,
+            request_timeouts: RequestTimeouts {
+                time_to_first_byte: config.time_to_first_byte_timeout,
+                overall: config.overall_request_timeout,
+            },
+            max_active_connections: config.max_active_connections,
+            list_objects_start_after_fallback: config.list_objects_start_after_fallback,
+            list_objects_page_retries: config.list_objects_page_retries,
+            upload_part_retries: config.upload_part_retries,
+            list_objects_page_delay: config.list_objects_page_delay.clone(),
+            max_response_body_bytes: config.max_response_body_bytes,
+            max_get_redirects: config.max_get_redirects,
+            egress_byte_budget: config.egress_byte_budget,
+            egress_bytes: Default::default(),
+            continuation_tokens: Default::default(),
+            optional_object_attributes_unsupported: AtomicBool::new(false),
+            signing_region,
+            signing_algorithm: config.signing_algorithm,
+            signing_service: config.signing_service,
+            express_support: config.express_support,
+            additional_retryable_statuses: config.additional_retryable_statuses,
+            clock_source: config.clock_source,
+            max_attempts,
+            retry_budget: config
+                .retry_budget
+                .map(|size| Arc::new(RetryBudget::new(size, config.retry_budget_refill))),
+            initial_backoff: config.initial_backoff,
+            max_backoff: config.max_backoff,
+            region_verified_buckets: config.verify_region_on_init.then(Default::default),
+            validate_bucket_names: config.validate_bucket_names,
+            in_flight_requests: Default::default(),
+            event_loop_group_shutdown: Mutex::new(event_loop_group_shutdown),
         })
     }
 
@@ -385,24 +1650,30 @@ impl S3CrtClientInner {
     /// response should be XML; this header should be overwritten for requests like GET that return
     /// object data.
     fn new_request_template(&self, method: &str, bucket: &str) -> Result<S3Message, ConstructionError> {
-        let endpoint = self.endpoint_config.resolve_for_bucket(bucket)?;
-        let uri = endpoint.uri()?;
-        trace!(?uri, "resolved endpoint");
+        let CachedEndpoint { uri, auth_scheme } = self.resolve_endpoint(bucket)?;
 
+        let mut signing_region_field = None;
         let signing_config = if let Some(credentials_provider) = &self.credentials_provider {
-            let auth_scheme = match endpoint.auth_scheme() {
-                Ok(auth_scheme) => auth_scheme,
-                Err(e) => {
-                    error!(error=?e, "no auth scheme for endpoint");
-                    return Err(e.into());
-                }
+            let auth_scheme = auth_scheme.expect("auth scheme is always resolved when signing requests");
+            let algorithm = match self.signing_algorithm.unwrap_or_else(|| auth_scheme.scheme_name()) {
+                // Without session authentication, fall back to signing each request
+                SigningAlgorithm::SigV4Express if !self.express_support => SigningAlgorithm::SigV4,
+                algorithm => algorithm,
             };
-            trace!(?auth_scheme, "resolved auth scheme");
-            let algorithm = Some(auth_scheme.scheme_name());
-            let service = Some(auth_scheme.signing_name());
+            let algorithm = Some(algorithm);
+            let service = Some(
+                self.signing_service
+                    .as_deref()
+                    .unwrap_or_else(|| auth_scheme.signing_name()),
+            );
             let use_double_uri_encode = Some(!auth_scheme.disable_double_encoding());
+            let signing_region = self
+                .signing_region
+                .as_deref()
+                .unwrap_or_else(|| auth_scheme.signing_region());
+            signing_region_field = Some(signing_region.to_owned());
             Some(init_signing_config(
-                auth_scheme.signing_region(),
+                signing_region,
                 credentials_provider.clone(),
                 algorithm,
                 service,
@@ -423,28 +1694,77 @@ impl S3CrtClientInner {
 
         let mut message = Message::new_request(&self.allocator)?;
         message.set_request_method(method)?;
-        message.add_header(&Header::new("Host", hostname_header))?;
+        message.add_header(&Header::new("Host", &hostname_header))?;
         message.add_header(&Header::new("accept", "application/xml"))?;
         message.add_header(&Header::new("User-Agent", &self.user_agent_header))?;
 
+        // Set rather than add these, so a default header replaces one the client set above (and a
+        // later default one with the same name), and the client's own headers below replace a
+        // default one, rather than sending both
+        for (name, value) in &self.default_headers {
+            message.set_header(&Header::new(name, value))?;
+        }
+
         if let Some(ref payer) = self.request_payer {
-            message.add_header(&Header::new("x-amz-request-payer", payer))?;
+            message.set_header(&Header::new("x-amz-request-payer", payer))?;
         }
 
         if let Some(ref owner) = self.bucket_owner {
-            message.add_header(&Header::new("x-amz-expected-bucket-owner", owner))?;
+            message.set_header(&Header::new("x-amz-expected-bucket-owner", owner))?;
         }
 
         Ok(S3Message {
             inner: message,
             uri,
             path_prefix,
+            host: hostname_header,
             checksum_config: None,
             signing_config,
+            signing_region: signing_region_field,
+            request_timeouts: self.request_timeouts,
+            check_embedded_error: false,
+            verify_body_length: method != "HEAD",
         })
     }
 
-    fn new_meta_request_options(message: S3Message, meta_request_type: MetaRequestType) -> MetaRequestOptions {
+    /// Resolve the endpoint for the given bucket, reusing a cached resolution if there is one.
+    fn resolve_endpoint(&self, bucket: &str) -> Result<CachedEndpoint, ConstructionError> {
+        if let Some(endpoint) = self.endpoint_cache.get(bucket) {
+            return Ok(endpoint);
+        }
+
+        let endpoint = self.endpoint_config.resolve_for_bucket(bucket)?;
+        self.endpoint_cache.resolutions.fetch_add(1, Ordering::Relaxed);
+        let uri = endpoint.uri()?;
+        trace!(?uri, "resolved endpoint");
+
+        let auth_scheme = if self.credentials_provider.is_some() {
+            let auth_scheme = match endpoint.auth_scheme() {
+                Ok(auth_scheme) => auth_scheme,
+                Err(e) => {
+                    error!(error=?e, "no auth scheme for endpoint");
+                    return Err(e.into());
+                }
+            };
+            trace!(?auth_scheme, "resolved auth scheme");
+            Some(auth_scheme)
+        } else {
+            None
+        };
+
+        let endpoint = CachedEndpoint { uri, auth_scheme };
+        self.endpoint_cache.insert(bucket, endpoint.clone());
+        Ok(endpoint)
+    }
+
+    /// Turn a message into the options for a meta request, recording where it's going on the span
+    /// of the request.
+    fn new_meta_request_options(
+        message: S3Message,
+        meta_request_type: MetaRequestType,
+        request_span: &Span,
+    ) -> MetaRequestOptions {
+        message.record_endpoint(request_span);
         let mut options = MetaRequestOptions::new();
         if let Some(checksum_config) = message.checksum_config {
             options.checksum_config(checksum_config);
@@ -478,15 +1798,27 @@ impl S3CrtClientInner {
             + Send
             + 'static,
     ) -> Result<S3HttpRequest<T, E>, S3RequestError> {
-        let options = Self::new_meta_request_options(message, meta_request_type);
-        self.make_meta_request_from_options(options, request_span, |_| {}, on_headers, on_body, on_finish)
+        let request_timeouts = message.request_timeouts;
+        let options = Self::new_meta_request_options(message, meta_request_type, &request_span);
+        self.make_meta_request_from_options(
+            options,
+            request_timeouts,
+            request_span,
+            |_| {},
+            on_headers,
+            on_body,
+            on_finish,
+        )
     }
 
     /// Make an HTTP request using this S3 client that invokes the given callbacks as the request
-    /// makes progress. See [make_meta_request] for arguments.
+    /// makes progress, and cancels it if it exceeds the given timeouts. See [make_meta_request]
+    /// for the other arguments.
+    #[allow(clippy::too_many_arguments)] // Each callback is for a different stage of the request
     fn make_meta_request_from_options<T: Send + 'static, E: std::error::Error + Send + 'static>(
         &self,
         mut options: MetaRequestOptions,
+        request_timeouts: RequestTimeouts,
         request_span: Span,
         on_request_finish: impl Fn(&RequestMetrics) + Send + 'static,
         mut on_headers: impl FnMut(&Headers, i32) + Send + 'static,
@@ -495,21 +1827,42 @@ impl S3CrtClientInner {
             + Send
             + 'static,
     ) -> Result<S3HttpRequest<T, E>, S3RequestError> {
+        let in_flight_id = self.in_flight_requests.lock().unwrap().register()?;
+        let in_flight_requests = Arc::clone(&self.in_flight_requests);
+
         let (tx, rx) = oneshot::channel::<ObjectClientResult<T, E, S3RequestError>>();
 
         let span_telemetry = request_span.clone();
         let span_body = request_span.clone();
+        let span_throttled = request_span.clone();
         let span_finish = request_span;
 
         let endpoint = options.get_endpoint().expect("S3Message always has an endpoint");
         let hostname = endpoint.host_name().to_str().unwrap().to_owned();
         let host_resolver = self.host_resolver.clone();
+        let expected_bucket_owner = self.bucket_owner.clone();
 
         let start_time = Instant::now();
         let first_body_part = Arc::new(AtomicBool::new(true));
         let first_body_part_clone = Arc::clone(&first_body_part);
         let total_bytes = Arc::new(AtomicU64::new(0));
         let total_bytes_clone = Arc::clone(&total_bytes);
+        let first_byte_received = Arc::new(AtomicBool::new(false));
+        let first_byte_received_headers = Arc::clone(&first_byte_received);
+        let first_byte_received_body = Arc::clone(&first_byte_received);
+        let expired_timeout: Arc<Mutex<Option<TimeoutKind>>> = Default::default();
+        let expired_timeout_finish = Arc::clone(&expired_timeout);
+
+        // Cancel the request rather than let the CRT retry it once the client's retry budget is spent
+        let retry_budget = self.retry_budget.clone();
+        let retry_budget_finish = self.retry_budget.clone();
+        let max_attempts = self.max_attempts;
+        let failed_attempts = AtomicUsize::new(0);
+        let retry_budget_exhausted = Arc::new(AtomicBool::new(false));
+        let retry_budget_exhausted_finish = Arc::clone(&retry_budget_exhausted);
+        let cancel_handle: Arc<CancelHandle> = Default::default();
+        let cancel_handle_telemetry = Arc::clone(&cancel_handle);
+        let cancel_handle_finish = Arc::clone(&cancel_handle);
 
         options
             .on_telemetry(move |metrics| {
@@ -520,6 +1873,17 @@ impl S3CrtClientInner {
                 let http_status = metrics.status_code();
                 let request_canceled = metrics.is_canceled();
                 let request_failure = http_status.map(|status| !(200..299).contains(&status)).unwrap_or(!request_canceled);
+
+                if let Some(retry_budget) = &retry_budget {
+                    // The CRT retries throttling and server errors and connection failures, until
+                    // the request has been attempted `max_attempts` times
+                    let retryable = http_status.map_or(!request_canceled, |status| status == 429 || (500..600).contains(&status));
+                    if retryable && failed_attempts.fetch_add(1, Ordering::SeqCst) + 1 < max_attempts && !retry_budget.try_acquire() {
+                        warn!("retry budget is exhausted, canceling the request instead of retrying it");
+                        retry_budget_exhausted.store(true, Ordering::SeqCst);
+                        cancel_handle_telemetry.cancel();
+                    }
+                }
                 let crt_error = Some(metrics.error()).filter(|e| e.is_err());
                 let request_type = request_type_to_metrics_string(metrics.request_type());
                 let request_id = metrics.request_id().unwrap_or_else(|| "<unknown>".into());
@@ -550,11 +1914,13 @@ impl S3CrtClientInner {
                 }
             })
             .on_headers(move |headers, response_status| {
+                first_byte_received_headers.store(true, Ordering::SeqCst);
                 (on_headers)(headers, response_status);
             })
             .on_body(move |range_start, data| {
                 let _guard = span_body.enter();
 
+                first_byte_received_body.store(true, Ordering::SeqCst);
                 if first_body_part.compare_exchange(true, false, Ordering::SeqCst, Ordering::SeqCst).ok() == Some(true) {
                     let latency = start_time.elapsed().as_micros() as f64;
                     let op = span_body.metadata().map(|m| m.name()).unwrap_or("unknown");
@@ -590,8 +1956,14 @@ impl S3CrtClientInner {
                     metrics::gauge!("s3.client.host_count", "host" => hostname).set(host_count as f64);
                 }
 
+                cancel_handle_finish.finish();
+
+                // If one of our timeouts canceled the request, report the timeout rather than the cancellation
+                let expired_timeout = (*expired_timeout_finish.lock().unwrap()).filter(|_| request_result.is_canceled());
+                let retry_budget_exhausted = expired_timeout.is_none() && request_result.is_canceled() && retry_budget_exhausted_finish.load(Ordering::SeqCst);
+
                 let status_code = request_result.response_status;
-                let log_level = if (200..=399).contains(&status_code) || status_code == 404 || request_result.is_canceled() {
+                let log_level = if (200..=399).contains(&status_code) || status_code == 404 || (request_result.is_canceled() && expired_timeout.is_none() && !retry_budget_exhausted) {
                     tracing::Level::DEBUG
                 } else {
                     tracing::Level::WARN
@@ -602,6 +1974,18 @@ impl S3CrtClientInner {
                 // error if that fails too.
                 let result = on_meta_request_finish(&request_result);
                 let result = result.map_err(|e| e.or_else(|| try_parse_generic_error(&request_result).map(ObjectClientError::ClientError)));
+                let result = match expired_timeout {
+                    Some(kind) => result.map_err(|_| Some(ObjectClientError::ClientError(S3RequestError::Timeout(kind)))),
+                    None if retry_budget_exhausted => result.map_err(|_| Some(ObjectClientError::ClientError(S3RequestError::RetryBudgetExhausted))),
+                    None => result,
+                };
+                if let Some(retry_budget) = retry_budget_finish.as_ref().filter(|_| result.is_ok()) {
+                    retry_budget.replenish();
+                }
+                let result = match &expected_bucket_owner {
+                    Some(owner) => result.map_err(|e| e.map(|e| attribute_access_denied_to_bucket_owner(e, owner))),
+                    None => result,
+                };
                 let result = match result {
                     Ok(t) => {
                         event!(log_level, ?duration, "meta request finished");
@@ -611,25 +1995,30 @@ impl S3CrtClientInner {
                         // Try to parse request header out of the failure. We can't just use the
                         // telemetry callback because there might be multiple requests per meta
                         // request, but these headers are known to be from the failed request.
-                        let request_id = match &request_result.error_response_headers {
-                            Some(headers) => headers.get("x-amz-request-id").map(|s| s.value().to_string_lossy().to_string()).ok(),
-                            None => None,
-                        };
-                        let request_id = request_id.unwrap_or_else(|| "<unknown>".into());
-
-                        let message = if request_result.is_canceled() {
+                        let request_id = error_response_header(&request_result, "x-amz-request-id").unwrap_or_else(|| "<unknown>".into());
+                        let extended_request_id = error_response_header(&request_result, "x-amz-id-2").unwrap_or_else(|| "<unknown>".into());
+
+                        let message = if expired_timeout.is_some() {
+                            "meta request timed out"
+                        } else if retry_budget_exhausted {
+                            "meta request failed, retry budget exhausted"
+                        } else if request_result.is_canceled() {
                             "meta request canceled"
                         } else {
                             "meta request failed"
                         };
                         if let Some(error) = &maybe_err {
-                            event!(log_level, ?duration, %request_id, ?error, message);
+                            event!(log_level, ?duration, %request_id, %extended_request_id, ?error, message);
                             debug!("meta request result: {:?}", request_result);
                         } else {
-                            event!(log_level, ?duration, %request_id, ?request_result, message);
+                            event!(log_level, ?duration, %request_id, %extended_request_id, ?request_result, message);
                         }
 
-                        if request_result.is_canceled() {
+                        if let Some(kind) = expired_timeout {
+                            metrics::counter!("s3.meta_requests.timeouts", "op" => op, "kind" => kind.to_string()).increment(1);
+                        } else if retry_budget_exhausted {
+                            metrics::counter!("s3.meta_requests.retry_budget_exhausted", "op" => op).increment(1);
+                        } else if request_result.is_canceled() {
                             metrics::counter!("s3.meta_requests.canceled", "op" => op).increment(1);
                         } else {
                             // If it's not a real HTTP status, encode the CRT error in the metric instead
@@ -647,20 +2036,102 @@ impl S3CrtClientInner {
                 };
 
                 let _ = tx.send(result);
+                in_flight_requests.lock().unwrap().requests.remove(&in_flight_id);
             });
 
         // Issue the HTTP request using the CRT's S3 meta request API
-        let meta_request = self.s3_client.make_meta_request(options)?;
-        Self::poll_client_metrics(&self.s3_client);
+        let meta_request = match self.s3_client.make_meta_request(options) {
+            Ok(meta_request) => meta_request,
+            Err(e) => {
+                self.in_flight_requests.lock().unwrap().requests.remove(&in_flight_id);
+                return Err(e.into());
+            }
+        };
+        cancel_handle.set(&meta_request);
+        // The request might already have finished, in which case there's nothing to record
+        if let Some(entry) = self.in_flight_requests.lock().unwrap().requests.get_mut(&in_flight_id) {
+            *entry = Some(meta_request.clone());
+        }
+        let client_metrics = Self::poll_client_metrics(&self.s3_client);
+        if let Some(max_active_connections) = self.max_active_connections {
+            let active_connections = client_metrics.num_total_network_io();
+            if active_connections >= max_active_connections {
+                span_throttled.in_scope(|| {
+                    debug!(
+                        active_connections,
+                        max_active_connections, "S3 client is at its connection limit, new request must wait"
+                    )
+                });
+            }
+        }
+
+        let timeout_watchdog = self.spawn_timeout_watchdog(
+            &meta_request,
+            request_timeouts,
+            start_time,
+            first_byte_received,
+            expired_timeout,
+        )?;
 
         Ok(S3HttpRequest {
             receiver: rx,
             meta_request,
+            timeout_watchdog,
         })
     }
 
-    /// Make an HTTP request using this S3 client that returns the body on success or invokes the
-    /// given callback to generate an error on failure.
+    /// Enforce the given time-to-first-byte and overall timeouts on a meta request. When one
+    /// expires, we record which one in `expired_timeout` and then cancel the meta request, so that
+    /// its `on_finish` callback can report the timeout. Returns `None` if there are no timeouts.
+    fn spawn_timeout_watchdog(
+        &self,
+        meta_request: &MetaRequest,
+        request_timeouts: RequestTimeouts,
+        start_time: Instant,
+        first_byte_received: Arc<AtomicBool>,
+        expired_timeout: Arc<Mutex<Option<TimeoutKind>>>,
+    ) -> Result<Option<FutureJoinHandle<()>>, S3RequestError> {
+        let RequestTimeouts {
+            time_to_first_byte,
+            overall,
+        } = request_timeouts;
+        if time_to_first_byte.is_none() && overall.is_none() {
+            return Ok(None);
+        }
+        // A time-to-first-byte timeout that isn't shorter than the overall timeout can never expire first
+        let time_to_first_byte = time_to_first_byte.filter(|ttfb| overall.map_or(true, |overall| *ttfb < overall));
+
+        let event_loop = self.event_loop_group.get_next_loop()?;
+        let timer_event_loop = event_loop.clone();
+        let meta_request = meta_request.clone();
+        let expire = move |kind: TimeoutKind| {
+            *expired_timeout.lock().unwrap() = Some(kind);
+            meta_request.cancel();
+        };
+
+        let watchdog = async move {
+            if let Some(time_to_first_byte) = time_to_first_byte {
+                let timer = EventLoopTimer::new(&timer_event_loop, time_to_first_byte);
+                if timer.await.is_err() {
+                    return;
+                }
+                if !first_byte_received.load(Ordering::SeqCst) {
+                    return expire(TimeoutKind::TimeToFirstByte);
+                }
+            }
+            if let Some(overall) = overall {
+                let timer = EventLoopTimer::new(&timer_event_loop, overall.saturating_sub(start_time.elapsed()));
+                if timer.await.is_err() {
+                    return;
+                }
+                expire(TimeoutKind::Overall);
+            }
+        };
+        Ok(Some(event_loop.spawn_future(watchdog)))
+    }
+
+    /// Make an HTTP request using this S3 client that returns the body and response metadata on
+    /// success or invokes the given callback to generate an error on failure.
     ///
     /// The `on_error` callback can assume that `result.is_err()` is true for the result it
     /// receives. It can return `None` if it considers the request to have failed but doesn't
@@ -672,46 +2143,134 @@ impl S3CrtClientInner {
         request_type: MetaRequestType,
         request_span: Span,
         on_error: impl FnOnce(&MetaRequestResult) -> Option<E> + Send + 'static,
-    ) -> Result<S3HttpRequest<Vec<u8>, E>, S3RequestError> {
-        let options = Self::new_meta_request_options(message, request_type);
-        self.make_simple_http_request_from_options(options, request_span, |_| {}, on_error, |_, _| ())
+    ) -> Result<S3HttpRequest<S3HttpResponse, E>, S3RequestError> {
+        let request_timeouts = message.request_timeouts;
+        let check_embedded_error = message.check_embedded_error;
+        let verify_body_length = message.verify_body_length;
+        let options = Self::new_meta_request_options(message, request_type, &request_span);
+        self.make_simple_http_request_from_options(
+            options,
+            request_timeouts,
+            request_span,
+            |_| {},
+            on_error,
+            |_, _| (),
+            check_embedded_error,
+            verify_body_length,
+        )
     }
 
     /// Make an HTTP request using this S3 client that returns the body on success or invokes the
-    /// given callback on failure. See [make_simple_http_request] for arguments.
+    /// given callback on failure. If `check_embedded_error` is set, successful responses whose body
+    /// is an `<Error>` document also fail (see [S3Message::check_embedded_error]). If
+    /// `verify_body_length` is set, successful responses whose body doesn't match their
+    /// `Content-Length` fail with [S3RequestError::IncompleteBody]. See [make_simple_http_request]
+    /// and [make_meta_request_from_options] for the other arguments.
+    #[allow(clippy::too_many_arguments)] // Each callback is for a different stage of the request
     fn make_simple_http_request_from_options<E: std::error::Error + Send + 'static>(
         &self,
         options: MetaRequestOptions,
+        request_timeouts: RequestTimeouts,
         request_span: Span,
         on_request_finish: impl Fn(&RequestMetrics) + Send + 'static,
         on_error: impl FnOnce(&MetaRequestResult) -> Option<E> + Send + 'static,
-        on_headers: impl FnMut(&Headers, i32) + Send + 'static,
-    ) -> Result<S3HttpRequest<Vec<u8>, E>, S3RequestError> {
+        mut on_headers: impl FnMut(&Headers, i32) + Send + 'static,
+        check_embedded_error: bool,
+        verify_body_length: bool,
+    ) -> Result<S3HttpRequest<S3HttpResponse, E>, S3RequestError> {
         // Accumulate the body of the response into this Vec<u8>
         let body: Arc<Mutex<Vec<u8>>> = Default::default();
         let body_clone = Arc::clone(&body);
 
-        self.make_meta_request_from_options(
+        // Remember the length of the successful response, to check that we received all of it
+        let content_length: Arc<Mutex<Option<u64>>> = Default::default();
+        let content_length_writer = Arc::clone(&content_length);
+
+        // Cancel the request as soon as we know its body is over the limit, rather than buffering
+        // all of it
+        let max_body_bytes = self.max_response_body_bytes;
+        let body_too_large = Arc::new(AtomicBool::new(false));
+        let body_too_large_headers = Arc::clone(&body_too_large);
+        let body_too_large_body = Arc::clone(&body_too_large);
+        let body_too_large_finish = Arc::clone(&body_too_large);
+        let cancel_handle: Arc<CancelHandle> = Default::default();
+        let cancel_handle_headers = Arc::clone(&cancel_handle);
+        let cancel_handle_body = Arc::clone(&cancel_handle);
+        let cancel_handle_finish = Arc::clone(&cancel_handle);
+
+        let request = self.make_meta_request_from_options(
             options,
+            request_timeouts,
             request_span,
             on_request_finish,
-            on_headers,
+            move |headers, status| {
+                if (200..300).contains(&status) {
+                    let length = headers
+                        .get("Content-Length")
+                        .ok()
+                        .and_then(|header| header.value().to_str()?.parse().ok());
+                    if length.is_some_and(|length: u64| length > max_body_bytes as u64) {
+                        body_too_large_headers.store(true, Ordering::SeqCst);
+                        cancel_handle_headers.cancel();
+                    }
+                    *content_length_writer.lock().unwrap() = length;
+                }
+                on_headers(headers, status);
+            },
             move |offset, data| {
+                if body_too_large_body.load(Ordering::SeqCst) {
+                    return;
+                }
                 let mut body = body_clone.lock().unwrap();
                 assert_eq!(offset as usize, body.len());
+                if body.len() + data.len() > max_body_bytes {
+                    body_too_large_body.store(true, Ordering::SeqCst);
+                    *body = Vec::new();
+                    cancel_handle_body.cancel();
+                    return;
+                }
                 body.extend_from_slice(data);
             },
             move |result| {
+                cancel_handle_finish.finish();
+                if body_too_large_finish.load(Ordering::SeqCst) {
+                    return Err(Some(ObjectClientError::ClientError(S3RequestError::ResponseTooLarge {
+                        limit: max_body_bytes,
+                    })));
+                }
                 if result.is_err() {
                     Err(on_error(result).map(ObjectClientError::ServiceError))
                 } else {
-                    Ok(std::mem::take(&mut *body.lock().unwrap()))
-                }
+                    let body = std::mem::take(&mut *body.lock().unwrap());
+                    if verify_body_length {
+                        check_body_length(*content_length.lock().unwrap(), body.len())
+                            .map_err(|e| Some(ObjectClientError::ClientError(e)))?;
+                    }
+                    if let Some(error_result) = check_embedded_error
+                        .then(|| embedded_error_result(result, &body))
+                        .flatten()
+                    {
+                        let error = match on_error(&error_result) {
+                            Some(error) => ObjectClientError::ServiceError(error),
+                            None => ObjectClientError::ClientError(
+                                try_parse_generic_error(&error_result)
+                                    .unwrap_or_else(|| S3RequestError::ResponseError(error_result)),
+                            ),
+                        };
+                        return Err(Some(error));
+                    }
+                    let mut metadata = ResponseMetadata::new(result.response_status);
+                    metadata.request_timings = result.metrics.last_request_timings;
+                    metadata.metrics = result.metrics.clone();
+                    Ok(S3HttpResponse { body, metadata })
+                }
             },
-        )
+        )?;
+        cancel_handle.set(&request.meta_request);
+        Ok(request)
     }
 
-    fn poll_client_metrics(s3_client: &Client) {
+    fn poll_client_metrics(s3_client: &Client) -> ClientMetrics {
         let metrics = s3_client.poll_client_metrics();
         metrics::gauge!("s3.client.num_requests_being_processed").set(metrics.num_requests_tracked_requests as f64);
         metrics::gauge!("s3.client.num_requests_being_prepared").set(metrics.num_requests_being_prepared as f64);
@@ -737,6 +2296,8 @@ impl S3CrtClientInner {
         metrics::gauge!("s3.client.buffer_pool.primary_num_blocks").set(buffer_pool_stats.primary_num_blocks as f64);
         metrics::gauge!("s3.client.buffer_pool.secondary_reserved").set(buffer_pool_stats.secondary_reserved as f64);
         metrics::gauge!("s3.client.buffer_pool.secondary_used").set(buffer_pool_stats.secondary_used as f64);
+
+        metrics
     }
 
     fn next_request_counter(&self) -> u64 {
@@ -744,6 +2305,60 @@ impl S3CrtClientInner {
     }
 }
 
+/// Client-side timeouts that the client enforces on a meta request by canceling it
+#[derive(Debug, Clone, Copy, Default)]
+struct RequestTimeouts {
+    time_to_first_byte: Option<Duration>,
+    overall: Option<Duration>,
+}
+
+/// The parts of a resolved endpoint needed to build a request for a bucket
+#[derive(Debug, Clone)]
+struct CachedEndpoint {
+    uri: Uri,
+    /// Only resolved if requests are signed
+    auth_scheme: Option<AuthScheme>,
+}
+
+/// A cache of resolved endpoints, keyed by bucket name. A client's endpoint config never changes,
+/// so the bucket alone determines the resolved endpoint.
+#[derive(Debug)]
+struct EndpointCache {
+    capacity: usize,
+    entries: Mutex<HashMap<String, CachedEndpoint>>,
+    /// Number of times an endpoint was resolved because it was not in the cache
+    resolutions: AtomicU64,
+}
+
+impl EndpointCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Default::default(),
+            resolutions: AtomicU64::new(0),
+        }
+    }
+
+    fn get(&self, bucket: &str) -> Option<CachedEndpoint> {
+        self.entries.lock().unwrap().get(bucket).cloned()
+    }
+
+    fn insert(&self, bucket: &str, endpoint: CachedEndpoint) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.capacity && !entries.contains_key(bucket) {
+            // Clients rarely access more than a handful of buckets, so evicting an arbitrary entry
+            // is good enough.
+            if let Some(evicted) = entries.keys().next().cloned() {
+                entries.remove(&evicted);
+            }
+        }
+        entries.insert(bucket.to_owned(), endpoint);
+    }
+}
+
 /// A HTTP message to be sent to S3. This is a wrapper around a plain HTTP message, except that it
 /// helps us correctly configure the endpoint and "Host" header to handle both path-style and
 /// virtual-hosted-style addresses. The `path_prefix` is appended to the front of all paths, and
@@ -753,8 +2368,18 @@ struct S3Message {
     inner: Message,
     uri: Uri,
     path_prefix: String,
+    /// The value of the `Host` header, for logging
+    host: String,
     checksum_config: Option<ChecksumConfig>,
     signing_config: Option<SigningConfig>,
+    /// The region the message is signed for, if it's signed, for logging
+    signing_region: Option<String>,
+    request_timeouts: RequestTimeouts,
+    check_embedded_error: bool,
+    /// Whether to check a successful response's body against its `Content-Length` header (see
+    /// [check_body_length]). Off for HEAD requests, whose `Content-Length` is for the body a GET
+    /// would return.
+    verify_body_length: bool,
 }
 
 impl S3Message {
@@ -769,71 +2394,210 @@ impl S3Message {
 
     /// Set the request path and query for this message. The components should not be URL-encoded;
     /// this method will handle that.
-    fn set_request_path_and_query<P: AsRef<OsStr>>(
+    fn set_request_path_and_query(
         &mut self,
         path: impl AsRef<OsStr>,
-        query: impl AsRef<[(P, P)]>,
+        query: &[QueryFragment<'_>],
     ) -> Result<(), mountpoint_s3_crt::common::error::Error> {
-        // This is RFC 3986 but with '/' also considered a safe character for path fragments.
-        const URLENCODE_QUERY_FRAGMENT: &AsciiSet =
-            &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
-        const URLENCODE_PATH_FRAGMENT: &AsciiSet = &URLENCODE_QUERY_FRAGMENT.remove(b'/');
-
-        fn write_encoded_fragment(s: &mut OsString, piece: impl AsRef<OsStr>, encoding: &'static AsciiSet) {
-            let iter = percent_encode(piece.as_ref().as_bytes(), encoding);
-            s.extend(iter.map(|s| OsStr::from_bytes(s.as_bytes())));
-        }
-
-        // This estimate is exact if no characters need encoding, otherwise we'll end up
-        // reallocating a couple of times. The '?' for the query is counted in the first key-value
-        // pair.
-        let space_needed = self.path_prefix.len()
-            + path.as_ref().len()
-            + query
-                .as_ref()
-                .iter()
-                .map(|(key, value)| key.as_ref().len() + value.as_ref().len() + 2) // +2 for & and =
-                .sum::<usize>();
+        let full_path = build_path_and_query(&self.path_prefix, path, query);
+        self.inner.set_request_path(full_path)
+    }
+
+    /// Set the request path for this message. The path should not be URL-encoded; this method will
+    /// handle that.
+    fn set_request_path(&mut self, path: impl AsRef<OsStr>) -> Result<(), mountpoint_s3_crt::common::error::Error> {
+        self.set_request_path_and_query(path, &[])
+    }
 
-        let mut full_path = OsString::with_capacity(space_needed);
+    /// Record the host, scheme, and signing region of this message on the span of its request, and
+    /// log them, so that it's clear where a request went when an endpoint override or region
+    /// mismatch sends it somewhere unexpected.
+    fn record_endpoint(&self, request_span: &Span) {
+        let scheme = self.uri.scheme().to_string_lossy();
+        request_span.record("host", self.host.as_str());
+        request_span.record("scheme", scheme.as_ref());
+        if let Some(signing_region) = &self.signing_region {
+            request_span.record("signing_region", signing_region.as_str());
+        }
+        request_span.in_scope(|| {
+            debug!(host = self.host.as_str(), %scheme, signing_region = ?self.signing_region, "resolved request endpoint");
+        });
+    }
 
-        write_encoded_fragment(&mut full_path, &self.path_prefix, URLENCODE_PATH_FRAGMENT);
-        write_encoded_fragment(&mut full_path, &path, URLENCODE_PATH_FRAGMENT);
+    /// Treat a successful response whose body is an `<Error>` document as a failure. S3 does this
+    /// for operations like CopyObject that send the response status before they finish, so a
+    /// failure part way through can't change it. Such errors go through the same error parsing as
+    /// error responses, with the 200 status.
+    fn check_embedded_error(&mut self) {
+        self.check_embedded_error = true;
+    }
 
-        // Build the query string
-        if !query.as_ref().is_empty() {
-            full_path.push("?");
-            for (i, (key, value)) in query.as_ref().iter().enumerate() {
-                if i != 0 {
-                    full_path.push("&");
-                }
+    /// Sets the checksum configuration for this message.
+    fn set_checksum_config(&mut self, checksum_config: Option<ChecksumConfig>) {
+        self.checksum_config = checksum_config;
+    }
+
+    /// Set the body of this message to the given buffer, along with a matching Content-Length header.
+    fn set_body(
+        &mut self,
+        body: impl AsRef<[u8]> + Send + Sync + 'static,
+    ) -> Result<(), mountpoint_s3_crt::common::error::Error> {
+        self.set_header(&Header::new("Content-Length", body.as_ref().len().to_string()))?;
+        let body_stream = InputStream::new_from_buffer(&Allocator::default(), body)?;
+        self.inner.set_body_stream(Some(body_stream));
+        Ok(())
+    }
+}
+
+/// A single component of the query string of a request. Components are written to the query
+/// string in the order they are given, and keys are allowed to repeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueryFragment<'a> {
+    /// A key with a value, written as `key=value`. The value may be empty.
+    Pair(&'a str, &'a str),
+    /// A key with no value, written as just `key` (for example `?tagging`).
+    Flag(&'a str),
+}
+
+/// Characters to encode in a query string fragment: everything but the RFC 3986 unreserved characters.
+const URLENCODE_QUERY_FRAGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+/// Characters to encode in a path: this is RFC 3986 but with '/' also considered a safe character
+/// for path fragments.
+const URLENCODE_PATH_FRAGMENT: &AsciiSet = &URLENCODE_QUERY_FRAGMENT.remove(b'/');
+
+/// Append the percent-encoding of `piece` to `s`, encoding the characters in `encoding`.
+fn write_encoded_fragment(s: &mut OsString, piece: impl AsRef<OsStr>, encoding: &'static AsciiSet) {
+    let iter = percent_encode(piece.as_ref().as_bytes(), encoding);
+    s.extend(iter.map(|s| OsStr::from_bytes(s.as_bytes())));
+}
+
+/// Percent-encode an S3 request path (such as `/` followed by an object key) according to the S3
+/// rules: every byte except the RFC 3986 unreserved characters and `/` is encoded. Empty path
+/// segments (consecutive slashes) are preserved, since they are significant in S3 keys.
+fn encode_s3_path(path: impl AsRef<OsStr>) -> OsString {
+    let mut encoded = OsString::with_capacity(path.as_ref().len());
+    write_encoded_fragment(&mut encoded, path, URLENCODE_PATH_FRAGMENT);
+    encoded
+}
+
+/// Build the full (URL-encoded) request path and query string from its unencoded components.
+fn build_path_and_query(path_prefix: &str, path: impl AsRef<OsStr>, query: &[QueryFragment<'_>]) -> OsString {
+    // This estimate is exact if no characters need encoding, otherwise we'll end up
+    // reallocating a couple of times. The '?' for the query is counted in the first fragment.
+    let space_needed = path_prefix.len()
+        + path.as_ref().len()
+        + query
+            .iter()
+            .map(|fragment| match fragment {
+                QueryFragment::Pair(key, value) => key.len() + value.len() + 2, // +2 for & and =
+                QueryFragment::Flag(key) => key.len() + 1,                      // +1 for &
+            })
+            .sum::<usize>();
+
+    let mut full_path = OsString::with_capacity(space_needed);
+
+    full_path.push(encode_s3_path(path_prefix));
+    full_path.push(encode_s3_path(path));
+
+    // Build the query string
+    for (i, fragment) in query.iter().enumerate() {
+        full_path.push(if i == 0 { "?" } else { "&" });
+        match fragment {
+            QueryFragment::Pair(key, value) => {
                 write_encoded_fragment(&mut full_path, key, URLENCODE_QUERY_FRAGMENT);
                 full_path.push("=");
                 write_encoded_fragment(&mut full_path, value, URLENCODE_QUERY_FRAGMENT);
             }
+            QueryFragment::Flag(key) => {
+                write_encoded_fragment(&mut full_path, key, URLENCODE_QUERY_FRAGMENT);
+            }
+        }
+    }
+
+    full_path
+}
+
+/// A handle for a meta request's own callbacks to cancel it with, although the meta request only
+/// exists once its callbacks do. A cancellation before then happens as soon as the meta request is
+/// set. The handle lets go of the meta request when it finishes, since a callback holding it would
+/// otherwise keep it alive forever.
+#[derive(Debug, Default)]
+struct CancelHandle(Mutex<CancelHandleState>);
+
+#[derive(Debug)]
+enum CancelHandleState {
+    Pending { canceled: bool },
+    Ready(MetaRequest),
+    Finished,
+}
+
+impl Default for CancelHandleState {
+    fn default() -> Self {
+        Self::Pending { canceled: false }
+    }
+}
+
+impl CancelHandle {
+    fn set(&self, meta_request: &MetaRequest) {
+        let mut state = self.0.lock().unwrap();
+        match *state {
+            CancelHandleState::Pending { canceled: true } => meta_request.cancel(),
+            CancelHandleState::Pending { canceled: false } => *state = CancelHandleState::Ready(meta_request.clone()),
+            CancelHandleState::Ready(_) | CancelHandleState::Finished => {}
         }
+    }
 
-        self.inner.set_request_path(full_path)
+    fn cancel(&self) {
+        let mut state = self.0.lock().unwrap();
+        match &*state {
+            CancelHandleState::Pending { .. } => *state = CancelHandleState::Pending { canceled: true },
+            CancelHandleState::Ready(meta_request) => meta_request.cancel(),
+            CancelHandleState::Finished => {}
+        }
     }
 
-    /// Set the request path for this message. The path should not be URL-encoded; this method will
-    /// handle that.
-    fn set_request_path(&mut self, path: impl AsRef<OsStr>) -> Result<(), mountpoint_s3_crt::common::error::Error> {
-        self.set_request_path_and_query::<&str>(path, &[])
+    fn finish(&self) {
+        *self.0.lock().unwrap() = CancelHandleState::Finished;
     }
+}
 
-    /// Sets the checksum configuration for this message.
-    fn set_checksum_config(&mut self, checksum_config: Option<ChecksumConfig>) {
-        self.checksum_config = checksum_config;
+/// Check that a buffered response body is as long as its `Content-Length` header said it would be,
+/// so that a connection that dropped part way through the body can't be mistaken for a complete
+/// response, even if none of the body arrived. Callers skip the check for requests whose
+/// `Content-Length` isn't for a body the CRT delivers, like HEAD requests.
+fn check_body_length(content_length: Option<u64>, received: usize) -> Result<(), S3RequestError> {
+    match content_length {
+        Some(expected) if expected != received as u64 => Err(S3RequestError::IncompleteBody {
+            expected,
+            received: received as u64,
+        }),
+        _ => Ok(()),
     }
 }
 
+/// The successful response to a request made with [S3CrtClientInner::make_simple_http_request]
+#[derive(Debug)]
+struct S3HttpResponse {
+    body: Vec<u8>,
+    metadata: ResponseMetadata,
+}
+
 #[derive(Debug)]
 #[pin_project(PinnedDrop)]
 struct S3HttpRequest<T, E> {
     #[pin]
     receiver: oneshot::Receiver<ObjectClientResult<T, E, S3RequestError>>,
     meta_request: MetaRequest,
+    timeout_watchdog: Option<FutureJoinHandle<()>>,
+}
+
+impl<T, E> S3HttpRequest<T, E> {
+    /// A handle that can cancel this request without dropping it
+    fn handle(&self) -> RequestHandle {
+        RequestHandle {
+            meta_request: self.meta_request.clone(),
+        }
+    }
 }
 
 impl<T: Send, E: Send> Future for S3HttpRequest<T, E> {
@@ -854,10 +2618,47 @@ impl<T: Send, E: Send> Future for S3HttpRequest<T, E> {
 #[pinned_drop]
 impl<T, E> PinnedDrop for S3HttpRequest<T, E> {
     fn drop(self: Pin<&mut Self>) {
+        let this = self.project();
+        this.meta_request.cancel();
+        if let Some(timeout_watchdog) = this.timeout_watchdog.take() {
+            timeout_watchdog.cancel();
+        }
+    }
+}
+
+/// A handle to an in-flight S3 request, which can cancel the request from elsewhere while its
+/// owner is still waiting on it.
+///
+/// Canceling the request stops the transfer in the CRT and releases its connections. The request
+/// then fails with [S3RequestError::RequestCanceled], unless it had already finished. Canceling a
+/// request more than once, or after it finished, does nothing.
+#[derive(Debug, Clone)]
+pub struct RequestHandle {
+    meta_request: MetaRequest,
+}
+
+impl RequestHandle {
+    /// Cancel the request.
+    pub fn cancel(&self) {
         self.meta_request.cancel();
     }
 }
 
+/// What happened to a client's requests during [S3CrtClient::shutdown]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ShutdownReport {
+    /// Number of requests in flight at shutdown that finished before the timeout, whether they
+    /// succeeded or failed
+    pub completed: usize,
+
+    /// Number of requests in flight at shutdown that were canceled at the timeout
+    pub canceled: usize,
+
+    /// Whether the client's event loop threads exited before the timeout
+    pub event_loop_group_shut_down: bool,
+}
+
 /// Failures to construct a new S3 client
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -899,10 +2700,44 @@ pub enum S3RequestError {
     #[error("Wrong region (expecting {0})")]
     IncorrectRegion(String),
 
+    /// The bucket is not in the region the client is configured for
+    #[error("Bucket {bucket} is in region {bucket_region}, but the client is configured for region {client_region}")]
+    RegionMismatch {
+        bucket: String,
+        bucket_region: String,
+        client_region: String,
+    },
+
     /// Forbidden
     #[error("Forbidden: {0}")]
     Forbidden(String, ClientErrorMetadata),
 
+    /// S3 denied a request from a client with an expected bucket owner (see
+    /// [S3ClientConfig::bucket_owner]). S3 denies requests to a bucket that the expected account
+    /// doesn't own with the same error as requests that aren't allowed for any other reason, so
+    /// either the bucket is owned by another account or the request isn't allowed.
+    #[error("Access denied, or the bucket is not owned by the expected account {expected_bucket_owner}: {message}")]
+    BucketOwnerAccessDenied {
+        /// The account ID the client expected to own the bucket
+        expected_bucket_owner: String,
+        /// The message of the error response
+        message: String,
+        /// Metadata about the error response
+        metadata: ClientErrorMetadata,
+    },
+
+    /// S3 rejected the request with a `RequestTimeTooSkewed` error, because its signing time was
+    /// too far from S3's own time, which usually means the system clock is wrong. The CRT signs
+    /// requests with the system clock, so they keep failing until it's fixed.
+    #[error("Request time is too skewed from the server time")]
+    ClockSkew {
+        /// S3's time when it responded, from the response's `Date` header, or from the error
+        /// response if there was no such header
+        server_time: Option<OffsetDateTime>,
+        /// Metadata about the error response
+        metadata: ClientErrorMetadata,
+    },
+
     /// No signing credential is set for requests
     #[error("No signing credentials found")]
     NoSigningCredentials,
@@ -914,29 +2749,151 @@ pub enum S3RequestError {
     /// The request was throttled by S3
     #[error("Request throttled")]
     Throttled,
+
+    /// The response did not match the checksum of the object
+    #[error("Checksum validation failed (algorithm: {0:?})")]
+    ChecksumValidationError(Option<ChecksumAlgorithm>),
+
+    /// The request exceeded one of the client's configured timeouts
+    #[error("Request timed out ({0} timeout)")]
+    Timeout(TimeoutKind),
+
+    /// The response body could not be decoded according to its `Content-Encoding`
+    #[error("Failed to decode response body according to its Content-Encoding")]
+    ContentDecodingError(#[source] std::io::Error),
+
+    /// The bucket name doesn't follow the S3 bucket naming rules. The request was not sent.
+    #[error("Invalid bucket name {bucket:?}: {reason}")]
+    InvalidBucketName { bucket: String, reason: &'static str },
+
+    /// The client is shutting down, or has shut down, so it doesn't accept new requests. See
+    /// [S3CrtClient::shutdown].
+    #[error("Client is shut down")]
+    ClientShutDown,
+
+    /// The response body was shorter or longer than its `Content-Length` header said it would be,
+    /// which usually means the connection dropped part way through the body
+    #[error("Incomplete response body: expected {expected} bytes, received {received}")]
+    IncompleteBody { expected: u64, received: u64 },
+
+    /// The response body was larger than the client buffers in memory. The request was canceled.
+    /// See [S3ClientConfig::max_response_body_bytes].
+    #[error("Response body is larger than the {limit} byte limit")]
+    ResponseTooLarge { limit: usize },
+
+    /// A GetObject request was redirected more times than the client follows. See
+    /// [S3ClientConfig::max_get_redirects].
+    #[error("Request was redirected more than {max_redirects} times")]
+    TooManyRedirects { max_redirects: u32 },
+
+    /// The client has downloaded more object body bytes than its budget allows. If the request was
+    /// in flight, it was canceled; otherwise it was not sent. See
+    /// [S3ClientConfig::egress_byte_budget].
+    #[error("Egress budget of {budget} bytes is exceeded")]
+    EgressBudgetExceeded { budget: u64 },
+
+    /// The request failed and was canceled rather than retried, because the client's retry budget
+    /// is exhausted. See [S3ClientConfig::retry_budget].
+    #[error("Retry budget is exhausted")]
+    RetryBudgetExhausted,
+}
+
+/// The client timeout that a request exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimeoutKind {
+    /// Establishing a connection took longer than [`S3ClientConfig::connect_timeout`]
+    Connect,
+    /// S3 took longer than [`S3ClientConfig::time_to_first_byte_timeout`] to start responding
+    TimeToFirstByte,
+    /// The request took longer than [`S3ClientConfig::overall_request_timeout`] to finish
+    Overall,
+}
+
+impl std::fmt::Display for TimeoutKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            TimeoutKind::Connect => "connect",
+            TimeoutKind::TimeToFirstByte => "time to first byte",
+            TimeoutKind::Overall => "overall",
+        };
+        f.write_str(kind)
+    }
 }
 
 impl S3RequestError {
     fn construction_failure(inner: impl Into<ConstructionError>) -> Self {
         S3RequestError::ConstructionFailure(inner.into())
     }
+
+    /// Whether this error is likely to be transient, so that repeating the same request might
+    /// succeed: throttling, timeouts, and 5xx server errors.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            S3RequestError::Throttled | S3RequestError::Timeout(_) | S3RequestError::IncompleteBody { .. } => true,
+            S3RequestError::ResponseError(result) => (500..600).contains(&result.response_status),
+            _ => false,
+        }
+    }
+
+    /// The fields of the `<Error>` body of the response this error is for, if it's an unmodeled
+    /// [ResponseError](S3RequestError::ResponseError) with such a body. The code and message of
+    /// other errors parsed from a response are in their [metadata](ProvideErrorMetadata::meta).
+    pub fn error_details(&self) -> Option<S3ErrorDetails> {
+        match self {
+            S3RequestError::ResponseError(result) => {
+                let body = result.error_response_body.as_ref()?;
+                parse_s3_error_body(body.as_bytes())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The fields of an S3 `<Error>` response body. See [Error
+/// responses](https://docs.aws.amazon.com/AmazonS3/latest/API/ErrorResponses.html) in the *Amazon
+/// S3 API Reference* for more details.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct S3ErrorDetails {
+    /// The error code, from the `<Code>` element, e.g. "EntityTooLarge"
+    pub code: Option<String>,
+
+    /// The error message, from the `<Message>` element
+    pub message: Option<String>,
+
+    /// The other elements of the body by name, like `Resource`, `RequestId`, and `HostId`, and
+    /// fields specific to the error, like `MaxAllowedSize` for `EntityTooLarge`
+    pub extra_fields: HashMap<String, String>,
+}
+
+/// Parse an S3 `<Error>` response body into its fields, or `None` if it isn't one.
+fn parse_s3_error_body(body: &[u8]) -> Option<S3ErrorDetails> {
+    let root = parse_xml(body).ok()?;
+    if root.name != "Error" {
+        return None;
+    }
+    let mut details = S3ErrorDetails::default();
+    for child in root.children.iter().filter_map(|node| node.as_element()) {
+        let text = child.get_text().map(|text| text.into_owned());
+        match child.name.as_str() {
+            "Code" => details.code = text,
+            "Message" => details.message = text,
+            name => {
+                details.extra_fields.insert(name.to_owned(), text.unwrap_or_default());
+            }
+        }
+    }
+    Some(details)
 }
 
 impl ProvideErrorMetadata for S3RequestError {
     fn meta(&self) -> ClientErrorMetadata {
         match self {
-            Self::ResponseError(request_result) => {
-                let http_code = if request_result.response_status >= 100 {
-                    Some(request_result.response_status)
-                } else {
-                    None
-                };
-                ClientErrorMetadata {
-                    http_code,
-                    ..Default::default()
-                }
-            }
-            Self::Forbidden(_, metadata) => metadata.clone(),
+            Self::ResponseError(request_result) => error_response_metadata(request_result),
+            Self::Forbidden(_, metadata)
+            | Self::BucketOwnerAccessDenied { metadata, .. }
+            | Self::ClockSkew { metadata, .. } => metadata.clone(),
             Self::Throttled => ClientErrorMetadata {
                 http_code: Some(503),
                 ..Default::default()
@@ -955,6 +2912,14 @@ pub enum ConstructionError {
     /// The S3 endpoint was invalid
     #[error("Invalid S3 endpoint")]
     InvalidEndpoint(#[from] EndpointError),
+
+    /// The parameters of the request can't be combined
+    #[error("Invalid request parameters: {0}")]
+    InvalidParameters(String),
+
+    /// The CRT this client was built with can't compute the requested checksums
+    #[error("Unsupported checksum algorithm")]
+    UnsupportedChecksumAlgorithm(#[from] UnsupportedChecksumAlgorithm),
 }
 
 /// Return a string version of a [RequestType] for use in metrics
@@ -995,6 +2960,80 @@ fn extract_range_header(headers: &Headers) -> Option<Range<u64>> {
     Some(start..end + 1)
 }
 
+/// Get the value of a response header from the failed request of a meta request, if it's present
+fn error_response_header(request_result: &MetaRequestResult, name: &str) -> Option<String> {
+    let headers = request_result.error_response_headers.as_ref()?;
+    let header = headers.get(name).ok()?;
+    Some(header.value().to_string_lossy().into_owned())
+}
+
+/// The metadata that a failing meta request's response provides before parsing its body: the
+/// response status and, for AWS endpoints, the request IDs to quote in support cases
+fn error_response_metadata(request_result: &MetaRequestResult) -> ClientErrorMetadata {
+    let http_code = if request_result.response_status >= 100 {
+        Some(request_result.response_status)
+    } else {
+        None
+    };
+    let details = request_result
+        .error_response_body
+        .as_ref()
+        .and_then(|body| parse_s3_error_body(body.as_bytes()))
+        .unwrap_or_default();
+    ClientErrorMetadata {
+        http_code,
+        error_code: details.code,
+        error_message: details.message,
+        request_id: error_response_header(request_result, "x-amz-request-id"),
+        extended_request_id: error_response_header(request_result, "x-amz-id-2"),
+    }
+}
+
+/// Build a failed result from a successful response whose body is an `<Error>` document, so that
+/// the error can be parsed like an error response. It keeps the response's 200 status.
+fn embedded_error_result(result: &MetaRequestResult, body: &[u8]) -> Option<MetaRequestResult> {
+    let root = parse_xml(body).ok()?;
+    if root.name != "Error" {
+        return None;
+    }
+    let code = root.get_child("Code").and_then(|code| code.get_text());
+    // The CRT reports throttling responses with their own error, which the generic parsing looks for
+    let crt_error = if code.as_deref() == Some("SlowDown") {
+        mountpoint_s3_crt_sys::aws_s3_errors::AWS_ERROR_S3_SLOW_DOWN
+    } else {
+        mountpoint_s3_crt_sys::aws_s3_errors::AWS_ERROR_S3_INVALID_RESPONSE_STATUS
+    };
+    Some(MetaRequestResult {
+        response_status: result.response_status,
+        crt_error: (crt_error as i32).into(),
+        error_response_headers: None,
+        error_response_body: Some(OsStr::from_bytes(body).to_owned()),
+        validation_algorithm: None,
+        metrics: Default::default(),
+    })
+}
+
+/// S3 denies a request whose expected bucket owner doesn't own the bucket with the same
+/// `AccessDenied` error as any other denied request, so report those errors as possibly being
+/// caused by the expected bucket owner.
+fn attribute_access_denied_to_bucket_owner<E>(
+    error: ObjectClientError<E, S3RequestError>,
+    expected_bucket_owner: &str,
+) -> ObjectClientError<E, S3RequestError> {
+    match error {
+        ObjectClientError::ClientError(S3RequestError::Forbidden(message, metadata))
+            if metadata.error_code.as_deref() == Some("AccessDenied") =>
+        {
+            ObjectClientError::ClientError(S3RequestError::BucketOwnerAccessDenied {
+                expected_bucket_owner: expected_bucket_owner.to_owned(),
+                message,
+                metadata,
+            })
+        }
+        error => error,
+    }
+}
+
 /// Try to parse a modeled error out of a failing meta request
 fn try_parse_generic_error(request_result: &MetaRequestResult) -> Option<S3RequestError> {
     /// Look for a redirect header pointing to a different region for the bucket
@@ -1012,13 +3051,10 @@ fn try_parse_generic_error(request_result: &MetaRequestResult) -> Option<S3Reque
             // error, so just trust the response code
             return Some(S3RequestError::Forbidden(
                 "<no message>".to_owned(),
-                ClientErrorMetadata {
-                    http_code: Some(request_result.response_status),
-                    ..Default::default()
-                },
+                error_response_metadata(request_result),
             ));
         };
-        let error_elem = xmltree::Element::parse(body.as_bytes()).ok()?;
+        let error_elem = parse_xml(body.as_bytes()).ok()?;
         let error_code = error_elem.get_child("Code")?;
         let error_code_str = error_code.get_text()?;
         // Always translate 403 to Forbidden, but otherwise first check the error code, since other
@@ -1033,14 +3069,22 @@ fn try_parse_generic_error(request_result: &MetaRequestResult) -> Option<S3Reque
                 .get_child("Message")
                 .and_then(|e| e.get_text())
                 .unwrap_or(error_code_str.clone());
-            Some(S3RequestError::Forbidden(
-                message.to_string(),
-                ClientErrorMetadata {
-                    http_code: Some(request_result.response_status),
-                    error_code: Some(error_code_str.to_string()),
-                    error_message: Some(message.into_owned()),
-                },
-            ))
+            let metadata = ClientErrorMetadata {
+                error_code: Some(error_code_str.to_string()),
+                error_message: Some(message.to_string()),
+                ..error_response_metadata(request_result)
+            };
+            if error_code_str == "RequestTimeTooSkewed" {
+                let server_time = error_response_header(request_result, "Date")
+                    .and_then(|date| parse_http_date(&date).ok())
+                    .or_else(|| {
+                        let server_time = error_elem.get_child("ServerTime")?.get_text()?;
+                        OffsetDateTime::parse(&server_time, &Rfc3339).ok()
+                    });
+                Some(S3RequestError::ClockSkew { server_time, metadata })
+            } else {
+                Some(S3RequestError::Forbidden(message.into_owned(), metadata))
+            }
         } else {
             None
         }
@@ -1065,25 +3109,180 @@ fn try_parse_generic_error(request_result: &MetaRequestResult) -> Option<S3Reque
         }
     }
 
+    /// Look for connection attempts that exceeded the connect timeout
+    fn try_parse_connect_timeout(request_result: &MetaRequestResult) -> Option<S3RequestError> {
+        let crt_error_code = request_result.crt_error.raw_error();
+        (crt_error_code == mountpoint_s3_crt_sys::aws_io_errors::AWS_IO_SOCKET_TIMEOUT as i32)
+            .then_some(S3RequestError::Timeout(TimeoutKind::Connect))
+    }
+
     /// Handle canceled requests
     fn try_parse_canceled_request(request_result: &MetaRequestResult) -> Option<S3RequestError> {
         request_result.is_canceled().then_some(S3RequestError::RequestCanceled)
     }
 
+    /// Look for responses that failed checksum validation. These can fail with any response
+    /// status, since the response was otherwise successful.
+    fn try_parse_checksum_mismatch(request_result: &MetaRequestResult) -> Option<S3RequestError> {
+        request_result
+            .is_checksum_mismatch()
+            .then_some(S3RequestError::ChecksumValidationError(
+                request_result.validation_algorithm,
+            ))
+    }
+
+    if let Some(error) = try_parse_checksum_mismatch(request_result) {
+        return Some(error);
+    }
+
     match request_result.response_status {
         301 => try_parse_redirect(request_result),
         // 400 is overloaded, it can be an access error (invalid token) or (for MRAP) a bucket
         // redirect
         400 => try_parse_forbidden(request_result).or_else(|| try_parse_redirect(request_result)),
         403 => try_parse_forbidden(request_result),
+        // An error embedded in a successful response (see [embedded_error_result])
+        200 => try_parse_throttled(request_result).or_else(|| try_parse_forbidden(request_result)),
         // if the http response status is not set, we look into crt_error_code to identify the error
         0 => try_parse_throttled(request_result)
+            .or_else(|| try_parse_connect_timeout(request_result))
             .or_else(|| try_parse_canceled_request(request_result))
             .or_else(|| Some(try_parse_no_credentials_or_generic(request_result))),
         _ => None,
     }
 }
 
+/// Whether the response headers confirm that the requester was charged for a request to a
+/// requester-pays bucket
+fn parse_request_charged(headers: &Headers) -> bool {
+    headers
+        .get("x-amz-request-charged")
+        .is_ok_and(|header| header.value().as_os_str() == "requester")
+}
+
+/// Prefix of the response headers that carry the user-defined metadata of an object
+const USER_METADATA_HEADER_PREFIX: &str = "x-amz-meta-";
+
+/// Headers that the client or the CRT set on every request, including the ones signing adds, which
+/// can't be replaced by [S3ClientConfig::default_header].
+const RESERVED_HEADER_NAMES: &[&str] = &[
+    "Authorization",
+    "Host",
+    "User-Agent",
+    "Content-Length",
+    "X-Amz-Date",
+    "X-Amz-Content-SHA256",
+    "X-Amz-Security-Token",
+    "X-Amz-S3session-Token",
+    "X-Amz-Region-Set",
+];
+
+/// Collect the user-defined metadata of an object from the `x-amz-meta-*` response headers, with
+/// the prefix stripped. The prefix is matched case-insensitively, but the rest of each key keeps
+/// the case S3 returned it in.
+fn parse_user_metadata(headers: &Headers) -> HashMap<String, String> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = name.to_string_lossy();
+            let prefix = name.get(..USER_METADATA_HEADER_PREFIX.len())?;
+            if !prefix.eq_ignore_ascii_case(USER_METADATA_HEADER_PREFIX) {
+                return None;
+            }
+            let key = name[USER_METADATA_HEADER_PREFIX.len()..].to_owned();
+            Some((key, value.to_string_lossy().into_owned()))
+        })
+        .collect()
+}
+
+/// The media type of an object, from the `Content-Type` response header
+fn parse_content_type(headers: &Headers) -> Option<String> {
+    let header = headers.get("Content-Type").ok()?;
+    Some(header.value().to_string_lossy().into_owned())
+}
+
+/// The standard content headers of an object, from the response headers of the same names
+fn parse_content_headers(headers: &Headers) -> ContentHeaders {
+    let get = |name: &str| {
+        let header = headers.get(name).ok()?;
+        Some(header.value().to_string_lossy().into_owned())
+    };
+    ContentHeaders {
+        content_encoding: get("Content-Encoding"),
+        cache_control: get("Cache-Control"),
+        content_disposition: get("Content-Disposition"),
+        content_language: get("Content-Language"),
+        expires: get("Expires"),
+    }
+}
+
+lazy_static! {
+    /// The obsolete asctime format of HTTP-date, like `Sun Nov  6 08:49:37 1994`
+    static ref ASCTIME_FORMAT: Vec<FormatItem<'static>> = time::format_description::parse(
+        "[weekday repr:short] [month repr:short] [day padding:space] [hour]:[minute]:[second] [year]"
+    )
+    .expect("asctime format should be valid");
+}
+
+/// Parse an HTTP-date (RFC 7231 section 7.1.1.1), the format of date headers like `Last-Modified`
+/// and `Date`. S3 sends the preferred IMF-fixdate format, `Sun, 06 Nov 1994 08:49:37 GMT`, which is
+/// a subset of RFC 2822; the obsolete asctime format is also accepted, but not the obsolete RFC 850
+/// format, since its two-digit years are ambiguous.
+///
+/// This isn't the RFC 3339 format of the timestamps in S3's XML responses, like the
+/// `LastModified` of a listing. HTTP-dates only have second precision, while those timestamps have
+/// milliseconds, but S3 always reports object modification times to the second, so an object's
+/// `Last-Modified` header and listed `LastModified` are equal once parsed.
+pub(crate) fn parse_http_date(value: &str) -> Result<OffsetDateTime, time::error::Parse> {
+    let value = value.trim();
+    OffsetDateTime::parse(value, &Rfc2822).or_else(|e| {
+        PrimitiveDateTime::parse(value, &ASCTIME_FORMAT)
+            .map(PrimitiveDateTime::assume_utc)
+            .map_err(|_| e)
+    })
+}
+
+/// When a lifecycle rule will expire an object, from the `x-amz-expiration` response header, which
+/// looks like `expiry-date="Fri, 21 Dec 2012 00:00:00 GMT", rule-id="picture-deletion-rule"`. `None`
+/// if the header is missing or malformed.
+fn parse_expiration(headers: &Headers) -> Option<ObjectExpiration> {
+    let header = headers.get("x-amz-expiration").ok()?;
+    let header = header.value().to_str()?;
+
+    // The expiry date contains a comma, so split the header into its quoted values rather than on
+    // commas
+    let mut expiry_date = None;
+    let mut rule_id = None;
+    let mut rest = header.trim();
+    while !rest.is_empty() {
+        let (name, value) = rest.split_once('=')?;
+        let value = value.trim_start().strip_prefix('"')?;
+        let (value, remaining) = value.split_once('"')?;
+        match name.trim() {
+            "expiry-date" => expiry_date = Some(value),
+            "rule-id" => rule_id = Some(value),
+            _ => {}
+        }
+        rest = remaining.trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+
+    let expiry = parse_http_date(expiry_date?).ok()?;
+    let rule_id = percent_decode_str(rule_id?).decode_utf8().ok()?.into_owned();
+    Some(ObjectExpiration { expiry, rule_id })
+}
+
+/// Return the name of the header S3 uses for an object checksum computed with the given algorithm
+fn checksum_header_name(algorithm: ChecksumAlgorithm) -> &'static str {
+    match algorithm {
+        ChecksumAlgorithm::Crc32c => "x-amz-checksum-crc32c",
+        ChecksumAlgorithm::Crc32 => "x-amz-checksum-crc32",
+        ChecksumAlgorithm::Sha1 => "x-amz-checksum-sha1",
+        ChecksumAlgorithm::Sha256 => "x-amz-checksum-sha256",
+        ChecksumAlgorithm::Crc64nvme => "x-amz-checksum-crc64nvme",
+    }
+}
+
 /// Record a throughput metric for GET/PUT. We can't inline this into S3CrtClient callbacks because
 /// PUT bytes don't transit those callbacks.
 fn emit_throughput_metric(bytes: u64, duration: Duration, op: &'static str) {
@@ -1113,44 +3312,121 @@ impl ObjectClient for S3CrtClient {
         Some(self.inner.part_size)
     }
 
+    async fn copy_object(
+        &self,
+        source_bucket: &str,
+        source_key: &str,
+        destination_bucket: &str,
+        destination_key: &str,
+        params: &CopyObjectParams,
+    ) -> ObjectClientResult<CopyObjectResult, CopyObjectError, Self::ClientError> {
+        self.check_bucket(destination_bucket).await?;
+        self.check_bucket_name(source_bucket)?;
+        self.copy_object(source_bucket, source_key, destination_bucket, destination_key, params)
+            .await
+    }
+
     async fn delete_object(
         &self,
         bucket: &str,
         key: &str,
+        params: &DeleteObjectParams,
     ) -> ObjectClientResult<DeleteObjectResult, DeleteObjectError, Self::ClientError> {
-        self.delete_object(bucket, key).await
+        self.check_bucket(bucket).await?;
+        self.with_additional_retries(|| self.delete_object(bucket, key, params))
+            .await
+    }
+
+    async fn delete_objects(
+        &self,
+        bucket: &str,
+        keys: &[String],
+    ) -> ObjectClientResult<DeleteObjectsResult, DeleteObjectError, Self::ClientError> {
+        self.check_bucket(bucket).await?;
+        self.delete_objects(bucket, keys).await
     }
 
     async fn get_object(
         &self,
         bucket: &str,
         key: &str,
-        range: Option<Range<u64>>,
-        if_match: Option<ETag>,
-        // TODO: If more arguments are added to get object, make a request struct having those arguments
-        // along with bucket and key.
+        params: &GetObjectParams,
     ) -> ObjectClientResult<Self::GetObjectRequest, GetObjectError, Self::ClientError> {
-        self.get_object(bucket, key, range, if_match)
+        self.check_bucket(bucket).await?;
+        self.get_object(bucket, key, params)
     }
 
     async fn list_objects(
         &self,
         bucket: &str,
         continuation_token: Option<&str>,
-        delimiter: &str,
+        delimiter: Option<&str>,
         max_keys: usize,
         prefix: &str,
     ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, Self::ClientError> {
-        self.list_objects(bucket, continuation_token, delimiter, max_keys, prefix)
-            .await
+        self.check_bucket(bucket).await?;
+        let response = self
+            .list_objects_with_page_retries(
+                bucket,
+                continuation_token,
+                delimiter,
+                max_keys,
+                prefix,
+                self.inner.list_objects_page_retries,
+            )
+            .await?;
+        let result = self.parse_list_objects_response(response)?;
+        self.inner
+            .continuation_tokens
+            .issue(result.next_continuation_token.as_deref(), prefix, delimiter);
+        Ok(result)
+    }
+
+    async fn list_objects_with_handler(
+        &self,
+        bucket: &str,
+        continuation_token: Option<&str>,
+        delimiter: Option<&str>,
+        max_keys: usize,
+        prefix: &str,
+        handler: &mut (dyn FnMut(&ObjectInfo) + Send),
+    ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, Self::ClientError> {
+        self.check_bucket(bucket).await?;
+        let response = self
+            .list_objects_with_page_retries(
+                bucket,
+                continuation_token,
+                delimiter,
+                max_keys,
+                prefix,
+                self.inner.list_objects_page_retries,
+            )
+            .await?;
+        let result = self.parse_list_objects_response_with_handler(response, &mut |object| handler(&object))?;
+        self.inner
+            .continuation_tokens
+            .issue(result.next_continuation_token.as_deref(), prefix, delimiter);
+        Ok(result)
     }
 
     async fn head_object(
         &self,
         bucket: &str,
         key: &str,
+        params: &HeadObjectParams,
     ) -> ObjectClientResult<HeadObjectResult, HeadObjectError, Self::ClientError> {
-        self.head_object(bucket, key).await
+        self.check_bucket(bucket).await?;
+        self.with_additional_retries(|| self.head_object(bucket, key, params))
+            .await
+    }
+
+    async fn object_exists(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<ObjectExistence, HeadObjectError, Self::ClientError> {
+        self.check_bucket(bucket).await?;
+        self.with_additional_retries(|| self.object_exists(bucket, key)).await
     }
 
     async fn put_object(
@@ -1159,9 +3435,21 @@ impl ObjectClient for S3CrtClient {
         key: &str,
         params: &PutObjectParams,
     ) -> ObjectClientResult<Self::PutObjectRequest, PutObjectError, Self::ClientError> {
+        self.check_bucket(bucket).await?;
         self.put_object(bucket, key, params).await
     }
 
+    async fn put_empty_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        params: &PutObjectParams,
+    ) -> ObjectClientResult<PutObjectResult, PutObjectError, Self::ClientError> {
+        self.check_bucket(bucket).await?;
+        self.with_additional_retries(|| self.put_empty_object(bucket, key, params))
+            .await
+    }
+
     async fn get_object_attributes(
         &self,
         bucket: &str,
@@ -1170,16 +3458,60 @@ impl ObjectClient for S3CrtClient {
         part_number_marker: Option<usize>,
         object_attributes: &[ObjectAttribute],
     ) -> ObjectClientResult<GetObjectAttributesResult, GetObjectAttributesError, Self::ClientError> {
-        self.get_object_attributes(bucket, key, max_parts, part_number_marker, object_attributes)
+        self.check_bucket(bucket).await?;
+        self.with_additional_retries(|| {
+            self.get_object_attributes(bucket, key, max_parts, part_number_marker, object_attributes)
+        })
+        .await
+    }
+
+    async fn get_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<Vec<(String, String)>, GetObjectTaggingError, Self::ClientError> {
+        self.check_bucket(bucket).await?;
+        self.with_additional_retries(|| self.get_object_tagging(bucket, key))
+            .await
+    }
+
+    async fn put_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+        tags: &[(String, String)],
+    ) -> ObjectClientResult<(), PutObjectTaggingError, Self::ClientError> {
+        self.check_bucket(bucket).await?;
+        self.with_additional_retries(|| self.put_object_tagging(bucket, key, tags))
+            .await
+    }
+
+    async fn restore_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        days: u32,
+        tier: RestoreTier,
+    ) -> ObjectClientResult<RestoreObjectResult, RestoreObjectError, Self::ClientError> {
+        self.check_bucket(bucket).await?;
+        self.with_additional_retries(|| self.restore_object(bucket, key, days, tier))
             .await
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use mountpoint_s3_crt::auth::credentials::CredentialsProviderStaticOptions;
     use mountpoint_s3_crt::common::error::Error;
+    use mountpoint_s3_crt::common::future::CrtFuture;
     use rusty_fork::rusty_fork_test;
     use std::assert_eq;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::time::SystemTime;
+
+    use crate::endpoint_config::AddressingStyle;
+    use crate::types::SelfTestStep;
 
     use super::*;
     use test_case::test_case;
@@ -1217,10 +3549,49 @@ mod tests {
         client_new_fails_with_invalid_part_size(part_size);
     }
 
-    /// Test if the prefix is added correctly to the User-Agent header
-    #[test]
-    fn test_user_agent_with_prefix() {
-        let user_agent_prefix = String::from("someprefix");
+    fn client_new_fails_with_invalid_multipart_upload_threshold(multipart_upload_threshold: usize) {
+        let config = S3ClientConfig::new().multipart_upload_threshold(multipart_upload_threshold);
+        let e = S3CrtClient::new(config).expect_err("creating a new client should fail");
+        assert!(matches!(e, NewClientError::InvalidConfiguration(_)));
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn client_new_fails_with_greater_multipart_upload_threshold() {
+        let multipart_upload_threshold = 6 * 1024 * 1024 * 1024; // greater than 5GiB
+        client_new_fails_with_invalid_multipart_upload_threshold(multipart_upload_threshold);
+    }
+
+    #[test]
+    fn client_new_fails_with_zero_multipart_upload_threshold() {
+        client_new_fails_with_invalid_multipart_upload_threshold(0);
+    }
+
+    #[test]
+    fn client_new_with_multipart_upload_threshold() {
+        let config = S3ClientConfig::new()
+            .part_size(16 * 1024 * 1024)
+            .multipart_upload_threshold(64 * 1024 * 1024);
+        S3CrtClient::new(config).expect("creating a new client should succeed");
+    }
+
+    #[test]
+    fn client_new_with_memory_limit() {
+        let config = S3ClientConfig::new().memory_limit_in_bytes(2 * 1024 * 1024 * 1024);
+        S3CrtClient::new(config).expect("creating a new client should succeed");
+    }
+
+    #[test]
+    fn client_new_fails_with_small_memory_limit() {
+        let config = S3ClientConfig::new().memory_limit_in_bytes(512 * 1024 * 1024);
+        let e = S3CrtClient::new(config).expect_err("creating a new client should fail");
+        assert!(matches!(e, NewClientError::InvalidConfiguration(_)));
+    }
+
+    /// Test if the prefix is added correctly to the User-Agent header
+    #[test]
+    fn test_user_agent_with_prefix() {
+        let user_agent_prefix = String::from("someprefix");
         let expected_user_agent = "someprefix mountpoint-s3-client/";
 
         let config = S3ClientConfig {
@@ -1231,7 +3602,6 @@ mod tests {
         let client = S3CrtClient::new(config).expect("Create test client");
 
         let mut message = client
-            .inner
             .new_request_template("GET", "doc-example-bucket")
             .expect("new request template expected");
 
@@ -1256,7 +3626,6 @@ mod tests {
         let client = S3CrtClient::new(config).expect("create test client");
 
         let mut message = client
-            .inner
             .new_request_template("GET", "")
             .expect("new request template expected");
 
@@ -1268,6 +3637,137 @@ mod tests {
         assert_eq!(host_header_value.to_string_lossy(), expected_host);
     }
 
+    #[test_case(AddressingStyle::Automatic, "doc-example-bucket.s3.example.com", "/key"; "virtual hosted")]
+    #[test_case(AddressingStyle::Path, "s3.example.com", "/doc-example-bucket/key"; "path")]
+    fn test_addressing_style_host_and_path(
+        addressing_style: AddressingStyle,
+        expected_host: &str,
+        expected_path: &str,
+    ) {
+        let endpoint = Uri::new_from_str(&Allocator::default(), "https://s3.example.com").unwrap();
+        let endpoint_config = EndpointConfig::new("us-east-1")
+            .endpoint(endpoint)
+            .addressing_style(addressing_style);
+        let client =
+            S3CrtClient::new(S3ClientConfig::new().endpoint_config(endpoint_config)).expect("create test client");
+
+        let mut message = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect("new request template expected");
+
+        let headers = message.inner.get_headers().expect("expected a block of HTTP headers");
+        let host_header = headers.get("Host").expect("Host header expected");
+        assert_eq!(host_header.value().to_string_lossy(), expected_host);
+        assert_eq!(build_path_and_query(&message.path_prefix, "/key", &[]), expected_path);
+    }
+
+    #[test_case(None, "us-east-1"; "derived from endpoint")]
+    #[test_case(Some("us-gov-west-1"), "us-gov-west-1"; "overridden")]
+    fn test_signing_region(signing_region: Option<&str>, expected_region: &str) {
+        let mut config = S3ClientConfig::new().endpoint_config(EndpointConfig::new("us-east-1"));
+        if let Some(signing_region) = signing_region {
+            config = config.signing_region(signing_region);
+        }
+        let client = S3CrtClient::new(config).expect("create test client");
+
+        let mut message = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect("new request template expected");
+        let signing_config = message.signing_config.as_ref().expect("requests should be signed");
+        assert_eq!(signing_config.region(), expected_region);
+
+        // The signing region doesn't change the endpoint
+        let headers = message.inner.get_headers().expect("expected a block of HTTP headers");
+        let host_header = headers.get("Host").expect("Host header expected");
+        assert_eq!(
+            host_header.value().to_string_lossy(),
+            "doc-example-bucket.s3.us-east-1.amazonaws.com"
+        );
+    }
+
+    #[test_case(None, SigningAlgorithm::SigV4, "us-east-1"; "derived from endpoint")]
+    #[test_case(Some(SigningAlgorithm::SigV4A), SigningAlgorithm::SigV4A, "*"; "sigv4a")]
+    #[test_case(Some(SigningAlgorithm::SigV4), SigningAlgorithm::SigV4, "us-east-1"; "sigv4")]
+    fn test_signing_algorithm(
+        signing_algorithm: Option<SigningAlgorithm>,
+        expected_algorithm: SigningAlgorithm,
+        expected_region: &str,
+    ) {
+        // A multi-region access point addressed through its endpoint rather than its ARN
+        let endpoint = Uri::new_from_str(
+            &Allocator::default(),
+            "https://mfzwi23gnjvgw.mrap.accesspoint.s3-global.amazonaws.com",
+        )
+        .unwrap();
+        let endpoint_config = EndpointConfig::new("us-east-1").endpoint(endpoint);
+        let mut config = S3ClientConfig::new().endpoint_config(endpoint_config);
+        if let Some(signing_algorithm) = signing_algorithm {
+            config = config.signing_algorithm(signing_algorithm);
+        }
+        let client = S3CrtClient::new(config).expect("create test client");
+
+        let message = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect("new request template expected");
+        let signing_config = message.signing_config.as_ref().expect("requests should be signed");
+        assert_eq!(signing_config.algorithm(), expected_algorithm);
+        assert_eq!(signing_config.region(), expected_region);
+    }
+
+    #[test_case(true, SigningAlgorithm::SigV4Express; "enabled")]
+    #[test_case(false, SigningAlgorithm::SigV4; "disabled")]
+    fn test_express_support(express_support: bool, expected_algorithm: SigningAlgorithm) {
+        let config = S3ClientConfig::new()
+            .endpoint_config(EndpointConfig::new("us-west-2"))
+            .express_support(express_support);
+        let client = S3CrtClient::new(config).expect("create test client");
+
+        let mut message = client
+            .new_request_template("GET", "doc-example-bucket--usw2-az1--x-s3")
+            .expect("new request template expected");
+        let signing_config = message.signing_config.as_ref().expect("requests should be signed");
+        assert_eq!(signing_config.algorithm(), expected_algorithm);
+
+        // Directory buckets always use the zonal endpoint
+        let headers = message.inner.get_headers().expect("expected a block of HTTP headers");
+        let host_header = headers.get("Host").expect("Host header expected");
+        assert_eq!(
+            host_header.value().to_string_lossy(),
+            "doc-example-bucket--usw2-az1--x-s3.s3express-usw2-az1.us-west-2.amazonaws.com"
+        );
+    }
+
+    #[test_case(None, "s3"; "derived from endpoint")]
+    #[test_case(Some("execute-api"), "execute-api"; "overridden")]
+    fn test_signing_service(signing_service: Option<&str>, expected_service: &str) {
+        let mut config = S3ClientConfig::new().endpoint_config(EndpointConfig::new("us-east-1"));
+        if let Some(signing_service) = signing_service {
+            config = config.signing_service(signing_service);
+        }
+        let client = S3CrtClient::new(config).expect("create test client");
+
+        let message = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect("new request template expected");
+        let signing_config = message.signing_config.as_ref().expect("requests should be signed");
+        assert_eq!(signing_config.service(), expected_service);
+    }
+
+    #[test]
+    fn test_signing_algorithm_with_signing_region() {
+        let config = S3ClientConfig::new()
+            .signing_algorithm(SigningAlgorithm::SigV4A)
+            .signing_region("us-east-1,us-west-2");
+        let client = S3CrtClient::new(config).expect("create test client");
+
+        let message = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect("new request template expected");
+        let signing_config = message.signing_config.as_ref().expect("requests should be signed");
+        assert_eq!(signing_config.algorithm(), SigningAlgorithm::SigV4A);
+        assert_eq!(signing_config.region(), "us-east-1,us-west-2");
+    }
+
     // run with rusty_fork to avoid issues with other tests and their env variables.
     rusty_fork_test! {
         #[test]
@@ -1314,7 +3814,6 @@ mod tests {
         let client = S3CrtClient::new(config).expect("Create test client");
 
         let mut message = client
-            .inner
             .new_request_template("GET", "doc-example-bucket")
             .expect("new request template expected");
 
@@ -1330,6 +3829,50 @@ mod tests {
             .starts_with(expected_user_agent));
     }
 
+    /// Test if the suffix is appended to the User-Agent header
+    #[test]
+    fn test_user_agent_with_suffix() {
+        let config = S3ClientConfig::new()
+            .user_agent(UserAgent::new(Some("someprefix".to_string())))
+            .user_agent_suffix("my-app/1.0");
+
+        let client = S3CrtClient::new(config).expect("Create test client");
+
+        let mut message = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect("new request template expected");
+
+        let headers = message.inner.get_headers().expect("Expected a block of HTTP headers");
+        let user_agent_header_value = headers
+            .get("User-Agent")
+            .expect("User Agent Header expected with given suffix")
+            .value()
+            .to_string_lossy()
+            .into_owned();
+
+        assert!(user_agent_header_value.starts_with("someprefix mountpoint-s3-client/"));
+        assert!(user_agent_header_value.ends_with(" my-app/1.0"));
+    }
+
+    #[test_case("my-app\r\nX-Injected: true"; "newline")]
+    #[test_case("my app (test)"; "comment")]
+    #[test_case(""; "empty")]
+    fn test_invalid_user_agent_suffix(suffix: &str) {
+        let config = S3ClientConfig::new().user_agent_suffix(suffix);
+        let err = S3CrtClient::new(config).expect_err("invalid suffix should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+
+    #[test]
+    fn test_tls_config() {
+        let config = S3ClientConfig::new().tls_config(TlsConfig::new().insecure_skip_verify(true));
+        S3CrtClient::new(config).expect("client with TLS config should be created");
+
+        let config = S3ClientConfig::new().tls_config(TlsConfig::new().ca_file("/nonexistent/ca-bundle.pem"));
+        let err = S3CrtClient::new(config).expect_err("missing CA file should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+
     #[test_case("bytes 200-1000/67589" => Some(200..1001))]
     #[test_case("bytes 200-1000/*" => Some(200..1001))]
     #[test_case("bytes 200-1000" => None)]
@@ -1342,6 +3885,27 @@ mod tests {
         extract_range_header(&headers)
     }
 
+    #[test_case("/", &[], "/"; "no query")]
+    #[test_case("/", &[QueryFragment::Flag("tagging")], "/?tagging"; "bare flag")]
+    #[test_case("/key", &[QueryFragment::Pair("attributes", "")], "/key?attributes="; "empty value")]
+    #[test_case("/", &[QueryFragment::Pair("prefix", "a b/c&d=e+f?#%")], "/?prefix=a%20b%2Fc%26d%3De%2Bf%3F%23%25"; "reserved characters in value")]
+    #[test_case("/", &[QueryFragment::Pair("b", "1"), QueryFragment::Flag("a"), QueryFragment::Pair("b", "2")], "/?b=1&a&b=2"; "insertion order and repeated keys")]
+    fn build_query_string(path: &str, query: &[QueryFragment<'_>], expected: &str) {
+        let full_path = build_path_and_query("", path, query);
+        assert_eq!(full_path, OsStr::new(expected));
+    }
+
+    #[test_case("/key", "/key"; "simple key")]
+    #[test_case("/dir/sub dir/file name.txt", "/dir/sub%20dir/file%20name.txt"; "spaces")]
+    #[test_case("/a#b+c?d&e=f%g", "/a%23b%2Bc%3Fd%26e%3Df%25g"; "reserved characters")]
+    #[test_case("/-._~", "/-._~"; "unreserved characters")]
+    #[test_case("/a//b///c/", "/a//b///c/"; "consecutive slashes")]
+    #[test_case("/日本語/ファイル", "/%E6%97%A5%E6%9C%AC%E8%AA%9E/%E3%83%95%E3%82%A1%E3%82%A4%E3%83%AB"; "unicode")]
+    #[test_case("/café", "/caf%C3%A9"; "accented")]
+    fn encode_path(path: &str, expected: &str) {
+        assert_eq!(encode_s3_path(path), OsStr::new(expected));
+    }
+
     /// Simple test to ensure the expected bucket owner can be set
     #[test]
     fn test_expected_bucket_owner() {
@@ -1352,7 +3916,6 @@ mod tests {
         let client = S3CrtClient::new(config).expect("Create test client");
 
         let mut message = client
-            .inner
             .new_request_template("GET", "doc-example-bucket")
             .expect("new request template expected");
 
@@ -1368,6 +3931,140 @@ mod tests {
             .starts_with(expected_bucket_owner));
     }
 
+    #[test]
+    fn test_default_headers() {
+        let config = S3ClientConfig::new()
+            .default_header("x-tenant-id", "tenant-1")
+            .default_header("x-gateway-route", "blue");
+        let client = S3CrtClient::new(config).expect("Create test client");
+
+        let mut message = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect("new request template expected");
+
+        let headers = message.inner.get_headers().expect("Expected a block of HTTP headers");
+        let value = |name: &str| {
+            headers
+                .get(name)
+                .map(|header| header.value().to_string_lossy().into_owned())
+        };
+        assert_eq!(value("x-tenant-id").unwrap(), "tenant-1");
+        assert_eq!(value("x-gateway-route").unwrap(), "blue");
+    }
+
+    #[test]
+    fn test_default_headers_are_not_duplicated() {
+        let config = S3ClientConfig::new()
+            .default_header("accept", "application/json")
+            .default_header("x-tenant-id", "tenant-1")
+            .default_header("X-Tenant-Id", "tenant-2")
+            .default_header("x-amz-request-payer", "other")
+            .request_payer("requester");
+        let client = S3CrtClient::new(config).expect("Create test client");
+
+        let mut message = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect("new request template expected");
+
+        let headers = message.inner.get_headers().expect("Expected a block of HTTP headers");
+        let values = |name: &str| {
+            headers
+                .iter()
+                .filter(|(header, _)| header.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values("accept"), vec!["application/json"]);
+        assert_eq!(values("x-tenant-id"), vec!["tenant-2"]);
+        assert_eq!(values("x-amz-request-payer"), vec!["requester"]);
+    }
+
+    #[test_case("Authorization"; "authorization")]
+    #[test_case("host"; "host, lowercase")]
+    #[test_case("X-AMZ-SECURITY-TOKEN"; "security token, uppercase")]
+    #[test_case(""; "empty name")]
+    fn test_reserved_default_header(name: &str) {
+        let config = S3ClientConfig::new().default_header(name, "value");
+        let err = S3CrtClient::new(config).expect_err("reserved header should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)), "got {err:?}");
+    }
+
+    #[test_case(None, None, None; "no request payer")]
+    #[test_case(Some("requester"), None, Some("requester"); "client-wide request payer")]
+    #[test_case(None, Some("requester"), Some("requester"); "per-request request payer")]
+    #[test_case(Some("requester"), Some("other"), Some("other"); "per-request overrides client-wide")]
+    fn test_request_payer_override(
+        client_request_payer: Option<&str>,
+        request_request_payer: Option<&str>,
+        expected: Option<&str>,
+    ) {
+        let (port, requests) = start_scripted_server_with_headers(vec![(200, "")]);
+        let mut config = S3ClientConfig::new();
+        if let Some(request_payer) = client_request_payer {
+            config = config.request_payer(request_payer);
+        }
+        let client = new_stalling_server_client(port, config);
+
+        let params = HeadObjectParams::new().request_payer(request_request_payer.map(str::to_owned));
+        // The scripted response isn't a valid HeadObject response, but we only need the request
+        let _ = futures::executor::block_on(client.head_object("test-bucket", "test-key", &params));
+
+        let request = requests.recv().expect("request should be recorded");
+        let request_payer_header = request.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("x-amz-request-payer")
+                .then(|| value.trim().to_owned())
+        });
+        assert_eq!(request_payer_header.as_deref(), expected);
+    }
+
+    #[test_case(16, 1; "cache enabled")]
+    #[test_case(0, 10; "cache disabled")]
+    fn test_endpoint_cache(capacity: usize, expected_resolutions: u64) {
+        let config = S3ClientConfig::new()
+            .endpoint_config(EndpointConfig::new("us-west-2"))
+            .endpoint_cache_capacity(capacity);
+        let client = S3CrtClient::new(config).expect("Create test client");
+
+        for _ in 0..10 {
+            let mut message = client
+                .new_request_template("GET", "doc-example-bucket")
+                .expect("new request template expected");
+            let headers = message.inner.get_headers().expect("Expected a block of HTTP headers");
+            let host = headers.get("Host").expect("Host header expected");
+            assert_eq!(host.value(), "doc-example-bucket.s3.us-west-2.amazonaws.com");
+        }
+        assert_eq!(
+            client.inner.endpoint_cache.resolutions.load(Ordering::Relaxed),
+            expected_resolutions
+        );
+
+        // A different bucket needs its own resolution
+        let _ = client
+            .new_request_template("GET", "doc-example-bucket-2")
+            .expect("new request template expected");
+        assert_eq!(
+            client.inner.endpoint_cache.resolutions.load(Ordering::Relaxed),
+            expected_resolutions + 1
+        );
+    }
+
+    #[test]
+    fn test_endpoint_cache_eviction() {
+        let config = S3ClientConfig::new()
+            .endpoint_config(EndpointConfig::new("us-west-2"))
+            .endpoint_cache_capacity(2);
+        let client = S3CrtClient::new(config).expect("Create test client");
+
+        for bucket in ["doc-example-bucket-1", "doc-example-bucket-2", "doc-example-bucket-3"] {
+            let _ = client
+                .new_request_template("GET", bucket)
+                .expect("new request template expected");
+        }
+        assert_eq!(client.inner.endpoint_cache.entries.lock().unwrap().len(), 2);
+        assert_eq!(client.inner.endpoint_cache.resolutions.load(Ordering::Relaxed), 3);
+    }
+
     fn make_result(
         response_status: i32,
         body: impl Into<OsString>,
@@ -1383,6 +4080,8 @@ mod tests {
             crt_error: 1i32.into(),
             error_response_headers,
             error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
         }
     }
 
@@ -1453,6 +4152,40 @@ mod tests {
         assert_eq!(message, "The request signature we calculated does not match the signature you provided. Check your key and signing method.");
     }
 
+    #[test]
+    fn parse_403_request_time_too_skewed() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>RequestTimeTooSkewed</Code><Message>The difference between the request time and the current time is too large.</Message><RequestTime>20240101T000000Z</RequestTime><ServerTime>2024-01-01T01:00:00Z</ServerTime><MaxAllowedSkewMilliseconds>900000</MaxAllowedSkewMilliseconds><RequestId>CM0R497NB0WAQ977</RequestId><HostId>w1TqUKGaIuNAIgzqm/L2azuzgEBINxTngWPbV1iH2IvpLsVCCTKHJTh4HsGp4JnggHqVkA+KN1MGqHDw1+WEuA==</HostId></Error>"#;
+        let mut result = make_result(403, OsStr::from_bytes(&body[..]), None);
+        let parsed = try_parse_generic_error(&result);
+        let Some(S3RequestError::ClockSkew { server_time, metadata }) = parsed else {
+            panic!("wrong result, got: {:?}", parsed);
+        };
+        assert_eq!(
+            server_time,
+            Some(OffsetDateTime::parse("2024-01-01T01:00:00Z", &Rfc3339).unwrap())
+        );
+        assert_eq!(metadata.error_code.as_deref(), Some("RequestTimeTooSkewed"));
+        assert_eq!(
+            metadata.error_message.as_deref(),
+            Some("The difference between the request time and the current time is too large.")
+        );
+
+        // The Date header takes precedence over the time in the body
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        headers
+            .add_header(&Header::new("Date", "Mon, 01 Jan 2024 01:00:05 GMT"))
+            .unwrap();
+        result.error_response_headers = Some(headers);
+        let parsed = try_parse_generic_error(&result);
+        let Some(S3RequestError::ClockSkew { server_time, .. }) = parsed else {
+            panic!("wrong result, got: {:?}", parsed);
+        };
+        assert_eq!(
+            server_time,
+            Some(OffsetDateTime::parse("2024-01-01T01:00:05Z", &Rfc3339).unwrap())
+        );
+    }
+
     #[test]
     fn parse_403_made_up_error() {
         // A made up error to check that we map all 403s even if we don't recognize them
@@ -1465,12 +4198,116 @@ mod tests {
         assert_eq!(message, "This error is made up.");
     }
 
+    #[test]
+    fn parse_403_request_ids() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>AccessDenied</Code><Message>Access Denied</Message><RequestId>CM0R497NB0WAQ977</RequestId><HostId>w1TqUKGaIuNAIgzqm/L2azuzgEBINxTngWPbV1iH2IvpLsVCCTKHJTh4HsGp4JnggHqVkA+KN1MGqHDw1+WEuA==</HostId></Error>"#;
+        let mut result = make_result(403, OsStr::from_bytes(&body[..]), None);
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        headers
+            .add_header(&Header::new("x-amz-request-id", "CM0R497NB0WAQ977"))
+            .unwrap();
+        headers
+            .add_header(&Header::new(
+                "x-amz-id-2",
+                "w1TqUKGaIuNAIgzqm/L2azuzgEBINxTngWPbV1iH2IvpLsVCCTKHJTh4HsGp4JnggHqVkA+KN1MGqHDw1+WEuA==",
+            ))
+            .unwrap();
+        result.error_response_headers = Some(headers);
+
+        let result = try_parse_generic_error(&result).expect("403 should be a modeled error");
+        assert_eq!(
+            result.meta(),
+            ClientErrorMetadata {
+                http_code: Some(403),
+                error_code: Some("AccessDenied".to_owned()),
+                error_message: Some("Access Denied".to_owned()),
+                request_id: Some("CM0R497NB0WAQ977".to_owned()),
+                extended_request_id: Some(
+                    "w1TqUKGaIuNAIgzqm/L2azuzgEBINxTngWPbV1iH2IvpLsVCCTKHJTh4HsGp4JnggHqVkA+KN1MGqHDw1+WEuA=="
+                        .to_owned()
+                ),
+            }
+        );
+    }
+
+    #[test]
+    fn response_error_request_ids() {
+        // Non-AWS endpoints might not return request IDs
+        let error = S3RequestError::ResponseError(make_crt_error_result(500, 1i32.into()));
+        assert_eq!(
+            error.meta(),
+            ClientErrorMetadata {
+                http_code: Some(500),
+                ..Default::default()
+            }
+        );
+
+        let mut result = make_crt_error_result(500, 1i32.into());
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        headers
+            .add_header(&Header::new("x-amz-request-id", "TX3KXRW0T8VPHC8Q"))
+            .unwrap();
+        result.error_response_headers = Some(headers);
+        let error = S3RequestError::ResponseError(result);
+        let meta = error.meta();
+        assert_eq!(meta.request_id.as_deref(), Some("TX3KXRW0T8VPHC8Q"));
+        assert_eq!(meta.extended_request_id, None);
+    }
+
+    #[test]
+    fn test_parse_s3_error_body() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>EntityTooLarge</Code><Message>Your proposed upload exceeds the maximum allowed size</Message><ProposedSize>5368709121</ProposedSize><MaxAllowedSize>5368709120</MaxAllowedSize><RequestId>CM0R497NB0WAQ977</RequestId><HostId>aGVsbG8=</HostId><Resource/></Error>"#;
+        let details = parse_s3_error_body(body).expect("error body should parse");
+        assert_eq!(details.code.as_deref(), Some("EntityTooLarge"));
+        assert_eq!(
+            details.message.as_deref(),
+            Some("Your proposed upload exceeds the maximum allowed size")
+        );
+        let expected_fields = [
+            ("ProposedSize", "5368709121"),
+            ("MaxAllowedSize", "5368709120"),
+            ("RequestId", "CM0R497NB0WAQ977"),
+            ("HostId", "aGVsbG8="),
+            ("Resource", ""),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .collect();
+        assert_eq!(details.extra_fields, expected_fields);
+    }
+
+    #[test_case(b"<ListBucketResult><Name>test-bucket</Name></ListBucketResult>"; "other document")]
+    #[test_case(b"not xml"; "not xml")]
+    fn test_parse_s3_error_body_not_error(body: &[u8]) {
+        assert_eq!(parse_s3_error_body(body), None);
+    }
+
+    #[test]
+    fn response_error_details() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InvalidArgument</Code><Message>Invalid argument</Message><ArgumentName>partNumber</ArgumentName></Error>"#;
+        let error = S3RequestError::ResponseError(make_result(400, OsStr::from_bytes(&body[..]), None));
+        let details = error.error_details().expect("response error should have details");
+        assert_eq!(details.code.as_deref(), Some("InvalidArgument"));
+        assert_eq!(
+            details.extra_fields.get("ArgumentName").map(String::as_str),
+            Some("partNumber")
+        );
+
+        let meta = error.meta();
+        assert_eq!(meta.error_code.as_deref(), Some("InvalidArgument"));
+        assert_eq!(meta.error_message.as_deref(), Some("Invalid argument"));
+
+        assert_eq!(S3RequestError::Throttled.error_details(), None);
+    }
+
     fn make_crt_error_result(response_status: i32, crt_error: Error) -> MetaRequestResult {
         MetaRequestResult {
             response_status,
             crt_error,
             error_response_headers: None,
             error_response_body: None,
+            validation_algorithm: None,
+            metrics: Default::default(),
         }
     }
 
@@ -1495,4 +4332,1758 @@ mod tests {
         };
         assert_eq!(error, error_code.into());
     }
+
+    #[test]
+    fn parse_checksum_mismatch() {
+        let error_code = mountpoint_s3_crt_sys::aws_s3_errors::AWS_ERROR_S3_RESPONSE_CHECKSUM_MISMATCH as i32;
+        let mut result = make_crt_error_result(200, error_code.into());
+        result.validation_algorithm = Some(ChecksumAlgorithm::Crc32c);
+        let result = try_parse_generic_error(&result);
+        let Some(S3RequestError::ChecksumValidationError(algorithm)) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+        assert_eq!(algorithm, Some(ChecksumAlgorithm::Crc32c));
+    }
+
+    #[test]
+    fn test_dual_stack_with_custom_endpoint() {
+        let endpoint = Uri::new_from_str(&Allocator::default(), "https://example.com").unwrap();
+        let endpoint_config = EndpointConfig::new("us-east-1").use_dual_stack(true).endpoint(endpoint);
+        let client =
+            S3CrtClient::new(S3ClientConfig::new().endpoint_config(endpoint_config)).expect("Create test client");
+
+        let err = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect_err("dual stack with a custom endpoint should fail");
+        assert!(matches!(
+            err,
+            ConstructionError::InvalidEndpoint(EndpointError::DualStackWithCustomEndpoint)
+        ));
+    }
+
+    #[test]
+    fn test_accelerate_host() {
+        let endpoint_config = EndpointConfig::new("eu-west-1").use_accelerate(true);
+        let client =
+            S3CrtClient::new(S3ClientConfig::new().endpoint_config(endpoint_config)).expect("create test client");
+
+        let mut message = client
+            .new_request_template("GET", "doc-example-bucket")
+            .expect("new request template expected");
+        let headers = message.inner.get_headers().expect("expected a block of HTTP headers");
+        let host_header = headers.get("Host").expect("Host header expected");
+        assert_eq!(
+            host_header.value().to_string_lossy(),
+            "doc-example-bucket.s3-accelerate.amazonaws.com"
+        );
+        assert_eq!(build_path_and_query(&message.path_prefix, "/key", &[]), "/key");
+        // Accelerate endpoints are signed for the bucket's region
+        let signing_config = message.signing_config.as_ref().expect("requests should be signed");
+        assert_eq!(signing_config.region(), "eu-west-1");
+    }
+
+    #[test_case(ChecksumMode::Disabled; "disabled")]
+    #[test_case(ChecksumMode::Enabled; "enabled")]
+    fn test_checksum_mode_config(checksum_mode: ChecksumMode) {
+        let config = S3ClientConfig::new().checksum_mode(checksum_mode);
+        let client = S3CrtClient::new(config).expect("Create test client");
+        assert_eq!(client.inner.checksum_mode, checksum_mode);
+    }
+
+    #[test_case(None; "default")]
+    #[test_case(Some(1); "one")]
+    #[test_case(Some(64); "many")]
+    fn test_max_active_connections_config(max_active_connections: Option<u32>) {
+        let mut config = S3ClientConfig::new();
+        if let Some(max_active_connections) = max_active_connections {
+            config = config.max_active_connections(max_active_connections);
+        }
+        let client = S3CrtClient::new(config).expect("Create test client");
+        assert_eq!(client.inner.max_active_connections, max_active_connections);
+    }
+
+    #[test]
+    fn test_max_active_connections_zero() {
+        let config = S3ClientConfig::new().max_active_connections(0);
+        let err = S3CrtClient::new(config).expect_err("zero connections should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+
+    #[test]
+    fn test_invalid_bucket_name_not_sent() {
+        // Nothing listens on this endpoint, so the request would fail differently if it were sent
+        let client = new_stalling_server_client(1, S3ClientConfig::new());
+        let result = futures::executor::block_on(client.list_objects("Doc_Example_Bucket", None, None, 1000, ""));
+        assert!(
+            matches!(
+                result,
+                Err(ObjectClientError::ClientError(S3RequestError::InvalidBucketName { .. }))
+            ),
+            "got {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_legacy_bucket_name_sent_without_validation() {
+        let (port, requests) = start_scripted_server(vec![(200, EMPTY_LIST_RESPONSE)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new().validate_bucket_names(false));
+        futures::executor::block_on(client.list_objects("Legacy_Bucket", None, None, 1000, ""))
+            .expect("legacy bucket name should be sent");
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /Legacy_Bucket/?"), "got {request}");
+    }
+
+    #[test]
+    fn test_empty_network_interface_name() {
+        let config = S3ClientConfig::new().network_interface_names(vec!["eth0".to_owned(), "".to_owned()]);
+        let err = S3CrtClient::new(config).expect_err("empty interface name should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+
+    #[test_case(60, 10, 3, true; "all set")]
+    #[test_case(0, 0, 0, true; "system defaults")]
+    #[test_case(60, 0, 3, false; "missing probe timeout")]
+    #[test_case(0, 10, 0, false; "missing interval")]
+    fn test_tcp_keep_alive_options(interval_sec: u16, timeout_sec: u16, max_failed_probes: u16, valid: bool) {
+        let tcp_keep_alive = TcpKeepAliveOptions {
+            keep_alive_interval_sec: interval_sec,
+            keep_alive_timeout_sec: timeout_sec,
+            keep_alive_max_failed_probes: max_failed_probes,
+        };
+        let result = S3CrtClient::new(S3ClientConfig::new().tcp_keep_alive(Some(tcp_keep_alive)));
+        if valid {
+            result.expect("keepalive options should be accepted");
+        } else {
+            let err = result.expect_err("keepalive options should be rejected");
+            assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+        }
+    }
+
+    #[test]
+    fn test_parse_user_metadata() {
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        for (name, value) in [
+            ("x-amz-meta-origin", "camera-7"),
+            ("X-Amz-Meta-Project-Name", "Mountpoint"),
+            ("x-amz-meta-", "empty key"),
+            ("x-amz-request-id", "TESTREQUESTID"),
+            ("x-amz-metadata-directive", "COPY"),
+        ] {
+            headers.add_header(&Header::new(name, value)).unwrap();
+        }
+        let expected = HashMap::from([
+            ("origin".to_owned(), "camera-7".to_owned()),
+            ("Project-Name".to_owned(), "Mountpoint".to_owned()),
+            ("".to_owned(), "empty key".to_owned()),
+        ]);
+        assert_eq!(parse_user_metadata(&headers), expected);
+    }
+
+    #[test]
+    fn test_parse_content_headers() {
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        for (name, value) in [
+            ("Content-Type", "text/html"),
+            ("content-encoding", "gzip"),
+            ("Cache-Control", "max-age=3600, public"),
+            ("Content-Disposition", "attachment; filename=\"index.html\""),
+            ("Expires", "Wed, 21 Oct 2015 07:28:00 GMT"),
+        ] {
+            headers.add_header(&Header::new(name, value)).unwrap();
+        }
+        let content_headers = parse_content_headers(&headers);
+        assert_eq!(content_headers.content_encoding.as_deref(), Some("gzip"));
+        assert_eq!(content_headers.cache_control.as_deref(), Some("max-age=3600, public"));
+        assert_eq!(
+            content_headers.content_disposition.as_deref(),
+            Some("attachment; filename=\"index.html\"")
+        );
+        assert_eq!(content_headers.content_language, None);
+        assert_eq!(
+            content_headers.expires.as_deref(),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT")
+        );
+    }
+
+    #[test_case(
+        r#"expiry-date="Fri, 21 Dec 2012 00:00:00 GMT", rule-id="picture-deletion-rule""#,
+        Some((1356048000, "picture-deletion-rule"));
+        "expiration"
+    )]
+    #[test_case(
+        r#"rule-id="rule%2C%20one",expiry-date="Sat, 01 Jun 2024 00:00:00 GMT""#,
+        Some((1717200000, "rule, one"));
+        "reordered with encoded rule id"
+    )]
+    #[test_case(r#"expiry-date="Fri, 21 Dec 2012 00:00:00 GMT""#, None; "missing rule id")]
+    #[test_case(r#"expiry-date="yesterday", rule-id="rule""#, None; "invalid date")]
+    #[test_case(r#"expiry-date="Fri, 21 Dec 2012 00:00:00 GMT, rule-id="rule""#, None; "unterminated quote")]
+    #[test_case(r#"expiry-date=Fri, rule-id="rule""#, None; "unquoted value")]
+    fn test_parse_expiration(header: &str, expected: Option<(i64, &str)>) {
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        headers.add_header(&Header::new("x-amz-expiration", header)).unwrap();
+        let expiration = parse_expiration(&headers);
+        let expiration = expiration
+            .as_ref()
+            .map(|e| (e.expiry.unix_timestamp(), e.rule_id.as_str()));
+        assert_eq!(expiration, expected);
+    }
+
+    #[test_case("Sun, 06 Nov 1994 08:49:37 GMT", 784111777; "imf-fixdate")]
+    #[test_case("Sun, 06 Nov 1994 08:49:37 +0000", 784111777; "numeric offset")]
+    #[test_case("Sun, 06 Nov 1994 10:49:37 +0200", 784111777; "non-utc offset")]
+    #[test_case("Sun Nov  6 08:49:37 1994", 784111777; "asctime")]
+    #[test_case(" Sun, 06 Nov 1994 08:49:37 GMT ", 784111777; "surrounding whitespace")]
+    fn test_parse_http_date(value: &str, expected: i64) {
+        let time = parse_http_date(value).expect("date should parse");
+        assert_eq!(time.unix_timestamp(), expected);
+        assert_eq!(time.nanosecond(), 0);
+    }
+
+    #[test_case("Sunday, 06-Nov-94 08:49:37 GMT"; "rfc 850")]
+    #[test_case("1994-11-06T08:49:37Z"; "rfc 3339")]
+    #[test_case("Mon, 06 Nov 1994 08:49:37 GMT"; "wrong weekday")]
+    #[test_case(""; "empty")]
+    fn test_parse_http_date_invalid(value: &str) {
+        parse_http_date(value).expect_err("date shouldn't parse");
+    }
+
+    #[test]
+    fn test_parse_http_date_matches_listing() {
+        let header = parse_http_date("Wed, 12 Oct 2009 17:50:30 GMT").unwrap();
+        let listed = OffsetDateTime::parse("2009-10-12T17:50:30.000Z", &Rfc3339).unwrap();
+        assert_eq!(header, listed);
+    }
+
+    #[test]
+    fn test_parse_expiration_missing() {
+        let headers = Headers::new(&Allocator::default()).unwrap();
+        assert_eq!(parse_expiration(&headers), None);
+    }
+
+    #[test_case(&[("x-amz-request-charged", "requester")], true; "requester charged")]
+    #[test_case(&[], false; "no header")]
+    #[test_case(&[("x-amz-request-charged", "owner")], false; "unknown value")]
+    fn test_parse_request_charged(headers: &[(&str, &str)], expected: bool) {
+        let mut response_headers = Headers::new(&Allocator::default()).unwrap();
+        for (name, value) in headers {
+            response_headers.add_header(&Header::new(*name, *value)).unwrap();
+        }
+        assert_eq!(parse_request_charged(&response_headers), expected);
+    }
+
+    #[test]
+    fn parse_connect_timeout() {
+        let error_code = mountpoint_s3_crt_sys::aws_io_errors::AWS_IO_SOCKET_TIMEOUT as i32;
+        let result = make_crt_error_result(0, error_code.into());
+        let result = try_parse_generic_error(&result);
+        let Some(S3RequestError::Timeout(TimeoutKind::Connect)) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+    }
+
+    #[test_case(Some(10), 10, true; "complete body")]
+    #[test_case(Some(10), 4, false; "truncated body")]
+    #[test_case(Some(10), 12, false; "body longer than expected")]
+    #[test_case(None, 4, true; "no content length")]
+    #[test_case(Some(10), 0, false; "no body")]
+    #[test_case(Some(0), 0, true; "empty body")]
+    fn test_check_body_length(content_length: Option<u64>, received: usize, ok: bool) {
+        match check_body_length(content_length, received) {
+            Ok(()) => assert!(ok, "should have failed"),
+            Err(S3RequestError::IncompleteBody {
+                expected,
+                received: actual,
+            }) => {
+                assert!(!ok, "should have succeeded");
+                assert_eq!(Some(expected), content_length);
+                assert_eq!(actual, received as u64);
+            }
+            Err(e) => panic!("wrong error: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn test_get_without_body_is_incomplete() {
+        let client = new_stalling_server_client(start_stalling_server(""), S3ClientConfig::new());
+
+        // HEAD responses have the Content-Length of the body a GET would return, so aren't checked
+        let head = client
+            .new_request_template("HEAD", "test-bucket")
+            .expect("new request template expected");
+        assert!(!head.verify_body_length);
+
+        // A GET whose connection dropped before the first byte of its body didn't get all of it
+        let get = client
+            .new_request_template("GET", "test-bucket")
+            .expect("new request template expected");
+        assert!(get.verify_body_length);
+        let Err(S3RequestError::IncompleteBody { expected, received }) = check_body_length(Some(10), 0) else {
+            panic!("GET without a body should be incomplete");
+        };
+        assert_eq!((expected, received), (10, 0));
+    }
+
+    /// Start a server on localhost that reads the first request on each connection, writes the
+    /// given (possibly incomplete) response, and then stalls without ever closing the connection.
+    fn start_stalling_server(response: &'static str) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind stalling server");
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let mut connections = Vec::new();
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let _ = stream.write_all(response.as_bytes());
+                connections.push(stream);
+            }
+        });
+        port
+    }
+
+    /// Start a server that answers each connection with the next of the given response statuses and
+    /// bodies, and then closes it. Returns the port and a channel that receives the request line of
+    /// each request.
+    fn start_scripted_server(responses: Vec<(u16, &'static str)>) -> (u16, std::sync::mpsc::Receiver<String>) {
+        start_scripted_server_recording(without_response_headers(responses), record_request_line)
+    }
+
+    /// Like [start_scripted_server], but the channel receives the request line and headers of each
+    /// request.
+    fn start_scripted_server_with_headers(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (u16, std::sync::mpsc::Receiver<String>) {
+        start_scripted_server_recording(without_response_headers(responses), |request| {
+            request.split("\r\n\r\n").next().unwrap_or_default().to_string()
+        })
+    }
+
+    /// Headers for a scripted server to send with a response, by name
+    type ScriptedHeaders = &'static [(&'static str, &'static str)];
+
+    /// Like [start_scripted_server], but each response also sends the given headers.
+    fn start_scripted_server_with_response_headers(
+        responses: Vec<(u16, ScriptedHeaders, &'static str)>,
+    ) -> (u16, std::sync::mpsc::Receiver<String>) {
+        start_scripted_server_recording(responses, record_request_line)
+    }
+
+    fn without_response_headers(responses: Vec<(u16, &'static str)>) -> Vec<(u16, ScriptedHeaders, &'static str)> {
+        responses
+            .into_iter()
+            .map(|(status, body)| (status, &[][..], body))
+            .collect()
+    }
+
+    fn record_request_line(request: &str) -> String {
+        request.lines().next().unwrap_or_default().to_string()
+    }
+
+    fn start_scripted_server_recording(
+        responses: Vec<(u16, ScriptedHeaders, &'static str)>,
+        record: fn(&str) -> String,
+    ) -> (u16, std::sync::mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind scripted server");
+        let port = listener.local_addr().unwrap().port();
+        let (request_tx, request_rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for (stream, (status, headers, body)) in listener.incoming().zip(responses) {
+                let Ok(mut stream) = stream else { continue };
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let _ = request_tx.send(record(&request));
+                let headers: String = headers
+                    .iter()
+                    .map(|(name, value)| format!("{name}: {value}\r\n"))
+                    .collect();
+                let response = format!(
+                    "HTTP/1.1 {status} Scripted\r\nContent-Length: {}\r\nConnection: close\r\n{headers}\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        (port, request_rx)
+    }
+
+    fn new_stalling_server_client(port: u16, config: S3ClientConfig) -> S3CrtClient {
+        let endpoint = Uri::new_from_str(&Allocator::default(), format!("http://127.0.0.1:{port}")).unwrap();
+        let endpoint_config = EndpointConfig::new("us-east-1")
+            .endpoint(endpoint)
+            .addressing_style(AddressingStyle::Path);
+        let config = config
+            .endpoint_config(endpoint_config)
+            .auth_config(S3ClientAuthConfig::NoSigning);
+        S3CrtClient::new(config).expect("create test client")
+    }
+
+    const EMPTY_LIST_RESPONSE: &str = r#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>test-bucket</Name><Prefix></Prefix><KeyCount>0</KeyCount><MaxKeys>1000</MaxKeys><Delimiter>/</Delimiter><IsTruncated>false</IsTruncated></ListBucketResult>"#;
+
+    #[test_case(EMPTY_LIST_RESPONSE.len(), true; "body at the limit")]
+    #[test_case(EMPTY_LIST_RESPONSE.len() - 1, false; "body over the limit")]
+    fn test_max_response_body_bytes(max_response_body_bytes: usize, ok: bool) {
+        let (port, _requests) = start_scripted_server(vec![(200, EMPTY_LIST_RESPONSE)]);
+        let config = S3ClientConfig::new().max_response_body_bytes(max_response_body_bytes);
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(ObjectClient::list_objects(
+            &client,
+            "test-bucket",
+            None,
+            Some("/"),
+            1000,
+            "",
+        ));
+        match result {
+            Ok(_) => assert!(ok, "should have failed"),
+            Err(ObjectClientError::ClientError(S3RequestError::ResponseTooLarge { limit })) => {
+                assert!(!ok, "should have succeeded");
+                assert_eq!(limit, max_response_body_bytes);
+            }
+            Err(e) => panic!("wrong error: {e:?}"),
+        }
+    }
+
+    #[test_case(None, vec![(503, ""), (503, ""), (200, EMPTY_LIST_RESPONSE)], true; "no budget")]
+    #[test_case(NonZeroUsize::new(1), vec![(503, ""), (200, EMPTY_LIST_RESPONSE)], true; "retry within budget")]
+    #[test_case(NonZeroUsize::new(1), vec![(503, ""), (503, ""), (200, EMPTY_LIST_RESPONSE)], false; "exhausted budget")]
+    fn test_retry_budget(retry_budget: Option<NonZeroUsize>, responses: Vec<(u16, &'static str)>, ok: bool) {
+        let (port, requests) = start_scripted_server(responses);
+        // Without jitter, there's a full backoff before each retry for the budget to cancel it in
+        let mut config = S3ClientConfig::new().backoff_jitter_mode(ExponentialBackoffJitterMode::None);
+        if let Some(retry_budget) = retry_budget {
+            config = config.retry_budget(retry_budget);
+        }
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(client.list_objects("test-bucket", None, None, 1000, ""));
+        match result {
+            Ok(_) => assert!(ok, "should have failed"),
+            Err(ObjectClientError::ClientError(S3RequestError::RetryBudgetExhausted)) => {
+                assert!(!ok, "should have succeeded");
+                // The second 503 used up the budget, so there was no third request
+                assert_eq!(requests.try_iter().count(), 2);
+            }
+            Err(e) => panic!("wrong error: {e:?}"),
+        }
+    }
+
+    #[test_case(0.0, false; "no refill")]
+    #[test_case(1.0, true; "refill")]
+    fn test_retry_budget_refill(refill: f64, ok: bool) {
+        let (port, _requests) = start_scripted_server(vec![
+            (503, ""),
+            (200, EMPTY_LIST_RESPONSE),
+            (503, ""),
+            (200, EMPTY_LIST_RESPONSE),
+        ]);
+        let config = S3ClientConfig::new()
+            .backoff_jitter_mode(ExponentialBackoffJitterMode::None)
+            .retry_budget(NonZeroUsize::new(1).unwrap())
+            .retry_budget_refill(refill);
+        let client = new_stalling_server_client(port, config);
+
+        // The first listing's retry takes the only token, and its success may return it
+        futures::executor::block_on(client.list_objects("test-bucket", None, None, 1000, ""))
+            .expect("first listing should succeed");
+        let result = futures::executor::block_on(client.list_objects("test-bucket", None, None, 1000, ""));
+        match result {
+            Ok(_) => assert!(ok, "should have failed"),
+            Err(ObjectClientError::ClientError(S3RequestError::RetryBudgetExhausted)) => {
+                assert!(!ok, "should have succeeded")
+            }
+            Err(e) => panic!("wrong error: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn test_self_test() {
+        let (port, _requests) = start_scripted_server(vec![(200, "")]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let report = futures::executor::block_on(client.self_test("test-bucket"));
+        assert!(report.is_ok(), "self test should pass: {report:?}");
+        // The test server uses plain HTTP and the client doesn't sign requests
+        assert_eq!(
+            report.passed,
+            vec![
+                SelfTestStep::Endpoint,
+                SelfTestStep::Dns,
+                SelfTestStep::Connection,
+                SelfTestStep::Request
+            ]
+        );
+    }
+
+    #[test]
+    fn test_self_test_connection_refused() {
+        // Find a port that nothing is listening on
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let report = futures::executor::block_on(client.self_test("test-bucket"));
+        let failure = report.failure.expect("self test should fail");
+        assert_eq!(failure.step, SelfTestStep::Connection, "got {:?}", failure.error);
+        assert_eq!(report.passed, vec![SelfTestStep::Endpoint, SelfTestStep::Dns]);
+    }
+
+    #[test]
+    fn test_self_test_invalid_bucket() {
+        let client = S3CrtClient::new(S3ClientConfig::new()).expect("create test client");
+
+        let report = futures::executor::block_on(client.self_test("Invalid_Bucket"));
+        let failure = report.failure.expect("self test should fail");
+        assert_eq!(failure.step, SelfTestStep::Endpoint);
+        assert!(report.passed.is_empty());
+    }
+
+    #[test]
+    fn test_additional_retryable_statuses() {
+        // The CRT doesn't retry 502 responses itself
+        let (port, requests) = start_scripted_server(vec![(502, ""), (200, EMPTY_LIST_RESPONSE)]);
+        let config = S3ClientConfig::new().additional_retryable_statuses(HashSet::from([502]));
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(ObjectClient::list_objects(
+            &client,
+            "test-bucket",
+            None,
+            Some("/"),
+            1000,
+            "",
+        ))
+        .expect("list should succeed after a retry");
+        assert!(result.objects.is_empty());
+        assert_eq!(requests.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_additional_retryable_statuses_unset() {
+        let (port, requests) = start_scripted_server(vec![(502, ""), (200, EMPTY_LIST_RESPONSE)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let result = futures::executor::block_on(ObjectClient::list_objects(
+            &client,
+            "test-bucket",
+            None,
+            Some("/"),
+            1000,
+            "",
+        ));
+        let Err(ObjectClientError::ClientError(S3RequestError::ResponseError(result))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+        assert_eq!(result.response_status, 502);
+        assert_eq!(requests.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn test_additional_retryable_statuses_max_attempts() {
+        let (port, requests) = start_scripted_server(vec![(502, ""), (502, ""), (200, EMPTY_LIST_RESPONSE)]);
+        let config = S3ClientConfig::new()
+            .additional_retryable_statuses(HashSet::from([502]))
+            .max_attempts(NonZeroUsize::new(2).unwrap());
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(ObjectClient::list_objects(
+            &client,
+            "test-bucket",
+            None,
+            Some("/"),
+            1000,
+            "",
+        ));
+        let Err(ObjectClientError::ClientError(S3RequestError::ResponseError(result))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+        assert_eq!(result.response_status, 502);
+        assert_eq!(requests.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_additional_retryable_statuses_count_crt_retries() {
+        // The CRT already retries 503 responses up to `max_attempts` times, so the additional
+        // retries shouldn't attempt the request again on top of that
+        let (port, requests) = start_scripted_server(vec![
+            (503, ""),
+            (503, ""),
+            (503, ""),
+            (503, ""),
+            (200, EMPTY_LIST_RESPONSE),
+        ]);
+        let config = S3ClientConfig::new()
+            .additional_retryable_statuses(HashSet::from([503]))
+            .max_attempts(NonZeroUsize::new(3).unwrap());
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(ObjectClient::list_objects(
+            &client,
+            "test-bucket",
+            None,
+            Some("/"),
+            1000,
+            "",
+        ));
+        let Err(ObjectClientError::ClientError(S3RequestError::ResponseError(result))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+        assert_eq!(result.response_status, 503);
+        assert_eq!(requests.try_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_list_objects_page_retries() {
+        // The CRT doesn't retry 502 responses itself, but they're transient
+        let (port, requests) = start_scripted_server(vec![(502, ""), (502, ""), (200, EMPTY_LIST_RESPONSE)]);
+        let config = S3ClientConfig::new().list_objects_page_retries(2);
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(ObjectClient::list_objects(
+            &client,
+            "test-bucket",
+            None,
+            Some("/"),
+            1000,
+            "",
+        ))
+        .expect("list should succeed after retrying the page");
+        assert!(result.objects.is_empty());
+        assert_eq!(requests.try_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_list_objects_with_retries() {
+        // The client doesn't retry pages by default, but this call does
+        let (port, requests) = start_scripted_server(vec![(502, ""), (502, ""), (200, EMPTY_LIST_RESPONSE)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let result =
+            futures::executor::block_on(client.list_objects_with_retries("test-bucket", None, Some("/"), 1000, "", 2))
+                .expect("list should succeed after retrying the page");
+        assert!(result.objects.is_empty());
+        assert_eq!(requests.try_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_list_objects_page_delay() {
+        const DELAY: Range<Duration> = Duration::from_millis(200)..Duration::from_millis(300);
+        let (port, requests) = start_scripted_server(vec![(200, EMPTY_LIST_RESPONSE), (200, EMPTY_LIST_RESPONSE)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new().list_objects_page_delay(DELAY));
+
+        // The first page isn't delayed
+        futures::executor::block_on(client.list_objects("test-bucket", None, Some("/"), 1000, ""))
+            .expect("list should succeed");
+
+        let start = Instant::now();
+        futures::executor::block_on(client.list_objects("test-bucket", Some("token"), Some("/"), 1000, ""))
+            .expect("list should succeed");
+        assert!(start.elapsed() >= DELAY.start, "page wasn't delayed");
+        assert_eq!(requests.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_successful_request_metrics() {
+        let (port, _requests) = start_scripted_server(vec![(200, EMPTY_LIST_RESPONSE)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let result = futures::executor::block_on(client.list_objects("test-bucket", None, Some("/"), 1000, ""))
+            .expect("list should succeed");
+        let metrics = &result.response_metadata.metrics;
+        assert_eq!(metrics.num_requests, 1);
+        assert_eq!(metrics.retry_count, 0);
+        assert_eq!(metrics.http_status, Some(200));
+    }
+
+    #[test]
+    fn test_jittered_delay() {
+        let range = Duration::from_millis(100)..Duration::from_millis(200);
+        for _ in 0..100 {
+            assert!(range.contains(&jittered_delay(&range)));
+        }
+        let fixed = Duration::from_millis(50);
+        assert_eq!(jittered_delay(&(fixed..fixed)), fixed);
+        assert_eq!(jittered_delay(&(Duration::ZERO..Duration::ZERO)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_list_objects_page_retries_exhausted() {
+        let (port, requests) = start_scripted_server(vec![(502, ""), (502, ""), (200, EMPTY_LIST_RESPONSE)]);
+        let config = S3ClientConfig::new().list_objects_page_retries(1);
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(ObjectClient::list_objects(
+            &client,
+            "test-bucket",
+            None,
+            Some("/"),
+            1000,
+            "",
+        ));
+        let Err(ObjectClientError::ClientError(S3RequestError::ResponseError(result))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+        assert_eq!(result.response_status, 502);
+        assert_eq!(requests.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_credentials_observer() {
+        #[derive(Default)]
+        struct Observer(Mutex<Vec<Option<SystemTime>>>);
+
+        impl ObserveCredentials for Observer {
+            fn on_refresh(&self, expiration: Option<SystemTime>) {
+                self.0.lock().unwrap().push(expiration);
+            }
+        }
+
+        let (port, _requests) = start_scripted_server(vec![(200, EMPTY_LIST_RESPONSE), (200, EMPTY_LIST_RESPONSE)]);
+        let endpoint = Uri::new_from_str(&Allocator::default(), format!("http://127.0.0.1:{port}")).unwrap();
+        let endpoint_config = EndpointConfig::new("us-east-1")
+            .endpoint(endpoint)
+            .addressing_style(AddressingStyle::Path);
+        let provider = CredentialsProvider::new_static(
+            &Allocator::default(),
+            CredentialsProviderStaticOptions {
+                access_key_id: "AKIDEXAMPLE",
+                secret_access_key: "secret",
+                session_token: None,
+            },
+        )
+        .unwrap();
+        let observer = Arc::new(Observer::default());
+        let config = S3ClientConfig::new()
+            .endpoint_config(endpoint_config)
+            .credentials_provider(provider)
+            .credentials_observer(observer.clone());
+        let client = S3CrtClient::new(config).expect("create test client");
+
+        for _ in 0..2 {
+            futures::executor::block_on(client.list_objects("test-bucket", None, Some("/"), 1000, ""))
+                .expect("list should succeed");
+        }
+        // Static credentials never expire, and reusing them isn't a refresh
+        assert_eq!(*observer.0.lock().unwrap(), vec![None]);
+    }
+
+    #[test]
+    fn test_upload_part_retries() {
+        let (port, requests) = start_scripted_server_with_response_headers(vec![
+            (502, &[], ""),
+            (502, &[], ""),
+            (200, &[("ETag", "\"abc\"")], ""),
+        ]);
+        let config = S3ClientConfig::new().upload_part_retries(2);
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(client.upload_part("test-bucket", "key", "upload", 1, vec![0; 16]))
+            .expect("part should be uploaded after retries");
+        assert_eq!(result.retries, 2);
+        assert_eq!(result.etag.as_str(), "\"abc\"");
+        assert_eq!(requests.try_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_upload_part_retries_exhausted() {
+        let (port, requests) = start_scripted_server(vec![(502, ""), (502, ""), (200, "")]);
+        let config = S3ClientConfig::new().upload_part_retries(1);
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(client.upload_part("test-bucket", "key", "upload", 1, vec![0; 16]));
+        let Err(ObjectClientError::ClientError(S3RequestError::ResponseError(result))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+        assert_eq!(result.response_status, 502);
+        assert_eq!(requests.try_iter().count(), 2);
+    }
+
+    #[test_case(S3RequestError::Throttled, true; "throttled")]
+    #[test_case(S3RequestError::Timeout(TimeoutKind::Overall), true; "timeout")]
+    #[test_case(S3RequestError::IncompleteBody { expected: 10, received: 4 }, true; "incomplete body")]
+    #[test_case(S3RequestError::ResponseError(make_result(502, "", None)), true; "server error")]
+    #[test_case(S3RequestError::ResponseError(make_result(400, "", None)), false; "client error")]
+    #[test_case(S3RequestError::RequestCanceled, false; "canceled")]
+    #[test_case(S3RequestError::NoSigningCredentials, false; "no credentials")]
+    fn test_is_transient(error: S3RequestError, expected: bool) {
+        assert_eq!(error.is_transient(), expected);
+    }
+
+    #[test]
+    fn test_builder() {
+        let client = S3CrtClient::builder()
+            .region("eu-west-1")
+            .part_size(16 * 1024 * 1024)
+            .throughput_target_gbps(1.0)
+            .connect_timeout(Duration::from_secs(5))
+            .time_to_first_byte_timeout(Duration::from_secs(10))
+            .overall_request_timeout(Duration::from_secs(60))
+            .build()
+            .expect("client should build");
+        assert_eq!(client.endpoint_config().get_region(), "eu-west-1");
+        assert_eq!(client.inner.part_size, 16 * 1024 * 1024);
+    }
+
+    #[test_case(S3CrtClient::builder().throughput_target_gbps(0.0); "zero throughput target")]
+    #[test_case(S3CrtClient::builder().throughput_target_gbps(f64::NAN); "nan throughput target")]
+    #[test_case(
+        S3CrtClient::builder()
+            .time_to_first_byte_timeout(Duration::from_secs(60))
+            .overall_request_timeout(Duration::from_secs(10));
+        "time to first byte timeout longer than overall timeout"
+    )]
+    fn test_builder_invalid_combination(config: S3ClientConfig) {
+        let err = config.build().expect_err("invalid configuration should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+
+    #[test_case(404; "client error")]
+    #[test_case(200; "success")]
+    fn test_additional_retryable_statuses_must_be_server_errors(status: u16) {
+        let config = S3ClientConfig::new().additional_retryable_statuses(HashSet::from([502, status]));
+        let err = S3CrtClient::new(config).expect_err("non-5xx statuses should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+
+    #[test_case(1 => Duration::from_millis(100))]
+    #[test_case(2 => Duration::from_millis(200))]
+    #[test_case(4 => Duration::from_millis(800))]
+    #[test_case(6 => Duration::from_secs(2))]
+    #[test_case(100 => Duration::from_secs(2))]
+    fn test_retry_backoff(attempt: u32) -> Duration {
+        retry_backoff(attempt, Duration::from_millis(100), Duration::from_secs(2))
+    }
+
+    #[test]
+    fn test_backoff_config() {
+        let config = S3ClientConfig::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(5))
+            .backoff_jitter_mode(ExponentialBackoffJitterMode::Decorrelated);
+        let client = S3CrtClient::new(config).expect("client should be created");
+        assert_eq!(client.inner.initial_backoff, Duration::from_millis(100));
+        assert_eq!(client.inner.max_backoff, Duration::from_secs(5));
+    }
+
+    #[test_case(2, 1; "one retry")]
+    #[test_case(64, 63; "most retries")]
+    fn test_standard_retry_options(max_attempts: usize, expected_retries: usize) {
+        let allocator = Allocator::default();
+        let mut event_loop_group = EventLoopGroup::new_default(&allocator, None, || {}).unwrap();
+        let config = S3ClientConfig::new().retry_budget(NonZeroUsize::new(100).unwrap());
+
+        let options = standard_retry_options(&config, max_attempts, &mut event_loop_group);
+        assert_eq!(options.backoff_retry_options.max_retries, expected_retries);
+        assert_eq!(
+            options.backoff_retry_options.backoff_scale_factor,
+            Duration::from_millis(500)
+        );
+        // The client's retry budget is separate from the CRT's retry token bucket
+        assert_eq!(options.initial_bucket_capacity, 0);
+    }
+
+    #[test_case(-1.0; "negative")]
+    #[test_case(f64::NAN; "not a number")]
+    #[test_case(f64::INFINITY; "infinite")]
+    fn test_invalid_retry_budget_refill(refill: f64) {
+        let config = S3ClientConfig::new().retry_budget_refill(refill);
+        let err = S3CrtClient::new(config).expect_err("invalid refill should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+
+    #[test]
+    fn test_retry_budget_tokens() {
+        let budget = RetryBudget::new(NonZeroUsize::new(2).unwrap(), 0.5);
+        assert!(budget.try_acquire());
+        assert!(budget.try_acquire());
+        assert!(!budget.try_acquire());
+        budget.replenish();
+        assert!(!budget.try_acquire(), "half a token isn't enough for a retry");
+        budget.replenish();
+        assert!(budget.try_acquire());
+        // Successes don't fill the budget past its size
+        for _ in 0..10 {
+            budget.replenish();
+        }
+        assert!(budget.try_acquire());
+        assert!(budget.try_acquire());
+        assert!(!budget.try_acquire());
+    }
+
+    #[test_case(1; "no retries")]
+    #[test_case(MAX_ATTEMPTS_LIMIT; "most attempts")]
+    fn test_max_attempts_config(max_attempts: usize) {
+        let config = S3ClientConfig::new().max_attempts(NonZeroUsize::new(max_attempts).unwrap());
+        let client = S3CrtClient::new(config).expect("client should be created");
+        assert_eq!(client.inner.max_attempts, max_attempts);
+    }
+
+    #[test]
+    fn test_max_attempts_over_limit() {
+        let config = S3ClientConfig::new().max_attempts(NonZeroUsize::new(MAX_ATTEMPTS_LIMIT + 1).unwrap());
+        let err = S3CrtClient::new(config).expect_err("too many attempts should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+
+    #[test_case(S3ClientConfig::new().initial_backoff(Duration::ZERO); "zero initial backoff")]
+    #[test_case(S3ClientConfig::new().max_backoff(Duration::from_millis(500)); "max backoff under a second")]
+    fn test_invalid_backoff_config(config: S3ClientConfig) {
+        let err = S3CrtClient::new(config).expect_err("invalid backoff should be rejected");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+
+    #[test]
+    fn test_time_to_first_byte_timeout() {
+        // Accept the request but never respond
+        let port = start_stalling_server("");
+        let config = S3ClientConfig::new()
+            .time_to_first_byte_timeout(Duration::from_millis(200))
+            .overall_request_timeout(Duration::from_secs(30));
+        let client = new_stalling_server_client(port, config);
+
+        let result =
+            futures::executor::block_on(client.head_object("test-bucket", "test-key", &HeadObjectParams::new()));
+        let Err(ObjectClientError::ClientError(S3RequestError::Timeout(TimeoutKind::TimeToFirstByte))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+    }
+
+    #[test]
+    fn test_overall_request_timeout() {
+        // Respond promptly with headers, but stall partway through the body
+        let port = start_stalling_server("HTTP/1.1 200 OK\r\nContent-Length: 1024\r\n\r\n<ListBucketResult>");
+        let config = S3ClientConfig::new()
+            .time_to_first_byte_timeout(Duration::from_millis(200))
+            .overall_request_timeout(Duration::from_secs(1));
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(client.list_objects("test-bucket", None, Some("/"), 1000, ""));
+        let Err(ObjectClientError::ClientError(S3RequestError::Timeout(TimeoutKind::Overall))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+    }
+
+    #[test]
+    fn test_meta_request_metrics() {
+        // Every attempt fails with a retryable error, and the server closes the connection after each
+        let port = start_stalling_server(
+            "HTTP/1.1 500 Internal Server Error\r\nx-amz-request-id: TESTREQUESTID\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        );
+        let config = S3ClientConfig::new().max_attempts(NonZeroUsize::new(2).unwrap());
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(client.list_objects("test-bucket", None, Some("/"), 1000, ""));
+        let Err(ObjectClientError::ClientError(S3RequestError::ResponseError(result))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+        let metrics = result.metrics;
+        assert_eq!(metrics.num_requests, 2);
+        assert_eq!(metrics.retry_count, 1);
+        assert_eq!(metrics.http_status, Some(500));
+        assert_eq!(metrics.request_id.as_deref(), Some("TESTREQUESTID"));
+        assert!(metrics.total_duration > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_crt_future_cancels_meta_request_on_drop() {
+        // Accept the request but never respond, so the meta request only finishes if it's canceled
+        let port = start_stalling_server("");
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let mut message = client.new_request_template("GET", "test-bucket").unwrap();
+        message.set_request_path("/test-key").unwrap();
+        let mut options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::Default, &Span::none());
+
+        let (promise, mut future) = CrtFuture::<(), Error>::new();
+        let (finished_tx, finished_rx) = std::sync::mpsc::channel();
+        options.on_finish(move |result| {
+            let _ = finished_tx.send(result.is_canceled());
+            promise.complete_with_error_code(result.crt_error.raw_error(), || ());
+        });
+        let meta_request = client.inner.s3_client.make_meta_request(options).unwrap();
+        future.set_cancel_handler(move || meta_request.cancel());
+
+        drop(future);
+        let canceled = finished_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("meta request should finish once the future is dropped");
+        assert!(canceled, "meta request should have been canceled");
+    }
+
+    #[test_case("us-east-1", true; "same region")]
+    #[test_case("eu-west-1", false; "different region")]
+    fn test_verify_region_on_init(bucket_region: &str, expect_verified: bool) {
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nx-amz-bucket-region: {bucket_region}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        );
+        let port = start_stalling_server(response.leak());
+        let config = S3ClientConfig::new().verify_region_on_init(true);
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(client.check_bucket("test-bucket"));
+        let verified_buckets = client.inner.region_verified_buckets.as_ref().unwrap();
+        assert_eq!(
+            verified_buckets.lock().unwrap().contains("test-bucket"),
+            expect_verified
+        );
+        if expect_verified {
+            result.expect("region should match");
+            return;
+        }
+
+        // The first request to the bucket fails with a clear description of the problem
+        let result = futures::executor::block_on(ObjectClient::list_objects(
+            &client,
+            "test-bucket",
+            None,
+            Some("/"),
+            1000,
+            "",
+        ));
+        let Err(ObjectClientError::ClientError(err @ S3RequestError::RegionMismatch { .. })) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+        assert_eq!(
+            err.to_string(),
+            "Bucket test-bucket is in region eu-west-1, but the client is configured for region us-east-1"
+        );
+    }
+
+    #[test]
+    fn test_list_objects_start_after_fallback() {
+        let first_page = r#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>true</IsTruncated><Contents><Key>a</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;etag&quot;</ETag><Size>1</Size></Contents></ListBucketResult>"#;
+        let second_page = r#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated><Contents><Key>b</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;etag&quot;</ETag><Size>1</Size></Contents></ListBucketResult>"#;
+        let (port, requests) = start_scripted_server(vec![(200, first_page), (200, second_page)]);
+        let config = S3ClientConfig::new().list_objects_start_after_fallback(true);
+        let client = new_stalling_server_client(port, config);
+
+        let result = futures::executor::block_on(client.list_objects("test-bucket", None, None, 1, ""))
+            .expect("first page should succeed");
+        assert_eq!(result.objects[0].key, "a");
+        let token = result.next_continuation_token.expect("listing should continue");
+        let first_request = requests.recv().unwrap();
+        assert!(!first_request.contains("start-after"), "got {first_request}");
+
+        let result = futures::executor::block_on(client.list_objects("test-bucket", Some(&token), None, 1, ""))
+            .expect("second page should succeed");
+        assert_eq!(result.objects[0].key, "b");
+        assert!(result.next_continuation_token.is_none());
+        let second_request = requests.recv().unwrap();
+        assert!(second_request.contains("start-after=a"), "got {second_request}");
+        assert!(!second_request.contains("continuation-token"), "got {second_request}");
+    }
+
+    const OPTIONAL_OBJECT_ATTRIBUTES_REJECTED: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InvalidArgument</Code><Message>Invalid attribute name specified.</Message><ArgumentName>x-amz-optional-object-attributes</ArgumentName></Error>"#;
+
+    #[test]
+    fn test_list_objects_optional_object_attributes_unsupported() {
+        let (port, requests) = start_scripted_server_with_headers(vec![
+            (400, OPTIONAL_OBJECT_ATTRIBUTES_REJECTED),
+            (200, EMPTY_LIST_RESPONSE),
+            (200, EMPTY_LIST_RESPONSE),
+        ]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        futures::executor::block_on(client.list_objects("test-bucket", None, Some("/"), 1000, ""))
+            .expect("list should succeed without the optional attributes");
+        let request = requests.recv().unwrap().to_ascii_lowercase();
+        assert!(
+            request.contains("x-amz-optional-object-attributes: restorestatus"),
+            "got {request}"
+        );
+        let request = requests.recv().unwrap().to_ascii_lowercase();
+        assert!(!request.contains("x-amz-optional-object-attributes"), "got {request}");
+
+        // Later listings don't ask for the attributes at all
+        futures::executor::block_on(client.list_objects("test-bucket", None, Some("/"), 1000, ""))
+            .expect("list should succeed");
+        let request = requests.recv().unwrap().to_ascii_lowercase();
+        assert!(!request.contains("x-amz-optional-object-attributes"), "got {request}");
+    }
+
+    #[test_case(&[]; "without optional attributes")]
+    #[test_case(&[OptionalObjectAttribute::RestoreStatus]; "unrelated to optional attributes")]
+    fn test_list_objects_bad_request_not_retried(attributes: &[OptionalObjectAttribute]) {
+        let (port, requests) = start_scripted_server(vec![(400, ""), (200, EMPTY_LIST_RESPONSE)]);
+        let client =
+            new_stalling_server_client(port, S3ClientConfig::new()).with_optional_object_attributes(attributes);
+
+        let result = futures::executor::block_on(client.list_objects("test-bucket", None, Some("/"), 1000, ""));
+        let Err(ObjectClientError::ClientError(S3RequestError::ResponseError(result))) = result else {
+            panic!("expected a response error, got {result:?}");
+        };
+        assert_eq!(result.response_status, 400);
+        requests.recv().unwrap();
+        assert!(requests.try_recv().is_err(), "request shouldn't be retried");
+    }
+
+    #[test]
+    fn test_bucket_owner_access_denied() {
+        const ACCESS_DENIED: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>AccessDenied</Code><Message>Access Denied</Message><RequestId>CM0R497NB0WAQ977</RequestId></Error>"#;
+        let (port, requests) = start_scripted_server_with_headers(vec![(403, ACCESS_DENIED), (403, ACCESS_DENIED)]);
+
+        let client = new_stalling_server_client(port, S3ClientConfig::new().bucket_owner("111122223333"));
+        let err = futures::executor::block_on(client.list_objects("test-bucket", None, None, 1000, ""))
+            .expect_err("denied request should fail");
+        let ObjectClientError::ClientError(S3RequestError::BucketOwnerAccessDenied {
+            expected_bucket_owner,
+            message,
+            metadata,
+        }) = err
+        else {
+            panic!("wrong error: {err:?}");
+        };
+        assert_eq!(expected_bucket_owner, "111122223333");
+        assert_eq!(message, "Access Denied");
+        assert_eq!(metadata.http_code, Some(403));
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(
+            request.contains("x-amz-expected-bucket-owner: 111122223333"),
+            "got {request}"
+        );
+
+        // Without an expected bucket owner, the same error is just forbidden
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+        let err = futures::executor::block_on(client.list_objects("test-bucket", None, None, 1000, ""))
+            .expect_err("denied request should fail");
+        assert!(
+            matches!(err, ObjectClientError::ClientError(S3RequestError::Forbidden(_, _))),
+            "got {err:?}"
+        );
+    }
+
+    #[test]
+    fn test_bucket_owner_access_denied_verifying_region() {
+        const ACCESS_DENIED: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>AccessDenied</Code><Message>Access Denied</Message><RequestId>CM0R497NB0WAQ977</RequestId></Error>"#;
+        let (port, requests) = start_scripted_server(vec![(403, ACCESS_DENIED), (403, ACCESS_DENIED)]);
+
+        let config = S3ClientConfig::new()
+            .bucket_owner("111122223333")
+            .verify_region_on_init(true);
+        let client = new_stalling_server_client(port, config);
+        // The region check can't tell the bucket's region, so the request itself is sent and
+        // reports the denial
+        let err = futures::executor::block_on(client.list_objects("test-bucket", None, None, 1000, ""))
+            .expect_err("denied request should fail");
+        assert!(
+            matches!(
+                &err,
+                ObjectClientError::ClientError(S3RequestError::BucketOwnerAccessDenied { expected_bucket_owner, .. })
+                    if expected_bucket_owner == "111122223333"
+            ),
+            "got {err:?}"
+        );
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("HEAD /test-bucket/ "), "got {request}");
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /test-bucket/?"), "got {request}");
+    }
+
+    #[test]
+    fn test_list_objects_delimiter_query() {
+        let page = r#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated></ListBucketResult>"#;
+        let (port, requests) = start_scripted_server(vec![(200, page), (200, page)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        futures::executor::block_on(client.list_objects("test-bucket", None, None, 1000, ""))
+            .expect("list without a delimiter should succeed");
+        let request = requests.recv().unwrap();
+        assert!(!request.contains("delimiter"), "got {request}");
+
+        futures::executor::block_on(client.list_objects("test-bucket", None, Some("/"), 1000, ""))
+            .expect("list with a delimiter should succeed");
+        let request = requests.recv().unwrap();
+        assert!(request.contains("delimiter="), "got {request}");
+    }
+
+    #[test_case(HostResolutionMode::Auto, true; "auto")]
+    #[test_case(HostResolutionMode::Ipv4Only, true; "ipv4 only")]
+    #[test_case(HostResolutionMode::Ipv6Only, false; "ipv6 only")]
+    fn test_host_resolution_mode(mode: HostResolutionMode, should_connect: bool) {
+        // The scripted server only listens on an IPv4 address
+        let (port, _requests) = start_scripted_server(vec![(200, EMPTY_LIST_RESPONSE)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new().host_resolution_mode(mode));
+
+        let result = futures::executor::block_on(client.list_objects("test-bucket", None, None, 1000, ""));
+        assert_eq!(result.is_ok(), should_connect, "got {result:?}");
+    }
+
+    #[test]
+    fn test_embedded_error_in_success_response() {
+        const NO_SUCH_KEY: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NoSuchKey</Code><Message>The specified key does not exist.</Message></Error>"#;
+        const INTERNAL_ERROR: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InternalError</Code><Message>We encountered an internal error. Please try again.</Message></Error>"#;
+        const SLOW_DOWN: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>SlowDown</Code><Message>Please reduce your request rate.</Message></Error>"#;
+        let (port, _requests) =
+            start_scripted_server(vec![(200, NO_SUCH_KEY), (200, INTERNAL_ERROR), (200, SLOW_DOWN)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+        let copy = || {
+            futures::executor::block_on(client.copy_object(
+                "test-bucket",
+                "src",
+                "test-bucket",
+                "dst",
+                &CopyObjectParams::new(),
+            ))
+            .expect_err("copy with an embedded error should fail")
+        };
+
+        let err = copy();
+        assert!(
+            matches!(err, ObjectClientError::ServiceError(CopyObjectError::NoSuchKey)),
+            "got {err:?}"
+        );
+
+        let err = copy();
+        let ObjectClientError::ClientError(S3RequestError::ResponseError(result)) = err else {
+            panic!("wrong error: {err:?}");
+        };
+        assert_eq!(result.response_status, 200);
+        assert_eq!(result.error_response_body.as_deref(), Some(OsStr::new(INTERNAL_ERROR)));
+
+        let err = copy();
+        assert!(
+            matches!(err, ObjectClientError::ClientError(S3RequestError::Throttled)),
+            "got {err:?}"
+        );
+    }
+
+    #[test]
+    fn test_embedded_error_not_checked_for_listings() {
+        // A listing can't contain an embedded error, so a body like one is just an invalid listing
+        const NO_SUCH_KEY: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NoSuchKey</Code></Error>"#;
+        let (port, _requests) = start_scripted_server(vec![(200, NO_SUCH_KEY)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let err = futures::executor::block_on(client.list_objects("test-bucket", None, None, 1000, ""))
+            .expect_err("invalid listing should fail");
+        assert!(
+            matches!(err, ObjectClientError::ClientError(S3RequestError::InternalError(_))),
+            "got {err:?}"
+        );
+    }
+
+    #[test]
+    fn test_delete_object_version_id_query() {
+        let (port, requests) = start_scripted_server(vec![(204, ""), (204, "")]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        futures::executor::block_on(client.delete_object("test-bucket", "key", &DeleteObjectParams::new()))
+            .expect("delete without a version should succeed");
+        let request = requests.recv().unwrap();
+        assert!(!request.contains("versionId"), "got {request}");
+
+        let params = DeleteObjectParams::new().version_id(Some("3HL4kqtJlcpXroDTDmJ.rmSpXd3dIbrHY".to_owned()));
+        futures::executor::block_on(client.delete_object("test-bucket", "key", &params))
+            .expect("delete with a version should succeed");
+        let request = requests.recv().unwrap();
+        assert!(
+            request.contains("/test-bucket/key?versionId=3HL4kqtJlcpXroDTDmJ.rmSpXd3dIbrHY "),
+            "got {request}"
+        );
+    }
+
+    const NO_SUCH_BUCKET: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NoSuchBucket</Code></Error>"#;
+
+    #[test]
+    fn test_delete_objects_later_request_fails() {
+        let (port, _requests) = start_scripted_server(vec![
+            (200, "<DeleteResult><Deleted><Key>a</Key></Deleted></DeleteResult>"),
+            (404, NO_SUCH_BUCKET),
+            (200, "<DeleteResult><Deleted><Key>c</Key></Deleted></DeleteResult>"),
+        ]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let keys = ["a".to_owned(), "b".to_owned(), "c".to_owned()];
+        let result = futures::executor::block_on(client.delete_objects_in_chunks("test-bucket", &keys, 1))
+            .expect("delete should succeed once the first request has");
+        assert_eq!(result.deleted, vec!["a", "c"]);
+        assert_eq!(
+            result.errors,
+            vec![(
+                "b".to_owned(),
+                DeleteObjectError::RequestFailed("The bucket does not exist".to_owned())
+            )]
+        );
+    }
+
+    #[test]
+    fn test_delete_objects_first_request_fails() {
+        let (port, requests) = start_scripted_server(vec![(404, NO_SUCH_BUCKET)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let keys = ["a".to_owned(), "b".to_owned()];
+        let result = futures::executor::block_on(client.delete_objects_in_chunks("test-bucket", &keys, 1));
+        assert!(
+            matches!(
+                result,
+                Err(ObjectClientError::ServiceError(DeleteObjectError::NoSuchBucket))
+            ),
+            "got {result:?}"
+        );
+        // The second request isn't made
+        requests.recv().unwrap();
+        assert!(requests.try_recv().is_err());
+    }
+
+    const INITIATE_MULTIPART_UPLOAD_RESPONSE: &str = r#"<?xml version="1.0" encoding="UTF-8"?><InitiateMultipartUploadResult><Bucket>test-bucket</Bucket><Key>key</Key><UploadId>upload-id</UploadId></InitiateMultipartUploadResult>"#;
+
+    #[test_case(PutObjectTrailingChecksums::Enabled, None, Some((ChecksumAlgorithm::Crc32c, "4waSgw==")); "default algorithm")]
+    #[test_case(PutObjectTrailingChecksums::ReviewOnly, Some(ChecksumAlgorithm::Crc32), Some((ChecksumAlgorithm::Crc32, "y/Q5Jg==")); "review only")]
+    #[test_case(PutObjectTrailingChecksums::Disabled, None, None; "disabled")]
+    #[test_case(PutObjectTrailingChecksums::Enabled, Some(ChecksumAlgorithm::Sha256), None; "unsupported algorithm")]
+    fn test_put_object_checksum(
+        trailing_checksums: PutObjectTrailingChecksums,
+        checksum_algorithm: Option<ChecksumAlgorithm>,
+        expected: Option<(ChecksumAlgorithm, &str)>,
+    ) {
+        // Only the CreateMultipartUpload request gets a response. The body is smaller than a part,
+        // so the writes are buffered without sending any parts.
+        let (port, _requests) = start_scripted_server(vec![(200, INITIATE_MULTIPART_UPLOAD_RESPONSE)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+        let params = PutObjectParams::new()
+            .trailing_checksums(trailing_checksums)
+            .checksum_algorithm(checksum_algorithm);
+
+        futures::executor::block_on(async {
+            let mut request = client
+                .put_object("test-bucket", "key", &params)
+                .await
+                .expect("put_object should succeed");
+            for chunk in [&b"1234"[..], &b"56789"[..]] {
+                request.write(chunk).await.expect("write should succeed");
+            }
+            assert_eq!(request.bytes_written(), 9);
+            let checksum = request.checksum();
+            assert_eq!(
+                checksum.as_ref().map(|(algorithm, value)| (*algorithm, value.as_str())),
+                expected
+            );
+        });
+    }
+
+    #[test]
+    fn test_send_request() {
+        const NO_SUCH_KEY: &str = r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NoSuchKey</Code></Error>"#;
+        let (port, requests) = start_scripted_server(vec![(200, "<Tagging/>"), (404, NO_SUCH_KEY)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let response = futures::executor::block_on(client.send_request(
+            "test-bucket",
+            "GET",
+            "/key",
+            &[("tagging", ""), ("versionId", "v1")],
+            &[],
+            None,
+        ))
+        .expect("request should succeed");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, b"<Tagging/>");
+        assert!(response
+            .headers
+            .iter()
+            .any(|(name, value)| name.eq_ignore_ascii_case("Content-Length") && value == "10"));
+        let request = requests.recv().unwrap();
+        assert!(
+            request.starts_with("GET /test-bucket/key?tagging=&versionId=v1 "),
+            "got {request}"
+        );
+
+        // Error responses are returned to the caller as they are
+        let response = futures::executor::block_on(client.send_request(
+            "test-bucket",
+            "PUT",
+            "/key",
+            &[],
+            &[("x-amz-meta-foo", "bar")],
+            Some(b"hello".to_vec()),
+        ))
+        .expect("request with an error response should still succeed");
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, NO_SUCH_KEY.as_bytes());
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("PUT /test-bucket/key "), "got {request}");
+    }
+
+    #[test]
+    fn test_small_range_single_request() {
+        let (port, requests) = start_scripted_server(vec![(206, "0123456789")]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new().parallel_get_threshold(1024));
+
+        let mut request = client
+            .get_object("test-bucket", "key", &GetObjectParams::new().range(Some(100..110)))
+            .expect("request should be created");
+        let mut parts = Vec::new();
+        while let Some(part) = futures::executor::block_on(futures::StreamExt::next(&mut request)) {
+            let (offset, body) = part.expect("body part should succeed");
+            parts.push((offset, body.to_vec()));
+        }
+
+        // Offsets are still relative to the object, not the response
+        assert_eq!(parts, vec![(100, b"0123456789".to_vec())]);
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /test-bucket/key "), "got {request}");
+    }
+
+    #[test]
+    fn test_egress_byte_budget() {
+        let (port, requests) = start_scripted_server(vec![(206, "0123456789"), (206, "0123456789")]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new().egress_byte_budget(15));
+        let params = GetObjectParams::new().part_number(Some(1));
+        let read_body = |client: &S3CrtClient| {
+            let mut request = client.get_object("test-bucket", "key", &params)?;
+            let mut body = Vec::new();
+            while let Some(part) = futures::executor::block_on(futures::StreamExt::next(&mut request)) {
+                body.extend_from_slice(&part?.1);
+            }
+            Ok::<_, ObjectClientError<GetObjectError, S3RequestError>>(body)
+        };
+
+        let body = read_body(&client).expect("first request is within the budget");
+        assert_eq!(body, b"0123456789");
+        assert_eq!(client.egress_bytes(), 10);
+
+        // The second response passes the budget part way through
+        let err = read_body(&client).expect_err("second request passes the budget");
+        assert!(
+            matches!(
+                err,
+                ObjectClientError::ClientError(S3RequestError::EgressBudgetExceeded { budget: 15 })
+            ),
+            "got {err:?}"
+        );
+        assert_eq!(client.egress_bytes(), 20);
+
+        // Later requests fail without being sent
+        let err = read_body(&client).expect_err("budget is already exceeded");
+        assert!(
+            matches!(
+                err,
+                ObjectClientError::ClientError(S3RequestError::EgressBudgetExceeded { budget: 15 })
+            ),
+            "got {err:?}"
+        );
+        assert_eq!(requests.try_iter().count(), 2);
+    }
+
+    #[test]
+    fn test_get_object_part_number() {
+        let (port, requests) = start_scripted_server(vec![(206, "0123456789")]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let params = GetObjectParams::new().part_number(Some(3));
+        let mut request = client
+            .get_object("test-bucket", "key", &params)
+            .expect("request should be created");
+        let part = futures::executor::block_on(futures::StreamExt::next(&mut request))
+            .expect("should return a body part")
+            .expect("body part should succeed");
+        assert_eq!(part, (0, b"0123456789".as_slice().into()));
+        let request = requests.recv().unwrap();
+        assert!(
+            request.starts_with("GET /test-bucket/key?partNumber=3 "),
+            "got {request}"
+        );
+
+        let params = params.range(Some(0..10));
+        let err = client
+            .get_object("test-bucket", "key", &params)
+            .expect_err("a range with a part number should be rejected");
+        assert!(
+            matches!(
+                err,
+                ObjectClientError::ClientError(S3RequestError::ConstructionFailure(
+                    ConstructionError::InvalidParameters(_)
+                ))
+            ),
+            "got {err:?}"
+        );
+    }
+
+    /// Read the whole body of a GetObject request for part 1 of the object `key` in `bucket`
+    fn read_part_one(
+        client: &S3CrtClient,
+        bucket: &str,
+    ) -> ObjectClientResult<Vec<u8>, GetObjectError, S3RequestError> {
+        let params = GetObjectParams::new().part_number(Some(1));
+        let mut request = client.get_object(bucket, "key", &params)?;
+        let mut body = Vec::new();
+        while let Some(part) = futures::executor::block_on(futures::StreamExt::next(&mut request)) {
+            body.extend_from_slice(&part?.1);
+        }
+        Ok(body)
+    }
+
+    #[test]
+    fn test_get_object_follows_redirect() {
+        let (port, requests) = start_scripted_server_with_response_headers(vec![
+            (307, &[("Location", "/other-bucket/key?partNumber=1")], ""),
+            (206, &[], "0123456789"),
+        ]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new().max_get_redirects(1));
+
+        let body = read_part_one(&client, "test-bucket").expect("redirect should be followed");
+        assert_eq!(body, b"0123456789");
+        let request = requests.recv().unwrap();
+        assert!(
+            request.starts_with("GET /test-bucket/key?partNumber=1 "),
+            "got {request}"
+        );
+        let request = requests.recv().unwrap();
+        assert!(
+            request.starts_with("GET /other-bucket/key?partNumber=1 "),
+            "got {request}"
+        );
+    }
+
+    #[test]
+    fn test_get_object_rejects_redirect_to_other_host() {
+        // The server would also answer on localhost, but it's another host as far as the client
+        // can tell
+        let (port, requests) = start_scripted_server_with_response_headers(vec![
+            (
+                307,
+                &[("Location", "http://localhost/test-bucket/key?partNumber=1")],
+                "",
+            ),
+            (206, &[], "0123456789"),
+        ]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new().max_get_redirects(1));
+
+        let err = read_part_one(&client, "test-bucket").expect_err("redirect should not be followed");
+        assert!(
+            matches!(
+                &err,
+                ObjectClientError::ClientError(S3RequestError::ResponseError(result)) if result.response_status == 307
+            ),
+            "got {err:?}"
+        );
+        assert_eq!(requests.try_iter().count(), 1);
+    }
+
+    #[test]
+    fn test_get_object_too_many_redirects() {
+        const REDIRECT: (u16, ScriptedHeaders, &str) = (307, &[("Location", "/test-bucket/key?partNumber=1")], "");
+        let (port, requests) =
+            start_scripted_server_with_response_headers(vec![REDIRECT, REDIRECT, REDIRECT, (206, &[], "0123456789")]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new().max_get_redirects(2));
+
+        let err = read_part_one(&client, "test-bucket").expect_err("redirect chain is too long");
+        assert!(
+            matches!(
+                err,
+                ObjectClientError::ClientError(S3RequestError::TooManyRedirects { max_redirects: 2 })
+            ),
+            "got {err:?}"
+        );
+        // The first request and the two redirects it followed
+        assert_eq!(requests.try_iter().count(), 3);
+    }
+
+    #[test]
+    fn test_get_object_decode_content_encoding_needs_whole_object() {
+        let client = new_stalling_server_client(0, S3ClientConfig::new());
+        for params in [
+            GetObjectParams::new().range(Some(0..10)),
+            GetObjectParams::new().part_number(Some(1)),
+        ] {
+            let params = params.decode_content_encoding(true);
+            let err = client
+                .get_object("test-bucket", "key", &params)
+                .expect_err("decoding part of an object should be rejected");
+            assert!(
+                matches!(
+                    err,
+                    ObjectClientError::ClientError(S3RequestError::ConstructionFailure(
+                        ConstructionError::InvalidParameters(_)
+                    ))
+                ),
+                "got {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_list_objects_with_handler() {
+        let page = r#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>true</IsTruncated><NextContinuationToken>token</NextContinuationToken><Contents><Key>a</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;etag&quot;</ETag><Size>1</Size></Contents><Contents><Key>b</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;etag&quot;</ETag><Size>2</Size></Contents><CommonPrefixes><Prefix>c/</Prefix></CommonPrefixes></ListBucketResult>"#;
+        let (port, _requests) = start_scripted_server(vec![(200, page)]);
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let mut objects = Vec::new();
+        let result = futures::executor::block_on(client.list_objects_with_handler(
+            "test-bucket",
+            None,
+            Some("/"),
+            1000,
+            "",
+            &mut |object| objects.push((object.key.clone(), object.size)),
+        ))
+        .expect("list should succeed");
+        assert_eq!(objects, vec![("a".to_owned(), 1), ("b".to_owned(), 2)]);
+        assert!(result.objects.is_empty());
+        assert_eq!(result.common_prefixes, vec!["c/".to_owned()]);
+        assert_eq!(result.next_continuation_token.as_deref(), Some("token"));
+    }
+
+    #[test]
+    fn test_request_timeout_override() {
+        let port = start_stalling_server("");
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let message = client
+            .new_request_template("GET", "test-bucket")
+            .expect("new request template expected");
+        assert_eq!(message.request_timeouts.overall, None);
+
+        let client = client.with_request_timeout(Some(Duration::from_millis(200)));
+        let message = client
+            .new_request_template("GET", "test-bucket")
+            .expect("new request template expected");
+        assert_eq!(message.request_timeouts.overall, Some(Duration::from_millis(200)));
+
+        let result =
+            futures::executor::block_on(client.head_object("test-bucket", "test-key", &HeadObjectParams::new()));
+        let Err(ObjectClientError::ClientError(S3RequestError::Timeout(TimeoutKind::Overall))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+    }
+
+    #[test]
+    fn test_request_handle_cancel() {
+        // Accept the request but never respond, so the request only finishes if it's canceled
+        let port = start_stalling_server("");
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+
+        let mut request = client
+            .get_object("test-bucket", "test-key", &GetObjectParams::new())
+            .expect("request should be created");
+        let handle = request.handle();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            handle.cancel();
+            // Canceling again is harmless
+            handle.cancel();
+        });
+
+        let result = futures::executor::block_on(futures::StreamExt::next(&mut request));
+        let Some(Err(ObjectClientError::ClientError(S3RequestError::RequestCanceled))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+    }
+
+    #[test]
+    fn test_shutdown_cancels_stalled_request() {
+        // Accept the request but never respond, so the request only finishes if it's canceled
+        let port = start_stalling_server("");
+        let client = new_stalling_server_client(port, S3ClientConfig::new());
+        let other_handle = client.clone();
+
+        let mut request = client
+            .get_object("test-bucket", "test-key", &GetObjectParams::new())
+            .expect("request should be created");
+
+        let report = futures::executor::block_on(client.shutdown(Duration::from_millis(100)));
+        assert_eq!((report.completed, report.canceled), (0, 1));
+        // The other handle still holds the client
+        assert!(!report.event_loop_group_shut_down);
+
+        let result = futures::executor::block_on(futures::StreamExt::next(&mut request));
+        let Some(Err(ObjectClientError::ClientError(S3RequestError::RequestCanceled))) = result else {
+            panic!("wrong result, got: {:?}", result);
+        };
+
+        let result = other_handle.get_object("test-bucket", "test-key", &GetObjectParams::new());
+        assert!(
+            matches!(
+                result,
+                Err(ObjectClientError::ClientError(S3RequestError::ClientShutDown))
+            ),
+            "got {result:?}"
+        );
+    }
+
+    /// A tracing writer that appends everything to a shared buffer
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test_case(Some("user-request-1234"), Some("context=\"user-request-1234\""); "with context")]
+    #[test_case(None, None; "without context")]
+    fn test_request_context(context: Option<&str>, expected_field: Option<&str>) {
+        let client = S3CrtClient::new(S3ClientConfig::new()).expect("create test client");
+        let handle = client.with_request_context(context);
+
+        let buffer = SharedBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt().with_writer(move || writer.clone()).finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let span = request_span!(handle, "test_op", key = "test-key");
+            span.in_scope(|| tracing::warn!("in request span"));
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line = output
+            .lines()
+            .find(|line| line.contains("in request span"))
+            .expect("event should be logged");
+        assert!(line.contains("test_op{"), "got {line}");
+        match expected_field {
+            Some(expected_field) => assert!(line.contains(expected_field), "got {line}"),
+            None => assert!(!line.contains("context="), "got {line}"),
+        }
+        // Other handles don't record the context
+        assert!(client.request_context.is_none());
+    }
+
+    #[test]
+    fn test_request_span_records_endpoint() {
+        let client = S3CrtClient::new(S3ClientConfig::new().endpoint_config(EndpointConfig::new("eu-west-1")))
+            .expect("create test client");
+
+        let buffer = SharedBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::DEBUG)
+            .with_writer(move || writer.clone())
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            let span = request_span!(client, "test_op");
+            let message = client.new_request_template("GET", "doc-example-bucket").unwrap();
+            let _options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::Default, &span);
+            span.in_scope(|| tracing::warn!("in request span"));
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let line = output
+            .lines()
+            .find(|line| line.contains("in request span"))
+            .expect("event should be logged");
+        assert!(
+            line.contains("host=\"doc-example-bucket.s3.eu-west-1.amazonaws.com\""),
+            "got {line}"
+        );
+        assert!(line.contains("scheme=\"https\""), "got {line}");
+        assert!(line.contains("signing_region=\"eu-west-1\""), "got {line}");
+        assert!(
+            output.lines().any(|line| line.contains("resolved request endpoint")),
+            "got {output}"
+        );
+    }
+
+    #[test]
+    fn test_shutdown_idle_client() {
+        let client = new_stalling_server_client(1, S3ClientConfig::new());
+        let report = futures::executor::block_on(client.shutdown(Duration::from_secs(10)));
+        assert_eq!((report.completed, report.canceled), (0, 0));
+        assert!(report.event_loop_group_shut_down);
+    }
 }
@@ -0,0 +1,159 @@
+use std::ops::Deref;
+use std::os::unix::prelude::OsStrExt;
+use std::str::FromStr;
+
+use mountpoint_s3_crt::http::request_response::Header;
+use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::object_client::{
+    CopyObjectError, CopyObjectParams, CopyObjectResult, ETag, MetadataDirective, ObjectClientError, ObjectClientResult,
+};
+use crate::s3_crt_client::list_objects::{get_field, parse_xml, ParseError};
+use crate::s3_crt_client::{encode_s3_path, S3CrtClient, S3RequestError};
+
+impl S3CrtClient {
+    /// Create and begin a new CopyObject request.
+    pub(super) async fn copy_object(
+        &self,
+        source_bucket: &str,
+        source_key: &str,
+        destination_bucket: &str,
+        destination_key: &str,
+        params: &CopyObjectParams,
+    ) -> ObjectClientResult<CopyObjectResult, CopyObjectError, S3RequestError> {
+        let span = request_span!(
+            self,
+            "copy_object",
+            source_bucket,
+            source_key,
+            destination_bucket,
+            destination_key
+        );
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("PUT", destination_bucket)
+                .map_err(S3RequestError::construction_failure)?;
+
+            // The copy source is URL-encoded the same way as a request path
+            let copy_source = encode_s3_path(format!("{source_bucket}/{source_key}"));
+            message
+                .set_header(&Header::new("x-amz-copy-source", copy_source))
+                .map_err(S3RequestError::construction_failure)?;
+
+            let metadata_directive = match params.metadata_directive {
+                MetadataDirective::Copy => "COPY",
+                MetadataDirective::Replace => "REPLACE",
+            };
+            message
+                .set_header(&Header::new("x-amz-metadata-directive", metadata_directive))
+                .map_err(S3RequestError::construction_failure)?;
+
+            if let Some(storage_class) = params.storage_class.as_deref() {
+                message
+                    .set_header(&Header::new("x-amz-storage-class", storage_class))
+                    .map_err(S3RequestError::construction_failure)?;
+            }
+
+            message
+                .set_request_path(format!("/{destination_key}"))
+                .map_err(S3RequestError::construction_failure)?;
+            // A successful response status doesn't mean the copy succeeded
+            message.check_embedded_error();
+
+            self.inner
+                .make_simple_http_request(message, MetaRequestType::Default, span, parse_copy_object_error)?
+        };
+
+        let response = request.await?;
+
+        let mut result = parse_copy_object_response(&response.body)?;
+        result.response_metadata = response.metadata;
+        Ok(result)
+    }
+}
+
+fn parse_copy_object_response(body: &[u8]) -> ObjectClientResult<CopyObjectResult, CopyObjectError, S3RequestError> {
+    let parse_error = |e: ParseError| ObjectClientError::ClientError(S3RequestError::InternalError(e.into()));
+
+    let root = xmltree::Element::parse(body).map_err(|e| parse_error(e.into()))?;
+    let etag = get_field(&root, "ETag").map_err(parse_error)?;
+    let last_modified = get_field(&root, "LastModified").map_err(parse_error)?;
+    let last_modified = OffsetDateTime::parse(&last_modified, &Rfc3339)
+        .map_err(|e| parse_error(ParseError::OffsetDateTime(e, "LastModified".to_string())))?;
+
+    Ok(CopyObjectResult {
+        etag: ETag::from_str(&etag).unwrap(),
+        last_modified,
+        response_metadata: Default::default(),
+    })
+}
+
+fn parse_copy_object_error(result: &MetaRequestResult) -> Option<CopyObjectError> {
+    match result.response_status {
+        // Errors embedded in a successful response keep its 200 status
+        200 | 404 => {
+            let body = result.error_response_body.as_ref()?;
+            let root = parse_xml(body.as_bytes()).ok()?;
+            let error_code = root.get_child("Code")?;
+            let error_str = error_code.get_text()?;
+            match error_str.deref() {
+                "NoSuchBucket" => Some(CopyObjectError::NoSuchBucket),
+                "NoSuchKey" => Some(CopyObjectError::NoSuchKey),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::{OsStr, OsString};
+
+    use test_case::test_case;
+
+    use super::*;
+
+    fn make_result(response_status: i32, body: impl Into<OsString>) -> MetaRequestResult {
+        MetaRequestResult {
+            response_status,
+            crt_error: 1i32.into(),
+            error_response_headers: None,
+            error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
+        }
+    }
+
+    #[test_case("NoSuchBucket", Some(CopyObjectError::NoSuchBucket); "no such bucket")]
+    #[test_case("NoSuchKey", Some(CopyObjectError::NoSuchKey); "no such key")]
+    #[test_case("InvalidRequest", None; "unmodeled error")]
+    fn parse_404(code: &str, expected: Option<CopyObjectError>) {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>{code}</Code><Message>Some message</Message><RequestId>4VAGDP5HMYTDNB3Y</RequestId><HostId>JsSt4AX5Cvi6aFS3+TZt/U/C1IIZ1Y2c7U3WyJCtYgNWOvVqqZaQR0gQp+3zoYMTlE42PWOsCMk=</HostId></Error>"#
+        );
+        let result = make_result(404, OsStr::from_bytes(body.as_bytes()));
+        assert_eq!(parse_copy_object_error(&result), expected);
+    }
+
+    #[test]
+    fn parse_copy_object_result() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><CopyObjectResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;9b2cf535f27731c974343645a3985328&quot;</ETag></CopyObjectResult>"#;
+        let result = parse_copy_object_response(&body[..]).expect("result should parse");
+        assert_eq!(result.etag.as_str(), "\"9b2cf535f27731c974343645a3985328\"");
+        let expected_last_modified = OffsetDateTime::parse("2024-07-01T12:34:56Z", &Rfc3339).unwrap();
+        assert_eq!(result.last_modified, expected_last_modified);
+    }
+
+    #[test_case(200; "embedded")]
+    #[test_case(404; "error response")]
+    fn parse_no_such_key(status: i32) {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NoSuchKey</Code><Message>The specified key does not exist.</Message></Error>"#;
+        let result = make_result(status, OsStr::from_bytes(&body[..]));
+        assert_eq!(parse_copy_object_error(&result), Some(CopyObjectError::NoSuchKey));
+    }
+}
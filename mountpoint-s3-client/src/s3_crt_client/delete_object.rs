@@ -1,10 +1,22 @@
 use std::ops::Deref;
 use std::os::unix::prelude::OsStrExt;
 
+use mountpoint_s3_crt::checksums::crc32c;
+use mountpoint_s3_crt::http::request_response::Header;
 use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use tracing::warn;
+use xmltree::{Element, XMLNode};
 
-use crate::object_client::{DeleteObjectError, DeleteObjectResult, ObjectClientResult};
-use crate::s3_crt_client::{S3CrtClient, S3RequestError};
+use crate::checksums::crc32c_to_base64;
+use crate::object_client::{
+    DeleteObjectError, DeleteObjectParams, DeleteObjectResult, DeleteObjectsResult, ObjectClientError,
+    ObjectClientResult,
+};
+use crate::s3_crt_client::list_objects::{get_field, parse_xml, ParseError};
+use crate::s3_crt_client::{QueryFragment, S3CrtClient, S3RequestError};
+
+/// The maximum number of keys S3 accepts in a single DeleteObjects request
+const MAX_DELETE_OBJECTS_KEYS: usize = 1000;
 
 impl S3CrtClient {
     /// Create and begin a new DeleteObject request.
@@ -12,34 +24,174 @@ impl S3CrtClient {
         &self,
         bucket: &str,
         key: &str,
+        params: &DeleteObjectParams,
     ) -> ObjectClientResult<DeleteObjectResult, DeleteObjectError, S3RequestError> {
-        let span = request_span!(self.inner, "delete_object", bucket, key);
+        let span = request_span!(
+            self,
+            "delete_object",
+            bucket,
+            key,
+            version_id = ?params.version_id,
+            if_match = ?params.if_match
+        );
 
         // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
         let request = {
             let mut message = self
-                .inner
                 .new_request_template("DELETE", bucket)
                 .map_err(S3RequestError::construction_failure)?;
+            let mut query = Vec::new();
+            if let Some(version_id) = &params.version_id {
+                query.push(QueryFragment::Pair("versionId", version_id));
+            }
             message
-                .set_request_path(format!("/{key}"))
+                .set_request_path_and_query(format!("/{key}"), &query)
                 .map_err(S3RequestError::construction_failure)?;
+            if let Some(etag) = &params.if_match {
+                message
+                    .set_header(&Header::new("If-Match", etag.as_str()))
+                    .map_err(S3RequestError::construction_failure)?;
+            }
 
             self.inner
                 .make_simple_http_request(message, MetaRequestType::Default, span, parse_delete_object_error)?
         };
 
-        let _body = request.await?;
+        let response = request.await?;
+
+        Ok(DeleteObjectResult {
+            response_metadata: response.metadata,
+        })
+    }
+
+    /// Delete the given keys, using as many DeleteObjects requests as needed to stay within the
+    /// S3 limit of [MAX_DELETE_OBJECTS_KEYS] keys per request. Requests are made one at a time.
+    pub(super) async fn delete_objects(
+        &self,
+        bucket: &str,
+        keys: &[String],
+    ) -> ObjectClientResult<DeleteObjectsResult, DeleteObjectError, S3RequestError> {
+        self.delete_objects_in_chunks(bucket, keys, MAX_DELETE_OBJECTS_KEYS)
+            .await
+    }
 
-        Ok(DeleteObjectResult {})
+    /// Delete the given keys with one DeleteObjects request for each `chunk_size` keys.
+    ///
+    /// If the first request fails, nothing has been deleted, so return its error. Once a request
+    /// has succeeded, a later failed request doesn't fail the whole operation, since the caller
+    /// needs to know which keys were deleted. Instead, each key of the failed request is reported
+    /// with [DeleteObjectError::RequestFailed], and the remaining requests are still made.
+    pub(super) async fn delete_objects_in_chunks(
+        &self,
+        bucket: &str,
+        keys: &[String],
+        chunk_size: usize,
+    ) -> ObjectClientResult<DeleteObjectsResult, DeleteObjectError, S3RequestError> {
+        let mut result = DeleteObjectsResult::default();
+        for (i, chunk) in keys.chunks(chunk_size).enumerate() {
+            match self.delete_objects_chunk(bucket, chunk).await {
+                Ok(chunk_result) => {
+                    result.deleted.extend(chunk_result.deleted);
+                    result.errors.extend(chunk_result.errors);
+                }
+                Err(e) if i == 0 => return Err(e),
+                Err(e) => {
+                    let message = match &e {
+                        ObjectClientError::ServiceError(e) => e.to_string(),
+                        ObjectClientError::ClientError(e) => e.to_string(),
+                    };
+                    warn!(bucket, num_keys = chunk.len(), error = ?e, "DeleteObjects request failed");
+                    result.errors.extend(
+                        chunk
+                            .iter()
+                            .map(|key| (key.clone(), DeleteObjectError::RequestFailed(message.clone()))),
+                    );
+                }
+            }
+        }
+        Ok(result)
     }
+
+    /// Create and begin a single DeleteObjects request for at most [MAX_DELETE_OBJECTS_KEYS] keys.
+    async fn delete_objects_chunk(
+        &self,
+        bucket: &str,
+        keys: &[String],
+    ) -> ObjectClientResult<DeleteObjectsResult, DeleteObjectError, S3RequestError> {
+        debug_assert!(keys.len() <= MAX_DELETE_OBJECTS_KEYS);
+        let span = request_span!(self, "delete_objects", bucket, num_keys = keys.len());
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("POST", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path_and_query("/", &[QueryFragment::Flag("delete")])
+                .map_err(S3RequestError::construction_failure)?;
+
+            // S3 requires an integrity checksum for the body of DeleteObjects requests
+            let body = build_delete_objects_body(keys)
+                .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(Box::new(e))))?;
+            let checksum = crc32c_to_base64(&crc32c::checksum(&body));
+            message
+                .set_header(&Header::new("x-amz-checksum-crc32c", checksum))
+                .map_err(S3RequestError::construction_failure)?;
+            message.set_body(body).map_err(S3RequestError::construction_failure)?;
+
+            self.inner
+                .make_simple_http_request(message, MetaRequestType::Default, span, parse_delete_object_error)?
+        };
+
+        let response = request.await?;
+
+        parse_delete_objects_result(&response.body)
+            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))
+    }
+}
+
+/// Build the XML body of a DeleteObjects request for the given keys.
+fn build_delete_objects_body(keys: &[String]) -> Result<Vec<u8>, xmltree::Error> {
+    let mut root = Element::new("Delete");
+    for key in keys {
+        let mut key_element = Element::new("Key");
+        key_element.children.push(XMLNode::Text(key.clone()));
+        let mut object = Element::new("Object");
+        object.children.push(XMLNode::Element(key_element));
+        root.children.push(XMLNode::Element(object));
+    }
+
+    let mut body = Vec::new();
+    root.write(&mut body)?;
+    Ok(body)
+}
+
+/// Parse the `<Deleted>` and `<Error>` entries of a DeleteObjects response.
+fn parse_delete_objects_result(bytes: &[u8]) -> Result<DeleteObjectsResult, ParseError> {
+    let mut element = Element::parse(bytes)?;
+
+    let mut deleted = Vec::new();
+    while let Some(entry) = element.take_child("Deleted") {
+        deleted.push(get_field(&entry, "Key")?);
+    }
+
+    let mut errors = Vec::new();
+    while let Some(entry) = element.take_child("Error") {
+        let key = get_field(&entry, "Key")?;
+        let code = get_field(&entry, "Code")?;
+        // The message is informational only, so don't fail if it's missing or empty
+        let message = get_field(&entry, "Message").unwrap_or_default();
+        errors.push((key, DeleteObjectError::KeyError { code, message }));
+    }
+
+    Ok(DeleteObjectsResult { deleted, errors })
 }
 
 fn parse_delete_object_error(result: &MetaRequestResult) -> Option<DeleteObjectError> {
     match result.response_status {
         404 => {
             let body = result.error_response_body.as_ref()?;
-            let root = xmltree::Element::parse(body.as_bytes()).ok()?;
+            let root = parse_xml(body.as_bytes()).ok()?;
             let error_code = root.get_child("Code")?;
             let error_str = error_code.get_text()?;
 
@@ -49,6 +201,7 @@ fn parse_delete_object_error(result: &MetaRequestResult) -> Option<DeleteObjectE
                 _ => None,
             }
         }
+        412 => Some(DeleteObjectError::PreconditionFailed),
         _ => None,
     }
 }
@@ -65,6 +218,8 @@ mod tests {
             crt_error: 1i32.into(),
             error_response_headers: None,
             error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
         }
     }
 
@@ -75,4 +230,55 @@ mod tests {
         let result = parse_delete_object_error(&result);
         assert_eq!(result, Some(DeleteObjectError::NoSuchBucket));
     }
+
+    #[test]
+    fn parse_412_precondition_failed() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>PreconditionFailed</Code><Message>At least one of the pre-conditions you specified did not hold</Message><Condition>If-Match</Condition><RequestId>TX3KXRW0T8VPHC8Q</RequestId><HostId>S6nZ1wGx2L2z1Ffa/sQ7sLEtXGmB7jr1IO3IabWZ6RLFBUMixW6g4Rgl8mdgL4+OXRamBC3f4yM=</HostId></Error>"#;
+        let result = make_result(412, OsStr::from_bytes(&body[..]));
+        let result = parse_delete_object_error(&result);
+        assert_eq!(result, Some(DeleteObjectError::PreconditionFailed));
+    }
+
+    #[test]
+    fn build_delete_objects_body_escapes_keys() {
+        let keys = vec!["hello".to_owned(), "a&b/<c>\"d\"".to_owned()];
+        let body = build_delete_objects_body(&keys).expect("body should build");
+        let body = String::from_utf8(body).unwrap();
+        assert!(body.contains("<Key>a&amp;b/&lt;c"), "unexpected body: {body}");
+
+        // The body should round-trip through an XML parser
+        let root = Element::parse(body.as_bytes()).unwrap();
+        let parsed_keys: Vec<_> = root
+            .children
+            .iter()
+            .filter_map(|node| get_field(node.as_element()?, "Key").ok())
+            .collect();
+        assert_eq!(parsed_keys, keys);
+    }
+
+    #[test]
+    fn parse_delete_objects_result_with_errors() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><DeleteResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Deleted><Key>sample1.txt</Key></Deleted><Error><Key>sample2.txt</Key><Code>AccessDenied</Code><Message>Access Denied</Message></Error><Deleted><Key>sample3.txt</Key></Deleted><Error><Key>sample4.txt</Key><Code>InternalError</Code><Message></Message></Error></DeleteResult>"#;
+        let result = parse_delete_objects_result(&body[..]).expect("result should parse");
+        assert_eq!(result.deleted, vec!["sample1.txt", "sample3.txt"]);
+        assert_eq!(
+            result.errors,
+            vec![
+                (
+                    "sample2.txt".to_owned(),
+                    DeleteObjectError::KeyError {
+                        code: "AccessDenied".to_owned(),
+                        message: "Access Denied".to_owned(),
+                    }
+                ),
+                (
+                    "sample4.txt".to_owned(),
+                    DeleteObjectError::KeyError {
+                        code: "InternalError".to_owned(),
+                        message: String::new(),
+                    }
+                ),
+            ]
+        );
+    }
 }
@@ -0,0 +1,266 @@
+//! A decoder for the `application/vnd.amazon.eventstream` framing that S3 uses to stream responses
+//! such as SelectObjectContent.
+//!
+//! Each message is framed as a 12-byte prelude (total length, headers length, and a CRC32 of the
+//! first 8 bytes), followed by the headers, the payload, and a CRC32 of everything before it. All
+//! integers are big-endian.
+
+use mountpoint_s3_crt::checksums::crc32;
+use thiserror::Error;
+
+/// Length of the prelude at the start of each message
+const PRELUDE_LEN: usize = 12;
+
+/// Length of the CRC at the end of each message
+const MESSAGE_CRC_LEN: usize = 4;
+
+/// S3 limits messages to 16MiB, so anything longer means the stream is corrupt
+const MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+/// A decoded event-stream message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Message {
+    headers: Vec<(String, HeaderValue)>,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl Message {
+    /// The value of the string header with the given name, if there is one
+    pub(crate) fn string_header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find_map(|(header_name, value)| match value {
+            HeaderValue::String(value) if header_name == name => Some(value.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// The value of an event-stream header. S3 only sends string headers, so we don't keep the values
+/// of headers of other types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HeaderValue {
+    String(String),
+    Other,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub(crate) enum EventStreamError {
+    #[error("prelude checksum mismatch")]
+    PreludeChecksumMismatch,
+
+    #[error("message checksum mismatch")]
+    MessageChecksumMismatch,
+
+    #[error("invalid message length {0}")]
+    InvalidLength(usize),
+
+    #[error("invalid message headers")]
+    InvalidHeaders,
+
+    #[error("stream ended with {0} bytes of an incomplete message")]
+    IncompleteMessage(usize),
+}
+
+/// Decodes event-stream messages from a response body that arrives in arbitrarily sized chunks
+#[derive(Debug, Default)]
+pub(crate) struct Decoder {
+    buffer: Vec<u8>,
+}
+
+impl Decoder {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the next chunk of the response body.
+    pub(crate) fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Decode the next complete message, if one has arrived.
+    pub(crate) fn next_message(&mut self) -> Result<Option<Message>, EventStreamError> {
+        if self.buffer.len() < PRELUDE_LEN {
+            return Ok(None);
+        }
+
+        let total_len = read_u32(&self.buffer[0..4]) as usize;
+        let headers_len = read_u32(&self.buffer[4..8]) as usize;
+        let prelude_crc = read_u32(&self.buffer[8..12]);
+        if crc32::checksum(&self.buffer[0..8]).value() != prelude_crc {
+            return Err(EventStreamError::PreludeChecksumMismatch);
+        }
+        if !(PRELUDE_LEN + MESSAGE_CRC_LEN..=MAX_MESSAGE_LEN).contains(&total_len)
+            || headers_len > total_len - PRELUDE_LEN - MESSAGE_CRC_LEN
+        {
+            return Err(EventStreamError::InvalidLength(total_len));
+        }
+
+        if self.buffer.len() < total_len {
+            return Ok(None);
+        }
+
+        let message: Vec<u8> = self.buffer.drain(..total_len).collect();
+        let crc_offset = total_len - MESSAGE_CRC_LEN;
+        let message_crc = read_u32(&message[crc_offset..]);
+        if crc32::checksum(&message[..crc_offset]).value() != message_crc {
+            return Err(EventStreamError::MessageChecksumMismatch);
+        }
+
+        let headers_end = PRELUDE_LEN + headers_len;
+        let headers = parse_headers(&message[PRELUDE_LEN..headers_end])?;
+        let payload = message[headers_end..crc_offset].to_vec();
+        Ok(Some(Message { headers, payload }))
+    }
+
+    /// Check that the response body didn't end partway through a message.
+    pub(crate) fn finish(&self) -> Result<(), EventStreamError> {
+        if self.buffer.is_empty() {
+            Ok(())
+        } else {
+            Err(EventStreamError::IncompleteMessage(self.buffer.len()))
+        }
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes[..4].try_into().unwrap())
+}
+
+fn parse_headers(mut bytes: &[u8]) -> Result<Vec<(String, HeaderValue)>, EventStreamError> {
+    // Take the next `len` bytes off the front of the headers
+    fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], EventStreamError> {
+        if bytes.len() < len {
+            return Err(EventStreamError::InvalidHeaders);
+        }
+        let (head, tail) = bytes.split_at(len);
+        *bytes = tail;
+        Ok(head)
+    }
+
+    let mut headers = Vec::new();
+    while !bytes.is_empty() {
+        let name_len = take(&mut bytes, 1)?[0] as usize;
+        let name = std::str::from_utf8(take(&mut bytes, name_len)?).map_err(|_| EventStreamError::InvalidHeaders)?;
+        let value_type = take(&mut bytes, 1)?[0];
+        let value = match value_type {
+            // Boolean true and false have no value bytes
+            0 | 1 => HeaderValue::Other,
+            // Byte, short, integer, long
+            2 => take(&mut bytes, 1).map(|_| HeaderValue::Other)?,
+            3 => take(&mut bytes, 2).map(|_| HeaderValue::Other)?,
+            4 => take(&mut bytes, 4).map(|_| HeaderValue::Other)?,
+            5 => take(&mut bytes, 8).map(|_| HeaderValue::Other)?,
+            // Byte array and string are prefixed with a 2-byte length
+            6 | 7 => {
+                let len = u16::from_be_bytes(take(&mut bytes, 2)?.try_into().unwrap()) as usize;
+                let value = take(&mut bytes, len)?;
+                if value_type == 7 {
+                    let value = std::str::from_utf8(value).map_err(|_| EventStreamError::InvalidHeaders)?;
+                    HeaderValue::String(value.to_owned())
+                } else {
+                    HeaderValue::Other
+                }
+            }
+            // Timestamp
+            8 => take(&mut bytes, 8).map(|_| HeaderValue::Other)?,
+            // UUID
+            9 => take(&mut bytes, 16).map(|_| HeaderValue::Other)?,
+            _ => return Err(EventStreamError::InvalidHeaders),
+        };
+        headers.push((name.to_owned(), value));
+    }
+    Ok(headers)
+}
+
+/// Encode a message with the given string headers, for tests of code that decodes event streams
+#[cfg(test)]
+pub(crate) fn encode_message(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+    let mut encoded_headers = Vec::new();
+    for (name, value) in headers {
+        encoded_headers.push(name.len() as u8);
+        encoded_headers.extend_from_slice(name.as_bytes());
+        encoded_headers.push(7);
+        encoded_headers.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        encoded_headers.extend_from_slice(value.as_bytes());
+    }
+
+    let total_len = PRELUDE_LEN + encoded_headers.len() + payload.len() + MESSAGE_CRC_LEN;
+    let mut message = Vec::with_capacity(total_len);
+    message.extend_from_slice(&(total_len as u32).to_be_bytes());
+    message.extend_from_slice(&(encoded_headers.len() as u32).to_be_bytes());
+    let prelude_crc = crc32::checksum(&message).value();
+    message.extend_from_slice(&prelude_crc.to_be_bytes());
+    message.extend_from_slice(&encoded_headers);
+    message.extend_from_slice(payload);
+    let message_crc = crc32::checksum(&message).value();
+    message.extend_from_slice(&message_crc.to_be_bytes());
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_message() {
+        let encoded = encode_message(&[(":message-type", "event"), (":event-type", "Records")], b"a,b,c\n");
+        let mut decoder = Decoder::new();
+        decoder.push(&encoded);
+        let message = decoder.next_message().unwrap().expect("message should be complete");
+        assert_eq!(message.string_header(":message-type"), Some("event"));
+        assert_eq!(message.string_header(":event-type"), Some("Records"));
+        assert_eq!(message.string_header(":content-type"), None);
+        assert_eq!(message.payload, b"a,b,c\n");
+        assert_eq!(decoder.next_message(), Ok(None));
+        assert_eq!(decoder.finish(), Ok(()));
+    }
+
+    #[test]
+    fn decode_split_messages() {
+        let mut encoded = encode_message(&[(":event-type", "Records")], b"first");
+        encoded.extend(encode_message(&[(":event-type", "End")], b""));
+
+        // Deliver the messages one byte at a time
+        let mut decoder = Decoder::new();
+        let mut messages = Vec::new();
+        for byte in &encoded {
+            decoder.push(&[*byte]);
+            while let Some(message) = decoder.next_message().unwrap() {
+                messages.push(message);
+            }
+        }
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].payload, b"first");
+        assert_eq!(messages[1].string_header(":event-type"), Some("End"));
+        assert_eq!(decoder.finish(), Ok(()));
+    }
+
+    #[test]
+    fn decode_incomplete_message() {
+        let encoded = encode_message(&[(":event-type", "Records")], b"payload");
+        let mut decoder = Decoder::new();
+        decoder.push(&encoded[..encoded.len() - 1]);
+        assert_eq!(decoder.next_message(), Ok(None));
+        assert_eq!(
+            decoder.finish(),
+            Err(EventStreamError::IncompleteMessage(encoded.len() - 1))
+        );
+    }
+
+    #[test]
+    fn decode_corrupt_message() {
+        let encoded = encode_message(&[(":event-type", "Records")], b"payload");
+
+        let mut corrupt_prelude = encoded.clone();
+        corrupt_prelude[1] ^= 0xff;
+        let mut decoder = Decoder::new();
+        decoder.push(&corrupt_prelude);
+        assert_eq!(decoder.next_message(), Err(EventStreamError::PreludeChecksumMismatch));
+
+        let mut corrupt_payload = encoded.clone();
+        let payload_offset = encoded.len() - MESSAGE_CRC_LEN - 1;
+        corrupt_payload[payload_offset] ^= 0xff;
+        let mut decoder = Decoder::new();
+        decoder.push(&corrupt_payload);
+        assert_eq!(decoder.next_message(), Err(EventStreamError::MessageChecksumMismatch));
+    }
+}
@@ -0,0 +1,133 @@
+use std::ops::Deref;
+use std::os::unix::prelude::OsStrExt;
+
+use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use thiserror::Error;
+
+use crate::object_client::{ObjectClientError, ObjectClientResult};
+use crate::s3_crt_client::list_objects::{parse_xml, xml_snippet, ParseError};
+use crate::s3_crt_client::{QueryFragment, S3CrtClient, S3RequestError};
+
+/// Errors returned by a [`get_bucket_location`](S3CrtClient::get_bucket_location) request.
+#[derive(Error, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GetBucketLocationError {
+    #[error("The bucket does not exist")]
+    NoSuchBucket,
+}
+
+impl S3CrtClient {
+    /// Find the region that a bucket is in, using a GetBucketLocation request. Unlike
+    /// [get_bucket_region](Self::get_bucket_region), this asks for the region explicitly instead of
+    /// relying on S3 to report it in a response header, but needs the `s3:GetBucketLocation`
+    /// permission, and S3 only answers it from the bucket's region or from `us-east-1`.
+    ///
+    /// Buckets in `us-east-1` have no location constraint, and buckets created with the legacy `EU`
+    /// constraint are in `eu-west-1`, so both are translated to their region.
+    pub async fn get_bucket_location(
+        &self,
+        bucket: &str,
+    ) -> ObjectClientResult<String, GetBucketLocationError, S3RequestError> {
+        self.check_bucket_name(bucket)?;
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("GET", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path_and_query("/", &[QueryFragment::Flag("location")])
+                .map_err(S3RequestError::construction_failure)?;
+
+            let span = request_span!(self, "get_bucket_location", bucket);
+
+            self.inner.make_simple_http_request(
+                message,
+                MetaRequestType::Default,
+                span,
+                parse_get_bucket_location_error,
+            )?
+        };
+
+        let response = request.await?;
+
+        parse_location_constraint(&response.body)
+            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))
+    }
+}
+
+/// Parse the `<LocationConstraint>` of a GetBucketLocation response into a region.
+fn parse_location_constraint(bytes: &[u8]) -> Result<String, ParseError> {
+    let root = parse_xml(bytes)?;
+    if root.name != "LocationConstraint" {
+        return Err(ParseError::InvalidResponse(
+            xml_snippet(&root),
+            "expected a LocationConstraint".to_string(),
+        ));
+    }
+    // The element is empty for buckets in us-east-1
+    let region = match root.get_text().unwrap_or_default().deref() {
+        "" => "us-east-1".to_string(),
+        "EU" => "eu-west-1".to_string(),
+        region => region.to_string(),
+    };
+    Ok(region)
+}
+
+fn parse_get_bucket_location_error(result: &MetaRequestResult) -> Option<GetBucketLocationError> {
+    match result.response_status {
+        404 => {
+            let body = result.error_response_body.as_ref()?;
+            let root = parse_xml(body.as_bytes()).ok()?;
+            let error_code = root.get_child("Code")?;
+            let error_str = error_code.get_text()?;
+            match error_str.deref() {
+                "NoSuchBucket" => Some(GetBucketLocationError::NoSuchBucket),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(r#"<LocationConstraint xmlns="http://s3.amazonaws.com/doc/2006-03-01/">us-west-2</LocationConstraint>"#, "us-west-2"; "region")]
+    #[test_case(r#"<LocationConstraint xmlns="http://s3.amazonaws.com/doc/2006-03-01/"/>"#, "us-east-1"; "empty")]
+    #[test_case(r#"<LocationConstraint xmlns="http://s3.amazonaws.com/doc/2006-03-01/">EU</LocationConstraint>"#, "eu-west-1"; "legacy eu")]
+    fn parse_location(body: &str, expected: &str) {
+        let body = format!(r#"<?xml version="1.0" encoding="UTF-8"?>{body}"#);
+        let region = parse_location_constraint(body.as_bytes()).expect("location should parse");
+        assert_eq!(region, expected);
+    }
+
+    #[test]
+    fn parse_unexpected_response() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Location>us-west-2</Location>"#;
+        let err = parse_location_constraint(body).expect_err("wrong root element should fail");
+        assert!(matches!(err, ParseError::InvalidResponse(_, _)), "got {err:?}");
+    }
+
+    #[test_case("NoSuchBucket", Some(GetBucketLocationError::NoSuchBucket); "no such bucket")]
+    #[test_case("NotARealError", None; "unmodeled error")]
+    fn parse_404(code: &str, expected: Option<GetBucketLocationError>) {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>{code}</Code><Message>Some message</Message><RequestId>4VAGDP5HMYTDNB3Y</RequestId></Error>"#
+        );
+        let result = MetaRequestResult {
+            response_status: 404,
+            crt_error: 1i32.into(),
+            error_response_headers: None,
+            error_response_body: Some(OsString::from(body)),
+            validation_algorithm: None,
+            metrics: Default::default(),
+        };
+        assert_eq!(parse_get_bucket_location_error(&result), expected);
+    }
+}
@@ -1,19 +1,33 @@
+use std::collections::HashMap;
 use std::future::Future;
+use std::io::Write;
 use std::ops::Deref;
-use std::ops::Range;
 use std::os::unix::prelude::OsStrExt;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 
+use flate2::write::GzDecoder;
 use futures::channel::mpsc::UnboundedReceiver;
 use futures::Stream;
-use mountpoint_s3_crt::common::error::Error;
-use mountpoint_s3_crt::http::request_response::Header;
-use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use mountpoint_s3_crt::common::uri::Uri;
+use mountpoint_s3_crt::http::request_response::{Header, Headers};
+use mountpoint_s3_crt::s3::client::{ChecksumConfig, MetaRequest, MetaRequestResult, MetaRequestType};
 use pin_project::pin_project;
+use time::{OffsetDateTime, UtcOffset};
+use tracing::{debug, warn};
 
-use crate::object_client::{ETag, GetBodyPart, GetObjectError, ObjectClientError, ObjectClientResult};
-use crate::s3_crt_client::{S3CrtClient, S3HttpRequest, S3RequestError};
+use crate::object_client::{
+    Checksum, ContentHeaders, GetBodyPart, GetObjectError, GetObjectParams, HeadObjectResult, ObjectClientError,
+    ObjectClientResult, ObjectExpiration, ResponseMetadata,
+};
+use crate::s3_crt_client::list_objects::parse_xml;
+use crate::s3_crt_client::{
+    parse_content_headers, parse_content_type, parse_expiration, parse_http_date, parse_request_charged,
+    parse_user_metadata, CancelHandle, ChecksumMode, ConstructionError, QueryFragment, RequestHandle, S3CrtClient,
+    S3HttpRequest, S3RequestError,
+};
 
 use super::GetObjectRequest;
 
@@ -24,13 +38,90 @@ impl S3CrtClient {
         &self,
         bucket: &str,
         key: &str,
-        range: Option<Range<u64>>,
-        if_match: Option<ETag>,
+        params: &GetObjectParams,
     ) -> Result<S3GetObjectRequest, ObjectClientError<GetObjectError, S3RequestError>> {
-        let span = request_span!(self.inner, "get_object", bucket, key, ?range, ?if_match);
+        self.make_get_object_request(bucket, key, params, None, 0)
+    }
+
+    /// Create and begin a GetObject request, either to the bucket's endpoint, or to the target of
+    /// a redirect that earlier requests for the object followed `redirects_followed` times.
+    fn make_get_object_request(
+        &self,
+        bucket: &str,
+        key: &str,
+        params: &GetObjectParams,
+        redirect_target: Option<&RedirectTarget>,
+        redirects_followed: u32,
+    ) -> Result<S3GetObjectRequest, ObjectClientError<GetObjectError, S3RequestError>> {
+        let GetObjectParams {
+            range,
+            if_match,
+            if_none_match,
+            if_modified_since,
+            if_unmodified_since,
+            decode_content_encoding,
+            version_id,
+            part_number,
+            checksum_mode,
+            request_payer,
+        } = params;
+        let span = request_span!(
+            self,
+            "get_object",
+            bucket,
+            key,
+            ?range,
+            ?if_match,
+            ?if_none_match,
+            ?if_modified_since,
+            ?if_unmodified_since,
+            decode_content_encoding,
+            ?version_id,
+            ?part_number,
+            checksum_mode
+        );
+
+        if range.as_ref().is_some_and(|range| range.start >= range.end) {
+            return Err(ObjectClientError::ServiceError(GetObjectError::InvalidRange));
+        }
+        if range.is_some() && part_number.is_some() {
+            return Err(ObjectClientError::ClientError(S3RequestError::construction_failure(
+                ConstructionError::InvalidParameters("a range can't be combined with a part number".to_string()),
+            )));
+        }
+        // A range or part of an encoded body can't be decoded on its own
+        if *decode_content_encoding && (range.is_some() || part_number.is_some()) {
+            return Err(ObjectClientError::ClientError(S3RequestError::construction_failure(
+                ConstructionError::InvalidParameters(
+                    "decode_content_encoding can't be combined with a range or part number".to_string(),
+                ),
+            )));
+        }
+        let egress_byte_budget = self.inner.egress_byte_budget;
+        let egress_bytes = self.inner.egress_bytes.clone();
+        if let Some(budget) = egress_byte_budget.filter(|budget| egress_bytes.load(Ordering::SeqCst) > *budget) {
+            return Err(ObjectClientError::ClientError(S3RequestError::EgressBudgetExceeded {
+                budget,
+            }));
+        }
+
+        // The CRT splits GetObject meta requests into parallel ranged requests, which isn't worth
+        // the overhead for small ranges, so send those as a single GET. That reports body offsets
+        // within the response rather than the object.
+        let single_request_offset = range
+            .as_ref()
+            .filter(|range| range.end - range.start < self.inner.parallel_get_threshold)
+            .map(|range| range.start);
+        // The CRT's GetObject meta requests send ranged GETs, which S3 doesn't allow for a single
+        // part, so send those as a single GET too. Body offsets are then within the part.
+        let meta_request_type = match single_request_offset {
+            Some(_) => MetaRequestType::Default,
+            None if part_number.is_some() => MetaRequestType::Default,
+            None => MetaRequestType::GetObject,
+        };
+        let body_offset = single_request_offset.unwrap_or(0);
 
         let mut message = self
-            .inner
             .new_request_template("GET", bucket)
             .map_err(S3RequestError::construction_failure)?;
 
@@ -46,6 +137,27 @@ impl S3CrtClient {
                 .map_err(S3RequestError::construction_failure)?;
         }
 
+        if let Some(etag) = if_none_match {
+            // Return the object only if its entity tag (ETag) is not matched
+            message
+                .set_header(&Header::new("If-None-Match", etag.as_str()))
+                .map_err(S3RequestError::construction_failure)?;
+        }
+
+        if let Some(since) = if_modified_since {
+            // Return the object only if it has been modified since the given time
+            message
+                .set_header(&Header::new("If-Modified-Since", format_http_date(*since)))
+                .map_err(S3RequestError::construction_failure)?;
+        }
+
+        if let Some(since) = if_unmodified_since {
+            // Return the object only if it has not been modified since the given time
+            message
+                .set_header(&Header::new("If-Unmodified-Since", format_http_date(*since)))
+                .map_err(S3RequestError::construction_failure)?;
+        }
+
         if let Some(range) = range {
             // Range HTTP header is bounded below *inclusive*
             let range_value = format!("bytes={}-{}", range.start, range.end.saturating_sub(1));
@@ -54,38 +166,350 @@ impl S3CrtClient {
                 .map_err(S3RequestError::construction_failure)?;
         }
 
-        let key = format!("/{key}");
-        message
-            .set_request_path(key)
-            .map_err(S3RequestError::construction_failure)?;
+        // S3 only returns the object's checksum if we ask for it
+        let validate_checksum = self.inner.checksum_mode == ChecksumMode::Enabled;
+        let checksum_mode = *checksum_mode || validate_checksum;
+        if checksum_mode {
+            message
+                .set_header(&Header::new("x-amz-checksum-mode", "ENABLED"))
+                .map_err(S3RequestError::construction_failure)?;
+        }
+        if validate_checksum {
+            message.set_checksum_config(Some(ChecksumConfig::validate_response()));
+        }
+
+        if let Some(request_payer) = request_payer {
+            message
+                .set_header(&Header::new("x-amz-request-payer", request_payer))
+                .map_err(S3RequestError::construction_failure)?;
+        }
+
+        if let Some(target) = redirect_target {
+            // The location already holds the path and query to request, so send the request there
+            // as it is. The CRT signs it for the new host when it sends it.
+            message.uri = Uri::new_from_str(&self.inner.allocator, &target.endpoint)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_header(&Header::new("Host", &target.authority))
+                .map_err(S3RequestError::construction_failure)?;
+            message.host = target.authority.clone();
+            message
+                .inner
+                .set_request_path(&target.path_and_query)
+                .map_err(S3RequestError::construction_failure)?;
+        } else {
+            let key = format!("/{key}");
+            let mut query = Vec::new();
+            let part_number = part_number.map(|part_number| part_number.to_string());
+            if let Some(part_number) = &part_number {
+                query.push(QueryFragment::Pair("partNumber", part_number));
+            }
+            if let Some(version_id) = version_id {
+                query.push(QueryFragment::Pair("versionId", version_id));
+            }
+            message
+                .set_request_path_and_query(key, &query)
+                .map_err(S3RequestError::construction_failure)?;
+        }
+        let redirects = (self.inner.max_get_redirects > 0).then(|| {
+            Box::new(GetObjectRedirects {
+                client: self.clone(),
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+                params: params.clone(),
+                scheme: message.uri.scheme().to_string_lossy().into_owned(),
+                authority: message.uri.authority().to_string_lossy().into_owned(),
+                followed: redirects_followed,
+            })
+        });
 
         let (sender, receiver) = futures::channel::mpsc::unbounded();
+        let finish_sender = sender.clone();
+        let decode_content_encoding = *decode_content_encoding;
+        // Only set once the headers show that the body has an encoding the client decodes
+        let decoder: Arc<Mutex<Option<BodyDecoder>>> = Default::default();
+        let decoder_headers = decoder.clone();
+        let decoder_finish = decoder.clone();
+        // The CRT counts the read window in bytes of the encoded body, but callers grow it by the
+        // length of the decoded parts they consumed
+        let read_window = decode_content_encoding.then(|| Arc::new(DecodedReadWindow::default()));
+        let read_window_body = read_window.clone();
+        let read_window_finish = read_window.clone();
+        let request_charged = Arc::new(AtomicBool::new(false));
+        let request_charged_writer = request_charged.clone();
+        let user_metadata: Arc<Mutex<HashMap<String, String>>> = Default::default();
+        let user_metadata_writer = user_metadata.clone();
+        let content_type: Arc<Mutex<Option<String>>> = Default::default();
+        let content_type_writer = content_type.clone();
+        let content_headers: Arc<Mutex<ContentHeaders>> = Default::default();
+        let content_headers_writer = content_headers.clone();
+        let expiration: Arc<Mutex<Option<ObjectExpiration>>> = Default::default();
+        let expiration_writer = expiration.clone();
+        let last_modified: Arc<Mutex<Option<OffsetDateTime>>> = Default::default();
+        let last_modified_writer = last_modified.clone();
+        let response_metadata: Arc<Mutex<Option<ResponseMetadata>>> = Default::default();
+        let response_metadata_writer = response_metadata.clone();
+        let content_range: Arc<Mutex<Option<ContentRange>>> = Default::default();
+        let content_range_writer = content_range.clone();
+        let parts_count: Arc<Mutex<Option<u32>>> = Default::default();
+        let parts_count_writer = parts_count.clone();
+        let checksum: Arc<Mutex<Option<Checksum>>> = Default::default();
+        let checksum_writer = checksum.clone();
+        let redirect_location: Arc<Mutex<Option<String>>> = Default::default();
+        let redirect_location_writer = redirect_location.clone();
+        let follow_redirects = redirects.is_some();
+        // Cancel the request as soon as the client's downloads pass its egress budget
+        let budget_exceeded = Arc::new(AtomicBool::new(false));
+        let budget_exceeded_finish = budget_exceeded.clone();
+        // Only warn about aws-chunked framing once, even if the CRT reports headers more than once
+        let mut warned_aws_chunked = false;
+        let cancel_handle: Arc<CancelHandle> = Default::default();
+        let cancel_handle_body = cancel_handle.clone();
+        let cancel_handle_finish = cancel_handle.clone();
 
         let request = self.inner.make_meta_request(
             message,
-            MetaRequestType::GetObject,
+            meta_request_type,
             span,
-            |_, _| (),
+            move |headers, status| {
+                request_charged_writer.store(parse_request_charged(headers), Ordering::SeqCst);
+                *user_metadata_writer.lock().unwrap() = parse_user_metadata(headers);
+                *content_type_writer.lock().unwrap() = parse_content_type(headers);
+                *content_headers_writer.lock().unwrap() = parse_content_headers(headers);
+                *expiration_writer.lock().unwrap() = parse_expiration(headers);
+                *last_modified_writer.lock().unwrap() = headers
+                    .get("Last-Modified")
+                    .ok()
+                    .and_then(|header| parse_http_date(&header.value().to_string_lossy()).ok());
+                *response_metadata_writer.lock().unwrap() = Some(ResponseMetadata::new(status));
+                // Only partial responses describe the range they hold
+                if status == 206 {
+                    *content_range_writer.lock().unwrap() = headers
+                        .get("Content-Range")
+                        .ok()
+                        .and_then(|header| parse_content_range(&header.value().to_string_lossy()));
+                }
+                *parts_count_writer.lock().unwrap() = headers
+                    .get("x-amz-mp-parts-count")
+                    .ok()
+                    .and_then(|header| header.value().to_str()?.parse().ok());
+                if checksum_mode {
+                    *checksum_writer.lock().unwrap() = HeadObjectResult::parse_checksum(headers)
+                        .ok()
+                        .filter(|checksum| checksum.algorithm_and_value().is_some());
+                }
+                let encodings = parse_body_encodings(headers);
+                if decode_content_encoding {
+                    *decoder_headers.lock().unwrap() = BodyDecoder::new(encodings, body_offset);
+                } else if encodings.aws_chunked && !warned_aws_chunked {
+                    warned_aws_chunked = true;
+                    warn!(
+                        "object is stored with aws-chunked framing, which is only removed with decode_content_encoding"
+                    );
+                }
+            },
             move |offset, data| {
-                let _ = sender.unbounded_send(Ok((offset, data.into())));
+                let total = egress_bytes.fetch_add(data.len() as u64, Ordering::SeqCst) + data.len() as u64;
+                if budget_exceeded.load(Ordering::SeqCst) {
+                    return;
+                }
+                if egress_byte_budget.is_some_and(|budget| total > budget) {
+                    budget_exceeded.store(true, Ordering::SeqCst);
+                    cancel_handle_body.cancel();
+                    return;
+                }
+                let part = match decoder.lock().unwrap().as_mut() {
+                    Some(decoder) => decoder.decode(data).map_err(S3RequestError::ContentDecodingError),
+                    None => Ok(Some((body_offset + offset, data.into()))),
+                };
+                if let Some(read_window) = &read_window_body {
+                    let decoded = match &part {
+                        Ok(Some((_, decoded))) => decoded.len(),
+                        _ => 0,
+                    };
+                    read_window.decoded(data.len(), decoded);
+                }
+                if let Some(part) = part.transpose() {
+                    let _ = sender.unbounded_send(part);
+                }
             },
             move |result| {
+                cancel_handle_finish.finish();
+                if let Some(budget) = egress_byte_budget.filter(|_| budget_exceeded_finish.load(Ordering::SeqCst)) {
+                    return Err(Some(ObjectClientError::ClientError(
+                        S3RequestError::EgressBudgetExceeded { budget },
+                    )));
+                }
                 if result.is_err() {
-                    Err(parse_get_object_error(result).map(ObjectClientError::ServiceError))
-                } else {
-                    Ok(())
+                    if follow_redirects {
+                        *redirect_location_writer.lock().unwrap() = parse_redirect_location(result);
+                    }
+                    return Err(parse_get_object_error(result).map(ObjectClientError::ServiceError));
                 }
+                if let Some(decoder) = decoder_finish.lock().unwrap().as_mut() {
+                    // The end of the body might still hold decoded bytes
+                    let part = decoder
+                        .finish()
+                        .map_err(|e| Some(ObjectClientError::ClientError(S3RequestError::ContentDecodingError(e))))?;
+                    if let Some(part) = part {
+                        if let Some(read_window) = &read_window_finish {
+                            read_window.decoded(0, part.1.len());
+                        }
+                        let _ = finish_sender.unbounded_send(Ok(part));
+                    }
+                }
+                Ok(())
             },
         )?;
+        cancel_handle.set(&request.meta_request);
+        if let Some(read_window) = &read_window {
+            read_window.set(&request.meta_request);
+        }
 
         Ok(S3GetObjectRequest {
             request,
+            read_window,
             finish_receiver: receiver,
             finished: false,
+            request_charged,
+            user_metadata,
+            content_type,
+            content_headers,
+            expiration,
+            last_modified,
+            response_metadata,
+            content_range,
+            parts_count,
+            checksum,
+            redirects,
+            redirect_location,
+            body_received: false,
         })
     }
 }
 
+/// What a GetObject request needs to follow a redirect, if the client follows them.
+#[derive(Debug)]
+struct GetObjectRedirects {
+    client: S3CrtClient,
+    bucket: String,
+    key: String,
+    params: GetObjectParams,
+    /// The scheme of the URI the request was sent to, to resolve the redirect's location against
+    scheme: String,
+    /// The authority of the URI the request was sent to, to resolve the redirect's location against
+    authority: String,
+    /// How many redirects were followed to get to this request
+    followed: u32,
+}
+
+/// Where to send a GetObject request that follows a redirect.
+#[derive(Debug, PartialEq, Eq)]
+struct RedirectTarget {
+    /// The scheme and authority of the new endpoint, like `https://example.com:8443`
+    endpoint: String,
+    /// The authority of the new endpoint, for the `Host` header
+    authority: String,
+    /// The path and query to request from the new endpoint, already URL-encoded
+    path_and_query: String,
+}
+
+/// Get the `Location` of a redirect response, if the request failed with one.
+fn parse_redirect_location(result: &MetaRequestResult) -> Option<String> {
+    if !matches!(result.response_status, 301 | 302 | 303 | 307 | 308) {
+        return None;
+    }
+    let headers = result.error_response_headers.as_ref()?;
+    let location = headers.get("Location").ok()?;
+    location.value().to_str().map(str::to_owned)
+}
+
+/// Resolve a redirect's `Location` against the scheme and authority of the request that was
+/// redirected. Returns `None` for locations that shouldn't be followed: ones that aren't HTTP(S)
+/// URIs or absolute paths, ones that would downgrade an HTTPS request to plain HTTP, and ones on
+/// hosts other than the redirected request's host or one of `bucket`'s S3 endpoints (see
+/// [is_bucket_s3_endpoint]), since the request is signed again for the new host.
+fn resolve_redirect_target(scheme: &str, authority: &str, bucket: &str, location: &str) -> Option<RedirectTarget> {
+    let (new_scheme, rest) = if let Some(rest) = location.strip_prefix("//") {
+        (scheme, Some(rest))
+    } else if location.starts_with('/') {
+        (scheme, None)
+    } else if let Some((new_scheme, rest)) = location.split_once("://") {
+        (new_scheme, Some(rest))
+    } else {
+        return None;
+    };
+    let new_scheme = new_scheme.to_ascii_lowercase();
+    if !matches!(new_scheme.as_str(), "http" | "https")
+        || (scheme.eq_ignore_ascii_case("https") && new_scheme == "http")
+    {
+        return None;
+    }
+    let (new_authority, path_and_query) = match rest {
+        Some(rest) => rest.split_at(rest.find(['/', '?']).unwrap_or(rest.len())),
+        None => (authority, location),
+    };
+    // Credentials in the authority could also hide the real host behind a trusted-looking one
+    if new_authority.is_empty() || new_authority.contains('@') {
+        return None;
+    }
+    let new_host = authority_host(new_authority);
+    if !new_host.eq_ignore_ascii_case(authority_host(authority)) && !is_bucket_s3_endpoint(bucket, new_host) {
+        return None;
+    }
+    let authority = new_authority;
+    let path_and_query = match path_and_query {
+        "" => "/".to_owned(),
+        path if path.starts_with('?') => format!("/{path}"),
+        path => path.to_owned(),
+    };
+    Some(RedirectTarget {
+        endpoint: format!("{new_scheme}://{authority}"),
+        authority: authority.to_owned(),
+        path_and_query,
+    })
+}
+
+/// The host of a URI authority, without its port.
+fn authority_host(authority: &str) -> &str {
+    if authority.starts_with('[') {
+        // An IPv6 address, which has colons of its own
+        authority.find(']').map_or(authority, |end| &authority[..=end])
+    } else {
+        authority.split_once(':').map_or(authority, |(host, _)| host)
+    }
+}
+
+/// Whether `host` is one of the virtual-hosted S3 endpoints of `bucket`, like
+/// `bucket.s3.us-west-2.amazonaws.com` or `bucket.s3.dualstack.us-west-2.amazonaws.com`, which S3
+/// may redirect requests for the bucket to.
+fn is_bucket_s3_endpoint(bucket: &str, host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    let Some(endpoint) = host
+        .strip_prefix(&bucket.to_ascii_lowercase())
+        .and_then(|host| host.strip_prefix(".s3."))
+    else {
+        return false;
+    };
+    let Some(region) = endpoint
+        .strip_suffix("amazonaws.com")
+        .or_else(|| endpoint.strip_suffix("amazonaws.com.cn"))
+    else {
+        return false;
+    };
+    let region = region.strip_prefix("dualstack.").unwrap_or(region);
+    // Bucket names can have dots, so make sure the rest isn't another bucket's website endpoint,
+    // like `my.s3.bucket.s3-website-us-west-2.amazonaws.com`
+    match region.strip_suffix('.') {
+        None => region.is_empty(),
+        Some(region) => {
+            !region.is_empty()
+                && !region.starts_with("s3")
+                && region.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-')
+        }
+    }
+}
+
 /// A streaming response to a GetObject request.
 ///
 /// This struct implements [`futures::Stream`], which you can use to read the body of the object.
@@ -96,61 +520,577 @@ impl S3CrtClient {
 pub struct S3GetObjectRequest {
     #[pin]
     request: S3HttpRequest<(), GetObjectError>,
+    /// `None` unless the request decodes the body
+    read_window: Option<Arc<DecodedReadWindow>>,
     #[pin]
-    finish_receiver: UnboundedReceiver<Result<GetBodyPart, Error>>,
+    finish_receiver: UnboundedReceiver<Result<GetBodyPart, S3RequestError>>,
     finished: bool,
+    request_charged: Arc<AtomicBool>,
+    user_metadata: Arc<Mutex<HashMap<String, String>>>,
+    content_type: Arc<Mutex<Option<String>>>,
+    content_headers: Arc<Mutex<ContentHeaders>>,
+    expiration: Arc<Mutex<Option<ObjectExpiration>>>,
+    last_modified: Arc<Mutex<Option<OffsetDateTime>>>,
+    response_metadata: Arc<Mutex<Option<ResponseMetadata>>>,
+    content_range: Arc<Mutex<Option<ContentRange>>>,
+    parts_count: Arc<Mutex<Option<u32>>>,
+    checksum: Arc<Mutex<Option<Checksum>>>,
+    /// `None` if the client doesn't follow redirects
+    redirects: Option<Box<GetObjectRedirects>>,
+    redirect_location: Arc<Mutex<Option<String>>>,
+    body_received: bool,
+}
+
+impl S3GetObjectRequest {
+    /// User-defined metadata of the object, from the `x-amz-meta-*` headers with the prefix
+    /// stripped. Only meaningful once the stream has returned its first body part.
+    pub fn user_metadata(&self) -> HashMap<String, String> {
+        self.user_metadata.lock().unwrap().clone()
+    }
+
+    /// The media type of the object, from the `Content-Type` header, if S3 returned one. Only
+    /// meaningful once the stream has returned its first body part.
+    pub fn content_type(&self) -> Option<String> {
+        self.content_type.lock().unwrap().clone()
+    }
+
+    /// The other standard headers describing how to present the object. `content_encoding` is the
+    /// encoding S3 stored the object with, even if the client decodes the body (see
+    /// [`GetObjectParams::decode_content_encoding`]). Only meaningful once the stream has returned
+    /// its first body part.
+    pub fn content_headers(&self) -> ContentHeaders {
+        self.content_headers.lock().unwrap().clone()
+    }
+
+    /// When a lifecycle rule of the bucket will expire the object, from the `x-amz-expiration`
+    /// header. `None` if no rule applies to the object, or if the header is malformed. Only
+    /// meaningful once the stream has returned its first body part.
+    pub fn expiration(&self) -> Option<ObjectExpiration> {
+        self.expiration.lock().unwrap().clone()
+    }
+
+    /// The time the object was last modified, from the `Last-Modified` header. `None` if the header
+    /// is missing or malformed. Only meaningful once the stream has returned its first body part.
+    pub fn last_modified(&self) -> Option<OffsetDateTime> {
+        *self.last_modified.lock().unwrap()
+    }
+
+    /// The range of the object that this response holds, from the `Content-Range` header of a
+    /// partial (206) response to a ranged request. `None` if S3 returned the whole object (200), or
+    /// if the header is missing or malformed. Only meaningful once the stream has returned its
+    /// first body part.
+    pub fn content_range(&self) -> Option<ContentRange> {
+        *self.content_range.lock().unwrap()
+    }
+
+    /// The number of parts the object was uploaded in, from the `x-amz-mp-parts-count` header. S3
+    /// only returns it for a request for a single part (see [`GetObjectParams::part_number`]) of
+    /// an object uploaded with a multipart upload. Only meaningful once the stream has returned
+    /// its first body part.
+    pub fn parts_count(&self) -> Option<u32> {
+        *self.parts_count.lock().unwrap()
+    }
+
+    /// The checksum of the object, from the `x-amz-checksum-*` headers, if S3 returned one. S3
+    /// only returns it if [`GetObjectParams::checksum_mode`] or
+    /// [`S3ClientConfig::checksum_mode`](crate::config::S3ClientConfig::checksum_mode) is set, and
+    /// only for objects uploaded with a checksum. For a ranged read this is still the checksum of
+    /// the whole object, not of the returned range, so it can't be used to validate the range on
+    /// its own; S3 may also leave it out of responses to ranged reads. Only meaningful once the
+    /// stream has returned its first body part.
+    pub fn checksum(&self) -> Option<Checksum> {
+        self.checksum.lock().unwrap().clone()
+    }
+
+    /// A handle that can cancel this request, for example from another task that isn't polling
+    /// the body. If the request follows a redirect (see
+    /// [`S3ClientConfig::max_get_redirects`](crate::config::S3ClientConfig::max_get_redirects)),
+    /// the handle can't cancel the request to the redirect's location, so only take it once the
+    /// stream has returned its first body part.
+    pub fn handle(&self) -> RequestHandle {
+        self.request.handle()
+    }
 }
 
 impl GetObjectRequest for S3GetObjectRequest {
     type ClientError = S3RequestError;
 
     fn increment_read_window(mut self: Pin<&mut Self>, len: usize) {
-        self.request.meta_request.increment_read_window(len as u64);
+        match &self.read_window {
+            Some(read_window) => read_window.increment(len),
+            None => self.request.meta_request.increment_read_window(len as u64),
+        }
+    }
+
+    fn response_metadata(&self) -> Option<ResponseMetadata> {
+        self.response_metadata.lock().unwrap().clone()
+    }
+
+    fn request_charged(&self) -> bool {
+        self.request_charged.load(Ordering::SeqCst)
+    }
+}
+
+impl S3GetObjectRequest {
+    /// Start the request for the redirect this request failed with, if it should follow it.
+    fn follow_redirect(&self) -> Option<ObjectClientResult<S3GetObjectRequest, GetObjectError, S3RequestError>> {
+        let redirects = self.redirects.as_ref()?;
+        if self.body_received {
+            return None;
+        }
+        let location = self.redirect_location.lock().unwrap().take()?;
+        let target = resolve_redirect_target(&redirects.scheme, &redirects.authority, &redirects.bucket, &location)?;
+        let max_redirects = redirects.client.inner.max_get_redirects;
+        if redirects.followed >= max_redirects {
+            return Some(Err(ObjectClientError::ClientError(S3RequestError::TooManyRedirects {
+                max_redirects,
+            })));
+        }
+        debug!(%location, "following redirect");
+        Some(redirects.client.make_get_object_request(
+            &redirects.bucket,
+            &redirects.key,
+            &redirects.params,
+            Some(&target),
+            redirects.followed + 1,
+        ))
     }
 }
 
 impl Stream for S3GetObjectRequest {
     type Item = ObjectClientResult<GetBodyPart, GetObjectError, S3RequestError>;
 
-    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        if self.finished {
-            return Poll::Ready(None);
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        loop {
+            if self.finished {
+                return Poll::Ready(None);
+            }
+
+            let this = self.as_mut().project();
+
+            if let Poll::Ready(Some(val)) = this.finish_receiver.poll_next(cx) {
+                *this.body_received |= val.is_ok();
+                return Poll::Ready(Some(val.map_err(ObjectClientError::ClientError)));
+            }
+
+            match this.request.poll(cx) {
+                Poll::Ready(Ok(_)) => {
+                    *this.finished = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Ready(Err(e)) => {
+                    let e = match self.follow_redirect() {
+                        // Replace this request with the one to the redirect's location, and poll
+                        // that instead
+                        Some(Ok(request)) => {
+                            self.set(request);
+                            continue;
+                        }
+                        Some(Err(redirect_error)) => redirect_error,
+                        None => e,
+                    };
+                    *self.as_mut().project().finished = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// The range of an object held by a partial GetObject response, from its `Content-Range` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ContentRange {
+    /// The offset of the first byte of the range
+    pub start: u64,
+
+    /// The offset of the last byte of the range, inclusive
+    pub end: u64,
+
+    /// The size of the whole object, or `None` if the server reported it as unknown (`*`)
+    pub total: Option<u64>,
+}
+
+/// Parse a `Content-Range` header value of the form `bytes <start>-<end>/<total>`, where the total
+/// may be `*`. Unsatisfied ranges (`bytes */<total>`) aren't ranges of the body, so are `None`.
+fn parse_content_range(value: &str) -> Option<ContentRange> {
+    let (unit, range) = value.trim().split_once(' ')?;
+    if !unit.eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+    let (range, total) = range.trim().split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    let start = start.parse().ok()?;
+    let end = end.parse().ok()?;
+    if end < start {
+        return None;
+    }
+    let total = match total {
+        "*" => None,
+        total => {
+            let total = total.parse().ok()?;
+            if end >= total {
+                return None;
+            }
+            Some(total)
+        }
+    };
+    Some(ContentRange { start, end, total })
+}
+
+/// The content encodings of a response's body that the client can decode, according to its
+/// `Content-Encoding` header.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct BodyEncodings {
+    /// The body has the framing of the `aws-chunked` encoding
+    aws_chunked: bool,
+    /// The body is gzip-encoded, once any `aws-chunked` framing is removed
+    gzip: bool,
+}
+
+fn parse_body_encodings(headers: &Headers) -> BodyEncodings {
+    let Ok(header) = headers.get("Content-Encoding") else {
+        return BodyEncodings::default();
+    };
+    let value = header.value().to_string_lossy();
+    let mut encodings: Vec<&str> = value.split(',').map(str::trim).filter(|e| !e.is_empty()).collect();
+    // S3 strips `aws-chunked` from the encodings of objects uploaded with it, so it's only here
+    // if the object was stored with its framing intact, like by some S3-compatible services
+    let aws_chunked = match encodings.iter().position(|e| e.eq_ignore_ascii_case("aws-chunked")) {
+        Some(index) => {
+            encodings.remove(index);
+            true
+        }
+        None => false,
+    };
+    // Only decode gzip if it's the only other encoding; we can't undo any others applied after it
+    let gzip =
+        matches!(encodings.as_slice(), [e] if e.eq_ignore_ascii_case("gzip") || e.eq_ignore_ascii_case("x-gzip"));
+    BodyEncodings { aws_chunked, gzip }
+}
+
+/// Decodes the content encodings of a body as its parts arrive. The CRT delivers the parts of a
+/// GetObject response in order, so the decoded parts are given contiguous offsets in the decoded
+/// body.
+#[derive(Debug)]
+struct BodyDecoder {
+    aws_chunked: Option<AwsChunkedDecoder>,
+    gzip: Option<GzDecoder<Vec<u8>>>,
+    /// The decoded bytes that are ready, if the body isn't gzip-encoded
+    decoded: Vec<u8>,
+    offset: u64,
+    failed: bool,
+}
+
+impl BodyDecoder {
+    /// A decoder for a body with the given encodings, whose first decoded byte is at `offset`, or
+    /// `None` if there's nothing to decode.
+    fn new(encodings: BodyEncodings, offset: u64) -> Option<Self> {
+        if !encodings.aws_chunked && !encodings.gzip {
+            return None;
+        }
+        Some(Self {
+            aws_chunked: encodings.aws_chunked.then(AwsChunkedDecoder::new),
+            gzip: encodings.gzip.then(|| GzDecoder::new(Vec::new())),
+            decoded: Vec::new(),
+            offset,
+            failed: false,
+        })
+    }
+
+    /// Decode the next part of the body, returning the decoded bytes that are ready. After an
+    /// error, the rest of the body is ignored.
+    fn decode(&mut self, data: &[u8]) -> std::io::Result<Option<GetBodyPart>> {
+        if self.failed {
+            return Ok(None);
+        }
+        if let Err(e) = self.write(data) {
+            self.failed = true;
+            return Err(e);
+        }
+        Ok(self.take_part())
+    }
+
+    /// Check that the body ended with complete framing and a complete gzip stream, returning any
+    /// remaining decoded bytes.
+    fn finish(&mut self) -> std::io::Result<Option<GetBodyPart>> {
+        if self.failed {
+            return Ok(None);
+        }
+        if let Some(aws_chunked) = &self.aws_chunked {
+            aws_chunked.finish()?;
+        }
+        if let Some(gzip) = &mut self.gzip {
+            gzip.try_finish()?;
+        }
+        Ok(self.take_part())
+    }
+
+    fn write(&mut self, data: &[u8]) -> std::io::Result<()> {
+        match (&mut self.aws_chunked, &mut self.gzip) {
+            (Some(aws_chunked), Some(gzip)) => {
+                let mut unframed = Vec::new();
+                aws_chunked.decode(data, &mut unframed)?;
+                gzip.write_all(&unframed)
+            }
+            (Some(aws_chunked), None) => aws_chunked.decode(data, &mut self.decoded),
+            (None, Some(gzip)) => gzip.write_all(data),
+            (None, None) => {
+                self.decoded.extend_from_slice(data);
+                Ok(())
+            }
+        }
+    }
+
+    fn take_part(&mut self) -> Option<GetBodyPart> {
+        let data = match &mut self.gzip {
+            Some(gzip) => std::mem::take(gzip.get_mut()),
+            None => std::mem::take(&mut self.decoded),
+        };
+        if data.is_empty() {
+            return None;
         }
+        let offset = self.offset;
+        self.offset += data.len() as u64;
+        Some((offset, data.into_boxed_slice()))
+    }
+}
 
-        let this = self.project();
+/// The read window of a request that decodes its body. The CRT counts the window in bytes of the
+/// encoded body it receives, while callers grow it by the length of the decoded parts they
+/// consume, so this converts between the two: each part of the body the CRT delivers is owed its
+/// encoded length, less the decoded length the caller will grow the window by once it consumes
+/// the part. Bytes owed are granted right away, so that a gzip decoder holding on to input can't
+/// stall the request.
+#[derive(Debug, Default)]
+struct DecodedReadWindow(Mutex<DecodedReadWindowState>);
 
-        if let Poll::Ready(Some(val)) = this.finish_receiver.poll_next(cx) {
-            return Poll::Ready(Some(val.map_err(|e| ObjectClientError::ClientError(e.into()))));
+#[derive(Debug, Default)]
+struct DecodedReadWindowState {
+    /// `None` until the meta request has been created
+    meta_request: Option<MetaRequest>,
+    balance: ReadWindowBalance,
+}
+
+impl DecodedReadWindow {
+    fn set(&self, meta_request: &MetaRequest) {
+        let mut state = self.0.lock().unwrap();
+        state.meta_request = Some(meta_request.clone());
+        state.grant();
+    }
+
+    /// Account for a part of the body of `encoded` bytes that decoded to `decoded` bytes.
+    fn decoded(&self, encoded: usize, decoded: usize) {
+        let mut state = self.0.lock().unwrap();
+        state.balance.add(encoded as i64 - decoded as i64);
+        state.grant();
+    }
+
+    /// Grow the window by `len` decoded bytes that the caller consumed.
+    fn increment(&self, len: usize) {
+        let mut state = self.0.lock().unwrap();
+        state.balance.add(len as i64);
+        state.grant();
+    }
+}
+
+impl DecodedReadWindowState {
+    fn grant(&mut self) {
+        if let Some(meta_request) = &mut self.meta_request {
+            if let Some(bytes) = self.balance.take() {
+                meta_request.increment_read_window(bytes);
+            }
         }
+    }
+}
 
-        match this.request.poll(cx) {
-            Poll::Ready(Ok(_)) => {
-                *this.finished = true;
-                Poll::Ready(None)
+/// Bytes owed to the CRT's read window, or granted ahead of the caller if negative, as when a
+/// gzip-encoded part decodes to more bytes than it held.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ReadWindowBalance(i64);
+
+impl ReadWindowBalance {
+    fn add(&mut self, bytes: i64) {
+        self.0 += bytes;
+    }
+
+    /// Take the bytes owed, if there are any.
+    fn take(&mut self) -> Option<u64> {
+        if self.0 <= 0 {
+            return None;
+        }
+        Some(std::mem::take(&mut self.0) as u64)
+    }
+}
+
+/// The longest line of `aws-chunked` framing we accept. Size lines hold a hex size and maybe a
+/// signature, and trailer lines a checksum, so they're all far shorter than this.
+const MAX_AWS_CHUNKED_LINE_LENGTH: usize = 4096;
+
+/// Removes the framing of the `aws-chunked` content encoding from a body as its parts arrive.
+///
+/// Each chunk is a line with its size in hex and optional extensions like `chunk-signature`, then
+/// that many bytes of data and a CRLF. A chunk of size zero ends the body, and is followed by
+/// optional trailer lines, like a trailing checksum, and an empty line. The framing is checked
+/// strictly, so a body that isn't actually `aws-chunked` fails rather than being returned mangled.
+#[derive(Debug)]
+struct AwsChunkedDecoder {
+    state: AwsChunkedState,
+    /// The part of the current line received so far
+    line: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AwsChunkedState {
+    /// Reading the size line of a chunk
+    Size,
+    /// Reading the data of a chunk, with this many bytes left
+    Data(u64),
+    /// Reading the CRLF after the data of a chunk
+    DataEnd,
+    /// Reading the trailer lines after the last chunk
+    Trailers,
+    /// Read the empty line that ends the body
+    Done,
+}
+
+impl AwsChunkedDecoder {
+    fn new() -> Self {
+        Self {
+            state: AwsChunkedState::Size,
+            line: Vec::new(),
+        }
+    }
+
+    /// Remove the framing from the next part of the body, appending the data it holds to `output`.
+    fn decode(&mut self, mut data: &[u8], output: &mut Vec<u8>) -> std::io::Result<()> {
+        while !data.is_empty() {
+            match self.state {
+                AwsChunkedState::Data(remaining) => {
+                    let len = remaining.min(data.len() as u64) as usize;
+                    output.extend_from_slice(&data[..len]);
+                    data = &data[len..];
+                    self.state = match remaining - len as u64 {
+                        0 => AwsChunkedState::DataEnd,
+                        remaining => AwsChunkedState::Data(remaining),
+                    };
+                }
+                AwsChunkedState::Done => return Err(invalid_aws_chunked("data after the end of the body")),
+                _ => {
+                    // The other states read a line at a time, which might span several parts
+                    let end = data.iter().position(|b| *b == b'\n').map_or(data.len(), |i| i + 1);
+                    if self.line.len() + end > MAX_AWS_CHUNKED_LINE_LENGTH {
+                        return Err(invalid_aws_chunked("line is too long"));
+                    }
+                    self.line.extend_from_slice(&data[..end]);
+                    data = &data[end..];
+                    if self.line.ends_with(b"\n") {
+                        let line = std::mem::take(&mut self.line);
+                        let line = line
+                            .strip_suffix(b"\r\n")
+                            .ok_or_else(|| invalid_aws_chunked("line doesn't end with CRLF"))?;
+                        self.state = self.next_state(line)?;
+                    }
+                }
             }
-            Poll::Ready(Err(e)) => {
-                *this.finished = true;
-                Poll::Ready(Some(Err(e)))
+        }
+        Ok(())
+    }
+
+    /// The state after a complete line, without its CRLF, in the current state.
+    fn next_state(&self, line: &[u8]) -> std::io::Result<AwsChunkedState> {
+        match self.state {
+            AwsChunkedState::Size => {
+                let size = line.split(|b| *b == b';').next().unwrap_or_default();
+                let size = std::str::from_utf8(size)
+                    .ok()
+                    .filter(|size| !size.is_empty() && size.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|size| u64::from_str_radix(size, 16).ok())
+                    .ok_or_else(|| invalid_aws_chunked("invalid chunk size"))?;
+                Ok(match size {
+                    0 => AwsChunkedState::Trailers,
+                    size => AwsChunkedState::Data(size),
+                })
             }
-            Poll::Pending => Poll::Pending,
+            AwsChunkedState::DataEnd if line.is_empty() => Ok(AwsChunkedState::Size),
+            AwsChunkedState::DataEnd => Err(invalid_aws_chunked("chunk is longer than its size")),
+            AwsChunkedState::Trailers if line.is_empty() => Ok(AwsChunkedState::Done),
+            AwsChunkedState::Trailers => Ok(AwsChunkedState::Trailers),
+            AwsChunkedState::Data(_) | AwsChunkedState::Done => unreachable!("only line states read lines"),
+        }
+    }
+
+    /// Check that the body ended after its last chunk.
+    fn finish(&self) -> std::io::Result<()> {
+        match self.state {
+            AwsChunkedState::Done => Ok(()),
+            // Some writers leave out the empty line after the last chunk when there are no trailers
+            AwsChunkedState::Trailers if self.line.is_empty() => Ok(()),
+            _ => Err(invalid_aws_chunked("body ended before its last chunk")),
         }
     }
 }
 
+fn invalid_aws_chunked(reason: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("invalid aws-chunked body: {reason}"),
+    )
+}
+
+/// Format a time as an HTTP-date in the preferred IMF-fixdate format of RFC 7231, e.g.
+/// `Sun, 06 Nov 1994 08:49:37 GMT`. This isn't RFC 3339 (which S3 uses in XML responses), and
+/// unlike RFC 2822 it always uses `GMT` rather than a numeric offset.
+fn format_http_date(time: OffsetDateTime) -> String {
+    let time = time.to_offset(UtcOffset::UTC);
+    // The English names of weekdays and months, abbreviated to three letters
+    let weekday = time.weekday().to_string();
+    let month = time.month().to_string();
+    format!(
+        "{}, {:02} {} {:04} {:02}:{:02}:{:02} GMT",
+        &weekday[..3],
+        time.day(),
+        &month[..3],
+        time.year(),
+        time.hour(),
+        time.minute(),
+        time.second(),
+    )
+}
+
 fn parse_get_object_error(result: &MetaRequestResult) -> Option<GetObjectError> {
     match result.response_status {
         404 => {
             let body = result.error_response_body.as_ref()?;
-            let root = xmltree::Element::parse(body.as_bytes()).ok()?;
+            let root = parse_xml(body.as_bytes()).ok()?;
             let error_code = root.get_child("Code")?;
             let error_str = error_code.get_text()?;
             match error_str.deref() {
                 "NoSuchBucket" => Some(GetObjectError::NoSuchBucket),
-                "NoSuchKey" => Some(GetObjectError::NoSuchKey),
+                // S3 reports a missing version of an existing key as NoSuchVersion
+                "NoSuchKey" | "NoSuchVersion" => Some(GetObjectError::NoSuchKey),
                 _ => None,
             }
         }
+        403 => {
+            let body = result.error_response_body.as_ref()?;
+            let root = parse_xml(body.as_bytes()).ok()?;
+            let error_code = root.get_child("Code")?;
+            let error_str = error_code.get_text()?;
+            if error_str != "InvalidObjectState" {
+                return None;
+            }
+            // S3 includes the storage class, and the access tier for Intelligent-Tiering objects
+            let get_text = |name: &str| Some(root.get_child(name)?.get_text()?.into_owned());
+            Some(GetObjectError::InvalidObjectState {
+                storage_class: get_text("StorageClass"),
+                access_tier: get_text("AccessTier"),
+            })
+        }
+        304 => Some(GetObjectError::NotModified),
         412 => Some(GetObjectError::PreconditionFailed),
+        416 => Some(GetObjectError::InvalidRange),
         _ => None,
     }
 }
@@ -159,6 +1099,9 @@ fn parse_get_object_error(result: &MetaRequestResult) -> Option<GetObjectError>
 mod tests {
     use std::ffi::{OsStr, OsString};
 
+    use mountpoint_s3_crt::common::allocator::Allocator;
+    use test_case::test_case;
+
     use super::*;
 
     fn make_result(response_status: i32, body: impl Into<OsString>) -> MetaRequestResult {
@@ -167,9 +1110,24 @@ mod tests {
             crt_error: 1i32.into(),
             error_response_headers: None,
             error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
         }
     }
 
+    #[test_case("bytes 0-99/1000", Some((0, 99, Some(1000))); "with total")]
+    #[test_case("bytes 500-999/*", Some((500, 999, None)); "unknown total")]
+    #[test_case(" BYTES 0-0/1 ", Some((0, 0, Some(1))); "case and whitespace")]
+    #[test_case("bytes */1000", None; "unsatisfied range")]
+    #[test_case("bytes 10-5/100", None; "end before start")]
+    #[test_case("bytes 0-100/100", None; "end past total")]
+    #[test_case("items 0-99/1000", None; "other unit")]
+    #[test_case("bytes 0-99", None; "missing total")]
+    fn parse_content_range_header(value: &str, expected: Option<(u64, u64, Option<u64>)>) {
+        let range = parse_content_range(value).map(|r| (r.start, r.end, r.total));
+        assert_eq!(range, expected);
+    }
+
     #[test]
     fn parse_404_no_such_key() {
         let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NoSuchKey</Code><Message>The specified key does not exist.</Message><Key>not-a-real-key</Key><RequestId>NTKJWKHQBYNS73A9</RequestId><HostId>Nc9kWNrf4kGoq5NIUnQ4t7u04ZZXGm/i463v+jwCI8sIrZBqeYI8uffLHQ+/qusdMWNuUwqeXHU=</HostId></Error>"#;
@@ -178,6 +1136,14 @@ mod tests {
         assert_eq!(result, Some(GetObjectError::NoSuchKey));
     }
 
+    #[test]
+    fn parse_404_no_such_version() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NoSuchVersion</Code><Message>The specified version does not exist.</Message><Key>hello</Key><VersionId>3HL4kqtJlcpXroDTDmJ.rmSpXd3dIbrHY</VersionId><RequestId>NTKJWKHQBYNS73A9</RequestId></Error>"#;
+        let result = make_result(404, OsStr::from_bytes(&body[..]));
+        let result = parse_get_object_error(&result);
+        assert_eq!(result, Some(GetObjectError::NoSuchKey));
+    }
+
     #[test]
     fn parse_404_no_such_bucket() {
         let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NoSuchBucket</Code><Message>The specified bucket does not exist</Message><BucketName>DOC-EXAMPLE-BUCKET</BucketName><RequestId>4VAGDP5HMYTDNB3Y</RequestId><HostId>JMgGqpVKIaaTieG68IODiV2piWw/q9VCTowGvWP36BEz6oIVEXiesn8cDE5ph7if0gpY5WU1Wc8=</HostId></Error>"#;
@@ -186,11 +1152,334 @@ mod tests {
         assert_eq!(result, Some(GetObjectError::NoSuchBucket));
     }
 
+    #[test]
+    fn parse_304_not_modified() {
+        // S3 doesn't send a body with 304 responses
+        let result = MetaRequestResult {
+            error_response_body: None,
+            ..make_result(304, "")
+        };
+        let result = parse_get_object_error(&result);
+        assert_eq!(result, Some(GetObjectError::NotModified));
+    }
+
+    #[test]
+    fn parse_412_precondition_failed() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>PreconditionFailed</Code><Message>At least one of the pre-conditions you specified did not hold</Message><Condition>If-Match</Condition><RequestId>TX3KXRW0T8VPHC8Q</RequestId><HostId>S6nZ1wGx2L2z1Ffa/sQ7sLEtXGmB7jr1IO3IabWZ6RLFBUMixW6g4Rgl8mdgL4+OXRamBC3f4yM=</HostId></Error>"#;
+        let result = make_result(412, OsStr::from_bytes(&body[..]));
+        let result = parse_get_object_error(&result);
+        assert_eq!(result, Some(GetObjectError::PreconditionFailed));
+    }
+
+    #[test]
+    fn parse_416_invalid_range() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InvalidRange</Code><Message>The requested range is not satisfiable</Message><RangeRequested>bytes=2000-2099</RangeRequested><ActualObjectSize>1000</ActualObjectSize><RequestId>TX3KXRW0T8VPHC8Q</RequestId><HostId>S6nZ1wGx2L2z1Ffa/sQ7sLEtXGmB7jr1IO3IabWZ6RLFBUMixW6g4Rgl8mdgL4+OXRamBC3f4yM=</HostId></Error>"#;
+        let result = make_result(416, OsStr::from_bytes(&body[..]));
+        let result = parse_get_object_error(&result);
+        assert_eq!(result, Some(GetObjectError::InvalidRange));
+    }
+
+    #[allow(clippy::reversed_empty_ranges)]
+    #[test]
+    fn get_object_empty_range() {
+        let client = S3CrtClient::new(Default::default()).expect("create test client");
+        for range in [10..10, 10..5] {
+            let params = GetObjectParams::new().range(Some(range));
+            let result = client.get_object("test-bucket", "test-key", &params);
+            assert!(matches!(
+                result,
+                Err(ObjectClientError::ServiceError(GetObjectError::InvalidRange))
+            ));
+        }
+    }
+
     #[test]
     fn parse_403_glacier_storage_class() {
-        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InvalidObjectState</Code><Message>The action is not valid for the object's storage class</Message><RequestId>9FEFFF118E15B86F</RequestId><HostId>WVQ5kzhiT+oiUfDCOiOYv8W4Tk9eNcxWi/MK+hTS/av34Xy4rBU3zsavf0aaaaa</HostId></Error>"#;
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InvalidObjectState</Code><Message>The action is not valid for the object's storage class</Message><StorageClass>GLACIER</StorageClass><RequestId>9FEFFF118E15B86F</RequestId><HostId>WVQ5kzhiT+oiUfDCOiOYv8W4Tk9eNcxWi/MK+hTS/av34Xy4rBU3zsavf0aaaaa</HostId></Error>"#;
+        let result = make_result(403, OsStr::from_bytes(&body[..]));
+        let result = parse_get_object_error(&result);
+        assert_eq!(
+            result,
+            Some(GetObjectError::InvalidObjectState {
+                storage_class: Some("GLACIER".to_owned()),
+                access_tier: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_403_intelligent_tiering_archive_access() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InvalidObjectState</Code><Message>The operation is not valid for the object's access tier</Message><StorageClass>INTELLIGENT_TIERING</StorageClass><AccessTier>ARCHIVE_ACCESS</AccessTier><RequestId>9FEFFF118E15B86F</RequestId><HostId>WVQ5kzhiT+oiUfDCOiOYv8W4Tk9eNcxWi/MK+hTS/av34Xy4rBU3zsavf0aaaaa</HostId></Error>"#;
+        let result = make_result(403, OsStr::from_bytes(&body[..]));
+        let result = parse_get_object_error(&result);
+        assert_eq!(
+            result,
+            Some(GetObjectError::InvalidObjectState {
+                storage_class: Some("INTELLIGENT_TIERING".to_owned()),
+                access_tier: Some("ARCHIVE_ACCESS".to_owned()),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_403_access_denied() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>AccessDenied</Code><Message>Access Denied</Message><RequestId>CM0R497NB0WAQ977</RequestId><HostId>w1tyPs4dibPL3WpJ3RvsvUVdx3VPsGGpQ9zprTHQYig3OOi5lPa3oeSHZVJsS45ujOcTbQP7Rqdh5tBJ+Kwygw==</HostId></Error>"#;
         let result = make_result(403, OsStr::from_bytes(&body[..]));
         let result = parse_get_object_error(&result);
         assert_eq!(result, None);
     }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn gzip_decoder() -> BodyDecoder {
+        BodyDecoder::new(
+            BodyEncodings {
+                aws_chunked: false,
+                gzip: true,
+            },
+            0,
+        )
+        .unwrap()
+    }
+
+    fn aws_chunked_decoder(gzip: bool) -> BodyDecoder {
+        BodyDecoder::new(
+            BodyEncodings {
+                aws_chunked: true,
+                gzip,
+            },
+            0,
+        )
+        .unwrap()
+    }
+
+    /// Frame data with the `aws-chunked` encoding, in chunks of the given size, with a signature
+    /// on each chunk and a trailing checksum.
+    fn aws_chunked(data: &[u8], chunk_size: usize) -> Vec<u8> {
+        let mut framed = Vec::new();
+        for chunk in data.chunks(chunk_size) {
+            framed.extend_from_slice(format!("{:x};chunk-signature=abcdef\r\n", chunk.len()).as_bytes());
+            framed.extend_from_slice(chunk);
+            framed.extend_from_slice(b"\r\n");
+        }
+        framed.extend_from_slice(b"0;chunk-signature=abcdef\r\nx-amz-checksum-crc32:AAAAAA==\r\n\r\n");
+        framed
+    }
+
+    fn decode_in_parts(decoder: &mut BodyDecoder, body: &[u8], part_size: usize) -> Vec<u8> {
+        let mut parts = Vec::new();
+        for chunk in body.chunks(part_size) {
+            parts.extend(decoder.decode(chunk).expect("valid body"));
+        }
+        parts.extend(decoder.finish().expect("complete body"));
+
+        let mut decoded = Vec::new();
+        for (offset, part) in parts {
+            assert_eq!(offset, decoded.len() as u64, "parts should be contiguous");
+            decoded.extend_from_slice(&part);
+        }
+        decoded
+    }
+
+    #[test]
+    fn read_window_balance() {
+        let mut balance = ReadWindowBalance::default();
+        // aws-chunked framing: the part held more bytes than it decoded to
+        balance.add(110 - 100);
+        assert_eq!(balance.take(), Some(10));
+        assert_eq!(balance.take(), None);
+        // gzip: the part decoded to more bytes than it held, which the caller's grant pays back
+        balance.add(100 - 400);
+        balance.add(250);
+        assert_eq!(balance.take(), None);
+        balance.add(150);
+        assert_eq!(balance.take(), Some(100));
+    }
+
+    #[test]
+    fn decode_gzip_body_in_parts() {
+        let data: Vec<u8> = (0..100_000u32).flat_map(|i| (i % 251).to_le_bytes()).collect();
+        let encoded = gzip(&data);
+
+        let mut decoder = gzip_decoder();
+        assert_eq!(decode_in_parts(&mut decoder, &encoded, 1000), data);
+    }
+
+    #[test]
+    fn decode_invalid_gzip_body() {
+        let mut decoder = gzip_decoder();
+        decoder
+            .decode(b"this is not gzip")
+            .expect_err("invalid gzip should fail");
+        // The rest of the body is ignored after a failure
+        assert!(decoder.decode(b"more").unwrap().is_none());
+        assert!(decoder.finish().unwrap().is_none());
+    }
+
+    #[test]
+    fn decode_truncated_gzip_body() {
+        let encoded = gzip(b"hello world");
+        let mut decoder = gzip_decoder();
+        decoder
+            .decode(&encoded[..encoded.len() - 4])
+            .expect("truncated gzip is valid so far");
+        decoder.finish().expect_err("truncated gzip should fail");
+    }
+
+    // Parts that split size lines, data, and CRLFs at every offset
+    #[test_case(1; "one byte parts")]
+    #[test_case(7; "small parts")]
+    #[test_case(100_000; "one part")]
+    fn decode_aws_chunked_body_in_parts(part_size: usize) {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let framed = aws_chunked(&data, 4096);
+        let mut decoder = aws_chunked_decoder(false);
+        assert_eq!(decode_in_parts(&mut decoder, &framed, part_size), data);
+    }
+
+    #[test]
+    fn decode_aws_chunked_gzip_body() {
+        let data: Vec<u8> = (0..100_000u32).flat_map(|i| (i % 251).to_le_bytes()).collect();
+        let framed = aws_chunked(&gzip(&data), 8192);
+        let mut decoder = aws_chunked_decoder(true);
+        assert_eq!(decode_in_parts(&mut decoder, &framed, 1000), data);
+    }
+
+    #[test]
+    fn decode_aws_chunked_body_without_trailers() {
+        let mut decoder = aws_chunked_decoder(false);
+        assert_eq!(decode_in_parts(&mut decoder, b"5\r\nhello\r\n0\r\n\r\n", 3), b"hello");
+        // Without trailers, the empty line after the last chunk is optional
+        let mut decoder = aws_chunked_decoder(false);
+        assert_eq!(decode_in_parts(&mut decoder, b"5\r\nhello\r\n0\r\n", 3), b"hello");
+    }
+
+    #[test_case(b"hello\r\nworld\r\n"; "not framed")]
+    #[test_case(b"5\r\nhello world\r\n0\r\n\r\n"; "chunk longer than its size")]
+    #[test_case(b"5\nhello\r\n0\r\n\r\n"; "missing CR")]
+    #[test_case(b"-5\r\nhello\r\n0\r\n\r\n"; "negative size")]
+    #[test_case(b"5\r\nhello\r\n0\r\n\r\nmore"; "data after the last chunk")]
+    fn decode_invalid_aws_chunked_body(body: &[u8]) {
+        let mut decoder = aws_chunked_decoder(false);
+        decoder.decode(body).expect_err("invalid framing should fail");
+        // The rest of the body is ignored after a failure
+        assert!(decoder.decode(b"more").unwrap().is_none());
+        assert!(decoder.finish().unwrap().is_none());
+    }
+
+    #[test_case(b"5\r\nhel"; "in a chunk")]
+    #[test_case(b"5\r\nhello\r\n"; "before the last chunk")]
+    #[test_case(b"5\r\nhello\r\n0\r\nx-amz-checksum-crc32:AAA"; "in the trailers")]
+    fn decode_truncated_aws_chunked_body(body: &[u8]) {
+        let mut decoder = aws_chunked_decoder(false);
+        decoder.decode(body).expect("truncated framing is valid so far");
+        decoder.finish().expect_err("truncated framing should fail");
+    }
+
+    #[test]
+    fn decode_aws_chunked_line_too_long() {
+        let mut decoder = aws_chunked_decoder(false);
+        let line = vec![b'0'; MAX_AWS_CHUNKED_LINE_LENGTH + 1];
+        decoder.decode(&line).expect_err("overlong line should fail");
+    }
+
+    #[test_case("gzip", false, true; "gzip")]
+    #[test_case("GZIP", false, true; "uppercase")]
+    #[test_case("x-gzip", false, true; "x-gzip")]
+    #[test_case("br", false, false; "other encoding")]
+    #[test_case("aws-chunked", true, false; "aws-chunked")]
+    #[test_case("aws-chunked,gzip", true, true; "aws-chunked and gzip")]
+    #[test_case("gzip, aws-chunked", true, true; "gzip and aws-chunked")]
+    #[test_case("aws-chunked, gzip, br", true, false; "aws-chunked and other encodings")]
+    #[test_case("gzip, br", false, false; "gzip and another encoding")]
+    fn content_encoding_header(value: &str, aws_chunked: bool, gzip: bool) {
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        headers.add_header(&Header::new("Content-Encoding", value)).unwrap();
+        assert_eq!(parse_body_encodings(&headers), BodyEncodings { aws_chunked, gzip });
+    }
+
+    #[test_case(307, Some("https://other.example.com/key") => Some("https://other.example.com/key".to_string()); "temporary redirect")]
+    #[test_case(301, Some("/key") => Some("/key".to_string()); "permanent redirect")]
+    #[test_case(301, None => None; "no location")]
+    #[test_case(404, Some("https://other.example.com/key") => None; "not a redirect")]
+    fn test_parse_redirect_location(response_status: i32, location: Option<&str>) -> Option<String> {
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        if let Some(location) = location {
+            headers.add_header(&Header::new("Location", location)).unwrap();
+        }
+        let result = MetaRequestResult {
+            error_response_headers: Some(headers),
+            ..make_result(response_status, "")
+        };
+        parse_redirect_location(&result)
+    }
+
+    #[test_case("https://example.com/dir/key?versionId=1", Some(("https://example.com", "example.com", "/dir/key?versionId=1")); "absolute")]
+    #[test_case("HTTPS://EXAMPLE.com:8443", Some(("https://EXAMPLE.com:8443", "EXAMPLE.com:8443", "/")); "port and no path")]
+    #[test_case("https://example.com?x=1", Some(("https://example.com", "example.com", "/?x=1")); "query and no path")]
+    #[test_case("//example.com/key", Some(("https://example.com", "example.com", "/key")); "scheme relative")]
+    #[test_case("/other/key", Some(("https://example.com", "example.com", "/other/key")); "absolute path")]
+    #[test_case("/key?next=http://x", Some(("https://example.com", "example.com", "/key?next=http://x")); "uri in query")]
+    #[test_case("https://bucket.s3.us-west-2.amazonaws.com/key", Some(("https://bucket.s3.us-west-2.amazonaws.com", "bucket.s3.us-west-2.amazonaws.com", "/key")); "bucket endpoint")]
+    #[test_case("https://bucket.s3.cn-north-1.amazonaws.com.cn/key", Some(("https://bucket.s3.cn-north-1.amazonaws.com.cn", "bucket.s3.cn-north-1.amazonaws.com.cn", "/key")); "bucket endpoint in china")]
+    #[test_case("https://other.example.com/key", None; "other host")]
+    #[test_case("https://example.com.evil.com/key", None; "host with same prefix")]
+    #[test_case("https://example.com@evil.com/key", None; "credentials in authority")]
+    #[test_case("https://other-bucket.s3.us-west-2.amazonaws.com/key", None; "other bucket endpoint")]
+    #[test_case("https://bucket.s3-website-us-west-2.amazonaws.com/key", None; "bucket website endpoint")]
+    #[test_case("https://bucket.s3.b.s3-website-us-west-2.amazonaws.com/key", None; "dotted bucket website endpoint")]
+    #[test_case("https://bucket.s3.amazonaws.com.evil.com/key", None; "bucket endpoint lookalike")]
+    #[test_case("http://example.com/key", None; "https downgrade")]
+    #[test_case("ftp://example.com/key", None; "other scheme")]
+    #[test_case("other/key", None; "relative path")]
+    #[test_case("https:///key", None; "empty authority")]
+    fn test_resolve_redirect_target(location: &str, expected: Option<(&str, &str, &str)>) {
+        let target = resolve_redirect_target("https", "example.com", "bucket", location);
+        let target = target
+            .as_ref()
+            .map(|t| (t.endpoint.as_str(), t.authority.as_str(), t.path_and_query.as_str()));
+        assert_eq!(target, expected);
+    }
+
+    #[test]
+    fn test_resolve_redirect_target_from_http() {
+        let target = resolve_redirect_target("http", "127.0.0.1:8080", "bucket", "http://127.0.0.1:8081/key")
+            .expect("http to http redirect should be followed");
+        assert_eq!(target.endpoint, "http://127.0.0.1:8081");
+        assert_eq!(target.path_and_query, "/key");
+    }
+
+    #[test_case("bucket.s3.amazonaws.com" => true; "global")]
+    #[test_case("Bucket.S3.us-west-2.amazonaws.com" => true; "regional")]
+    #[test_case("bucket.s3.dualstack.us-west-2.amazonaws.com" => true; "dualstack")]
+    #[test_case("bucket.s3.cn-north-1.amazonaws.com.cn" => true; "china")]
+    #[test_case("bucket.s3.s3-website-us-west-2.amazonaws.com" => false; "website of bucket named bucket.s3")]
+    #[test_case("bucket.s3.evilamazonaws.com" => false; "not amazonaws.com")]
+    #[test_case("bucket.s3.x.y.amazonaws.com" => false; "too many labels")]
+    #[test_case("s3.us-west-2.amazonaws.com" => false; "path style")]
+    fn test_is_bucket_s3_endpoint(host: &str) -> bool {
+        is_bucket_s3_endpoint("bucket", host)
+    }
+
+    #[test_case("[::1]:8080", "[::1]"; "ipv6 with port")]
+    #[test_case("[::1]", "[::1]"; "ipv6")]
+    #[test_case("example.com:443", "example.com"; "with port")]
+    #[test_case("example.com", "example.com"; "no port")]
+    fn test_authority_host(authority: &str, expected: &str) {
+        assert_eq!(authority_host(authority), expected);
+    }
+
+    #[test_case(784111777, 0 => "Sun, 06 Nov 1994 08:49:37 GMT"; "utc")]
+    #[test_case(1704067205, -5 => "Mon, 01 Jan 2024 00:00:05 GMT"; "other offset")]
+    fn test_format_http_date(timestamp: i64, offset_hours: i8) -> String {
+        let offset = UtcOffset::from_hms(offset_hours, 0, 0).unwrap();
+        let time = OffsetDateTime::from_unix_timestamp(timestamp)
+            .unwrap()
+            .to_offset(offset);
+        format_http_date(time)
+    }
 }
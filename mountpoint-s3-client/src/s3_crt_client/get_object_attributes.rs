@@ -9,22 +9,23 @@ use mountpoint_s3_crt::{
 use thiserror::Error;
 
 use crate::object_client::{
-    Checksum, GetObjectAttributesError, GetObjectAttributesParts, GetObjectAttributesResult, ObjectAttribute,
-    ObjectClientError, ObjectClientResult, ObjectPart,
+    Checksum, ChecksumType, GetObjectAttributesError, GetObjectAttributesParts, GetObjectAttributesResult,
+    ObjectAttribute, ObjectClientError, ObjectClientResult, ObjectPart,
 };
-use crate::s3_crt_client::{S3CrtClient, S3RequestError};
+use crate::s3_crt_client::list_objects::{parse_xml, xml_snippet};
+use crate::s3_crt_client::{QueryFragment, S3CrtClient, S3RequestError};
 
 #[derive(Error, Debug)]
 #[non_exhaustive]
 pub enum ParseError {
-    #[error("XML response was not valid: problem = {1}, xml node = {0:?}")]
-    InvalidResponse(xmltree::Element, String),
+    #[error("XML response was not valid: problem = {1}, xml node = {0}")]
+    InvalidResponse(String, String),
 
     #[error("XML parsing error: {0:?}")]
     Xml(#[from] xmltree::ParseError),
 
-    #[error("Missing field {1} from XML element {0:?}")]
-    MissingField(xmltree::Element, String),
+    #[error("Missing field {1} from XML element {0}")]
+    MissingField(String, String),
 }
 
 impl GetObjectAttributesResult {
@@ -81,6 +82,7 @@ impl GetObjectAttributesResult {
             object_parts,
             storage_class,
             object_size,
+            response_metadata: Default::default(),
         })
     }
 
@@ -89,12 +91,18 @@ impl GetObjectAttributesResult {
         let checksum_crc32c = get_field_or_none(element, "ChecksumCRC32C")?;
         let checksum_sha1 = get_field_or_none(element, "ChecksumSHA1")?;
         let checksum_sha256 = get_field_or_none(element, "ChecksumSHA256")?;
+        let checksum_crc64nvme = get_field_or_none(element, "ChecksumCRC64NVME")?;
+        let checksum_type = get_field_or_none::<String>(element, "ChecksumType")?
+            .as_deref()
+            .and_then(ChecksumType::from_value);
 
         Ok(Checksum {
             checksum_crc32,
             checksum_crc32c,
             checksum_sha1,
             checksum_sha256,
+            checksum_crc64nvme,
+            checksum_type,
         })
     }
 }
@@ -110,15 +118,12 @@ impl S3CrtClient {
     ) -> ObjectClientResult<GetObjectAttributesResult, GetObjectAttributesError, S3RequestError> {
         let body = {
             let mut message = self
-                .inner
                 .new_request_template("GET", bucket)
                 .map_err(S3RequestError::construction_failure)?;
 
-            let query = vec![("attributes", "")];
-
             let path = format!("/{key}");
             message
-                .set_request_path_and_query(path, query)
+                .set_request_path_and_query(path, &[QueryFragment::Flag("attributes")])
                 .map_err(S3RequestError::construction_failure)?;
 
             if let Some(max_parts) = max_parts {
@@ -141,7 +146,7 @@ impl S3CrtClient {
                 .map_err(S3RequestError::construction_failure)?;
 
             let span = request_span!(
-                self.inner,
+                self,
                 "get_object_attributes",
                 bucket,
                 key,
@@ -158,10 +163,12 @@ impl S3CrtClient {
             )?
         };
 
-        let body = body.await?;
+        let response = body.await?;
 
-        GetObjectAttributesResult::parse_from_bytes(&body)
-            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))
+        let mut result = GetObjectAttributesResult::parse_from_bytes(&response.body)
+            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))?;
+        result.response_metadata = response.metadata;
+        Ok(result)
     }
 }
 
@@ -169,7 +176,7 @@ fn parse_get_object_attributes_error(result: &MetaRequestResult) -> Option<GetOb
     match result.response_status {
         404 => {
             let body = result.error_response_body.as_ref()?;
-            let root = xmltree::Element::parse(body.as_bytes()).ok()?;
+            let root = parse_xml(body.as_bytes()).ok()?;
             let error_code = root.get_child("Code")?;
             let error_str = error_code.get_text()?;
             match error_str.deref() {
@@ -186,7 +193,7 @@ fn parse_get_object_attributes_error(result: &MetaRequestResult) -> Option<GetOb
 fn get_text(element: &xmltree::Element) -> Result<String, ParseError> {
     Ok(element
         .get_text()
-        .ok_or_else(|| ParseError::InvalidResponse(element.clone(), "field has no text".to_owned()))?
+        .ok_or_else(|| ParseError::InvalidResponse(xml_snippet(element), "field has no text".to_owned()))?
         .to_string())
 }
 
@@ -194,7 +201,7 @@ fn get_text(element: &xmltree::Element) -> Result<String, ParseError> {
 fn get_child<'a>(element: &'a xmltree::Element, name: &str) -> Result<&'a xmltree::Element, ParseError> {
     element
         .get_child(name)
-        .ok_or_else(|| ParseError::MissingField(element.clone(), name.to_string()))
+        .ok_or_else(|| ParseError::MissingField(xml_snippet(element), name.to_string()))
 }
 
 /// Get the text out of a child node, with the right error type.
@@ -205,10 +212,9 @@ fn get_field(element: &xmltree::Element, name: &str) -> Result<String, ParseErro
 /// Get the value out of a child node, return [None] if the child node is missing.
 fn get_field_or_none<T: FromStr>(element: &xmltree::Element, name: &str) -> Result<Option<T>, ParseError> {
     match get_field(element, name) {
-        Ok(str) => str
-            .parse::<T>()
-            .map(Some)
-            .map_err(|_| ParseError::InvalidResponse(element.clone(), "failed to parse field from string".to_owned())),
+        Ok(str) => str.parse::<T>().map(Some).map_err(|_| {
+            ParseError::InvalidResponse(xml_snippet(element), "failed to parse field from string".to_owned())
+        }),
         Err(ParseError::MissingField(_, _)) => Ok(None),
         Err(e) => Err(e),
     }
@@ -226,6 +232,8 @@ mod tests {
             crt_error: 1i32.into(),
             error_response_headers: None,
             error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
         }
     }
 
@@ -272,6 +280,16 @@ mod tests {
         assert_eq!(result, 1024);
     }
 
+    #[test]
+    fn parse_checksum_type() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><GetObjectAttributesResponse xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Checksum><ChecksumCRC32C>aHR0cHM=</ChecksumCRC32C><ChecksumType>COMPOSITE</ChecksumType></Checksum><ObjectParts><PartsCount>1</PartsCount><Part><PartNumber>1</PartNumber><Size>1024</Size><ChecksumCRC32C>aHR0cHM=</ChecksumCRC32C></Part></ObjectParts></GetObjectAttributesResponse>"#;
+        let result = GetObjectAttributesResult::parse_from_bytes(body).expect("response should parse");
+        let checksum = result.checksum.expect("response should have a checksum");
+        assert_eq!(checksum.checksum_type, Some(ChecksumType::Composite));
+        let parts = result.object_parts.unwrap().parts.unwrap();
+        assert_eq!(parts[0].checksum.as_ref().unwrap().checksum_type, None);
+    }
+
     #[test]
     fn get_none() {
         let body = br#"<?xml version="1.0" encoding="UTF-8"?><GetObjectAttributesResponse xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><ETag>fc3ff98e8c6a0d3087d515c0473f8677</ETag><IsTruncated>false</IsTruncated><ObjectSize>1024</ObjectSize></GetObjectAttributesResponse>"#;
@@ -1,40 +1,217 @@
-use crate::object_client::ObjectClientResult;
-use crate::s3_crt_client::{S3CrtClient, S3RequestError};
-use mountpoint_s3_crt::s3::client::MetaRequestType;
+use std::sync::{Arc, Mutex};
+
+use crate::bucket_name::validate_bucket_name;
+use crate::object_client::{ObjectClientError, ObjectClientResult};
+use crate::s3_crt_client::{S3CrtClient, S3CrtClientInner, S3RequestError};
+use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
 use thiserror::Error;
+use tracing::warn;
+
+const BUCKET_REGION_HEADER: &str = "x-amz-bucket-region";
 
 /// Errors returned by a [`head_bucket`](S3CrtClient::head_bucket) request.
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum HeadBucketError {
     #[error("The bucket did not exist")]
-    NoSuchBucket,
+    NotFound,
+
+    /// HeadBucket responses have no body, so S3 can't say why access was denied
+    #[error("Access to the bucket was denied")]
+    AccessDenied,
+
+    #[error("The bucket is in region {region}, not the region the client is configured for")]
+    WrongRegion { region: String },
 }
 
 impl S3CrtClient {
+    /// Check that a bucket exists and that we have permission to access it, using a HeadBucket
+    /// request. This is a cheap way to check that the client can reach a bucket, for example
+    /// before starting to serve requests.
     pub async fn head_bucket(&self, bucket: &str) -> ObjectClientResult<(), HeadBucketError, S3RequestError> {
-        let body =
-            {
-                let mut message = self
-                    .inner
-                    .new_request_template("HEAD", bucket)
-                    .map_err(S3RequestError::construction_failure)?;
-
-                message
-                    .set_request_path("/")
-                    .map_err(S3RequestError::construction_failure)?;
-
-                let span = request_span!(self.inner, "head_bucket");
-
-                self.inner
-                    .make_simple_http_request(message, MetaRequestType::Default, span, |request_result| {
-                        match request_result.response_status {
-                            404 => Some(HeadBucketError::NoSuchBucket),
-                            _ => None,
-                        }
-                    })?
-            };
-
-        body.await.map(|_body| ())
+        self.head_bucket_region(bucket).await.map(|_region| ())
+    }
+
+    /// Find the region that a bucket is in, using a HeadBucket request. This works even if the bucket
+    /// is in a different region than this client is configured for, so callers can use it to
+    /// correct their endpoint configuration. Returns `None` if the endpoint doesn't report a region,
+    /// as some S3-compatible endpoints don't.
+    pub async fn get_bucket_region(
+        &self,
+        bucket: &str,
+    ) -> ObjectClientResult<Option<String>, HeadBucketError, S3RequestError> {
+        match self.head_bucket_region(bucket).await {
+            // S3 rejects requests sent to the wrong region, but tells us the right one
+            Err(ObjectClientError::ServiceError(HeadBucketError::WrongRegion { region })) => Ok(Some(region)),
+            Err(ObjectClientError::ClientError(S3RequestError::IncorrectRegion(region))) => Ok(Some(region)),
+            result => result,
+        }
+    }
+
+    /// Send a HeadBucket request, and return the region of the bucket if S3 reports it.
+    async fn head_bucket_region(
+        &self,
+        bucket: &str,
+    ) -> ObjectClientResult<Option<String>, HeadBucketError, S3RequestError> {
+        self.check_bucket_name(bucket)?;
+
+        let region: Arc<Mutex<Option<String>>> = Default::default();
+        let region_writer = region.clone();
+
+        let body = {
+            let mut message = self
+                .new_request_template("HEAD", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+
+            message
+                .set_request_path("/")
+                .map_err(S3RequestError::construction_failure)?;
+
+            let span = request_span!(self, "head_bucket");
+
+            let request_timeouts = message.request_timeouts;
+            let verify_body_length = message.verify_body_length;
+            let options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::Default, &span);
+            self.inner.make_simple_http_request_from_options(
+                options,
+                request_timeouts,
+                span,
+                |_| {},
+                parse_head_bucket_error,
+                move |headers, _| {
+                    let header = headers.get(BUCKET_REGION_HEADER).ok();
+                    *region_writer.lock().unwrap() = header.and_then(|h| h.value().to_owned().into_string().ok());
+                },
+                false,
+                verify_body_length,
+            )?
+        };
+
+        body.await?;
+        let region = region.lock().unwrap().take();
+        Ok(region)
+    }
+
+    /// Check the name of a bucket, unless this client was configured not to.
+    pub(super) fn check_bucket_name(&self, bucket: &str) -> Result<(), S3RequestError> {
+        if self.inner.validate_bucket_names {
+            validate_bucket_name(bucket)?;
+        }
+        Ok(())
+    }
+
+    /// Check the name of a bucket, and, if this client was configured to verify bucket regions,
+    /// that the bucket is in the client's region. Each bucket's region is only checked until a
+    /// check succeeds.
+    pub(super) async fn check_bucket(&self, bucket: &str) -> Result<(), S3RequestError> {
+        self.check_bucket_name(bucket)?;
+        let Some(verified_buckets) = &self.inner.region_verified_buckets else {
+            return Ok(());
+        };
+        if verified_buckets.lock().unwrap().contains(bucket) {
+            return Ok(());
+        }
+
+        let bucket_region = match self.get_bucket_region(bucket).await {
+            Ok(region) => region,
+            // Leave a missing bucket for the request itself to report
+            Err(ObjectClientError::ServiceError(HeadBucketError::NotFound)) => return Ok(()),
+            Err(ObjectClientError::ServiceError(HeadBucketError::AccessDenied)) => {
+                warn!(bucket, "could not verify the region of the bucket: access denied");
+                None
+            }
+            Err(ObjectClientError::ClientError(S3RequestError::Forbidden(message, _))) => {
+                warn!(bucket, ?message, "could not verify the region of the bucket");
+                None
+            }
+            // The bucket may not be owned by the expected owner, which the request itself reports
+            Err(ObjectClientError::ClientError(S3RequestError::BucketOwnerAccessDenied {
+                expected_bucket_owner,
+                message,
+                ..
+            })) => {
+                warn!(
+                    bucket,
+                    expected_bucket_owner,
+                    ?message,
+                    "could not verify the region of the bucket"
+                );
+                None
+            }
+            // `get_bucket_region` returns the region of a bucket in another region
+            Err(ObjectClientError::ServiceError(HeadBucketError::WrongRegion { region })) => Some(region),
+            Err(ObjectClientError::ClientError(e)) => return Err(e),
+        };
+
+        let client_region = self.inner.endpoint_config.get_region();
+        match bucket_region {
+            Some(bucket_region) if bucket_region != client_region => Err(S3RequestError::RegionMismatch {
+                bucket: bucket.to_owned(),
+                bucket_region,
+                client_region: client_region.to_owned(),
+            }),
+            _ => {
+                verified_buckets.lock().unwrap().insert(bucket.to_owned());
+                Ok(())
+            }
+        }
+    }
+}
+
+fn parse_head_bucket_error(result: &MetaRequestResult) -> Option<HeadBucketError> {
+    match result.response_status {
+        404 => Some(HeadBucketError::NotFound),
+        403 => Some(HeadBucketError::AccessDenied),
+        301 => {
+            let headers = result.error_response_headers.as_ref()?;
+            let region = headers.get(BUCKET_REGION_HEADER).ok()?;
+            let region = region.value().to_owned().into_string().ok()?;
+            Some(HeadBucketError::WrongRegion { region })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mountpoint_s3_crt::common::allocator::Allocator;
+    use mountpoint_s3_crt::http::request_response::{Header, Headers};
+    use test_case::test_case;
+
+    use super::*;
+
+    fn make_result(response_status: i32, headers: Option<Headers>) -> MetaRequestResult {
+        MetaRequestResult {
+            response_status,
+            crt_error: 1i32.into(),
+            error_response_headers: headers,
+            error_response_body: None,
+            validation_algorithm: None,
+            metrics: Default::default(),
+        }
+    }
+
+    #[test_case(404, Some(HeadBucketError::NotFound); "not found")]
+    #[test_case(403, Some(HeadBucketError::AccessDenied); "access denied")]
+    #[test_case(301, None; "redirect without region")]
+    #[test_case(500, None; "internal error")]
+    fn parse_error(status: i32, expected: Option<HeadBucketError>) {
+        let result = make_result(status, None);
+        assert_eq!(parse_head_bucket_error(&result), expected);
+    }
+
+    #[test]
+    fn parse_wrong_region() {
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        headers
+            .add_header(&Header::new(BUCKET_REGION_HEADER, "eu-west-1"))
+            .unwrap();
+        let result = make_result(301, Some(headers));
+        assert_eq!(
+            parse_head_bucket_error(&result),
+            Some(HeadBucketError::WrongRegion {
+                region: "eu-west-1".to_owned()
+            })
+        );
     }
 }
@@ -3,18 +3,21 @@ use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use lazy_static::lazy_static;
-use mountpoint_s3_crt::http::request_response::{Headers, HeadersError};
+use mountpoint_s3_crt::http::request_response::{Header, Headers, HeadersError};
 use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
 use regex::Regex;
 use thiserror::Error;
-use time::format_description::well_known::Rfc2822;
 use time::OffsetDateTime;
 use tracing::error;
 
 use crate::object_client::{
-    HeadObjectError, HeadObjectResult, ObjectClientError, ObjectClientResult, ObjectInfo, RestoreStatus,
+    Checksum, ChecksumAlgorithm, ChecksumType, HeadObjectError, HeadObjectParams, HeadObjectResult, ObjectClientError,
+    ObjectClientResult, ObjectInfo, ResponseMetadata, RestoreStatus,
+};
+use crate::s3_crt_client::{
+    checksum_header_name, error_response_metadata, parse_content_headers, parse_content_type, parse_expiration,
+    parse_http_date, parse_request_charged, parse_user_metadata, QueryFragment, S3CrtClient, S3RequestError,
 };
-use crate::s3_crt_client::{S3CrtClient, S3RequestError};
 
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -80,28 +83,57 @@ impl HeadObjectResult {
         let Some(caps) = RESTORE_DONE_RE.captures(&header) else {
             return Err(ParseError::InvalidRestore(header));
         };
-        let expiry = OffsetDateTime::parse(&caps["expiry"], &Rfc2822)
+        let expiry = parse_http_date(&caps["expiry"])
             .map_err(|e| ParseError::OffsetDateTime(e, "x-amz-restore::expiry".into()))?;
         Ok(Some(RestoreStatus::Restored { expiry: expiry.into() }))
     }
 
+    pub(super) fn parse_checksum(headers: &Headers) -> Result<Checksum, ParseError> {
+        let get_checksum = |algorithm| get_optional_field(headers, checksum_header_name(algorithm));
+        Ok(Checksum {
+            checksum_crc32: get_checksum(ChecksumAlgorithm::Crc32)?,
+            checksum_crc32c: get_checksum(ChecksumAlgorithm::Crc32c)?,
+            checksum_sha1: get_checksum(ChecksumAlgorithm::Sha1)?,
+            checksum_sha256: get_checksum(ChecksumAlgorithm::Sha256)?,
+            checksum_crc64nvme: get_checksum(ChecksumAlgorithm::Crc64nvme)?,
+            checksum_type: Self::parse_checksum_type(headers)?,
+        })
+    }
+
+    fn parse_checksum_type(headers: &Headers) -> Result<Option<ChecksumType>, ParseError> {
+        let checksum_type = get_optional_field(headers, "x-amz-checksum-type")?;
+        Ok(checksum_type.as_deref().and_then(ChecksumType::from_value))
+    }
+
     fn parse_from_hdr(bucket: String, key: String, headers: &Headers) -> Result<Self, ParseError> {
-        let last_modified = OffsetDateTime::parse(&get_field(headers, "Last-Modified")?, &Rfc2822)
+        let last_modified = parse_http_date(&get_field(headers, "Last-Modified")?)
             .map_err(|e| ParseError::OffsetDateTime(e, "LastModified".into()))?;
         let size = u64::from_str(&get_field(headers, "Content-Length")?)
             .map_err(|e| ParseError::Int(e, "ContentLength".into()))?;
         let etag = get_field(headers, "Etag")?;
         let storage_class = get_optional_field(headers, "x-amz-storage-class")?;
         let restore_status = Self::parse_restore_status(headers)?;
+        let checksum = Self::parse_checksum(headers)?;
         let object = ObjectInfo {
             key,
             size,
             last_modified,
             storage_class,
             restore_status,
-            etag,
+            etag: Some(etag),
+            checksum_type: checksum.checksum_type,
         };
-        Ok(HeadObjectResult { bucket, object })
+        Ok(HeadObjectResult {
+            bucket,
+            object,
+            checksum,
+            request_charged: parse_request_charged(headers),
+            user_metadata: parse_user_metadata(headers),
+            content_type: parse_content_type(headers),
+            content_headers: parse_content_headers(headers),
+            expiration: parse_expiration(headers),
+            response_metadata: Default::default(),
+        })
     }
 }
 
@@ -110,6 +142,7 @@ impl S3CrtClient {
         &self,
         bucket: &str,
         key: &str,
+        params: &HeadObjectParams,
     ) -> ObjectClientResult<HeadObjectResult, HeadObjectError, S3RequestError> {
         // Stash the response from the head_object in this lock during the on_headers
         // callback, and pull them out once the request is done.
@@ -118,30 +151,53 @@ impl S3CrtClient {
 
         let request = {
             let mut message = self
-                .inner
                 .new_request_template("HEAD", bucket)
                 .map_err(S3RequestError::construction_failure)?;
 
             let key = key.to_string();
+            let mut query = Vec::new();
+            if let Some(version_id) = &params.version_id {
+                query.push(QueryFragment::Pair("versionId", version_id));
+            }
             message
-                .set_request_path(format!("/{key}"))
+                .set_request_path_and_query(format!("/{key}"), &query)
                 .map_err(S3RequestError::construction_failure)?;
 
+            // S3 only returns the object's checksums if we ask for them
+            if params.checksum_mode {
+                message
+                    .set_header(&Header::new("x-amz-checksum-mode", "ENABLED"))
+                    .map_err(S3RequestError::construction_failure)?;
+            }
+
+            if let Some(request_payer) = &params.request_payer {
+                message
+                    .set_header(&Header::new("x-amz-request-payer", request_payer))
+                    .map_err(S3RequestError::construction_failure)?;
+            }
+
             let bucket = bucket.to_owned();
 
-            let span = request_span!(self.inner, "head_object", bucket, key);
+            let span = request_span!(
+                self,
+                "head_object",
+                bucket,
+                key,
+                version_id = ?params.version_id,
+                checksum_mode = params.checksum_mode
+            );
 
             self.inner.make_meta_request(
                 message,
                 MetaRequestType::Default,
                 span,
-                move |headers, _status| {
+                move |headers, status| {
                     let mut header = header1.lock().unwrap();
-                    *header = Some(HeadObjectResult::parse_from_hdr(
-                        bucket.to_string(),
-                        key.to_string(),
-                        headers,
-                    ));
+                    let result = HeadObjectResult::parse_from_hdr(bucket.to_string(), key.to_string(), headers);
+                    *header = Some(result.map(|mut result| {
+                        result.response_metadata = ResponseMetadata::new(status);
+                        result
+                    }));
                 },
                 |_, _| (),
                 move |result| {
@@ -163,6 +219,7 @@ impl S3CrtClient {
 
 fn parse_head_object_error(result: &MetaRequestResult) -> Option<HeadObjectError> {
     match result.response_status {
+        403 => Some(HeadObjectError::AccessDenied(error_response_metadata(result))),
         404 => Some(HeadObjectError::NotFound),
         _ => None,
     }
@@ -171,9 +228,10 @@ fn parse_head_object_error(result: &MetaRequestResult) -> Option<HeadObjectError
 #[cfg(test)]
 mod tests {
     use mountpoint_s3_crt::common::allocator::Allocator;
-    use mountpoint_s3_crt::http::request_response::Header;
+    use time::format_description::well_known::Rfc2822;
 
     use super::*;
+    use crate::error_metadata::ClientErrorMetadata;
 
     use test_case::test_case;
 
@@ -183,6 +241,8 @@ mod tests {
             crt_error: 1i32.into(),
             error_response_headers: None,
             error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
         }
     }
 
@@ -193,6 +253,92 @@ mod tests {
         assert_eq!(result, Some(HeadObjectError::NotFound));
     }
 
+    #[test]
+    fn parse_403() {
+        let result = make_result(403, "");
+        let result = parse_head_object_error(&result);
+        let expected = ClientErrorMetadata {
+            http_code: Some(403),
+            ..Default::default()
+        };
+        assert_eq!(result, Some(HeadObjectError::AccessDenied(expected)));
+    }
+
+    fn make_headers(pairs: &[(&str, &str)]) -> Headers {
+        let mut headers = Headers::new(&Allocator::default()).unwrap();
+        for (name, value) in pairs {
+            headers.add_header(&Header::new(*name, *value)).unwrap();
+        }
+        headers
+    }
+
+    #[test]
+    fn test_parse_from_hdr() {
+        let headers = make_headers(&[
+            ("Content-Length", "1024"),
+            ("Last-Modified", "Fri, 21 Dec 2012 00:00:00 GMT"),
+            ("ETag", "\"3bebe4037c8f040e0e573e191d34b2c6\""),
+            ("x-amz-storage-class", "STANDARD_IA"),
+            ("x-amz-checksum-crc32c", "sOO8/Q=="),
+            ("x-amz-checksum-sha256", "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="),
+        ]);
+        let result = HeadObjectResult::parse_from_hdr("bucket".to_owned(), "key".to_owned(), &headers)
+            .expect("failed to parse headers");
+
+        assert_eq!(result.bucket, "bucket");
+        assert_eq!(result.object.key, "key");
+        assert_eq!(result.object.size, 1024);
+        assert_eq!(
+            OffsetDateTime::format(result.object.last_modified, &Rfc2822).unwrap(),
+            "Fri, 21 Dec 2012 00:00:00 +0000"
+        );
+        assert_eq!(
+            result.object.etag.as_deref(),
+            Some("\"3bebe4037c8f040e0e573e191d34b2c6\"")
+        );
+        assert_eq!(result.object.storage_class.as_deref(), Some("STANDARD_IA"));
+        assert!(result.object.restore_status.is_none());
+        assert_eq!(result.checksum.checksum_crc32, None);
+        assert_eq!(result.checksum.checksum_crc32c.as_deref(), Some("sOO8/Q=="));
+        assert_eq!(result.checksum.checksum_sha1, None);
+        assert_eq!(
+            result.checksum.checksum_sha256.as_deref(),
+            Some("47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=")
+        );
+        assert_eq!(
+            result.checksum.algorithm_and_value(),
+            Some((ChecksumAlgorithm::Crc32c, "sOO8/Q==".to_owned()))
+        );
+        assert!(!result.request_charged);
+    }
+
+    #[test]
+    fn test_parse_from_hdr_request_charged() {
+        let headers = make_headers(&[
+            ("Content-Length", "1024"),
+            ("Last-Modified", "Fri, 21 Dec 2012 00:00:00 GMT"),
+            ("ETag", "\"3bebe4037c8f040e0e573e191d34b2c6\""),
+            ("x-amz-request-charged", "requester"),
+        ]);
+        let result = HeadObjectResult::parse_from_hdr("bucket".to_owned(), "key".to_owned(), &headers)
+            .expect("failed to parse headers");
+        assert!(result.request_charged);
+    }
+
+    #[test_case("Content-Length"; "missing size")]
+    #[test_case("Last-Modified"; "missing last modified")]
+    #[test_case("ETag"; "missing etag")]
+    fn test_parse_from_hdr_missing_field(missing: &str) {
+        let pairs = [
+            ("Content-Length", "1024"),
+            ("Last-Modified", "Fri, 21 Dec 2012 00:00:00 GMT"),
+            ("ETag", "\"3bebe4037c8f040e0e573e191d34b2c6\""),
+        ];
+        let pairs: Vec<_> = pairs.into_iter().filter(|(name, _)| *name != missing).collect();
+        let headers = make_headers(&pairs);
+        assert!(HeadObjectResult::parse_from_hdr("bucket".to_owned(), "key".to_owned(), &headers).is_err());
+    }
+
     #[test_case(r#"ongoing-request="false", expiry-date="Fri, 21 Dec 2012 00:00:00 GMT""#; "from documentation")]
     #[test_case(r#"ongoing-request="false",expiry-date="Fri, 21 Dec 2012 00:00:00 GMT""#; "no whitespace")]
     #[test_case("ongoing-request=\"false\",   \t   \t  expiry-date=\"Fri, 21 Dec 2012 00:00:00 GMT\""; "lots of whitespaces")]
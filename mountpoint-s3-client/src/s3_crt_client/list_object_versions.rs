@@ -0,0 +1,268 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use mountpoint_s3_crt::s3::client::MetaRequestType;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::object_client::{ListObjectsError, ObjectClientError, ObjectClientResult, ObjectInfo, ResponseMetadata};
+use crate::s3_crt_client::list_objects::{
+    decode_url_encoded_listing, get_field, get_text, parse_list_objects_error, parse_object_info_from_xml, parse_xml,
+    xml_snippet, ParseError,
+};
+use crate::s3_crt_client::{parse_request_charged, QueryFragment, S3CrtClient, S3CrtClientInner, S3RequestError};
+
+/// Result of a [`list_object_versions`](S3CrtClient::list_object_versions) request
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ListVersionsResult {
+    /// The versions and delete markers in this page of the listing, in the order S3 returned them:
+    /// by key, and then from newest to oldest.
+    pub versions: Vec<ObjectVersion>,
+
+    /// The list of common prefixes. This rolls up all of the objects with a common prefix up to
+    /// the next instance of the delimiter.
+    pub common_prefixes: Vec<String>,
+
+    /// If present, the key marker to use to query more results.
+    pub next_key_marker: Option<String>,
+
+    /// If present, the version ID marker to use, along with `next_key_marker`, to query more
+    /// results.
+    pub next_version_id_marker: Option<String>,
+
+    /// Whether S3 confirmed that the requester was charged for this request to a requester-pays
+    /// bucket
+    pub request_charged: bool,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// A single version of an object, returned by a
+/// [`list_object_versions`](S3CrtClient::list_object_versions) request.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ObjectVersion {
+    /// The version ID of this version. S3 uses `null` for objects written before versioning was
+    /// enabled on the bucket.
+    pub version_id: String,
+
+    /// Whether this is the current version of the object
+    pub is_latest: bool,
+
+    /// Whether this version is an object or a delete marker
+    pub kind: ObjectVersionKind,
+}
+
+impl ObjectVersion {
+    /// The key of the object this is a version of.
+    pub fn key(&self) -> &str {
+        match &self.kind {
+            ObjectVersionKind::Object(object) => &object.key,
+            ObjectVersionKind::DeleteMarker { key, .. } => key,
+        }
+    }
+
+    /// Whether this version is a delete marker.
+    pub fn is_delete_marker(&self) -> bool {
+        matches!(self.kind, ObjectVersionKind::DeleteMarker { .. })
+    }
+}
+
+/// The contents of an [ObjectVersion]
+#[derive(Debug, Clone)]
+pub enum ObjectVersionKind {
+    /// A version of the object's data
+    Object(ObjectInfo),
+
+    /// A delete marker, which makes the object appear deleted while it's the latest version
+    DeleteMarker {
+        /// The key of the deleted object
+        key: String,
+
+        /// The time the delete marker was created
+        last_modified: OffsetDateTime,
+    },
+}
+
+fn parse_is_latest(element: &xmltree::Element) -> Result<bool, ParseError> {
+    bool::from_str(&get_field(element, "IsLatest")?).map_err(|e| ParseError::Bool(e, "IsLatest".to_string()))
+}
+
+fn parse_delete_marker_from_xml(element: &xmltree::Element) -> Result<ObjectVersionKind, ParseError> {
+    let key = get_field(element, "Key")?;
+    let last_modified = OffsetDateTime::parse(&get_field(element, "LastModified")?, &Rfc3339)
+        .map_err(|e| ParseError::OffsetDateTime(e, "LastModified".to_string()))?;
+    Ok(ObjectVersionKind::DeleteMarker { key, last_modified })
+}
+
+fn parse_versions_from_bytes(bytes: &[u8]) -> Result<ListVersionsResult, ParseError> {
+    let mut element = parse_xml(bytes)?;
+    decode_url_encoded_listing(&mut element)?;
+
+    // Versions and delete markers are interleaved in key order, so walk the children in order
+    // rather than taking each kind separately
+    let mut versions = Vec::new();
+    for child in element.children.iter().filter_map(|node| node.as_element()) {
+        let kind = match child.name.as_str() {
+            "Version" => ObjectVersionKind::Object(parse_object_info_from_xml(child)?),
+            "DeleteMarker" => parse_delete_marker_from_xml(child)?,
+            _ => continue,
+        };
+        versions.push(ObjectVersion {
+            version_id: get_field(child, "VersionId")?,
+            is_latest: parse_is_latest(child)?,
+            kind,
+        });
+    }
+
+    let mut common_prefixes = Vec::new();
+    while let Some(common_prefix) = element.take_child("CommonPrefixes") {
+        common_prefixes.push(get_field(&common_prefix, "Prefix")?);
+    }
+
+    let next_key_marker = element.get_child("NextKeyMarker").map(get_text).transpose()?;
+    let next_version_id_marker = element.get_child("NextVersionIdMarker").map(get_text).transpose()?;
+
+    let is_truncated = get_field(&element, "IsTruncated")?;
+    let is_truncated = bool::from_str(&is_truncated).map_err(|e| ParseError::Bool(e, "IsTruncated".to_string()))?;
+    if is_truncated != next_key_marker.is_some() {
+        return Err(ParseError::InvalidResponse(
+            xml_snippet(&element),
+            "IsTruncated doesn't match NextKeyMarker".to_string(),
+        ));
+    }
+
+    Ok(ListVersionsResult {
+        versions,
+        common_prefixes,
+        next_key_marker,
+        next_version_id_marker,
+        request_charged: false,
+        response_metadata: Default::default(),
+    })
+}
+
+impl S3CrtClient {
+    /// List the versions of the objects in a bucket under a given prefix, including delete
+    /// markers, with a ListObjectVersions request.
+    ///
+    /// To continue a truncated listing, pass the `next_key_marker` and `next_version_id_marker` of
+    /// the previous page as `key_marker` and `version_id_marker`.
+    pub async fn list_object_versions(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        delimiter: Option<&str>,
+        key_marker: Option<&str>,
+        version_id_marker: Option<&str>,
+    ) -> ObjectClientResult<ListVersionsResult, ListObjectsError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+
+        let request_charged = Arc::new(AtomicBool::new(false));
+        let request_charged_writer = request_charged.clone();
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("GET", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            let mut query = vec![QueryFragment::Flag("versions")];
+            if let Some(delimiter) = delimiter {
+                query.push(QueryFragment::Pair("delimiter", delimiter));
+            }
+            if let Some(key_marker) = key_marker {
+                query.push(QueryFragment::Pair("key-marker", key_marker));
+            }
+            query.push(QueryFragment::Pair("prefix", prefix));
+            if let Some(version_id_marker) = version_id_marker {
+                query.push(QueryFragment::Pair("version-id-marker", version_id_marker));
+            }
+            message
+                .set_request_path_and_query("/", &query)
+                .map_err(S3RequestError::construction_failure)?;
+
+            let span = request_span!(
+                self,
+                "list_object_versions",
+                bucket,
+                prefix,
+                ?delimiter,
+                ?key_marker,
+                ?version_id_marker
+            );
+
+            let request_timeouts = message.request_timeouts;
+            let verify_body_length = message.verify_body_length;
+            let options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::Default, &span);
+            self.inner.make_simple_http_request_from_options(
+                options,
+                request_timeouts,
+                span,
+                |_| {},
+                parse_list_objects_error,
+                move |headers, _| {
+                    request_charged_writer.store(parse_request_charged(headers), Ordering::SeqCst);
+                },
+                false,
+                verify_body_length,
+            )?
+        };
+
+        let response = request.await?;
+
+        let mut result = parse_versions_from_bytes(&response.body)
+            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))?;
+        result.request_charged = request_charged.load(Ordering::SeqCst);
+        result.response_metadata = response.metadata;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_versions_and_delete_markers() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?>
+<ListVersionsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name><Prefix></Prefix><KeyMarker></KeyMarker><VersionIdMarker></VersionIdMarker>
+  <NextKeyMarker>b</NextKeyMarker><NextVersionIdMarker>v3</NextVersionIdMarker>
+  <MaxKeys>3</MaxKeys><IsTruncated>true</IsTruncated>
+  <DeleteMarker><Key>a</Key><VersionId>v1</VersionId><IsLatest>true</IsLatest><LastModified>2024-07-01T12:34:56.000Z</LastModified></DeleteMarker>
+  <Version><Key>a</Key><VersionId>v2</VersionId><IsLatest>false</IsLatest><LastModified>2024-07-01T12:00:00.000Z</LastModified><ETag>&quot;etag&quot;</ETag><Size>5</Size><StorageClass>STANDARD</StorageClass></Version>
+  <Version><Key>b</Key><VersionId>v3</VersionId><IsLatest>true</IsLatest><LastModified>2024-07-01T12:00:00.000Z</LastModified><ETag>&quot;etag&quot;</ETag><Size>7</Size><StorageClass>STANDARD</StorageClass></Version>
+</ListVersionsResult>"#;
+        let result = parse_versions_from_bytes(body).expect("listing should parse");
+
+        let versions: Vec<_> = result
+            .versions
+            .iter()
+            .map(|v| (v.key(), v.version_id.as_str(), v.is_latest, v.is_delete_marker()))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("a", "v1", true, true),
+                ("a", "v2", false, false),
+                ("b", "v3", true, false)
+            ]
+        );
+        let ObjectVersionKind::Object(object) = &result.versions[1].kind else {
+            panic!("expected an object version");
+        };
+        assert_eq!(object.size, 5);
+        assert_eq!(result.next_key_marker.as_deref(), Some("b"));
+        assert_eq!(result.next_version_id_marker.as_deref(), Some("v3"));
+    }
+
+    #[test]
+    fn parse_truncated_without_marker() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListVersionsResult><Name>test-bucket</Name><IsTruncated>true</IsTruncated></ListVersionsResult>"#;
+        let err = parse_versions_from_bytes(body).expect_err("truncated listing without a marker should fail");
+        assert!(matches!(err, ParseError::InvalidResponse(_, _)), "got {err:?}");
+    }
+}
@@ -1,30 +1,117 @@
-use std::ops::Deref;
+use std::collections::HashMap;
+use std::fmt;
 use std::os::unix::prelude::OsStrExt;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use mountpoint_s3_crt::http::request_response::Header;
 use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use percent_encoding::percent_decode_str;
 use thiserror::Error;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
-use tracing::error;
+use tracing::{error, warn};
+use xml::attribute::OwnedAttribute;
+use xml::name::OwnedName;
+use xml::reader::XmlEvent;
 
 use crate::object_client::{
-    ListObjectsError, ListObjectsResult, ObjectClientError, ObjectClientResult, ObjectInfo, RestoreStatus,
+    ChecksumType, ListObjectsError, ListObjectsResult, ObjectClientError, ObjectClientResult, ObjectInfo,
+    ResponseMetadata, RestoreStatus,
 };
-use crate::s3_crt_client::{S3CrtClient, S3RequestError};
+use crate::s3_crt_client::{
+    parse_request_charged, parse_s3_error_body, QueryFragment, S3CrtClient, S3CrtClientInner, S3RequestError,
+};
+
+/// Prefix of the continuation tokens the client makes up for endpoints that truncate a listing
+/// without returning a `NextContinuationToken`. The rest of the token is the key to start after.
+const START_AFTER_TOKEN_PREFIX: &str = "mountpoint-start-after:";
+
+/// The header that asks ListObjectsV2 for optional object attributes, in lowercase
+const OPTIONAL_OBJECT_ATTRIBUTES_HEADER: &str = "x-amz-optional-object-attributes";
+
+/// The prefix and delimiter of the listings the client has returned continuation tokens for, so
+/// that debug builds can check each listing is continued with the parameters it started with. S3
+/// doesn't reliably reject a token sent with a different prefix or delimiter, and the page it
+/// returns then belongs to neither listing. Release builds don't record anything.
+#[derive(Debug, Default)]
+pub(super) struct ContinuationTokens {
+    listings: Mutex<HashMap<String, (String, Option<String>)>>,
+}
+
+impl ContinuationTokens {
+    /// Listings abandoned before their last page leave their token behind, so forget every token
+    /// once there are this many rather than growing without bound.
+    const CAPACITY: usize = 1024;
+
+    /// Record the parameters of the listing that returned `token`.
+    pub(super) fn issue(&self, token: Option<&str>, prefix: &str, delimiter: Option<&str>) {
+        let Some(token) = token.filter(|_| cfg!(debug_assertions)) else {
+            return;
+        };
+        let mut listings = self.listings.lock().unwrap();
+        if listings.len() >= Self::CAPACITY {
+            listings.clear();
+        }
+        listings.insert(token.to_owned(), (prefix.to_owned(), delimiter.map(str::to_owned)));
+    }
+
+    /// Check that a listing continued with `token` has the same parameters as the one that
+    /// returned it. Tokens the client didn't return, or has forgotten, aren't checked. The token
+    /// stays recorded, since the same page can be requested again.
+    pub(super) fn check(&self, token: &str, prefix: &str, delimiter: Option<&str>) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        // Release the lock before asserting so a failure doesn't poison it
+        let expected = self.listings.lock().unwrap().get(token).cloned();
+        if let Some((expected_prefix, expected_delimiter)) = expected {
+            assert!(
+                expected_prefix == prefix && expected_delimiter.as_deref() == delimiter,
+                "continuation token for a listing with prefix {expected_prefix:?} and delimiter {expected_delimiter:?} \
+                 was used to continue a listing with prefix {prefix:?} and delimiter {delimiter:?}"
+            );
+        }
+    }
+}
+
+/// Optional object attributes that ListObjectsV2 can return for each object, when requested with
+/// [`S3CrtClient::with_optional_object_attributes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OptionalObjectAttribute {
+    /// The restore status of archived objects
+    RestoreStatus,
+
+    /// An attribute the client doesn't model, such as one supported by an S3-compatible endpoint.
+    /// The value is sent as is.
+    Other(String),
+}
+
+impl fmt::Display for OptionalObjectAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionalObjectAttribute::RestoreStatus => write!(f, "RestoreStatus"),
+            OptionalObjectAttribute::Other(name) => write!(f, "{name}"),
+        }
+    }
+}
 
 #[derive(Error, Debug)]
 #[non_exhaustive]
 pub enum ParseError {
-    #[error("XML response was not valid: problem = {1}, xml node = {0:?}")]
-    InvalidResponse(xmltree::Element, String),
+    #[error("XML response was not valid: problem = {1}, xml node = {0}")]
+    InvalidResponse(String, String),
 
     #[error("XML parsing error: {0:?}")]
     Xml(#[from] xmltree::ParseError),
 
-    #[error("Missing field {1} from XML element {0:?}")]
-    MissingField(xmltree::Element, String),
+    #[error("XML response ended unexpectedly after {0} bytes; it was probably truncated in transit")]
+    TruncatedResponse(usize),
+
+    #[error("Missing field {1} from XML element {0}")]
+    MissingField(String, String),
 
     #[error("Failed to parse field {1} as bool: {0:?}")]
     Bool(#[source] std::str::ParseBoolError, String),
@@ -34,37 +121,341 @@ pub enum ParseError {
 
     #[error("Failed to parse field {1} as OffsetDateTime: {0:?}")]
     OffsetDateTime(#[source] time::error::Parse, String),
+
+    #[error("Failed to URL-decode field {1}: {0:?}")]
+    UrlDecode(#[source] std::str::Utf8Error, String),
+
+    #[error("XML response is too complex to parse: {0}")]
+    TooComplex(String),
+}
+
+/// Longest rendering of an XML node to include in a [ParseError]
+const MAX_XML_SNIPPET_LENGTH: usize = 256;
+
+/// Render the start of an XML element for a parse error, without rendering (or cloning) the rest
+/// of it, since the element can be the root of a large response.
+pub(super) fn xml_snippet(element: &xmltree::Element) -> String {
+    /// Keeps the first `limit` bytes written to it, then fails the write to stop the rendering
+    struct LimitedWriter {
+        buf: Vec<u8>,
+        limit: usize,
+    }
+
+    impl std::io::Write for LimitedWriter {
+        fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+            let len = data.len().min(self.limit - self.buf.len());
+            self.buf.extend_from_slice(&data[..len]);
+            if len < data.len() {
+                return Err(std::io::ErrorKind::WriteZero.into());
+            }
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = LimitedWriter {
+        buf: Vec::new(),
+        limit: MAX_XML_SNIPPET_LENGTH,
+    };
+    let config = xmltree::EmitterConfig::new().write_document_declaration(false);
+    let truncated = element.write_with_config(&mut writer, config).is_err();
+    // The limit can cut a character in half, so only keep the characters before it
+    let mut snippet = match String::from_utf8(writer.buf) {
+        Ok(snippet) => snippet,
+        Err(e) => {
+            let valid = e.utf8_error().valid_up_to();
+            let mut bytes = e.into_bytes();
+            bytes.truncate(valid);
+            String::from_utf8(bytes).expect("prefix is valid UTF-8")
+        }
+    };
+    if truncated {
+        snippet.push_str("...");
+    }
+    snippet
 }
 
 /// Copy text out of an XML element, with the right error type.
-fn get_text(element: &xmltree::Element) -> Result<String, ParseError> {
+pub(super) fn get_text(element: &xmltree::Element) -> Result<String, ParseError> {
     Ok(element
         .get_text()
-        .ok_or_else(|| ParseError::InvalidResponse(element.clone(), "field has no text".to_string()))?
+        .ok_or_else(|| ParseError::InvalidResponse(xml_snippet(element), "field has no text".to_string()))?
         .to_string())
 }
 
 /// Wrapper to get child with some name out of an XML element, with the right error type.
-fn get_child<'a>(element: &'a xmltree::Element, name: &str) -> Result<&'a xmltree::Element, ParseError> {
+///
+/// Like all the lookups by name in this crate, this matches on the local name of the child, so it
+/// finds children that the server qualified with a namespace prefix (`<s3:Key>`) as well as ones
+/// in the default namespace.
+pub(super) fn get_child<'a>(element: &'a xmltree::Element, name: &str) -> Result<&'a xmltree::Element, ParseError> {
     element
         .get_child(name)
-        .ok_or_else(|| ParseError::MissingField(element.clone(), name.to_string()))
+        .ok_or_else(|| ParseError::MissingField(xml_snippet(element), name.to_string()))
 }
 
 /// Get the text out of a child node, with the right error type.
-fn get_field(element: &xmltree::Element, name: &str) -> Result<String, ParseError> {
+pub(super) fn get_field(element: &xmltree::Element, name: &str) -> Result<String, ParseError> {
     get_text(get_child(element, name)?)
 }
 
-fn parse_result_from_bytes(bytes: &[u8]) -> Result<ListObjectsResult, ParseError> {
-    parse_result_from_xml(&mut xmltree::Element::parse(bytes)?)
+/// The value of the `x-amz-optional-object-attributes` header for the given attributes, or `None`
+/// if the header shouldn't be sent.
+fn optional_object_attributes_header(attributes: &[OptionalObjectAttribute]) -> Option<String> {
+    if attributes.is_empty() {
+        return None;
+    }
+    let attributes: Vec<_> = attributes.iter().map(ToString::to_string).collect();
+    Some(attributes.join(","))
+}
+
+/// Result of a [`list_object_keys`](S3CrtClient::list_object_keys) request
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ListObjectKeysResult {
+    /// The keys of the objects
+    pub keys: Vec<String>,
+
+    /// The list of common prefixes. This rolls up all of the objects with a common prefix up to
+    /// the next instance of the delimiter.
+    pub common_prefixes: Vec<String>,
+
+    /// If present, the continuation token to use to query more results.
+    pub next_continuation_token: Option<String>,
+
+    /// Whether S3 confirmed that the requester was charged for this request to a requester-pays
+    /// bucket
+    pub request_charged: bool,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Parse a ListObjectsV2 response, passing each object to `handler` as it's parsed rather than
+/// collecting them. The `objects` of the returned result are always empty. If parsing fails,
+/// `handler` has already seen the objects before the error.
+fn parse_result_from_bytes_with_handler(
+    bytes: &[u8],
+    start_after_fallback: bool,
+    handler: &mut dyn FnMut(ObjectInfo),
+) -> Result<ListObjectsResult, ParseError> {
+    parse_listing(bytes, start_after_fallback, &mut |content| {
+        handler(parse_object_info_from_xml(content)?);
+        Ok(())
+    })
+}
+
+/// Parse a ListObjectsV2 response, passing only the key of each object to `handler`. The `objects`
+/// of the returned result are always empty.
+fn parse_keys_from_bytes_with_handler(
+    bytes: &[u8],
+    start_after_fallback: bool,
+    handler: &mut dyn FnMut(String),
+) -> Result<ListObjectsResult, ParseError> {
+    parse_listing(bytes, start_after_fallback, &mut |content| {
+        handler(get_field(content, "Key")?);
+        Ok(())
+    })
+}
+
+pub(super) fn parse_xml(bytes: &[u8]) -> Result<xmltree::Element, ParseError> {
+    check_xml_complexity(bytes)?;
+    xmltree::Element::parse(bytes).map_err(|e| {
+        if is_unexpected_eof(&e) {
+            ParseError::TruncatedResponse(bytes.len())
+        } else {
+            ParseError::Xml(e)
+        }
+    })
+}
+
+/// The deepest nesting of elements to accept in an XML response. S3's responses are only a few
+/// levels deep.
+const MAX_XML_DEPTH: usize = 64;
+
+/// The most elements to accept in an XML response. A full page of ListObjectVersions has a few tens
+/// of thousands.
+const MAX_XML_ELEMENTS: usize = 1_000_000;
+
+/// Check that an XML document doesn't nest elements more than [MAX_XML_DEPTH] deep or have more
+/// than [MAX_XML_ELEMENTS] elements, before building a tree of it, so that a misbehaving endpoint
+/// or proxy can't make the client use a lot of memory or stack with a small response. This only
+/// counts tags, skipping comments, CDATA sections, and declarations, and leaves checking that the
+/// document is well formed to the parser.
+fn check_xml_complexity(bytes: &[u8]) -> Result<(), ParseError> {
+    /// Find the end of a tag, ignoring any `>` in its attribute values.
+    fn tag_end(tag: &[u8]) -> Option<usize> {
+        let mut quote = None;
+        tag.iter().position(|&b| match quote {
+            Some(q) => {
+                if b == q {
+                    quote = None;
+                }
+                false
+            }
+            None => {
+                if b == b'"' || b == b'\'' {
+                    quote = Some(b);
+                }
+                b == b'>'
+            }
+        })
+    }
+
+    let mut depth = 0usize;
+    let mut elements = 0usize;
+    let mut rest = bytes;
+    while let Some(start) = rest.iter().position(|&b| b == b'<') {
+        rest = &rest[start + 1..];
+        let end_marker: &[u8] = if rest.starts_with(b"!--") {
+            b"-->"
+        } else if rest.starts_with(b"![CDATA[") {
+            b"]]>"
+        } else if rest.starts_with(b"?") {
+            b"?>"
+        } else if rest.starts_with(b"!") {
+            b">"
+        } else {
+            let Some(end) = tag_end(rest) else {
+                break;
+            };
+            let tag = &rest[..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with(b"/") {
+                depth = depth.saturating_sub(1);
+                continue;
+            }
+            elements += 1;
+            if elements > MAX_XML_ELEMENTS {
+                return Err(ParseError::TooComplex(format!("more than {MAX_XML_ELEMENTS} elements")));
+            }
+            if depth + 1 > MAX_XML_DEPTH {
+                return Err(ParseError::TooComplex(format!(
+                    "elements nested more than {MAX_XML_DEPTH} deep"
+                )));
+            }
+            if !tag.ends_with(b"/") {
+                depth += 1;
+            }
+            continue;
+        };
+        match rest.windows(end_marker.len()).position(|window| window == end_marker) {
+            Some(end) => rest = &rest[end + end_marker.len()..],
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+/// Decode the keys and prefixes of a listing in place if S3 URL-encoded them, which it does when
+/// the response's `EncodingType` is `url`. All of them are decoded together, so that callers never
+/// see a mix of encoded and decoded values. This works for both ListObjectsV2 and
+/// ListObjectVersions responses.
+pub(super) fn decode_url_encoded_listing(element: &mut xmltree::Element) -> Result<(), ParseError> {
+    let encoding_type = element.get_child("EncodingType").and_then(|e| e.get_text());
+    if encoding_type.as_deref() != Some("url") {
+        return Ok(());
+    }
+
+    for child in element.children.iter_mut().filter_map(|node| node.as_mut_element()) {
+        decode_url_encoded_child(child)?;
+    }
+    Ok(())
+}
+
+/// Decode a child of the root element of a URL-encoded listing in place.
+fn decode_url_encoded_child(child: &mut xmltree::Element) -> Result<(), ParseError> {
+    match child.name.as_str() {
+        "Prefix" | "Delimiter" | "StartAfter" | "KeyMarker" | "NextKeyMarker" => url_decode_text(child)?,
+        "Contents" | "Version" | "DeleteMarker" | "CommonPrefixes" => {
+            for field in child.children.iter_mut().filter_map(|node| node.as_mut_element()) {
+                if field.name == "Key" || field.name == "Prefix" {
+                    url_decode_text(field)?;
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace the text of an element with its URL-decoded value. S3 encodes spaces as `+`, like an
+/// HTML form, so a literal `+` is always encoded as `%2B`.
+fn url_decode_text(element: &mut xmltree::Element) -> Result<(), ParseError> {
+    let Some(text) = element.get_text() else {
+        return Ok(());
+    };
+    let decoded = percent_decode_str(&text.replace('+', " "))
+        .decode_utf8()
+        .map_err(|e| ParseError::UrlDecode(e, element.name.clone()))?
+        .into_owned();
+    element.children = vec![xmltree::XMLNode::Text(decoded)];
+    Ok(())
 }
 
-fn parse_result_from_xml(element: &mut xmltree::Element) -> Result<ListObjectsResult, ParseError> {
-    let mut objects = Vec::new();
+/// Whether an XML parse failed because the document ended before all its elements were closed.
+///
+/// xmltree doesn't re-export xml-rs's error kinds, so we go by the message. xml-rs reports an EOF
+/// inside a token as "Unexpected EOF", and an EOF between tokens as "Unexpected end of stream".
+fn is_unexpected_eof(error: &xmltree::ParseError) -> bool {
+    match error {
+        xmltree::ParseError::MalformedXml(e) => {
+            let message = e.msg();
+            message.starts_with("Unexpected EOF") || message.starts_with("Unexpected end of stream")
+        }
+        _ => false,
+    }
+}
+
+/// Parse a page of a listing, passing each `<Contents>` element to `parse_content`, which parses as
+/// much of the object as the caller needs.
+///
+/// This reads the response one child of the root element at a time instead of building a tree of
+/// the whole response, so each `<Contents>` element is passed on as soon as it's been read and
+/// then dropped. The other children are small and are kept for the rest of the result.
+fn parse_listing(
+    bytes: &[u8],
+    start_after_fallback: bool,
+    parse_content: &mut dyn FnMut(&xmltree::Element) -> Result<(), ParseError>,
+) -> Result<ListObjectsResult, ParseError> {
+    check_xml_complexity(bytes)?;
+    // S3 puts `EncodingType` after the objects, so find out whether to decode them up front
+    let url_encoded = listing_encoding_type(bytes)?.as_deref() == Some("url");
 
-    while let Some(content) = element.take_child("Contents") {
-        objects.push(parse_object_info_from_xml(&content)?);
+    let mut reader = xml::EventReader::new(bytes);
+    let mut element = loop {
+        match next_xml_event(&mut reader, bytes)? {
+            XmlEvent::StartElement { name, attributes, .. } => break new_xml_element(name, attributes),
+            XmlEvent::EndDocument => return Err(ParseError::TruncatedResponse(bytes.len())),
+            _ => {}
+        }
+    };
+
+    // Only needed to continue a truncated listing with the start-after fallback
+    let mut last_key = None;
+
+    loop {
+        match next_xml_event(&mut reader, bytes)? {
+            XmlEvent::StartElement { name, attributes, .. } => {
+                let mut child = read_xml_element(&mut reader, bytes, name, attributes)?;
+                if url_encoded {
+                    decode_url_encoded_child(&mut child)?;
+                }
+                if child.name == "Contents" {
+                    if start_after_fallback {
+                        last_key = Some(get_field(&child, "Key")?);
+                    }
+                    parse_content(&child)?;
+                } else {
+                    element.children.push(xmltree::XMLNode::Element(child));
+                }
+            }
+            XmlEvent::EndElement { .. } => break,
+            _ => {}
+        }
     }
 
     let mut common_prefixes = Vec::new();
@@ -79,23 +470,130 @@ fn parse_result_from_xml(element: &mut xmltree::Element) -> Result<ListObjectsRe
         next_continuation_token = Some(get_text(elem)?);
     }
 
-    let is_truncated = get_field(element, "IsTruncated")?;
+    let is_truncated = get_field(&element, "IsTruncated")?;
     let is_truncated = bool::from_str(&is_truncated).map_err(|e| ParseError::Bool(e, "IsTruncated".to_string()))?;
 
+    if is_truncated && next_continuation_token.is_none() && start_after_fallback {
+        next_continuation_token =
+            start_after_key(last_key, &common_prefixes).map(|key| format!("{START_AFTER_TOKEN_PREFIX}{key}"));
+    }
+
     if is_truncated != next_continuation_token.is_some() {
         return Err(ParseError::InvalidResponse(
-            element.clone(),
+            xml_snippet(&element),
             "IsTruncated doesn't match NextContinuationToken".to_string(),
         ));
     }
 
+    // S3 echoes the parameters it applied, but S3-compatible endpoints might not, and an empty
+    // parameter comes back as an element without text
+    let get_echo = |name: &str| {
+        element
+            .get_child(name)
+            .map(|e| e.get_text().unwrap_or_default().into_owned())
+    };
+    let max_keys = get_echo("MaxKeys")
+        .map(|max_keys| usize::from_str(&max_keys).map_err(|e| ParseError::Int(e, "MaxKeys".to_string())))
+        .transpose()?;
+
     Ok(ListObjectsResult {
-        objects,
+        objects: Vec::new(),
         common_prefixes,
         next_continuation_token,
+        prefix: get_echo("Prefix"),
+        delimiter: get_echo("Delimiter"),
+        max_keys,
+        encoding_type: get_echo("EncodingType"),
+        request_charged: false,
+        response_metadata: Default::default(),
     })
 }
 
+/// The text of the `EncodingType` child of the root element of a listing, if it has one. This only
+/// reads the events of the response, and only if it mentions `EncodingType` at all, which
+/// responses to requests that don't ask for an encoding usually don't.
+fn listing_encoding_type(bytes: &[u8]) -> Result<Option<String>, ParseError> {
+    const NAME: &[u8] = b"EncodingType";
+    if !bytes.windows(NAME.len()).any(|window| window == NAME) {
+        return Ok(None);
+    }
+
+    let mut reader = xml::EventReader::new(bytes);
+    let mut depth = 0usize;
+    loop {
+        match next_xml_event(&mut reader, bytes)? {
+            XmlEvent::StartElement { name, attributes, .. } if depth == 1 && name.local_name == "EncodingType" => {
+                let element = read_xml_element(&mut reader, bytes, name, attributes)?;
+                return Ok(element.get_text().map(|text| text.into_owned()));
+            }
+            XmlEvent::StartElement { .. } => depth += 1,
+            XmlEvent::EndElement { .. } => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(None);
+                }
+            }
+            XmlEvent::EndDocument => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+/// Read the next event of an XML document, with the same errors as [parse_xml].
+fn next_xml_event(reader: &mut xml::EventReader<&[u8]>, bytes: &[u8]) -> Result<XmlEvent, ParseError> {
+    reader.next().map_err(|e| {
+        let e = xmltree::ParseError::MalformedXml(e);
+        if is_unexpected_eof(&e) {
+            ParseError::TruncatedResponse(bytes.len())
+        } else {
+            ParseError::Xml(e)
+        }
+    })
+}
+
+/// Make an element with the given name and attributes, and no children.
+fn new_xml_element(name: OwnedName, attributes: Vec<OwnedAttribute>) -> xmltree::Element {
+    let mut element = xmltree::Element::new(&name.local_name);
+    element.prefix = name.prefix;
+    element.namespace = name.namespace;
+    element.attributes = attributes
+        .into_iter()
+        .map(|attribute| (attribute.name.local_name, attribute.value))
+        .collect();
+    element
+}
+
+/// Read the rest of an element whose start tag was just read into a tree, like [parse_xml] does
+/// for a whole document. [check_xml_complexity] bounds how deep this recurses.
+fn read_xml_element(
+    reader: &mut xml::EventReader<&[u8]>,
+    bytes: &[u8],
+    name: OwnedName,
+    attributes: Vec<OwnedAttribute>,
+) -> Result<xmltree::Element, ParseError> {
+    let mut element = new_xml_element(name, attributes);
+    loop {
+        match next_xml_event(reader, bytes)? {
+            XmlEvent::StartElement { name, attributes, .. } => {
+                let child = read_xml_element(reader, bytes, name, attributes)?;
+                element.children.push(xmltree::XMLNode::Element(child));
+            }
+            XmlEvent::EndElement { .. } => return Ok(element),
+            XmlEvent::Characters(text) => element.children.push(xmltree::XMLNode::Text(text)),
+            XmlEvent::CData(text) => element.children.push(xmltree::XMLNode::CData(text)),
+            XmlEvent::EndDocument => return Err(ParseError::TruncatedResponse(bytes.len())),
+            _ => {}
+        }
+    }
+}
+
+/// The key to start after to continue a truncated listing. A common prefix rolls up every key that
+/// starts with it, so to skip past a prefix we start after the greatest key it could contain.
+fn start_after_key(last_key: Option<String>, common_prefixes: &[String]) -> Option<String> {
+    let last_prefix = common_prefixes.last().map(|prefix| format!("{prefix}{}", char::MAX));
+    last_key.into_iter().chain(last_prefix).max()
+}
+
 fn parse_restore_status(element: &xmltree::Element) -> Result<Option<RestoreStatus>, ParseError> {
     let Some(restore_status) = element.get_child("RestoreStatus") else {
         return Ok(None);
@@ -114,12 +612,16 @@ fn parse_restore_status(element: &xmltree::Element) -> Result<Option<RestoreStat
     }))
 }
 
-fn parse_object_info_from_xml(element: &xmltree::Element) -> Result<ObjectInfo, ParseError> {
+pub(super) fn parse_object_info_from_xml(element: &xmltree::Element) -> Result<ObjectInfo, ParseError> {
     let key = get_field(element, "Key")?;
 
-    let size = get_field(element, "Size")?;
-
-    let size = u64::from_str(&size).map_err(|e| ParseError::Int(e, "Size".to_string()))?;
+    // Some S3-compatible endpoints leave the size or ETag out of some entries, which shouldn't
+    // fail the whole listing
+    let size = get_field(element, "Size")
+        .ok()
+        .map(|size| u64::from_str(&size).map_err(|e| ParseError::Int(e, "Size".to_string())))
+        .transpose()?
+        .unwrap_or(0);
 
     let last_modified = get_field(element, "LastModified")?;
 
@@ -132,7 +634,13 @@ fn parse_object_info_from_xml(element: &xmltree::Element) -> Result<ObjectInfo,
 
     let restore_status = parse_restore_status(element)?;
 
-    let etag = get_field(element, "ETag")?;
+    let etag = get_field(element, "ETag").ok();
+
+    // Endpoints that predate checksum types don't report them
+    let checksum_type = get_field(element, "ChecksumType")
+        .ok()
+        .as_deref()
+        .and_then(ChecksumType::from_value);
 
     Ok(ObjectInfo {
         key,
@@ -141,75 +649,289 @@ fn parse_object_info_from_xml(element: &xmltree::Element) -> Result<ObjectInfo,
         storage_class,
         restore_status,
         etag,
+        checksum_type,
     })
 }
 
+/// The unparsed response to a ListObjectsV2 request
+#[derive(Debug)]
+pub(super) struct ListObjectsResponse {
+    body: Vec<u8>,
+    request_charged: bool,
+    metadata: ResponseMetadata,
+}
+
 impl S3CrtClient {
-    pub(super) async fn list_objects(
+    /// Send a ListObjectsV2 request for one page of a listing, without parsing the response.
+    ///
+    /// If the request asks for optional object attributes and the endpoint rejects the
+    /// `x-amz-optional-object-attributes` header with a 400 response, as endpoints that don't
+    /// support it do, the request is retried once without them. If the retry succeeds, later
+    /// listings don't ask for them at all. Other 400 responses fail the request as usual.
+    pub(super) async fn list_objects_request(
         &self,
         bucket: &str,
         continuation_token: Option<&str>,
-        delimiter: &str,
+        delimiter: Option<&str>,
         max_keys: usize,
         prefix: &str,
-    ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, S3RequestError> {
+    ) -> ObjectClientResult<ListObjectsResponse, ListObjectsError, S3RequestError> {
+        let attributes = optional_object_attributes_header(&self.optional_object_attributes)
+            .filter(|_| !self.inner.optional_object_attributes_unsupported.load(Ordering::SeqCst));
+        let result = self
+            .send_list_objects_request(
+                bucket,
+                continuation_token,
+                delimiter,
+                max_keys,
+                prefix,
+                attributes.as_deref(),
+            )
+            .await;
+        let Some(attributes) = attributes else {
+            return result;
+        };
+        match &result {
+            Err(ObjectClientError::ClientError(S3RequestError::ResponseError(response)))
+                if rejects_optional_object_attributes(response) => {}
+            _ => return result,
+        }
+
+        let fallback = self
+            .send_list_objects_request(bucket, continuation_token, delimiter, max_keys, prefix, None)
+            .await;
+        if fallback.is_err() {
+            // The optional attributes weren't the problem, so report the original failure
+            return result;
+        }
+        let already_unsupported = self
+            .inner
+            .optional_object_attributes_unsupported
+            .swap(true, Ordering::SeqCst);
+        if !already_unsupported {
+            warn!(
+                attributes,
+                "endpoint rejected ListObjectsV2 request with optional object attributes, listing without them"
+            );
+        }
+        fallback
+    }
+
+    /// Send a single ListObjectsV2 request, asking for the given optional object attributes (the
+    /// value of the `x-amz-optional-object-attributes` header) if there are any.
+    async fn send_list_objects_request(
+        &self,
+        bucket: &str,
+        continuation_token: Option<&str>,
+        delimiter: Option<&str>,
+        max_keys: usize,
+        prefix: &str,
+        optional_object_attributes: Option<&str>,
+    ) -> ObjectClientResult<ListObjectsResponse, ListObjectsError, S3RequestError> {
+        let request_charged = Arc::new(AtomicBool::new(false));
+        let request_charged_writer = request_charged.clone();
+
         // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
         let body = {
             let mut message = self
-                .inner
                 .new_request_template("GET", bucket)
                 .map_err(S3RequestError::construction_failure)?;
-            message
-                .set_header(&Header::new("x-amz-optional-object-attributes", "RestoreStatus"))
-                .map_err(S3RequestError::construction_failure)?;
+            if let Some(attributes) = optional_object_attributes {
+                message
+                    .set_header(&Header::new(OPTIONAL_OBJECT_ATTRIBUTES_HEADER, attributes))
+                    .map_err(S3RequestError::construction_failure)?;
+            }
             let max_keys = format!("{max_keys}");
-            let mut query = vec![
-                ("list-type", "2"),
-                ("delimiter", delimiter),
-                ("max-keys", &max_keys),
-                ("prefix", prefix),
-            ];
+            let mut query = vec![QueryFragment::Pair("list-type", "2")];
+            if let Some(delimiter) = delimiter {
+                query.push(QueryFragment::Pair("delimiter", delimiter));
+            }
+            query.push(QueryFragment::Pair("max-keys", &max_keys));
+            query.push(QueryFragment::Pair("prefix", prefix));
             if let Some(continuation_token) = continuation_token {
-                query.push(("continuation-token", continuation_token));
+                let start_after = continuation_token
+                    .strip_prefix(START_AFTER_TOKEN_PREFIX)
+                    .filter(|_| self.inner.list_objects_start_after_fallback);
+                match start_after {
+                    Some(start_after) => query.push(QueryFragment::Pair("start-after", start_after)),
+                    None => query.push(QueryFragment::Pair("continuation-token", continuation_token)),
+                }
             }
 
             message
-                .set_request_path_and_query("/", query)
+                .set_request_path_and_query("/", &query)
                 .map_err(S3RequestError::construction_failure)?;
 
             let span = request_span!(
-                self.inner,
+                self,
                 "list_objects",
                 bucket,
                 continued = continuation_token.is_some(),
-                delimiter,
+                ?delimiter,
                 max_keys,
                 prefix
             );
 
-            self.inner
-                .make_simple_http_request(message, MetaRequestType::Default, span, parse_list_objects_error)?
+            let request_timeouts = message.request_timeouts;
+            let verify_body_length = message.verify_body_length;
+            let options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::Default, &span);
+            self.inner.make_simple_http_request_from_options(
+                options,
+                request_timeouts,
+                span,
+                |_| {},
+                parse_list_objects_error,
+                move |headers, _| {
+                    request_charged_writer.store(parse_request_charged(headers), Ordering::SeqCst);
+                },
+                false,
+                verify_body_length,
+            )?
         };
 
-        let body = body.await?;
+        let response = body.await?;
+
+        Ok(ListObjectsResponse {
+            body: response.body,
+            request_charged: request_charged.load(Ordering::SeqCst),
+            metadata: response.metadata,
+        })
+    }
+
+    /// Parse the response to a ListObjectsV2 request into a page of the listing.
+    pub(super) fn parse_list_objects_response(
+        &self,
+        response: ListObjectsResponse,
+    ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, S3RequestError> {
+        let mut objects = Vec::new();
+        let mut result = self.parse_list_objects_response_with_handler(response, &mut |object| objects.push(object))?;
+        result.objects = objects;
+        Ok(result)
+    }
+
+    /// Parse the response to a ListObjectsV2 request, passing each object in it to `handler`
+    /// instead of collecting them into the result.
+    pub(super) fn parse_list_objects_response_with_handler(
+        &self,
+        response: ListObjectsResponse,
+        handler: &mut dyn FnMut(ObjectInfo),
+    ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, S3RequestError> {
+        let mut result =
+            parse_result_from_bytes_with_handler(&response.body, self.inner.list_objects_start_after_fallback, handler)
+                .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))?;
+        result.request_charged = response.request_charged;
+        result.response_metadata = response.metadata;
+        Ok(result)
+    }
+
+    /// List the objects in a bucket under a given prefix, like
+    /// [`list_objects`](crate::ObjectClient::list_objects), but retry the page up to `page_retries`
+    /// times with the same continuation token if it fails with a
+    /// [transient](S3RequestError::is_transient) error, instead of the client's default of
+    /// [list_objects_page_retries](crate::config::S3ClientConfig::list_objects_page_retries).
+    pub async fn list_objects_with_retries(
+        &self,
+        bucket: &str,
+        continuation_token: Option<&str>,
+        delimiter: Option<&str>,
+        max_keys: usize,
+        prefix: &str,
+        page_retries: u32,
+    ) -> ObjectClientResult<ListObjectsResult, ListObjectsError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+        let response = self
+            .list_objects_with_page_retries(bucket, continuation_token, delimiter, max_keys, prefix, page_retries)
+            .await?;
+        let result = self.parse_list_objects_response(response)?;
+        self.inner
+            .continuation_tokens
+            .issue(result.next_continuation_token.as_deref(), prefix, delimiter);
+        Ok(result)
+    }
+
+    /// List the keys of the objects in a bucket under a given prefix, like
+    /// [`list_objects`](crate::ObjectClient::list_objects), but without parsing the rest of each
+    /// object's metadata. This is cheaper for large listings where only the keys are needed.
+    pub async fn list_object_keys(
+        &self,
+        bucket: &str,
+        continuation_token: Option<&str>,
+        delimiter: Option<&str>,
+        max_keys: usize,
+        prefix: &str,
+    ) -> ObjectClientResult<ListObjectKeysResult, ListObjectsError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+        let response = self
+            .list_objects_with_page_retries(
+                bucket,
+                continuation_token,
+                delimiter,
+                max_keys,
+                prefix,
+                self.inner.list_objects_page_retries,
+            )
+            .await?;
 
-        parse_result_from_bytes(&body)
-            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))
+        let mut keys = Vec::new();
+        let result = parse_keys_from_bytes_with_handler(
+            &response.body,
+            self.inner.list_objects_start_after_fallback,
+            &mut |key| keys.push(key),
+        )
+        .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))?;
+        self.inner
+            .continuation_tokens
+            .issue(result.next_continuation_token.as_deref(), prefix, delimiter);
+        Ok(ListObjectKeysResult {
+            keys,
+            common_prefixes: result.common_prefixes,
+            next_continuation_token: result.next_continuation_token,
+            request_charged: response.request_charged,
+            response_metadata: response.metadata,
+        })
     }
 }
 
-fn parse_list_objects_error(result: &MetaRequestResult) -> Option<ListObjectsError> {
+/// Whether a failed ListObjectsV2 request was rejected because of its
+/// `x-amz-optional-object-attributes` header, rather than for some other reason. Endpoints name
+/// the header in the error's message or in a field like `ArgumentName`.
+fn rejects_optional_object_attributes(result: &MetaRequestResult) -> bool {
+    if result.response_status != 400 {
+        return false;
+    }
+    let Some(details) = result
+        .error_response_body
+        .as_ref()
+        .and_then(|body| parse_s3_error_body(body.as_bytes()))
+    else {
+        return false;
+    };
+    let names_header = |text: &str| text.to_ascii_lowercase().contains(OPTIONAL_OBJECT_ATTRIBUTES_HEADER);
+    details.message.as_deref().is_some_and(names_header)
+        || details.extra_fields.values().map(String::as_str).any(names_header)
+}
+
+pub(super) fn parse_list_objects_error(result: &MetaRequestResult) -> Option<ListObjectsError> {
     match result.response_status {
         404 => {
             let body = result.error_response_body.as_ref()?;
-            let root = xmltree::Element::parse(body.as_bytes()).ok()?;
-            let error_code = root.get_child("Code")?;
-            let error_str = error_code.get_text()?;
-            match error_str.deref() {
+            let details = parse_s3_error_body(body.as_bytes())?;
+            match details.code.as_deref()? {
                 "NoSuchBucket" => Some(ListObjectsError::NoSuchBucket),
                 _ => None,
             }
         }
+        301 => {
+            let body = result.error_response_body.as_ref()?;
+            let details = parse_s3_error_body(body.as_bytes())?;
+            if details.code.as_deref()? != "PermanentRedirect" {
+                return None;
+            }
+            let headers = result.error_response_headers.as_ref()?;
+            let region = headers.get("x-amz-bucket-region").ok()?;
+            let expected = region.value().to_owned().into_string().ok()?;
+            Some(ListObjectsError::WrongRegion { expected })
+        }
         _ => None,
     }
 }
@@ -218,15 +940,359 @@ fn parse_list_objects_error(result: &MetaRequestResult) -> Option<ListObjectsErr
 mod tests {
     use std::ffi::{OsStr, OsString};
 
+    use test_case::test_case;
+
     use super::*;
+    use crate::meta_request_result::MetaRequestResultBuilder;
+
+    fn parse_result_from_bytes(bytes: &[u8], start_after_fallback: bool) -> Result<ListObjectsResult, ParseError> {
+        let mut objects = Vec::new();
+        let mut result =
+            parse_result_from_bytes_with_handler(bytes, start_after_fallback, &mut |object| objects.push(object))?;
+        result.objects = objects;
+        Ok(result)
+    }
 
     fn make_result(response_status: i32, body: impl Into<OsString>) -> MetaRequestResult {
-        MetaRequestResult {
-            response_status,
-            crt_error: 1i32.into(),
-            error_response_headers: None,
-            error_response_body: Some(body.into()),
+        MetaRequestResultBuilder::new(response_status).body(body).build()
+    }
+
+    const TRUNCATED_WITHOUT_TOKEN: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>test-bucket</Name><Prefix></Prefix><KeyCount>2</KeyCount><MaxKeys>2</MaxKeys><Delimiter>/</Delimiter><IsTruncated>true</IsTruncated><Contents><Key>a</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;9b2cf535f27731c974343645a3985328&quot;</ETag><Size>1</Size><StorageClass>STANDARD</StorageClass></Contents><CommonPrefixes><Prefix>b/</Prefix></CommonPrefixes></ListBucketResult>"#;
+
+    #[test_case(400, "<Error><Code>InvalidArgument</Code><Message>Invalid attribute name specified.</Message><ArgumentName>x-amz-optional-object-attributes</ArgumentName></Error>", true; "argument name")]
+    #[test_case(400, "<Error><Code>InvalidRequest</Code><Message>Unsupported header X-Amz-Optional-Object-Attributes</Message></Error>", true; "message")]
+    #[test_case(400, "<Error><Code>InvalidArgument</Code><Message>Invalid max-keys</Message><ArgumentName>max-keys</ArgumentName></Error>", false; "other argument")]
+    #[test_case(400, "", false; "no body")]
+    #[test_case(403, "<Error><Code>AccessDenied</Code><Message>x-amz-optional-object-attributes</Message></Error>", false; "not a 400")]
+    fn rejected_optional_object_attributes(response_status: i32, body: &str, rejected: bool) {
+        let result = make_result(response_status, body);
+        assert_eq!(rejects_optional_object_attributes(&result), rejected);
+    }
+
+    #[test]
+    fn parse_truncated_without_token() {
+        let err = parse_result_from_bytes(TRUNCATED_WITHOUT_TOKEN, false).expect_err("missing token should fail");
+        assert!(matches!(err, ParseError::InvalidResponse(_, _)));
+
+        let result = parse_result_from_bytes(TRUNCATED_WITHOUT_TOKEN, true).expect("fallback should continue");
+        let token = result
+            .next_continuation_token
+            .expect("should have a continuation token");
+        assert_eq!(token, format!("{START_AFTER_TOKEN_PREFIX}b/{}", char::MAX));
+    }
+
+    #[test]
+    fn continuation_token_with_same_parameters() {
+        let tokens = ContinuationTokens::default();
+        tokens.issue(Some("token"), "dir/", Some("/"));
+        tokens.check("token", "dir/", Some("/"));
+        tokens.check("token", "dir/", Some("/"));
+        // Tokens the client never returned aren't checked
+        tokens.check("other-token", "other/", None);
+    }
+
+    #[test]
+    #[should_panic(expected = "continuation token for a listing with prefix")]
+    fn continuation_token_with_different_prefix() {
+        let tokens = ContinuationTokens::default();
+        tokens.issue(Some("token"), "dir/", Some("/"));
+        tokens.check("token", "other/", Some("/"));
+    }
+
+    #[test]
+    #[should_panic(expected = "continuation token for a listing with prefix")]
+    fn continuation_token_with_different_delimiter() {
+        let tokens = ContinuationTokens::default();
+        tokens.issue(Some("token"), "dir/", Some("/"));
+        tokens.check("token", "dir/", None);
+    }
+
+    #[test]
+    fn parse_too_deep() {
+        let body = format!(
+            "<ListBucketResult>{}{}</ListBucketResult>",
+            "<a>".repeat(MAX_XML_DEPTH),
+            "</a>".repeat(MAX_XML_DEPTH)
+        );
+        let err = parse_result_from_bytes(body.as_bytes(), false).expect_err("deep document should fail");
+        assert!(matches!(err, ParseError::TooComplex(_)), "got {err:?}");
+    }
+
+    #[test]
+    fn parse_too_many_elements() {
+        let body = format!(
+            "<ListBucketResult>{}</ListBucketResult>",
+            "<a/>".repeat(MAX_XML_ELEMENTS)
+        );
+        let err = parse_result_from_bytes(body.as_bytes(), false).expect_err("large document should fail");
+        assert!(matches!(err, ParseError::TooComplex(_)), "got {err:?}");
+    }
+
+    #[test]
+    fn check_xml_complexity_skips_non_elements() {
+        // None of the tag-like text in comments, CDATA, or attribute values counts towards the depth
+        let nested = "<a>".repeat(MAX_XML_DEPTH);
+        let body = format!(
+            r#"<?xml version="1.0"?><!-- {nested} --><Root attr="{nested}"><![CDATA[{nested}]]><Leaf/></Root>"#
+        );
+        check_xml_complexity(body.as_bytes()).expect("document should be simple enough");
+        assert!(check_xml_complexity(nested.as_bytes()).is_ok());
+        assert!(check_xml_complexity(format!("{nested}<a>").as_bytes()).is_err());
+    }
+
+    #[test]
+    fn parse_echoed_parameters() {
+        let result = parse_result_from_bytes(TRUNCATED_WITHOUT_TOKEN, true).expect("listing should parse");
+        assert_eq!(result.prefix.as_deref(), Some(""));
+        assert_eq!(result.delimiter.as_deref(), Some("/"));
+        assert_eq!(result.max_keys, Some(2));
+        assert_eq!(result.encoding_type, None);
+
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><Prefix>dir%2F</Prefix><MaxKeys>1000</MaxKeys><EncodingType>url</EncodingType><IsTruncated>false</IsTruncated></ListBucketResult>"#;
+        let result = parse_result_from_bytes(body, false).expect("listing should parse");
+        assert_eq!(result.prefix.as_deref(), Some("dir/"));
+        assert_eq!(result.delimiter, None);
+        assert_eq!(result.max_keys, Some(1000));
+        assert_eq!(result.encoding_type.as_deref(), Some("url"));
+    }
+
+    #[test]
+    fn parse_url_encoded_listing() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><Prefix>my+dir%2F</Prefix><Delimiter>%2F</Delimiter><EncodingType>url</EncodingType><IsTruncated>false</IsTruncated><Contents><Key>my+dir%2Fa%2Bb%E2%9C%93</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;etag&quot;</ETag><Size>1</Size></Contents><CommonPrefixes><Prefix>my+dir%2Fsub%0A%2F</Prefix></CommonPrefixes></ListBucketResult>"#;
+        let result = parse_result_from_bytes(body, false).expect("listing should parse");
+        assert_eq!(result.objects[0].key, "my dir/a+b\u{2713}");
+        assert_eq!(result.common_prefixes, vec!["my dir/sub\n/"]);
+        assert_eq!(result.prefix.as_deref(), Some("my dir/"));
+        assert_eq!(result.delimiter.as_deref(), Some("/"));
+
+        let mut keys = Vec::new();
+        parse_keys_from_bytes_with_handler(body, false, &mut |key| keys.push(key)).expect("listing should parse");
+        assert_eq!(keys, vec!["my dir/a+b\u{2713}"]);
+
+        // Without the encoding type, the same values are taken as they are
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated><CommonPrefixes><Prefix>a+b%2F</Prefix></CommonPrefixes></ListBucketResult>"#;
+        let result = parse_result_from_bytes(body, false).expect("listing should parse");
+        assert_eq!(result.common_prefixes, vec!["a+b%2F"]);
+
+        // S3 puts the encoding type after the objects
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated><Contents><Key>a+b%2Fc</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><Size>1</Size></Contents><EncodingType>url</EncodingType></ListBucketResult>"#;
+        let result = parse_result_from_bytes(body, false).expect("listing should parse");
+        assert_eq!(result.objects[0].key, "a b/c");
+    }
+
+    #[test]
+    fn parse_listing_passes_objects_on_as_they_are_read() {
+        // The first object is passed on before the parser reaches the malformed second one
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated><Contents><Key>a</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><Size>1</Size></Contents><Contents><Key>b</Key></Oops></ListBucketResult>"#;
+        let mut keys = Vec::new();
+        let err = parse_result_from_bytes_with_handler(body, false, &mut |object| keys.push(object.key))
+            .expect_err("malformed listing should fail");
+        assert!(matches!(err, ParseError::Xml(_)), "unexpected error: {err:?}");
+        assert_eq!(keys, vec!["a"]);
+    }
+
+    #[test]
+    fn parse_namespace_prefixed_listing() {
+        // Some S3-compatible stores qualify every element with an explicit namespace prefix
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><s3:ListBucketResult xmlns:s3="http://s3.amazonaws.com/doc/2006-03-01/"><s3:Name>test-bucket</s3:Name><s3:Prefix></s3:Prefix><s3:KeyCount>1</s3:KeyCount><s3:MaxKeys>1000</s3:MaxKeys><s3:Delimiter>/</s3:Delimiter><s3:IsTruncated>true</s3:IsTruncated><s3:NextContinuationToken>token</s3:NextContinuationToken><s3:Contents><s3:Key>a</s3:Key><s3:LastModified>2024-07-01T12:34:56.000Z</s3:LastModified><s3:ETag>&quot;9b2cf535f27731c974343645a3985328&quot;</s3:ETag><s3:Size>1</s3:Size><s3:StorageClass>STANDARD</s3:StorageClass></s3:Contents><s3:CommonPrefixes><s3:Prefix>b/</s3:Prefix></s3:CommonPrefixes></s3:ListBucketResult>"#;
+        let result = parse_result_from_bytes(body, false).expect("listing should parse");
+        assert_eq!(result.objects.len(), 1);
+        assert_eq!(result.objects[0].key, "a");
+        assert_eq!(result.objects[0].size, 1);
+        assert_eq!(result.common_prefixes, vec!["b/"]);
+        assert_eq!(result.next_continuation_token.as_deref(), Some("token"));
+        assert_eq!(result.delimiter.as_deref(), Some("/"));
+
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><s3:Error xmlns:s3="http://s3.amazonaws.com/doc/2006-03-01/"><s3:Code>NoSuchBucket</s3:Code><s3:Message>The specified bucket does not exist</s3:Message></s3:Error>"#;
+        let result = make_result(404, OsStr::from_bytes(body));
+        assert_eq!(parse_list_objects_error(&result), Some(ListObjectsError::NoSuchBucket));
+    }
+
+    #[test]
+    fn parse_keys_only() {
+        // The key-only parser doesn't need the other fields, so shouldn't fail without them
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated><Contents><Key>a</Key></Contents><Contents><Key>c</Key><Size>not a number</Size></Contents><CommonPrefixes><Prefix>b/</Prefix></CommonPrefixes></ListBucketResult>"#;
+        let mut keys = Vec::new();
+        let result =
+            parse_keys_from_bytes_with_handler(body, false, &mut |key| keys.push(key)).expect("keys should parse");
+        assert_eq!(keys, vec!["a", "c"]);
+        assert_eq!(result.common_prefixes, vec!["b/"]);
+        assert!(result.next_continuation_token.is_none());
+
+        let mut keys = Vec::new();
+        let result = parse_keys_from_bytes_with_handler(TRUNCATED_WITHOUT_TOKEN, true, &mut |key| keys.push(key))
+            .expect("fallback should continue");
+        assert_eq!(keys, vec!["a"]);
+        assert_eq!(
+            result.next_continuation_token,
+            Some(format!("{START_AFTER_TOKEN_PREFIX}b/{}", char::MAX))
+        );
+    }
+
+    #[test]
+    fn optional_object_attributes() {
+        assert_eq!(optional_object_attributes_header(&[]), None);
+        assert_eq!(
+            optional_object_attributes_header(&[OptionalObjectAttribute::RestoreStatus]).as_deref(),
+            Some("RestoreStatus")
+        );
+        assert_eq!(
+            optional_object_attributes_header(&[
+                OptionalObjectAttribute::RestoreStatus,
+                OptionalObjectAttribute::Other("Owner".to_owned()),
+            ])
+            .as_deref(),
+            Some("RestoreStatus,Owner")
+        );
+    }
+
+    #[test]
+    fn parse_without_restore_status() {
+        // Listings without the RestoreStatus optional attribute don't include the element at all
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated><Contents><Key>a</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;etag&quot;</ETag><Size>1</Size><StorageClass>GLACIER</StorageClass></Contents></ListBucketResult>"#;
+        let result = parse_result_from_bytes(body, false).expect("listing should parse");
+        assert_eq!(result.objects.len(), 1);
+        assert_eq!(result.objects[0].storage_class.as_deref(), Some("GLACIER"));
+        assert!(result.objects[0].restore_status.is_none());
+    }
+
+    /// A listing of a directory bucket, whose keys aren't in lexicographic order
+    const DIRECTORY_BUCKET_LISTING: &[u8] = br#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket--use1-az4--x-s3</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>4</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <Delimiter>/</Delimiter>
+  <IsTruncated>false</IsTruncated>
+  <Contents><Key>dir/c</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;a5c1f8c7e1f54a6b8dc0a1c5f1f1e3b2&quot;</ETag><Size>5</Size><StorageClass>EXPRESS_ONEZONE</StorageClass></Contents>
+  <Contents><Key>dir/a</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;6805f2cfc46c0f04559748bb039d69ae&quot;</ETag><Size>0</Size><StorageClass>EXPRESS_ONEZONE</StorageClass></Contents>
+  <Contents><Key>dir/b</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;c4ca4238a0b923820dcc509a6f75849b&quot;</ETag><Size>7</Size><StorageClass>EXPRESS_ONEZONE</StorageClass></Contents>
+  <CommonPrefixes><Prefix>dir/sub/</Prefix></CommonPrefixes>
+</ListBucketResult>"#;
+
+    #[test]
+    fn parse_directory_bucket_listing() {
+        let result = parse_result_from_bytes(DIRECTORY_BUCKET_LISTING, false).expect("listing should parse");
+        let objects: Vec<_> = result
+            .objects
+            .iter()
+            .map(|object| (object.key.as_str(), object.size, object.etag.as_deref()))
+            .collect();
+        assert_eq!(
+            objects,
+            vec![
+                ("dir/c", 5, Some("\"a5c1f8c7e1f54a6b8dc0a1c5f1f1e3b2\"")),
+                ("dir/a", 0, Some("\"6805f2cfc46c0f04559748bb039d69ae\"")),
+                ("dir/b", 7, Some("\"c4ca4238a0b923820dcc509a6f75849b\"")),
+            ]
+        );
+        assert!(result
+            .objects
+            .iter()
+            .all(|object| object.storage_class.as_deref() == Some("EXPRESS_ONEZONE")));
+        assert_eq!(result.common_prefixes, vec!["dir/sub/".to_string()]);
+        assert_eq!(result.next_continuation_token, None);
+    }
+
+    #[test]
+    fn parse_listing_without_size_or_etag() {
+        // Some S3-compatible endpoints leave the size or ETag out of some entries
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated><Contents><Key>a</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;6805f2cfc46c0f04559748bb039d69ae&quot;</ETag></Contents><Contents><Key>b</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><Size>7</Size></Contents></ListBucketResult>"#;
+        let result = parse_result_from_bytes(body, false).expect("listing should parse");
+        let objects: Vec<_> = result
+            .objects
+            .iter()
+            .map(|object| (object.key.as_str(), object.size, object.etag.as_deref()))
+            .collect();
+        assert_eq!(
+            objects,
+            vec![("a", 0, Some("\"6805f2cfc46c0f04559748bb039d69ae\"")), ("b", 7, None)]
+        );
+    }
+
+    #[test]
+    fn parse_invalid_size() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated><Contents><Key>a</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><Size>big</Size></Contents></ListBucketResult>"#;
+        let err = parse_result_from_bytes(body, false).expect_err("invalid size should fail");
+        assert!(matches!(err, ParseError::Int(_, _)), "got {err:?}");
+    }
+
+    #[test]
+    fn parse_checksum_type() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListBucketResult><Name>test-bucket</Name><IsTruncated>false</IsTruncated><Contents><Key>a</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;etag&quot;</ETag><ChecksumAlgorithm>CRC64NVME</ChecksumAlgorithm><ChecksumType>FULL_OBJECT</ChecksumType><Size>1</Size></Contents><Contents><Key>b</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;etag-2&quot;</ETag><ChecksumAlgorithm>CRC32C</ChecksumAlgorithm><ChecksumType>COMPOSITE</ChecksumType><Size>1</Size></Contents><Contents><Key>c</Key><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;etag&quot;</ETag><Size>1</Size></Contents></ListBucketResult>"#;
+        let result = parse_result_from_bytes(body, false).expect("listing should parse");
+        let checksum_types: Vec<_> = result.objects.iter().map(|object| object.checksum_type).collect();
+        assert_eq!(
+            checksum_types,
+            vec![Some(ChecksumType::FullObject), Some(ChecksumType::Composite), None]
+        );
+    }
+
+    #[test]
+    fn parse_cut_off_response() {
+        // Cut the body off inside an attribute, inside text, between elements, and inside a closing tag
+        for len in [100, 114, 128, TRUNCATED_WITHOUT_TOKEN.len() - 1] {
+            let body = &TRUNCATED_WITHOUT_TOKEN[..len];
+            let err = parse_result_from_bytes(body, true).expect_err("cut-off response should fail");
+            assert!(
+                matches!(err, ParseError::TruncatedResponse(l) if l == len),
+                "unexpected error for {len} bytes: {err:?}"
+            );
         }
+
+        let err = parse_result_from_bytes(b"<ListBucketResult></Contents>", true).expect_err("invalid XML should fail");
+        assert!(matches!(err, ParseError::Xml(_)));
+    }
+
+    #[test]
+    fn xml_snippet_is_bounded() {
+        let element = parse_xml(b"<Contents><Key>a</Key></Contents>").unwrap();
+        assert_eq!(xml_snippet(&element), "<Contents><Key>a</Key></Contents>");
+
+        let keys: String = (0..100).map(|i| format!("<Key>\u{2713}{i}</Key>")).collect();
+        let element = parse_xml(format!("<Contents>{keys}</Contents>").as_bytes()).unwrap();
+        let snippet = xml_snippet(&element);
+        assert!(snippet.starts_with("<Contents><Key>\u{2713}0</Key>"), "got {snippet}");
+        assert!(snippet.ends_with("..."), "got {snippet}");
+        assert!(snippet.len() <= MAX_XML_SNIPPET_LENGTH + "...".len());
+
+        let err = get_field(&element, "Size").expect_err("missing field should fail");
+        let ParseError::MissingField(snippet, field) = err else {
+            panic!("wrong error: {err:?}");
+        };
+        assert_eq!(field, "Size");
+        assert!(snippet.len() <= MAX_XML_SNIPPET_LENGTH + "...".len());
+    }
+
+    #[test]
+    fn start_after_last_key() {
+        assert_eq!(
+            start_after_key(Some("c".to_string()), &["b/".to_string()]).as_deref(),
+            Some("c")
+        );
+        assert_eq!(
+            start_after_key(Some("a".to_string()), &["b/".to_string()]),
+            Some(format!("b/{}", char::MAX))
+        );
+        assert_eq!(start_after_key(Some("a".to_string()), &[]).as_deref(), Some("a"));
+        assert_eq!(start_after_key(None, &[]), None);
+    }
+
+    #[test]
+    fn parse_301_permanent_redirect() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>PermanentRedirect</Code><Message>The bucket you are attempting to access must be addressed using the specified endpoint. Please send all future requests to this endpoint.</Message><Endpoint>DOC-EXAMPLE-BUCKET.s3-eu-west-1.amazonaws.com</Endpoint><Bucket>DOC-EXAMPLE-BUCKET</Bucket><RequestId>CM0Z9YFABRVSWXDJ</RequestId><HostId>HHmbUtaLRJ9fTyGBDpsk3Dy6K2qOZdorjhBXIVvW6xcdsI1uAHzrhifn6UJjWpxAzVJ1ncBTTeY=</HostId></Error>"#;
+        let result = MetaRequestResultBuilder::new(301)
+            .header("x-amz-bucket-region", "eu-west-1")
+            .body(OsStr::from_bytes(&body[..]))
+            .build();
+        let result = parse_list_objects_error(&result);
+        assert_eq!(
+            result,
+            Some(ListObjectsError::WrongRegion {
+                expected: "eu-west-1".to_owned()
+            })
+        );
     }
 
     #[test]
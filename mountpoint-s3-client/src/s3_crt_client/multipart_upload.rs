@@ -0,0 +1,665 @@
+use std::ops::Deref;
+use std::os::unix::prelude::OsStrExt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use thiserror::Error;
+use tracing::debug;
+use xmltree::{Element, XMLNode};
+
+use crate::object_client::{Checksum, ETag, ObjectClientError, ObjectClientResult, ResponseMetadata};
+use crate::s3_crt_client::list_objects::{get_field, get_text, parse_xml, ParseError};
+use crate::s3_crt_client::{retry_backoff, QueryFragment, S3CrtClient, S3CrtClientInner, S3RequestError};
+
+/// Errors returned by the multipart upload requests of an [S3CrtClient]:
+/// [`create_multipart_upload`](S3CrtClient::create_multipart_upload),
+/// [`upload_part`](S3CrtClient::upload_part),
+/// [`complete_multipart_upload`](S3CrtClient::complete_multipart_upload), and
+/// [`abort_multipart_upload`](S3CrtClient::abort_multipart_upload).
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MultipartUploadError {
+    #[error("The bucket does not exist")]
+    NoSuchBucket,
+
+    /// The upload ID doesn't exist, or the upload was already completed or aborted
+    #[error("The multipart upload does not exist")]
+    NoSuchUpload,
+
+    /// A part to complete the upload with wasn't uploaded, or its ETag doesn't match
+    #[error("One or more of the specified parts could not be found")]
+    InvalidPart,
+
+    #[error("The parts were not listed in ascending order of part number")]
+    InvalidPartOrder,
+
+    /// Every part except the last must be at least 5 MiB
+    #[error("One or more of the parts is smaller than the minimum part size")]
+    EntityTooSmall,
+}
+
+/// Result of a [`create_multipart_upload`](S3CrtClient::create_multipart_upload) request
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CreateMultipartUploadResult {
+    /// The ID of the new upload, to pass to the other multipart upload requests
+    pub upload_id: String,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Result of an [`upload_part`](S3CrtClient::upload_part) request
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct UploadPartResult {
+    /// The ETag of the uploaded part, to pass to
+    /// [`complete_multipart_upload`](S3CrtClient::complete_multipart_upload)
+    pub etag: ETag,
+
+    /// The number of times the part was retried after a transient error before it was uploaded.
+    /// See [S3ClientConfig::upload_part_retries](crate::config::S3ClientConfig::upload_part_retries).
+    pub retries: u32,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// Result of a [`complete_multipart_upload`](S3CrtClient::complete_multipart_upload) request
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CompleteMultipartUploadResult {
+    /// The ETag of the completed object
+    pub etag: ETag,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// A part to complete a multipart upload with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletedPart {
+    /// The number of the part, between 1 and 10,000
+    pub part_number: u32,
+
+    /// The ETag S3 returned when the part was uploaded
+    pub etag: ETag,
+}
+
+impl CompletedPart {
+    /// Create a part from its number and the ETag of its upload.
+    pub fn new(part_number: u32, etag: ETag) -> Self {
+        Self { part_number, etag }
+    }
+}
+
+/// Result of a [`list_parts`](S3CrtClient::list_parts) request
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ListPartsResult {
+    /// The parts uploaded so far in this page of the listing, in ascending order of part number
+    pub parts: Vec<UploadedPart>,
+
+    /// If present, the part number marker to use to query more results.
+    pub next_part_number_marker: Option<u32>,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// A part of a multipart upload that has already been uploaded, returned by a
+/// [`list_parts`](S3CrtClient::list_parts) request
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct UploadedPart {
+    /// The number of the part
+    pub part_number: u32,
+
+    /// The ETag of the part
+    pub etag: ETag,
+
+    /// Size of the part in bytes
+    pub size: u64,
+
+    /// The checksum of the part, if it was uploaded with one
+    pub checksum: Checksum,
+}
+
+impl From<&UploadedPart> for CompletedPart {
+    fn from(part: &UploadedPart) -> Self {
+        Self::new(part.part_number, part.etag.clone())
+    }
+}
+
+impl S3CrtClient {
+    /// Start a new multipart upload with a CreateMultipartUpload request.
+    ///
+    /// Most callers should use [`put_object`](crate::ObjectClient::put_object), which splits the
+    /// body into parts automatically. These requests are for callers that need to control the
+    /// parts themselves. An upload that's never completed keeps its parts, and is billed for them,
+    /// until it's aborted with [`abort_multipart_upload`](Self::abort_multipart_upload).
+    pub async fn create_multipart_upload(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<CreateMultipartUploadResult, MultipartUploadError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("POST", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path_and_query(format!("/{key}"), &[QueryFragment::Flag("uploads")])
+                .map_err(S3RequestError::construction_failure)?;
+
+            let span = request_span!(self, "create_multipart_upload", bucket, key);
+            self.inner.make_simple_http_request(
+                message,
+                MetaRequestType::Default,
+                span,
+                parse_multipart_upload_error,
+            )?
+        };
+
+        let response = request.await?;
+
+        let upload_id = parse_create_multipart_upload_response(&response.body)
+            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))?;
+        Ok(CreateMultipartUploadResult {
+            upload_id,
+            response_metadata: response.metadata,
+        })
+    }
+
+    /// Upload one part of a multipart upload with an UploadPart request. Uploading a part with the
+    /// same number as an earlier one replaces it.
+    ///
+    /// A part that fails with a transient error is retried up to
+    /// [upload_part_retries](crate::config::S3ClientConfig::upload_part_retries) times, and the
+    /// result says how many retries it took.
+    pub async fn upload_part(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        part_number: u32,
+        body: Vec<u8>,
+    ) -> ObjectClientResult<UploadPartResult, MultipartUploadError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+
+        if self.inner.upload_part_retries == 0 {
+            return self
+                .upload_part_request(bucket, key, upload_id, part_number, body)
+                .await;
+        }
+
+        // Every attempt sends the same bytes, so share them rather than copying the body each time
+        let body: Arc<[u8]> = body.into();
+        let mut attempt: u32 = 1;
+        loop {
+            let retries_left = attempt <= self.inner.upload_part_retries;
+            let result = self
+                .upload_part_request(bucket, key, upload_id, part_number, body.clone())
+                .await;
+            match result {
+                Ok(mut result) => {
+                    result.retries = attempt - 1;
+                    return Ok(result);
+                }
+                Err(ObjectClientError::ClientError(e)) if e.is_transient() && retries_left => {
+                    let backoff = retry_backoff(attempt, self.inner.initial_backoff, self.inner.max_backoff);
+                    debug!(
+                        attempt,
+                        ?backoff,
+                        part_number,
+                        error = ?e,
+                        "retrying UploadPart that failed with a transient error"
+                    );
+                    metrics::counter!("s3.client.upload_part_retries").increment(1);
+                    if !self.sleep(backoff).await? {
+                        return Err(ObjectClientError::ClientError(e));
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Make a single UploadPart request, without the retries of [`upload_part`](Self::upload_part).
+    async fn upload_part_request(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        part_number: u32,
+        body: impl AsRef<[u8]> + Send + Sync + 'static,
+    ) -> ObjectClientResult<UploadPartResult, MultipartUploadError, S3RequestError> {
+        let etag: Arc<Mutex<Option<String>>> = Default::default();
+        let etag_writer = etag.clone();
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("PUT", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            let part_number_str = part_number.to_string();
+            message
+                .set_request_path_and_query(
+                    format!("/{key}"),
+                    &[
+                        QueryFragment::Pair("partNumber", &part_number_str),
+                        QueryFragment::Pair("uploadId", upload_id),
+                    ],
+                )
+                .map_err(S3RequestError::construction_failure)?;
+            let size = body.as_ref().len();
+            message.set_body(body).map_err(S3RequestError::construction_failure)?;
+
+            let span = request_span!(self, "upload_part", bucket, key, upload_id, part_number, size);
+
+            let request_timeouts = message.request_timeouts;
+            let verify_body_length = message.verify_body_length;
+            let options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::Default, &span);
+            self.inner.make_simple_http_request_from_options(
+                options,
+                request_timeouts,
+                span,
+                |_| {},
+                parse_multipart_upload_error,
+                move |headers, status| {
+                    if (200..300).contains(&status) {
+                        *etag_writer.lock().unwrap() = headers
+                            .get("ETag")
+                            .ok()
+                            .and_then(|header| header.value().clone().into_string().ok());
+                    }
+                },
+                false,
+                verify_body_length,
+            )?
+        };
+
+        let response = request.await?;
+
+        let etag = etag.lock().unwrap().take().ok_or_else(|| {
+            ObjectClientError::ClientError(S3RequestError::InternalError(
+                "UploadPart response is missing the ETag header".into(),
+            ))
+        })?;
+        Ok(UploadPartResult {
+            etag: ETag::from_str(&etag).unwrap(),
+            retries: 0,
+            response_metadata: response.metadata,
+        })
+    }
+
+    /// Complete a multipart upload with a CompleteMultipartUpload request, assembling the given
+    /// parts into the object. The parts must be listed in ascending order of part number.
+    pub async fn complete_multipart_upload(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        parts: &[CompletedPart],
+    ) -> ObjectClientResult<CompleteMultipartUploadResult, MultipartUploadError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("POST", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path_and_query(format!("/{key}"), &[QueryFragment::Pair("uploadId", upload_id)])
+                .map_err(S3RequestError::construction_failure)?;
+
+            let body = build_complete_multipart_upload_body(parts)
+                .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(Box::new(e))))?;
+            message.set_body(body).map_err(S3RequestError::construction_failure)?;
+            // Like CopyObject, a successful response status doesn't mean the upload completed
+            message.check_embedded_error();
+
+            let num_parts = parts.len();
+            let span = request_span!(self, "complete_multipart_upload", bucket, key, upload_id, num_parts);
+            self.inner.make_simple_http_request(
+                message,
+                MetaRequestType::Default,
+                span,
+                parse_multipart_upload_error,
+            )?
+        };
+
+        let response = request.await?;
+
+        let mut result = parse_complete_multipart_upload_response(&response.body)?;
+        result.response_metadata = response.metadata;
+        Ok(result)
+    }
+
+    /// Abort a multipart upload with an AbortMultipartUpload request, deleting the parts uploaded
+    /// so far. Parts that are still being uploaded might not be deleted until they finish.
+    pub async fn abort_multipart_upload(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+    ) -> ObjectClientResult<ResponseMetadata, MultipartUploadError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("DELETE", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path_and_query(format!("/{key}"), &[QueryFragment::Pair("uploadId", upload_id)])
+                .map_err(S3RequestError::construction_failure)?;
+
+            let span = request_span!(self, "abort_multipart_upload", bucket, key, upload_id);
+            self.inner.make_simple_http_request(
+                message,
+                MetaRequestType::Default,
+                span,
+                parse_multipart_upload_error,
+            )?
+        };
+
+        let response = request.await?;
+        Ok(response.metadata)
+    }
+
+    /// List the parts uploaded so far to a multipart upload with a ListParts request, for example
+    /// to resume an interrupted upload without uploading those parts again.
+    ///
+    /// To continue a truncated listing, pass the `next_part_number_marker` of the previous page as
+    /// `part_number_marker`.
+    pub async fn list_parts(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        part_number_marker: Option<u32>,
+    ) -> ObjectClientResult<ListPartsResult, MultipartUploadError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("GET", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            let part_number_marker_str = part_number_marker.map(|marker| marker.to_string());
+            let mut query = Vec::new();
+            if let Some(part_number_marker) = part_number_marker_str.as_deref() {
+                query.push(QueryFragment::Pair("part-number-marker", part_number_marker));
+            }
+            query.push(QueryFragment::Pair("uploadId", upload_id));
+            message
+                .set_request_path_and_query(format!("/{key}"), &query)
+                .map_err(S3RequestError::construction_failure)?;
+
+            let span = request_span!(self, "list_parts", bucket, key, upload_id, ?part_number_marker);
+            self.inner.make_simple_http_request(
+                message,
+                MetaRequestType::Default,
+                span,
+                parse_multipart_upload_error,
+            )?
+        };
+
+        let response = request.await?;
+
+        let mut result = parse_list_parts_response(&response.body)
+            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))?;
+        result.response_metadata = response.metadata;
+        Ok(result)
+    }
+}
+
+fn parse_create_multipart_upload_response(body: &[u8]) -> Result<String, ParseError> {
+    let root = parse_xml(body)?;
+    get_field(&root, "UploadId")
+}
+
+fn parse_number<T: FromStr<Err = std::num::ParseIntError>>(element: &Element, name: &str) -> Result<T, ParseError> {
+    get_field(element, name)?
+        .parse()
+        .map_err(|e| ParseError::Int(e, name.to_string()))
+}
+
+fn parse_uploaded_part_from_xml(element: &Element) -> Result<UploadedPart, ParseError> {
+    let optional_field = |name: &str| element.get_child(name).map(get_text).transpose();
+    Ok(UploadedPart {
+        part_number: parse_number(element, "PartNumber")?,
+        etag: ETag::from_str(&get_field(element, "ETag")?).unwrap(),
+        size: parse_number(element, "Size")?,
+        checksum: Checksum {
+            checksum_crc32: optional_field("ChecksumCRC32")?,
+            checksum_crc32c: optional_field("ChecksumCRC32C")?,
+            checksum_sha1: optional_field("ChecksumSHA1")?,
+            checksum_sha256: optional_field("ChecksumSHA256")?,
+            checksum_crc64nvme: optional_field("ChecksumCRC64NVME")?,
+            checksum_type: None,
+        },
+    })
+}
+
+fn parse_list_parts_response(body: &[u8]) -> Result<ListPartsResult, ParseError> {
+    let mut element = parse_xml(body)?;
+
+    let mut parts = Vec::new();
+    while let Some(part) = element.take_child("Part") {
+        parts.push(parse_uploaded_part_from_xml(&part)?);
+    }
+
+    // S3 includes `NextPartNumberMarker` in every page, so only the truncation flag says whether
+    // there are more parts to list
+    let is_truncated = get_field(&element, "IsTruncated")?;
+    let is_truncated = bool::from_str(&is_truncated).map_err(|e| ParseError::Bool(e, "IsTruncated".to_string()))?;
+    let next_part_number_marker = if is_truncated {
+        Some(parse_number(&element, "NextPartNumberMarker")?)
+    } else {
+        None
+    };
+
+    Ok(ListPartsResult {
+        parts,
+        next_part_number_marker,
+        response_metadata: Default::default(),
+    })
+}
+
+/// Build the XML body of a CompleteMultipartUpload request.
+fn build_complete_multipart_upload_body(parts: &[CompletedPart]) -> Result<Vec<u8>, xmltree::Error> {
+    let text_element = |name: &str, text: &str| {
+        let mut element = Element::new(name);
+        element.children.push(XMLNode::Text(text.to_owned()));
+        element
+    };
+
+    let mut root = Element::new("CompleteMultipartUpload");
+    for part in parts {
+        let mut part_element = Element::new("Part");
+        part_element.children.push(XMLNode::Element(text_element(
+            "PartNumber",
+            &part.part_number.to_string(),
+        )));
+        part_element
+            .children
+            .push(XMLNode::Element(text_element("ETag", part.etag.as_str())));
+        root.children.push(XMLNode::Element(part_element));
+    }
+
+    let mut body = Vec::new();
+    root.write(&mut body)?;
+    Ok(body)
+}
+
+fn parse_complete_multipart_upload_response(
+    body: &[u8],
+) -> ObjectClientResult<CompleteMultipartUploadResult, MultipartUploadError, S3RequestError> {
+    let parse_error = |e: ParseError| ObjectClientError::ClientError(S3RequestError::InternalError(e.into()));
+
+    let root = Element::parse(body).map_err(|e| parse_error(e.into()))?;
+    let etag = get_field(&root, "ETag").map_err(parse_error)?;
+    Ok(CompleteMultipartUploadResult {
+        etag: ETag::from_str(&etag).unwrap(),
+        response_metadata: Default::default(),
+    })
+}
+
+fn service_error_from_code(code: &str) -> Option<MultipartUploadError> {
+    match code {
+        "NoSuchBucket" => Some(MultipartUploadError::NoSuchBucket),
+        "NoSuchUpload" => Some(MultipartUploadError::NoSuchUpload),
+        "InvalidPart" => Some(MultipartUploadError::InvalidPart),
+        "InvalidPartOrder" => Some(MultipartUploadError::InvalidPartOrder),
+        "EntityTooSmall" => Some(MultipartUploadError::EntityTooSmall),
+        _ => None,
+    }
+}
+
+fn parse_multipart_upload_error(result: &MetaRequestResult) -> Option<MultipartUploadError> {
+    match result.response_status {
+        // Errors embedded in a successful CompleteMultipartUpload response keep its 200 status
+        200 | 400 | 404 => {
+            let body = result.error_response_body.as_ref()?;
+            let root = parse_xml(body.as_bytes()).ok()?;
+            let error_code = root.get_child("Code")?.get_text()?;
+            service_error_from_code(error_code.deref())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    fn make_result(response_status: i32, body: impl Into<OsString>) -> MetaRequestResult {
+        MetaRequestResult {
+            response_status,
+            crt_error: 1i32.into(),
+            error_response_headers: None,
+            error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
+        }
+    }
+
+    fn error_body(code: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>{code}</Code><Message>Some message</Message><RequestId>4VAGDP5HMYTDNB3Y</RequestId></Error>"#
+        )
+    }
+
+    #[test_case(404, "NoSuchBucket", Some(MultipartUploadError::NoSuchBucket); "no such bucket")]
+    #[test_case(404, "NoSuchUpload", Some(MultipartUploadError::NoSuchUpload); "no such upload")]
+    #[test_case(400, "InvalidPart", Some(MultipartUploadError::InvalidPart); "invalid part")]
+    #[test_case(400, "InvalidPartOrder", Some(MultipartUploadError::InvalidPartOrder); "invalid part order")]
+    #[test_case(400, "EntityTooSmall", Some(MultipartUploadError::EntityTooSmall); "entity too small")]
+    #[test_case(400, "InvalidArgument", None; "unmodeled error")]
+    #[test_case(403, "NoSuchUpload", None; "unexpected status")]
+    fn parse_error(status: i32, code: &str, expected: Option<MultipartUploadError>) {
+        let result = make_result(status, error_body(code));
+        assert_eq!(parse_multipart_upload_error(&result), expected);
+    }
+
+    #[test]
+    fn parse_upload_id() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><InitiateMultipartUploadResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Bucket>test-bucket</Bucket><Key>key</Key><UploadId>VXBsb2FkIElEIGZvciBlbHZpbmcncyBteS1tb3ZpZS5tMnRzIHVwbG9hZA</UploadId></InitiateMultipartUploadResult>"#;
+        let upload_id = parse_create_multipart_upload_response(&body[..]).expect("result should parse");
+        assert_eq!(upload_id, "VXBsb2FkIElEIGZvciBlbHZpbmcncyBteS1tb3ZpZS5tMnRzIHVwbG9hZA");
+    }
+
+    #[test]
+    fn complete_request_body() {
+        let parts = [
+            CompletedPart::new(1, ETag::from_str("\"a54357aff0632cce46d942af68356b38\"").unwrap()),
+            CompletedPart::new(2, ETag::from_str("\"0c78aef83f66abc1fa1e8477f296d394\"").unwrap()),
+        ];
+        let body = build_complete_multipart_upload_body(&parts).expect("body should serialize");
+        let root = Element::parse(&body[..]).expect("body should parse");
+        assert_eq!(root.name, "CompleteMultipartUpload");
+        let parsed: Vec<_> = root
+            .children
+            .iter()
+            .filter_map(|node| node.as_element())
+            .map(|part| {
+                assert_eq!(part.name, "Part");
+                (get_field(part, "PartNumber").unwrap(), get_field(part, "ETag").unwrap())
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                ("1".to_string(), "\"a54357aff0632cce46d942af68356b38\"".to_string()),
+                ("2".to_string(), "\"0c78aef83f66abc1fa1e8477f296d394\"".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_complete_result() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><CompleteMultipartUploadResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Location>https://test-bucket.s3.amazonaws.com/key</Location><Bucket>test-bucket</Bucket><Key>key</Key><ETag>&quot;3858f62230ac3c915f300c664312c11f-9&quot;</ETag></CompleteMultipartUploadResult>"#;
+        let result = parse_complete_multipart_upload_response(&body[..]).expect("result should parse");
+        assert_eq!(result.etag.as_str(), "\"3858f62230ac3c915f300c664312c11f-9\"");
+    }
+
+    #[test]
+    fn parse_complete_embedded_error() {
+        let body = error_body("InvalidPart");
+        let result = make_result(200, body);
+        assert_eq!(
+            parse_multipart_upload_error(&result),
+            Some(MultipartUploadError::InvalidPart)
+        );
+    }
+
+    #[test]
+    fn parse_list_parts() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?>
+<ListPartsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Bucket>test-bucket</Bucket><Key>key</Key><UploadId>upload</UploadId>
+  <PartNumberMarker>0</PartNumberMarker><NextPartNumberMarker>2</NextPartNumberMarker>
+  <MaxParts>2</MaxParts><IsTruncated>true</IsTruncated>
+  <Part><PartNumber>1</PartNumber><LastModified>2024-07-01T12:34:56.000Z</LastModified><ETag>&quot;7778aef83f66abc1fa1e8477f296d394&quot;</ETag><Size>5242880</Size><ChecksumCRC32C>AAAAAA==</ChecksumCRC32C></Part>
+  <Part><PartNumber>2</PartNumber><LastModified>2024-07-01T12:35:56.000Z</LastModified><ETag>&quot;aaaa18db4cc2f85cedef654fccc4a4x8&quot;</ETag><Size>100</Size></Part>
+</ListPartsResult>"#;
+        let result = parse_list_parts_response(&body[..]).expect("listing should parse");
+
+        let parts: Vec<_> = result
+            .parts
+            .iter()
+            .map(|p| (p.part_number, p.etag.as_str(), p.size))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                (1, "\"7778aef83f66abc1fa1e8477f296d394\"", 5242880),
+                (2, "\"aaaa18db4cc2f85cedef654fccc4a4x8\"", 100),
+            ]
+        );
+        assert_eq!(result.parts[0].checksum.checksum_crc32c.as_deref(), Some("AAAAAA=="));
+        assert_eq!(result.parts[1].checksum.checksum_crc32c, None);
+        assert_eq!(result.next_part_number_marker, Some(2));
+
+        let completed: CompletedPart = (&result.parts[1]).into();
+        assert_eq!(completed.part_number, 2);
+    }
+
+    #[test]
+    fn parse_list_parts_last_page() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><ListPartsResult><Bucket>test-bucket</Bucket><Key>key</Key><UploadId>upload</UploadId><PartNumberMarker>2</PartNumberMarker><NextPartNumberMarker>3</NextPartNumberMarker><IsTruncated>false</IsTruncated><Part><PartNumber>3</PartNumber><ETag>&quot;etag&quot;</ETag><Size>7</Size></Part></ListPartsResult>"#;
+        let result = parse_list_parts_response(&body[..]).expect("listing should parse");
+        assert_eq!(result.parts.len(), 1);
+        assert_eq!(result.next_part_number_marker, None);
+    }
+}
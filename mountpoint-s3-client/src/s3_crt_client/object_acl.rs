@@ -0,0 +1,325 @@
+use std::os::unix::prelude::OsStrExt;
+
+use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use thiserror::Error;
+use xmltree::Element;
+
+use crate::object_client::{ObjectClientError, ObjectClientResult, ResponseMetadata};
+use crate::s3_crt_client::list_objects::{get_child, get_field, parse_xml, xml_snippet, ParseError};
+use crate::s3_crt_client::{parse_s3_error_body, QueryFragment, S3CrtClient, S3RequestError};
+
+/// Errors returned by a [`get_object_acl`](S3CrtClient::get_object_acl) request
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GetObjectAclError {
+    #[error("The bucket does not exist")]
+    NoSuchBucket,
+
+    #[error("The key does not exist")]
+    NoSuchKey,
+}
+
+/// The access control list of an object, returned by a
+/// [`get_object_acl`](S3CrtClient::get_object_acl) request
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ObjectAcl {
+    /// The owner of the object
+    pub owner: AclOwner,
+
+    /// The permissions granted on the object, in the order S3 returned them
+    pub grants: Vec<AclGrant>,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+/// The owner of an object, from its [ObjectAcl]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AclOwner {
+    /// The canonical user ID of the owner
+    pub id: String,
+
+    /// The display name of the owner. S3 only returns it in some regions.
+    pub display_name: Option<String>,
+}
+
+/// A permission granted to a grantee in an [ObjectAcl]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AclGrant {
+    /// Who the permission is granted to
+    pub grantee: AclGrantee,
+
+    /// The permission granted
+    pub permission: AclPermission,
+}
+
+/// The grantee of an [AclGrant]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AclGrantee {
+    /// An AWS account, by its canonical user ID
+    CanonicalUser { id: String, display_name: Option<String> },
+
+    /// A predefined group of users, like
+    /// `http://acs.amazonaws.com/groups/global/AllUsers`, by its URI
+    Group { uri: String },
+
+    /// An AWS account, by the email address of its owner
+    AmazonCustomerByEmail { email_address: String },
+}
+
+/// A permission in an [AclGrant]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AclPermission {
+    /// All of the other permissions
+    FullControl,
+    /// Read the object and its metadata
+    Read,
+    /// Write the object. S3 doesn't apply this permission to objects, but it can still be granted.
+    Write,
+    /// Read the object's ACL
+    ReadAcp,
+    /// Write the object's ACL
+    WriteAcp,
+    /// A permission this client doesn't recognize
+    Other(String),
+}
+
+impl AclPermission {
+    fn from_value(value: &str) -> Self {
+        match value {
+            "FULL_CONTROL" => AclPermission::FullControl,
+            "READ" => AclPermission::Read,
+            "WRITE" => AclPermission::Write,
+            "READ_ACP" => AclPermission::ReadAcp,
+            "WRITE_ACP" => AclPermission::WriteAcp,
+            other => AclPermission::Other(other.to_owned()),
+        }
+    }
+}
+
+impl S3CrtClient {
+    /// Get the access control list of an object with a GetObjectAcl request.
+    ///
+    /// Buckets with the default Object Ownership setting of bucket owner enforced have ACLs
+    /// disabled, and every object's ACL grants full control to just the bucket owner.
+    pub async fn get_object_acl(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<ObjectAcl, GetObjectAclError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+        self.with_additional_retries(|| self.get_object_acl_request(bucket, key))
+            .await
+    }
+
+    async fn get_object_acl_request(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<ObjectAcl, GetObjectAclError, S3RequestError> {
+        let span = request_span!(self, "get_object_acl", bucket, key);
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("GET", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path_and_query(format!("/{key}"), &[QueryFragment::Flag("acl")])
+                .map_err(S3RequestError::construction_failure)?;
+
+            self.inner
+                .make_simple_http_request(message, MetaRequestType::Default, span, parse_get_object_acl_error)?
+        };
+
+        let response = request.await?;
+
+        let mut acl = parse_acl(&response.body)
+            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))?;
+        acl.response_metadata = response.metadata;
+        Ok(acl)
+    }
+}
+
+/// Parse the `<AccessControlPolicy>` of a GetObjectAcl response.
+fn parse_acl(bytes: &[u8]) -> Result<ObjectAcl, ParseError> {
+    let root = parse_xml(bytes)?;
+
+    let owner = get_child(&root, "Owner")?;
+    let owner = AclOwner {
+        id: get_field(owner, "ID")?,
+        display_name: get_optional_field(owner, "DisplayName"),
+    };
+
+    let mut grants = Vec::new();
+    let access_control_list = get_child(&root, "AccessControlList")?;
+    for grant in access_control_list
+        .children
+        .iter()
+        .filter_map(|node| node.as_element())
+        .filter(|element| element.name == "Grant")
+    {
+        grants.push(AclGrant {
+            grantee: parse_grantee(get_child(grant, "Grantee")?)?,
+            permission: AclPermission::from_value(&get_field(grant, "Permission")?),
+        });
+    }
+
+    Ok(ObjectAcl {
+        owner,
+        grants,
+        response_metadata: Default::default(),
+    })
+}
+
+/// Parse a `<Grantee>`, whose kind is in its `xsi:type` attribute.
+fn parse_grantee(element: &Element) -> Result<AclGrantee, ParseError> {
+    // xmltree keys attributes by their local name, without the `xsi` prefix
+    let grantee_type = element
+        .attributes
+        .get("type")
+        .ok_or_else(|| ParseError::MissingField(xml_snippet(element), "xsi:type".to_string()))?;
+    match grantee_type.as_str() {
+        "CanonicalUser" => Ok(AclGrantee::CanonicalUser {
+            id: get_field(element, "ID")?,
+            display_name: get_optional_field(element, "DisplayName"),
+        }),
+        "Group" => Ok(AclGrantee::Group {
+            uri: get_field(element, "URI")?,
+        }),
+        "AmazonCustomerByEmail" => Ok(AclGrantee::AmazonCustomerByEmail {
+            email_address: get_field(element, "EmailAddress")?,
+        }),
+        other => Err(ParseError::InvalidResponse(
+            xml_snippet(element),
+            format!("unknown grantee type {other:?}"),
+        )),
+    }
+}
+
+fn get_optional_field(element: &Element, name: &str) -> Option<String> {
+    element
+        .get_child(name)
+        .and_then(|child| child.get_text())
+        .map(|text| text.into_owned())
+}
+
+fn parse_get_object_acl_error(result: &MetaRequestResult) -> Option<GetObjectAclError> {
+    match result.response_status {
+        404 => {
+            let body = result.error_response_body.as_ref()?;
+            let details = parse_s3_error_body(body.as_bytes())?;
+            match details.code.as_deref()? {
+                "NoSuchBucket" => Some(GetObjectAclError::NoSuchBucket),
+                "NoSuchKey" => Some(GetObjectAclError::NoSuchKey),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    fn make_result(response_status: i32, body: impl Into<OsString>) -> MetaRequestResult {
+        MetaRequestResult {
+            response_status,
+            crt_error: 1i32.into(),
+            error_response_headers: None,
+            error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
+        }
+    }
+
+    #[test_case("NoSuchBucket", Some(GetObjectAclError::NoSuchBucket); "no such bucket")]
+    #[test_case("NoSuchKey", Some(GetObjectAclError::NoSuchKey); "no such key")]
+    #[test_case("NoSuchVersion", None; "unmodeled error")]
+    fn parse_404(code: &str, expected: Option<GetObjectAclError>) {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>{code}</Code><Message>Some message</Message><RequestId>4VAGDP5HMYTDNB3Y</RequestId></Error>"#
+        );
+        let result = make_result(404, body);
+        assert_eq!(parse_get_object_acl_error(&result), expected);
+    }
+
+    #[test]
+    fn parse_acl_result() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?>
+<AccessControlPolicy xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Owner><ID>75aa57f09aa0c8caeab4f8c24e99d10f8e7faeebf76c078efc7c6caea54ba06a</ID><DisplayName>owner</DisplayName></Owner>
+  <AccessControlList>
+    <Grant>
+      <Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="CanonicalUser"><ID>75aa57f09aa0c8caeab4f8c24e99d10f8e7faeebf76c078efc7c6caea54ba06a</ID></Grantee>
+      <Permission>FULL_CONTROL</Permission>
+    </Grant>
+    <Grant>
+      <Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="Group"><URI>http://acs.amazonaws.com/groups/global/AllUsers</URI></Grantee>
+      <Permission>READ</Permission>
+    </Grant>
+    <Grant>
+      <Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="AmazonCustomerByEmail"><EmailAddress>user@example.com</EmailAddress></Grantee>
+      <Permission>READ_ACP</Permission>
+    </Grant>
+  </AccessControlList>
+</AccessControlPolicy>"#;
+        let acl = parse_acl(&body[..]).expect("result should parse");
+        let owner_id = "75aa57f09aa0c8caeab4f8c24e99d10f8e7faeebf76c078efc7c6caea54ba06a";
+        assert_eq!(
+            acl.owner,
+            AclOwner {
+                id: owner_id.to_owned(),
+                display_name: Some("owner".to_owned()),
+            }
+        );
+        assert_eq!(
+            acl.grants,
+            vec![
+                AclGrant {
+                    grantee: AclGrantee::CanonicalUser {
+                        id: owner_id.to_owned(),
+                        display_name: None,
+                    },
+                    permission: AclPermission::FullControl,
+                },
+                AclGrant {
+                    grantee: AclGrantee::Group {
+                        uri: "http://acs.amazonaws.com/groups/global/AllUsers".to_owned(),
+                    },
+                    permission: AclPermission::Read,
+                },
+                AclGrant {
+                    grantee: AclGrantee::AmazonCustomerByEmail {
+                        email_address: "user@example.com".to_owned(),
+                    },
+                    permission: AclPermission::ReadAcp,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_acl_unknown_grantee_type() {
+        let body = br#"<AccessControlPolicy><Owner><ID>owner</ID></Owner><AccessControlList><Grant><Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="Robot"><ID>r2d2</ID></Grantee><Permission>READ</Permission></Grant></AccessControlList></AccessControlPolicy>"#;
+        let err = parse_acl(&body[..]).expect_err("unknown grantee type should fail");
+        assert!(matches!(err, ParseError::InvalidResponse(_, _)), "got {err:?}");
+    }
+
+    #[test]
+    fn parse_acl_unknown_permission() {
+        let body = br#"<AccessControlPolicy><Owner><ID>owner</ID></Owner><AccessControlList><Grant><Grantee xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:type="CanonicalUser"><ID>owner</ID></Grantee><Permission>EXECUTE</Permission></Grant></AccessControlList></AccessControlPolicy>"#;
+        let acl = parse_acl(&body[..]).expect("result should parse");
+        assert_eq!(acl.grants[0].permission, AclPermission::Other("EXECUTE".to_owned()));
+    }
+}
@@ -0,0 +1,128 @@
+use mountpoint_s3_crt::http::request_response::Header;
+use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use thiserror::Error;
+
+use crate::object_client::{HeadObjectError, HeadObjectParams, ObjectClientError, ObjectClientResult, ObjectExistence};
+use crate::s3_crt_client::{S3CrtClient, S3RequestError};
+
+/// Outcomes of a ranged GET existence probe that the CRT reports as failed requests
+#[derive(Error, Debug, PartialEq, Eq)]
+enum RangedGetProbeError {
+    #[error("The object was not found")]
+    NotFound,
+
+    /// S3 can't satisfy a range on an empty object, but the object does exist
+    #[error("The object is empty")]
+    EmptyObject,
+
+    #[error("Access to the object was denied")]
+    AccessDenied,
+}
+
+impl S3CrtClient {
+    /// Check whether an object exists. Uses a HeadObject request unless the client is configured
+    /// to probe with a ranged GetObject request instead.
+    pub(super) async fn object_exists(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<ObjectExistence, HeadObjectError, S3RequestError> {
+        if self.inner.ranged_get_existence_check {
+            return self.ranged_get_probe(bucket, key).await;
+        }
+
+        match self.head_object(bucket, key, &HeadObjectParams::new()).await {
+            Ok(_) => Ok(ObjectExistence::Exists),
+            Err(ObjectClientError::ServiceError(HeadObjectError::NotFound)) => Ok(ObjectExistence::NotFound),
+            Err(ObjectClientError::ServiceError(HeadObjectError::AccessDenied(_))) => Ok(ObjectExistence::AccessDenied),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Check whether an object exists by requesting only its first byte.
+    async fn ranged_get_probe(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<ObjectExistence, HeadObjectError, S3RequestError> {
+        let span = request_span!(self, "ranged_get_probe", bucket, key);
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("GET", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+
+            // Overwrite "accept" header since this returns raw object data.
+            message
+                .set_header(&Header::new("accept", "*/*"))
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_header(&Header::new("Range", "bytes=0-0"))
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path(format!("/{key}"))
+                .map_err(S3RequestError::construction_failure)?;
+
+            self.inner.make_simple_http_request(
+                message,
+                MetaRequestType::Default,
+                span,
+                parse_ranged_get_probe_error,
+            )?
+        };
+
+        match request.await {
+            Ok(_response) => Ok(ObjectExistence::Exists),
+            Err(ObjectClientError::ServiceError(RangedGetProbeError::NotFound)) => Ok(ObjectExistence::NotFound),
+            Err(ObjectClientError::ServiceError(RangedGetProbeError::EmptyObject)) => Ok(ObjectExistence::Exists),
+            Err(ObjectClientError::ServiceError(RangedGetProbeError::AccessDenied)) => {
+                Ok(ObjectExistence::AccessDenied)
+            }
+            Err(ObjectClientError::ClientError(e)) => Err(ObjectClientError::ClientError(e)),
+        }
+    }
+}
+
+fn parse_ranged_get_probe_error(result: &MetaRequestResult) -> Option<RangedGetProbeError> {
+    match result.response_status {
+        // Like HeadObject, treat a missing bucket the same as a missing key
+        404 => Some(RangedGetProbeError::NotFound),
+        416 => Some(RangedGetProbeError::EmptyObject),
+        403 => Some(RangedGetProbeError::AccessDenied),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    fn make_result(response_status: i32, body: impl Into<OsString>) -> MetaRequestResult {
+        MetaRequestResult {
+            response_status,
+            crt_error: 1i32.into(),
+            error_response_headers: None,
+            error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
+        }
+    }
+
+    #[test_case(404, "NoSuchKey", Some(RangedGetProbeError::NotFound); "no such key")]
+    #[test_case(404, "NoSuchBucket", Some(RangedGetProbeError::NotFound); "no such bucket")]
+    #[test_case(416, "InvalidRange", Some(RangedGetProbeError::EmptyObject); "empty object")]
+    #[test_case(403, "AccessDenied", Some(RangedGetProbeError::AccessDenied); "access denied")]
+    #[test_case(500, "InternalError", None; "internal error")]
+    fn parse_probe_error(status: i32, code: &str, expected: Option<RangedGetProbeError>) {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>{code}</Code><Message>Some message</Message></Error>"#
+        );
+        let result = make_result(status, body);
+        assert_eq!(parse_ranged_get_probe_error(&result), expected);
+    }
+}
@@ -0,0 +1,253 @@
+use std::ops::Deref;
+use std::os::unix::prelude::OsStrExt;
+
+use mountpoint_s3_crt::checksums::crc32c;
+use mountpoint_s3_crt::http::request_response::Header;
+use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use xmltree::{Element, XMLNode};
+
+use crate::checksums::crc32c_to_base64;
+use crate::object_client::{
+    GetObjectTaggingError, ObjectClientError, ObjectClientResult, PutObjectTaggingError, MAX_OBJECT_TAGS,
+};
+use crate::s3_crt_client::list_objects::{get_child, get_text, parse_xml, ParseError};
+use crate::s3_crt_client::{ConstructionError, QueryFragment, S3CrtClient, S3RequestError};
+
+impl S3CrtClient {
+    /// Create and begin a new GetObjectTagging request.
+    pub(super) async fn get_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> ObjectClientResult<Vec<(String, String)>, GetObjectTaggingError, S3RequestError> {
+        let span = request_span!(self, "get_object_tagging", bucket, key);
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("GET", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path_and_query(format!("/{key}"), &[QueryFragment::Flag("tagging")])
+                .map_err(S3RequestError::construction_failure)?;
+
+            self.inner.make_simple_http_request(
+                message,
+                MetaRequestType::Default,
+                span,
+                parse_get_object_tagging_error,
+            )?
+        };
+
+        let response = request.await?;
+
+        parse_tagging(&response.body)
+            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(e.into())))
+    }
+
+    /// Create and begin a new PutObjectTagging request, after checking that S3 would accept the
+    /// number of tags.
+    pub(super) async fn put_object_tagging(
+        &self,
+        bucket: &str,
+        key: &str,
+        tags: &[(String, String)],
+    ) -> ObjectClientResult<(), PutObjectTaggingError, S3RequestError> {
+        if tags.len() > MAX_OBJECT_TAGS {
+            return Err(ObjectClientError::ClientError(S3RequestError::construction_failure(
+                ConstructionError::InvalidParameters(format!(
+                    "{} tags, but objects can have at most {MAX_OBJECT_TAGS}",
+                    tags.len()
+                )),
+            )));
+        }
+
+        let span = request_span!(self, "put_object_tagging", bucket, key, num_tags = tags.len());
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("PUT", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path_and_query(format!("/{key}"), &[QueryFragment::Flag("tagging")])
+                .map_err(S3RequestError::construction_failure)?;
+
+            // S3 requires an integrity checksum for the body of PutObjectTagging requests
+            let body = build_tagging_body(tags)
+                .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(Box::new(e))))?;
+            let checksum = crc32c_to_base64(&crc32c::checksum(&body));
+            message
+                .set_header(&Header::new("x-amz-checksum-crc32c", checksum))
+                .map_err(S3RequestError::construction_failure)?;
+            message.set_body(body).map_err(S3RequestError::construction_failure)?;
+
+            self.inner.make_simple_http_request(
+                message,
+                MetaRequestType::Default,
+                span,
+                parse_put_object_tagging_error,
+            )?
+        };
+
+        let _response = request.await?;
+
+        Ok(())
+    }
+}
+
+/// Build the XML body of a PutObjectTagging request for the given tags.
+fn build_tagging_body(tags: &[(String, String)]) -> Result<Vec<u8>, xmltree::Error> {
+    let text_element = |name: &str, text: &str| {
+        let mut element = Element::new(name);
+        element.children.push(XMLNode::Text(text.to_owned()));
+        element
+    };
+
+    let mut tag_set = Element::new("TagSet");
+    for (key, value) in tags {
+        let mut tag = Element::new("Tag");
+        tag.children.push(XMLNode::Element(text_element("Key", key)));
+        tag.children.push(XMLNode::Element(text_element("Value", value)));
+        tag_set.children.push(XMLNode::Element(tag));
+    }
+    let mut root = Element::new("Tagging");
+    root.children.push(XMLNode::Element(tag_set));
+
+    let mut body = Vec::new();
+    root.write(&mut body)?;
+    Ok(body)
+}
+
+/// Parse the `<Tagging><TagSet>` of a GetObjectTagging response into key-value pairs.
+fn parse_tagging(bytes: &[u8]) -> Result<Vec<(String, String)>, ParseError> {
+    let root = Element::parse(bytes)?;
+    let mut tag_set = get_child(&root, "TagSet")?.clone();
+
+    let mut tags = Vec::new();
+    while let Some(tag) = tag_set.take_child("Tag") {
+        let key = get_text(get_child(&tag, "Key")?)?;
+        // Tag values can be empty, in which case the element has no text
+        let value = get_child(&tag, "Value")?.get_text().unwrap_or_default().into_owned();
+        tags.push((key, value));
+    }
+    Ok(tags)
+}
+
+fn parse_get_object_tagging_error(result: &MetaRequestResult) -> Option<GetObjectTaggingError> {
+    match result.response_status {
+        404 => {
+            let body = result.error_response_body.as_ref()?;
+            let root = parse_xml(body.as_bytes()).ok()?;
+            let error_code = root.get_child("Code")?;
+            let error_str = error_code.get_text()?;
+            match error_str.deref() {
+                "NoSuchBucket" => Some(GetObjectTaggingError::NoSuchBucket),
+                "NoSuchKey" => Some(GetObjectTaggingError::NoSuchKey),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn parse_put_object_tagging_error(result: &MetaRequestResult) -> Option<PutObjectTaggingError> {
+    match result.response_status {
+        404 => {
+            let body = result.error_response_body.as_ref()?;
+            let root = parse_xml(body.as_bytes()).ok()?;
+            let error_code = root.get_child("Code")?;
+            let error_str = error_code.get_text()?;
+            match error_str.deref() {
+                "NoSuchBucket" => Some(PutObjectTaggingError::NoSuchBucket),
+                "NoSuchKey" => Some(PutObjectTaggingError::NoSuchKey),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    fn make_result(response_status: i32, body: impl Into<OsString>) -> MetaRequestResult {
+        MetaRequestResult {
+            response_status,
+            crt_error: 1i32.into(),
+            error_response_headers: None,
+            error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
+        }
+    }
+
+    fn error_body(code: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>{code}</Code><Message>Some message</Message><RequestId>4VAGDP5HMYTDNB3Y</RequestId></Error>"#
+        )
+    }
+
+    #[test_case("NoSuchBucket", Some(GetObjectTaggingError::NoSuchBucket); "no such bucket")]
+    #[test_case("NoSuchKey", Some(GetObjectTaggingError::NoSuchKey); "no such key")]
+    #[test_case("NoSuchVersion", None; "unmodeled error")]
+    fn parse_get_404(code: &str, expected: Option<GetObjectTaggingError>) {
+        let result = make_result(404, error_body(code));
+        assert_eq!(parse_get_object_tagging_error(&result), expected);
+    }
+
+    #[test_case("NoSuchBucket", Some(PutObjectTaggingError::NoSuchBucket); "no such bucket")]
+    #[test_case("NoSuchKey", Some(PutObjectTaggingError::NoSuchKey); "no such key")]
+    #[test_case("NoSuchVersion", None; "unmodeled error")]
+    fn parse_put_404(code: &str, expected: Option<PutObjectTaggingError>) {
+        let result = make_result(404, error_body(code));
+        assert_eq!(parse_put_object_tagging_error(&result), expected);
+    }
+
+    #[test]
+    fn parse_tagging_result() {
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><TagSet><Tag><Key>project</Key><Value>mountpoint</Value></Tag><Tag><Key>empty</Key><Value></Value></Tag></TagSet></Tagging>"#;
+        let tags = parse_tagging(&body[..]).expect("result should parse");
+        assert_eq!(
+            tags,
+            vec![
+                ("project".to_owned(), "mountpoint".to_owned()),
+                ("empty".to_owned(), "".to_owned()),
+            ]
+        );
+
+        let body = br#"<?xml version="1.0" encoding="UTF-8"?><Tagging xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><TagSet></TagSet></Tagging>"#;
+        let tags = parse_tagging(&body[..]).expect("result should parse");
+        assert!(tags.is_empty());
+    }
+
+    #[test]
+    fn tagging_body_round_trip() {
+        let tags = vec![
+            ("project".to_owned(), "mountpoint & friends".to_owned()),
+            ("team".to_owned(), "<storage>".to_owned()),
+        ];
+        let body = build_tagging_body(&tags).unwrap();
+        assert_eq!(parse_tagging(&body).unwrap(), tags);
+    }
+
+    #[test]
+    fn put_too_many_tags() {
+        let client = S3CrtClient::new(Default::default()).expect("create test client");
+        let tags: Vec<_> = (0..=MAX_OBJECT_TAGS)
+            .map(|i| (format!("key{i}"), format!("value{i}")))
+            .collect();
+        let result = futures::executor::block_on(client.put_object_tagging("test-bucket", "test-key", &tags));
+        assert!(matches!(
+            result,
+            Err(ObjectClientError::ClientError(S3RequestError::ConstructionFailure(
+                ConstructionError::InvalidParameters(_)
+            )))
+        ));
+    }
+}
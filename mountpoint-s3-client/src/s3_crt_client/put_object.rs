@@ -1,18 +1,46 @@
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
-use crate::object_client::{ObjectClientResult, PutObjectError, PutObjectParams, PutObjectRequest, PutObjectResult};
-use crate::s3_crt_client::{emit_throughput_metric, PutObjectTrailingChecksums, S3CrtClient, S3RequestError};
+use crate::checksums::UploadChecksumHasher;
+use crate::object_client::{
+    ChecksumAlgorithm, ETag, HeadObjectResult, ObjectClientResult, ObjectLock, PutObjectError, PutObjectParams,
+    PutObjectRequest, PutObjectResult, ResponseMetadata, ServerSideEncryption,
+};
+use crate::s3_crt_client::{
+    checksum_header_name, emit_throughput_metric, parse_http_date, PutObjectTrailingChecksums, S3CrtClient,
+    S3RequestError,
+};
 use async_trait::async_trait;
+use base64ct::{Base64, Encoding};
 use futures::channel::oneshot;
+use md5::{Digest as _, Md5};
 use mountpoint_s3_crt::http::request_response::{Header, Headers};
 use mountpoint_s3_crt::s3::client::{ChecksumConfig, MetaRequestType, RequestType, UploadReview};
-use tracing::error;
+use time::format_description::well_known::Rfc3339;
+use tracing::{error, warn};
 
-use super::{S3CrtClientInner, S3HttpRequest};
+use super::{
+    ConstructionError, RequestHandle, RequestTimeouts, S3CrtClientInner, S3HttpRequest, S3HttpResponse, S3Message,
+};
 
+const ETAG_HEADER_NAME: &str = "ETag";
+const LAST_MODIFIED_HEADER_NAME: &str = "Last-Modified";
+const DATE_HEADER_NAME: &str = "Date";
 const SSE_TYPE_HEADER_NAME: &str = "x-amz-server-side-encryption";
 const SSE_KEY_ID_HEADER_NAME: &str = "x-amz-server-side-encryption-aws-kms-key-id";
+const SSE_CONTEXT_HEADER_NAME: &str = "x-amz-server-side-encryption-context";
+const SSE_BUCKET_KEY_ENABLED_HEADER_NAME: &str = "x-amz-server-side-encryption-bucket-key-enabled";
+const SSE_CUSTOMER_ALGORITHM_HEADER_NAME: &str = "x-amz-server-side-encryption-customer-algorithm";
+const SSE_CUSTOMER_KEY_HEADER_NAME: &str = "x-amz-server-side-encryption-customer-key";
+const SSE_CUSTOMER_KEY_MD5_HEADER_NAME: &str = "x-amz-server-side-encryption-customer-key-MD5";
+const CHECKSUM_TYPE_HEADER_NAME: &str = "x-amz-checksum-type";
+const OBJECT_LOCK_MODE_HEADER_NAME: &str = "x-amz-object-lock-mode";
+const OBJECT_LOCK_RETAIN_UNTIL_DATE_HEADER_NAME: &str = "x-amz-object-lock-retain-until-date";
+const OBJECT_LOCK_LEGAL_HOLD_HEADER_NAME: &str = "x-amz-object-lock-legal-hold";
+
+/// SSE-C keys are 256-bit AES keys
+const SSE_CUSTOMER_KEY_LEN: usize = 32;
 
 impl S3CrtClient {
     pub(super) async fn put_object(
@@ -21,42 +49,21 @@ impl S3CrtClient {
         key: &str,
         params: &PutObjectParams,
     ) -> ObjectClientResult<S3PutObjectRequest, PutObjectError, S3RequestError> {
-        let span = request_span!(self.inner, "put_object", bucket, key);
-        let mut message = self
-            .inner
-            .new_request_template("PUT", bucket)
+        let span = request_span!(self, "put_object", bucket, key);
+        let message = self
+            .new_put_object_message(bucket, key, params)
             .map_err(S3RequestError::construction_failure)?;
 
-        let key = format!("/{}", key);
-        message
-            .set_request_path(&key)
-            .map_err(S3RequestError::construction_failure)?;
-
-        let checksum_config = match params.trailing_checksums {
-            PutObjectTrailingChecksums::Enabled => Some(ChecksumConfig::trailing_crc32c()),
-            PutObjectTrailingChecksums::ReviewOnly => Some(ChecksumConfig::upload_review_crc32c()),
-            PutObjectTrailingChecksums::Disabled => None,
+        // Compute the checksum of the whole body as it's written, so callers that need it don't
+        // have to read the body again
+        let checksum_hasher = match params.upload_checksums() {
+            (PutObjectTrailingChecksums::Disabled, _) => None,
+            (_, checksum_algorithm) => UploadChecksumHasher::new(checksum_algorithm),
         };
-        message.set_checksum_config(checksum_config);
 
         let review_callback = ReviewCallbackBox::default();
         let callback = review_callback.clone();
 
-        if let Some(storage_class) = params.storage_class.to_owned() {
-            message
-                .set_header(&Header::new("x-amz-storage-class", storage_class))
-                .map_err(S3RequestError::construction_failure)?;
-        }
-        if let Some(sse) = params.server_side_encryption.as_ref() {
-            message
-                .set_header(&Header::new(SSE_TYPE_HEADER_NAME, sse))
-                .map_err(S3RequestError::construction_failure)?;
-        }
-        if let Some(key_id) = params.ssekms_key_id.as_ref() {
-            message
-                .set_header(&Header::new(SSE_KEY_ID_HEADER_NAME, key_id))
-                .map_err(S3RequestError::construction_failure)?;
-        }
         // Variable `response_headers` will be accessed from different threads: from CRT thread which executes `on_headers` callback
         // and from our thread which executes `review_and_complete`. Callback `on_headers` is guaranteed to finish before this
         // variable is accessed in `review_and_complete` (see `S3HttpRequest::poll` implementation).
@@ -65,7 +72,7 @@ impl S3CrtClient {
         let on_headers = move |headers: &Headers, _: i32| {
             *response_headers_writer.lock().unwrap() = Some(headers.clone());
         };
-        let mut options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::PutObject);
+        let mut options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::PutObject, &span);
         options.send_using_async_writes(true);
         options.on_upload_review(move |review| callback.invoke(review));
 
@@ -77,8 +84,10 @@ impl S3CrtClient {
         let on_mpu_created_sender = Arc::new(Mutex::new(Some(mpu_created_sender)));
         let on_error_sender = on_mpu_created_sender.clone();
 
+        // PutObject responses wait for the caller to finish writing, so our timeouts don't apply
         let body = self.inner.make_simple_http_request_from_options(
             options,
+            RequestTimeouts::default(),
             span,
             move |metrics| {
                 if metrics.request_type() == RequestType::CreateMultipartUpload && !metrics.error().is_err() {
@@ -96,6 +105,10 @@ impl S3CrtClient {
                 None
             },
             on_headers,
+            false,
+            // The CRT doesn't deliver the body of the CompleteMultipartUpload response that ends
+            // the meta request
+            false,
         )?;
 
         Ok(S3PutObjectRequest {
@@ -103,10 +116,206 @@ impl S3CrtClient {
             review_callback,
             start_time: Instant::now(),
             total_bytes: 0,
+            checksum_hasher,
             response_headers,
             state: S3PutObjectRequestState::CreatingMPU(mpu_created),
         })
     }
+
+    /// Put an empty object with a single PutObject request. The streaming
+    /// [`put_object`](Self::put_object) can't know the body will be empty until it's completed, so
+    /// it always starts a multipart upload.
+    pub(super) async fn put_empty_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        params: &PutObjectParams,
+    ) -> ObjectClientResult<PutObjectResult, PutObjectError, S3RequestError> {
+        let response_headers: Arc<Mutex<Option<Headers>>> = Default::default();
+        let response_headers_writer = response_headers.clone();
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let span = request_span!(self, "put_empty_object", bucket, key);
+            let mut message = self
+                .new_put_object_message(bucket, key, params)
+                .map_err(S3RequestError::construction_failure)?;
+
+            // The CRT's trailing checksums need a body to stream, so send the checksum of the
+            // empty body up front instead
+            message.set_checksum_config(None);
+            let (trailing_checksums, checksum_algorithm) = params.upload_checksums();
+            if trailing_checksums == PutObjectTrailingChecksums::Enabled {
+                message
+                    .set_header(&Header::new(
+                        checksum_header_name(checksum_algorithm),
+                        empty_body_checksum(checksum_algorithm),
+                    ))
+                    .map_err(S3RequestError::construction_failure)?;
+            }
+            message
+                .set_body(Vec::<u8>::new())
+                .map_err(S3RequestError::construction_failure)?;
+
+            let request_timeouts = message.request_timeouts;
+            let verify_body_length = message.verify_body_length;
+            let options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::Default, &span);
+            self.inner.make_simple_http_request_from_options(
+                options,
+                request_timeouts,
+                span,
+                |_| {},
+                |_| None,
+                move |headers: &Headers, _| {
+                    *response_headers_writer.lock().unwrap() = Some(headers.clone());
+                },
+                false,
+                verify_body_length,
+            )?
+        };
+
+        let response = request.await?;
+
+        let response_headers = response_headers
+            .lock()
+            .unwrap()
+            .take()
+            .expect("PUT response headers must be available at this point");
+        Ok(parse_put_object_result(&response_headers, response.metadata))
+    }
+}
+
+/// The base64-encoded checksum of an empty body with the given algorithm.
+fn empty_body_checksum(algorithm: ChecksumAlgorithm) -> &'static str {
+    match algorithm {
+        ChecksumAlgorithm::Crc32c | ChecksumAlgorithm::Crc32 => "AAAAAA==",
+        ChecksumAlgorithm::Crc64nvme => "AAAAAAAAAAA=",
+        ChecksumAlgorithm::Sha1 => "2jmj7l5rSw0yVb/vlWAYkK/YBwk=",
+        ChecksumAlgorithm::Sha256 => "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=",
+    }
+}
+
+impl S3CrtClient {
+    /// Create the message for a new PutObject request, including the headers derived from the
+    /// request params. The CRT copies these headers onto the CreateMultipartUpload request.
+    fn new_put_object_message(
+        &self,
+        bucket: &str,
+        key: &str,
+        params: &PutObjectParams,
+    ) -> Result<S3Message, ConstructionError> {
+        let mut message = self.new_request_template("PUT", bucket)?;
+
+        let key = format!("/{}", key);
+        message.set_request_path(&key)?;
+
+        // The CRT computes the checksum of each part, and S3 combines them into the checksum of
+        // the object, so there's nothing to compute here.
+        let (trailing_checksums, checksum_algorithm) = params.upload_checksums();
+        let checksum_config = match trailing_checksums {
+            PutObjectTrailingChecksums::Enabled => Some(ChecksumConfig::trailing(checksum_algorithm)?),
+            PutObjectTrailingChecksums::ReviewOnly => Some(ChecksumConfig::upload_review(checksum_algorithm)?),
+            PutObjectTrailingChecksums::Disabled => None,
+        };
+        message.set_checksum_config(checksum_config);
+
+        if let Some(storage_class) = params.storage_class.to_owned() {
+            message.set_header(&Header::new("x-amz-storage-class", storage_class))?;
+        }
+        if let Some(sse) = params.sse.as_ref() {
+            if params.server_side_encryption.is_some() || params.ssekms_key_id.is_some() {
+                return Err(ConstructionError::InvalidParameters(
+                    "sse can't be combined with server_side_encryption or ssekms_key_id".to_string(),
+                ));
+            }
+            set_sse_headers(&mut message, sse)?;
+        } else {
+            if let Some(sse) = params.server_side_encryption.as_ref() {
+                message.set_header(&Header::new(SSE_TYPE_HEADER_NAME, sse))?;
+            }
+            if let Some(key_id) = params.ssekms_key_id.as_ref() {
+                message.set_header(&Header::new(SSE_KEY_ID_HEADER_NAME, key_id))?;
+            }
+        }
+        if params.bucket_key_enabled {
+            // S3 Bucket Keys only apply to SSE-KMS, not to DSSE-KMS or other kinds of encryption
+            let is_sse_kms = match params.sse.as_ref() {
+                Some(sse) => matches!(sse, ServerSideEncryption::Kms { .. }),
+                None => params.server_side_encryption.as_deref() == Some("aws:kms"),
+            };
+            if !is_sse_kms {
+                return Err(ConstructionError::InvalidParameters(
+                    "bucket keys can only be enabled with aws:kms encryption".to_string(),
+                ));
+            }
+            message.set_header(&Header::new(SSE_BUCKET_KEY_ENABLED_HEADER_NAME, "true"))?;
+        }
+        if let Some(checksum_type) = params.checksum_type {
+            message.set_header(&Header::new(CHECKSUM_TYPE_HEADER_NAME, checksum_type.as_str()))?;
+        }
+        if let Some(object_lock) = params.object_lock.as_ref() {
+            set_object_lock_headers(&mut message, object_lock)?;
+        }
+
+        Ok(message)
+    }
+}
+
+/// Set the headers that apply the given Object Lock settings to a new object.
+fn set_object_lock_headers(message: &mut S3Message, object_lock: &ObjectLock) -> Result<(), ConstructionError> {
+    match (object_lock.mode, object_lock.retain_until) {
+        (Some(mode), Some(retain_until)) => {
+            let retain_until = retain_until
+                .format(&Rfc3339)
+                .map_err(|e| ConstructionError::InvalidParameters(format!("invalid retain-until date: {e}")))?;
+            message.set_header(&Header::new(OBJECT_LOCK_MODE_HEADER_NAME, mode.as_str()))?;
+            message.set_header(&Header::new(OBJECT_LOCK_RETAIN_UNTIL_DATE_HEADER_NAME, retain_until))?;
+        }
+        (None, None) => {}
+        _ => {
+            return Err(ConstructionError::InvalidParameters(
+                "Object Lock mode and retain-until date must be set together".to_string(),
+            ))
+        }
+    }
+    if let Some(legal_hold) = object_lock.legal_hold {
+        let legal_hold = if legal_hold { "ON" } else { "OFF" };
+        message.set_header(&Header::new(OBJECT_LOCK_LEGAL_HOLD_HEADER_NAME, legal_hold))?;
+    }
+    Ok(())
+}
+
+/// Set the headers that request the given server-side encryption for a new object.
+fn set_sse_headers(message: &mut S3Message, sse: &ServerSideEncryption) -> Result<(), ConstructionError> {
+    match sse {
+        ServerSideEncryption::S3 => {
+            message.set_header(&Header::new(SSE_TYPE_HEADER_NAME, "AES256"))?;
+        }
+        ServerSideEncryption::Kms { key_id, context } => {
+            message.set_header(&Header::new(SSE_TYPE_HEADER_NAME, "aws:kms"))?;
+            if let Some(key_id) = key_id {
+                message.set_header(&Header::new(SSE_KEY_ID_HEADER_NAME, key_id))?;
+            }
+            if let Some(context) = context {
+                let context = Base64::encode_string(context.as_bytes());
+                message.set_header(&Header::new(SSE_CONTEXT_HEADER_NAME, context))?;
+            }
+        }
+        ServerSideEncryption::Customer { key } => {
+            if key.len() != SSE_CUSTOMER_KEY_LEN {
+                return Err(ConstructionError::InvalidParameters(format!(
+                    "SSE-C keys must be {SSE_CUSTOMER_KEY_LEN} bytes, but the key is {} bytes",
+                    key.len()
+                )));
+            }
+            // S3 uses the MD5 digest of the key to check it wasn't corrupted in transit
+            let key_md5 = Base64::encode_string(&Md5::digest(key));
+            message.set_header(&Header::new(SSE_CUSTOMER_ALGORITHM_HEADER_NAME, "AES256"))?;
+            message.set_header(&Header::new(SSE_CUSTOMER_KEY_HEADER_NAME, Base64::encode_string(key)))?;
+            message.set_header(&Header::new(SSE_CUSTOMER_KEY_MD5_HEADER_NAME, key_md5))?;
+        }
+    }
+    Ok(())
 }
 
 type ReviewCallback = dyn FnOnce(UploadReview) -> bool + Send;
@@ -148,10 +357,13 @@ impl ReviewCallbackBox {
 /// object.
 #[derive(Debug)]
 pub struct S3PutObjectRequest {
-    body: S3HttpRequest<Vec<u8>, PutObjectError>,
+    body: S3HttpRequest<S3HttpResponse, PutObjectError>,
     review_callback: ReviewCallbackBox,
     start_time: Instant,
     total_bytes: u64,
+    /// Checksum of the bytes written so far, if the upload has trailing checksums with an
+    /// algorithm [UploadChecksumHasher] supports
+    checksum_hasher: Option<UploadChecksumHasher>,
     /// Headers of the CompleteMultipartUpload response, available after the request was finished
     response_headers: Arc<Mutex<Option<Headers>>>,
     state: S3PutObjectRequestState,
@@ -174,6 +386,32 @@ fn try_get_header_value(headers: &Headers, key: &str) -> Option<String> {
     headers.get(key).ok()?.value().clone().into_string().ok()
 }
 
+/// Build the result of a completed upload from the headers of the PUT (or
+/// CompleteMultipartUpload) response.
+fn parse_put_object_result(headers: &Headers, response_metadata: ResponseMetadata) -> PutObjectResult {
+    let etag = try_get_header_value(headers, ETAG_HEADER_NAME).map(|etag| ETag::from_str(&etag).unwrap());
+    if etag.is_none() {
+        warn!("PUT response is missing the ETag header");
+    }
+    let last_modified = try_get_header_value(headers, LAST_MODIFIED_HEADER_NAME)
+        .or_else(|| try_get_header_value(headers, DATE_HEADER_NAME))
+        .and_then(|date| parse_http_date(&date).ok());
+    PutObjectResult {
+        etag,
+        last_modified,
+        sse_type: try_get_header_value(headers, SSE_TYPE_HEADER_NAME),
+        sse_kms_key_id: try_get_header_value(headers, SSE_KEY_ID_HEADER_NAME),
+        checksum: try_get_checksum(headers),
+        response_metadata,
+    }
+}
+
+/// Find the object checksum in the PUT response headers. S3 returns at most one, for the algorithm
+/// the object was uploaded with.
+fn try_get_checksum(headers: &Headers) -> Option<(ChecksumAlgorithm, String)> {
+    HeadObjectResult::parse_checksum(headers).ok()?.algorithm_and_value()
+}
+
 #[cfg_attr(not(docs_rs), async_trait)]
 impl PutObjectRequest for S3PutObjectRequest {
     type ClientError = S3RequestError;
@@ -194,6 +432,10 @@ impl PutObjectRequest for S3PutObjectRequest {
             S3PutObjectRequestState::Idle => {}
         }
 
+        if let Some(hasher) = &mut self.checksum_hasher {
+            hasher.update(slice);
+        }
+
         let meta_request = &mut self.body.meta_request;
         let mut slice = slice;
         while !slice.is_empty() {
@@ -235,7 +477,7 @@ impl PutObjectRequest for S3PutObjectRequest {
             .map_err(S3RequestError::CrtError)?;
 
         // Now wait for the request to finish.
-        let _ = self.body.await?;
+        let response = self.body.await?;
 
         let elapsed = self.start_time.elapsed();
         emit_throughput_metric(self.total_bytes, elapsed, "put_object");
@@ -246,10 +488,7 @@ impl PutObjectRequest for S3PutObjectRequest {
             .expect("must be able to acquire headers lock")
             .take()
             .expect("PUT response headers must be available at this point");
-        Ok(PutObjectResult {
-            sse_type: try_get_header_value(&response_headers, SSE_TYPE_HEADER_NAME),
-            sse_kms_key_id: try_get_header_value(&response_headers, SSE_KEY_ID_HEADER_NAME),
-        })
+        Ok(parse_put_object_result(&response_headers, response.metadata))
     }
 }
 
@@ -259,4 +498,337 @@ impl S3PutObjectRequest {
     pub fn bytes_written(&self) -> u64 {
         self.total_bytes
     }
+
+    /// The checksum of the whole body written so far, and its algorithm, computed as it's written.
+    /// For multipart uploads, this differs from the composite checksum S3 reports for the object.
+    /// `None` unless the upload has trailing checksums, with CRC32C or CRC32 as the algorithm.
+    pub fn checksum(&self) -> Option<(ChecksumAlgorithm, String)> {
+        let hasher = self.checksum_hasher.clone()?;
+        Some((hasher.algorithm(), hasher.finalize_base64()))
+    }
+
+    /// A handle that can cancel this upload, for example from another task while a write is in
+    /// progress. A canceled upload is aborted as if the request had been dropped.
+    pub fn handle(&self) -> RequestHandle {
+        self.body.handle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use time::OffsetDateTime;
+
+    use crate::object_client::{ChecksumType, ObjectLockMode};
+
+    #[test]
+    fn empty_body_crc_checksums() {
+        use mountpoint_s3_crt::checksums::{crc32, crc32c};
+
+        use crate::checksums::{crc32_to_base64, crc32c_to_base64};
+
+        assert_eq!(
+            empty_body_checksum(ChecksumAlgorithm::Crc32c),
+            crc32c_to_base64(&crc32c::checksum(&[]))
+        );
+        assert_eq!(
+            empty_body_checksum(ChecksumAlgorithm::Crc32),
+            crc32_to_base64(&crc32::checksum(&[]))
+        );
+    }
+
+    fn get_header(message: &mut S3Message, name: &str) -> Option<String> {
+        let headers = message.inner.get_headers().expect("Expected a block of HTTP headers");
+        headers
+            .get(name)
+            .ok()
+            .map(|header| header.value().to_string_lossy().into_owned())
+    }
+
+    #[test_case(Some(ChecksumType::FullObject), Some("FULL_OBJECT"); "full object")]
+    #[test_case(Some(ChecksumType::Composite), Some("COMPOSITE"); "composite")]
+    #[test_case(None, None; "unset")]
+    fn test_checksum_type_header(checksum_type: Option<ChecksumType>, expected: Option<&str>) {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = PutObjectParams::new().checksum_type(checksum_type);
+
+        let mut message = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect("new put object message expected");
+
+        let headers = message.inner.get_headers().expect("Expected a block of HTTP headers");
+        let checksum_type_header = headers
+            .get(CHECKSUM_TYPE_HEADER_NAME)
+            .ok()
+            .map(|header| header.value().to_string_lossy().into_owned());
+        assert_eq!(checksum_type_header.as_deref(), expected);
+    }
+
+    #[test_case(PutObjectTrailingChecksums::Disabled, None, None; "disabled")]
+    #[test_case(PutObjectTrailingChecksums::Enabled, None, Some(ChecksumAlgorithm::Crc32c); "default algorithm")]
+    #[test_case(PutObjectTrailingChecksums::Disabled, Some(ChecksumAlgorithm::Sha256), Some(ChecksumAlgorithm::Sha256); "algorithm enables checksums")]
+    #[test_case(PutObjectTrailingChecksums::ReviewOnly, Some(ChecksumAlgorithm::Crc32), Some(ChecksumAlgorithm::Crc32); "review only")]
+    fn test_checksum_algorithm(
+        trailing_checksums: PutObjectTrailingChecksums,
+        checksum_algorithm: Option<ChecksumAlgorithm>,
+        expected: Option<ChecksumAlgorithm>,
+    ) {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = PutObjectParams::new()
+            .trailing_checksums(trailing_checksums)
+            .checksum_algorithm(checksum_algorithm);
+
+        let message = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect("new put object message expected");
+
+        let algorithm = message.checksum_config.as_ref().and_then(|config| config.algorithm());
+        assert_eq!(algorithm, expected);
+    }
+
+    #[test]
+    fn test_crc64nvme_checksum_algorithm() {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = PutObjectParams::new().checksum_algorithm(Some(ChecksumAlgorithm::Crc64nvme));
+
+        let result = client.new_put_object_message("doc-example-bucket", "key", &params);
+        if ChecksumAlgorithm::Crc64nvme.is_supported() {
+            let message = result.expect("new put object message expected");
+            let algorithm = message.checksum_config.as_ref().and_then(|config| config.algorithm());
+            assert_eq!(algorithm, Some(ChecksumAlgorithm::Crc64nvme));
+        } else {
+            let err = result.expect_err("unsupported algorithm should be rejected");
+            assert!(
+                matches!(err, ConstructionError::UnsupportedChecksumAlgorithm(_)),
+                "got {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sse_s3_headers() {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = PutObjectParams::new().sse(Some(ServerSideEncryption::S3));
+
+        let mut message = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect("new put object message expected");
+
+        assert_eq!(
+            get_header(&mut message, SSE_TYPE_HEADER_NAME).as_deref(),
+            Some("AES256")
+        );
+        assert_eq!(get_header(&mut message, SSE_KEY_ID_HEADER_NAME), None);
+    }
+
+    #[test]
+    fn test_sse_kms_headers() {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = PutObjectParams::new().sse(Some(ServerSideEncryption::Kms {
+            key_id: Some("arn:aws:kms:us-east-1:111122223333:key/example".to_owned()),
+            context: Some(r#"{"project":"mountpoint"}"#.to_owned()),
+        }));
+
+        let mut message = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect("new put object message expected");
+
+        assert_eq!(
+            get_header(&mut message, SSE_TYPE_HEADER_NAME).as_deref(),
+            Some("aws:kms")
+        );
+        assert_eq!(
+            get_header(&mut message, SSE_KEY_ID_HEADER_NAME).as_deref(),
+            Some("arn:aws:kms:us-east-1:111122223333:key/example")
+        );
+        assert_eq!(
+            get_header(&mut message, SSE_CONTEXT_HEADER_NAME).as_deref(),
+            Some("eyJwcm9qZWN0IjoibW91bnRwb2ludCJ9")
+        );
+    }
+
+    #[test_case(PutObjectParams::new().sse(Some(ServerSideEncryption::Kms { key_id: None, context: None })); "sse")]
+    #[test_case(PutObjectParams::new().server_side_encryption(Some("aws:kms".to_owned())); "server_side_encryption")]
+    fn test_sse_kms_bucket_key(params: PutObjectParams) {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = params.bucket_key_enabled(true);
+
+        let mut message = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect("new put object message expected");
+
+        assert_eq!(
+            get_header(&mut message, SSE_BUCKET_KEY_ENABLED_HEADER_NAME).as_deref(),
+            Some("true")
+        );
+    }
+
+    #[test_case(PutObjectParams::new(); "no encryption")]
+    #[test_case(PutObjectParams::new().sse(Some(ServerSideEncryption::S3)); "sse-s3")]
+    #[test_case(PutObjectParams::new().server_side_encryption(Some("aws:kms:dsse".to_owned())); "dsse-kms")]
+    fn test_bucket_key_without_sse_kms(params: PutObjectParams) {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = params.bucket_key_enabled(true);
+
+        let err = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect_err("bucket key without SSE-KMS should fail");
+        assert!(matches!(err, ConstructionError::InvalidParameters(_)), "got {err:?}");
+    }
+
+    #[test_case(PutObjectParams::new().server_side_encryption(Some("aws:kms".to_owned())); "server_side_encryption")]
+    #[test_case(PutObjectParams::new().ssekms_key_id(Some("key-id".to_owned())); "ssekms_key_id")]
+    fn test_sse_with_server_side_encryption(params: PutObjectParams) {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = params.sse(Some(ServerSideEncryption::S3));
+
+        let err = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect_err("combining both kinds of SSE options should fail");
+        assert!(matches!(err, ConstructionError::InvalidParameters(_)), "got {err:?}");
+    }
+
+    #[test_case(16; "too short")]
+    #[test_case(64; "too long")]
+    fn test_sse_customer_key_length(len: usize) {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = PutObjectParams::new().sse(Some(ServerSideEncryption::Customer { key: vec![0u8; len] }));
+
+        let err = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect_err("SSE-C keys that aren't 256 bits should fail");
+        assert!(matches!(err, ConstructionError::InvalidParameters(_)), "got {err:?}");
+    }
+
+    #[test]
+    fn test_sse_customer_headers() {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = PutObjectParams::new().sse(Some(ServerSideEncryption::Customer { key: vec![0u8; 32] }));
+
+        let mut message = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect("new put object message expected");
+
+        assert_eq!(get_header(&mut message, SSE_TYPE_HEADER_NAME), None);
+        assert_eq!(
+            get_header(&mut message, SSE_CUSTOMER_ALGORITHM_HEADER_NAME).as_deref(),
+            Some("AES256")
+        );
+        assert_eq!(
+            get_header(&mut message, SSE_CUSTOMER_KEY_HEADER_NAME).as_deref(),
+            Some("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
+        );
+        assert_eq!(
+            get_header(&mut message, SSE_CUSTOMER_KEY_MD5_HEADER_NAME).as_deref(),
+            Some("cLyPS3KoaSFGi/joRB3OUQ==")
+        );
+    }
+
+    #[test]
+    fn test_object_lock_headers() {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let retain_until = OffsetDateTime::parse("2030-01-01T12:34:56Z", &Rfc3339).unwrap();
+        let object_lock = ObjectLock::new()
+            .retention(ObjectLockMode::Compliance, retain_until)
+            .legal_hold(true);
+        let params = PutObjectParams::new().object_lock(Some(object_lock));
+
+        let mut message = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect("new put object message expected");
+
+        assert_eq!(
+            get_header(&mut message, OBJECT_LOCK_MODE_HEADER_NAME).as_deref(),
+            Some("COMPLIANCE")
+        );
+        assert_eq!(
+            get_header(&mut message, OBJECT_LOCK_RETAIN_UNTIL_DATE_HEADER_NAME).as_deref(),
+            Some("2030-01-01T12:34:56Z")
+        );
+        assert_eq!(
+            get_header(&mut message, OBJECT_LOCK_LEGAL_HOLD_HEADER_NAME).as_deref(),
+            Some("ON")
+        );
+    }
+
+    #[test]
+    fn test_object_lock_legal_hold_only() {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let params = PutObjectParams::new().object_lock(Some(ObjectLock::new().legal_hold(false)));
+
+        let mut message = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect("new put object message expected");
+
+        assert_eq!(get_header(&mut message, OBJECT_LOCK_MODE_HEADER_NAME), None);
+        assert_eq!(
+            get_header(&mut message, OBJECT_LOCK_RETAIN_UNTIL_DATE_HEADER_NAME),
+            None
+        );
+        assert_eq!(
+            get_header(&mut message, OBJECT_LOCK_LEGAL_HOLD_HEADER_NAME).as_deref(),
+            Some("OFF")
+        );
+    }
+
+    #[test_case(Some(ObjectLockMode::Governance), None; "mode without date")]
+    #[test_case(None, Some(OffsetDateTime::UNIX_EPOCH); "date without mode")]
+    fn test_object_lock_incomplete_retention(mode: Option<ObjectLockMode>, retain_until: Option<OffsetDateTime>) {
+        let client = S3CrtClient::new(Default::default()).expect("Create test client");
+        let mut object_lock = ObjectLock::new();
+        object_lock.mode = mode;
+        object_lock.retain_until = retain_until;
+        let params = PutObjectParams::new().object_lock(Some(object_lock));
+
+        let err = client
+            .new_put_object_message("doc-example-bucket", "key", &params)
+            .expect_err("incomplete retention should be rejected");
+        assert!(matches!(err, ConstructionError::InvalidParameters(_)), "got {err:?}");
+    }
+
+    #[test]
+    fn test_parse_put_object_result() {
+        let mut headers = Headers::new(&Default::default()).unwrap();
+        headers
+            .add_header(&Header::new("Date", "Fri, 21 Dec 2012 00:00:00 GMT"))
+            .unwrap();
+        let result = parse_put_object_result(&headers, ResponseMetadata::new(200));
+        assert_eq!(result.etag, None);
+        assert_eq!(
+            result.last_modified,
+            Some(OffsetDateTime::from_unix_timestamp(1356048000).unwrap())
+        );
+
+        headers.add_header(&Header::new("ETag", "\"abc\"")).unwrap();
+        headers
+            .add_header(&Header::new("Last-Modified", "Thu, 20 Dec 2012 00:00:00 GMT"))
+            .unwrap();
+        let result = parse_put_object_result(&headers, ResponseMetadata::new(200));
+        assert_eq!(result.etag.as_ref().map(ETag::as_str), Some("\"abc\""));
+        assert_eq!(
+            result.last_modified,
+            Some(OffsetDateTime::from_unix_timestamp(1355961600).unwrap())
+        );
+    }
+
+    #[test_case(&[("x-amz-checksum-crc32c", "sOO8/Q==")], Some((ChecksumAlgorithm::Crc32c, "sOO8/Q==")); "crc32c")]
+    #[test_case(&[("x-amz-checksum-crc32", "NSRBwg==")], Some((ChecksumAlgorithm::Crc32, "NSRBwg==")); "crc32")]
+    #[test_case(&[("x-amz-checksum-sha1", "qZk+NkcGgWq6PiVxeFDCbJzQ2J0=")], Some((ChecksumAlgorithm::Sha1, "qZk+NkcGgWq6PiVxeFDCbJzQ2J0=")); "sha1")]
+    #[test_case(&[("x-amz-checksum-sha256", "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=")], Some((ChecksumAlgorithm::Sha256, "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=")); "sha256")]
+    #[test_case(&[("x-amz-checksum-crc64nvme", "M3eFcAZSQlc=")], Some((ChecksumAlgorithm::Crc64nvme, "M3eFcAZSQlc=")); "crc64nvme")]
+    #[test_case(&[("ETag", "\"abc\"")], None; "no checksum")]
+    fn test_parse_checksum(headers: &[(&str, &str)], expected: Option<(ChecksumAlgorithm, &str)>) {
+        let mut response_headers = Headers::new(&Default::default()).unwrap();
+        for (name, value) in headers {
+            response_headers.add_header(&Header::new(name, value)).unwrap();
+        }
+
+        let checksum = try_get_checksum(&response_headers);
+        assert_eq!(
+            checksum.as_ref().map(|(algorithm, value)| (*algorithm, value.as_str())),
+            expected
+        );
+    }
 }
@@ -0,0 +1,147 @@
+use std::ops::Deref;
+use std::os::unix::prelude::OsStrExt;
+
+use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use xmltree::{Element, XMLNode};
+
+use crate::object_client::{
+    ObjectClientError, ObjectClientResult, RestoreObjectError, RestoreObjectResult, RestoreTier,
+};
+use crate::s3_crt_client::list_objects::parse_xml;
+use crate::s3_crt_client::{QueryFragment, S3CrtClient, S3RequestError};
+
+impl S3CrtClient {
+    /// Create and begin a new RestoreObject request.
+    pub(super) async fn restore_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        days: u32,
+        tier: RestoreTier,
+    ) -> ObjectClientResult<RestoreObjectResult, RestoreObjectError, S3RequestError> {
+        let span = request_span!(self, "restore_object", bucket, key, days, ?tier);
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template("POST", bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            message
+                .set_request_path_and_query(format!("/{key}"), &[QueryFragment::Flag("restore")])
+                .map_err(S3RequestError::construction_failure)?;
+
+            let body = build_restore_request_body(days, tier)
+                .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(Box::new(e))))?;
+            message.set_body(body).map_err(S3RequestError::construction_failure)?;
+
+            self.inner
+                .make_simple_http_request(message, MetaRequestType::Default, span, parse_restore_object_error)?
+        };
+
+        let response = request.await?;
+
+        // S3 responds with 202 Accepted when it starts a restore, and 200 OK when the object was
+        // already restored, in which case it just updates the expiry of the restored copy
+        match response.metadata.response_status {
+            Some(200) => Ok(RestoreObjectResult::AlreadyRestored),
+            _ => Ok(RestoreObjectResult::RestoreStarted),
+        }
+    }
+}
+
+/// Build the XML body of a RestoreObject request.
+fn build_restore_request_body(days: u32, tier: RestoreTier) -> Result<Vec<u8>, xmltree::Error> {
+    let text_element = |name: &str, text: &str| {
+        let mut element = Element::new(name);
+        element.children.push(XMLNode::Text(text.to_owned()));
+        element
+    };
+
+    let mut job_parameters = Element::new("GlacierJobParameters");
+    job_parameters
+        .children
+        .push(XMLNode::Element(text_element("Tier", tier.as_str())));
+    let mut root = Element::new("RestoreRequest");
+    root.children
+        .push(XMLNode::Element(text_element("Days", &days.to_string())));
+    root.children.push(XMLNode::Element(job_parameters));
+
+    let mut body = Vec::new();
+    root.write(&mut body)?;
+    Ok(body)
+}
+
+fn parse_restore_object_error(result: &MetaRequestResult) -> Option<RestoreObjectError> {
+    let error_code = || {
+        let body = result.error_response_body.as_ref()?;
+        let root = parse_xml(body.as_bytes()).ok()?;
+        Some(root.get_child("Code")?.get_text()?.into_owned())
+    };
+    match result.response_status {
+        404 => match error_code()?.deref() {
+            "NoSuchBucket" => Some(RestoreObjectError::NoSuchBucket),
+            "NoSuchKey" => Some(RestoreObjectError::NoSuchKey),
+            _ => None,
+        },
+        409 => match error_code()?.deref() {
+            "RestoreAlreadyInProgress" => Some(RestoreObjectError::RestoreAlreadyInProgress),
+            _ => None,
+        },
+        403 => match error_code()?.deref() {
+            "InvalidObjectState" => Some(RestoreObjectError::ObjectNotArchived),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    fn make_result(response_status: i32, body: impl Into<OsString>) -> MetaRequestResult {
+        MetaRequestResult {
+            response_status,
+            crt_error: 1i32.into(),
+            error_response_headers: None,
+            error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
+        }
+    }
+
+    fn error_body(code: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>{code}</Code><Message>Some message</Message><RequestId>4VAGDP5HMYTDNB3Y</RequestId></Error>"#
+        )
+    }
+
+    #[test_case(404, "NoSuchBucket", Some(RestoreObjectError::NoSuchBucket); "no such bucket")]
+    #[test_case(404, "NoSuchKey", Some(RestoreObjectError::NoSuchKey); "no such key")]
+    #[test_case(409, "RestoreAlreadyInProgress", Some(RestoreObjectError::RestoreAlreadyInProgress); "already in progress")]
+    #[test_case(403, "InvalidObjectState", Some(RestoreObjectError::ObjectNotArchived); "not archived")]
+    #[test_case(403, "AccessDenied", None; "access denied")]
+    #[test_case(409, "OperationAborted", None; "unmodeled error")]
+    fn parse_error(status: i32, code: &str, expected: Option<RestoreObjectError>) {
+        let result = make_result(status, error_body(code));
+        assert_eq!(parse_restore_object_error(&result), expected);
+    }
+
+    #[test]
+    fn restore_request_body() {
+        let body = build_restore_request_body(7, RestoreTier::Bulk).expect("body should serialize");
+        let root = Element::parse(&body[..]).expect("body should parse");
+        assert_eq!(root.name, "RestoreRequest");
+        assert_eq!(root.get_child("Days").unwrap().get_text().unwrap(), "7");
+        let tier = root
+            .get_child("GlacierJobParameters")
+            .unwrap()
+            .get_child("Tier")
+            .unwrap();
+        assert_eq!(tier.get_text().unwrap(), "Bulk");
+    }
+}
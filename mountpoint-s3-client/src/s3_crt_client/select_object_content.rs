@@ -0,0 +1,477 @@
+use std::future::Future;
+use std::ops::Deref;
+use std::os::unix::prelude::OsStrExt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::Stream;
+use mountpoint_s3_crt::http::request_response::Header;
+use mountpoint_s3_crt::s3::client::{MetaRequestResult, MetaRequestType};
+use pin_project::pin_project;
+use thiserror::Error;
+use xmltree::{Element, XMLNode};
+
+use crate::object_client::{ObjectClientError, ObjectClientResult};
+use crate::s3_crt_client::event_stream::{Decoder, Message};
+use crate::s3_crt_client::list_objects::{get_field, parse_xml, ParseError};
+use crate::s3_crt_client::{QueryFragment, RequestHandle, S3CrtClient, S3HttpRequest, S3RequestError};
+
+/// Errors returned by a [`select_object_content`](S3CrtClient::select_object_content) request
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectObjectContentError {
+    #[error("The bucket does not exist")]
+    NoSuchBucket,
+
+    #[error("The key does not exist")]
+    NoSuchKey,
+
+    /// S3 rejected the query, or failed while running it. A query can fail partway through the
+    /// results, after some records have already been returned.
+    #[error("SelectObjectContent failed with error {code}: {message}")]
+    QueryFailed { code: String, message: String },
+}
+
+/// The format of the object that a SelectObjectContent request queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectInputSerialization {
+    /// CSV, with the given handling of the first line
+    Csv { file_header_info: CsvFileHeaderInfo },
+    /// JSON, either as a single document or as one document per line
+    Json { json_type: JsonType },
+    /// Apache Parquet
+    Parquet,
+}
+
+/// How a SelectObjectContent request treats the first line of a CSV object
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CsvFileHeaderInfo {
+    /// The first line is a record
+    #[default]
+    None,
+    /// The first line is a header, which is skipped
+    Ignore,
+    /// The first line is a header, whose column names can be used in the expression
+    Use,
+}
+
+/// How a JSON object that a SelectObjectContent request queries is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonType {
+    /// A single JSON document, which may span many lines
+    Document,
+    /// One JSON document per line
+    Lines,
+}
+
+/// The format of the records that a SelectObjectContent request returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectOutputSerialization {
+    /// CSV, with one record per line
+    Csv,
+    /// JSON, with one record per line
+    Json,
+}
+
+/// Statistics about the data a SelectObjectContent request processed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SelectStats {
+    /// Number of bytes of the object that S3 scanned
+    pub bytes_scanned: u64,
+    /// Number of uncompressed bytes that S3 processed
+    pub bytes_processed: u64,
+    /// Number of bytes of records that S3 returned
+    pub bytes_returned: u64,
+}
+
+/// An event in the response to a SelectObjectContent request
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectEvent {
+    /// A chunk of the records that matched the query, in the requested output format. Records can
+    /// be split across chunks.
+    Records(Box<[u8]>),
+    /// Statistics about the data the request processed, sent once all the records have been sent
+    Stats(SelectStats),
+    /// The request finished successfully. This is the last event of the response.
+    End,
+}
+
+impl S3CrtClient {
+    /// Create and begin a new SelectObjectContent request, which runs an SQL expression against an
+    /// object and returns only the records that match it. The returned
+    /// [S3SelectObjectContentRequest] is a [Stream] of the events in the response.
+    pub async fn select_object_content(
+        &self,
+        bucket: &str,
+        key: &str,
+        expression: &str,
+        input_serialization: SelectInputSerialization,
+        output_serialization: SelectOutputSerialization,
+    ) -> ObjectClientResult<S3SelectObjectContentRequest, SelectObjectContentError, S3RequestError> {
+        self.check_bucket(bucket).await?;
+
+        let span = request_span!(self, "select_object_content", bucket, key);
+
+        let mut message = self
+            .new_request_template("POST", bucket)
+            .map_err(S3RequestError::construction_failure)?;
+
+        // Overwrite "accept" header since this returns an event stream.
+        message
+            .set_header(&Header::new("accept", "*/*"))
+            .map_err(S3RequestError::construction_failure)?;
+        message
+            .set_request_path_and_query(
+                format!("/{key}"),
+                &[QueryFragment::Flag("select"), QueryFragment::Pair("select-type", "2")],
+            )
+            .map_err(S3RequestError::construction_failure)?;
+
+        let body = build_select_object_content_body(expression, input_serialization, output_serialization)
+            .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(Box::new(e))))?;
+        message.set_body(body).map_err(S3RequestError::construction_failure)?;
+
+        let (sender, receiver) = futures::channel::mpsc::unbounded();
+        let decoder = Arc::new(Mutex::new(Decoder::new()));
+        let decoder_finish = decoder.clone();
+        let mut failed = false;
+
+        let request = self.inner.make_meta_request(
+            message,
+            MetaRequestType::Default,
+            span,
+            |_, _| {},
+            move |_offset, data| {
+                // Once the stream is corrupt or S3 reports an error, there's nothing more to decode
+                if failed {
+                    return;
+                }
+                let mut decoder = decoder.lock().unwrap();
+                decoder.push(data);
+                loop {
+                    let event = match decoder.next_message() {
+                        Ok(Some(message)) => parse_event(&message),
+                        Ok(None) => break,
+                        Err(e) => Err(ObjectClientError::ClientError(S3RequestError::InternalError(Box::new(
+                            e,
+                        )))),
+                    };
+                    failed = event.is_err();
+                    if let Some(event) = event.transpose() {
+                        let _ = sender.unbounded_send(event);
+                    }
+                    if failed {
+                        break;
+                    }
+                }
+            },
+            move |result| {
+                if result.is_err() {
+                    Err(parse_select_object_content_error(result).map(ObjectClientError::ServiceError))
+                } else {
+                    // A successful response shouldn't end partway through a message
+                    decoder_finish.lock().unwrap().finish().map_err(|e| {
+                        Some(ObjectClientError::ClientError(S3RequestError::InternalError(Box::new(
+                            e,
+                        ))))
+                    })
+                }
+            },
+        )?;
+
+        Ok(S3SelectObjectContentRequest {
+            request,
+            event_receiver: receiver,
+            finished: false,
+        })
+    }
+}
+
+/// A streaming response to a SelectObjectContent request.
+///
+/// This struct implements [`futures::Stream`], which you can use to read the events of the
+/// response. The stream stops after the first error, which can arrive after some records if the
+/// query fails partway through the object.
+#[derive(Debug)]
+#[pin_project]
+pub struct S3SelectObjectContentRequest {
+    #[pin]
+    request: S3HttpRequest<(), SelectObjectContentError>,
+    #[pin]
+    event_receiver: UnboundedReceiver<ObjectClientResult<SelectEvent, SelectObjectContentError, S3RequestError>>,
+    finished: bool,
+}
+
+impl S3SelectObjectContentRequest {
+    /// A handle that can cancel this request, for example from another task that isn't polling
+    /// the events.
+    pub fn handle(&self) -> RequestHandle {
+        self.request.handle()
+    }
+}
+
+impl Stream for S3SelectObjectContentRequest {
+    type Item = ObjectClientResult<SelectEvent, SelectObjectContentError, S3RequestError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+
+        let this = self.project();
+
+        if let Poll::Ready(Some(event)) = this.event_receiver.poll_next(cx) {
+            *this.finished = event.is_err();
+            return Poll::Ready(Some(event));
+        }
+
+        match this.request.poll(cx) {
+            Poll::Ready(Ok(_)) => {
+                *this.finished = true;
+                Poll::Ready(None)
+            }
+            Poll::Ready(Err(e)) => {
+                *this.finished = true;
+                Poll::Ready(Some(Err(e)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Build the XML body of a SelectObjectContent request.
+fn build_select_object_content_body(
+    expression: &str,
+    input_serialization: SelectInputSerialization,
+    output_serialization: SelectOutputSerialization,
+) -> Result<Vec<u8>, xmltree::Error> {
+    fn element(name: &str, children: Vec<Element>) -> Element {
+        let mut element = Element::new(name);
+        element.children = children.into_iter().map(XMLNode::Element).collect();
+        element
+    }
+
+    fn text_element(name: &str, text: &str) -> Element {
+        let mut element = Element::new(name);
+        element.children.push(XMLNode::Text(text.to_owned()));
+        element
+    }
+
+    let input = match input_serialization {
+        SelectInputSerialization::Csv { file_header_info } => {
+            let file_header_info = match file_header_info {
+                CsvFileHeaderInfo::None => "NONE",
+                CsvFileHeaderInfo::Ignore => "IGNORE",
+                CsvFileHeaderInfo::Use => "USE",
+            };
+            element("CSV", vec![text_element("FileHeaderInfo", file_header_info)])
+        }
+        SelectInputSerialization::Json { json_type } => {
+            let json_type = match json_type {
+                JsonType::Document => "DOCUMENT",
+                JsonType::Lines => "LINES",
+            };
+            element("JSON", vec![text_element("Type", json_type)])
+        }
+        SelectInputSerialization::Parquet => element("Parquet", vec![]),
+    };
+    let output = match output_serialization {
+        SelectOutputSerialization::Csv => element("CSV", vec![]),
+        SelectOutputSerialization::Json => element("JSON", vec![]),
+    };
+
+    let mut root = element(
+        "SelectObjectContentRequest",
+        vec![
+            text_element("Expression", expression),
+            text_element("ExpressionType", "SQL"),
+            element("InputSerialization", vec![input]),
+            element("OutputSerialization", vec![output]),
+        ],
+    );
+    root.namespace = Some("http://s3.amazonaws.com/doc/2006-03-01/".to_owned());
+
+    let mut body = Vec::new();
+    root.write(&mut body)?;
+    Ok(body)
+}
+
+/// Convert an event-stream message into an event. Returns `None` for messages that don't need to
+/// be surfaced, like keep-alive `Cont` events.
+fn parse_event(message: &Message) -> ObjectClientResult<Option<SelectEvent>, SelectObjectContentError, S3RequestError> {
+    match message.string_header(":message-type") {
+        Some("error") => {
+            let code = message.string_header(":error-code").unwrap_or_default().to_owned();
+            let message = message.string_header(":error-message").unwrap_or_default().to_owned();
+            Err(ObjectClientError::ServiceError(SelectObjectContentError::QueryFailed {
+                code,
+                message,
+            }))
+        }
+        Some("event") => match message.string_header(":event-type") {
+            Some("Records") => Ok(Some(SelectEvent::Records(message.payload.clone().into_boxed_slice()))),
+            Some("Stats") => parse_stats(&message.payload)
+                .map(|stats| Some(SelectEvent::Stats(stats)))
+                .map_err(|e| ObjectClientError::ClientError(S3RequestError::InternalError(Box::new(e)))),
+            Some("End") => Ok(Some(SelectEvent::End)),
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Parse the XML payload of a `Stats` event.
+fn parse_stats(payload: &[u8]) -> Result<SelectStats, ParseError> {
+    let root = Element::parse(payload)?;
+    let get_u64 = |name: &str| {
+        get_field(&root, name)?
+            .parse::<u64>()
+            .map_err(|e| ParseError::Int(e, name.to_owned()))
+    };
+    Ok(SelectStats {
+        bytes_scanned: get_u64("BytesScanned")?,
+        bytes_processed: get_u64("BytesProcessed")?,
+        bytes_returned: get_u64("BytesReturned")?,
+    })
+}
+
+fn parse_select_object_content_error(result: &MetaRequestResult) -> Option<SelectObjectContentError> {
+    let body = result.error_response_body.as_ref()?;
+    let root = parse_xml(body.as_bytes()).ok()?;
+    let error_code = root.get_child("Code")?;
+    let error_str = error_code.get_text()?;
+    match (result.response_status, error_str.deref()) {
+        (404, "NoSuchBucket") => Some(SelectObjectContentError::NoSuchBucket),
+        (404, "NoSuchKey") => Some(SelectObjectContentError::NoSuchKey),
+        // S3 rejects invalid expressions and objects it can't parse with a 400
+        (400, _) => {
+            let message = root
+                .get_child("Message")
+                .and_then(|message| message.get_text())
+                .unwrap_or_default();
+            Some(SelectObjectContentError::QueryFailed {
+                code: error_str.into_owned(),
+                message: message.into_owned(),
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsString;
+
+    use test_case::test_case;
+
+    use super::*;
+    use crate::s3_crt_client::event_stream::encode_message;
+
+    fn make_result(response_status: i32, body: impl Into<OsString>) -> MetaRequestResult {
+        MetaRequestResult {
+            response_status,
+            crt_error: 1i32.into(),
+            error_response_headers: None,
+            error_response_body: Some(body.into()),
+            validation_algorithm: None,
+            metrics: Default::default(),
+        }
+    }
+
+    fn decode(encoded: &[u8]) -> Message {
+        let mut decoder = Decoder::new();
+        decoder.push(encoded);
+        decoder.next_message().unwrap().expect("message should be complete")
+    }
+
+    #[test_case(404, "NoSuchBucket", Some(SelectObjectContentError::NoSuchBucket); "no such bucket")]
+    #[test_case(404, "NoSuchKey", Some(SelectObjectContentError::NoSuchKey); "no such key")]
+    #[test_case(400, "InvalidExpressionType", Some(SelectObjectContentError::QueryFailed { code: "InvalidExpressionType".to_owned(), message: "Some message".to_owned() }); "invalid expression")]
+    #[test_case(500, "InternalError", None; "internal error")]
+    fn parse_error(status: i32, code: &str, expected: Option<SelectObjectContentError>) {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>{code}</Code><Message>Some message</Message><RequestId>4VAGDP5HMYTDNB3Y</RequestId></Error>"#
+        );
+        let result = make_result(status, body);
+        assert_eq!(parse_select_object_content_error(&result), expected);
+    }
+
+    #[test]
+    fn build_body() {
+        let body = build_select_object_content_body(
+            "SELECT * FROM S3Object s WHERE s.size > 10",
+            SelectInputSerialization::Csv {
+                file_header_info: CsvFileHeaderInfo::Use,
+            },
+            SelectOutputSerialization::Json,
+        )
+        .unwrap();
+        let root = Element::parse(&body[..]).unwrap();
+        assert_eq!(root.name, "SelectObjectContentRequest");
+        assert_eq!(
+            get_field(&root, "Expression").unwrap(),
+            "SELECT * FROM S3Object s WHERE s.size > 10"
+        );
+        assert_eq!(get_field(&root, "ExpressionType").unwrap(), "SQL");
+        let csv = root.get_child("InputSerialization").unwrap().get_child("CSV").unwrap();
+        assert_eq!(get_field(csv, "FileHeaderInfo").unwrap(), "USE");
+        let output = root.get_child("OutputSerialization").unwrap();
+        assert!(output.get_child("JSON").is_some());
+    }
+
+    #[test]
+    fn parse_events() {
+        let records = encode_message(
+            &[(":message-type", "event"), (":event-type", "Records")],
+            b"{\"a\":1}\n",
+        );
+        assert_eq!(
+            parse_event(&decode(&records)).unwrap(),
+            Some(SelectEvent::Records(b"{\"a\":1}\n".to_vec().into_boxed_slice()))
+        );
+
+        let stats = encode_message(
+            &[(":message-type", "event"), (":event-type", "Stats")],
+            b"<Stats><BytesScanned>100</BytesScanned><BytesProcessed>200</BytesProcessed><BytesReturned>8</BytesReturned></Stats>",
+        );
+        assert_eq!(
+            parse_event(&decode(&stats)).unwrap(),
+            Some(SelectEvent::Stats(SelectStats {
+                bytes_scanned: 100,
+                bytes_processed: 200,
+                bytes_returned: 8,
+            }))
+        );
+
+        let cont = encode_message(&[(":message-type", "event"), (":event-type", "Cont")], b"");
+        assert_eq!(parse_event(&decode(&cont)).unwrap(), None);
+
+        let end = encode_message(&[(":message-type", "event"), (":event-type", "End")], b"");
+        assert_eq!(parse_event(&decode(&end)).unwrap(), Some(SelectEvent::End));
+    }
+
+    #[test]
+    fn parse_error_event() {
+        let error = encode_message(
+            &[
+                (":message-type", "error"),
+                (":error-code", "CSVParsingError"),
+                (":error-message", "Encountered an error parsing the CSV file."),
+            ],
+            b"",
+        );
+        let Err(ObjectClientError::ServiceError(SelectObjectContentError::QueryFailed { code, message })) =
+            parse_event(&decode(&error))
+        else {
+            panic!("error event should fail the request");
+        };
+        assert_eq!(code, "CSVParsingError");
+        assert_eq!(message, "Encountered an error parsing the CSV file.");
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+
+use mountpoint_s3_crt_sys::{aws_auth_errors, aws_io_errors};
+
+use crate::object_client::ObjectClientError;
+use crate::s3_crt_client::{HeadBucketError, S3CrtClient, S3RequestError, TimeoutKind};
+
+/// A step of a [`self_test`](S3CrtClient::self_test), in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelfTestStep {
+    /// Checking the bucket name and resolving the endpoint for the bucket from the client's
+    /// endpoint configuration
+    Endpoint,
+
+    /// Getting credentials from the client's credentials provider to sign the request with. This
+    /// step doesn't run for clients that don't sign requests.
+    Credentials,
+
+    /// Looking up the endpoint's host name
+    Dns,
+
+    /// Opening a connection to the endpoint
+    Connection,
+
+    /// Negotiating TLS on the connection. This step only runs for HTTPS endpoints.
+    Tls,
+
+    /// Sending an authenticated HeadBucket request and getting a successful response
+    Request,
+}
+
+impl fmt::Display for SelfTestStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let step = match self {
+            SelfTestStep::Endpoint => "endpoint resolution",
+            SelfTestStep::Credentials => "credentials",
+            SelfTestStep::Dns => "DNS lookup",
+            SelfTestStep::Connection => "connection",
+            SelfTestStep::Tls => "TLS negotiation",
+            SelfTestStep::Request => "HeadBucket request",
+        };
+        f.write_str(step)
+    }
+}
+
+/// The step of a [`self_test`](S3CrtClient::self_test) that failed, and its error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SelfTestFailure {
+    /// The step that failed
+    pub step: SelfTestStep,
+
+    /// The error the step failed with
+    pub error: ObjectClientError<HeadBucketError, S3RequestError>,
+}
+
+/// The result of a [`self_test`](S3CrtClient::self_test).
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct SelfTestReport {
+    /// The steps that succeeded, in the order they ran
+    pub passed: Vec<SelfTestStep>,
+
+    /// The step that failed, if any. The steps after it didn't run.
+    pub failure: Option<SelfTestFailure>,
+}
+
+impl SelfTestReport {
+    /// Whether every step succeeded.
+    pub fn is_ok(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+impl S3CrtClient {
+    /// Check that the client can reach a bucket, and report which step failed if it can't, so that
+    /// credential problems can be told apart from network problems, for example at startup.
+    ///
+    /// The steps run in the order of [SelfTestStep]. Endpoint resolution runs on its own, and the
+    /// rest all happen as part of a single HeadBucket request, so they're told apart by the error
+    /// the request fails with. Failures that can't be attributed to an earlier step are reported as
+    /// failures of the [request](SelfTestStep::Request) itself.
+    pub async fn self_test(&self, bucket: &str) -> SelfTestReport {
+        let mut passed = Vec::new();
+
+        let endpoint = self.check_bucket_name(bucket).and_then(|()| {
+            self.inner
+                .resolve_endpoint(bucket)
+                .map_err(S3RequestError::construction_failure)
+        });
+        let endpoint = match endpoint {
+            Ok(endpoint) => endpoint,
+            Err(e) => {
+                return SelfTestReport {
+                    passed,
+                    failure: Some(SelfTestFailure {
+                        step: SelfTestStep::Endpoint,
+                        error: ObjectClientError::ClientError(e),
+                    }),
+                }
+            }
+        };
+        passed.push(SelfTestStep::Endpoint);
+
+        let mut steps = Vec::new();
+        if self.inner.credentials_provider.is_some() {
+            steps.push(SelfTestStep::Credentials);
+        }
+        steps.extend([SelfTestStep::Dns, SelfTestStep::Connection]);
+        if endpoint.uri.scheme() == "https" {
+            steps.push(SelfTestStep::Tls);
+        }
+        steps.push(SelfTestStep::Request);
+        let failure = match self.head_bucket(bucket).await {
+            Ok(()) => None,
+            Err(error) => Some(SelfTestFailure {
+                step: failed_step(&error),
+                error,
+            }),
+        };
+        let failed = failure.as_ref().map(|failure| failure.step);
+        passed.extend(steps.into_iter().take_while(|step| Some(*step) != failed));
+        SelfTestReport { passed, failure }
+    }
+}
+
+/// Find the step of a self test that a HeadBucket request failed at.
+fn failed_step(error: &ObjectClientError<HeadBucketError, S3RequestError>) -> SelfTestStep {
+    let crt_error = match error {
+        ObjectClientError::ClientError(S3RequestError::NoSigningCredentials) => return SelfTestStep::Credentials,
+        ObjectClientError::ClientError(S3RequestError::Timeout(TimeoutKind::Connect)) => {
+            return SelfTestStep::Connection
+        }
+        ObjectClientError::ClientError(S3RequestError::CrtError(e)) => e.raw_error(),
+        ObjectClientError::ClientError(S3RequestError::ResponseError(result)) if result.response_status == 0 => {
+            result.crt_error.raw_error()
+        }
+        _ => return SelfTestStep::Request,
+    };
+
+    let auth_errors = aws_auth_errors::AWS_AUTH_PROFILE_PARSE_RECOVERABLE_ERROR as i32
+        ..aws_auth_errors::AWS_AUTH_ERROR_END_RANGE as i32;
+    if auth_errors.contains(&crt_error) {
+        return SelfTestStep::Credentials;
+    }
+    let dns_errors = [
+        aws_io_errors::AWS_IO_DNS_QUERY_FAILED,
+        aws_io_errors::AWS_IO_DNS_INVALID_NAME,
+        aws_io_errors::AWS_IO_DNS_NO_ADDRESS_FOR_HOST,
+    ];
+    let connection_errors = [
+        aws_io_errors::AWS_IO_SOCKET_CONNECTION_REFUSED,
+        aws_io_errors::AWS_IO_SOCKET_NO_ROUTE_TO_HOST,
+        aws_io_errors::AWS_IO_SOCKET_NETWORK_DOWN,
+        aws_io_errors::AWS_IO_SOCKET_TIMEOUT,
+    ];
+    let tls_errors = [
+        aws_io_errors::AWS_IO_TLS_ERROR_NEGOTIATION_FAILURE,
+        aws_io_errors::AWS_IO_TLS_ERROR_ALERT_RECEIVED,
+        aws_io_errors::AWS_IO_TLS_NEGOTIATION_TIMEOUT,
+    ];
+    let is_one_of = |errors: &[aws_io_errors]| errors.iter().any(|error| *error as i32 == crt_error);
+    if is_one_of(&dns_errors) {
+        SelfTestStep::Dns
+    } else if is_one_of(&connection_errors) {
+        SelfTestStep::Connection
+    } else if is_one_of(&tls_errors) {
+        SelfTestStep::Tls
+    } else {
+        SelfTestStep::Request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(S3RequestError::NoSigningCredentials, SelfTestStep::Credentials; "no credentials")]
+    #[test_case(
+        S3RequestError::CrtError((aws_auth_errors::AWS_AUTH_CREDENTIALS_PROVIDER_DELEGATE_FAILURE as i32).into()),
+        SelfTestStep::Credentials;
+        "credentials provider failure"
+    )]
+    #[test_case(
+        S3RequestError::CrtError((aws_io_errors::AWS_IO_DNS_INVALID_NAME as i32).into()),
+        SelfTestStep::Dns;
+        "dns failure"
+    )]
+    #[test_case(S3RequestError::Timeout(TimeoutKind::Connect), SelfTestStep::Connection; "connect timeout")]
+    #[test_case(
+        S3RequestError::CrtError((aws_io_errors::AWS_IO_SOCKET_CONNECTION_REFUSED as i32).into()),
+        SelfTestStep::Connection;
+        "connection refused"
+    )]
+    #[test_case(
+        S3RequestError::CrtError((aws_io_errors::AWS_IO_TLS_ERROR_NEGOTIATION_FAILURE as i32).into()),
+        SelfTestStep::Tls;
+        "tls failure"
+    )]
+    #[test_case(S3RequestError::Throttled, SelfTestStep::Request; "throttled")]
+    fn test_failed_step(error: S3RequestError, expected: SelfTestStep) {
+        assert_eq!(failed_step(&ObjectClientError::ClientError(error)), expected);
+    }
+
+    #[test]
+    fn test_failed_step_service_error() {
+        let error = ObjectClientError::ServiceError(HeadBucketError::AccessDenied);
+        assert_eq!(failed_step(&error), SelfTestStep::Request);
+    }
+}
@@ -0,0 +1,149 @@
+use std::convert::Infallible;
+use std::os::unix::prelude::OsStrExt;
+use std::sync::{Arc, Mutex};
+
+use mountpoint_s3_crt::http::request_response::{Header, Headers};
+use mountpoint_s3_crt::s3::client::MetaRequestType;
+
+use crate::object_client::{ObjectClientError, ResponseMetadata};
+use crate::s3_crt_client::{QueryFragment, S3CrtClient, S3CrtClientInner, S3RequestError};
+
+/// The response to a raw request made with [`send_request`](S3CrtClient::send_request)
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RawResponse {
+    /// The HTTP status of the response
+    pub status: i32,
+
+    /// The headers of the response, in the order S3 sent them
+    pub headers: Vec<(String, String)>,
+
+    /// The body of the response
+    pub body: Vec<u8>,
+
+    /// Metadata about the response
+    pub response_metadata: ResponseMetadata,
+}
+
+fn collect_headers(headers: &Headers) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string_lossy().into_owned(),
+                value.to_string_lossy().into_owned(),
+            )
+        })
+        .collect()
+}
+
+impl S3CrtClient {
+    /// Send an arbitrary request to a bucket, for S3 operations this client doesn't otherwise
+    /// support. The request is signed like any other request from this client, and goes to the
+    /// endpoint the client resolves for the bucket.
+    ///
+    /// The `path` is relative to the bucket (for example `/key`, or `/` for bucket-level
+    /// operations), and neither it nor the `query` should be URL-encoded. Query parameters with an
+    /// empty value are written as `key=`, which S3 accepts for subresources like `tagging`.
+    ///
+    /// Any response S3 sends is returned as a [RawResponse], whatever its status, and it's up to
+    /// the caller to interpret it; error responses are never parsed. An error is only returned if
+    /// the request couldn't be made or no response was received. The client still retries
+    /// responses it considers retryable, like 503 SlowDown, before returning the last of them.
+    pub async fn send_request(
+        &self,
+        bucket: &str,
+        method: &str,
+        path: &str,
+        query: &[(&str, &str)],
+        headers: &[(&str, &str)],
+        body: Option<Vec<u8>>,
+    ) -> Result<RawResponse, S3RequestError> {
+        self.check_bucket(bucket).await?;
+
+        // The headers of a successful response are only available through the headers callback
+        let response_headers: Arc<Mutex<Vec<(String, String)>>> = Default::default();
+        let response_headers_writer = Arc::clone(&response_headers);
+        let response_body: Arc<Mutex<Vec<u8>>> = Default::default();
+        let response_body_writer = Arc::clone(&response_body);
+
+        // Scope the endpoint, message, etc. since otherwise rustc thinks we use Message across the await.
+        let request = {
+            let mut message = self
+                .new_request_template(method, bucket)
+                .map_err(S3RequestError::construction_failure)?;
+            let query: Vec<_> = query
+                .iter()
+                .map(|(key, value)| QueryFragment::Pair(key, value))
+                .collect();
+            message
+                .set_request_path_and_query(path, &query)
+                .map_err(S3RequestError::construction_failure)?;
+            for (name, value) in headers {
+                message
+                    .set_header(&Header::new(name, value))
+                    .map_err(S3RequestError::construction_failure)?;
+            }
+            if let Some(body) = body {
+                message.set_body(body).map_err(S3RequestError::construction_failure)?;
+            }
+
+            let span = request_span!(self, "send_request", bucket, method, path);
+
+            let request_timeouts = message.request_timeouts;
+            let options = S3CrtClientInner::new_meta_request_options(message, MetaRequestType::Default, &span);
+            self.inner.make_meta_request_from_options(
+                options,
+                request_timeouts,
+                span,
+                |_| {},
+                move |headers, status| {
+                    if (200..300).contains(&status) {
+                        *response_headers_writer.lock().unwrap() = collect_headers(headers);
+                    }
+                },
+                move |offset, data| {
+                    let mut body = response_body_writer.lock().unwrap();
+                    assert_eq!(offset as usize, body.len());
+                    body.extend_from_slice(data);
+                },
+                move |result| {
+                    let mut response_metadata = ResponseMetadata::new(result.response_status);
+                    response_metadata.request_timings = result.metrics.last_request_timings;
+                    response_metadata.metrics = result.metrics.clone();
+                    if !result.is_err() {
+                        return Ok(RawResponse {
+                            status: result.response_status,
+                            headers: std::mem::take(&mut *response_headers.lock().unwrap()),
+                            body: std::mem::take(&mut *response_body.lock().unwrap()),
+                            response_metadata,
+                        });
+                    }
+                    // A status of 0 means no response was received, so there's nothing to return
+                    if result.response_status == 0 {
+                        return Err(None::<ObjectClientError<Infallible, S3RequestError>>);
+                    }
+                    Ok(RawResponse {
+                        status: result.response_status,
+                        headers: result
+                            .error_response_headers
+                            .as_ref()
+                            .map(collect_headers)
+                            .unwrap_or_default(),
+                        body: result
+                            .error_response_body
+                            .as_ref()
+                            .map(|body| body.as_bytes().to_vec())
+                            .unwrap_or_default(),
+                        response_metadata,
+                    })
+                },
+            )?
+        };
+
+        request.await.map_err(|err| match err {
+            ObjectClientError::ServiceError(never) => match never {},
+            ObjectClientError::ClientError(err) => err,
+        })
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+use mountpoint_s3_crt::common::allocator::Allocator;
+use mountpoint_s3_crt::io::tls::{TlsConnectionOptions, TlsContext, TlsContextOptions};
+
+use crate::s3_crt_client::NewClientError;
+
+/// TLS configuration for connections to S3. By default, the client verifies the endpoint's
+/// certificate against the system's trust store and doesn't present a client certificate.
+#[derive(Debug, Clone, Default)]
+pub struct TlsConfig {
+    ca_file: Option<PathBuf>,
+    client_certificate: Option<(PathBuf, PathBuf)>,
+    insecure_skip_verify: bool,
+}
+
+impl TlsConfig {
+    /// Create a new TLS configuration with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify the endpoint's certificate against the certificate authorities in the given PEM
+    /// file instead of the system's trust store, for example for an endpoint with a certificate
+    /// from an internal CA. Client creation fails if the file can't be read.
+    pub fn ca_file(mut self, ca_file: impl AsRef<Path>) -> Self {
+        self.ca_file = Some(ca_file.as_ref().to_owned());
+        self
+    }
+
+    /// Authenticate to the endpoint with the certificate and private key in the given PEM files
+    /// (mutual TLS). Client creation fails if either file can't be read.
+    pub fn client_certificate(mut self, cert_file: impl AsRef<Path>, private_key_file: impl AsRef<Path>) -> Self {
+        self.client_certificate = Some((cert_file.as_ref().to_owned(), private_key_file.as_ref().to_owned()));
+        self
+    }
+
+    /// **Insecure:** skip verifying the endpoint's certificate.
+    ///
+    /// This allows anyone who can intercept connections to impersonate the endpoint and read or
+    /// modify requests and responses, including credentials and object data. Only use it for
+    /// testing against endpoints with throwaway certificates.
+    pub fn insecure_skip_verify(mut self, insecure_skip_verify: bool) -> Self {
+        self.insecure_skip_verify = insecure_skip_verify;
+        self
+    }
+
+    /// Create the CRT's TLS options for connections to S3.
+    pub(crate) fn to_tls_connection_options(
+        &self,
+        allocator: &Allocator,
+    ) -> Result<TlsConnectionOptions, NewClientError> {
+        // The CRT reports unreadable files as a generic error, so check them first to give a
+        // clearer one
+        let check_readable = |kind: &str, path: &Path| {
+            std::fs::File::open(path).map(|_| ()).map_err(|e| {
+                NewClientError::InvalidConfiguration(format!("could not read {kind} {}: {e}", path.display()))
+            })
+        };
+
+        let mut options = match &self.client_certificate {
+            Some((cert_file, private_key_file)) => {
+                check_readable("client certificate", cert_file)?;
+                check_readable("client private key", private_key_file)?;
+                TlsContextOptions::new_client_mtls_from_path(allocator, cert_file, private_key_file)
+                    .map_err(NewClientError::CrtError)?
+            }
+            None => TlsContextOptions::new_client(allocator),
+        };
+        if let Some(ca_file) = &self.ca_file {
+            check_readable("CA file", ca_file)?;
+            options.ca_file(ca_file).map_err(NewClientError::CrtError)?;
+        }
+        options.verify_peer(!self.insecure_skip_verify);
+
+        let context = TlsContext::new_client_with_options(allocator, &options).map_err(NewClientError::CrtError)?;
+        Ok(TlsConnectionOptions::from_context(&context))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_tls_config() {
+        let allocator = Allocator::default();
+        TlsConfig::new()
+            .to_tls_connection_options(&allocator)
+            .expect("default config should succeed");
+    }
+
+    #[test]
+    fn missing_ca_file() {
+        let allocator = Allocator::default();
+        let err = TlsConfig::new()
+            .ca_file("/nonexistent/ca-bundle.pem")
+            .to_tls_connection_options(&allocator)
+            .expect_err("missing CA file should fail");
+        let NewClientError::InvalidConfiguration(message) = err else {
+            panic!("wrong error: {err:?}");
+        };
+        assert!(message.contains("/nonexistent/ca-bundle.pem"), "got {message}");
+    }
+
+    #[test]
+    fn missing_client_certificate() {
+        let allocator = Allocator::default();
+        let err = TlsConfig::new()
+            .client_certificate("/nonexistent/client.pem", "/nonexistent/client.key")
+            .to_tls_connection_options(&allocator)
+            .expect_err("missing client certificate should fail");
+        assert!(matches!(err, NewClientError::InvalidConfiguration(_)));
+    }
+}
@@ -81,6 +81,24 @@ fn sanitize_string(s: impl AsRef<str>) -> String {
         .replace(|c: char| !c.is_alphanumeric() && !VALID_CHARS.contains(&c), "-")
 }
 
+/// Whether `suffix` is safe to append to a User-agent header: one or more products separated by
+/// single spaces, where each product is a token optionally followed by `/` and a version token
+/// (RFC 7230 section 3.2.6 and RFC 7231 section 5.5.3).
+pub(crate) fn is_valid_user_agent_suffix(suffix: &str) -> bool {
+    fn is_token(s: &str) -> bool {
+        const VALID_CHARS: &[char] = &[
+            '!', '#', '$', '%', '&', '\'', '*', '+', '-', '.', '^', '_', '`', '|', '~',
+        ];
+        !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || VALID_CHARS.contains(&c))
+    }
+
+    !suffix.is_empty()
+        && suffix.split(' ').all(|product| match product.split_once('/') {
+            Some((name, version)) => is_token(name) && is_token(version),
+            None => is_token(product),
+        })
+}
+
 fn canonicalize_sysname(sysname: impl AsRef<str>) -> &'static str {
     match sysname.as_ref() {
         "Linux" => "linux",
@@ -157,6 +175,21 @@ mod tests {
         assert!(user_agent.starts_with("mountpoint-s3-client/"));
     }
 
+    #[test]
+    fn test_user_agent_suffix() {
+        assert!(is_valid_user_agent_suffix("my-app"));
+        assert!(is_valid_user_agent_suffix("my-app/1.2.3"));
+        assert!(is_valid_user_agent_suffix("my-app/1.2.3 team#storage"));
+        assert!(!is_valid_user_agent_suffix(""));
+        assert!(!is_valid_user_agent_suffix("my-app/"));
+        assert!(!is_valid_user_agent_suffix("my-app/1/2"));
+        assert!(!is_valid_user_agent_suffix("my-app  other"));
+        assert!(!is_valid_user_agent_suffix("my-app\r\nX-Injected: true"));
+        assert!(!is_valid_user_agent_suffix("my-app\n"));
+        assert!(!is_valid_user_agent_suffix("(comment)"));
+        assert!(!is_valid_user_agent_suffix("app\u{e9}"));
+    }
+
     #[test]
     fn test_sanitize() {
         assert_eq!(
@@ -15,6 +15,7 @@ use futures::StreamExt;
 use mountpoint_s3_client::config::{EndpointConfig, S3ClientAuthConfig, S3ClientConfig};
 #[cfg(not(feature = "s3express_tests"))]
 use mountpoint_s3_client::error::ObjectClientError;
+use mountpoint_s3_client::types::GetObjectParams;
 #[cfg(not(feature = "s3express_tests"))]
 use mountpoint_s3_client::S3RequestError;
 use mountpoint_s3_client::{ObjectClient, S3CrtClient};
@@ -63,7 +64,7 @@ async fn test_static_provider() {
     let client = S3CrtClient::new(config).unwrap();
 
     let result = client
-        .get_object(&bucket, &key, None, None)
+        .get_object(&bucket, &key, &GetObjectParams::new())
         .await
         .expect("get_object should succeed");
     check_get_result(result, None, &body[..]).await;
@@ -83,7 +84,7 @@ async fn test_static_provider() {
     let client = S3CrtClient::new(config).unwrap();
 
     let mut request = client
-        .get_object(&bucket, &key, None, None)
+        .get_object(&bucket, &key, &GetObjectParams::new())
         .await
         .expect("get_object request should be sent");
 
@@ -152,7 +153,7 @@ async fn test_profile_provider_static_async() {
     let client = S3CrtClient::new(config).unwrap();
 
     let result = client
-        .get_object(&bucket, &key, None, None)
+        .get_object(&bucket, &key, &GetObjectParams::new())
         .await
         .expect("get_object should succeed");
     check_get_result(result, None, &body[..]).await;
@@ -168,7 +169,7 @@ async fn test_profile_provider_static_async() {
     let client = S3CrtClient::new(config).unwrap();
 
     let mut request = client
-        .get_object(&bucket, &key, None, None)
+        .get_object(&bucket, &key, &GetObjectParams::new())
         .await
         .expect("get_object should be sent");
 
@@ -241,7 +242,7 @@ async fn test_profile_provider_assume_role_async() {
     let client = S3CrtClient::new(config).unwrap();
 
     let mut request = client
-        .get_object(&bucket, &key, None, None)
+        .get_object(&bucket, &key, &GetObjectParams::new())
         .await
         .expect("get_object should be sent");
 
@@ -256,7 +257,7 @@ async fn test_profile_provider_assume_role_async() {
     let client = S3CrtClient::new(config).unwrap();
 
     let result = client
-        .get_object(&bucket, &key, None, None)
+        .get_object(&bucket, &key, &GetObjectParams::new())
         .await
         .expect("get_object should succeed");
     check_get_result(result, None, &body[..]).await;
@@ -338,17 +339,17 @@ async fn test_scoped_credentials() {
 
     // Inside the prefix, things should be fine
     let _result = client
-        .get_object(&bucket, &format!("{prefix}foo/foo.txt"), None, None)
+        .get_object(&bucket, &format!("{prefix}foo/foo.txt"), &GetObjectParams::new())
         .await
         .expect("get_object should succeed");
     let _result = client
-        .list_objects(&bucket, None, "/", 10, &format!("{prefix}foo/"))
+        .list_objects(&bucket, None, Some("/"), 10, &format!("{prefix}foo/"))
         .await
         .expect("list_objects_should_succeed");
 
     // Outside the prefix, requests should fail with permissions errors
     let mut request = client
-        .get_object(&bucket, &format!("{prefix}baz.txt"), None, None)
+        .get_object(&bucket, &format!("{prefix}baz.txt"), &GetObjectParams::new())
         .await
         .expect("request should be sent");
     let err = request
@@ -361,7 +362,7 @@ async fn test_scoped_credentials() {
         ObjectClientError::ClientError(S3RequestError::Forbidden(_, _))
     ));
     let err = client
-        .list_objects(&bucket, None, "/", 10, &format!("{prefix}/"))
+        .list_objects(&bucket, None, Some("/"), 10, &format!("{prefix}/"))
         .await
         .expect_err("should fail in different prefix");
     assert!(matches!(
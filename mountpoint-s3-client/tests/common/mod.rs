@@ -73,6 +73,19 @@ pub fn get_test_client() -> S3CrtClient {
     S3CrtClient::new(S3ClientConfig::new().endpoint_config(endpoint_config)).expect("could not create test client")
 }
 
+/// A test client that sends requests to buckets with invalid names, like `DOC-EXAMPLE-BUCKET`.
+/// Since bucket names are case sensitive and new buckets can't have uppercase names, S3 reports
+/// such buckets as missing, which is useful for testing that error.
+pub fn get_test_client_without_bucket_name_validation() -> S3CrtClient {
+    let endpoint_config = EndpointConfig::new(&get_test_region());
+    S3CrtClient::new(
+        S3ClientConfig::new()
+            .endpoint_config(endpoint_config)
+            .validate_bucket_names(false),
+    )
+    .expect("could not create test client")
+}
+
 pub fn get_test_backpressure_client(initial_read_window: usize) -> S3CrtClient {
     let endpoint_config = EndpointConfig::new(&get_test_region());
     S3CrtClient::new(
@@ -0,0 +1,83 @@
+#![cfg(feature = "s3_tests")]
+
+pub mod common;
+
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use common::*;
+use mountpoint_s3_client::error::{CopyObjectError, ObjectClientError};
+use mountpoint_s3_client::types::{CopyObjectParams, MetadataDirective};
+use mountpoint_s3_client::{ObjectClient, S3CrtClient};
+use test_case::test_case;
+
+#[test_case(MetadataDirective::Copy, "value"; "copy metadata")]
+#[test_case(MetadataDirective::Replace, ""; "replace metadata")]
+#[tokio::test]
+async fn test_copy_object(metadata_directive: MetadataDirective, expected_metadata: &str) {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_copy_object");
+
+    let source_key = format!("{prefix}/source");
+    let destination_key = format!("{prefix}/destination");
+    let body = b"hello world!";
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&source_key)
+        .metadata("test-key", "value")
+        .body(ByteStream::from(Bytes::from_static(body)))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let params = CopyObjectParams::new().metadata_directive(metadata_directive);
+    let result = client
+        .copy_object(&bucket, &source_key, &bucket, &destination_key, &params)
+        .await
+        .expect("copy_object should succeed");
+
+    let head = sdk_client
+        .head_object()
+        .bucket(&bucket)
+        .key(&destination_key)
+        .send()
+        .await
+        .expect("destination object should exist");
+    assert_eq!(head.e_tag(), Some(result.etag.as_str()));
+    let metadata = head.metadata().and_then(|m| m.get("test-key")).map(String::as_str);
+    assert_eq!(metadata.unwrap_or_default(), expected_metadata);
+
+    let get = sdk_client
+        .get_object()
+        .bucket(&bucket)
+        .key(&destination_key)
+        .send()
+        .await
+        .unwrap();
+    let copied = get.body.collect().await.unwrap().into_bytes();
+    assert_eq!(&copied[..], body);
+}
+
+#[tokio::test]
+async fn test_copy_object_no_such_key() {
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_copy_object_no_such_key");
+
+    let source_key = format!("{prefix}/nonexistent_key");
+    let destination_key = format!("{prefix}/destination");
+
+    let client: S3CrtClient = get_test_client();
+    let result = client
+        .copy_object(
+            &bucket,
+            &source_key,
+            &bucket,
+            &destination_key,
+            &CopyObjectParams::new(),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(ObjectClientError::ServiceError(CopyObjectError::NoSuchKey))
+    ));
+}
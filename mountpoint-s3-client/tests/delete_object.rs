@@ -2,10 +2,13 @@
 
 pub mod common;
 
+use std::str::FromStr;
+
 use aws_sdk_s3::primitives::ByteStream;
 use bytes::Bytes;
 use common::*;
 use mountpoint_s3_client::error::{DeleteObjectError, ObjectClientError};
+use mountpoint_s3_client::types::{DeleteObjectParams, ETag};
 use mountpoint_s3_client::{ObjectClient, S3CrtClient, S3RequestError};
 
 #[tokio::test]
@@ -26,7 +29,7 @@ async fn test_delete_object() {
 
     let client: S3CrtClient = get_test_client();
     let _result = client
-        .delete_object(&bucket, &key)
+        .delete_object(&bucket, &key, &DeleteObjectParams::new())
         .await
         .expect("delete_object should succeed");
 
@@ -41,6 +44,53 @@ async fn test_delete_object() {
     assert!(head_obj_err.into_service_error().is_not_found());
 }
 
+#[tokio::test]
+async fn test_delete_object_if_match() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_delete_object_if_match");
+
+    let key = format!("{prefix}/hello");
+    let response = sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(ByteStream::from(Bytes::from_static(b"hello world!")))
+        .send()
+        .await
+        .unwrap();
+    let etag = ETag::from_str(response.e_tag().expect("E-Tag should be set")).unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let other_etag = ETag::from_str("\"incorrect_etag\"").unwrap();
+    let result = client
+        .delete_object(&bucket, &key, &DeleteObjectParams::new().if_match(Some(other_etag)))
+        .await;
+    assert!(matches!(
+        result,
+        Err(ObjectClientError::ServiceError(DeleteObjectError::PreconditionFailed))
+    ));
+    sdk_client
+        .head_object()
+        .bucket(&bucket)
+        .key(&key)
+        .send()
+        .await
+        .expect("object should still exist");
+
+    client
+        .delete_object(&bucket, &key, &DeleteObjectParams::new().if_match(Some(etag)))
+        .await
+        .expect("delete_object should succeed");
+    let head_obj_err = sdk_client
+        .head_object()
+        .bucket(&bucket)
+        .key(&key)
+        .send()
+        .await
+        .expect_err("object should not exist");
+    assert!(head_obj_err.into_service_error().is_not_found());
+}
+
 #[tokio::test]
 async fn test_delete_object_no_obj() {
     let sdk_client = get_test_sdk_client().await;
@@ -60,7 +110,7 @@ async fn test_delete_object_no_obj() {
 
     let client: S3CrtClient = get_test_client();
     let _result = client
-        .delete_object(&bucket, &key)
+        .delete_object(&bucket, &key, &DeleteObjectParams::new())
         .await
         .expect("delete_object should not fail for non-existent object");
 }
@@ -71,9 +121,11 @@ async fn test_delete_object_404_bucket() {
 
     let key = format!("{prefix}/nonexistent_key");
 
-    let client: S3CrtClient = get_test_client();
+    let client: S3CrtClient = get_test_client_without_bucket_name_validation();
 
-    let result = client.delete_object("DOC-EXAMPLE-BUCKET", &key).await;
+    let result = client
+        .delete_object("DOC-EXAMPLE-BUCKET", &key, &DeleteObjectParams::new())
+        .await;
     assert!(matches!(
         result,
         Err(ObjectClientError::ServiceError(DeleteObjectError::NoSuchBucket))
@@ -89,10 +141,69 @@ async fn test_delete_object_no_perm() {
 
     let client: S3CrtClient = get_test_client();
 
-    let result = client.delete_object(&bucket, &key).await;
+    let result = client.delete_object(&bucket, &key, &DeleteObjectParams::new()).await;
 
     assert!(matches!(
         result,
         Err(ObjectClientError::ClientError(S3RequestError::Forbidden(_, _)))
     ));
 }
+
+#[tokio::test]
+async fn test_delete_objects() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_delete_objects");
+
+    // More keys than fit in a single DeleteObjects request, most of which don't exist
+    let keys: Vec<_> = (0..1005).map(|i| format!("{prefix}/key{i}")).collect();
+    let existing_keys = [&keys[0], &keys[1002]];
+    for key in existing_keys {
+        sdk_client
+            .put_object()
+            .bucket(&bucket)
+            .key(key)
+            .body(ByteStream::from(Bytes::from_static(b"hello world!")))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let client: S3CrtClient = get_test_client();
+    let result = client
+        .delete_objects(&bucket, &keys)
+        .await
+        .expect("delete_objects should succeed");
+    assert!(result.errors.is_empty(), "unexpected errors: {:?}", result.errors);
+
+    let mut deleted = result.deleted;
+    deleted.sort();
+    let mut expected_keys = keys.clone();
+    expected_keys.sort();
+    assert_eq!(deleted, expected_keys);
+
+    for key in existing_keys {
+        let head_obj_err = sdk_client
+            .head_object()
+            .bucket(&bucket)
+            .key(key)
+            .send()
+            .await
+            .expect_err("object should not exist");
+        assert!(head_obj_err.into_service_error().is_not_found());
+    }
+}
+
+#[tokio::test]
+async fn test_delete_objects_404_bucket() {
+    let (_bucket, prefix) = get_test_bucket_and_prefix("test_delete_objects_404_bucket");
+
+    let keys = vec![format!("{prefix}/nonexistent_key")];
+
+    let client: S3CrtClient = get_test_client_without_bucket_name_validation();
+
+    let result = client.delete_objects("DOC-EXAMPLE-BUCKET", &keys).await;
+    assert!(matches!(
+        result,
+        Err(ObjectClientError::ServiceError(DeleteObjectError::NoSuchBucket))
+    ));
+}
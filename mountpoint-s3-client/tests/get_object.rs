@@ -2,6 +2,7 @@
 
 pub mod common;
 
+use std::io::Write;
 use std::ops::Range;
 use std::option::Option::None;
 use std::str::FromStr;
@@ -10,12 +11,13 @@ use std::thread;
 use std::time::Duration;
 
 use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
 use bytes::Bytes;
 use common::*;
 use futures::stream::StreamExt;
 use mountpoint_s3_client::error::{GetObjectError, ObjectClientError};
-use mountpoint_s3_client::types::ETag;
-use mountpoint_s3_client::{ObjectClient, S3CrtClient};
+use mountpoint_s3_client::types::{ChecksumAlgorithm, ETag, GetObjectParams, GetObjectRequest, HeadObjectParams};
+use mountpoint_s3_client::{ObjectClient, S3CrtClient, S3RequestError};
 
 use test_case::test_case;
 
@@ -45,7 +47,7 @@ async fn test_get_object(size: usize, range: Option<Range<u64>>) {
     let client: S3CrtClient = get_test_client();
 
     let result = client
-        .get_object(&bucket, &key, range.clone(), None)
+        .get_object(&bucket, &key, &GetObjectParams::new().range(range.clone()))
         .await
         .expect("get_object should succeed");
     let expected = match range {
@@ -55,6 +57,216 @@ async fn test_get_object(size: usize, range: Option<Range<u64>>) {
     check_get_result(result, range, expected).await;
 }
 
+#[test_case(None, 200; "whole object")]
+#[test_case(Some(2..6), 206; "range")]
+#[tokio::test]
+async fn test_get_object_response_status(range: Option<Range<u64>>, expected_status: i32) {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_get_object_response_status");
+
+    let key = format!("{prefix}/test");
+    let body = vec![0x42; 10];
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(ByteStream::from(body.clone()))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+
+    let mut result = client
+        .get_object(&bucket, &key, &GetObjectParams::new().range(range))
+        .await
+        .expect("get_object should succeed");
+    let _ = result.next().await.expect("should return a body part").unwrap();
+    let metadata = result
+        .response_metadata()
+        .expect("response metadata should be available");
+    assert_eq!(metadata.response_status, Some(expected_status));
+}
+
+#[test_case(None; "whole object")]
+#[test_case(Some(2..6); "range")]
+#[tokio::test]
+async fn test_get_object_checksum_mode(range: Option<Range<u64>>) {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_get_object_checksum_mode");
+
+    let key = format!("{prefix}/hello");
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(ByteStream::from(Bytes::from_static(b"hello world!")))
+        .checksum_algorithm(aws_sdk_s3::types::ChecksumAlgorithm::Sha256)
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let params = GetObjectParams::new().range(range.clone());
+    let mut result = client
+        .get_object(&bucket, &key, &params)
+        .await
+        .expect("get_object should succeed");
+    let _ = result.next().await.expect("should return a body part").unwrap();
+    assert!(result.checksum().is_none());
+
+    let mut result = client
+        .get_object(&bucket, &key, &params.checksum_mode(true))
+        .await
+        .expect("get_object should succeed");
+    let _ = result.next().await.expect("should return a body part").unwrap();
+    let checksum = result.checksum().and_then(|checksum| checksum.algorithm_and_value());
+    let whole_object_checksum = (
+        ChecksumAlgorithm::Sha256,
+        "dQnlvaDHYtK6x/kNdYtbImP6Acy8VCq1498WO+CObKk=".to_owned(),
+    );
+    if range.is_none() {
+        assert_eq!(checksum, Some(whole_object_checksum));
+    } else if let Some(checksum) = checksum {
+        // S3 may leave the checksum out of ranged responses, but if it's there, it's the checksum
+        // of the whole object rather than the range
+        assert_eq!(checksum, whole_object_checksum);
+    }
+}
+
+#[tokio::test]
+async fn test_get_object_part_number() {
+    const PART_SIZES: [usize; 2] = [5 * 1024 * 1024, 1000];
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_get_object_part_number");
+
+    let key = format!("{prefix}/test");
+    let upload = sdk_client
+        .create_multipart_upload()
+        .bucket(&bucket)
+        .key(&key)
+        .send()
+        .await
+        .unwrap();
+    let upload_id = upload.upload_id().unwrap();
+    let mut completed_parts = Vec::new();
+    for (index, size) in PART_SIZES.iter().enumerate() {
+        let part_number = index as i32 + 1;
+        let output = sdk_client
+            .upload_part()
+            .bucket(&bucket)
+            .key(&key)
+            .upload_id(upload_id)
+            .part_number(part_number)
+            .body(ByteStream::from(vec![part_number as u8; *size]))
+            .send()
+            .await
+            .unwrap();
+        completed_parts.push(
+            CompletedPart::builder()
+                .e_tag(output.e_tag().unwrap())
+                .part_number(part_number)
+                .build(),
+        );
+    }
+    sdk_client
+        .complete_multipart_upload()
+        .bucket(&bucket)
+        .key(&key)
+        .upload_id(upload_id)
+        .multipart_upload(
+            CompletedMultipartUpload::builder()
+                .set_parts(Some(completed_parts))
+                .build(),
+        )
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let mut request = client
+        .get_object(&bucket, &key, &GetObjectParams::new().part_number(Some(2)))
+        .await
+        .expect("get_object should succeed");
+    let mut body = Vec::new();
+    while let Some(part) = request.next().await {
+        let (offset, part) = part.expect("body part should succeed");
+        assert_eq!(offset, body.len() as u64, "offsets should be within the part");
+        body.extend_from_slice(&part);
+    }
+    assert_eq!(body, vec![2u8; PART_SIZES[1]]);
+    assert_eq!(request.parts_count(), Some(2));
+}
+
+#[test_case(10; "small object")]
+#[test_case(30000000; "large object")]
+#[tokio::test]
+async fn test_get_object_decode_content_encoding(size: usize) {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_get_object_decode_content_encoding");
+
+    let key = format!("{prefix}/test");
+    let body: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&body).unwrap();
+    let encoded = encoder.finish().unwrap();
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .content_encoding("gzip")
+        .body(ByteStream::from(encoded.clone()))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+
+    let params = GetObjectParams::new().decode_content_encoding(true);
+    let result = client
+        .get_object(&bucket, &key, &params)
+        .await
+        .expect("get_object should succeed");
+    check_get_result(result, None, &body[..]).await;
+
+    // Without the option, the body is returned as stored
+    let result = client
+        .get_object(&bucket, &key, &GetObjectParams::new())
+        .await
+        .expect("get_object should succeed");
+    check_get_result(result, None, &encoded[..]).await;
+}
+
+#[tokio::test]
+async fn test_get_object_decode_invalid_content_encoding() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_get_object_decode_invalid_content_encoding");
+
+    let key = format!("{prefix}/test");
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .content_encoding("gzip")
+        .body(ByteStream::from(Bytes::from_static(b"this is not gzip")))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+
+    let params = GetObjectParams::new().decode_content_encoding(true);
+    let mut result = client
+        .get_object(&bucket, &key, &params)
+        .await
+        .expect("get_object should succeed");
+    let part = result.next().await.expect("should return a result");
+    assert!(matches!(
+        part,
+        Err(ObjectClientError::ClientError(S3RequestError::ContentDecodingError(_)))
+    ));
+}
+
 #[test_case(1, None; "1-byte object")]
 #[test_case(10, None; "small object")]
 #[test_case(30000000, None; "large object")]
@@ -82,7 +294,7 @@ async fn test_get_object_backpressure(size: usize, range: Option<Range<u64>>) {
     let client: S3CrtClient = get_test_backpressure_client(initial_window_size);
 
     let request = client
-        .get_object(&bucket, &key, range.clone(), None)
+        .get_object(&bucket, &key, &GetObjectParams::new().range(range.clone()))
         .await
         .expect("get_object should succeed");
     let expected = match range {
@@ -116,7 +328,7 @@ async fn verify_backpressure_get_object() {
         .unwrap();
 
     let mut get_request = client
-        .get_object(&bucket, &key, Some(range.clone()), None)
+        .get_object(&bucket, &key, &GetObjectParams::new().range(Some(range.clone())))
         .await
         .expect("should not fail");
 
@@ -153,7 +365,7 @@ async fn test_get_object_404_key() {
     let client: S3CrtClient = get_test_client();
 
     let mut result = client
-        .get_object(&bucket, &key, None, None)
+        .get_object(&bucket, &key, &GetObjectParams::new())
         .await
         .expect("get_object should succeed");
     let next = StreamExt::next(&mut result).await.expect("stream needs to return Err");
@@ -171,10 +383,10 @@ async fn test_get_object_404_bucket() {
 
     let key = format!("{prefix}/nonexistent_key");
 
-    let client: S3CrtClient = get_test_client();
+    let client: S3CrtClient = get_test_client_without_bucket_name_validation();
 
     let mut result = client
-        .get_object("DOC-EXAMPLE-BUCKET", &key, None, None)
+        .get_object("DOC-EXAMPLE-BUCKET", &key, &GetObjectParams::new())
         .await
         .expect("get_object failed");
     let next = StreamExt::next(&mut result).await.expect("stream needs to return Err");
@@ -184,6 +396,45 @@ async fn test_get_object_404_bucket() {
     ));
 }
 
+#[tokio::test]
+async fn test_get_object_416_range() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_get_object_416_range");
+
+    let key = format!("{prefix}/hello");
+    let body = b"hello world!";
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(ByteStream::from(Bytes::from_static(body)))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+
+    // A range that starts past the end of the object can't be satisfied
+    let mut result = client
+        .get_object(&bucket, &key, &GetObjectParams::new().range(Some(100..200)))
+        .await
+        .expect("get_object should succeed");
+    let next = StreamExt::next(&mut result).await.expect("stream needs to return Err");
+    assert!(matches!(
+        next,
+        Err(ObjectClientError::ServiceError(GetObjectError::InvalidRange))
+    ));
+
+    // An empty range is rejected before sending a request
+    let result = client
+        .get_object(&bucket, &key, &GetObjectParams::new().range(Some(5..5)))
+        .await;
+    assert!(matches!(
+        result,
+        Err(ObjectClientError::ServiceError(GetObjectError::InvalidRange))
+    ));
+}
+
 #[tokio::test]
 async fn test_get_object_success_if_match() {
     let sdk_client = get_test_sdk_client().await;
@@ -206,7 +457,7 @@ async fn test_get_object_success_if_match() {
     let etag = Some(ETag::from_str(response.e_tag().expect("E-Tag should be set")).unwrap());
 
     let result = client
-        .get_object(&bucket, &key, None, etag)
+        .get_object(&bucket, &key, &GetObjectParams::new().if_match(etag))
         .await
         .expect("get_object should succeed");
     check_get_result(result, None, &body[..]).await;
@@ -233,7 +484,7 @@ async fn test_get_object_412_if_match() {
     let etag = Some(ETag::from_str("incorrect_etag").unwrap());
 
     let mut result = client
-        .get_object(&bucket, &key, None, etag)
+        .get_object(&bucket, &key, &GetObjectParams::new().if_match(etag))
         .await
         .expect("get_object should succeed");
 
@@ -245,6 +496,72 @@ async fn test_get_object_412_if_match() {
     ));
 }
 
+#[tokio::test]
+async fn test_get_object_conditional_time() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_get_object_conditional_time");
+
+    let key = format!("{prefix}/hello");
+    let body = b"hello world!";
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(ByteStream::from(Bytes::from_static(body)))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let last_modified = client
+        .head_object(&bucket, &key, &HeadObjectParams::new())
+        .await
+        .expect("head_object should succeed")
+        .object
+        .last_modified;
+    let an_hour_ago = last_modified - time::Duration::hours(1);
+
+    let result = client
+        .get_object(
+            &bucket,
+            &key,
+            &GetObjectParams::new()
+                .if_modified_since(Some(an_hour_ago))
+                .if_unmodified_since(Some(last_modified)),
+        )
+        .await
+        .expect("get_object should succeed");
+    check_get_result(result, None, &body[..]).await;
+
+    let mut result = client
+        .get_object(
+            &bucket,
+            &key,
+            &GetObjectParams::new().if_modified_since(Some(last_modified)),
+        )
+        .await
+        .expect("get_object should succeed");
+    let next = StreamExt::next(&mut result).await.expect("stream needs to return Err");
+    assert!(matches!(
+        next,
+        Err(ObjectClientError::ServiceError(GetObjectError::NotModified))
+    ));
+
+    let mut result = client
+        .get_object(
+            &bucket,
+            &key,
+            &GetObjectParams::new().if_unmodified_since(Some(an_hour_ago)),
+        )
+        .await
+        .expect("get_object should succeed");
+    let next = StreamExt::next(&mut result).await.expect("stream needs to return Err");
+    assert!(matches!(
+        next,
+        Err(ObjectClientError::ServiceError(GetObjectError::PreconditionFailed))
+    ));
+}
+
 #[test_case(false; "early")]
 #[test_case(true; "after read")]
 #[tokio::test]
@@ -269,7 +586,7 @@ async fn test_get_object_cancel(read: bool) {
     let client: S3CrtClient = get_test_client();
 
     let mut request = client
-        .get_object(&bucket, &key, None, None)
+        .get_object(&bucket, &key, &GetObjectParams::new())
         .await
         .expect("get_object should succeed");
 
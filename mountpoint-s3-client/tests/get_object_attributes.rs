@@ -468,7 +468,7 @@ async fn test_get_attributes_404_bucket() {
 
     let key = format!("{prefix}/nonexistent_key");
 
-    let client: S3CrtClient = get_test_client();
+    let client: S3CrtClient = get_test_client_without_bucket_name_validation();
     let object_attributes = vec![ObjectAttribute::ETag];
 
     let result = client
@@ -5,10 +5,11 @@ pub mod common;
 use common::*;
 #[cfg(not(feature = "s3express_tests"))]
 use mountpoint_s3_client::config::{EndpointConfig, S3ClientConfig};
+#[cfg(not(feature = "s3express_tests"))]
+use mountpoint_s3_client::error::GetBucketLocationError;
 use mountpoint_s3_client::error::{HeadBucketError, ObjectClientError};
 #[cfg(not(feature = "s3express_tests"))]
 use mountpoint_s3_client::S3CrtClient;
-use mountpoint_s3_client::S3RequestError;
 
 #[tokio::test]
 async fn test_head_bucket_correct_region() {
@@ -30,13 +31,34 @@ async fn test_head_bucket_wrong_region() {
     let result = client.head_bucket(&bucket).await;
 
     match result {
-        Err(ObjectClientError::ClientError(S3RequestError::IncorrectRegion(actual_region))) => {
-            assert_eq!(actual_region, expected_region, "wrong region returned")
+        Err(ObjectClientError::ServiceError(HeadBucketError::WrongRegion { region })) => {
+            assert_eq!(region, expected_region, "wrong region returned")
         }
         _ => panic!("incorrect result {result:?}"),
     }
 }
 
+#[tokio::test]
+async fn test_get_bucket_region() {
+    let client = get_test_client();
+    let (bucket, _) = get_test_bucket_and_prefix("test_get_bucket_region");
+
+    let region = client.get_bucket_region(&bucket).await.expect("HeadBucket failed");
+    assert_eq!(region, Some(get_test_region()));
+}
+
+#[tokio::test]
+#[cfg(not(feature = "s3express_tests"))]
+async fn test_get_bucket_region_wrong_region() {
+    let (bucket, _) = get_test_bucket_and_prefix("test_get_bucket_region_wrong_region");
+    let endpoint_config = EndpointConfig::new(&get_secondary_test_region());
+    let client =
+        S3CrtClient::new(S3ClientConfig::new().endpoint_config(endpoint_config)).expect("could not create test client");
+
+    let region = client.get_bucket_region(&bucket).await.expect("HeadBucket failed");
+    assert_eq!(region, Some(get_test_region()));
+}
+
 #[tokio::test]
 async fn test_head_bucket_forbidden() {
     let client = get_test_client();
@@ -46,13 +68,13 @@ async fn test_head_bucket_forbidden() {
 
     assert!(matches!(
         result,
-        Err(ObjectClientError::ClientError(S3RequestError::Forbidden(_, _)))
+        Err(ObjectClientError::ServiceError(HeadBucketError::AccessDenied))
     ));
 }
 
 #[tokio::test]
 async fn test_head_bucket_not_found() {
-    let client = get_test_client();
+    let client = get_test_client_without_bucket_name_validation();
     // Buckets are case sensitive. This bucket will use path-style access and 404.
     let bucket = "DOC-EXAMPLE-BUCKET";
 
@@ -60,6 +82,36 @@ async fn test_head_bucket_not_found() {
 
     assert!(matches!(
         result,
-        Err(ObjectClientError::ServiceError(HeadBucketError::NoSuchBucket))
+        Err(ObjectClientError::ServiceError(HeadBucketError::NotFound))
     ));
 }
+
+#[tokio::test]
+#[cfg(not(feature = "s3express_tests"))]
+async fn test_get_bucket_location() {
+    let client = get_test_client();
+    let (bucket, _) = get_test_bucket_and_prefix("test_get_bucket_location");
+
+    let region = client
+        .get_bucket_location(&bucket)
+        .await
+        .expect("GetBucketLocation failed");
+    assert_eq!(region, get_test_region());
+}
+
+#[tokio::test]
+#[cfg(not(feature = "s3express_tests"))]
+async fn test_get_bucket_location_not_found() {
+    let client = get_test_client_without_bucket_name_validation();
+    let bucket = "DOC-EXAMPLE-BUCKET";
+
+    let result = client.get_bucket_location(bucket).await;
+
+    assert!(
+        matches!(
+            result,
+            Err(ObjectClientError::ServiceError(GetBucketLocationError::NoSuchBucket))
+        ),
+        "unexpected result {result:?}"
+    );
+}
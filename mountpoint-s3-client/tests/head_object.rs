@@ -10,10 +10,12 @@ use aws_sdk_s3::primitives::ByteStream;
 use aws_sdk_s3::types::{GlacierJobParameters, RestoreRequest, Tier};
 use bytes::Bytes;
 use common::*;
+use futures::StreamExt;
 use mountpoint_s3_client::error::{HeadObjectError, ObjectClientError};
 #[cfg(not(feature = "s3express_tests"))]
 use mountpoint_s3_client::types::RestoreStatus;
-use mountpoint_s3_client::{ObjectClient, S3CrtClient, S3RequestError};
+use mountpoint_s3_client::types::{ChecksumAlgorithm, GetObjectParams, HeadObjectParams};
+use mountpoint_s3_client::{ObjectClient, S3CrtClient};
 #[cfg(not(feature = "s3express_tests"))]
 use test_case::test_case;
 
@@ -34,11 +36,125 @@ async fn test_head_object() {
         .unwrap();
 
     let client: S3CrtClient = get_test_client();
-    let result = client.head_object(&bucket, &key).await.expect("head_object failed");
+    let result = client
+        .head_object(&bucket, &key, &HeadObjectParams::new())
+        .await
+        .expect("head_object failed");
 
     assert_eq!(result.bucket, bucket);
     assert_eq!(result.object.key, key);
     assert_eq!(result.object.size as usize, body.len());
+    assert!(result.user_metadata.is_empty());
+}
+
+#[tokio::test]
+async fn test_head_object_checksum_mode() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_head_object_checksum_mode");
+
+    let key = format!("{prefix}/hello");
+    let body = b"hello world!";
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(ByteStream::from(Bytes::from_static(body)))
+        .checksum_algorithm(aws_sdk_s3::types::ChecksumAlgorithm::Sha256)
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let result = client
+        .head_object(&bucket, &key, &HeadObjectParams::new())
+        .await
+        .expect("head_object failed");
+    assert_eq!(result.checksum.algorithm_and_value(), None);
+
+    let result = client
+        .head_object(&bucket, &key, &HeadObjectParams::new().checksum_mode(true))
+        .await
+        .expect("head_object failed");
+    assert_eq!(
+        result.checksum.algorithm_and_value(),
+        Some((
+            ChecksumAlgorithm::Sha256,
+            "dQnlvaDHYtK6x/kNdYtbImP6Acy8VCq1498WO+CObKk=".to_owned()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_head_object_user_metadata() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_head_object_user_metadata");
+
+    let key = format!("{prefix}/hello");
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .metadata("origin", "camera-7")
+        .metadata("project", "mountpoint")
+        .body(ByteStream::from(Bytes::from_static(b"hello world!")))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let result = client
+        .head_object(&bucket, &key, &HeadObjectParams::new())
+        .await
+        .expect("head_object failed");
+
+    assert_eq!(result.user_metadata.len(), 2);
+    assert_eq!(result.user_metadata.get("origin").map(String::as_str), Some("camera-7"));
+    assert_eq!(
+        result.user_metadata.get("project").map(String::as_str),
+        Some("mountpoint")
+    );
+}
+
+#[tokio::test]
+async fn test_head_object_content_type() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_head_object_content_type");
+
+    let key = format!("{prefix}/hello.json");
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .content_type("application/json")
+        .cache_control("max-age=3600")
+        .content_disposition("attachment; filename=\"hello.json\"")
+        .content_language("en-US")
+        .body(ByteStream::from(Bytes::from_static(b"{}")))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let result = client
+        .head_object(&bucket, &key, &HeadObjectParams::new())
+        .await
+        .expect("head_object failed");
+    assert_eq!(result.content_type.as_deref(), Some("application/json"));
+    assert_eq!(result.content_headers.cache_control.as_deref(), Some("max-age=3600"));
+    assert_eq!(
+        result.content_headers.content_disposition.as_deref(),
+        Some("attachment; filename=\"hello.json\"")
+    );
+    assert_eq!(result.content_headers.content_language.as_deref(), Some("en-US"));
+    assert_eq!(result.content_headers.content_encoding, None);
+
+    let mut request = client
+        .get_object(&bucket, &key, &GetObjectParams::new())
+        .await
+        .expect("get_object failed");
+    let _ = request.next().await.expect("should return a body part").unwrap();
+    assert_eq!(request.content_type().as_deref(), Some("application/json"));
+    assert_eq!(request.content_headers(), result.content_headers);
 }
 
 #[test_case("INTELLIGENT_TIERING")]
@@ -63,7 +179,10 @@ async fn test_head_object_storage_class(storage_class: &str) {
         .unwrap();
 
     let client: S3CrtClient = get_test_client();
-    let result = client.head_object(&bucket, &key).await.expect("head_object failed");
+    let result = client
+        .head_object(&bucket, &key, &HeadObjectParams::new())
+        .await
+        .expect("head_object failed");
 
     assert_eq!(result.bucket, bucket);
     assert_eq!(result.object.key, key);
@@ -80,7 +199,7 @@ async fn test_head_object_404_key() {
 
     let client: S3CrtClient = get_test_client();
 
-    let result = client.head_object(&bucket, &key).await;
+    let result = client.head_object(&bucket, &key, &HeadObjectParams::new()).await;
     assert!(matches!(
         result,
         Err(ObjectClientError::ServiceError(HeadObjectError::NotFound))
@@ -93,9 +212,11 @@ async fn test_head_object_404_bucket() {
 
     let key = format!("{prefix}/nonexistent_key");
 
-    let client: S3CrtClient = get_test_client();
+    let client: S3CrtClient = get_test_client_without_bucket_name_validation();
 
-    let result = client.head_object("DOC-EXAMPLE-BUCKET", &key).await;
+    let result = client
+        .head_object("DOC-EXAMPLE-BUCKET", &key, &HeadObjectParams::new())
+        .await;
     assert!(matches!(
         result,
         Err(ObjectClientError::ServiceError(HeadObjectError::NotFound))
@@ -111,10 +232,10 @@ async fn test_head_object_no_perm() {
 
     let client: S3CrtClient = get_test_client();
 
-    let result = client.head_object(&bucket, &key).await;
+    let result = client.head_object(&bucket, &key, &HeadObjectParams::new()).await;
     assert!(matches!(
         result,
-        Err(ObjectClientError::ClientError(S3RequestError::Forbidden(_, _)))
+        Err(ObjectClientError::ServiceError(HeadObjectError::AccessDenied(metadata))) if metadata.http_code == Some(403)
     ));
 }
 
@@ -138,7 +259,10 @@ async fn test_head_object_restored() {
         .unwrap();
 
     let client: S3CrtClient = get_test_client();
-    let result = client.head_object(&bucket, &key).await.expect("head_object failed");
+    let result = client
+        .head_object(&bucket, &key, &HeadObjectParams::new())
+        .await
+        .expect("head_object failed");
 
     assert_eq!(result.bucket, bucket);
     assert_eq!(result.object.key, key);
@@ -171,7 +295,7 @@ async fn test_head_object_restored() {
     let mut timeouted = true;
     while start.elapsed() < timeout {
         let object = client
-            .head_object(&bucket, &key)
+            .head_object(&bucket, &key, &HeadObjectParams::new())
             .await
             .expect("head_object failed")
             .object;
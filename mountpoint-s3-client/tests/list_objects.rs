@@ -3,6 +3,8 @@
 pub mod common;
 
 use common::*;
+#[cfg(not(feature = "s3express_tests"))]
+use mountpoint_s3_client::config::{EndpointConfig, S3ClientConfig};
 use mountpoint_s3_client::error::{ListObjectsError, ObjectClientError};
 use mountpoint_s3_client::{ObjectClient, S3CrtClient};
 
@@ -15,7 +17,7 @@ async fn test_list_objects() {
     let client: S3CrtClient = get_test_client();
 
     let result = client
-        .list_objects(&bucket, None, "/", 1000, &prefix)
+        .list_objects(&bucket, None, Some("/"), 1000, &prefix)
         .await
         .expect("ListObjects failed");
 
@@ -27,6 +29,59 @@ async fn test_list_objects() {
     assert_eq!(result.common_prefixes[0], format!("{}{}", prefix, "dir/"));
 }
 
+#[tokio::test]
+async fn test_list_object_keys() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_list_object_keys");
+    create_objects_for_test(&sdk_client, &bucket, &prefix, &["hello", "dir/a", "dir/b", "world"]).await;
+
+    let client: S3CrtClient = get_test_client();
+
+    let result = client
+        .list_object_keys(&bucket, None, Some("/"), 2, &prefix)
+        .await
+        .expect("ListObjects failed");
+    assert_eq!(result.keys, vec![format!("{prefix}hello")]);
+    assert_eq!(result.common_prefixes, vec![format!("{prefix}dir/")]);
+
+    let result = client
+        .list_object_keys(
+            &bucket,
+            result.next_continuation_token.as_deref(),
+            Some("/"),
+            2,
+            &prefix,
+        )
+        .await
+        .expect("ListObjects failed");
+    assert_eq!(result.keys, vec![format!("{prefix}world")]);
+    assert!(result.common_prefixes.is_empty());
+    assert!(result.next_continuation_token.is_none());
+}
+
+// S3 Express One Zone doesn't support versioning
+#[cfg(not(feature = "s3express_tests"))]
+#[tokio::test]
+async fn test_list_object_versions() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_list_object_versions");
+    create_objects_for_test(&sdk_client, &bucket, &prefix, &["hello", "dir/a"]).await;
+
+    let client: S3CrtClient = get_test_client();
+
+    let result = client
+        .list_object_versions(&bucket, &prefix, Some("/"), None, None)
+        .await
+        .expect("ListObjectVersions failed");
+    assert_eq!(result.versions.len(), 1);
+    let version = &result.versions[0];
+    assert_eq!(version.key(), format!("{prefix}hello"));
+    assert!(version.is_latest);
+    assert!(!version.is_delete_marker());
+    assert_eq!(result.common_prefixes, vec![format!("{prefix}dir/")]);
+    assert!(result.next_key_marker.is_none());
+}
+
 #[tokio::test]
 async fn test_max_keys_continuation_token() {
     // Max keys to get per request
@@ -55,7 +110,7 @@ async fn test_max_keys_continuation_token() {
             .list_objects(
                 &bucket,
                 continuation_token.as_deref(),
-                "/",
+                Some("/"),
                 MAX_KEYS_PER_REQUEST,
                 &prefix,
             )
@@ -97,7 +152,7 @@ async fn test_invalid_list_objects() {
     // Make a ListObjects request using some made-up continuation token.
     let continuation_token = Some("Made-up invalid token here");
     let result = client
-        .list_objects(&bucket, continuation_token, "/", 1000, &prefix)
+        .list_objects(&bucket, continuation_token, Some("/"), 1000, &prefix)
         .await;
 
     let err = result.expect_err("this request should have failed: we made up an invalid continuation token");
@@ -108,10 +163,10 @@ async fn test_invalid_list_objects() {
 async fn test_list_objects_404_bucket() {
     let (_bucket, prefix) = get_test_bucket_and_prefix("test_list_objects_404_bucket");
 
-    let client: S3CrtClient = get_test_client();
+    let client: S3CrtClient = get_test_client_without_bucket_name_validation();
 
     let result = client
-        .list_objects("DOC-EXAMPLE-BUCKET", None, "/", 1000, &prefix)
+        .list_objects("DOC-EXAMPLE-BUCKET", None, Some("/"), 1000, &prefix)
         .await;
     assert!(matches!(
         result,
@@ -119,6 +174,23 @@ async fn test_list_objects_404_bucket() {
     ));
 }
 
+#[cfg(not(feature = "s3express_tests"))]
+#[tokio::test]
+async fn test_list_objects_wrong_region() {
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_list_objects_wrong_region");
+    let endpoint_config = EndpointConfig::new(&get_secondary_test_region());
+    let client =
+        S3CrtClient::new(S3ClientConfig::new().endpoint_config(endpoint_config)).expect("could not create test client");
+
+    let result = client.list_objects(&bucket, None, Some("/"), 1000, &prefix).await;
+    match result {
+        Err(ObjectClientError::ServiceError(ListObjectsError::WrongRegion { expected })) => {
+            assert_eq!(expected, get_test_region(), "wrong region returned")
+        }
+        _ => panic!("incorrect result {result:?}"),
+    }
+}
+
 // Test list with keys and arguments that poke at URL encoding
 // For S3 Express One Zone, ListObjectsV2 API results are not lexicographically sorted and this test will be failing.
 #[cfg(not(feature = "s3express_tests"))]
@@ -137,14 +209,14 @@ async fn test_interesting_keys() {
     let client: S3CrtClient = get_test_client();
 
     let result = client
-        .list_objects(&bucket, None, "/", 2, &prefix)
+        .list_objects(&bucket, None, Some("/"), 2, &prefix)
         .await
         .expect("ListObjects failed");
     assert_eq!(result.common_prefixes[0], format!("{prefix}{}/", keys[0]));
     assert_eq!(result.objects[0].key, format!("{prefix}{}", keys[0]));
 
     let result = client
-        .list_objects(&bucket, None, "/", 1, &format!("{prefix}{}/", keys[0]))
+        .list_objects(&bucket, None, Some("/"), 1, &format!("{prefix}{}/", keys[0]))
         .await
         .expect("ListObjects failed");
     assert_eq!(result.objects.len(), 1);
@@ -155,7 +227,7 @@ async fn test_interesting_keys() {
         .list_objects(
             &bucket,
             result.next_continuation_token.as_deref(),
-            "/",
+            Some("/"),
             1000,
             &format!("{prefix}{}/", keys[0]),
         )
@@ -0,0 +1,146 @@
+#![cfg(feature = "s3_tests")]
+
+pub mod common;
+
+use common::*;
+use mountpoint_s3_client::error::{MultipartUploadError, ObjectClientError};
+use mountpoint_s3_client::types::CompletedPart;
+use mountpoint_s3_client::S3CrtClient;
+
+#[tokio::test]
+async fn test_multipart_upload() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_multipart_upload");
+    let key = format!("{prefix}/hello");
+
+    let client: S3CrtClient = get_test_client();
+    let upload = client
+        .create_multipart_upload(&bucket, &key)
+        .await
+        .expect("create_multipart_upload should succeed");
+
+    // Every part but the last must be at least 5 MiB
+    let first_part = vec![b'a'; 5 * 1024 * 1024];
+    let last_part = b"hello world".to_vec();
+    let mut parts = Vec::new();
+    for (i, body) in [first_part.clone(), last_part.clone()].into_iter().enumerate() {
+        let part_number = i as u32 + 1;
+        let result = client
+            .upload_part(&bucket, &key, &upload.upload_id, part_number, body)
+            .await
+            .expect("upload_part should succeed");
+        parts.push(CompletedPart::new(part_number, result.etag));
+    }
+
+    let result = client
+        .complete_multipart_upload(&bucket, &key, &upload.upload_id, &parts)
+        .await
+        .expect("complete_multipart_upload should succeed");
+
+    let object = sdk_client
+        .get_object()
+        .bucket(&bucket)
+        .key(&key)
+        .send()
+        .await
+        .expect("object should exist");
+    assert_eq!(object.e_tag(), Some(result.etag.as_str()));
+    let body = object.body.collect().await.unwrap().into_bytes();
+    assert_eq!(&body[..first_part.len()], &first_part[..]);
+    assert_eq!(&body[first_part.len()..], &last_part[..]);
+}
+
+#[tokio::test]
+async fn test_abort_multipart_upload() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_abort_multipart_upload");
+    let key = format!("{prefix}/hello");
+
+    let client: S3CrtClient = get_test_client();
+    let upload = client
+        .create_multipart_upload(&bucket, &key)
+        .await
+        .expect("create_multipart_upload should succeed");
+    client
+        .upload_part(&bucket, &key, &upload.upload_id, 1, b"hello world".to_vec())
+        .await
+        .expect("upload_part should succeed");
+    assert_eq!(
+        get_mpu_count_for_key(&sdk_client, &bucket, &prefix, &key)
+            .await
+            .unwrap(),
+        1
+    );
+
+    client
+        .abort_multipart_upload(&bucket, &key, &upload.upload_id)
+        .await
+        .expect("abort_multipart_upload should succeed");
+    assert_eq!(
+        get_mpu_count_for_key(&sdk_client, &bucket, &prefix, &key)
+            .await
+            .unwrap(),
+        0
+    );
+
+    let result = client
+        .upload_part(&bucket, &key, &upload.upload_id, 2, b"hello world".to_vec())
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(ObjectClientError::ServiceError(MultipartUploadError::NoSuchUpload))
+        ),
+        "unexpected result {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_list_parts() {
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_list_parts");
+    let key = format!("{prefix}/hello");
+
+    let client: S3CrtClient = get_test_client();
+    let upload = client
+        .create_multipart_upload(&bucket, &key)
+        .await
+        .expect("create_multipart_upload should succeed");
+    let mut etags = Vec::new();
+    for part_number in 1..=2 {
+        let result = client
+            .upload_part(
+                &bucket,
+                &key,
+                &upload.upload_id,
+                part_number,
+                vec![b'a'; part_number as usize],
+            )
+            .await
+            .expect("upload_part should succeed");
+        etags.push(result.etag);
+    }
+
+    let result = client
+        .list_parts(&bucket, &key, &upload.upload_id, None)
+        .await
+        .expect("list_parts should succeed");
+    let parts: Vec<_> = result
+        .parts
+        .iter()
+        .map(|p| (p.part_number, p.etag.clone(), p.size))
+        .collect();
+    assert_eq!(parts, vec![(1, etags[0].clone(), 1), (2, etags[1].clone(), 2)]);
+    assert_eq!(result.next_part_number_marker, None);
+
+    let result = client
+        .list_parts(&bucket, &key, &upload.upload_id, Some(1))
+        .await
+        .expect("list_parts should succeed");
+    assert_eq!(result.parts.len(), 1);
+    assert_eq!(result.parts[0].part_number, 2);
+
+    client
+        .abort_multipart_upload(&bucket, &key, &upload.upload_id)
+        .await
+        .expect("abort_multipart_upload should succeed");
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "s3_tests")]
+
+pub mod common;
+
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use common::*;
+use mountpoint_s3_client::config::{EndpointConfig, S3ClientConfig};
+use mountpoint_s3_client::types::ObjectExistence;
+use mountpoint_s3_client::{ObjectClient, S3CrtClient};
+use test_case::test_case;
+
+fn get_test_client_with_existence_check(ranged_get_existence_check: bool) -> S3CrtClient {
+    let config = S3ClientConfig::new()
+        .endpoint_config(EndpointConfig::new(&get_test_region()))
+        .ranged_get_existence_check(ranged_get_existence_check);
+    S3CrtClient::new(config).expect("could not create test client")
+}
+
+#[test_case(false, b"hello world"; "head")]
+#[test_case(true, b"hello world"; "ranged get")]
+#[test_case(false, b""; "head empty object")]
+#[test_case(true, b""; "ranged get empty object")]
+#[tokio::test]
+async fn test_object_exists(ranged_get_existence_check: bool, body: &'static [u8]) {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_object_exists");
+
+    let key = format!("{prefix}/hello");
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(ByteStream::from(Bytes::from_static(body)))
+        .send()
+        .await
+        .unwrap();
+
+    let client = get_test_client_with_existence_check(ranged_get_existence_check);
+    let exists = client
+        .object_exists(&bucket, &key)
+        .await
+        .expect("object_exists should succeed");
+    assert_eq!(exists, ObjectExistence::Exists);
+
+    let missing_key = format!("{prefix}/nonexistent_key");
+    let exists = client
+        .object_exists(&bucket, &missing_key)
+        .await
+        .expect("object_exists should succeed");
+    assert_eq!(exists, ObjectExistence::NotFound);
+}
+
+#[test_case(false; "head")]
+#[test_case(true; "ranged get")]
+#[tokio::test]
+async fn test_object_exists_no_perm(ranged_get_existence_check: bool) {
+    let (_bucket, prefix) = get_test_bucket_and_prefix("test_object_exists_no_perm");
+    let bucket = get_test_bucket_without_permissions();
+
+    let key = format!("{prefix}/some_key");
+
+    let client = get_test_client_with_existence_check(ranged_get_existence_check);
+    // The object doesn't exist, but without permission to list the bucket we can't tell
+    let result = client
+        .object_exists(&bucket, &key)
+        .await
+        .expect("object_exists should succeed");
+    assert_eq!(result, ObjectExistence::AccessDenied);
+}
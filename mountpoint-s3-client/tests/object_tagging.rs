@@ -0,0 +1,72 @@
+#![cfg(feature = "s3_tests")]
+// S3 Express One Zone doesn't support object tagging
+#![cfg(not(feature = "s3express_tests"))]
+
+pub mod common;
+
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use common::*;
+use mountpoint_s3_client::error::{GetObjectTaggingError, ObjectClientError, PutObjectTaggingError};
+use mountpoint_s3_client::{ObjectClient, S3CrtClient};
+
+#[tokio::test]
+async fn test_object_tagging() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_object_tagging");
+
+    let key = format!("{prefix}/hello");
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .tagging("project=mountpoint")
+        .body(ByteStream::from(Bytes::from_static(b"hello world")))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let tags = client
+        .get_object_tagging(&bucket, &key)
+        .await
+        .expect("get_object_tagging should succeed");
+    assert_eq!(tags, vec![("project".to_owned(), "mountpoint".to_owned())]);
+
+    let new_tags = vec![
+        ("team".to_owned(), "storage".to_owned()),
+        ("empty".to_owned(), "".to_owned()),
+    ];
+    client
+        .put_object_tagging(&bucket, &key, &new_tags)
+        .await
+        .expect("put_object_tagging should succeed");
+    let mut tags = client
+        .get_object_tagging(&bucket, &key)
+        .await
+        .expect("get_object_tagging should succeed");
+    tags.sort();
+    let mut expected_tags = new_tags;
+    expected_tags.sort();
+    assert_eq!(tags, expected_tags);
+}
+
+#[tokio::test]
+async fn test_object_tagging_no_such_key() {
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_object_tagging_no_such_key");
+    let key = format!("{prefix}/nonexistent_key");
+
+    let client: S3CrtClient = get_test_client();
+    let result = client.get_object_tagging(&bucket, &key).await;
+    assert!(matches!(
+        result,
+        Err(ObjectClientError::ServiceError(GetObjectTaggingError::NoSuchKey))
+    ));
+
+    let tags = vec![("project".to_owned(), "mountpoint".to_owned())];
+    let result = client.put_object_tagging(&bucket, &key, &tags).await;
+    assert!(matches!(
+        result,
+        Err(ObjectClientError::ServiceError(PutObjectTaggingError::NoSuchKey))
+    ));
+}
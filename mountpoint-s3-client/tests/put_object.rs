@@ -10,7 +10,8 @@ use mountpoint_s3_client::checksums::crc32c_to_base64;
 use mountpoint_s3_client::config::{EndpointConfig, S3ClientConfig};
 use mountpoint_s3_client::error::{GetObjectError, ObjectClientError};
 use mountpoint_s3_client::types::{
-    ChecksumAlgorithm, ObjectClientResult, PutObjectParams, PutObjectResult, PutObjectTrailingChecksums,
+    ChecksumAlgorithm, GetObjectParams, HeadObjectParams, ObjectClientResult, PutObjectParams, PutObjectResult,
+    PutObjectTrailingChecksums,
 };
 use mountpoint_s3_client::{ObjectClient, PutObjectRequest, S3CrtClient, S3RequestError};
 use mountpoint_s3_crt::checksums::crc32c;
@@ -39,7 +40,7 @@ async fn test_put_object(
     let put_object_result = request.complete().await.unwrap();
 
     let result = client
-        .get_object(bucket, key, None, None)
+        .get_object(bucket, key, &GetObjectParams::new())
         .await
         .expect("get_object should succeed");
     check_get_result(result, None, &contents[..]).await;
@@ -65,7 +66,7 @@ async fn test_put_object_empty(
     let put_object_result = request.complete().await.unwrap();
 
     let result = client
-        .get_object(bucket, key, None, None)
+        .get_object(bucket, key, &GetObjectParams::new())
         .await
         .expect("get_object should succeed");
     check_get_result(result, None, &[]).await;
@@ -75,6 +76,39 @@ async fn test_put_object_empty(
 
 object_client_test!(test_put_object_empty);
 
+#[test_case(None; "default checksum")]
+#[test_case(Some(ChecksumAlgorithm::Crc32); "crc32")]
+#[test_case(Some(ChecksumAlgorithm::Sha256); "sha256")]
+#[test_case(Some(ChecksumAlgorithm::Crc64nvme); "crc64nvme")]
+#[tokio::test]
+async fn test_put_empty_object(checksum_algorithm: Option<ChecksumAlgorithm>) {
+    if checksum_algorithm.is_some_and(|algorithm| !algorithm.is_supported()) {
+        return;
+    }
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_put_empty_object");
+    let client = get_test_client();
+    let key = format!("{prefix}dir/");
+
+    let params = PutObjectParams::new().checksum_algorithm(checksum_algorithm);
+    let result = client
+        .put_empty_object(&bucket, &key, &params)
+        .await
+        .expect("put_empty_object should succeed");
+    // Objects from a multipart upload have an ETag ending in the number of parts
+    let etag = result.etag.expect("put should return an ETag");
+    assert!(!etag.as_str().contains('-'), "got {etag:?}");
+    if let Some(algorithm) = checksum_algorithm {
+        let (returned_algorithm, _) = result.checksum.expect("put should return a checksum");
+        assert_eq!(returned_algorithm, algorithm);
+    }
+
+    let result = client
+        .get_object(&bucket, &key, &GetObjectParams::new())
+        .await
+        .expect("get_object should succeed");
+    check_get_result(result, None, &[]).await;
+}
+
 // Test for multi-part PUT interface. Splits up a small object into a number of pieces, and streams
 // the pieces to the object client. Checks contents are correct using a GET.
 async fn test_put_object_multi_part(
@@ -100,7 +134,7 @@ async fn test_put_object_multi_part(
     let put_object_result = request.complete().await.unwrap();
 
     let result = client
-        .get_object(bucket, key, None, None)
+        .get_object(bucket, key, &GetObjectParams::new())
         .await
         .expect("get_object failed");
     check_get_result(result, None, &contents[..]).await;
@@ -137,7 +171,7 @@ async fn test_put_object_large(
     let put_object_result = request.complete().await.unwrap();
 
     let result = client
-        .get_object(bucket, key, None, None)
+        .get_object(bucket, key, &GetObjectParams::new())
         .await
         .expect("get_object failed");
     check_get_result(result, None, &contents[..]).await;
@@ -328,7 +362,7 @@ async fn test_put_checksums(trailing_checksums: PutObjectTrailingChecksums) {
         .expect("put_object should succeed");
 
     request.write(&contents).await.unwrap();
-    request
+    let result = request
         .review_and_complete(move |review| {
             let parts = review.parts;
             if trailing_checksums == PutObjectTrailingChecksums::Disabled {
@@ -342,6 +376,13 @@ async fn test_put_checksums(trailing_checksums: PutObjectTrailingChecksums) {
         .await
         .unwrap();
 
+    let checksum_algorithm = result.checksum.map(|(algorithm, _)| algorithm);
+    if trailing_checksums == PutObjectTrailingChecksums::Enabled {
+        assert_eq!(checksum_algorithm, Some(ChecksumAlgorithm::Crc32c));
+    } else {
+        assert_eq!(checksum_algorithm, None);
+    }
+
     let sdk_client = get_test_sdk_client().await;
     let attributes = sdk_client
         .get_object_attributes()
@@ -375,6 +416,46 @@ async fn test_put_checksums(trailing_checksums: PutObjectTrailingChecksums) {
     }
 }
 
+#[tokio::test]
+async fn test_put_checksum_crc64nvme() {
+    if !ChecksumAlgorithm::Crc64nvme.is_supported() {
+        return;
+    }
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_put_checksum_crc64nvme");
+    let client = get_test_client();
+    let key = format!("{prefix}hello");
+
+    let mut rng = rand::thread_rng();
+    let mut contents = vec![0u8; 32];
+    rng.fill(&mut contents[..]);
+
+    let params = PutObjectParams::new().checksum_algorithm(Some(ChecksumAlgorithm::Crc64nvme));
+    let mut request = client
+        .put_object(&bucket, &key, &params)
+        .await
+        .expect("put_object should succeed");
+
+    request.write(&contents).await.unwrap();
+    let result = request
+        .review_and_complete(|review| {
+            assert_eq!(review.checksum_algorithm, Some(ChecksumAlgorithm::Crc64nvme));
+            true
+        })
+        .await
+        .unwrap();
+
+    // S3 echoes the checksum it validated in the x-amz-checksum-crc64nvme header
+    let (algorithm, checksum) = result.checksum.expect("put should return a checksum");
+    assert_eq!(algorithm, ChecksumAlgorithm::Crc64nvme);
+    assert!(!checksum.is_empty());
+
+    let get_result = client
+        .get_object(&bucket, &key, &GetObjectParams::new())
+        .await
+        .expect("get_object should succeed");
+    check_get_result(get_result, None, &contents[..]).await;
+}
+
 #[test_case(true; "pass review")]
 #[test_case(false; "fail review")]
 #[tokio::test]
@@ -438,7 +519,7 @@ async fn check_get_object<Client: ObjectClient>(
     bucket: &str,
     key: &str,
 ) -> ObjectClientResult<(), GetObjectError, Client::ClientError> {
-    let result = client.get_object(bucket, key, None, None).await?;
+    let result = client.get_object(bucket, key, &GetObjectParams::new()).await?;
     pin_mut!(result);
     result.next().await.unwrap()?;
     Ok(())
@@ -613,7 +694,7 @@ async fn test_concurrent_put_objects(throughput_target_gbps: f64, max_concurrent
         // Also try to issue an unrelated request (head_object).
         tokio::time::timeout(TIMEOUT, async {
             client
-                .head_object(&bucket, &not_existing_key)
+                .head_object(&bucket, &not_existing_key, &HeadObjectParams::new())
                 .await
                 .expect_err("head object should fail")
         })
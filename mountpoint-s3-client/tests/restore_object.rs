@@ -0,0 +1,82 @@
+#![cfg(feature = "s3_tests")]
+// S3 Express One Zone doesn't support archival storage classes
+#![cfg(not(feature = "s3express_tests"))]
+
+pub mod common;
+
+use aws_sdk_s3::primitives::ByteStream;
+use bytes::Bytes;
+use common::*;
+use mountpoint_s3_client::error::{ObjectClientError, RestoreObjectError};
+use mountpoint_s3_client::types::{HeadObjectParams, RestoreObjectResult, RestoreStatus, RestoreTier};
+use mountpoint_s3_client::{ObjectClient, S3CrtClient};
+
+#[tokio::test]
+async fn test_restore_object() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_restore_object");
+
+    let key = format!("{prefix}/hello");
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .storage_class("GLACIER".into())
+        .body(ByteStream::from(Bytes::from_static(b"hello world")))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    // Bulk restores take hours, so the restore will still be in progress below
+    let result = client
+        .restore_object(&bucket, &key, 1, RestoreTier::Bulk)
+        .await
+        .expect("restore_object should succeed");
+    assert_eq!(result, RestoreObjectResult::RestoreStarted);
+
+    let head = client
+        .head_object(&bucket, &key, &HeadObjectParams::new())
+        .await
+        .expect("head_object failed");
+    assert!(matches!(head.object.restore_status, Some(RestoreStatus::InProgress)));
+
+    let result = client.restore_object(&bucket, &key, 1, RestoreTier::Bulk).await;
+    assert!(matches!(
+        result,
+        Err(ObjectClientError::ServiceError(
+            RestoreObjectError::RestoreAlreadyInProgress
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn test_restore_object_not_archived() {
+    let sdk_client = get_test_sdk_client().await;
+    let (bucket, prefix) = get_test_bucket_and_prefix("test_restore_object_not_archived");
+
+    let key = format!("{prefix}/hello");
+    sdk_client
+        .put_object()
+        .bucket(&bucket)
+        .key(&key)
+        .body(ByteStream::from(Bytes::from_static(b"hello world")))
+        .send()
+        .await
+        .unwrap();
+
+    let client: S3CrtClient = get_test_client();
+    let result = client.restore_object(&bucket, &key, 1, RestoreTier::Standard).await;
+    assert!(matches!(
+        result,
+        Err(ObjectClientError::ServiceError(RestoreObjectError::ObjectNotArchived))
+    ));
+
+    let result = client
+        .restore_object(&bucket, &format!("{prefix}/missing"), 1, RestoreTier::Standard)
+        .await;
+    assert!(matches!(
+        result,
+        Err(ObjectClientError::ServiceError(RestoreObjectError::NoSuchKey))
+    ));
+}
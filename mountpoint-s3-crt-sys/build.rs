@@ -29,6 +29,8 @@ const CRT_LIBRARIES: &[&str] = &[
 const CRT_HEADERS: &[&str] = &[
     "auth/credentials.h",
     "auth/aws_imds_client.h",
+    "auth/signable.h",
+    "auth/signing.h",
     "checksums/crc.h",
     "common/atomics.h",
     "common/log_channel.h",
@@ -38,6 +40,8 @@ const CRT_HEADERS: &[&str] = &[
     "common/error.h",
     "http/http.h",
     "http/connection.h",
+    "http/connection_manager.h",
+    "http/proxy.h",
     "http/request_response.h",
     "io/async_stream.h",
     "io/channel_bootstrap.h",
@@ -45,6 +49,7 @@ const CRT_HEADERS: &[&str] = &[
     "io/future.h",
     "io/host_resolver.h",
     "io/stream.h",
+    "io/tls_channel_handler.h",
     "io/uri.h",
     "s3/s3.h",
     "s3/s3_client.h",
@@ -61,6 +66,10 @@ const PRIVATE_CRT_HEADERS: &[&str] = &[
     "aws-c-s3/include/aws/s3/private/s3_buffer_pool.h",
 ];
 
+/// Private CRT headers only required with the `debug-signing` feature, to build the intermediate
+/// signing values for debugging. Paths are relative to [CRT_PATH].
+const DEBUG_SIGNING_PRIVATE_CRT_HEADERS: &[&str] = &["aws-c-auth/include/aws/auth/private/aws_signing.h"];
+
 /// Get the OS name we are compiling to
 fn target_os() -> String {
     env::var("CARGO_CFG_TARGET_OS").expect("target OS not defined")
@@ -176,7 +185,11 @@ fn generate_bindings(include_dir: &Path, output_path: &Path) -> Result<(), Bindg
         builder = builder.header(header_path.to_str().unwrap());
     }
 
-    for header in PRIVATE_CRT_HEADERS {
+    let mut private_headers = PRIVATE_CRT_HEADERS.to_vec();
+    if env::var_os("CARGO_FEATURE_DEBUG_SIGNING").is_some() {
+        private_headers.extend_from_slice(DEBUG_SIGNING_PRIVATE_CRT_HEADERS);
+    }
+    for header in private_headers {
         let header_path = Path::new(CRT_PATH).join(header);
         if !header_path.exists() {
             panic!(
@@ -300,6 +313,53 @@ fn compile_logging_shim(crt_include_dir: impl AsRef<Path>) {
     println!("cargo:rerun-if-changed=src/logging_shim.c");
 }
 
+/// Generate `crt_versions.rs`, containing the version of each CRT library we build, read from the
+/// `VERSION` file in the library's source directory, which must exist. The versions are unknown if
+/// we don't build the libraries ourselves.
+fn generate_versions(output_path: &Path, built_from_source: bool) {
+    let mut contents = format!(
+        "/// Whether the CRT libraries were built from the source embedded in this crate, rather than\n\
+         /// linked from `MOUNTPOINT_CRT_LIB_DIR`.\n\
+         pub const CRT_BUILT_FROM_SOURCE: bool = {built_from_source};\n\
+         \n\
+         /// The versions of the CRT libraries this crate links to, by library name. A version is `None`\n\
+         /// if it's unknown, such as when linking to prebuilt libraries with `MOUNTPOINT_CRT_LIB_DIR`.\n\
+         pub const CRT_LIBRARY_VERSIONS: &[(&str, Option<&str>)] = &[\n",
+    );
+    for lib in CRT_LIBRARIES {
+        let version = built_from_source.then(|| {
+            let version_path = Path::new(CRT_PATH).join(lib).join("VERSION");
+            let version = fs::read_to_string(&version_path).unwrap_or_else(|e| {
+                panic!(
+                    "failed to read {}: {e}; perhaps you need to fetch git submodules",
+                    version_path.display()
+                )
+            });
+            version.trim().to_owned()
+        });
+        contents.push_str(&format!("    ({lib:?}, {version:?}),\n"));
+    }
+    contents.push_str("];\n");
+    fs::write(output_path, contents).expect("failed to write CRT versions");
+}
+
+/// Tell crates that depend on this one which optional fields of the CRT's structs the headers in
+/// `include_dir` have, so they can use them only when they're there. Crates with a build script
+/// see each one as a `DEP_AWS_C_S3_<NAME>` environment variable.
+fn detect_crt_features(include_dir: &Path) {
+    let s3_client_header = include_dir.join("aws").join("s3").join("s3_client.h");
+    let s3_client_header = fs::read_to_string(&s3_client_header)
+        .unwrap_or_else(|e| panic!("failed to read {}: {e}", s3_client_header.display()));
+    // Added to `aws_s3_client_config` in later versions of aws-c-s3
+    if s3_client_header.contains("network_interface_names_array") {
+        println!("cargo:network_interface_names=1");
+    }
+    // Added to `aws_s3_checksum_algorithm` in later versions of aws-c-s3
+    if s3_client_header.contains("AWS_SCA_CRC64NVME") {
+        println!("cargo:crc64nvme=1");
+    }
+}
+
 /// Build or link to the CRT.
 ///
 /// By default, we build and statically link the CRT libraries embedded in this crate as Git
@@ -320,7 +380,9 @@ fn main() {
     let output_dir = PathBuf::from(env::var("OUT_DIR").expect("no OUT_DIR set"));
 
     // Compile or link the CRT libraries
-    let include_dir = if let Some(path) = get_env("MOUNTPOINT_CRT_LIB_DIR") {
+    let prebuilt_lib_dir = get_env("MOUNTPOINT_CRT_LIB_DIR");
+    generate_versions(&output_dir.join("crt_versions.rs"), prebuilt_lib_dir.is_none());
+    let include_dir = if let Some(path) = prebuilt_lib_dir {
         println!("cargo:rustc-link-search=native={path}");
 
         let link_type = match get_env("MOUNTPOINT_CRT_LIB_LINK_STATIC") {
@@ -345,6 +407,7 @@ fn main() {
     // Generate Rust bindings from the CRT headers
     let bindings_path = output_dir.join("bindings.rs");
     generate_bindings(&include_dir, &bindings_path).expect("failed to generate bindings");
+    detect_crt_features(&include_dir);
 
     compile_logging_shim(include_dir);
 }
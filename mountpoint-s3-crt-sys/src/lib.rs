@@ -13,6 +13,8 @@ mod generated {
 
 pub use generated::*;
 
+include!(concat!(env!("OUT_DIR"), "/crt_versions.rs"));
+
 pub mod logging_shim;
 
 #[cfg(test)]
@@ -0,0 +1,14 @@
+//! Enable the parts of this crate that need optional CRT features, if the CRT that
+//! mountpoint-s3-crt-sys builds or links to has them.
+
+fn main() {
+    for (feature, cfg) in [
+        ("NETWORK_INTERFACE_NAMES", "crt_network_interface_names"),
+        ("CRC64NVME", "crt_crc64nvme"),
+    ] {
+        println!("cargo:rustc-check-cfg=cfg({cfg})");
+        if std::env::var_os(format!("DEP_AWS_C_S3_{feature}")).is_some() {
+            println!("cargo:rustc-cfg={cfg}");
+        }
+    }
+}
@@ -97,6 +97,7 @@ impl CrtClient {
         let bootstrap_options = ClientBootstrapOptions {
             event_loop_group: &mut event_loop_group,
             host_resolver: &mut host_resolver,
+            host_resolution_mode: Default::default(),
         };
         let mut client_bootstrap = ClientBootstrap::new(&allocator, &bootstrap_options).unwrap();
 
@@ -8,7 +8,10 @@ use crate::common::allocator::Allocator;
 
 pub mod credentials;
 pub mod imds_client;
+pub mod presign;
 pub mod signing_config;
+#[cfg(feature = "debug-signing")]
+pub mod signing_debug;
 
 static AUTH_LIBRARY_INIT: Once = Once::new();
 
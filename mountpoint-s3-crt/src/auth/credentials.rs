@@ -1,21 +1,32 @@
 //! AWS credentials providers
 
 use std::fmt::Debug;
+use std::future::Future;
 use std::ptr::NonNull;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use mountpoint_s3_crt_sys::{
-    aws_credentials_provider, aws_credentials_provider_acquire, aws_credentials_provider_chain_default_options,
-    aws_credentials_provider_new_anonymous, aws_credentials_provider_new_chain_default,
+    aws_allocator, aws_auth_errors, aws_credentials, aws_credentials_get_expiration_timepoint_seconds,
+    aws_credentials_new, aws_credentials_provider, aws_credentials_provider_acquire,
+    aws_credentials_provider_chain_default_options, aws_credentials_provider_delegate_options,
+    aws_credentials_provider_get_credentials, aws_credentials_provider_new_anonymous,
+    aws_credentials_provider_new_chain_default, aws_credentials_provider_new_delegate,
     aws_credentials_provider_new_profile, aws_credentials_provider_new_static,
     aws_credentials_provider_profile_options, aws_credentials_provider_release,
-    aws_credentials_provider_static_options,
+    aws_credentials_provider_shutdown_options, aws_credentials_provider_static_options, aws_credentials_release,
+    aws_on_get_credentials_callback_fn,
 };
 
 use crate::auth::auth_library_init;
 use crate::common::allocator::Allocator;
+use crate::common::byte_cursor::ByteCursor;
 use crate::common::error::Error;
+use crate::common::redact::REDACTED;
 use crate::io::channel_bootstrap::ClientBootstrap;
-use crate::{CrtError as _, ToAwsByteCursor as _};
+use crate::io::event_loop::EventLoopGroup;
+use crate::io::futures::FutureSpawner as _;
+use crate::{CrtError as _, ResultExt as _, ToAwsByteCursor as _};
 
 /// Options for creating a default credentials provider
 #[derive(Debug)]
@@ -46,13 +57,289 @@ pub struct CredentialsProviderStaticOptions<'a> {
 impl Debug for CredentialsProviderStaticOptions<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("CredentialsProviderStaticOptions")
-            .field("access_key_id", &"** redacted **")
-            .field("secret_access_key", &"** redacted **")
-            .field("session_token", &self.session_token.map(|_| "** redacted **"))
+            .field("access_key_id", &REDACTED)
+            .field("secret_access_key", &REDACTED)
+            .field("session_token", &self.session_token.map(|_| REDACTED))
             .finish()
     }
 }
 
+/// AWS credentials returned by a [ProvideCredentials] implementation
+#[derive(Clone)]
+pub struct Credentials {
+    /// AWS access key ID
+    pub access_key_id: String,
+    /// AWS secret access key
+    pub secret_access_key: String,
+    /// AWS session token (only required for some credentials sources, e.g. STS)
+    pub session_token: Option<String>,
+    /// When the credentials expire, or `None` if they don't. The CRT fetches new credentials
+    /// shortly before expiry.
+    pub expiration: Option<SystemTime>,
+}
+
+impl Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &REDACTED)
+            .field("secret_access_key", &REDACTED)
+            .field("session_token", &self.session_token.as_ref().map(|_| REDACTED))
+            .field("expiration", &self.expiration)
+            .finish()
+    }
+}
+
+/// The error type returned by a [ProvideCredentials] implementation
+pub type ProvideCredentialsError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A source of AWS credentials implemented in Rust, for use with
+/// [CredentialsProvider::new_delegate].
+///
+/// This is named after the equivalent trait in the AWS SDK for Rust, since [CredentialsProvider]
+/// is the CRT's provider object. Implementations can use an `async fn`:
+///
+/// ```
+/// # use mountpoint_s3_crt::auth::credentials::{Credentials, ProvideCredentials, ProvideCredentialsError};
+/// struct VaultCredentials;
+///
+/// impl ProvideCredentials for VaultCredentials {
+///     async fn get_credentials(&self) -> Result<Credentials, ProvideCredentialsError> {
+///         Ok(Credentials {
+///             access_key_id: "AKIDEXAMPLE".to_string(),
+///             secret_access_key: "secret".to_string(),
+///             session_token: None,
+///             expiration: None,
+///         })
+///     }
+/// }
+/// ```
+pub trait ProvideCredentials: Send + Sync + 'static {
+    /// Fetch credentials. The CRT calls this whenever it needs credentials and doesn't have
+    /// unexpired ones cached, and fails the request that needed them if this returns an error.
+    ///
+    /// The future is driven on a thread of the CRT's event loop group, not by a Tokio runtime, so
+    /// it must not call APIs that need one, like `tokio::net` or `tokio::time`, which panic or
+    /// never complete there. It also blocks other work on that event loop while it runs, so spawn
+    /// any slow or blocking work elsewhere (e.g. on a runtime the implementation owns) and await
+    /// its result.
+    fn get_credentials(&self) -> impl Future<Output = Result<Credentials, ProvideCredentialsError>> + Send;
+}
+
+/// Observes the credentials a [CredentialsProvider] fetches, for example to emit metrics or alert
+/// before credentials expire, for use with [CredentialsProvider::new_observed]. Both methods do
+/// nothing by default, and they're called on the CRT thread that fetched the credentials, so they
+/// should return quickly.
+pub trait ObserveCredentials: Send + Sync + 'static {
+    /// Called when the provider returns new credentials, with when they expire, or `None` if they
+    /// don't. Providers return cached credentials until they're about to expire, so this is called
+    /// for the first credentials and then whenever the expiration changes, rather than for every
+    /// fetch.
+    fn on_refresh(&self, _expiration: Option<SystemTime>) {}
+
+    /// Called whenever the provider fails to provide credentials, with the error it failed with.
+    fn on_error(&self, _error: Error) {}
+}
+
+impl<O: ObserveCredentials + ?Sized> ObserveCredentials for Arc<O> {
+    fn on_refresh(&self, expiration: Option<SystemTime>) {
+        (**self).on_refresh(expiration)
+    }
+
+    fn on_error(&self, error: Error) {
+        (**self).on_error(error)
+    }
+}
+
+/// The state of an observed credentials provider, shared with the CRT as its user data and with
+/// each fetch in progress.
+struct ObservedCredentialsProvider<O> {
+    provider: CredentialsProvider,
+    observer: O,
+    /// The expiration of the last credentials the provider returned, as the CRT reports it
+    last_expiration: Mutex<Option<u64>>,
+}
+
+impl<O: ObserveCredentials> ObservedCredentialsProvider<O> {
+    /// Report the result of a fetch to the observer.
+    ///
+    /// SAFETY: `credentials` must be valid credentials if `error_code` is zero.
+    unsafe fn observe(&self, credentials: *mut aws_credentials, error_code: i32) {
+        if error_code != 0 || credentials.is_null() {
+            self.observer.on_error(error_code.into());
+            return;
+        }
+        let expiration = aws_credentials_get_expiration_timepoint_seconds(credentials);
+        let changed = self.last_expiration.lock().unwrap().replace(expiration) != Some(expiration);
+        if changed {
+            // The CRT uses the largest timepoint for credentials that don't expire
+            let expiration = (expiration != u64::MAX).then(|| UNIX_EPOCH + Duration::from_secs(expiration));
+            self.observer.on_refresh(expiration);
+        }
+    }
+}
+
+/// A fetch in progress through an observed provider, passed to the wrapped provider as the user
+/// data of its callback.
+struct ObservedFetch<O> {
+    state: Arc<ObservedCredentialsProvider<O>>,
+    callback: aws_on_get_credentials_callback_fn,
+    callback_user_data: *mut libc::c_void,
+}
+
+/// SAFETY: not safe to call directly, only let the CRT call this function as a callback.
+unsafe extern "C" fn observed_get_credentials<O: ObserveCredentials>(
+    delegate_user_data: *mut libc::c_void,
+    callback: aws_on_get_credentials_callback_fn,
+    callback_user_data: *mut libc::c_void,
+) -> libc::c_int {
+    assert!(!delegate_user_data.is_null());
+    let state = &*(delegate_user_data as *const Arc<ObservedCredentialsProvider<O>>);
+    let fetch = Box::into_raw(Box::new(ObservedFetch {
+        state: state.clone(),
+        callback,
+        callback_user_data,
+    }));
+    let result = aws_credentials_provider_get_credentials(
+        state.provider.inner.as_ptr(),
+        Some(observed_on_get_credentials::<O>),
+        fetch as *mut libc::c_void,
+    );
+    // The callback is only called if the fetch started
+    if result != 0 {
+        drop(Box::from_raw(fetch));
+    }
+    result
+}
+
+/// SAFETY: not safe to call directly, only let the CRT call this function as a callback.
+unsafe extern "C" fn observed_on_get_credentials<O: ObserveCredentials>(
+    credentials: *mut aws_credentials,
+    error_code: i32,
+    user_data: *mut libc::c_void,
+) {
+    assert!(!user_data.is_null());
+    let fetch = Box::from_raw(user_data as *mut ObservedFetch<O>);
+    fetch.state.observe(credentials, error_code);
+    // Pass the wrapped provider's result on unchanged; the callback takes its own reference to the
+    // credentials if it keeps them
+    if let Some(callback) = fetch.callback {
+        callback(credentials, error_code, fetch.callback_user_data);
+    }
+}
+
+/// SAFETY: not safe to call directly, only let the CRT call this function as a callback.
+unsafe extern "C" fn observed_shutdown<O: ObserveCredentials>(user_data: *mut libc::c_void) {
+    assert!(!user_data.is_null());
+    let state = Box::from_raw(user_data as *mut Arc<ObservedCredentialsProvider<O>>);
+    std::mem::drop(state);
+}
+
+/// The state of a delegate credentials provider, shared with the CRT as its user data.
+struct DelegateCredentialsProvider<P> {
+    allocator: Allocator,
+    event_loop_group: EventLoopGroup,
+    provider: Arc<P>,
+}
+
+impl<P: ProvideCredentials> DelegateCredentialsProvider<P> {
+    /// Fetch credentials on the event loop group and pass them to the CRT's callback.
+    fn get_credentials(&self, callback: aws_on_get_credentials_callback_fn, callback_user_data: CallbackUserData) {
+        let provider = self.provider.clone();
+        let allocator = self.allocator.clone();
+        let _handle = self.event_loop_group.spawn_future(async move {
+            let result = provider.get_credentials().await;
+            // Don't keep the provider alive past the callback, which may be the last use of it
+            drop(provider);
+            // SAFETY: the CRT gave us the callback and its user data to call exactly once.
+            unsafe { complete_get_credentials(&allocator, result, callback, callback_user_data.0) };
+        });
+    }
+}
+
+/// The CRT's user data for a get-credentials callback. The CRT allows the callback to be called on
+/// any thread.
+struct CallbackUserData(*mut libc::c_void);
+
+// SAFETY: the CRT doesn't require the callback to be called on the thread that asked for it.
+unsafe impl Send for CallbackUserData {}
+
+/// Pass the result of a [ProvideCredentials] call to the CRT.
+///
+/// SAFETY: `callback` and `callback_user_data` must be the arguments the CRT passed to a delegate
+/// `get_credentials` call, and this must be called at most once for them.
+unsafe fn complete_get_credentials(
+    allocator: &Allocator,
+    result: Result<Credentials, ProvideCredentialsError>,
+    callback: aws_on_get_credentials_callback_fn,
+    callback_user_data: *mut libc::c_void,
+) {
+    let Some(callback) = callback else {
+        return;
+    };
+    let credentials = result.and_then(|credentials| new_aws_credentials(allocator.inner.as_ptr(), &credentials));
+    match credentials {
+        Ok(credentials) => {
+            callback(credentials.as_ptr(), 0, callback_user_data);
+            // The callback takes its own reference to the credentials if it keeps them
+            aws_credentials_release(credentials.as_ptr());
+        }
+        Err(e) => {
+            log::warn!("credentials provider failed: {e}");
+            callback(
+                std::ptr::null_mut(),
+                aws_auth_errors::AWS_AUTH_CREDENTIALS_PROVIDER_DELEGATE_FAILURE as i32,
+                callback_user_data,
+            );
+        }
+    }
+}
+
+/// Create CRT credentials from [Credentials].
+///
+/// SAFETY: `allocator` must be a valid `aws_allocator`.
+unsafe fn new_aws_credentials(
+    allocator: *mut aws_allocator,
+    credentials: &Credentials,
+) -> Result<NonNull<mountpoint_s3_crt_sys::aws_credentials>, ProvideCredentialsError> {
+    // Credentials without an expiration never expire
+    let expiration = match credentials.expiration {
+        Some(expiration) => expiration.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        None => u64::MAX,
+    };
+    let access_key_id = ByteCursor::new(&credentials.access_key_id);
+    let secret_access_key = ByteCursor::new(&credentials.secret_access_key);
+    let session_token = credentials.session_token.as_ref().map(ByteCursor::new);
+    // SAFETY: `aws_credentials_new` makes a copy of the strings.
+    let credentials = aws_credentials_new(
+        allocator,
+        access_key_id.as_raw(),
+        secret_access_key.as_raw(),
+        session_token.map(|t| t.as_raw()).unwrap_or_default(),
+        expiration,
+    )
+    .ok_or_last_error()?;
+    Ok(credentials)
+}
+
+/// SAFETY: not safe to call directly, only let the CRT call this function as a callback.
+unsafe extern "C" fn delegate_get_credentials<P: ProvideCredentials>(
+    delegate_user_data: *mut libc::c_void,
+    callback: aws_on_get_credentials_callback_fn,
+    callback_user_data: *mut libc::c_void,
+) -> libc::c_int {
+    assert!(!delegate_user_data.is_null());
+    let delegate = &*(delegate_user_data as *const DelegateCredentialsProvider<P>);
+    delegate.get_credentials(callback, CallbackUserData(callback_user_data));
+    0
+}
+
+/// SAFETY: not safe to call directly, only let the CRT call this function as a callback.
+unsafe extern "C" fn delegate_shutdown<P: ProvideCredentials>(user_data: *mut libc::c_void) {
+    assert!(!user_data.is_null());
+    let delegate = Box::from_raw(user_data as *mut DelegateCredentialsProvider<P>);
+    std::mem::drop(delegate);
+}
+
 /// A credentials provider is an object that has an asynchronous query function for retrieving AWS
 /// credentials
 #[derive(Debug)]
@@ -139,6 +426,99 @@ impl CredentialsProvider {
 
         Ok(Self { inner })
     }
+
+    /// Creates a credentials provider that gets its credentials from a [ProvideCredentials]
+    /// implementation, for credentials sources the CRT doesn't support. Each call to
+    /// [ProvideCredentials::get_credentials] runs as a future on `event_loop_group`, not on a
+    /// Tokio runtime, so it mustn't call Tokio-dependent APIs.
+    pub fn new_delegate(
+        allocator: &Allocator,
+        event_loop_group: &EventLoopGroup,
+        provider: impl ProvideCredentials,
+    ) -> Result<Self, Error> {
+        Self::new_delegate_inner(allocator, event_loop_group, Arc::new(provider))
+    }
+
+    fn new_delegate_inner<P: ProvideCredentials>(
+        allocator: &Allocator,
+        event_loop_group: &EventLoopGroup,
+        provider: Arc<P>,
+    ) -> Result<Self, Error> {
+        auth_library_init(allocator);
+
+        let delegate = Box::new(DelegateCredentialsProvider {
+            allocator: allocator.clone(),
+            event_loop_group: event_loop_group.clone(),
+            provider,
+        });
+        let delegate = Box::into_raw(delegate) as *mut libc::c_void;
+
+        let inner_options = aws_credentials_provider_delegate_options {
+            shutdown_options: aws_credentials_provider_shutdown_options {
+                shutdown_callback: Some(delegate_shutdown::<P>),
+                shutdown_user_data: delegate,
+            },
+            get_credentials: Some(delegate_get_credentials::<P>),
+            delegate_user_data: delegate,
+        };
+
+        // SAFETY: the delegate lives until the CRT calls the shutdown callback, once the provider
+        // is destroyed. If creating the provider fails, the CRT never calls the shutdown callback,
+        // so we free the delegate here instead.
+        let inner = unsafe {
+            aws_credentials_provider_new_delegate(allocator.inner.as_ptr(), &inner_options)
+                .ok_or_last_error()
+                .on_err(|| delegate_shutdown::<P>(delegate))?
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Creates a credentials provider that gets its credentials from `provider`, and reports each
+    /// refresh and failure to `observer`. The credentials themselves aren't passed to the observer.
+    pub fn new_observed(
+        allocator: &Allocator,
+        provider: CredentialsProvider,
+        observer: impl ObserveCredentials,
+    ) -> Result<Self, Error> {
+        Self::new_observed_inner(allocator, provider, observer)
+    }
+
+    fn new_observed_inner<O: ObserveCredentials>(
+        allocator: &Allocator,
+        provider: CredentialsProvider,
+        observer: O,
+    ) -> Result<Self, Error> {
+        auth_library_init(allocator);
+
+        // Fetches in progress keep the state alive, so it can outlive the provider
+        let state = Box::new(Arc::new(ObservedCredentialsProvider {
+            provider,
+            observer,
+            last_expiration: Mutex::new(None),
+        }));
+        let state = Box::into_raw(state) as *mut libc::c_void;
+
+        let inner_options = aws_credentials_provider_delegate_options {
+            shutdown_options: aws_credentials_provider_shutdown_options {
+                shutdown_callback: Some(observed_shutdown::<O>),
+                shutdown_user_data: state,
+            },
+            get_credentials: Some(observed_get_credentials::<O>),
+            delegate_user_data: state,
+        };
+
+        // SAFETY: the state lives until the CRT calls the shutdown callback, once the provider is
+        // destroyed. If creating the provider fails, the CRT never calls the shutdown callback, so
+        // we free the state here instead.
+        let inner = unsafe {
+            aws_credentials_provider_new_delegate(allocator.inner.as_ptr(), &inner_options)
+                .ok_or_last_error()
+                .on_err(|| observed_shutdown::<O>(state))?
+        };
+
+        Ok(Self { inner })
+    }
 }
 
 impl Clone for CredentialsProvider {
@@ -161,3 +541,176 @@ impl Drop for CredentialsProvider {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc::{self, Sender};
+
+    use mountpoint_s3_crt_sys::aws_credentials_get_access_key_id;
+
+    use super::*;
+    use crate::aws_byte_cursor_as_slice;
+
+    struct TestProvider {
+        fail: bool,
+        dropped: Arc<AtomicBool>,
+    }
+
+    impl ProvideCredentials for TestProvider {
+        async fn get_credentials(&self) -> Result<Credentials, ProvideCredentialsError> {
+            if self.fail {
+                return Err("vault unavailable".into());
+            }
+            Ok(Credentials {
+                access_key_id: "AKIDEXAMPLE".to_string(),
+                secret_access_key: "secret".to_string(),
+                session_token: Some("token".to_string()),
+                expiration: Some(UNIX_EPOCH + std::time::Duration::from_secs(2_000_000_000)),
+            })
+        }
+    }
+
+    impl Drop for TestProvider {
+        fn drop(&mut self) {
+            self.dropped.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// The access key ID and expiration of the fetched credentials, or the error code
+    type CallbackResult = Result<(String, u64), i32>;
+
+    unsafe extern "C" fn on_get_credentials(
+        credentials: *mut aws_credentials,
+        error_code: i32,
+        user_data: *mut libc::c_void,
+    ) {
+        let sender = Box::from_raw(user_data as *mut Sender<CallbackResult>);
+        let result = if error_code == 0 {
+            let access_key_id = aws_byte_cursor_as_slice(&aws_credentials_get_access_key_id(credentials));
+            let expiration = aws_credentials_get_expiration_timepoint_seconds(credentials);
+            Ok((String::from_utf8_lossy(access_key_id).into_owned(), expiration))
+        } else {
+            Err(error_code)
+        };
+        sender.send(result).unwrap();
+    }
+
+    fn get_credentials(provider: &CredentialsProvider) -> CallbackResult {
+        let (sender, receiver) = mpsc::channel();
+        let user_data = Box::into_raw(Box::new(sender)) as *mut libc::c_void;
+        // SAFETY: the callback frees its user data, and is called exactly once if the call succeeds.
+        let result = unsafe {
+            aws_credentials_provider_get_credentials(provider.inner.as_ptr(), Some(on_get_credentials), user_data)
+        };
+        assert_eq!(result, 0, "get_credentials should start");
+        receiver.recv().unwrap()
+    }
+
+    #[test]
+    fn delegate_provides_credentials() {
+        let allocator = Allocator::default();
+        let event_loop_group = EventLoopGroup::new_default(&allocator, None, || {}).unwrap();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let provider = TestProvider {
+            fail: false,
+            dropped: dropped.clone(),
+        };
+        let provider = CredentialsProvider::new_delegate(&allocator, &event_loop_group, provider).unwrap();
+
+        let (access_key_id, expiration) = get_credentials(&provider).expect("credentials should be provided");
+        assert_eq!(access_key_id, "AKIDEXAMPLE");
+        assert_eq!(expiration, 2_000_000_000);
+
+        drop(provider);
+        assert!(
+            dropped.load(Ordering::SeqCst),
+            "provider should be dropped with the CRT provider"
+        );
+    }
+
+    #[test]
+    fn delegate_reports_failure() {
+        let allocator = Allocator::default();
+        let event_loop_group = EventLoopGroup::new_default(&allocator, None, || {}).unwrap();
+        let provider = TestProvider {
+            fail: true,
+            dropped: Default::default(),
+        };
+        let provider = CredentialsProvider::new_delegate(&allocator, &event_loop_group, provider).unwrap();
+
+        let error_code = get_credentials(&provider).expect_err("credentials should fail");
+        assert_eq!(
+            error_code,
+            aws_auth_errors::AWS_AUTH_CREDENTIALS_PROVIDER_DELEGATE_FAILURE as i32
+        );
+    }
+
+    #[derive(Default)]
+    struct TestObserver {
+        refreshes: Mutex<Vec<Option<SystemTime>>>,
+        errors: Mutex<Vec<i32>>,
+    }
+
+    impl ObserveCredentials for TestObserver {
+        fn on_refresh(&self, expiration: Option<SystemTime>) {
+            self.refreshes.lock().unwrap().push(expiration);
+        }
+
+        fn on_error(&self, error: Error) {
+            self.errors.lock().unwrap().push(error.raw_error());
+        }
+    }
+
+    #[test]
+    fn observed_reports_refresh() {
+        let allocator = Allocator::default();
+        let event_loop_group = EventLoopGroup::new_default(&allocator, None, || {}).unwrap();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let provider = TestProvider {
+            fail: false,
+            dropped: dropped.clone(),
+        };
+        let provider = CredentialsProvider::new_delegate(&allocator, &event_loop_group, provider).unwrap();
+        let observer = Arc::new(TestObserver::default());
+        let provider = CredentialsProvider::new_observed(&allocator, provider, observer.clone()).unwrap();
+
+        // The credentials are passed through unchanged
+        let (access_key_id, expiration) = get_credentials(&provider).expect("credentials should be provided");
+        assert_eq!(access_key_id, "AKIDEXAMPLE");
+        assert_eq!(expiration, 2_000_000_000);
+
+        // Credentials with the same expiration aren't a refresh
+        get_credentials(&provider).expect("credentials should be provided");
+        assert_eq!(
+            *observer.refreshes.lock().unwrap(),
+            vec![Some(UNIX_EPOCH + Duration::from_secs(2_000_000_000))]
+        );
+        assert!(observer.errors.lock().unwrap().is_empty());
+
+        drop(provider);
+        assert!(
+            dropped.load(Ordering::SeqCst),
+            "wrapped provider should be dropped with the observed provider"
+        );
+    }
+
+    #[test]
+    fn observed_reports_failure() {
+        let allocator = Allocator::default();
+        let event_loop_group = EventLoopGroup::new_default(&allocator, None, || {}).unwrap();
+        let provider = TestProvider {
+            fail: true,
+            dropped: Default::default(),
+        };
+        let provider = CredentialsProvider::new_delegate(&allocator, &event_loop_group, provider).unwrap();
+        let observer = Arc::new(TestObserver::default());
+        let provider = CredentialsProvider::new_observed(&allocator, provider, observer.clone()).unwrap();
+
+        let error_code = get_credentials(&provider).expect_err("credentials should fail");
+        let expected = aws_auth_errors::AWS_AUTH_CREDENTIALS_PROVIDER_DELEGATE_FAILURE as i32;
+        assert_eq!(error_code, expected);
+        assert_eq!(*observer.errors.lock().unwrap(), vec![expected]);
+        assert!(observer.refreshes.lock().unwrap().is_empty());
+    }
+}
@@ -1,12 +1,17 @@
 //! Configuration for signing requests to AWS APIs
 
 use crate::auth::credentials::CredentialsProvider;
-use crate::ToAwsByteCursor;
-use mountpoint_s3_crt_sys::{aws_s3_init_default_signing_config, aws_signing_algorithm, aws_signing_config_aws};
-use std::ffi::OsString;
+use crate::{aws_byte_cursor_as_slice, ToAwsByteCursor};
+use mountpoint_s3_crt_sys::{
+    aws_date_time, aws_date_time_init_epoch_millis, aws_s3_init_default_signing_config, aws_signing_algorithm,
+    aws_signing_config_aws,
+};
+use std::ffi::{OsStr, OsString};
 use std::fmt::Debug;
 use std::marker::PhantomPinned;
+use std::os::unix::prelude::OsStrExt;
 use std::pin::Pin;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(crate) struct SigningConfigInner {
     /// The raw `aws_signing_config` for this config
@@ -21,6 +26,9 @@ pub(crate) struct SigningConfigInner {
     /// An owned copy of the service string, since the `aws_signing_config_aws` holds a pointer to it.
     pub(crate) service: Option<OsString>,
 
+    /// The signing algorithm set in the `aws_signing_config_aws`
+    pub(crate) algorithm: SigningAlgorithm,
+
     /// This forces the struct to be !Unpin, because the signing config can contain pointers to itself
     pub(crate) _pinned: PhantomPinned,
 }
@@ -41,6 +49,8 @@ impl SigningConfigInner {
             region: region.to_owned().into(),
             credentials_provider,
             service: None,
+            // `aws_s3_init_default_signing_config` uses SigV4
+            algorithm: SigningAlgorithm::SigV4,
             _pinned: Default::default(),
         };
 
@@ -72,6 +82,7 @@ impl SigningConfigInner {
 
     /// Set the signing algorithm
     pub fn algorithm(&mut self, algorithm: SigningAlgorithm) {
+        self.algorithm = algorithm;
         self.inner.algorithm = algorithm.into();
     }
 }
@@ -85,6 +96,34 @@ impl SigningConfig {
     pub(crate) fn to_inner_ptr(&self) -> *const aws_signing_config_aws {
         &self.0.as_ref().get_ref().inner
     }
+
+    /// The region that requests will be signed for
+    pub fn region(&self) -> &OsStr {
+        // SAFETY: the cursor points into `self.0.region`, which lives as long as `self` does.
+        let region = unsafe { aws_byte_cursor_as_slice(&self.0.inner.region) };
+        OsStr::from_bytes(region)
+    }
+
+    /// The name of the service that requests will be signed for
+    pub fn service(&self) -> &OsStr {
+        // SAFETY: the cursor points into either `self.0.service` or the CRT's static default
+        // service name, both of which live as long as `self` does.
+        let service = unsafe { aws_byte_cursor_as_slice(&self.0.inner.service) };
+        OsStr::from_bytes(service)
+    }
+
+    /// The algorithm that requests will be signed with
+    pub fn algorithm(&self) -> SigningAlgorithm {
+        self.0.algorithm
+    }
+}
+
+/// Set the date of a signing config to `time`, for signatures that don't use the current time.
+/// Times before the Unix epoch are treated as the epoch.
+pub(crate) fn set_signing_date(date: &mut aws_date_time, time: SystemTime) {
+    let millis = time.duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or(0);
+    // SAFETY: `date` is a valid `aws_date_time` to initialize.
+    unsafe { aws_date_time_init_epoch_millis(date, millis.try_into().unwrap_or(u64::MAX)) };
 }
 
 /// The version of the AWS signing process.
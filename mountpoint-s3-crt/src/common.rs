@@ -7,8 +7,11 @@ use mountpoint_s3_crt_sys::*;
 use crate::common::allocator::Allocator;
 
 pub mod allocator;
+pub mod byte_cursor;
 pub mod error;
+pub mod future;
 pub mod logging;
+pub mod redact;
 pub mod ref_count;
 pub mod rust_log_adapter;
 pub mod string;
@@ -3,8 +3,8 @@
 use mountpoint_s3_crt_sys::*;
 use std::ptr::NonNull;
 
-/// An allocator for use by the CRT
-#[derive(Debug)]
+/// An allocator for use by the CRT. Allocators are never freed, so copies can be shared freely.
+#[derive(Debug, Clone)]
 pub struct Allocator {
     /// Pointer to the underlying `aws_allocator`
     pub(crate) inner: NonNull<aws_allocator>,
@@ -0,0 +1,98 @@
+//! Borrowed byte cursors for passing Rust-owned bytes to the CRT
+
+use std::ffi::OsStr;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::os::unix::prelude::OsStrExt;
+
+use mountpoint_s3_crt_sys::aws_byte_cursor;
+
+/// A CRT byte cursor that borrows the bytes it points at, so it can't outlive them.
+///
+/// Use this to pass Rust-owned bytes to CRT functions that only read them for the duration of the
+/// call. The borrow checker ensures the bytes stay alive for as long as the cursor does:
+///
+/// ```compile_fail
+/// use mountpoint_s3_crt::common::byte_cursor::ByteCursor;
+///
+/// let cursor = {
+///     let name = String::from("x-amz-request-payer");
+///     ByteCursor::new(&name)
+/// };
+/// println!("{:?}", cursor.as_bytes());
+/// ```
+#[derive(Clone, Copy)]
+pub struct ByteCursor<'a> {
+    inner: aws_byte_cursor,
+    _bytes: PhantomData<&'a [u8]>,
+}
+
+impl<'a> ByteCursor<'a> {
+    /// Create a new cursor that points at the given bytes.
+    pub fn new(bytes: &'a (impl AsRef<[u8]> + ?Sized)) -> Self {
+        let bytes = bytes.as_ref();
+        Self {
+            inner: aws_byte_cursor {
+                ptr: bytes.as_ptr() as *mut _,
+                len: bytes.len(),
+            },
+            _bytes: PhantomData,
+        }
+    }
+
+    /// Create a new cursor that points at the bytes of the given string.
+    pub fn from_os_str(s: &'a (impl AsRef<OsStr> + ?Sized)) -> Self {
+        Self::new(s.as_ref().as_bytes())
+    }
+
+    /// The bytes this cursor points at.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        if self.inner.ptr.is_null() {
+            return &[];
+        }
+        // SAFETY: the cursor was created from a slice that is borrowed for at least `'a`.
+        unsafe { std::slice::from_raw_parts(self.inner.ptr, self.inner.len) }
+    }
+
+    /// The raw cursor to pass to a CRT function. Nothing ties the raw cursor to the lifetime of
+    /// the bytes, so if the CRT holds on to it after the call returns, or it's stored in an options
+    /// struct, the caller must make sure the bytes outlive every use of it.
+    pub(crate) fn as_raw(&self) -> aws_byte_cursor {
+        self.inner
+    }
+}
+
+impl Debug for ByteCursor<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ByteCursor")
+            .field(&OsStr::from_bytes(self.as_bytes()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::aws_byte_cursor_as_slice;
+
+    #[test]
+    fn test_byte_cursor_points_at_bytes() {
+        let header = String::from("x-amz-request-payer");
+        let cursor = ByteCursor::from_os_str(&header);
+        assert_eq!(cursor.as_bytes(), header.as_bytes());
+
+        let raw = cursor.as_raw();
+        assert_eq!(raw.ptr as *const u8, header.as_ptr());
+        assert_eq!(raw.len, header.len());
+        // SAFETY: `header` outlives `raw` in this test.
+        assert_eq!(unsafe { aws_byte_cursor_as_slice(&raw) }, b"x-amz-request-payer");
+    }
+
+    #[test]
+    fn test_byte_cursor_empty() {
+        let cursor = ByteCursor::new(b"");
+        assert_eq!(cursor.as_bytes(), b"");
+        assert_eq!(cursor.as_raw().len, 0);
+        assert_eq!(format!("{cursor:?}"), "ByteCursor(\"\")");
+    }
+}
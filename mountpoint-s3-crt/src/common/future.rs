@@ -0,0 +1,178 @@
+//! A [Future] that completes when a CRT operation reports its result through a callback
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::channel::oneshot;
+use futures::FutureExt;
+use thiserror::Error;
+
+use crate::common::error::Error;
+
+/// Callback run when a [CrtFuture] is dropped before its operation completes
+type CancelHandler = Box<dyn FnOnce() + Send>;
+
+/// A [Future] for the result of a CRT operation that reports completion through a callback.
+///
+/// Create one with [CrtFuture::new], move the [CrtPromise] into the operation's completion
+/// callback, and complete the promise from there. If the future is dropped before the operation
+/// completes, it runs the handler set by [CrtFuture::set_cancel_handler], which should cancel the
+/// operation so the CRT releases its resources promptly.
+#[must_use = "futures do nothing unless polled"]
+pub struct CrtFuture<T, E = Error> {
+    receiver: oneshot::Receiver<Result<T, E>>,
+    cancel_handler: Option<CancelHandler>,
+}
+
+/// The completion side of a [CrtFuture], to be called from the CRT operation's callback
+#[derive(Debug)]
+pub struct CrtPromise<T, E = Error> {
+    sender: oneshot::Sender<Result<T, E>>,
+}
+
+/// Errors returned by a [CrtFuture]
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CrtFutureError<E> {
+    /// The operation completed with an error
+    #[error("CRT operation failed: {0}")]
+    Failed(E),
+
+    /// The promise was dropped without being completed
+    #[error("CRT operation finished without reporting a result")]
+    Abandoned,
+}
+
+impl<T, E> CrtFuture<T, E> {
+    /// Create a new future, and the promise that completes it.
+    pub fn new() -> (CrtPromise<T, E>, Self) {
+        let (sender, receiver) = oneshot::channel();
+        let future = Self {
+            receiver,
+            cancel_handler: None,
+        };
+        (CrtPromise { sender }, future)
+    }
+
+    /// Set the handler to run if this future is dropped before the operation completes, replacing
+    /// any previous handler. The handler isn't run if the future has already returned a result.
+    pub fn set_cancel_handler(&mut self, cancel_handler: impl FnOnce() + Send + 'static) {
+        self.cancel_handler = Some(Box::new(cancel_handler));
+    }
+}
+
+impl<T, E> Future for CrtFuture<T, E> {
+    type Output = Result<T, CrtFutureError<E>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = futures::ready!(self.receiver.poll_unpin(cx));
+        // The operation is done, so there's nothing left to cancel.
+        self.cancel_handler = None;
+        Poll::Ready(match result {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(err)) => Err(CrtFutureError::Failed(err)),
+            Err(oneshot::Canceled) => Err(CrtFutureError::Abandoned),
+        })
+    }
+}
+
+impl<T, E> Drop for CrtFuture<T, E> {
+    fn drop(&mut self) {
+        if let Some(cancel_handler) = self.cancel_handler.take() {
+            cancel_handler();
+        }
+    }
+}
+
+impl<T, E> Debug for CrtFuture<T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CrtFuture")
+            .field("cancel_handler", &self.cancel_handler.is_some())
+            .finish()
+    }
+}
+
+impl<T, E> CrtPromise<T, E> {
+    /// Complete the future with the given result. Does nothing if the future has been dropped.
+    pub fn complete(self, result: Result<T, E>) {
+        _ = self.sender.send(result);
+    }
+
+    /// Return whether the future has been dropped, so the result will never be observed.
+    pub fn is_canceled(&self) -> bool {
+        self.sender.is_canceled()
+    }
+}
+
+impl<T> CrtPromise<T, Error> {
+    /// Complete the future from a CRT error code, with an error if the code is an error or with
+    /// the result of `on_success` otherwise.
+    pub fn complete_with_error_code(self, error_code: i32, on_success: impl FnOnce() -> T) {
+        let error = Error::from(error_code);
+        let result = if error.is_err() { Err(error) } else { Ok(on_success()) };
+        self.complete(result);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use futures::executor::block_on;
+    use mountpoint_s3_crt_sys::AWS_OP_SUCCESS;
+
+    use super::*;
+
+    /// Create a future whose cancel handler sets the returned flag
+    fn new_future_with_cancel_flag<T, E>() -> (CrtPromise<T, E>, CrtFuture<T, E>, Arc<AtomicBool>) {
+        let (promise, mut future) = CrtFuture::new();
+        let canceled = Arc::new(AtomicBool::new(false));
+        let canceled_clone = canceled.clone();
+        future.set_cancel_handler(move || canceled_clone.store(true, Ordering::SeqCst));
+        (promise, future, canceled)
+    }
+
+    #[test]
+    fn test_crt_future_resolves_on_completion() {
+        let (promise, future, canceled) = new_future_with_cancel_flag::<_, Error>();
+        let handle = std::thread::spawn(move || promise.complete_with_error_code(AWS_OP_SUCCESS, || 42));
+        assert_eq!(block_on(future), Ok(42));
+        handle.join().unwrap();
+        assert!(
+            !canceled.load(Ordering::SeqCst),
+            "completed future should not be canceled"
+        );
+    }
+
+    #[test]
+    fn test_crt_future_propagates_errors() {
+        let (promise, future) = CrtFuture::<(), Error>::new();
+        // AWS_ERROR_OOM
+        promise.complete_with_error_code(1, || ());
+        assert_eq!(block_on(future), Err(CrtFutureError::Failed(Error::from(1))));
+
+        let (promise, future) = CrtFuture::<(), &str>::new();
+        promise.complete(Err("failed"));
+        assert_eq!(block_on(future), Err(CrtFutureError::Failed("failed")));
+    }
+
+    #[test]
+    fn test_crt_future_abandoned() {
+        let (promise, future) = CrtFuture::<(), Error>::new();
+        drop(promise);
+        assert_eq!(block_on(future), Err(CrtFutureError::Abandoned));
+    }
+
+    #[test]
+    fn test_crt_future_cancels_on_drop() {
+        let (promise, future, canceled) = new_future_with_cancel_flag::<(), Error>();
+        assert!(!promise.is_canceled());
+        drop(future);
+        assert!(canceled.load(Ordering::SeqCst), "dropped future should be canceled");
+        assert!(promise.is_canceled());
+        // Completing after the future is dropped is fine
+        promise.complete(Ok(()));
+    }
+}
@@ -0,0 +1,197 @@
+//! Redaction of secrets from HTTP headers and query strings before they are logged
+
+use std::ffi::{OsStr, OsString};
+use std::os::unix::prelude::OsStrExt;
+
+use crate::common::allocator::Allocator;
+use crate::http::request_response::{Header, Headers, HeadersError};
+
+/// The value that replaces any redacted header value or query parameter
+pub const REDACTED: &str = "** redacted **";
+
+/// Headers that carry credentials or encryption keys, and so are always redacted
+const SENSITIVE_HEADERS: &[&str] = &[
+    "Authorization",
+    "x-amz-security-token",
+    "x-amz-server-side-encryption-customer-key",
+    "x-amz-copy-source-server-side-encryption-customer-key",
+];
+
+/// Query parameters that carry credentials in presigned requests, and so are always redacted
+const SENSITIVE_QUERY_PARAMS: &[&str] = &["X-Amz-Signature", "X-Amz-Security-Token", "X-Amz-Credential"];
+
+/// Redacts secrets from headers and query strings. Every logging path that might print a request
+/// or response should go through a [Redactor], so that secrets are masked consistently.
+///
+/// The well-known sensitive headers and query parameters are always redacted. Additional names
+/// can be added with [Redactor::redact_header] and [Redactor::redact_query_param]. All names are
+/// matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    additional_headers: Vec<String>,
+    additional_query_params: Vec<String>,
+}
+
+impl Redactor {
+    /// Create a new [Redactor] that only redacts the well-known sensitive names
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also redact the value of the header with the given name
+    pub fn redact_header(mut self, name: impl Into<String>) -> Self {
+        self.additional_headers.push(name.into());
+        self
+    }
+
+    /// Also redact the value of the query parameter with the given name
+    pub fn redact_query_param(mut self, name: impl Into<String>) -> Self {
+        self.additional_query_params.push(name.into());
+        self
+    }
+
+    /// Return a copy of the given [Headers] with the values of any sensitive headers redacted
+    pub fn redact_headers(&self, headers: &Headers) -> Result<Headers, HeadersError> {
+        let mut redacted = Headers::new(&Allocator::default())?;
+        for (name, value) in headers.iter() {
+            if self.is_sensitive_header(&name) {
+                redacted.add_header(&Header::new(name, REDACTED))?;
+            } else {
+                redacted.add_header(&Header::new(name, value))?;
+            }
+        }
+        Ok(redacted)
+    }
+
+    /// Return a copy of the given query string (without the leading `?`) with the values of any
+    /// sensitive parameters redacted. Parameter order and valueless parameters are preserved.
+    pub fn redact_query(&self, query: impl AsRef<OsStr>) -> OsString {
+        let mut redacted = OsString::with_capacity(query.as_ref().len());
+        for (i, param) in query.as_ref().as_bytes().split(|b| *b == b'&').enumerate() {
+            if i > 0 {
+                redacted.push("&");
+            }
+            let mut parts = param.splitn(2, |b| *b == b'=');
+            let name = parts.next().unwrap_or_default();
+            match parts.next() {
+                Some(_) if self.is_sensitive_query_param(name) => {
+                    redacted.push(OsStr::from_bytes(name));
+                    redacted.push("=");
+                    redacted.push(REDACTED);
+                }
+                _ => redacted.push(OsStr::from_bytes(param)),
+            }
+        }
+        redacted
+    }
+
+    fn is_sensitive_header(&self, name: &OsStr) -> bool {
+        matches_any(name.as_bytes(), SENSITIVE_HEADERS.iter().copied())
+            || matches_any(name.as_bytes(), self.additional_headers.iter().map(String::as_str))
+    }
+
+    fn is_sensitive_query_param(&self, name: &[u8]) -> bool {
+        matches_any(name, SENSITIVE_QUERY_PARAMS.iter().copied())
+            || matches_any(name, self.additional_query_params.iter().map(String::as_str))
+    }
+}
+
+fn matches_any<'a>(name: &[u8], mut candidates: impl Iterator<Item = &'a str>) -> bool {
+    candidates.any(|candidate| candidate.as_bytes().eq_ignore_ascii_case(name))
+}
+
+/// Return a copy of the given [Headers] with the values of the well-known sensitive headers
+/// redacted. See [Redactor] to redact additional headers.
+pub fn redact_headers(headers: &Headers) -> Result<Headers, HeadersError> {
+    Redactor::new().redact_headers(headers)
+}
+
+/// Return a copy of the given query string with the values of the well-known sensitive parameters
+/// redacted. See [Redactor] to redact additional parameters.
+pub fn redact_query(query: impl AsRef<OsStr>) -> OsString {
+    Redactor::new().redact_query(query)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn make_headers(pairs: &[(&str, &str)]) -> Headers {
+        let mut headers = Headers::new(&Allocator::default()).expect("failed to create headers");
+        for (name, value) in pairs {
+            headers.add_header(&Header::new(*name, *value)).unwrap();
+        }
+        headers
+    }
+
+    /// Test that all the known-sensitive headers are redacted and other headers are untouched.
+    #[test]
+    fn test_redact_headers() {
+        let headers = make_headers(&[
+            (
+                "Authorization",
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20240101/us-east-1/s3/aws4_request",
+            ),
+            ("X-Amz-Security-Token", "session-token"),
+            ("x-amz-server-side-encryption-customer-key", "c2VjcmV0LWtleQ=="),
+            (
+                "x-amz-copy-source-server-side-encryption-customer-key",
+                "c2VjcmV0LWtleQ==",
+            ),
+            ("x-amz-server-side-encryption-customer-algorithm", "AES256"),
+            ("Content-Length", "1024"),
+            ("Host", "bucket.s3.amazonaws.com"),
+        ]);
+
+        let redacted = redact_headers(&headers).expect("failed to redact headers");
+        assert_eq!(redacted.count(), headers.count());
+
+        for name in SENSITIVE_HEADERS {
+            assert_eq!(
+                redacted.get(name).unwrap().value(),
+                REDACTED,
+                "{name} should be redacted"
+            );
+        }
+        assert_eq!(
+            redacted
+                .get("x-amz-server-side-encryption-customer-algorithm")
+                .unwrap()
+                .value(),
+            "AES256"
+        );
+        assert_eq!(redacted.get("Content-Length").unwrap().value(), "1024");
+        assert_eq!(redacted.get("Host").unwrap().value(), "bucket.s3.amazonaws.com");
+
+        // The original headers should be unchanged
+        assert_eq!(headers.get("X-Amz-Security-Token").unwrap().value(), "session-token");
+    }
+
+    /// Test that additional headers can be configured for redaction.
+    #[test]
+    fn test_redact_additional_headers() {
+        let headers = make_headers(&[("x-custom-secret", "hunter2"), ("x-custom-public", "hello")]);
+
+        let redacted = Redactor::new()
+            .redact_header("X-Custom-Secret")
+            .redact_headers(&headers)
+            .expect("failed to redact headers");
+        assert_eq!(redacted.get("x-custom-secret").unwrap().value(), REDACTED);
+        assert_eq!(redacted.get("x-custom-public").unwrap().value(), "hello");
+    }
+
+    /// Test that presign signature parameters are redacted and other parameters are untouched.
+    #[test]
+    fn test_redact_query() {
+        let query = "list-type=2&X-Amz-Credential=AKIDEXAMPLE%2F20240101&x-amz-signature=abcdef&tagging&X-Amz-Security-Token=token&prefix=";
+        assert_eq!(
+            redact_query(query),
+            "list-type=2&X-Amz-Credential=** redacted **&x-amz-signature=** redacted **&tagging&X-Amz-Security-Token=** redacted **&prefix="
+        );
+
+        let redacted = Redactor::new()
+            .redact_query_param("prefix")
+            .redact_query("prefix=secret&delimiter=%2F");
+        assert_eq!(redacted, "prefix=** redacted **&delimiter=%2F");
+    }
+}
@@ -15,16 +15,29 @@ pub const AWSCRT_LOG_TARGET: &str = "awscrt";
 /// will be sent to the `log` facade. These messages will follow that facade's logic for when to
 /// emit log messages. All CRT log messages will have a target that starts with the value of
 /// [AWSCRT_LOG_TARGET].
+///
+/// To stop the CRT generating verbose messages at all, rather than having the `log` facade filter
+/// them out, install the adapter with [RustLogAdapter::try_init_with_level].
 #[derive(Debug)]
 #[non_exhaustive]
-pub struct RustLogAdapter;
+pub struct RustLogAdapter {
+    max_level: log::LevelFilter,
+}
 
 impl RustLogAdapter {
     /// Try to install the `log` adapter as the current CRT logger. Only one CRT logger can be
     /// installed for the lifetime of the program, so this returns Err if a logger has already been
     /// installed.
     pub fn try_init() -> Result<(), LoggerInitError> {
-        let logger = Logger::new(&Allocator::default(), Self);
+        Self::try_init_with_level(log::LevelFilter::Trace)
+    }
+
+    /// Try to install the `log` adapter as the current CRT logger, asking the CRT to generate only
+    /// messages at `max_level` or above. This threshold applies in addition to the `log` facade's
+    /// own [log::max_level]. Only one CRT logger can be installed for the lifetime of the program,
+    /// so this returns Err if a logger has already been installed.
+    pub fn try_init_with_level(max_level: log::LevelFilter) -> Result<(), LoggerInitError> {
+        let logger = Logger::new(&Allocator::default(), Self { max_level });
         logger.try_init()
     }
 }
@@ -36,7 +49,11 @@ impl LoggerImpl for RustLogAdapter {
         log::log!(target: target.as_str(), log_level.into(), "{}", message);
     }
     fn get_log_level(&self, _subject: Subject) -> Level {
-        log::max_level().to_level().map(|l| l.into()).unwrap_or(Level::None)
+        log::max_level()
+            .min(self.max_level)
+            .to_level()
+            .map(|l| l.into())
+            .unwrap_or(Level::None)
     }
 }
 
@@ -68,3 +85,32 @@ impl From<log::Level> for Level {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_max_level_caps_crt_log_level() {
+        // The unit test harness sets the `log` facade's max level to at least `ERROR`
+        let subject = Subject::from(0);
+        let adapter = RustLogAdapter {
+            max_level: log::LevelFilter::Off,
+        };
+        assert_eq!(adapter.get_log_level(subject), Level::None);
+
+        let adapter = RustLogAdapter {
+            max_level: log::LevelFilter::Error,
+        };
+        assert_eq!(adapter.get_log_level(subject), Level::Fatal);
+    }
+
+    #[test]
+    fn test_repeated_init_fails() {
+        // The unit test harness already installed a logger
+        let err = RustLogAdapter::try_init_with_level(log::LevelFilter::Warn).expect_err("logger already installed");
+        assert!(matches!(err, LoggerInitError::AlreadyInitialized));
+        let err = RustLogAdapter::try_init().expect_err("logger already installed");
+        assert!(matches!(err, LoggerInitError::AlreadyInitialized));
+    }
+}
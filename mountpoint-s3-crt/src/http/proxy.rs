@@ -0,0 +1,98 @@
+//! Options for connecting through HTTP proxies
+
+use std::pin::Pin;
+
+use mountpoint_s3_crt_sys::{
+    aws_http_proxy_authentication_type, aws_http_proxy_connection_type, aws_http_proxy_options,
+};
+
+use crate::common::byte_cursor::ByteCursor;
+use crate::common::redact::REDACTED;
+use crate::io::tls::TlsConnectionOptions;
+
+/// Options for connecting through an HTTP proxy. Connections are tunneled through the proxy with
+/// HTTP CONNECT requests. Follows the builder pattern.
+#[derive(Debug)]
+pub struct ProxyOptions(Pin<Box<ProxyOptionsInner>>);
+
+struct ProxyOptionsInner {
+    /// The struct we can pass into the CRT's functions. It points into the owned fields below.
+    inner: aws_http_proxy_options,
+
+    /// Owned copy of the proxy host
+    host: String,
+
+    /// Owned copies of the basic authentication username and password, if set
+    auth: Option<(String, String)>,
+
+    /// TLS options for the connection to the proxy, if it uses TLS
+    tls_options: Option<TlsConnectionOptions>,
+
+    /// The inner struct points to the fields above, so it must not move
+    _pinned: std::marker::PhantomPinned,
+}
+
+impl std::fmt::Debug for ProxyOptionsInner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProxyOptionsInner")
+            .field("host", &self.host)
+            .field("port", &self.inner.port)
+            .field("auth", &self.auth.as_ref().map(|_| REDACTED))
+            .field("tls_options", &self.tls_options)
+            .finish()
+    }
+}
+
+impl ProxyOptions {
+    /// Create options for a proxy at the given host and port, without authentication or TLS.
+    pub fn new(host: &str, port: u16) -> Self {
+        let mut options = Box::pin(ProxyOptionsInner {
+            inner: Default::default(),
+            host: host.to_owned(),
+            auth: None,
+            tls_options: None,
+            _pinned: Default::default(),
+        });
+        // SAFETY: we don't move out of the pinned options.
+        let options_mut = unsafe { options.as_mut().get_unchecked_mut() };
+        options_mut.inner.connection_type = aws_http_proxy_connection_type::AWS_HPCT_HTTP_TUNNEL;
+        options_mut.inner.port = port as u32;
+        // `host` is owned by the options and never changes, so the cursor stays valid as long as the
+        // options do.
+        options_mut.inner.host = ByteCursor::new(&options_mut.host).as_raw();
+        Self(options)
+    }
+
+    /// Authenticate to the proxy with basic authentication.
+    pub fn basic_auth(&mut self, username: &str, password: &str) -> &mut Self {
+        // SAFETY: we don't move out of the pinned options.
+        let options = unsafe { self.0.as_mut().get_unchecked_mut() };
+        options.auth = Some((username.to_owned(), password.to_owned()));
+        let (username, password) = options.auth.as_ref().unwrap();
+        options.inner.auth_type = aws_http_proxy_authentication_type::AWS_HPAT_BASIC;
+        // The username and password are owned by the options and only replaced by another call to
+        // this method, which updates the cursors.
+        options.inner.auth_username = ByteCursor::new(username).as_raw();
+        options.inner.auth_password = ByteCursor::new(password).as_raw();
+        self
+    }
+
+    /// Use TLS for the connection to the proxy.
+    pub fn tls_options(&mut self, tls_options: TlsConnectionOptions) -> &mut Self {
+        // SAFETY: we don't move out of the pinned options.
+        let options = unsafe { self.0.as_mut().get_unchecked_mut() };
+        options.tls_options = Some(tls_options);
+        options.inner.tls_options = &options.tls_options.as_ref().unwrap().inner;
+        self
+    }
+
+    /// Get out the inner pointer to the proxy options
+    pub(crate) fn to_inner_ptr(&self) -> *const aws_http_proxy_options {
+        &self.0.inner
+    }
+}
+
+// SAFETY: the raw pointers in the options only point into the options themselves.
+unsafe impl Send for ProxyOptions {}
+// SAFETY: the raw pointers in the options only point into the options themselves.
+unsafe impl Sync for ProxyOptions {}
@@ -9,8 +9,10 @@ use mountpoint_s3_crt_sys::*;
 use thiserror::Error;
 
 use crate::common::allocator::Allocator;
+use crate::common::byte_cursor::ByteCursor;
 use crate::common::error::Error;
 use crate::http::http_library_init;
+use crate::io::stream::InputStream;
 use crate::{aws_byte_cursor_as_slice, CrtError, ToAwsByteCursor};
 
 /// An HTTP header.
@@ -156,17 +158,19 @@ impl Headers {
 
     /// Returns whether a header with the given name is present in these [Headers].
     pub fn has_header(&self, name: impl AsRef<OsStr>) -> bool {
+        let name = ByteCursor::from_os_str(&name);
         // SAFETY: `aws_http_headers_has` doesn't hold on to a copy of the name we pass in, so it's
-        // okay to call with with an `aws_byte_cursor` that may not outlive this `Headers`.
-        unsafe { aws_http_headers_has(self.inner.as_ptr(), name.as_ref().as_aws_byte_cursor()) }
+        // okay to call with a cursor that only lives as long as this call.
+        unsafe { aws_http_headers_has(self.inner.as_ptr(), name.as_raw()) }
     }
 
     /// Erases a header with the given name from these [Headers].
     pub fn erase_header(&self, name: impl AsRef<OsStr>) -> Result<(), HeadersError> {
+        let name = ByteCursor::from_os_str(&name);
         // SAFETY: `aws_http_headers_erase` doesn't hold on to a copy of the name we pass in, so it's
-        // okay to call with with an `aws_byte_cursor` that may not outlive this `Headers`.
+        // okay to call with a cursor that only lives as long as this call.
         unsafe {
-            aws_http_headers_erase(self.inner.as_ptr(), name.as_ref().as_aws_byte_cursor()).ok_or_last_error()?;
+            aws_http_headers_erase(self.inner.as_ptr(), name.as_raw()).ok_or_last_error()?;
         }
 
         Ok(())
@@ -174,16 +178,12 @@ impl Headers {
 
     /// Get a single header by name from this block of headers
     pub fn get<H: AsRef<OsStr>>(&self, name: H) -> Result<Header<OsString, OsString>, HeadersError> {
+        let name_cursor = ByteCursor::from_os_str(&name);
         // SAFETY: `self.inner` is a valid aws_http_headers, and `aws_http_headers_get` promises to
         // initialize the output `struct aws_byte_cursor *out_value` on success.
         let value = unsafe {
             let mut value: MaybeUninit<aws_byte_cursor> = MaybeUninit::uninit();
-            aws_http_headers_get(
-                self.inner.as_ptr(),
-                name.as_ref().as_aws_byte_cursor(),
-                value.as_mut_ptr(),
-            )
-            .ok_or_last_error()?;
+            aws_http_headers_get(self.inner.as_ptr(), name_cursor.as_raw(), value.as_mut_ptr()).ok_or_last_error()?;
             value.assume_init()
         };
 
@@ -253,6 +253,9 @@ impl<'a> Iterator for HeadersIterator<'a> {
 pub struct Message {
     /// The pointer to the inner `aws_http_message`.
     pub(crate) inner: NonNull<aws_http_message>,
+    /// The body stream of the message, if any. The message only holds a pointer to the stream, so
+    /// we keep it alive as long as the message.
+    body_stream: Option<InputStream>,
 }
 
 impl Message {
@@ -264,7 +267,10 @@ impl Message {
         // SAFETY: `allocator.inner` is a valid `aws_allocator`.
         let inner = unsafe { aws_http_message_new_request(allocator.inner.as_ptr()).ok_or_last_error()? };
 
-        Ok(Self { inner })
+        Ok(Self {
+            inner,
+            body_stream: None,
+        })
     }
 
     /// Add a header to this message. If the header already exists in the message, this will add a
@@ -281,29 +287,47 @@ impl Message {
         // SAFETY: `self.inner` is a valid aws_http_message
         let headers = unsafe { aws_http_message_get_headers(self.inner.as_ptr()) };
         assert!(!headers.is_null(), "headers are always initialized");
+        let name = ByteCursor::from_os_str(header.name());
+        let value = ByteCursor::from_os_str(header.value());
         // SAFETY: `aws_http_headers_set` makes a copy of the values in `header`
-        unsafe {
-            aws_http_headers_set(
-                headers,
-                header.name().as_aws_byte_cursor(),
-                header.value().as_aws_byte_cursor(),
-            )
-            .ok_or_last_error()
-        }
+        unsafe { aws_http_headers_set(headers, name.as_raw(), value.as_raw()).ok_or_last_error() }
     }
 
     /// Set the request path for this message.
     pub fn set_request_path(&mut self, path: impl AsRef<OsStr>) -> Result<(), Error> {
+        let path = ByteCursor::from_os_str(&path);
         // SAFETY: `aws_http_message_set_request_path` makes a copy of `path`.
-        unsafe { aws_http_message_set_request_path(self.inner.as_ptr(), path.as_aws_byte_cursor()).ok_or_last_error() }
+        unsafe { aws_http_message_set_request_path(self.inner.as_ptr(), path.as_raw()).ok_or_last_error() }
+    }
+
+    /// The request path of this message, including its query string.
+    pub fn request_path(&self) -> Result<OsString, Error> {
+        let mut path = aws_byte_cursor::default();
+        // SAFETY: `self.inner` is a valid `aws_http_message`, and the cursor points into its path,
+        // which we copy before the message can change.
+        unsafe {
+            aws_http_message_get_request_path(self.inner.as_ptr(), &mut path).ok_or_last_error()?;
+            Ok(OsStr::from_bytes(aws_byte_cursor_as_slice(&path)).to_owned())
+        }
     }
 
     /// Set the request method for this message.
     pub fn set_request_method(&mut self, method: impl AsRef<OsStr>) -> Result<(), Error> {
+        let method = ByteCursor::from_os_str(&method);
         // SAFETY: `aws_http_message_set_request_method` makes a copy of `method`.
-        unsafe {
-            aws_http_message_set_request_method(self.inner.as_ptr(), method.as_aws_byte_cursor()).ok_or_last_error()
-        }
+        unsafe { aws_http_message_set_request_method(self.inner.as_ptr(), method.as_raw()).ok_or_last_error() }
+    }
+
+    /// Set the body stream for this message, replacing any existing one. Pass `None` to remove the
+    /// body.
+    pub fn set_body_stream(&mut self, body_stream: Option<InputStream>) {
+        let stream_ptr = body_stream
+            .as_ref()
+            .map_or(std::ptr::null_mut(), |stream| stream.inner.as_ptr());
+        // SAFETY: `self.inner` is a valid `aws_http_message`, and we keep `body_stream` alive at
+        // least as long as the message by storing it in `self`.
+        unsafe { aws_http_message_set_body_stream(self.inner.as_ptr(), stream_ptr) };
+        self.body_stream = body_stream;
     }
 
     /// get the headers from the message and increases the reference count for the Headers in CRT.
@@ -385,6 +409,26 @@ mod test {
         assert_eq!(map.get(OsStr::new("a")), Some(&OsString::from("2")));
     }
 
+    /// Test setting and removing the body stream of a message.
+    #[test]
+    fn test_message_body_stream() {
+        let allocator = Allocator::default();
+        let mut message = Message::new_request(&allocator).expect("failed to create message");
+
+        let stream = InputStream::new_from_buffer(&allocator, b"hello world".to_vec()).unwrap();
+        let stream_ptr = stream.inner.as_ptr();
+        message.set_body_stream(Some(stream));
+        // SAFETY: `message.inner` is a valid `aws_http_message`
+        assert_eq!(
+            unsafe { aws_http_message_get_body_stream(message.inner.as_ptr()) },
+            stream_ptr
+        );
+
+        message.set_body_stream(None);
+        // SAFETY: `message.inner` is a valid `aws_http_message`
+        assert!(unsafe { aws_http_message_get_body_stream(message.inner.as_ptr()) }.is_null());
+    }
+
     /// Test erasing a header.
     #[test]
     fn test_headers_erase() {
@@ -18,6 +18,111 @@ pub struct HostResolverDefaultOptions<'a> {
     pub event_loop_group: &'a mut EventLoopGroup,
 }
 
+/// Which address families a [ClientBootstrap](crate::io::channel_bootstrap::ClientBootstrap) uses
+/// for the connections it creates
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HostResolutionMode {
+    /// Resolve both IPv4 and IPv6 addresses, and connect to whichever answers first (the CRT's
+    /// default behavior)
+    #[default]
+    Auto,
+    /// Only resolve IPv4 (A) addresses
+    Ipv4Only,
+    /// Only resolve IPv6 (AAAA) addresses
+    Ipv6Only,
+}
+
+/// How long the CRT caches resolved addresses for, in seconds. This is the CRT's default.
+const DEFAULT_DNS_TTL: usize = 30;
+
+impl HostResolutionMode {
+    /// The host resolution config that implements this mode, or `None` for the CRT's default.
+    pub(crate) fn to_inner(self) -> Option<aws_host_resolution_config> {
+        let resolve: unsafe extern "C" fn(_, _, _, _) -> _ = match self {
+            HostResolutionMode::Auto => return None,
+            HostResolutionMode::Ipv4Only => resolve_ipv4_only,
+            HostResolutionMode::Ipv6Only => resolve_ipv6_only,
+        };
+        Some(aws_host_resolution_config {
+            impl_: Some(resolve),
+            max_ttl: DEFAULT_DNS_TTL,
+            impl_data: std::ptr::null_mut(),
+            resolve_frequency_ns: 0,
+        })
+    }
+}
+
+/// SAFETY: not safe to call directly, only let the CRT call this function as a callback.
+unsafe extern "C" fn resolve_ipv4_only(
+    allocator: *mut aws_allocator,
+    host_name: *const aws_string,
+    output_addresses: *mut aws_array_list,
+    user_data: *mut libc::c_void,
+) -> libc::c_int {
+    resolve_record_type(
+        allocator,
+        host_name,
+        output_addresses,
+        user_data,
+        aws_address_record_type::AWS_ADDRESS_RECORD_TYPE_A,
+    )
+}
+
+/// SAFETY: not safe to call directly, only let the CRT call this function as a callback.
+unsafe extern "C" fn resolve_ipv6_only(
+    allocator: *mut aws_allocator,
+    host_name: *const aws_string,
+    output_addresses: *mut aws_array_list,
+    user_data: *mut libc::c_void,
+) -> libc::c_int {
+    resolve_record_type(
+        allocator,
+        host_name,
+        output_addresses,
+        user_data,
+        aws_address_record_type::AWS_ADDRESS_RECORD_TYPE_AAAA,
+    )
+}
+
+/// Resolve a host with the CRT's default resolver, and then drop the addresses that aren't of the
+/// given record type. Fails if none are left, like a resolution that finds no addresses.
+///
+/// SAFETY: the arguments must be the ones the CRT passed to a host resolution callback.
+unsafe fn resolve_record_type(
+    allocator: *mut aws_allocator,
+    host_name: *const aws_string,
+    output_addresses: *mut aws_array_list,
+    user_data: *mut libc::c_void,
+    record_type: aws_address_record_type::Type,
+) -> libc::c_int {
+    if aws_default_dns_resolve(allocator, host_name, output_addresses, user_data) != AWS_OP_SUCCESS {
+        return AWS_OP_ERR;
+    }
+
+    // SAFETY: the default resolver fills the list with `aws_host_address`es, which we own. We move
+    // the ones we keep to the front of the list and clean up the rest.
+    let list = &mut *output_addresses;
+    let addresses = list.data as *mut aws_host_address;
+    let mut kept = 0;
+    for i in 0..list.length {
+        let address = addresses.add(i);
+        if (*address).record_type == record_type {
+            if kept != i {
+                std::ptr::copy_nonoverlapping(address, addresses.add(kept), 1);
+            }
+            kept += 1;
+        } else {
+            aws_host_address_clean_up(address);
+        }
+    }
+    list.length = kept;
+
+    if kept == 0 {
+        return aws_raise_error(aws_io_errors::AWS_IO_DNS_NO_ADDRESS_FOR_HOST as i32);
+    }
+    AWS_OP_SUCCESS
+}
+
 /// A [HostResolver] is a tool for doing async DNS resolution and caching the results, including
 /// pooling multiple resolutions for a single hostname to enable load balancing and fanout.
 #[derive(Debug)]
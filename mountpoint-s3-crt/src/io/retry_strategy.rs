@@ -4,8 +4,9 @@ use std::ptr::NonNull;
 use std::time::Duration;
 
 use mountpoint_s3_crt_sys::{
-    aws_exponential_backoff_jitter_mode, aws_exponential_backoff_retry_options, aws_retry_strategy,
-    aws_retry_strategy_new_standard, aws_retry_strategy_release, aws_standard_retry_options,
+    aws_exponential_backoff_jitter_mode, aws_exponential_backoff_retry_options, aws_no_retry_options,
+    aws_retry_strategy, aws_retry_strategy_new_no_retry, aws_retry_strategy_new_standard, aws_retry_strategy_release,
+    aws_standard_retry_options,
 };
 
 use crate::common::allocator::Allocator;
@@ -31,6 +32,18 @@ impl RetryStrategy {
 
         Ok(Self { inner })
     }
+
+    /// Create a new retry strategy that never retries failed requests. The standard strategy can't
+    /// be configured that way, since it treats zero `max_retries` as its default.
+    pub fn no_retry(allocator: &Allocator) -> Result<Self, Error> {
+        // No shutdown callback, so the options hold nothing
+        let options: aws_no_retry_options = Default::default();
+
+        // Safety: the CRT copies what it needs from the options
+        let inner = unsafe { aws_retry_strategy_new_no_retry(allocator.inner.as_ptr(), &options).ok_or_last_error()? };
+
+        Ok(Self { inner })
+    }
 }
 
 impl Drop for RetryStrategy {
@@ -76,10 +89,13 @@ impl<'a> StandardRetryOptions<'a> {
 pub struct ExponentialBackoffRetryOptions<'a> {
     /// Event loop group to use for scheduling tasks
     pub event_loop_group: &'a mut EventLoopGroup,
-    /// Max retries to allow
+    /// Max retries to allow. Zero uses the CRT's default; use [RetryStrategy::no_retry] for a
+    /// strategy that doesn't retry. The CRT doesn't allow more than 63.
     pub max_retries: usize,
     /// Scaling factor to add for the backoff. Default is 25ms.
     pub backoff_scale_factor: Duration,
+    /// Maximum backoff between retries, in whole seconds. Default is 20s.
+    pub max_backoff: Duration,
     /// Jitter mode to use. Default is [ExponentialBackoffJitterMode::Full].
     pub jitter_mode: ExponentialBackoffJitterMode,
 }
@@ -92,6 +108,7 @@ impl<'a> ExponentialBackoffRetryOptions<'a> {
             // Defer to the CRT's defaults for everything else
             max_retries: 0,
             backoff_scale_factor: Duration::from_millis(0),
+            max_backoff: Duration::from_secs(0),
             jitter_mode: ExponentialBackoffJitterMode::Full,
         }
     }
@@ -101,6 +118,7 @@ impl<'a> ExponentialBackoffRetryOptions<'a> {
             el_group: self.event_loop_group.inner.as_ptr(),
             max_retries: self.max_retries,
             backoff_scale_factor_ms: self.backoff_scale_factor.as_millis().min(u32::MAX as u128) as u32,
+            max_backoff_secs: self.max_backoff.as_secs().min(u32::MAX as u64) as u32,
             jitter_mode: self.jitter_mode.into(),
             ..Default::default()
         }
@@ -135,3 +153,36 @@ impl From<ExponentialBackoffJitterMode> for aws_exponential_backoff_jitter_mode
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_retry_options() {
+        let allocator = Allocator::default();
+        let mut event_loop_group = EventLoopGroup::new_default(&allocator, None, || {}).unwrap();
+
+        let mut options = StandardRetryOptions::default(&mut event_loop_group);
+        options.backoff_retry_options.max_retries = 4;
+        options.backoff_retry_options.backoff_scale_factor = Duration::from_millis(200);
+        options.backoff_retry_options.max_backoff = Duration::from_secs(5);
+        options.backoff_retry_options.jitter_mode = ExponentialBackoffJitterMode::Decorrelated;
+
+        let inner = options.to_inner();
+        assert_eq!(inner.backoff_retry_options.max_retries, 4);
+        assert_eq!(inner.backoff_retry_options.backoff_scale_factor_ms, 200);
+        assert_eq!(inner.backoff_retry_options.max_backoff_secs, 5);
+        assert_eq!(
+            inner.backoff_retry_options.jitter_mode,
+            aws_exponential_backoff_jitter_mode::AWS_EXPONENTIAL_BACKOFF_JITTER_DECORRELATED
+        );
+
+        let _strategy = RetryStrategy::standard(&allocator, &options).expect("create retry strategy");
+    }
+
+    #[test]
+    fn no_retry() {
+        let _strategy = RetryStrategy::no_retry(&Allocator::default()).expect("create retry strategy");
+    }
+}
@@ -0,0 +1,64 @@
+//! Input streams for supplying the body of an HTTP request
+
+use std::ptr::NonNull;
+
+use mountpoint_s3_crt_sys::*;
+
+use crate::common::allocator::Allocator;
+use crate::common::byte_cursor::ByteCursor;
+use crate::common::error::Error;
+use crate::io::io_library_init;
+use crate::CrtError as _;
+
+/// An input stream that reads from an in-memory buffer, which it owns or shares (e.g. an
+/// `Arc<[u8]>`, so that the same bytes can be sent again by another request without a copy).
+pub struct InputStream {
+    /// The pointer to the inner `aws_input_stream`.
+    pub(crate) inner: NonNull<aws_input_stream>,
+    /// The buffer the stream reads from. It is boxed, so its bytes stay at the same address even
+    /// if this struct moves.
+    _buffer: Box<dyn AsRef<[u8]> + Send + Sync>,
+}
+
+impl std::fmt::Debug for InputStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputStream")
+            .field("inner", &self.inner)
+            .field("len", &(*self._buffer).as_ref().len())
+            .finish()
+    }
+}
+
+// SAFETY: `aws_input_stream` is reference counted, and we only read from the buffer.
+unsafe impl Send for InputStream {}
+// SAFETY: `aws_input_stream` is reference counted, and we only read from the buffer.
+unsafe impl Sync for InputStream {}
+
+impl InputStream {
+    /// Create a new [InputStream] that reads the given buffer from the start.
+    pub fn new_from_buffer(
+        allocator: &Allocator,
+        buffer: impl AsRef<[u8]> + Send + Sync + 'static,
+    ) -> Result<Self, Error> {
+        io_library_init(allocator);
+
+        let buffer: Box<dyn AsRef<[u8]> + Send + Sync> = Box::new(buffer);
+        let cursor = ByteCursor::new((*buffer).as_ref()).as_raw();
+        // SAFETY: the stream does not copy the buffer, but `_buffer` owns it and is only dropped
+        // after we release our reference to the stream. Anyone else holding a reference to the
+        // stream (e.g. an `aws_http_message`) must not outlive this struct.
+        let inner = unsafe { aws_input_stream_new_from_cursor(allocator.inner.as_ptr(), &cursor).ok_or_last_error()? };
+
+        Ok(Self { inner, _buffer: buffer })
+    }
+}
+
+impl Drop for InputStream {
+    fn drop(&mut self) {
+        // SAFETY: `self.inner` is a valid `aws_input_stream`, and on Drop it's safe to decrement
+        // the reference count since we won't use it again through `self.`
+        unsafe {
+            aws_input_stream_release(self.inner.as_ptr());
+        }
+    }
+}
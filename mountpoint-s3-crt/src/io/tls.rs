@@ -0,0 +1,235 @@
+//! TLS contexts and connection options
+
+use std::ffi::CString;
+use std::fmt::Debug;
+use std::os::unix::prelude::OsStrExt;
+use std::path::Path;
+use std::ptr::NonNull;
+
+use mountpoint_s3_crt_sys::{
+    aws_common_error, aws_tls_client_ctx_new, aws_tls_connection_options, aws_tls_connection_options_clean_up,
+    aws_tls_connection_options_init_from_ctx, aws_tls_connection_options_set_server_name, aws_tls_ctx,
+    aws_tls_ctx_options, aws_tls_ctx_options_clean_up, aws_tls_ctx_options_init_client_mtls_from_path,
+    aws_tls_ctx_options_init_default_client, aws_tls_ctx_options_override_default_trust_store_from_path,
+    aws_tls_ctx_options_set_verify_peer, aws_tls_ctx_release,
+};
+
+use crate::common::allocator::Allocator;
+use crate::common::byte_cursor::ByteCursor;
+use crate::common::error::Error;
+use crate::io::io_library_init;
+use crate::CrtError as _;
+
+/// A TLS context, which holds the configuration shared by many TLS connections
+#[derive(Debug)]
+pub struct TlsContext {
+    inner: NonNull<aws_tls_ctx>,
+}
+
+// SAFETY: `aws_tls_ctx` is reference counted and immutable once created.
+unsafe impl Send for TlsContext {}
+// SAFETY: `aws_tls_ctx` is reference counted and immutable once created.
+unsafe impl Sync for TlsContext {}
+
+impl TlsContext {
+    /// Create a new client TLS context with the default options, which verify peers against the
+    /// system's trust store.
+    pub fn new_client(allocator: &Allocator) -> Result<Self, Error> {
+        Self::new_client_with_options(allocator, &TlsContextOptions::new_client(allocator))
+    }
+
+    /// Create a new client TLS context with the given options.
+    pub fn new_client_with_options(allocator: &Allocator, options: &TlsContextOptions) -> Result<Self, Error> {
+        // SAFETY: `aws_tls_client_ctx_new` copies what it needs from the options.
+        let inner = unsafe { aws_tls_client_ctx_new(allocator.inner.as_ptr(), &options.inner).ok_or_last_error()? };
+
+        Ok(Self { inner })
+    }
+}
+
+impl Drop for TlsContext {
+    fn drop(&mut self) {
+        // SAFETY: `self.inner` is a valid `aws_tls_ctx`, and we're dropping our reference to it.
+        unsafe {
+            aws_tls_ctx_release(self.inner.as_ptr());
+        }
+    }
+}
+
+/// Options for creating a client [TlsContext]. Follows the builder pattern.
+pub struct TlsContextOptions {
+    inner: aws_tls_ctx_options,
+}
+
+// SAFETY: the options own their copies of the paths and certificates they refer to.
+unsafe impl Send for TlsContextOptions {}
+// SAFETY: the options are only mutated through `&mut self`.
+unsafe impl Sync for TlsContextOptions {}
+
+impl TlsContextOptions {
+    /// Create the default options for a client, which verify peers against the system's trust
+    /// store.
+    pub fn new_client(allocator: &Allocator) -> Self {
+        io_library_init(allocator);
+
+        let mut inner: aws_tls_ctx_options = Default::default();
+        // SAFETY: `inner` is cleaned up when the options are dropped.
+        unsafe { aws_tls_ctx_options_init_default_client(&mut inner, allocator.inner.as_ptr()) };
+        Self { inner }
+    }
+
+    /// Create options for a client that authenticates itself with the certificate and private
+    /// key in the given PEM files (mutual TLS). Fails if either file can't be read.
+    pub fn new_client_mtls_from_path(
+        allocator: &Allocator,
+        cert_path: &Path,
+        private_key_path: &Path,
+    ) -> Result<Self, Error> {
+        io_library_init(allocator);
+
+        let cert_path = path_to_cstring(cert_path)?;
+        let private_key_path = path_to_cstring(private_key_path)?;
+        let mut inner: aws_tls_ctx_options = Default::default();
+        // SAFETY: the CRT reads the files during this call and doesn't keep the path pointers. On
+        // failure, the CRT cleans up the options itself.
+        unsafe {
+            aws_tls_ctx_options_init_client_mtls_from_path(
+                &mut inner,
+                allocator.inner.as_ptr(),
+                cert_path.as_ptr(),
+                private_key_path.as_ptr(),
+            )
+            .ok_or_last_error()?;
+        }
+        Ok(Self { inner })
+    }
+
+    /// Verify peers against the certificate authorities in the given PEM file, instead of the
+    /// system's trust store. Fails if the file can't be read.
+    pub fn ca_file(&mut self, ca_file: &Path) -> Result<&mut Self, Error> {
+        let ca_file = path_to_cstring(ca_file)?;
+        // SAFETY: the CRT reads the file during this call and doesn't keep the path pointer.
+        unsafe {
+            aws_tls_ctx_options_override_default_trust_store_from_path(
+                &mut self.inner,
+                std::ptr::null(),
+                ca_file.as_ptr(),
+            )
+            .ok_or_last_error()?;
+        }
+        Ok(self)
+    }
+
+    /// Set whether to verify the peer's certificate. Disabling verification is insecure, since it
+    /// allows anyone to impersonate the peer, and should only be used for testing.
+    pub fn verify_peer(&mut self, verify_peer: bool) -> &mut Self {
+        // SAFETY: `self.inner` is a valid, initialized `aws_tls_ctx_options`.
+        unsafe { aws_tls_ctx_options_set_verify_peer(&mut self.inner, verify_peer) };
+        self
+    }
+}
+
+impl Debug for TlsContextOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsContextOptions")
+            .field("verify_peer", &self.inner.verify_peer)
+            .finish()
+    }
+}
+
+impl Drop for TlsContextOptions {
+    fn drop(&mut self) {
+        // SAFETY: `self.inner` was initialized when the options were created, and we're dropping
+        // it.
+        unsafe {
+            aws_tls_ctx_options_clean_up(&mut self.inner);
+        }
+    }
+}
+
+/// Convert a path to a C string for the CRT, which can't represent paths containing nul bytes.
+fn path_to_cstring(path: &Path) -> Result<CString, Error> {
+    CString::new(path.as_os_str().as_bytes()).map_err(|_| (aws_common_error::AWS_ERROR_INVALID_ARGUMENT as i32).into())
+}
+
+/// Options for a single TLS connection
+pub struct TlsConnectionOptions {
+    pub(crate) inner: aws_tls_connection_options,
+}
+
+// SAFETY: the connection options own their copies of the server name and TLS context.
+unsafe impl Send for TlsConnectionOptions {}
+// SAFETY: the connection options are immutable once created.
+unsafe impl Sync for TlsConnectionOptions {}
+
+impl TlsConnectionOptions {
+    /// Create options for a connection using the given TLS context, verifying that the peer's
+    /// certificate is valid for `server_name`.
+    pub fn new(allocator: &Allocator, context: &TlsContext, server_name: &str) -> Result<Self, Error> {
+        let mut options = Self::from_context(context);
+        let server_name = ByteCursor::new(server_name).as_raw();
+        // SAFETY: `aws_tls_connection_options_set_server_name` copies the server name.
+        unsafe {
+            aws_tls_connection_options_set_server_name(&mut options.inner, allocator.inner.as_ptr(), &server_name)
+                .ok_or_last_error()?;
+        }
+        Ok(options)
+    }
+
+    /// Create options for connections using the given TLS context, without a server name. This is
+    /// for connections whose server name is set by the CRT, like those of the S3 client, which
+    /// uses the endpoint it connects to.
+    pub fn from_context(context: &TlsContext) -> Self {
+        let mut inner: aws_tls_connection_options = Default::default();
+        // SAFETY: `aws_tls_connection_options_init_from_ctx` acquires a reference to the context.
+        // The options are cleaned up when dropped.
+        unsafe { aws_tls_connection_options_init_from_ctx(&mut inner, context.inner.as_ptr()) };
+        Self { inner }
+    }
+}
+
+impl Debug for TlsConnectionOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsConnectionOptions").finish()
+    }
+}
+
+impl Drop for TlsConnectionOptions {
+    fn drop(&mut self) {
+        // SAFETY: `self.inner` was initialized in `new`, and we're dropping it.
+        unsafe {
+            aws_tls_connection_options_clean_up(&mut self.inner);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ca_file_must_exist() {
+        let allocator = Allocator::default();
+        let mut options = TlsContextOptions::new_client(&allocator);
+        options
+            .ca_file(Path::new("/nonexistent/ca-bundle.pem"))
+            .expect_err("missing CA file should fail");
+    }
+
+    #[test]
+    fn insecure_tls_context() {
+        let allocator = Allocator::default();
+        let mut options = TlsContextOptions::new_client(&allocator);
+        options.verify_peer(false);
+        let context = TlsContext::new_client_with_options(&allocator, &options).expect("create TLS context");
+        let _options = TlsConnectionOptions::from_context(&context);
+    }
+
+    #[test]
+    fn new_tls_connection_options() {
+        let allocator = Allocator::default();
+        let context = TlsContext::new_client(&allocator).expect("create TLS context");
+        let _options = TlsConnectionOptions::new(&allocator, &context, "proxy.example.com")
+            .expect("create TLS connection options");
+    }
+}
@@ -16,6 +16,23 @@ use std::{ffi::OsStr, os::unix::prelude::OsStrExt};
 
 use crate::common::error::Error;
 
+/// The version of the `aws-c-s3` library this crate links to, such as `0.6.0`, for logging and bug
+/// reports. Returns `None` if the version is unknown, which is the case when linking to prebuilt
+/// CRT libraries instead of the ones embedded in `mountpoint-s3-crt-sys`. See
+/// [crt_library_versions] for the versions of the other CRT libraries.
+pub fn crt_version() -> Option<&'static str> {
+    crt_library_versions()
+        .iter()
+        .find(|(name, _)| *name == "aws-c-s3")
+        .and_then(|(_, version)| *version)
+}
+
+/// The versions of the CRT libraries this crate links to, by library name, in order of their
+/// dependencies. See [crt_version].
+pub fn crt_library_versions() -> &'static [(&'static str, Option<&'static str>)] {
+    CRT_LIBRARY_VERSIONS
+}
+
 pub(crate) mod private {
     /// Seals a trait to prevent clients from implementing it for their own types, since this trait
     /// is only accessible to this crate.
@@ -144,6 +161,24 @@ mod test {
         crate::s3::s3_library_init(&crate::common::allocator::Allocator::default());
     }
 
+    #[test]
+    fn crt_versions_cover_aws_c_s3() {
+        let versions = super::crt_library_versions();
+        assert!(versions.iter().any(|(name, _)| *name == "aws-c-s3"));
+        assert!(versions.iter().any(|(name, _)| *name == "aws-c-common"));
+        if mountpoint_s3_crt_sys::CRT_BUILT_FROM_SOURCE {
+            assert!(
+                versions.iter().all(|(_, version)| version.is_some()),
+                "missing versions {versions:?}"
+            );
+            let version = super::crt_version().expect("aws-c-s3 version should be known");
+            assert!(
+                !version.is_empty() && !version.contains('\n'),
+                "bad version {version:?}"
+            );
+        }
+    }
+
     /// Validate that ASan is working across both Rust and the CRT by intentionally provoking a
     /// use-after-free that crosses the boundary: the allocation is created and freed by Rust, but
     /// accessed by the CRT. Ignored by default, and run only by ASan in CI.
@@ -3,12 +3,15 @@
 use crate::auth::credentials::CredentialsProvider;
 use crate::auth::signing_config::{SigningAlgorithm, SigningConfig, SigningConfigInner};
 use crate::common::allocator::Allocator;
+use crate::common::byte_cursor::ByteCursor;
 use crate::common::error::Error;
 use crate::common::thread::ThreadId;
 use crate::common::uri::Uri;
+use crate::http::proxy::ProxyOptions;
 use crate::http::request_response::{Headers, Message};
 use crate::io::channel_bootstrap::ClientBootstrap;
 use crate::io::retry_strategy::RetryStrategy;
+use crate::io::tls::TlsConnectionOptions;
 use crate::s3::s3_library_init;
 use crate::{aws_byte_cursor_as_slice, CrtError, ResultExt, ToAwsByteCursor};
 use futures::Future;
@@ -61,6 +64,25 @@ pub struct ClientConfig {
 
     /// The region
     region: Option<String>,
+
+    /// The proxy to connect to S3 through. The CRT copies these options when the client is
+    /// created, but we hold onto them until then since the config points to them.
+    proxy_options: Option<ProxyOptions>,
+
+    /// The TLS options for connections to S3. The CRT copies these options when the client is
+    /// created, but we hold onto them until then since the config points to them. They're boxed
+    /// so that they don't move when the config does.
+    tls_connection_options: Option<Box<TlsConnectionOptions>>,
+
+    /// The names of the network interfaces to bind connections to. The config points to the byte
+    /// cursors, which point into these strings, so we hold onto both.
+    network_interface_names: Vec<String>,
+    network_interface_name_cursors: Vec<aws_byte_cursor>,
+
+    /// The TCP keepalive options for connections to S3. The CRT copies these options when the
+    /// client is created, but we hold onto them until then since the config points to them.
+    /// They're boxed so that they don't move when the config does.
+    tcp_keep_alive_options: Option<Box<aws_s3_tcp_keep_alive_options>>,
 }
 
 impl ClientConfig {
@@ -125,12 +147,26 @@ impl ClientConfig {
         self
     }
 
+    /// Size in bytes at which uploads switch from a single PutObject request to a multi-part
+    /// upload. If zero, the CRT uses the part size.
+    pub fn multipart_upload_threshold(&mut self, multipart_upload_threshold: usize) -> &mut Self {
+        self.inner.multipart_upload_threshold = multipart_upload_threshold as u64;
+        self
+    }
+
     /// If the part size needs to be adjusted for service limits, this is the maximum size it will be adjusted to.
     pub fn max_part_size(&mut self, max_part_size: usize) -> &mut Self {
         self.inner.max_part_size = max_part_size as u64;
         self
     }
 
+    /// Limit in bytes on the memory the client uses for buffering parts. If zero, the CRT chooses a
+    /// limit based on the target throughput.
+    pub fn memory_limit_in_bytes(&mut self, memory_limit_in_bytes: u64) -> &mut Self {
+        self.inner.memory_limit_in_bytes = memory_limit_in_bytes;
+        self
+    }
+
     /// Throughput target in Gbps that we are trying to reach.
     pub fn throughput_target_gbps(&mut self, throughput_target_gbps: f64) -> &mut Self {
         self.inner.throughput_target_gbps = throughput_target_gbps;
@@ -143,6 +179,101 @@ impl ClientConfig {
         self.inner.max_active_connections_override = max_active_connections_override;
         self
     }
+
+    /// Timeout for establishing a connection to S3, in milliseconds, which the CRT sets as the
+    /// `connect_timeout_ms` of the socket options for every connection. It only covers the TCP
+    /// connect, not the TLS handshake or the requests sent on the connection. If zero, the CRT's
+    /// default of 3 seconds is used.
+    pub fn connect_timeout_ms(&mut self, connect_timeout_ms: u32) -> &mut Self {
+        self.inner.connect_timeout_ms = connect_timeout_ms;
+        self
+    }
+
+    /// Connect to S3 through the given HTTP proxy.
+    pub fn proxy_options(&mut self, proxy_options: ProxyOptions) -> &mut Self {
+        self.inner.proxy_options = proxy_options.to_inner_ptr() as *mut aws_http_proxy_options;
+        self.proxy_options = Some(proxy_options);
+        self
+    }
+
+    /// Connect to S3 with the given TLS options rather than the CRT's defaults. The CRT sets the
+    /// server name of each connection to the endpoint it connects to.
+    pub fn tls_connection_options(&mut self, tls_connection_options: TlsConnectionOptions) -> &mut Self {
+        self.tls_connection_options = Some(Box::new(tls_connection_options));
+        self.inner.tls_mode = aws_s3_meta_request_tls_mode::AWS_MR_TLS_ENABLED;
+        self.inner.tls_connection_options = &mut self.tls_connection_options.as_mut().unwrap().inner;
+        self
+    }
+
+    /// Bind connections to S3 to the network interfaces with the given names, for example `eth1`.
+    /// The CRT spreads connections across the interfaces round-robin. If empty, connections use
+    /// the interface that the operating system chooses.
+    ///
+    /// Older versions of the CRT can't bind connections to interfaces, and ignore the names; check
+    /// [supports_network_interface_names](Self::supports_network_interface_names) first.
+    pub fn network_interface_names(&mut self, network_interface_names: Vec<String>) -> &mut Self {
+        self.network_interface_names = network_interface_names;
+        self.network_interface_name_cursors = self
+            .network_interface_names
+            .iter()
+            // The strings are not mutated further and live as long as the `ClientConfig`, which
+            // outlives the client
+            .map(|name| ByteCursor::new(name).as_raw())
+            .collect();
+        #[cfg(crt_network_interface_names)]
+        {
+            self.inner.network_interface_names_array = self.network_interface_name_cursors.as_ptr();
+            self.inner.num_network_interface_names = self.network_interface_name_cursors.len();
+        }
+        self
+    }
+
+    /// Whether the CRT this crate was built with can bind connections to network interfaces (see
+    /// [network_interface_names](Self::network_interface_names)).
+    pub const fn supports_network_interface_names() -> bool {
+        cfg!(crt_network_interface_names)
+    }
+
+    /// Enable TCP keepalive on connections to S3 with the given options, or disable it if `None`.
+    /// The CRT doesn't enable keepalive unless this is set.
+    pub fn tcp_keep_alive_options(&mut self, tcp_keep_alive_options: Option<TcpKeepAliveOptions>) -> &mut Self {
+        self.tcp_keep_alive_options = tcp_keep_alive_options.map(|options| {
+            Box::new(aws_s3_tcp_keep_alive_options {
+                keep_alive_interval_sec: options.keep_alive_interval_sec,
+                keep_alive_timeout_sec: options.keep_alive_timeout_sec,
+                keep_alive_max_failed_probes: options.keep_alive_max_failed_probes,
+            })
+        });
+        self.inner.tcp_keep_alive_options = match &mut self.tcp_keep_alive_options {
+            Some(options) => options.as_mut() as *mut _,
+            None => std::ptr::null_mut(),
+        };
+        self
+    }
+}
+
+/// TCP keepalive options for the connections a [Client] makes, which the CRT sets in the
+/// `aws_socket_options` of each connection. Keepalive probes stop NATs and load balancers with a
+/// short idle timeout from silently dropping pooled connections, and detect connections that were
+/// dropped anyway.
+///
+/// The default options enable keepalive with the operating system's default timings, which on
+/// Linux only start probing after a connection has been idle for 2 hours. For a NAT gateway, set
+/// the interval below the gateway's idle timeout (350 seconds for AWS NAT gateways).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TcpKeepAliveOptions {
+    /// How long a connection is idle before the first keepalive probe, in seconds. Only applied if
+    /// `keep_alive_timeout_sec` is also non-zero; otherwise the operating system's default is used.
+    pub keep_alive_interval_sec: u16,
+
+    /// How long to wait between keepalive probes that aren't acknowledged, in seconds. Only applied
+    /// if `keep_alive_interval_sec` is also non-zero; otherwise the operating system's default is
+    /// used.
+    pub keep_alive_timeout_sec: u16,
+
+    /// How many unacknowledged probes to send before closing the connection. If zero, the operating
+    /// system's default is used.
+    pub keep_alive_max_failed_probes: u16,
 }
 
 /// Callback for telemetry received as part of a successful meta request.
@@ -193,6 +324,9 @@ struct MetaRequestOptionsInner {
     /// Finish callback, if provided (and not already called, since it's FnOnce).
     on_finish: Option<FinishCallback>,
 
+    /// Metrics aggregated over the requests made so far, reported in the [MetaRequestResult].
+    metrics: MetaRequestMetrics,
+
     /// Pin this struct because inner.user_data will be a pointer to this object.
     _pinned: PhantomPinned,
 }
@@ -262,6 +396,7 @@ impl MetaRequestOptions {
             on_body: None,
             on_upload_review: None,
             on_finish: None,
+            metrics: Default::default(),
             _pinned: Default::default(),
         });
 
@@ -343,6 +478,10 @@ impl MetaRequestOptions {
     }
 
     /// Provide a callback to run when the request's body arrives.
+    ///
+    /// The callback gets each part of the body as a slice of the CRT's own buffer, without a copy.
+    /// The CRT releases the buffer once the callback returns, so callbacks that need the data
+    /// afterwards must copy it.
     pub fn on_body(&mut self, callback: impl FnMut(u64, &[u8]) + Send + 'static) -> &mut Self {
         // SAFETY: we aren't moving out of the struct.
         let options = unsafe { Pin::get_unchecked_mut(Pin::as_mut(&mut self.0)) };
@@ -433,9 +572,11 @@ unsafe extern "C" fn meta_request_telemetry_callback(
     // in MetaRequestOptions::new.
     let user_data = MetaRequestOptionsInner::from_user_data_ptr(user_data);
 
+    let metrics = NonNull::new(metrics).expect("request metrics is never null");
+    let metrics = RequestMetrics { inner: metrics };
+    user_data.metrics.record(&metrics);
+
     if let Some(callback) = user_data.on_telemetry.as_ref() {
-        let metrics = NonNull::new(metrics).expect("request metrics is never null");
-        let metrics = RequestMetrics { inner: metrics };
         // The docs say "`metrics` is only valid for the duration of the callback", so we need to
         // pass a reference here.
         callback(&metrics);
@@ -473,8 +614,10 @@ unsafe extern "C" fn meta_request_receive_body_callback(
     // in MetaRequestOptions::new.
     let user_data = MetaRequestOptionsInner::from_user_data_ptr(user_data);
 
+    let slice: &[u8] = aws_byte_cursor_as_slice(&*body);
+    user_data.metrics.bytes_received += slice.len() as u64;
+
     if let Some(callback) = user_data.on_body.as_mut() {
-        let slice: &[u8] = aws_byte_cursor_as_slice(&*body);
         callback(range_start, slice);
     }
 
@@ -495,7 +638,9 @@ unsafe extern "C" fn meta_request_finish_callback(
 
     // take ownership of the callback, since it can only be called once.
     if let Some(callback) = user_data.on_finish.take() {
-        callback(MetaRequestResult::from_crt_result(result));
+        let mut result = MetaRequestResult::from_crt_result(result);
+        result.metrics = std::mem::take(&mut user_data.metrics).finish(result.is_err());
+        callback(result);
     }
 }
 
@@ -739,6 +884,21 @@ impl ClientMetrics {
     }
 }
 
+/// Connection pool stats, summed over the connection managers for every endpoint the client has
+/// connected to
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConnectionStats {
+    /// Number of connections currently open, whether in use or idle.
+    pub open: u32,
+
+    /// Number of open connections not currently in use by a request.
+    pub idle: u32,
+
+    /// Number of requests waiting to acquire a connection.
+    pub pending_acquisitions: u32,
+}
+
 /// S3 buffer pool usage stats
 #[derive(Debug)]
 pub struct BufferPoolUsageStats {
@@ -892,6 +1052,30 @@ impl Client {
         }
     }
 
+    /// Poll [ConnectionStats] from the connection managers of the underlying CRT client.
+    pub fn poll_connection_stats(&self) -> ConnectionStats {
+        let mut stats = ConnectionStats::default();
+        let client = self.inner.as_ptr();
+        // SAFETY: The `aws_s3_client` in `self.inner` is guaranteed to be initialized and
+        // dereferencable as long as Client lives. We hold the client's lock while iterating over its
+        // endpoints, which keeps each endpoint and its connection manager alive.
+        unsafe {
+            aws_s3_client_lock_synced_data(client);
+            let mut iter = aws_hash_iter_begin(&(*client).synced_data.endpoints);
+            while !aws_hash_iter_done(&iter) {
+                let endpoint = iter.element.value as *const aws_s3_endpoint;
+                let mut metrics: aws_http_manager_metrics = Default::default();
+                aws_http_connection_manager_fetch_metrics((*endpoint).http_connection_manager, &mut metrics);
+                stats.idle += metrics.available_concurrency as u32;
+                stats.open += (metrics.available_concurrency + metrics.leased_concurrency) as u32;
+                stats.pending_acquisitions += metrics.pending_concurrency_acquires as u32;
+                aws_hash_iter_next(&mut iter);
+            }
+            aws_s3_client_unlock_synced_data(client);
+        }
+        stats
+    }
+
     fn get_num_requests_network_io(client: &aws_s3_client, meta_request_type: aws_s3_meta_request_type) -> u32 {
         let mut num_requests_network_io: u32 = 0;
         if meta_request_type == aws_s3_meta_request_type::AWS_S3_META_REQUEST_TYPE_MAX {
@@ -935,6 +1119,12 @@ pub struct MetaRequestResult {
 
     /// Error HTTP response, if present.
     pub error_response_body: Option<OsString>,
+
+    /// The checksum algorithm used to validate the response, if it was validated.
+    pub validation_algorithm: Option<ChecksumAlgorithm>,
+
+    /// Metrics aggregated over all the requests the meta request made.
+    pub metrics: MetaRequestMetrics,
 }
 
 impl MetaRequestResult {
@@ -948,6 +1138,12 @@ impl MetaRequestResult {
         self.crt_error.raw_error() == mountpoint_s3_crt_sys::aws_s3_errors::AWS_ERROR_S3_CANCELED as i32
     }
 
+    /// Return whether this request failed because the response did not match its checksum.
+    pub fn is_checksum_mismatch(&self) -> bool {
+        self.crt_error.raw_error()
+            == mountpoint_s3_crt_sys::aws_s3_errors::AWS_ERROR_S3_RESPONSE_CHECKSUM_MISMATCH as i32
+    }
+
     /// Convert the CRT's meta request result struct into a safe, owned result.
     /// SAFETY: This copies from the raw pointer inside of the request result, so only call on
     /// results given to us from the CRT.
@@ -963,15 +1159,122 @@ impl MetaRequestResult {
             OsStr::from_bytes(slice).to_owned()
         });
 
+        let validation_algorithm = if inner.did_validate {
+            ChecksumAlgorithm::from_aws_s3_checksum_algorithm(inner.validation_algorithm)
+        } else {
+            None
+        };
+
         Self {
             response_status: inner.response_status,
             crt_error: inner.error_code.into(),
             error_response_headers,
             error_response_body,
+            validation_algorithm,
+            metrics: Default::default(),
         }
     }
 }
 
+/// Metrics for a meta request, aggregated over all the requests it made to S3.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MetaRequestMetrics {
+    /// Request ID of the last request to finish, if available.
+    pub request_id: Option<String>,
+
+    /// HTTP status of the last request to finish, if available.
+    pub http_status: Option<i32>,
+
+    /// Number of requests made to S3, including retries.
+    pub num_requests: u32,
+
+    /// Number of requests that failed and were retried.
+    pub retry_count: u32,
+
+    /// Time to first byte of the first request to receive a response.
+    pub time_to_first_byte: Option<Duration>,
+
+    /// Time from the start of the first request to the end of the last one.
+    pub total_duration: Duration,
+
+    /// Number of response body bytes received.
+    pub bytes_received: u64,
+
+    /// Breakdown of the time the last request to finish spent in each phase, if available.
+    pub last_request_timings: Option<RequestTimings>,
+
+    num_failed_requests: u32,
+    first_start_timestamp_ns: Option<u64>,
+    last_end_timestamp_ns: Option<u64>,
+}
+
+impl MetaRequestMetrics {
+    /// Add the metrics of a finished request.
+    fn record(&mut self, metrics: &RequestMetrics) {
+        self.num_requests += 1;
+        if let Some(request_id) = metrics.request_id() {
+            self.request_id = Some(request_id);
+        }
+        if let Some(http_status) = metrics.status_code() {
+            self.http_status = Some(http_status);
+        }
+        let failed = metrics.error().is_err() || metrics.status_code().is_some_and(|s| !(200..300).contains(&s));
+        if failed && !metrics.is_canceled() {
+            self.num_failed_requests += 1;
+        }
+        if self.time_to_first_byte.is_none() {
+            self.time_to_first_byte = metrics.time_to_first_byte();
+        }
+        self.last_request_timings = Some(metrics.timings());
+
+        let start = metrics.start_timestamp_ns();
+        let end = metrics.end_timestamp_ns();
+        self.first_start_timestamp_ns = Some(self.first_start_timestamp_ns.map_or(start, |s| s.min(start)));
+        self.last_end_timestamp_ns = Some(self.last_end_timestamp_ns.map_or(end, |e| e.max(end)));
+        self.total_duration = Duration::from_nanos(
+            self.last_end_timestamp_ns
+                .unwrap_or_default()
+                .saturating_sub(self.first_start_timestamp_ns.unwrap_or_default()),
+        );
+    }
+
+    /// Finalize the metrics once the meta request finishes. If it failed, the last failed request
+    /// ended it rather than being retried.
+    fn finish(mut self, meta_request_failed: bool) -> Self {
+        self.retry_count = if meta_request_failed {
+            self.num_failed_requests.saturating_sub(1)
+        } else {
+            self.num_failed_requests
+        };
+        self
+    }
+}
+
+/// Breakdown of the time an individual request spent in each phase.
+///
+/// The CRT doesn't report DNS resolution, TCP connect, or TLS handshake times, since requests
+/// acquire connections from a pool and most reuse a connection that's already established. Time
+/// spent waiting for a connection is included in `total_duration` but not in any of the phases.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RequestTimings {
+    /// Time spent sending the request, from when it started to be encoded to when its last byte
+    /// was sent, or None if the request failed before sending.
+    pub send_duration: Option<Duration>,
+
+    /// Time from when the last byte of the request was sent to when the first byte of the response
+    /// was received, or None if no response was received.
+    pub time_to_first_byte: Option<Duration>,
+
+    /// Time spent receiving the response, from its first byte to its last, or None if the response
+    /// wasn't fully received.
+    pub receive_duration: Option<Duration>,
+
+    /// Total duration of the request.
+    pub total_duration: Duration,
+}
+
 /// Metrics for an individual request
 pub struct RequestMetrics {
     inner: NonNull<aws_s3_request_metrics>,
@@ -1230,6 +1533,18 @@ impl RequestMetrics {
         Some(Duration::from_nanos(receive_start.saturating_sub(send_end)))
     }
 
+    /// Return the breakdown of the time this request spent in each phase
+    pub fn timings(&self) -> RequestTimings {
+        let duration_between =
+            |start: Option<u64>, end: Option<u64>| Some(Duration::from_nanos(end?.saturating_sub(start?)));
+        RequestTimings {
+            send_duration: duration_between(self.send_start_timestamp_ns(), self.send_end_timestamp_ns()),
+            time_to_first_byte: self.time_to_first_byte(),
+            receive_duration: duration_between(self.receive_start_timestamp_ns(), self.receive_end_timestamp_ns()),
+            total_duration: self.total_duration(),
+        }
+    }
+
     /// Return whether the request was canceled according to its error code
     pub fn is_canceled(&self) -> bool {
         self.error().raw_error() == mountpoint_s3_crt_sys::aws_s3_errors::AWS_ERROR_S3_CANCELED as i32
@@ -1335,21 +1650,63 @@ pub struct ChecksumConfig {
 impl ChecksumConfig {
     /// Create a [ChecksumConfig] enabling Crc32c trailing checksums in PUT requests.
     pub fn trailing_crc32c() -> Self {
+        Self::with_algorithm(
+            aws_s3_checksum_location::AWS_SCL_TRAILER,
+            aws_s3_checksum_algorithm::AWS_SCA_CRC32C,
+        )
+    }
+
+    /// Create a [ChecksumConfig] enabling trailing checksums with the given algorithm in PUT
+    /// requests. For multi-part uploads, the CRT computes a checksum for each part and S3 combines
+    /// them into the checksum of the object. Fails if the CRT this crate was built with doesn't
+    /// support the algorithm (see [ChecksumAlgorithm::is_supported]).
+    pub fn trailing(algorithm: ChecksumAlgorithm) -> Result<Self, UnsupportedChecksumAlgorithm> {
+        Ok(Self::with_algorithm(
+            aws_s3_checksum_location::AWS_SCL_TRAILER,
+            algorithm.to_aws_s3_checksum_algorithm()?,
+        ))
+    }
+
+    /// Create a [ChecksumConfig] enabling Crc32c trailing checksums only for upload review.
+    pub fn upload_review_crc32c() -> Self {
+        Self::with_algorithm(
+            aws_s3_checksum_location::AWS_SCL_NONE,
+            aws_s3_checksum_algorithm::AWS_SCA_CRC32C,
+        )
+    }
+
+    /// Create a [ChecksumConfig] enabling checksums with the given algorithm only for upload
+    /// review. The checksums are computed but not sent to S3. Fails if the CRT this crate was
+    /// built with doesn't support the algorithm (see [ChecksumAlgorithm::is_supported]).
+    pub fn upload_review(algorithm: ChecksumAlgorithm) -> Result<Self, UnsupportedChecksumAlgorithm> {
+        Ok(Self::with_algorithm(
+            aws_s3_checksum_location::AWS_SCL_NONE,
+            algorithm.to_aws_s3_checksum_algorithm()?,
+        ))
+    }
+
+    fn with_algorithm(location: aws_s3_checksum_location, checksum_algorithm: aws_s3_checksum_algorithm) -> Self {
         Self {
             inner: aws_s3_checksum_config {
-                location: aws_s3_checksum_location::AWS_SCL_TRAILER,
-                checksum_algorithm: aws_s3_checksum_algorithm::AWS_SCA_CRC32C,
+                location,
+                checksum_algorithm,
                 ..Default::default()
             },
         }
     }
 
-    /// Create a [ChecksumConfig] enabling Crc32c trailing checksums only for upload review.
-    pub fn upload_review_crc32c() -> Self {
+    /// The algorithm of the checksums this config computes, if any.
+    pub fn algorithm(&self) -> Option<ChecksumAlgorithm> {
+        ChecksumAlgorithm::from_aws_s3_checksum_algorithm(self.inner.checksum_algorithm)
+    }
+
+    /// Create a [ChecksumConfig] that validates the checksum of GET responses, for objects that
+    /// were uploaded with a checksum.
+    pub fn validate_response() -> Self {
         Self {
             inner: aws_s3_checksum_config {
                 location: aws_s3_checksum_location::AWS_SCL_NONE,
-                checksum_algorithm: aws_s3_checksum_algorithm::AWS_SCA_CRC32C,
+                validate_response_checksum: true,
                 ..Default::default()
             },
         }
@@ -1372,9 +1729,19 @@ pub enum ChecksumAlgorithm {
     Sha1,
     /// Sha256 checksum.
     Sha256,
+    /// Crc64nvme checksum. Only supported by later versions of the CRT.
+    Crc64nvme,
 }
 
 impl ChecksumAlgorithm {
+    /// Whether the CRT this crate was built with can compute checksums with this algorithm.
+    pub const fn is_supported(self) -> bool {
+        match self {
+            ChecksumAlgorithm::Crc64nvme => cfg!(crt_crc64nvme),
+            _ => true,
+        }
+    }
+
     fn from_aws_s3_checksum_algorithm(algorithm: aws_s3_checksum_algorithm) -> Option<Self> {
         match algorithm {
             aws_s3_checksum_algorithm::AWS_SCA_NONE => None,
@@ -1382,11 +1749,31 @@ impl ChecksumAlgorithm {
             aws_s3_checksum_algorithm::AWS_SCA_CRC32 => Some(ChecksumAlgorithm::Crc32),
             aws_s3_checksum_algorithm::AWS_SCA_SHA1 => Some(ChecksumAlgorithm::Sha1),
             aws_s3_checksum_algorithm::AWS_SCA_SHA256 => Some(ChecksumAlgorithm::Sha256),
+            #[cfg(crt_crc64nvme)]
+            aws_s3_checksum_algorithm::AWS_SCA_CRC64NVME => Some(ChecksumAlgorithm::Crc64nvme),
             _ => unreachable!("unknown aws_s3_checksum_algorithm"),
         }
     }
+
+    fn to_aws_s3_checksum_algorithm(self) -> Result<aws_s3_checksum_algorithm, UnsupportedChecksumAlgorithm> {
+        match self {
+            ChecksumAlgorithm::Crc32c => Ok(aws_s3_checksum_algorithm::AWS_SCA_CRC32C),
+            ChecksumAlgorithm::Crc32 => Ok(aws_s3_checksum_algorithm::AWS_SCA_CRC32),
+            ChecksumAlgorithm::Sha1 => Ok(aws_s3_checksum_algorithm::AWS_SCA_SHA1),
+            ChecksumAlgorithm::Sha256 => Ok(aws_s3_checksum_algorithm::AWS_SCA_SHA256),
+            #[cfg(crt_crc64nvme)]
+            ChecksumAlgorithm::Crc64nvme => Ok(aws_s3_checksum_algorithm::AWS_SCA_CRC64NVME),
+            #[cfg(not(crt_crc64nvme))]
+            ChecksumAlgorithm::Crc64nvme => Err(UnsupportedChecksumAlgorithm(self)),
+        }
+    }
 }
 
+/// The CRT this crate was built with can't compute checksums with this algorithm.
+#[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+#[error("the CRT this crate was built with doesn't support {0:?} checksums")]
+pub struct UnsupportedChecksumAlgorithm(pub ChecksumAlgorithm);
+
 /// Info for the caller to review before an upload completes.
 #[derive(Debug)]
 pub struct UploadReview {
@@ -1443,8 +1830,8 @@ impl UploadReviewPart {
 mod tests {
     use test_case::test_case;
 
-    use crate::aws_s3_request_type;
-    use crate::s3::client::RequestType;
+    use crate::s3::client::{ChecksumAlgorithm, ChecksumConfig, ClientConfig, MetaRequestMetrics, RequestType};
+    use crate::{aws_s3_checksum_location, aws_s3_request_type};
 
     #[test_case(aws_s3_request_type::AWS_S3_REQUEST_TYPE_UNKNOWN, RequestType::Unknown)]
     #[test_case(aws_s3_request_type::AWS_S3_REQUEST_TYPE_HEAD_OBJECT, RequestType::HeadObject)]
@@ -1453,4 +1840,66 @@ mod tests {
         // Simple, but was previously broken.
         assert_eq!(expected_request_type, RequestType::from(c_request_type));
     }
+
+    #[test_case(0, false, 0; "no failures")]
+    #[test_case(2, false, 2; "retried then succeeded")]
+    #[test_case(3, true, 2; "retried then failed")]
+    #[test_case(0, true, 0; "failed before any request")]
+    fn meta_request_metrics_retry_count(num_failed_requests: u32, meta_request_failed: bool, expected: u32) {
+        let metrics = MetaRequestMetrics {
+            num_failed_requests,
+            ..Default::default()
+        };
+        assert_eq!(metrics.finish(meta_request_failed).retry_count, expected);
+    }
+
+    #[test_case(ChecksumAlgorithm::Crc32c)]
+    #[test_case(ChecksumAlgorithm::Crc32)]
+    #[test_case(ChecksumAlgorithm::Sha1)]
+    #[test_case(ChecksumAlgorithm::Sha256)]
+    #[cfg_attr(crt_crc64nvme, test_case(ChecksumAlgorithm::Crc64nvme))]
+    fn checksum_config_algorithm(algorithm: ChecksumAlgorithm) {
+        let trailing = ChecksumConfig::trailing(algorithm).expect("algorithm should be supported");
+        assert_eq!(trailing.algorithm(), Some(algorithm));
+        assert!(matches!(
+            trailing.inner.location,
+            aws_s3_checksum_location::AWS_SCL_TRAILER
+        ));
+
+        let review = ChecksumConfig::upload_review(algorithm).expect("algorithm should be supported");
+        assert_eq!(review.algorithm(), Some(algorithm));
+        assert!(matches!(review.inner.location, aws_s3_checksum_location::AWS_SCL_NONE));
+
+        assert_eq!(ChecksumConfig::validate_response().algorithm(), None);
+    }
+
+    #[test]
+    fn checksum_config_unsupported_algorithm() {
+        let algorithm = ChecksumAlgorithm::Crc64nvme;
+        let trailing = ChecksumConfig::trailing(algorithm);
+        let review = ChecksumConfig::upload_review(algorithm);
+        if algorithm.is_supported() {
+            assert_eq!(trailing.unwrap().algorithm(), Some(algorithm));
+            assert_eq!(review.unwrap().algorithm(), Some(algorithm));
+        } else {
+            assert_eq!(trailing.unwrap_err(), UnsupportedChecksumAlgorithm(algorithm));
+            assert_eq!(review.unwrap_err(), UnsupportedChecksumAlgorithm(algorithm));
+        }
+        assert_eq!(
+            ChecksumConfig::trailing_crc32c().algorithm(),
+            Some(ChecksumAlgorithm::Crc32c)
+        );
+        assert_eq!(
+            ChecksumConfig::upload_review_crc32c().algorithm(),
+            Some(ChecksumAlgorithm::Crc32c)
+        );
+    }
+
+    #[test]
+    fn client_config_connect_timeout() {
+        let mut config = ClientConfig::new();
+        assert_eq!(config.inner.connect_timeout_ms, 0, "should defer to the CRT's default");
+        config.connect_timeout_ms(500);
+        assert_eq!(config.inner.connect_timeout_ms, 500);
+    }
 }
@@ -10,8 +10,8 @@ use thiserror::Error;
 
 use crate::{
     aws_byte_cursor_as_slice,
-    common::{allocator::Allocator, error::Error},
-    CrtError, ToAwsByteCursor,
+    common::{allocator::Allocator, byte_cursor::ByteCursor, error::Error},
+    CrtError,
 };
 
 use super::s3_library_init;
@@ -112,14 +112,16 @@ impl RequestContext {
         name: impl AsRef<OsStr>,
         value: impl AsRef<OsStr>,
     ) -> Result<(), Error> {
+        let name = ByteCursor::from_os_str(&name);
+        let value = ByteCursor::from_os_str(&value);
         // SAFETY: allocator.inner and self.inner should be valid pointers.
         // `name` and `value` will be copied by CRT, thus borrowing is safe.
         unsafe {
             aws_endpoints_request_context_add_string(
                 allocator.inner.as_ptr(),
                 self.inner.as_ptr(),
-                name.as_aws_byte_cursor(),
-                value.as_aws_byte_cursor(),
+                name.as_raw(),
+                value.as_raw(),
             )
             .ok_or_last_error()
         }
@@ -127,13 +129,14 @@ impl RequestContext {
 
     /// Add the parameter to [RequestContext] whose value is in form of boolean
     pub fn add_boolean(&mut self, allocator: &Allocator, name: impl AsRef<OsStr>, value: bool) -> Result<(), Error> {
+        let name = ByteCursor::from_os_str(&name);
         // SAFETY: allocator.inner and self.inner should be valid pointers.
         // `name` will be copied by CRT, thus borrowing is safe.
         unsafe {
             aws_endpoints_request_context_add_boolean(
                 allocator.inner.as_ptr(),
                 self.inner.as_ptr(),
-                name.as_aws_byte_cursor(),
+                name.as_raw(),
                 value,
             )
             .ok_or_last_error()
@@ -12,7 +12,7 @@ use clap::{value_parser, Parser, ValueEnum};
 use fuser::{MountOption, Session};
 use futures::task::Spawn;
 use mountpoint_s3_client::config::{AddressingStyle, EndpointConfig, S3ClientAuthConfig, S3ClientConfig};
-use mountpoint_s3_client::error::ObjectClientError;
+use mountpoint_s3_client::error::{ListObjectsError, ObjectClientError};
 use mountpoint_s3_client::instance_info::InstanceInfo;
 use mountpoint_s3_client::user_agent::UserAgent;
 use mountpoint_s3_client::{ObjectClient, S3CrtClient, S3RequestError};
@@ -615,7 +615,10 @@ pub fn create_s3_client(args: &CliArgs) -> anyhow::Result<(S3CrtClient, EventLoo
         .auth_config(auth_config)
         .throughput_target_gbps(throughput_target_gbps)
         .part_size(args.part_size as usize)
-        .user_agent(user_agent);
+        .user_agent(user_agent)
+        // `parse_bucket_name` accepts legacy bucket names with uppercase letters and underscores,
+        // which the client would otherwise reject
+        .validate_bucket_names(false);
     if args.requester_pays {
         client_config = client_config.request_payer("requester");
     }
@@ -834,14 +837,17 @@ fn create_client_for_bucket(
 
     let client = S3CrtClient::new(client_config.clone().endpoint_config(endpoint_config.clone()))?;
 
-    let list_request = client.list_objects(bucket, None, "", 0, prefix.as_str());
+    let list_request = client.list_objects(bucket, None, None, 0, prefix.as_str());
     match futures::executor::block_on(list_request) {
         Ok(_) => Ok(client),
         // Don't try to automatically correct the region if it was manually specified incorrectly
-        Err(ObjectClientError::ClientError(S3RequestError::IncorrectRegion(region))) if !user_provided_region => {
+        Err(
+            ObjectClientError::ClientError(S3RequestError::IncorrectRegion(region))
+            | ObjectClientError::ServiceError(ListObjectsError::WrongRegion { expected: region }),
+        ) if !user_provided_region => {
             tracing::warn!("bucket {bucket} is in region {region}, not {region_to_try}. redirecting...");
             let new_client = S3CrtClient::new(client_config.endpoint_config(endpoint_config.region(&region)))?;
-            let list_request = new_client.list_objects(bucket, None, "", 0, prefix.as_str());
+            let list_request = new_client.list_objects(bucket, None, None, 0, prefix.as_str());
             futures::executor::block_on(list_request)
                 .map(|_| new_client)
                 .with_context(|| format!("initial ListObjectsV2 failed for bucket {bucket} in region {region}"))
@@ -1007,6 +1013,7 @@ mod tests {
 
     #[test_case("test-bucket", true; "simple bucket")]
     #[test_case("test-123.buc_ket", true; "bucket name with .")]
+    #[test_case("Legacy_Bucket", true; "legacy bucket name")]
     #[test_case("my-access-point-hrzrlukc5m36ft7okagglf3gmwluquse1b-s3alias", true; "access point alias")]
     #[test_case("my-object-lambda-acc-1a4n8yjrb3kda96f67zwrwiiuse1a--ol-s3", true; "object lambda access point alias")]
     #[test_case("s3://test-bucket", false; "not providing bare bucket name")]
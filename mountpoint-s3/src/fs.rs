@@ -19,7 +19,8 @@ use mountpoint_s3_client::ObjectClient;
 
 use crate::fs::error_metadata::{ErrorMetadata, MOUNTPOINT_ERROR_LOOKUP_NONEXISTENT};
 use crate::inode::{
-    Inode, InodeError, InodeKind, LookedUp, ReadHandle, ReaddirHandle, Superblock, SuperblockConfig, WriteHandle,
+    Inode, InodeError, InodeKind, LookedUp, ReadHandle, ReaddirConfig, ReaddirHandle, Superblock, SuperblockConfig,
+    WriteHandle,
 };
 use crate::logging;
 use crate::prefetch::{Prefetch, PrefetchReadError, PrefetchResult};
@@ -191,7 +192,7 @@ impl<Client: ObjectClient> UploadState<Client> {
                 // Abort the request.
                 match std::mem::replace(self, Self::Failed(e.to_errno())) {
                     UploadState::InProgress { handle, .. } => {
-                        if let Err(err) = handle.finish() {
+                        if let Err(err) = handle.finish(None) {
                             // Log the issue but still return the write error.
                             error!(?err, ?key, "error updating the inode status");
                         }
@@ -247,14 +248,14 @@ impl<Client: ObjectClient> UploadState<Client> {
 
     async fn complete_upload(upload: UploadRequest<Client>, key: &str, handle: WriteHandle) -> Result<(), Error> {
         let size = upload.size();
-        let put_result = match upload.complete().await {
-            Ok(_) => {
-                debug!(key, size, "put succeeded");
-                Ok(())
+        let (put_result, result) = match upload.complete().await {
+            Ok(result) => {
+                debug!(key, size, etag=?result.etag, "put succeeded");
+                (Ok(()), Some(result))
             }
-            Err(e) => Err(err!(libc::EIO, source:e, "put failed")),
+            Err(e) => (Err(err!(libc::EIO, source:e, "put failed")), None),
         };
-        if let Err(err) = handle.finish() {
+        if let Err(err) = handle.finish(result.as_ref()) {
             // Log the issue but still return put_result.
             error!(?err, ?key, "error updating the inode status");
         }
@@ -315,6 +316,12 @@ pub struct CacheConfig {
     pub dir_ttl: Duration,
     /// Maximum number of negative entries to cache.
     pub negative_cache_size: usize,
+    /// Should the file system store the metadata returned by a completed upload (such as the new
+    /// ETag) in the inode's cached stat, rather than invalidating it?
+    ///
+    /// When enabled, a lookup immediately after a write can be served from cache instead of
+    /// requiring a round trip to S3.
+    pub write_through: bool,
 }
 
 impl Default for CacheConfig {
@@ -343,6 +350,7 @@ impl Default for CacheConfig {
             file_ttl,
             dir_ttl,
             negative_cache_size,
+            write_through: false,
         }
     }
 }
@@ -374,6 +382,8 @@ pub struct S3FilesystemConfig {
     pub cache_config: CacheConfig,
     /// Readdir page size
     pub readdir_size: usize,
+    /// Readdir listing behavior
+    pub readdir_config: ReaddirConfig,
     /// User id
     pub uid: u32,
     /// Group id
@@ -404,6 +414,7 @@ impl Default for S3FilesystemConfig {
         Self {
             cache_config: Default::default(),
             readdir_size: 100,
+            readdir_config: Default::default(),
             uid,
             gid,
             dir_mode: 0o755,
@@ -555,6 +566,7 @@ where
         let superblock_config = SuperblockConfig {
             cache_config: config.cache_config.clone(),
             s3_personality: config.s3_personality,
+            readdir_config: config.readdir_config.clone(),
         };
         let superblock = Superblock::new(bucket, prefix, superblock_config);
 
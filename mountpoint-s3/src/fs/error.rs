@@ -21,7 +21,7 @@ use crate::upload::UploadWriteError;
 /// include its source. For example:
 ///
 /// ```ignore
-/// let err = client.head_object("DOC-EXAMPLE-BUCKET", "mykey").await.expect_err("failed");
+/// let err = client.head_object("DOC-EXAMPLE-BUCKET", "mykey", &HeadObjectParams::new()).await.expect_err("failed");
 /// return Err(err!(libc::ENOENT, source:err, "file does not exist"));
 /// ```
 /// will print "file does not exist: service error: ...".
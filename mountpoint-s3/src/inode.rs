@@ -32,8 +32,10 @@ use anyhow::anyhow;
 use fuser::FileType;
 use futures::{select_biased, FutureExt};
 use mountpoint_s3_client::error::{HeadObjectError, ObjectClientError};
-use mountpoint_s3_client::error_metadata::ProvideErrorMetadata;
-use mountpoint_s3_client::types::{HeadObjectResult, RestoreStatus};
+use mountpoint_s3_client::error_metadata::{ClientErrorMetadata, ProvideErrorMetadata};
+use mountpoint_s3_client::types::{
+    DeleteObjectParams, HeadObjectParams, HeadObjectResult, PutObjectResult, RestoreStatus,
+};
 use mountpoint_s3_client::ObjectClient;
 use mountpoint_s3_crt::checksums::crc32c::{self, Crc32c};
 use thiserror::Error;
@@ -57,7 +59,7 @@ mod negative_cache;
 use negative_cache::NegativeCache;
 
 mod readdir;
-pub use readdir::ReaddirHandle;
+pub use readdir::{ReaddirConfig, ReaddirHandle, ReaddirRuntime};
 
 pub type InodeNo = u64;
 
@@ -100,6 +102,7 @@ struct SuperblockInner {
 pub struct SuperblockConfig {
     pub cache_config: CacheConfig,
     pub s3_personality: S3Personality,
+    pub readdir_config: ReaddirConfig,
 }
 
 impl Superblock {
@@ -569,7 +572,7 @@ impl Superblock {
             WriteStatus::Remote => {
                 let (bucket, s3_key) = (self.inner.bucket.as_str(), inode.full_key());
                 debug!(parent=?parent_ino, ?name, "unlink on remote file will delete key {}", s3_key);
-                let delete_obj_result = client.delete_object(bucket, s3_key).await;
+                let delete_obj_result = client.delete_object(bucket, s3_key, &DeleteObjectParams::new()).await;
 
                 match delete_obj_result {
                     Ok(_res) => (),
@@ -769,9 +772,11 @@ impl SuperblockInner {
         //       "/" to the prefix in the request, the first common prefix we'll get back will be
         //       "dir-1/", because that precedes "dir/" in lexicographic order. Doing the
         //       ListObjects with "/" appended makes sure we always observe the correct prefix.
-        let mut file_lookup = client.head_object(&self.bucket, &full_path).fuse();
+        let mut file_lookup = client
+            .head_object(&self.bucket, &full_path, &HeadObjectParams::new())
+            .fuse();
         let mut dir_lookup = client
-            .list_objects(&self.bucket, None, "/", 1, &full_path_suffixed)
+            .list_objects(&self.bucket, None, Some("/"), 1, &full_path_suffixed)
             .fuse();
 
         let mut file_state = None;
@@ -781,12 +786,19 @@ impl SuperblockInner {
                 result = file_lookup => {
                     match result {
                         Ok(HeadObjectResult { object, .. }) => {
-                            let stat = InodeStat::for_file(object.size as usize, object.last_modified, Some(object.etag.clone()), object.storage_class, object.restore_status, self.config.cache_config.file_ttl);
+                            let stat = InodeStat::for_file(object.size as usize, object.last_modified, object.etag.clone(), object.storage_class, object.restore_status, self.config.cache_config.file_ttl);
                             file_state = Some(stat);
                         }
                         // If the object is not found, might be a directory, so keep going
                         Err(ObjectClientError::ServiceError(HeadObjectError::NotFound)) => {},
-                        Err(e) => return Err(InodeError::client_error(e, "HeadObject failed", &self.bucket, &full_path)),
+                        Err(e) => {
+                            // Service errors have no metadata, except for the one HeadObject returns for 403 responses
+                            let client_error_meta = match &e {
+                                ObjectClientError::ServiceError(HeadObjectError::AccessDenied(metadata)) => metadata.clone(),
+                                e => e.meta(),
+                            };
+                            return Err(InodeError::client_error_with_meta(e, client_error_meta, "HeadObject failed", &self.bucket, &full_path));
+                        }
                     }
                 }
 
@@ -1153,7 +1165,12 @@ impl WriteHandle {
     }
 
     /// Update status of the inode and of containing "local" directories.
-    pub fn finish(self) -> Result<(), InodeError> {
+    ///
+    /// `put_result` is the result of the upload if it completed successfully. If write-through is
+    /// enabled in the [CacheConfig] and the result has both the new ETag and the server's
+    /// modification time, they're stored in the inode's stat so the next lookup can be served from
+    /// cache. Otherwise, the stat is invalidated and refreshed from S3 when next queried.
+    pub fn finish(self, put_result: Option<&PutObjectResult>) -> Result<(), InodeError> {
         // Collect ancestor inodes that may need updating,
         // from parent to first remote ancestor.
         let ancestors = {
@@ -1184,8 +1201,21 @@ impl WriteHandle {
             WriteStatus::LocalOpen => {
                 state.write_status = WriteStatus::Remote;
 
-                // Invalidate the inode's stats so we refresh them from S3 when next queried
-                state.stat.update_validity(Duration::from_secs(0));
+                let uploaded = put_result.and_then(|result| Some((result.etag.as_ref()?, result.last_modified?)));
+                match uploaded {
+                    Some((etag, last_modified)) if self.inner.config.cache_config.write_through => {
+                        // Keep the stats we already know (like the size) and store the new ETag and
+                        // the server's modification time, so an immediate lookup doesn't need to go to S3
+                        state.stat.etag = Some(etag.as_str().to_owned());
+                        state.stat.mtime = last_modified;
+                        state.stat.ctime = last_modified;
+                        state.stat.update_validity(self.inner.config.cache_config.file_ttl);
+                    }
+                    _ => {
+                        // Invalidate the inode's stats so we refresh them from S3 when next queried
+                        state.stat.update_validity(Duration::from_secs(0));
+                    }
+                }
 
                 // Walk up the ancestors from parent to first remote ancestor to transition
                 // the inode and all "local" containing directories to "remote".
@@ -1651,9 +1681,25 @@ impl InodeError {
     fn client_error<E>(err: E, context: &'static str, bucket: &str, key: &str) -> Self
     where
         E: ProvideErrorMetadata + std::error::Error + Send + Sync + 'static,
+    {
+        let client_error_meta = err.meta();
+        Self::client_error_with_meta(err, client_error_meta, context, bucket, key)
+    }
+
+    /// Like [InodeError::client_error], but with the client metadata already extracted from the
+    /// error, for service errors that carry their own.
+    fn client_error_with_meta<E>(
+        err: E,
+        client_error_meta: ClientErrorMetadata,
+        context: &'static str,
+        bucket: &str,
+        key: &str,
+    ) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
     {
         let metadata = ErrorMetadata {
-            client_error_meta: err.meta(),
+            client_error_meta,
             error_code: Some(MOUNTPOINT_ERROR_CLIENT.to_string()),
             s3_bucket_name: Some(bucket.to_string()),
             s3_object_key: Some(key.to_string()),
@@ -1679,9 +1725,11 @@ impl InodeError {
 mod tests {
     use std::str::FromStr;
 
+    use futures::executor::ThreadPool;
     use mountpoint_s3_client::{
-        mock_client::{MockClient, MockClientConfig, MockObject},
-        types::ETag,
+        mock_client::{MockClient, MockClientConfig, MockObject, Operation},
+        types::{ETag, PutObjectParams},
+        PutObjectRequest,
     };
     use test_case::test_case;
     use time::{Duration, OffsetDateTime};
@@ -1797,7 +1845,7 @@ mod tests {
                         .expect("inode should exist");
                     // Grab last modified time according to mock S3
                     let modified_time = client
-                        .head_object(bucket, file.inode.full_key())
+                        .head_object(bucket, file.inode.full_key(), &HeadObjectParams::new())
                         .await
                         .expect("object should exist")
                         .object
@@ -1857,6 +1905,7 @@ mod tests {
                     ..Default::default()
                 },
                 s3_personality: S3Personality::Standard,
+                ..Default::default()
             },
         );
 
@@ -1912,6 +1961,7 @@ mod tests {
                     ..Default::default()
                 },
                 s3_personality: S3Personality::Standard,
+                ..Default::default()
             },
         );
 
@@ -2458,6 +2508,74 @@ mod tests {
         }
     }
 
+    #[test_case(false, false; "ordered")]
+    #[test_case(true, false; "ordered with prefetch")]
+    #[test_case(false, true; "skip ordering")]
+    #[test_case(true, true; "skip ordering with prefetch")]
+    #[tokio::test]
+    async fn test_readdir_config(prefetch_next_page: bool, skip_ordering: bool) {
+        let client_config = MockClientConfig {
+            bucket: "test_bucket".to_string(),
+            part_size: 1024 * 1024,
+            ..Default::default()
+        };
+        let client = Arc::new(MockClient::new(client_config));
+
+        let prefix = Prefix::new("").expect("valid prefix");
+        let superblock = Superblock::new(
+            "test_bucket",
+            &prefix,
+            SuperblockConfig {
+                readdir_config: ReaddirConfig {
+                    prefetch_next_page: prefetch_next_page
+                        .then(|| ReaddirRuntime::new(ThreadPool::builder().pool_size(1).create().unwrap())),
+                    skip_ordering,
+                },
+                ..Default::default()
+            },
+        );
+
+        // Mix files and directories so that pages need re-sorting, and add a local file in the
+        // middle of the remote entries to check it is merged in the right place.
+        let mut expected_entries = vec![];
+        for i in 0..20 {
+            let name = format!("entry{i:02}");
+            let key = if i % 3 == 0 {
+                format!("{name}/file.txt")
+            } else {
+                name.clone()
+            };
+            client.add_object(&key, MockObject::constant(0xaa, 30, ETag::for_tests()));
+            expected_entries.push(name);
+        }
+        let local_name = "entry10a";
+        let new_inode = superblock
+            .create(&client, FUSE_ROOT_INODE, local_name.as_ref(), InodeKind::File)
+            .await
+            .unwrap();
+        superblock
+            .write(&client, new_inode.inode.ino(), false, false)
+            .await
+            .expect("should be able to start writing");
+        expected_entries.push(local_name.to_owned());
+        expected_entries.sort();
+
+        let list_counter = client.new_counter(Operation::ListObjectsV2);
+        let dir_handle = superblock.readdir(&client, FUSE_ROOT_INODE, 3).await.unwrap();
+        let entries = dir_handle.collect(&client).await.unwrap();
+        let mut entries: Vec<_> = entries.iter().map(|l| l.inode.name().to_owned()).collect();
+
+        // Prefetching should never issue more requests than there are pages
+        assert_eq!(list_counter.count(), 7);
+
+        if skip_ordering {
+            // Local entries come after all the remote ones
+            assert_eq!(entries.last().map(String::as_str), Some(local_name));
+            entries.sort();
+        }
+        assert_eq!(entries, expected_entries);
+    }
+
     #[test_case(""; "unprefixed")]
     #[test_case("test_prefix/"; "prefixed")]
     #[tokio::test]
@@ -2693,7 +2811,7 @@ mod tests {
 
         // Invoke [finish_writing], without actually adding the
         // object to the client
-        writehandle.finish().unwrap();
+        writehandle.finish(None).unwrap();
 
         // All nested dirs disappear
         let dirname = nested_dirs.first().unwrap();
@@ -2701,6 +2819,68 @@ mod tests {
         assert!(matches!(lookedup, Err(InodeError::FileDoesNotExist(_, _))));
     }
 
+    #[test_case(true; "write-through")]
+    #[test_case(false; "invalidate")]
+    #[tokio::test]
+    async fn test_finish_writing_write_through(write_through: bool) {
+        let bucket = "test_bucket";
+        let client_config = MockClientConfig {
+            bucket: bucket.to_string(),
+            part_size: 1024 * 1024,
+            ..Default::default()
+        };
+        let client = Arc::new(MockClient::new(client_config));
+        let ttl = std::time::Duration::from_secs(60);
+        let superblock = Superblock::new(
+            bucket,
+            &Default::default(),
+            SuperblockConfig {
+                cache_config: CacheConfig {
+                    serve_lookup_from_cache: true,
+                    dir_ttl: ttl,
+                    file_ttl: ttl,
+                    write_through,
+                    ..Default::default()
+                },
+                s3_personality: S3Personality::Standard,
+                ..Default::default()
+            },
+        );
+
+        let filename = "newfile.txt";
+        let new_inode = superblock
+            .create(&client, FUSE_ROOT_INODE, filename.as_ref(), InodeKind::File)
+            .await
+            .unwrap();
+        let writehandle = superblock
+            .write(&client, new_inode.inode.ino(), false, false)
+            .await
+            .expect("should be able to start writing");
+
+        // Upload the object directly, then finish writing with the result of the upload
+        let body = b"hello world";
+        let mut put_request = client
+            .put_object(bucket, filename, &PutObjectParams::new())
+            .await
+            .expect("put_object should succeed");
+        put_request.write(body).await.unwrap();
+        writehandle.inc_file_size(body.len());
+        let put_result = put_request.complete().await.unwrap();
+        writehandle.finish(Some(&put_result)).unwrap();
+
+        let head_counter = client.new_counter(Operation::HeadObject);
+        let lookedup = superblock
+            .lookup(&client, FUSE_ROOT_INODE, filename.as_ref())
+            .await
+            .expect("lookup should succeed");
+        let expected_etag = put_result.etag.as_ref().expect("mock client should return an ETag");
+        assert_eq!(lookedup.stat.etag.as_deref(), Some(expected_etag.as_str()));
+        assert_eq!(Some(lookedup.stat.mtime), put_result.last_modified);
+        assert_eq!(lookedup.stat.size, body.len());
+        let expected_head_count = if write_through { 0 } else { 1 };
+        assert_eq!(head_counter.count(), expected_head_count);
+    }
+
     #[tokio::test]
     async fn test_inode_reuse() {
         let client_config = MockClientConfig {
@@ -2885,7 +3065,7 @@ mod tests {
         assert_eq!(stat.mtime, mtime);
 
         // Invoke [finish_writing] to make the file remote
-        writehandle.finish().unwrap();
+        writehandle.finish(None).unwrap();
 
         // Should get an error back when calling setattr
         let result = superblock
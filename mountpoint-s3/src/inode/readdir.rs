@@ -30,15 +30,20 @@
 //!   returns to handle point 1.
 //! * [RemoteIter] is an iterator over [ReaddirEntry]s returned by paginated calls to ListObjectsV2.
 //!   Rather than directly streaming the entries out of the list call, it collects them in memory
-//!   and re-sorts them to handle point 3.
+//!   and re-sorts them to handle point 3. It can optionally request the next page while the current
+//!   one is still being consumed (see [ReaddirConfig]).
 //! * [LocalIter] is an iterator over [ReaddirEntry]s that are local children of the directory.
 //!   These children are listed only once, at the start of the readdir operation, and so are a
 //!   snapshot in time of the directory.
 
 use std::cmp::Ordering;
 use std::collections::VecDeque;
+use std::fmt::{self, Debug};
 
-use mountpoint_s3_client::types::ObjectInfo;
+use futures::future::{BoxFuture, RemoteHandle};
+use futures::task::{Spawn, SpawnError, SpawnExt};
+use futures::FutureExt;
+use mountpoint_s3_client::types::{ListObjectsResult, ObjectInfo};
 use mountpoint_s3_client::ObjectClient;
 use tracing::{error, trace, warn};
 
@@ -48,6 +53,37 @@ use super::{
     valid_inode_name, InodeError, InodeKind, InodeKindData, InodeNo, InodeStat, LookedUp, RemoteLookup, SuperblockInner,
 };
 
+/// Configuration for how a [ReaddirHandle] lists the remote entries of a directory
+#[derive(Debug, Clone, Default)]
+pub struct ReaddirConfig {
+    /// If set, start the ListObjectsV2 request for the next page on this runtime as soon as the
+    /// current page arrives, so that it is likely to be ready by the time the current page has been
+    /// consumed. Entries are still returned in the same order.
+    pub prefetch_next_page: Option<ReaddirRuntime>,
+    /// Skip ordering: return entries in the order they are listed, followed by local entries,
+    /// rather than in lexicographic order. This only saves re-sorting each page and merging it with
+    /// local entries; the directory is still listed with one ListObjectsV2 request at a time, so it
+    /// doesn't make the listing itself any faster. It is always the case for S3 implementations
+    /// that do not provide ordered LIST.
+    pub skip_ordering: bool,
+}
+
+/// A runtime that [ReaddirHandle]s spawn prefetched ListObjectsV2 requests on
+#[derive(Clone)]
+pub struct ReaddirRuntime(Arc<dyn Spawn + Send + Sync>);
+
+impl ReaddirRuntime {
+    pub fn new(runtime: impl Spawn + Send + Sync + 'static) -> Self {
+        Self(Arc::new(runtime))
+    }
+}
+
+impl Debug for ReaddirRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReaddirRuntime").finish_non_exhaustive()
+    }
+}
+
 /// Handle for an inflight directory listing
 #[derive(Debug)]
 pub struct ReaddirHandle {
@@ -92,10 +128,12 @@ impl ReaddirHandle {
             }
         };
 
-        let iter = if inner.config.s3_personality.is_list_ordered() {
-            ReaddirIter::ordered(&inner.bucket, &full_path, page_size, local_entries.into())
+        let readdir_config = &inner.config.readdir_config;
+        let prefetch = readdir_config.prefetch_next_page.clone();
+        let iter = if inner.config.s3_personality.is_list_ordered() && !readdir_config.skip_ordering {
+            ReaddirIter::ordered(&inner.bucket, &full_path, page_size, prefetch, local_entries.into())
         } else {
-            ReaddirIter::unordered(&inner.bucket, &full_path, page_size, local_entries.into())
+            ReaddirIter::unordered(&inner.bucket, &full_path, page_size, prefetch, local_entries.into())
         };
 
         Ok(Self {
@@ -110,7 +148,10 @@ impl ReaddirHandle {
     /// Return the next inode for the directory stream. If the stream is finished, returns
     /// `Ok(None)`. Does not increment the lookup count of the returned inodes: the caller
     /// is responsible for calling [`remember()`] if required.
-    pub async fn next<OC: ObjectClient>(&self, client: &OC) -> Result<Option<LookedUp>, InodeError> {
+    pub async fn next<OC>(&self, client: &OC) -> Result<Option<LookedUp>, InodeError>
+    where
+        OC: ObjectClient + Clone + Send + Sync + 'static,
+    {
         if let Some(readded) = self.readded.lock().unwrap().take() {
             return Ok(Some(readded));
         }
@@ -172,7 +213,7 @@ impl ReaddirHandle {
                 let stat = InodeStat::for_file(
                     object_info.size as usize,
                     object_info.last_modified,
-                    Some(object_info.etag.clone()),
+                    object_info.etag.clone(),
                     object_info.storage_class.clone(),
                     object_info.restore_status,
                     self.inner.config.cache_config.file_ttl,
@@ -187,7 +228,10 @@ impl ReaddirHandle {
     }
 
     #[cfg(test)]
-    pub(super) async fn collect<OC: ObjectClient>(&self, client: &OC) -> Result<Vec<LookedUp>, InodeError> {
+    pub(super) async fn collect<OC>(&self, client: &OC) -> Result<Vec<LookedUp>, InodeError>
+    where
+        OC: ObjectClient + Clone + Send + Sync + 'static,
+    {
         let mut result = vec![];
         while let Some(entry) = self.next(client).await? {
             result.push(entry);
@@ -282,15 +326,39 @@ enum ReaddirIter {
 }
 
 impl ReaddirIter {
-    fn ordered(bucket: &str, full_path: &str, page_size: usize, local_entries: VecDeque<ReaddirEntry>) -> Self {
-        Self::Ordered(ordered::ReaddirIter::new(bucket, full_path, page_size, local_entries))
+    fn ordered(
+        bucket: &str,
+        full_path: &str,
+        page_size: usize,
+        prefetch: Option<ReaddirRuntime>,
+        local_entries: VecDeque<ReaddirEntry>,
+    ) -> Self {
+        Self::Ordered(ordered::ReaddirIter::new(
+            bucket,
+            full_path,
+            page_size,
+            prefetch,
+            local_entries,
+        ))
     }
 
-    fn unordered(bucket: &str, full_path: &str, page_size: usize, local_entries: VecDeque<ReaddirEntry>) -> Self {
-        Self::Unordered(unordered::ReaddirIter::new(bucket, full_path, page_size, local_entries))
+    fn unordered(
+        bucket: &str,
+        full_path: &str,
+        page_size: usize,
+        prefetch: Option<ReaddirRuntime>,
+        local_entries: VecDeque<ReaddirEntry>,
+    ) -> Self {
+        Self::Unordered(unordered::ReaddirIter::new(
+            bucket,
+            full_path,
+            page_size,
+            prefetch,
+            local_entries,
+        ))
     }
 
-    async fn next(&mut self, client: &impl ObjectClient) -> Result<Option<ReaddirEntry>, InodeError> {
+    async fn next(&mut self, client: &impl RemoteClient) -> Result<Option<ReaddirEntry>, InodeError> {
         match self {
             Self::Ordered(iter) => iter.next(client).await,
             Self::Unordered(iter) => iter.next(client).await,
@@ -298,14 +366,46 @@ impl ReaddirIter {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// The [ObjectClient]s a [RemoteIter] can list with. Prefetching a page requires a request that can
+/// outlive the borrow of the client, so the client must be cheap to clone (e.g. an `Arc`).
+trait RemoteClient: ObjectClient + Clone + Send + Sync + 'static {}
+
+impl<OC: ObjectClient + Clone + Send + Sync + 'static> RemoteClient for OC {}
+
+type ListObjectsFuture = BoxFuture<'static, Result<ListObjectsResult, InodeError>>;
+
+#[derive(Debug)]
 enum RemoteIterState {
     /// Next ListObjects call should use this continuation token
     InProgress(Option<String>),
+    /// The ListObjects call for the next page has already been started
+    Prefetching(PrefetchedPage),
     /// No more ListObjects calls to make
     Finished,
 }
 
+/// A ListObjects request for the next page, started before the current page has been consumed.
+/// Dropping it cancels the request.
+struct PrefetchedPage(RemoteHandle<Result<ListObjectsResult, InodeError>>);
+
+impl PrefetchedPage {
+    /// Spawn the request on the runtime, so that it runs to completion without being polled until
+    /// we want the result.
+    fn start(runtime: &ReaddirRuntime, request: ListObjectsFuture) -> Result<Self, SpawnError> {
+        runtime.0.spawn_with_handle(request).map(Self)
+    }
+
+    async fn wait(self) -> Result<ListObjectsResult, InodeError> {
+        self.0.await
+    }
+}
+
+impl Debug for PrefetchedPage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrefetchedPage").finish_non_exhaustive()
+    }
+}
+
 /// An iterator over [ReaddirEntry]s returned by paginated ListObjects calls to S3. This iterator
 /// handles combining directories (common prefixes) and files (objects) into a single stream,
 /// and re-sorting that stream to account for common prefixes not being in lexicographic order (see
@@ -318,10 +418,11 @@ struct RemoteIter {
     page_size: usize,
     state: RemoteIterState,
     ordered: bool,
+    prefetch: Option<ReaddirRuntime>,
 }
 
 impl RemoteIter {
-    fn new(bucket: &str, full_path: &str, page_size: usize, ordered: bool) -> Self {
+    fn new(bucket: &str, full_path: &str, page_size: usize, ordered: bool, prefetch: Option<ReaddirRuntime>) -> Self {
         Self {
             entries: VecDeque::new(),
             bucket: bucket.to_owned(),
@@ -329,35 +430,42 @@ impl RemoteIter {
             page_size,
             state: RemoteIterState::InProgress(None),
             ordered,
+            prefetch,
         }
     }
 
-    async fn next(&mut self, client: &impl ObjectClient) -> Result<Option<ReaddirEntry>, InodeError> {
+    async fn next(&mut self, client: &impl RemoteClient) -> Result<Option<ReaddirEntry>, InodeError> {
         if self.entries.is_empty() {
-            let continuation_token = match &mut self.state {
+            let result = match std::mem::replace(&mut self.state, RemoteIterState::InProgress(None)) {
                 RemoteIterState::Finished => {
+                    self.state = RemoteIterState::Finished;
                     trace!(self=?self as *const _, prefix=?self.full_path, "remote iter finished");
                     return Ok(None);
                 }
-                RemoteIterState::InProgress(token) => token.take(),
+                RemoteIterState::InProgress(continuation_token) => {
+                    trace!(self=?self as *const _, prefix=?self.full_path, ?continuation_token, "continuing remote iter");
+                    self.list_objects(client, continuation_token).await?
+                }
+                RemoteIterState::Prefetching(page) => {
+                    trace!(self=?self as *const _, prefix=?self.full_path, "waiting for prefetched page");
+                    page.wait().await?
+                }
             };
 
-            trace!(self=?self as *const _, prefix=?self.full_path, ?continuation_token, "continuing remote iter");
-
-            let result = client
-                .list_objects(
-                    &self.bucket,
-                    continuation_token.as_deref(),
-                    "/",
-                    self.page_size,
-                    self.full_path.as_str(),
-                )
-                .await
-                .map_err(|e| InodeError::client_error(e, "ListObjectsV2 failed", &self.bucket, &self.full_path))?;
-
-            self.state = match result.next_continuation_token {
-                Some(token) => RemoteIterState::InProgress(Some(token)),
-                None => RemoteIterState::Finished,
+            self.state = match (result.next_continuation_token, &self.prefetch) {
+                (Some(token), Some(runtime)) => {
+                    trace!(self=?self as *const _, prefix=?self.full_path, continuation_token=?token, "prefetching next page");
+                    match PrefetchedPage::start(runtime, self.list_objects(client, Some(token.clone()))) {
+                        Ok(page) => RemoteIterState::Prefetching(page),
+                        Err(e) => {
+                            // The runtime is shutting down, so just list the next page when it's needed
+                            warn!(error=?e, prefix=?self.full_path, "failed to prefetch next page");
+                            RemoteIterState::InProgress(Some(token))
+                        }
+                    }
+                }
+                (Some(token), None) => RemoteIterState::InProgress(Some(token)),
+                (None, _) => RemoteIterState::Finished,
             };
 
             let prefixes = result
@@ -391,6 +499,20 @@ impl RemoteIter {
 
         Ok(self.entries.pop_front())
     }
+
+    fn list_objects(&self, client: &impl RemoteClient, continuation_token: Option<String>) -> ListObjectsFuture {
+        let client = client.clone();
+        let bucket = self.bucket.clone();
+        let full_path = self.full_path.clone();
+        let page_size = self.page_size;
+        async move {
+            client
+                .list_objects(&bucket, continuation_token.as_deref(), Some("/"), page_size, &full_path)
+                .await
+                .map_err(|e| InodeError::client_error(e, "ListObjectsV2 failed", &bucket, &full_path))
+        }
+        .boxed()
+    }
 }
 
 /// Iterator implementation for S3 implementations that provide lexicographically ordered LIST.
@@ -414,10 +536,11 @@ mod ordered {
             bucket: &str,
             full_path: &str,
             page_size: usize,
+            prefetch: Option<ReaddirRuntime>,
             local_entries: VecDeque<ReaddirEntry>,
         ) -> Self {
             Self {
-                remote: RemoteIter::new(bucket, full_path, page_size, true),
+                remote: RemoteIter::new(bucket, full_path, page_size, true, prefetch),
                 local: LocalIter::new(local_entries),
                 next_remote: None,
                 next_local: None,
@@ -427,7 +550,7 @@ mod ordered {
 
         /// Return the next [ReaddirEntry] for the directory stream. If the stream is finished, returns
         /// `Ok(None)`.
-        pub(super) async fn next(&mut self, client: &impl ObjectClient) -> Result<Option<ReaddirEntry>, InodeError> {
+        pub(super) async fn next(&mut self, client: &impl RemoteClient) -> Result<Option<ReaddirEntry>, InodeError> {
             // The only reason to go around this loop more than once is if the next entry to return is
             // a duplicate, in which case it's skipped.
             loop {
@@ -516,6 +639,7 @@ mod unordered {
             bucket: &str,
             full_path: &str,
             page_size: usize,
+            prefetch: Option<ReaddirRuntime>,
             local_entries: VecDeque<ReaddirEntry>,
         ) -> Self {
             let local_map = local_entries
@@ -529,7 +653,7 @@ mod unordered {
                 .collect::<HashMap<_, _>>();
 
             Self {
-                remote: RemoteIter::new(bucket, full_path, page_size, false),
+                remote: RemoteIter::new(bucket, full_path, page_size, false, prefetch),
                 local: local_map,
                 local_iter: VecDeque::new(),
             }
@@ -537,7 +661,7 @@ mod unordered {
 
         /// Return the next [ReaddirEntry] for the directory stream. If the stream is finished, returns
         /// `Ok(None)`.
-        pub(super) async fn next(&mut self, client: &impl ObjectClient) -> Result<Option<ReaddirEntry>, InodeError> {
+        pub(super) async fn next(&mut self, client: &impl RemoteClient) -> Result<Option<ReaddirEntry>, InodeError> {
             if let Some(remote) = self.remote.next(client).await? {
                 self.local.remove(remote.name());
                 return Ok(Some(remote));
@@ -4,7 +4,8 @@ use std::{ops::Range, sync::Arc};
 use bytes::Bytes;
 use futures::task::{Spawn, SpawnExt};
 use futures::{pin_mut, StreamExt};
-use mountpoint_s3_client::{types::ETag, ObjectClient};
+use mountpoint_s3_client::types::{ETag, GetObjectParams};
+use mountpoint_s3_client::ObjectClient;
 use tracing::{debug_span, trace, warn, Instrument};
 
 use crate::checksums::ChecksummedBytes;
@@ -169,8 +170,9 @@ where
             .get_object(
                 &self.bucket,
                 key,
-                Some(block_aligned_byte_range),
-                Some(self.cache_key.etag().clone()),
+                &GetObjectParams::new()
+                    .range(Some(block_aligned_byte_range))
+                    .if_match(Some(self.cache_key.etag().clone())),
             )
             .await
         {
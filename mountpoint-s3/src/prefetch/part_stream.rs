@@ -3,7 +3,8 @@ use std::{fmt::Debug, ops::Range};
 use bytes::Bytes;
 use futures::task::SpawnExt;
 use futures::{pin_mut, task::Spawn, StreamExt};
-use mountpoint_s3_client::{types::ETag, ObjectClient};
+use mountpoint_s3_client::types::{ETag, GetObjectParams};
+use mountpoint_s3_client::ObjectClient;
 use tracing::{debug_span, error, trace, Instrument};
 
 use crate::checksums::ChecksummedBytes;
@@ -191,7 +192,13 @@ where
 
             async move {
                 let get_object_result = match client
-                    .get_object(&bucket, id.key(), Some(request_range.into()), Some(id.etag().clone()))
+                    .get_object(
+                        &bucket,
+                        id.key(),
+                        &GetObjectParams::new()
+                            .range(Some(request_range.into()))
+                            .if_match(Some(id.etag().clone())),
+                    )
                     .await
                 {
                     Ok(get_object_result) => get_object_result,
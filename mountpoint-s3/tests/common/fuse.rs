@@ -108,7 +108,7 @@ pub mod mock_session {
     use futures::executor::ThreadPool;
     use mountpoint_s3::prefetch::{caching_prefetch, default_prefetch};
     use mountpoint_s3_client::mock_client::{MockClient, MockClientConfig, MockObject};
-    use mountpoint_s3_client::types::ObjectAttribute;
+    use mountpoint_s3_client::types::{ObjectAttribute, RestoreTier};
 
     const BUCKET_NAME: &str = "test_bucket";
 
@@ -247,9 +247,15 @@ pub mod mock_session {
             Ok(attrs.object_parts.and_then(|parts| parts.parts))
         }
 
-        fn restore_object(&mut self, key: &str, _expedited: bool) -> Result<(), Box<dyn std::error::Error>> {
+        fn restore_object(&mut self, key: &str, expedited: bool) -> Result<(), Box<dyn std::error::Error>> {
             let full_key = format!("{}{}", self.prefix, key);
-            Ok(self.client.restore_object(&full_key)?)
+            let tier = if expedited {
+                RestoreTier::Expedited
+            } else {
+                RestoreTier::Bulk
+            };
+            tokio_block_on(self.client.restore_object(BUCKET_NAME, &full_key, 1, tier))?;
+            Ok(())
         }
 
         fn is_object_restored(&mut self, key: &str) -> Result<bool, Box<dyn std::error::Error>> {
@@ -459,6 +465,8 @@ pub mod s3_session {
                         checksum_crc32c: part.checksum_crc32_c.to_owned(),
                         checksum_sha1: part.checksum_sha1.to_owned(),
                         checksum_sha256: part.checksum_sha256.to_owned(),
+                        checksum_crc64nvme: None,
+                        checksum_type: None,
                     }),
                     part_number: part.part_number.unwrap() as usize,
                     size: part.size.unwrap() as usize,
@@ -15,7 +15,7 @@ use mountpoint_s3_client::config::{EndpointConfig, S3ClientConfig};
 use mountpoint_s3_client::error_metadata::ClientErrorMetadata;
 use mountpoint_s3_client::failure_client::countdown_failure_client;
 use mountpoint_s3_client::mock_client::{MockClient, MockClientConfig, MockClientError, MockObject, Operation};
-use mountpoint_s3_client::types::{ETag, RestoreStatus};
+use mountpoint_s3_client::types::{ETag, GetObjectParams, RestoreStatus};
 #[cfg(all(feature = "s3_tests", not(feature = "s3express_tests")))]
 use mountpoint_s3_client::PutObjectRequest;
 use mountpoint_s3_client::{ObjectClient, S3CrtClient};
@@ -603,7 +603,7 @@ async fn test_sequential_write(write_size: usize) {
 
     // Check that the object made it to S3 as we expected
     let get = client
-        .get_object(BUCKET_NAME, "dir1/file2.bin", None, None)
+        .get_object(BUCKET_NAME, "dir1/file2.bin", &GetObjectParams::new())
         .await
         .unwrap();
     let actual = get.collect().await.unwrap();
@@ -1545,6 +1545,8 @@ async fn test_lookup_forbidden() {
         .await
         .expect_err("lookup must fail");
     let metadata = err.meta();
+    // Request IDs are unpredictable
+    let client_error_meta = &metadata.client_error_meta;
     assert_eq!(
         *metadata,
         ErrorMetadata {
@@ -1552,6 +1554,8 @@ async fn test_lookup_forbidden() {
                 http_code: Some(403), // here we assume that HeadObject failes with 403 code
                 error_code: None,
                 error_message: None,
+                request_id: client_error_meta.request_id.clone(),
+                extended_request_id: client_error_meta.extended_request_id.clone(),
             },
             error_code: Some(MOUNTPOINT_ERROR_CLIENT.to_string()),
             s3_bucket_name: Some(bucket.to_string()),
@@ -79,6 +79,8 @@ async fn test_lookup_throttled_mock(head_object_throttled: bool, list_object_thr
                 http_code: Some(503),
                 error_code: None,
                 error_message: None,
+                request_id: None,
+                extended_request_id: None,
             },
             error_code: Some(MOUNTPOINT_ERROR_CLIENT.to_string()),
             s3_bucket_name: Some(bucket.to_string()),
@@ -116,6 +118,8 @@ async fn test_lookup_unhandled_error_mock() {
                 http_code: Some(409),
                 error_code: None,
                 error_message: None,
+                request_id: None,
+                extended_request_id: None,
             },
             error_code: Some(MOUNTPOINT_ERROR_CLIENT.to_string()),
             s3_bucket_name: Some(bucket.to_string()),
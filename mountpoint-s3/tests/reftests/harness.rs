@@ -10,6 +10,7 @@ use mountpoint_s3::fs::{CacheConfig, InodeNo, ToErrno, FUSE_ROOT_INODE};
 use mountpoint_s3::prefix::Prefix;
 use mountpoint_s3::S3FilesystemConfig;
 use mountpoint_s3_client::mock_client::{MockClient, MockObject};
+use mountpoint_s3_client::types::DeleteObjectParams;
 use mountpoint_s3_client::ObjectClient;
 use proptest::prelude::*;
 use proptest_derive::Arbitrary;
@@ -583,7 +584,7 @@ impl Harness {
         trace!(key, "delete object");
 
         self.client
-            .delete_object(&self.bucket, &key)
+            .delete_object(&self.bucket, &key, &DeleteObjectParams::new())
             .await
             .expect("delete should succeed");
         self.reference.remove_remote_key(&key);